@@ -364,6 +364,39 @@ impl Accounts {
             .map(|_| collector)
     }
 
+    /// Loads at most `limit` accounts owned by `program_id` that pass `filter`
+    ///
+    /// The scan stops as soon as `limit` accounts have been found. Use a sorted `config` whose
+    /// `start_after` is the last pubkey of the previous page to page through all accounts.
+    pub fn load_page_by_program_with_filter<F: Fn(&AccountSharedData) -> bool>(
+        &self,
+        ancestors: &Ancestors,
+        bank_id: BankId,
+        program_id: &Pubkey,
+        filter: F,
+        limit: usize,
+        config: &ScanConfig,
+    ) -> ScanResult<Vec<KeyedAccountSharedData>> {
+        let config = config.recreate_with_abort();
+        let mut collector = Vec::new();
+        self.accounts_db
+            .scan_accounts(
+                ancestors,
+                bank_id,
+                |some_account_tuple| {
+                    Self::load_while_filtering(&mut collector, some_account_tuple, |account| {
+                        account.owner() == program_id && filter(account)
+                    });
+                    if collector.len() >= limit {
+                        // the page is full, so stop scanning
+                        config.abort();
+                    }
+                },
+                &config,
+            )
+            .map(|_| collector)
+    }
+
     fn calc_scan_result_size(account: &AccountSharedData) -> usize {
         account.data().len()
             + std::mem::size_of::<AccountSharedData>()
@@ -408,6 +441,31 @@ impl Accounts {
         filter: F,
         config: &ScanConfig,
         byte_limit_for_scan: Option<usize>,
+    ) -> ScanResult<Vec<KeyedAccountSharedData>> {
+        self.load_page_by_index_key_with_filter(
+            ancestors,
+            bank_id,
+            index_key,
+            filter,
+            usize::MAX,
+            config,
+            byte_limit_for_scan,
+        )
+    }
+
+    /// Loads at most `limit` accounts found through `index_key` that pass `filter`
+    ///
+    /// Filling the page stops the scan without error, while exceeding `byte_limit_for_scan`
+    /// fails it.
+    pub fn load_page_by_index_key_with_filter<F: Fn(&AccountSharedData) -> bool>(
+        &self,
+        ancestors: &Ancestors,
+        bank_id: BankId,
+        index_key: &IndexKey,
+        filter: F,
+        limit: usize,
+        config: &ScanConfig,
+        byte_limit_for_scan: Option<usize>,
     ) -> ScanResult<Vec<KeyedAccountSharedData>> {
         let sum = AtomicUsize::default();
         let config = config.recreate_with_abort();
@@ -417,7 +475,7 @@ impl Accounts {
             .index_scan_accounts(
                 ancestors,
                 bank_id,
-                *index_key,
+                index_key.clone(),
                 |some_account_tuple| {
                     Self::load_while_filtering(&mut collector, some_account_tuple, |account| {
                         let use_account = filter(account);
@@ -433,11 +491,18 @@ impl Accounts {
                         }
                         use_account
                     });
+                    if collector.len() >= limit {
+                        // the page is full, so stop scanning
+                        config.abort();
+                    }
                 },
                 &config,
             )
             .map(|_| collector);
-        Self::maybe_abort_scan(result, &config)
+        match result {
+            Ok(collector) if collector.len() >= limit => Ok(collector),
+            result => Self::maybe_abort_scan(result, &config),
+        }
     }
 
     pub fn account_indexes_include_key(&self, key: &Pubkey) -> bool {
@@ -791,6 +856,67 @@ mod tests {
         assert_eq!(loaded, vec![]);
     }
 
+    #[test]
+    fn test_load_page_by_program_with_filter() {
+        let accounts_db = AccountsDb::new_single_for_tests();
+        let accounts = Accounts::new(Arc::new(accounts_db));
+
+        let program_id = Pubkey::new_unique();
+        let mut expected: Vec<_> = (0..10u64)
+            .map(|lamports| {
+                let pubkey = solana_pubkey::new_rand();
+                let account = AccountSharedData::new(lamports + 1, 0, &program_id);
+                accounts.store_for_tests(0, &pubkey, &account);
+                (pubkey, account)
+            })
+            .collect();
+        // accounts owned by other programs are never returned
+        accounts.store_for_tests(
+            0,
+            &solana_pubkey::new_rand(),
+            &AccountSharedData::new(1, 0, &Pubkey::new_unique()),
+        );
+        accounts.add_root_and_flush_write_cache(0);
+        expected.sort_unstable_by_key(|(pubkey, _)| *pubkey);
+
+        let ancestors = vec![(0, 0)].into_iter().collect();
+        let load_page = |start_after| {
+            let config = ScanConfig {
+                start_after,
+                ..ScanConfig::new(ScanOrder::Sorted)
+            };
+            accounts
+                .load_page_by_program_with_filter(&ancestors, 0, &program_id, |_| true, 4, &config)
+                .unwrap()
+        };
+
+        let mut start_after = None;
+        let mut loaded = Vec::new();
+        loop {
+            let page = load_page(start_after);
+            assert!(page.len() <= 4);
+            let Some((last, _)) = page.last() else {
+                break;
+            };
+            start_after = Some(*last);
+            loaded.extend(page);
+        }
+        assert_eq!(loaded, expected);
+
+        // the filter is applied before the limit
+        let loaded = accounts
+            .load_page_by_program_with_filter(
+                &ancestors,
+                0,
+                &program_id,
+                |account| account.lamports() % 2 == 0,
+                usize::MAX,
+                &ScanConfig::new(ScanOrder::Sorted),
+            )
+            .unwrap();
+        assert_eq!(loaded.len(), 5);
+    }
+
     #[test_case(false; "old")]
     #[test_case(true; "simd83")]
     fn test_lock_accounts_with_duplicates(relax_intrabatch_account_locks: bool) {
@@ -851,7 +851,7 @@ impl AccountStorageEntry {
         provider: AccountsFileProvider,
         storage_access: StorageAccess,
     ) -> Self {
-        let tail = AccountsFile::file_name(slot, id);
+        let tail = provider.file_name(slot, id);
         let path = Path::new(path).join(tail);
         let accounts = provider.new_writable(path, file_size, storage_access);
 
@@ -1024,6 +1024,17 @@ impl AccountStorageEntry {
     pub fn path(&self) -> &Path {
         self.accounts.path()
     }
+
+    /// Returns the file name of this storage in snapshots, which is composed from its slot and id
+    /// and tells its format
+    pub fn file_name(&self) -> String {
+        match &self.accounts {
+            AccountsFile::AppendVec(_) => AccountsFile::file_name(self.slot, self.id),
+            AccountsFile::TieredStorage(_) => {
+                AccountsFile::tiered_storage_file_name(self.slot, self.id)
+            }
+        }
+    }
 }
 
 pub fn get_temp_accounts_paths(count: u32) -> io::Result<(Vec<TempDir>, Vec<PathBuf>)> {
@@ -1191,6 +1202,9 @@ pub struct AccountsDb {
     /// storage format to use for new storages
     accounts_file_provider: AccountsFileProvider,
 
+    /// storage format to use for new ancient storages
+    ancient_accounts_file_provider: AccountsFileProvider,
+
     /// method to use for accessing storages
     storage_access: StorageAccess,
 
@@ -1376,6 +1390,9 @@ impl AccountsDb {
             zero_lamport_accounts_to_purge_after_full_snapshot: DashSet::default(),
             log_dead_slots: AtomicBool::new(true),
             accounts_file_provider: AccountsFileProvider::default(),
+            ancient_accounts_file_provider: accounts_db_config
+                .ancient_accounts_file_provider
+                .unwrap_or_default(),
             latest_full_snapshot_slot: SeqLock::new(None),
             best_ancient_slots_to_shrink: RwLock::default(),
             mark_obsolete_accounts: accounts_db_config.mark_obsolete_accounts,
@@ -1412,13 +1429,19 @@ impl AccountsDb {
         next_id
     }
 
-    fn new_storage_entry(&self, slot: Slot, path: &Path, size: u64) -> AccountStorageEntry {
+    fn new_storage_entry(
+        &self,
+        slot: Slot,
+        path: &Path,
+        size: u64,
+        provider: AccountsFileProvider,
+    ) -> AccountStorageEntry {
         AccountStorageEntry::new(
             path,
             slot,
             self.next_id(),
             size,
-            self.accounts_file_provider,
+            provider,
             self.storage_access,
         )
     }
@@ -3314,6 +3337,25 @@ impl AccountsDb {
         self.storage.shrinking_in_progress(slot, shrunken_store)
     }
 
+    /// return an ancient store that can contain 'size' bytes
+    ///
+    /// The store uses `ancient_accounts_file_provider`, which allows ancient
+    /// storages to use a different format than other storages.
+    pub(crate) fn get_ancient_store_for_shrink(
+        &self,
+        slot: Slot,
+        size: u64,
+    ) -> ShrinkInProgress<'_> {
+        let ancient_store = self.create_store_with_provider(
+            slot,
+            size,
+            "ancient",
+            self.shrink_paths.as_slice(),
+            self.ancient_accounts_file_provider,
+        );
+        self.storage.shrinking_in_progress(slot, ancient_store)
+    }
+
     // Reads all accounts in given slot's AppendVecs and filter only to alive,
     // then create a minimum AppendVec filled with the alive.
     fn shrink_slot_forced(&self, slot: Slot) {
@@ -3699,12 +3741,7 @@ impl AccountsDb {
     where
         F: FnMut(Option<(&Pubkey, AccountSharedData, Slot)>),
     {
-        let key = match &index_key {
-            IndexKey::ProgramId(key) => key,
-            IndexKey::SplTokenMint(key) => key,
-            IndexKey::SplTokenOwner(key) => key,
-        };
-        if !self.account_indexes.include_key(key) {
+        if !self.account_indexes.include_key(index_key.include_key()) {
             // the requested key was not indexed in the secondary index, so do a normal scan
             let used_index = false;
             self.scan_accounts(ancestors, bank_id, scan_func, config)?;
@@ -4354,12 +4391,24 @@ impl AccountsDb {
         size: u64,
         from: &str,
         paths: &[PathBuf],
+    ) -> Arc<AccountStorageEntry> {
+        self.create_store_with_provider(slot, size, from, paths, self.accounts_file_provider)
+    }
+
+    fn create_store_with_provider(
+        &self,
+        slot: Slot,
+        size: u64,
+        from: &str,
+        paths: &[PathBuf],
+        provider: AccountsFileProvider,
     ) -> Arc<AccountStorageEntry> {
         self.stats
             .create_store_count
             .fetch_add(1, Ordering::Relaxed);
         let path_index = thread_rng().gen_range(0..paths.len());
-        let store = Arc::new(self.new_storage_entry(slot, Path::new(&paths[path_index]), size));
+        let store =
+            Arc::new(self.new_storage_entry(slot, Path::new(&paths[path_index]), size, provider));
 
         debug!(
             "creating store: {} slot: {} len: {} size: {} from: {} path: {}",
@@ -4,7 +4,7 @@ use {
         MEMLOCK_BUDGET_SIZE_FOR_TESTS,
     },
     crate::{
-        accounts_file::StorageAccess,
+        accounts_file::{AccountsFileProvider, StorageAccess},
         accounts_index::{
             AccountSecondaryIndexes, AccountsIndexConfig, ScanFilter,
             ACCOUNTS_INDEX_CONFIG_FOR_BENCHMARKS, ACCOUNTS_INDEX_CONFIG_FOR_TESTING,
@@ -36,6 +36,9 @@ pub struct AccountsDbConfig {
     pub ancient_append_vec_offset: Option<i64>,
     pub ancient_storage_ideal_size: Option<u64>,
     pub max_ancient_storages: Option<usize>,
+    /// The storage format used when packing ancient storages.
+    /// If None, ancient storages are created as append vecs.
+    pub ancient_accounts_file_provider: Option<AccountsFileProvider>,
     pub skip_initial_hash_calc: bool,
     pub exhaustively_verify_refcounts: bool,
     pub partitioned_epoch_rewards_config: PartitionedEpochRewardsConfig,
@@ -64,6 +67,7 @@ pub const ACCOUNTS_DB_CONFIG_FOR_TESTING: AccountsDbConfig = AccountsDbConfig {
     ancient_append_vec_offset: None,
     ancient_storage_ideal_size: None,
     max_ancient_storages: None,
+    ancient_accounts_file_provider: None,
     skip_initial_hash_calc: false,
     exhaustively_verify_refcounts: false,
     partitioned_epoch_rewards_config: DEFAULT_PARTITIONED_EPOCH_REWARDS_CONFIG,
@@ -87,6 +91,7 @@ pub const ACCOUNTS_DB_CONFIG_FOR_BENCHMARKS: AccountsDbConfig = AccountsDbConfig
     ancient_append_vec_offset: None,
     ancient_storage_ideal_size: None,
     max_ancient_storages: None,
+    ancient_accounts_file_provider: None,
     skip_initial_hash_calc: false,
     exhaustively_verify_refcounts: false,
     partitioned_epoch_rewards_config: DEFAULT_PARTITIONED_EPOCH_REWARDS_CONFIG,
@@ -1630,7 +1630,7 @@ fn test_clean_old_with_both_normal_and_zero_lamport_accounts(
         .index_scan_accounts(
             &Ancestors::default(),
             bank_id,
-            index_key,
+            index_key.clone(),
             |key, _| {
                 found_accounts.insert(*key);
             },
@@ -1652,7 +1652,7 @@ fn test_clean_old_with_both_normal_and_zero_lamport_accounts(
             .index_scan_accounts(
                 &Ancestors::default(),
                 bank_id,
-                index_key,
+                index_key.clone(),
                 |account| {
                     found_accounts.insert(*account.unwrap().0);
                 },
@@ -3776,7 +3776,7 @@ define_accounts_db_test!(
                     0
                 );
             }
-            AccountsFileProvider::HotStorage => {
+            AccountsFileProvider::HotStorage | AccountsFileProvider::ColdStorage => {
                 // For tired-storage, alive bytes are only an approximation.
                 // Therefore, it won't be zero.
                 assert!(
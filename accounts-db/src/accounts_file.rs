@@ -9,7 +9,8 @@ use {
         buffered_reader::RequiredLenBufFileRead,
         storable_accounts::StorableAccounts,
         tiered_storage::{
-            error::TieredStorageError, hot::HOT_FORMAT, index::IndexOffset, TieredStorage,
+            cold::COLD_FORMAT, error::TieredStorageError, hot::HOT_FORMAT, index::IndexOffset,
+            TieredStorage,
         },
     },
     solana_account::AccountSharedData,
@@ -18,6 +19,7 @@ use {
     std::{
         mem,
         path::{Path, PathBuf},
+        str::FromStr,
     },
     thiserror::Error,
 };
@@ -33,6 +35,12 @@ macro_rules! u64_align {
     };
 }
 
+/// The extension of the file names of tiered storages
+///
+/// Append vecs are named `<slot>.<id>` and tiered storages `<slot>.<id>.tiered`.  The writer picks
+/// the name, so reopening a storage never has to guess its format from the file content.
+pub const TIERED_STORAGE_FILE_EXTENSION: &str = "tiered";
+
 pub type Result<T> = std::result::Result<T, AccountsFileError>;
 
 /// An enum for AccountsFile related errors.
@@ -69,14 +77,21 @@ pub enum AccountsFile {
 impl AccountsFile {
     /// Create an AccountsFile instance from the specified path.
     ///
-    /// The second element of the returned tuple is the number of accounts in the
-    /// accounts file.
+    /// The format of the accounts file is given by its file name, see
+    /// [`TIERED_STORAGE_FILE_EXTENSION`].  The second element of the returned tuple is the number
+    /// of accounts in the accounts file.
     #[cfg(feature = "dev-context-only-utils")]
     pub fn new_from_file(
         path: impl Into<PathBuf>,
         current_len: usize,
         storage_access: StorageAccess,
     ) -> Result<(Self, usize)> {
+        let path = path.into();
+        if Self::is_tiered_storage_path(&path) {
+            let ts = TieredStorage::new_readonly(path)?;
+            let num_accounts = ts.reader().map_or(0, |reader| reader.num_accounts());
+            return Ok((Self::TieredStorage(ts), num_accounts));
+        }
         let (av, num_accounts) = AppendVec::new_from_file(path, current_len, storage_access)?;
         Ok((Self::AppendVec(av), num_accounts))
     }
@@ -85,12 +100,17 @@ impl AccountsFile {
     ///
     /// This version of `new()` may only be called when reconstructing storages as part of startup.
     /// It trusts the snapshot's value for `current_len`, and relies on later index generation or
-    /// accounts verification to ensure it is valid.
+    /// accounts verification to ensure it is valid.  Tiered storages, as told by their file name,
+    /// are read-only and sized exactly, so `current_len` only applies to append vecs.
     pub fn new_for_startup(
         path: impl Into<PathBuf>,
         current_len: usize,
         storage_access: StorageAccess,
     ) -> Result<Self> {
+        let path = path.into();
+        if Self::is_tiered_storage_path(&path) {
+            return Ok(Self::TieredStorage(TieredStorage::new_readonly(path)?));
+        }
         let av = AppendVec::new_for_startup(path, current_len, storage_access)?;
         Ok(Self::AppendVec(av))
     }
@@ -155,6 +175,17 @@ impl AccountsFile {
         format!("{slot}.{id}")
     }
 
+    pub fn tiered_storage_file_name(slot: Slot, id: AccountsFileId) -> String {
+        format!("{slot}.{id}.{TIERED_STORAGE_FILE_EXTENSION}")
+    }
+
+    /// Returns whether `path` is the path of a tiered storage, as opposed to an append vec
+    pub fn is_tiered_storage_path(path: impl AsRef<Path>) -> bool {
+        path.as_ref()
+            .extension()
+            .is_some_and(|extension| extension == TIERED_STORAGE_FILE_EXTENSION)
+    }
+
     /// Calls `callback` with the stored account at `offset`.
     ///
     /// Returns `None` if there is no account at `offset`, otherwise returns the result of
@@ -366,7 +397,7 @@ impl AccountsFile {
             // assumes all offsets are multiple of 8 while TieredStorage uses
             // IndexOffset that is equivalent to AccountInfo::reduced_offset.
             Self::TieredStorage(ts) => ts
-                .write_accounts(accounts, skip, &HOT_FORMAT)
+                .write_accounts(accounts, skip)
                 .map(|mut stored_accounts_info| {
                     stored_accounts_info.offsets.iter_mut().for_each(|offset| {
                         *offset = AccountInfo::reduced_offset_to_offset(*offset as u32);
@@ -396,9 +427,38 @@ pub enum AccountsFileProvider {
     #[default]
     AppendVec,
     HotStorage,
+    /// Tiered storage with compressed account blocks, which trades read
+    /// performance for a smaller footprint.  Intended for ancient storages.
+    ColdStorage,
+}
+
+impl FromStr for AccountsFileProvider {
+    type Err = String;
+
+    fn from_str(format: &str) -> std::result::Result<Self, Self::Err> {
+        match format {
+            "append-vec" => Ok(Self::AppendVec),
+            "hot" => Ok(Self::HotStorage),
+            "cold" => Ok(Self::ColdStorage),
+            _ => Err(format!("unknown accounts file format: {format}")),
+        }
+    }
 }
 
 impl AccountsFileProvider {
+    /// The accounts file formats accepted by `from_str()`
+    pub const POSSIBLE_VALUES: &'static [&'static str] = &["append-vec", "hot", "cold"];
+
+    /// Returns the name of the file of the storage of `slot` and `id` in this format
+    pub fn file_name(&self, slot: Slot, id: AccountsFileId) -> String {
+        match self {
+            Self::AppendVec => AccountsFile::file_name(slot, id),
+            Self::HotStorage | Self::ColdStorage => {
+                AccountsFile::tiered_storage_file_name(slot, id)
+            }
+        }
+    }
+
     pub fn new_writable(
         &self,
         path: impl Into<PathBuf>,
@@ -412,7 +472,12 @@ impl AccountsFileProvider {
                 file_size as usize,
                 storage_access,
             )),
-            Self::HotStorage => AccountsFile::TieredStorage(TieredStorage::new_writable(path)),
+            Self::HotStorage => {
+                AccountsFile::TieredStorage(TieredStorage::new_writable(path, HOT_FORMAT))
+            }
+            Self::ColdStorage => {
+                AccountsFile::TieredStorage(TieredStorage::new_writable(path, COLD_FORMAT))
+            }
         }
     }
 }
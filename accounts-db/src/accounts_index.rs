@@ -16,6 +16,7 @@ use {
         rolling_bit_field::RollingBitField,
     },
     account_map_entry::{AccountMapEntry, PreAllocatedAccountMapEntry},
+    dashmap::DashMap,
     in_mem_accounts_index::{
         ExistedLocation, InMemAccountsIndex, InsertNewEntryResults, StartupStats,
     },
@@ -46,7 +47,8 @@ use {
 pub use {
     iter::ITER_BATCH_SIZE,
     secondary::{
-        AccountIndex, AccountSecondaryIndexes, AccountSecondaryIndexesIncludeExclude, IndexKey,
+        AccountIndex, AccountSecondaryIndexes, AccountSecondaryIndexesIncludeExclude,
+        DataSliceIndex, IndexKey,
     },
 };
 
@@ -143,6 +145,10 @@ pub struct ScanConfig {
 
     /// In what order should items be scanned?
     pub scan_order: ScanOrder,
+
+    /// If set, only pubkeys greater than this are scanned.
+    /// Combined with `ScanOrder::Sorted`, this allows resuming a scan where a previous one stopped.
+    pub start_after: Option<Pubkey>,
 }
 
 impl Default for ScanConfig {
@@ -150,6 +156,7 @@ impl Default for ScanConfig {
         Self {
             abort: None,
             scan_order: ScanOrder::Unsorted,
+            start_after: None,
         }
     }
 }
@@ -174,6 +181,7 @@ impl ScanConfig {
         ScanConfig {
             abort: Some(self.abort.clone().unwrap_or_default()),
             scan_order: self.scan_order,
+            start_after: self.start_after,
         }
     }
 
@@ -296,6 +304,8 @@ pub struct AccountsIndex<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> {
     program_id_index: SecondaryIndex<RwLockSecondaryIndexEntry>,
     spl_token_mint_index: SecondaryIndex<RwLockSecondaryIndexEntry>,
     spl_token_owner_index: SecondaryIndex<RwLockSecondaryIndexEntry>,
+    /// user-configured indexes over account data, created when first updated
+    data_slice_indexes: DashMap<DataSliceIndex, SecondaryIndex<RwLockSecondaryIndexEntry, Vec<u8>>>,
     pub roots_tracker: RwLock<RootsTracker>,
     ongoing_scan_roots: RwLock<BTreeMap<Slot, u64>>,
     // Each scan has some latest slot `S` that is the tip of the fork the scan
@@ -348,6 +358,7 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
             spl_token_owner_index: SecondaryIndex::<RwLockSecondaryIndexEntry>::new(
                 "spl_token_owner_index_stats",
             ),
+            data_slice_indexes: DashMap::default(),
             roots_tracker: RwLock::<RootsTracker>::default(),
             ongoing_scan_roots: RwLock::<BTreeMap<Slot, u64>>::default(),
             removed_bank_ids: Mutex::<HashSet<BankId>>::default(),
@@ -624,8 +635,7 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
                 self.do_scan_secondary_index(
                     ancestors,
                     func,
-                    &self.program_id_index,
-                    &program_id,
+                    self.program_id_index.get(&program_id),
                     Some(max_root),
                     config,
                 );
@@ -634,8 +644,7 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
                 self.do_scan_secondary_index(
                     ancestors,
                     func,
-                    &self.spl_token_mint_index,
-                    &mint_key,
+                    self.spl_token_mint_index.get(&mint_key),
                     Some(max_root),
                     config,
                 );
@@ -644,8 +653,20 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
                 self.do_scan_secondary_index(
                     ancestors,
                     func,
-                    &self.spl_token_owner_index,
-                    &owner_key,
+                    self.spl_token_owner_index.get(&owner_key),
+                    Some(max_root),
+                    config,
+                );
+            }
+            ScanTypes::Indexed(IndexKey::DataSlice {
+                index,
+                offset,
+                bytes,
+            }) => {
+                self.do_scan_secondary_index(
+                    ancestors,
+                    func,
+                    self.get_data_slice_index_keys(&index, offset, &bytes),
                     Some(max_root),
                     config,
                 );
@@ -710,7 +731,26 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
         let mut iterator_elapsed = 0;
         let mut iterator_timer = Measure::start("iterator_elapsed");
 
-        for pubkeys in self.iter(range.as_ref(), returns_items) {
+        // Resumed scans seek past `start_after`, so the bins and keys before it are skipped
+        let start_bound = match (
+            range.as_ref().map(|range| range.start_bound()),
+            config.start_after,
+        ) {
+            (Some(Bound::Included(start)), Some(start_after)) if *start <= start_after => {
+                Bound::Excluded(start_after)
+            }
+            (Some(Bound::Excluded(start)), Some(start_after)) if *start < start_after => {
+                Bound::Excluded(start_after)
+            }
+            (None | Some(Bound::Unbounded), Some(start_after)) => Bound::Excluded(start_after),
+            (start_bound, _) => start_bound.map_or(Bound::Unbounded, Bound::cloned),
+        };
+        let end_bound = range
+            .as_ref()
+            .map_or(Bound::Unbounded, |range| range.end_bound().cloned());
+        let range = (start_bound, end_bound);
+
+        for pubkeys in self.iter(Some(&range), returns_items) {
             iterator_timer.stop();
             iterator_elapsed += iterator_timer.as_us();
             for pubkey in pubkeys {
@@ -755,21 +795,24 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
         }
     }
 
-    fn do_scan_secondary_index<
-        F,
-        SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send,
-    >(
+    /// call `func` with each of `pubkeys`, which were found in a secondary index
+    fn do_scan_secondary_index<F>(
         &self,
         ancestors: &Ancestors,
         mut func: F,
-        index: &SecondaryIndex<SecondaryIndexEntryType>,
-        index_key: &Pubkey,
+        mut pubkeys: Vec<Pubkey>,
         max_root: Option<Slot>,
         config: &ScanConfig,
     ) where
         F: FnMut(&Pubkey, (&T, Slot)),
     {
-        for pubkey in index.get(index_key) {
+        if let Some(start_after) = config.start_after {
+            pubkeys.retain(|pubkey| *pubkey > start_after);
+        }
+        if config.scan_order == ScanOrder::Sorted {
+            pubkeys.sort_unstable();
+        }
+        for pubkey in pubkeys {
             if config.is_aborted() {
                 break;
             }
@@ -1199,6 +1242,52 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
         }
     }
 
+    /// Returns the pubkeys of the accounts in data slice index `index` whose indexed bytes
+    /// contain `bytes`, starting `offset` bytes into the indexed bytes
+    fn get_data_slice_index_keys(
+        &self,
+        index: &DataSliceIndex,
+        offset: usize,
+        bytes: &[u8],
+    ) -> Vec<Pubkey> {
+        let Some(data_slice_index) = self.data_slice_indexes.get(index) else {
+            return vec![];
+        };
+        if offset == 0 && bytes.len() == index.length {
+            data_slice_index.get(bytes)
+        } else {
+            data_slice_index.get_matching(|key| {
+                key.get(offset..offset.saturating_add(bytes.len())) == Some(bytes)
+            })
+        }
+    }
+
+    fn update_data_slice_secondary_indexes(
+        &self,
+        pubkey: &Pubkey,
+        account_owner: &Pubkey,
+        account_data: &[u8],
+        account_indexes: &AccountSecondaryIndexes,
+    ) {
+        for account_index in &account_indexes.indexes {
+            let AccountIndex::DataSlice(index) = account_index else {
+                continue;
+            };
+            if index.program_id != *account_owner {
+                continue;
+            }
+            if let Some(key) = index.key(account_data) {
+                let data_slice_index = self.data_slice_indexes.get(index).unwrap_or_else(|| {
+                    self.data_slice_indexes
+                        .entry(index.clone())
+                        .or_insert_with(|| SecondaryIndex::new("data_slice_index_stats"))
+                        .downgrade()
+                });
+                data_slice_index.insert(key, pubkey);
+            }
+        }
+    }
+
     pub fn get_index_key_size(&self, index: &AccountIndex, index_key: &Pubkey) -> Option<usize> {
         match index {
             AccountIndex::ProgramId => self.program_id_index.index.get(index_key).map(|x| x.len()),
@@ -1212,6 +1301,8 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
                 .index
                 .get(index_key)
                 .map(|x| x.len()),
+            // data slice indexes are keyed by byte slices of any length, not by pubkeys
+            AccountIndex::DataSlice(_) => None,
         }
     }
 
@@ -1229,6 +1320,15 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
             info!("secondary index: {:?}", AccountIndex::SplTokenOwner);
             self.spl_token_owner_index.log_contents();
         }
+        for entry in self.data_slice_indexes.iter() {
+            if !entry.value().index.is_empty() {
+                info!(
+                    "secondary index: {:?}",
+                    AccountIndex::DataSlice(entry.key().clone())
+                );
+                entry.value().log_contents();
+            }
+        }
     }
 
     pub(crate) fn update_secondary_indexes(
@@ -1249,6 +1349,14 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
         {
             self.program_id_index.insert(account_owner, pubkey);
         }
+        if account_indexes.include_key(account_owner) {
+            self.update_data_slice_secondary_indexes(
+                pubkey,
+                account_owner,
+                account_data,
+                account_indexes,
+            );
+        }
         // Note because of the below check below on the account data length, when an
         // account hits zero lamports and is reset to AccountSharedData::Default, then we skip
         // the below updates to the secondary indexes.
@@ -1481,6 +1589,10 @@ impl<T: IndexValue, U: DiskIndexValue + From<T> + Into<T>> AccountsIndex<T, U> {
         if account_indexes.contains(&AccountIndex::SplTokenMint) {
             self.spl_token_mint_index.remove_by_inner_key(inner_key);
         }
+
+        for entry in self.data_slice_indexes.iter() {
+            entry.value().remove_by_inner_key(inner_key);
+        }
     }
 
     fn purge_older_root_entries(
@@ -1741,7 +1853,7 @@ pub mod tests {
             accounts_index::account_map_entry::AccountMapEntryMeta,
             bucket_map_holder::BucketMapHolder,
         },
-        secondary::DashMapSecondaryIndexEntry,
+        secondary::{DashMapSecondaryIndexEntry, SecondaryIndexEntry},
         solana_account::{AccountSharedData, WritableAccount},
         solana_pubkey::PUBKEY_BYTES,
         spl_generic_token::{spl_token_ids, token::SPL_TOKEN_ACCOUNT_OWNER_OFFSET},
@@ -1865,6 +1977,83 @@ pub mod tests {
         assert!(index.include_key(&pk2));
     }
 
+    #[test]
+    fn test_data_slice_index_covered_range() {
+        let index = DataSliceIndex {
+            program_id: Pubkey::new_unique(),
+            offset: 8,
+            length: 32,
+        };
+        assert_eq!(index.covered_range(8, 32), Some(0..32));
+        assert_eq!(index.covered_range(8, 4), Some(0..4));
+        assert_eq!(index.covered_range(12, 28), Some(4..32));
+        assert_eq!(index.covered_range(4, 8), None);
+        assert_eq!(index.covered_range(12, 32), None);
+        assert_eq!(index.covered_range(8, 0), None);
+        assert_eq!(index.key(&[0; 39]), None);
+        assert_eq!(index.key(&[1; 40]), Some(&[1; 32][..]));
+    }
+
+    #[test]
+    fn test_data_slice_secondary_index() {
+        let program_id = Pubkey::new_unique();
+        let data_slice_index = DataSliceIndex {
+            program_id,
+            offset: 8,
+            length: 32,
+        };
+        let account_indexes = AccountSecondaryIndexes {
+            keys: None,
+            indexes: HashSet::from([AccountIndex::DataSlice(data_slice_index.clone())]),
+        };
+        let index = AccountsIndex::<bool, bool>::default_for_tests();
+
+        let authority = Pubkey::new_unique();
+        let mut data = vec![0; 64];
+        data[8..40].copy_from_slice(authority.as_ref());
+        let account_key = Pubkey::new_unique();
+        let account = AccountSharedData::create(1, data.clone(), program_id, false, 0);
+        index.update_secondary_indexes(&account_key, &account, &account_indexes);
+
+        // accounts owned by other programs, or too short, are not indexed
+        let other_key = Pubkey::new_unique();
+        let other_account = AccountSharedData::create(1, data, Pubkey::new_unique(), false, 0);
+        index.update_secondary_indexes(&other_key, &other_account, &account_indexes);
+        let short_key = Pubkey::new_unique();
+        let short_account = AccountSharedData::create(1, vec![0; 39], program_id, false, 0);
+        index.update_secondary_indexes(&short_key, &short_account, &account_indexes);
+
+        // exact, prefix and sub-range lookups
+        let authority_bytes = authority.to_bytes();
+        assert_eq!(
+            index.get_data_slice_index_keys(&data_slice_index, 0, &authority_bytes),
+            vec![account_key]
+        );
+        assert_eq!(
+            index.get_data_slice_index_keys(&data_slice_index, 0, &authority_bytes[..4]),
+            vec![account_key]
+        );
+        assert_eq!(
+            index.get_data_slice_index_keys(&data_slice_index, 4, &authority_bytes[4..8]),
+            vec![account_key]
+        );
+        assert!(index
+            .get_data_slice_index_keys(&data_slice_index, 0, &Pubkey::new_unique().to_bytes())
+            .is_empty());
+        assert_eq!(
+            index.get_index_key_size(
+                &AccountIndex::DataSlice(data_slice_index.clone()),
+                &authority
+            ),
+            None
+        );
+
+        index.purge_secondary_indexes_by_inner_key(&account_key, &account_indexes);
+        assert!(index
+            .get_data_slice_index_keys(&data_slice_index, 0, &authority_bytes)
+            .is_empty());
+    }
+
     const UPSERT_RECLAIM_TEST_DEFAULT: UpsertReclaim = UpsertReclaim::PopulateReclaims;
 
     #[test]
@@ -2674,6 +2863,30 @@ pub mod tests {
         run_test_scan_accounts(ITER_BATCH_SIZE * 10 + 1);
     }
 
+    #[test]
+    fn test_scan_accounts_start_after() {
+        let (index, mut pubkeys) = setup_accounts_index_keys(ITER_BATCH_SIZE * 3);
+        pubkeys.sort_unstable();
+
+        // resume from several points, including before the first and at the last pubkey
+        for start_after in [0, 1, ITER_BATCH_SIZE * 2, pubkeys.len() - 1] {
+            let config = ScanConfig {
+                start_after: Some(pubkeys[start_after]),
+                ..ScanConfig::new(ScanOrder::Sorted)
+            };
+            let mut scanned_keys = Vec::new();
+            index
+                .scan_accounts(
+                    &Ancestors::default(),
+                    0,
+                    |pubkey, _index| scanned_keys.push(*pubkey),
+                    &config,
+                )
+                .expect("scan should succeed");
+            assert_eq!(scanned_keys, pubkeys[start_after + 1..]);
+        }
+    }
+
     #[test]
     fn test_is_alive_root() {
         let index = AccountsIndex::<bool, bool>::default_for_tests();
@@ -4119,5 +4332,11 @@ pub mod tests {
 
         let config = config.recreate_with_abort();
         assert!(config.is_aborted());
+
+        let config = ScanConfig {
+            start_after: Some(Pubkey::new_unique()),
+            ..ScanConfig::new(ScanOrder::Sorted)
+        };
+        assert_eq!(config.recreate_with_abort().start_after, config.start_after);
     }
 }
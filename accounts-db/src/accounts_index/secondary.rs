@@ -4,8 +4,11 @@ use {
     solana_pubkey::Pubkey,
     solana_time_utils::AtomicInterval,
     std::{
+        borrow::Borrow,
         collections::HashSet,
         fmt::Debug,
+        hash::Hash,
+        ops::Range,
         sync::{
             atomic::{AtomicU64, Ordering},
             RwLock,
@@ -45,20 +48,71 @@ pub enum AccountIndex {
     ProgramId,
     SplTokenMint,
     SplTokenOwner,
+    DataSlice(DataSliceIndex),
 }
 
-#[derive(Debug, Clone, Copy)]
+/// A user-configured index over `length` bytes of account data, starting at
+/// `offset`, for the accounts owned by `program_id`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DataSliceIndex {
+    pub program_id: Pubkey,
+    pub offset: usize,
+    pub length: usize,
+}
+
+impl DataSliceIndex {
+    /// Returns the indexed bytes of `data`, or None if `data` is too short
+    pub fn key<'a>(&self, data: &'a [u8]) -> Option<&'a [u8]> {
+        let end = self.offset.checked_add(self.length)?;
+        data.get(self.offset..end)
+    }
+
+    /// Returns the range, relative to the start of the indexed bytes, covered by
+    /// comparing `len` bytes of account data at `offset`
+    ///
+    /// Returns None if the compared bytes are not entirely within the indexed bytes.
+    pub fn covered_range(&self, offset: usize, len: usize) -> Option<Range<usize>> {
+        let start = offset.checked_sub(self.offset)?;
+        let end = start.checked_add(len)?;
+        (len > 0 && end <= self.length).then_some(start..end)
+    }
+}
+
+#[derive(Debug, Clone)]
 pub enum IndexKey {
     ProgramId(Pubkey),
     SplTokenMint(Pubkey),
     SplTokenOwner(Pubkey),
+    /// Accounts whose indexed bytes in `index` contain `bytes`, starting `offset` bytes
+    /// into the indexed bytes.
+    ///
+    /// When `bytes` covers all the indexed bytes, this is an exact lookup. Otherwise all
+    /// distinct keys in the index are checked, which is still much cheaper than loading
+    /// every account of the program.
+    DataSlice {
+        index: DataSliceIndex,
+        offset: usize,
+        bytes: Vec<u8>,
+    },
+}
+
+impl IndexKey {
+    /// Returns the pubkey checked against the include/exclude keys of the indexes
+    ///
+    /// For data slice indexes, this is the program id.
+    pub fn include_key(&self) -> &Pubkey {
+        match self {
+            Self::ProgramId(key) | Self::SplTokenMint(key) | Self::SplTokenOwner(key) => key,
+            Self::DataSlice { index, .. } => &index.program_id,
+        }
+    }
 }
 
 // The only cases where an inner key should map to a different outer key is
 // if the key had different account data for the indexed key across different
 // slots. As this is rare, it should be ok to use a Vec here over a HashSet, even
 // though we are running some key existence checks.
-type SecondaryReverseIndexEntry = RwLock<Vec<Pubkey>>;
+type SecondaryReverseIndexEntry<Key> = RwLock<Vec<Key>>;
 
 pub trait SecondaryIndexEntry: Debug {
     fn insert_if_not_exists(&self, key: &Pubkey, inner_keys_count: &AtomicU64);
@@ -145,16 +199,21 @@ impl SecondaryIndexEntry for RwLockSecondaryIndexEntry {
 }
 
 #[derive(Debug, Default)]
-pub struct SecondaryIndex<SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send> {
+pub struct SecondaryIndex<
+    SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send,
+    Key: Eq + Hash = Pubkey,
+> {
     metrics_name: &'static str,
     // Map from index keys to index values
-    pub index: DashMap<Pubkey, SecondaryIndexEntryType>,
-    pub reverse_index: DashMap<Pubkey, SecondaryReverseIndexEntry>,
+    pub index: DashMap<Key, SecondaryIndexEntryType>,
+    pub reverse_index: DashMap<Pubkey, SecondaryReverseIndexEntry<Key>>,
     stats: SecondaryIndexStats,
 }
 
-impl<SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send>
-    SecondaryIndex<SecondaryIndexEntryType>
+impl<
+        SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send,
+        Key: Clone + Debug + Default + Eq + Hash + Ord,
+    > SecondaryIndex<SecondaryIndexEntryType, Key>
 {
     pub fn new(metrics_name: &'static str) -> Self {
         Self {
@@ -163,12 +222,16 @@ impl<SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send>
         }
     }
 
-    pub fn insert(&self, key: &Pubkey, inner_key: &Pubkey) {
+    pub fn insert<Q>(&self, key: &Q, inner_key: &Pubkey)
+    where
+        Key: Borrow<Q>,
+        Q: Eq + Hash + ToOwned<Owned = Key> + ?Sized,
+    {
         {
             let pubkeys_map = self
                 .index
                 .get(key)
-                .unwrap_or_else(|| self.index.entry(*key).or_default().downgrade());
+                .unwrap_or_else(|| self.index.entry(key.to_owned()).or_default().downgrade());
 
             pubkeys_map.insert_if_not_exists(inner_key, &self.stats.num_inner_keys);
         }
@@ -181,11 +244,18 @@ impl<SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send>
                     .downgrade()
             });
 
-            let should_insert = !outer_keys.read().unwrap().contains(key);
+            let should_insert = !outer_keys
+                .read()
+                .unwrap()
+                .iter()
+                .any(|outer_key| <Key as Borrow<Q>>::borrow(outer_key) == key);
             if should_insert {
                 let mut w_outer_keys = outer_keys.write().unwrap();
-                if !w_outer_keys.contains(key) {
-                    w_outer_keys.push(*key);
+                if !w_outer_keys
+                    .iter()
+                    .any(|outer_key| <Key as Borrow<Q>>::borrow(outer_key) == key)
+                {
+                    w_outer_keys.push(key.to_owned());
                 }
             }
         }
@@ -209,7 +279,7 @@ impl<SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send>
     }
 
     // Only safe to call from `remove_by_inner_key()` due to asserts
-    fn remove_index_entries(&self, outer_key: &Pubkey, removed_inner_key: &Pubkey) {
+    fn remove_index_entries(&self, outer_key: &Key, removed_inner_key: &Pubkey) {
         let is_outer_key_empty = {
             let inner_key_map = self
                 .index
@@ -225,7 +295,7 @@ impl<SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send>
         if is_outer_key_empty {
             // Other threads may have interleaved writes to this `key`,
             // so double-check again for its emptiness
-            if let Occupied(key_entry) = self.index.entry(*outer_key) {
+            if let Occupied(key_entry) = self.index.entry(outer_key.clone()) {
                 if key_entry.get().is_empty() {
                     key_entry.remove();
                 }
@@ -236,7 +306,7 @@ impl<SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send>
     pub fn remove_by_inner_key(&self, inner_key: &Pubkey) {
         // Save off which keys in `self.index` had slots removed so we can remove them
         // after we purge the reverse index
-        let mut removed_outer_keys: HashSet<Pubkey> = HashSet::new();
+        let mut removed_outer_keys: HashSet<Key> = HashSet::new();
 
         // Check if the entry for `inner_key` in the reverse index is empty
         // and can be removed
@@ -260,7 +330,11 @@ impl<SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send>
             .fetch_sub(removed_outer_keys.len() as u64, Ordering::Relaxed);
     }
 
-    pub fn get(&self, key: &Pubkey) -> Vec<Pubkey> {
+    pub fn get<Q>(&self, key: &Q) -> Vec<Pubkey>
+    where
+        Key: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
         if let Some(inner_keys_map) = self.index.get(key) {
             inner_keys_map.keys()
         } else {
@@ -268,18 +342,31 @@ impl<SecondaryIndexEntryType: SecondaryIndexEntry + Default + Sync + Send>
         }
     }
 
+    /// Returns the inner keys of every key for which `predicate` returns true
+    ///
+    /// This visits every key in the index, so prefer `get()` for exact lookups.
+    pub fn get_matching(&self, mut predicate: impl FnMut(&Key) -> bool) -> Vec<Pubkey> {
+        let mut inner_keys = HashSet::new();
+        for entry in self.index.iter() {
+            if predicate(entry.key()) {
+                inner_keys.extend(entry.value().keys());
+            }
+        }
+        inner_keys.into_iter().collect()
+    }
+
     /// log top 20 (owner, # accounts) in descending order of # accounts
     pub fn log_contents(&self) {
         let mut entries = self
             .index
             .iter()
-            .map(|entry| (entry.value().len(), *entry.key()))
+            .map(|entry| (entry.value().len(), entry.key().clone()))
             .collect::<Vec<_>>();
         entries.sort_unstable();
         entries
             .iter()
             .rev()
             .take(20)
-            .for_each(|(v, k)| info!("owner: {k}, accounts: {v}"));
+            .for_each(|(v, k)| info!("owner: {k:?}, accounts: {v}"));
     }
 }
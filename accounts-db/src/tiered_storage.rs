@@ -1,6 +1,7 @@
 #![allow(dead_code)]
 
 pub mod byte_block;
+pub mod cold;
 pub mod error;
 pub mod file;
 pub mod footer;
@@ -14,6 +15,7 @@ mod test_utils;
 
 use {
     crate::{accounts_file::StoredAccountsInfo, storable_accounts::StorableAccounts},
+    cold::{ColdStorageWriter, COLD_FORMAT},
     error::TieredStorageError,
     footer::{AccountBlockFormat, AccountMetaFormat},
     hot::{HotStorageWriter, HOT_FORMAT},
@@ -45,6 +47,17 @@ pub struct TieredStorageFormat {
     pub account_block_format: AccountBlockFormat,
 }
 
+impl TieredStorageFormat {
+    /// Returns the TieredStorageFormat associated with the specified
+    /// AccountMetaFormat.
+    pub fn from_account_meta_format(account_meta_format: AccountMetaFormat) -> Self {
+        match account_meta_format {
+            AccountMetaFormat::Hot => HOT_FORMAT,
+            AccountMetaFormat::Cold => COLD_FORMAT,
+        }
+    }
+}
+
 /// The implementation of AccountsFile for tiered-storage.
 #[derive(Debug)]
 pub struct TieredStorage {
@@ -54,6 +67,8 @@ pub struct TieredStorage {
     already_written: AtomicBool,
     /// The path to the file that stores accounts.
     path: PathBuf,
+    /// The format of the file that stores accounts.
+    format: TieredStorageFormat,
 }
 
 impl Drop for TieredStorage {
@@ -77,11 +92,12 @@ impl TieredStorage {
     ///
     /// Note that the actual file will not be created until write_accounts
     /// is called.
-    pub fn new_writable(path: impl Into<PathBuf>) -> Self {
+    pub fn new_writable(path: impl Into<PathBuf>, format: TieredStorageFormat) -> Self {
         Self {
             reader: OnceLock::<TieredStorageReader>::new(),
             already_written: false.into(),
             path: path.into(),
+            format,
         }
     }
 
@@ -89,10 +105,14 @@ impl TieredStorage {
     /// specified path.
     pub fn new_readonly(path: impl Into<PathBuf>) -> TieredStorageResult<Self> {
         let path = path.into();
+        let reader = TieredStorageReader::new_from_path(&path)?;
+        let format =
+            TieredStorageFormat::from_account_meta_format(reader.footer().account_meta_format);
         Ok(Self {
-            reader: TieredStorageReader::new_from_path(&path).map(OnceLock::from)?,
+            reader: OnceLock::from(reader),
             already_written: true.into(),
             path,
+            format,
         })
     }
 
@@ -101,6 +121,11 @@ impl TieredStorage {
         self.path.as_path()
     }
 
+    /// Returns the format of this TieredStorage.
+    pub fn format(&self) -> &TieredStorageFormat {
+        &self.format
+    }
+
     /// Writes the specified accounts into this TieredStorage.
     ///
     /// Note that this function can only be called once per a TieredStorage
@@ -109,7 +134,6 @@ impl TieredStorage {
         &self,
         accounts: &impl StorableAccounts<'a>,
         skip: usize,
-        format: &TieredStorageFormat,
     ) -> TieredStorageResult<StoredAccountsInfo> {
         let was_written = self.already_written.swap(true, Ordering::AcqRel);
 
@@ -117,26 +141,29 @@ impl TieredStorage {
             panic!("cannot write same tiered storage file more than once");
         }
 
-        if format == &HOT_FORMAT {
-            let stored_accounts_info = {
-                let mut writer = HotStorageWriter::new(&self.path)?;
-                let stored_accounts_info = writer.write_accounts(accounts, skip)?;
-                writer.flush()?;
-                stored_accounts_info
-            };
+        let stored_accounts_info = if self.format == HOT_FORMAT {
+            let mut writer = HotStorageWriter::new(&self.path)?;
+            let stored_accounts_info = writer.write_accounts(accounts, skip)?;
+            writer.flush()?;
+            stored_accounts_info
+        } else if self.format == COLD_FORMAT {
+            let mut writer = ColdStorageWriter::new(&self.path)?;
+            let stored_accounts_info = writer.write_accounts(accounts, skip)?;
+            writer.flush()?;
+            stored_accounts_info
+        } else {
+            return Err(TieredStorageError::UnknownFormat(self.path.to_path_buf()));
+        };
 
-            // panic here if self.reader.get() is not None as self.reader can only be
-            // None since a false-value `was_written` indicates the accounts file has
-            // not been written previously, implying is_read_only() was also false.
-            debug_assert!(!self.is_read_only());
-            self.reader
-                .set(TieredStorageReader::new_from_path(&self.path)?)
-                .unwrap();
+        // panic here if self.reader.get() is not None as self.reader can only be
+        // None since a false-value `was_written` indicates the accounts file has
+        // not been written previously, implying is_read_only() was also false.
+        debug_assert!(!self.is_read_only());
+        self.reader
+            .set(TieredStorageReader::new_from_path(&self.path)?)
+            .unwrap();
 
-            Ok(stored_accounts_info)
-        } else {
-            Err(TieredStorageError::UnknownFormat(self.path.to_path_buf()))
-        }
+        Ok(stored_accounts_info)
     }
 
     /// Returns the underlying reader of the TieredStorage.  None will be
@@ -175,10 +202,14 @@ impl TieredStorage {
 mod tests {
     use {
         super::*,
-        file::TieredStorageMagicNumber,
+        crate::{
+            accounts_db::AccountsFileId,
+            accounts_file::{AccountsFile, AccountsFileProvider, StorageAccess},
+        },
+        file::{TieredStorageMagicNumber, FILE_MAGIC_NUMBER},
         footer::TieredStorageFooter,
         hot::HOT_FORMAT,
-        solana_account::{AccountSharedData, ReadableAccount},
+        solana_account::{Account, AccountSharedData, ReadableAccount},
         solana_clock::Slot,
         solana_pubkey::Pubkey,
         solana_system_interface::MAX_PERMITTED_DATA_LENGTH,
@@ -206,7 +237,7 @@ mod tests {
         let account_refs = Vec::<(&Pubkey, &AccountSharedData)>::new();
         let storable_accounts = (slot_ignored, account_refs.as_slice());
 
-        let result = tiered_storage.write_accounts(&storable_accounts, 0, &HOT_FORMAT);
+        let result = tiered_storage.write_accounts(&storable_accounts, 0);
 
         match (&result, &expected_result) {
             (
@@ -236,8 +267,10 @@ mod tests {
         let tiered_storage_path = temp_dir.path().join("test_new_meta_file_only");
 
         {
-            let tiered_storage =
-                ManuallyDrop::new(TieredStorage::new_writable(&tiered_storage_path));
+            let tiered_storage = ManuallyDrop::new(TieredStorage::new_writable(
+                &tiered_storage_path,
+                HOT_FORMAT,
+            ));
 
             assert!(!tiered_storage.is_read_only());
             assert_eq!(tiered_storage.path(), tiered_storage_path);
@@ -274,7 +307,7 @@ mod tests {
         let temp_dir = tempdir().unwrap();
         let tiered_storage_path = temp_dir.path().join("test_write_accounts_twice");
 
-        let tiered_storage = TieredStorage::new_writable(&tiered_storage_path);
+        let tiered_storage = TieredStorage::new_writable(&tiered_storage_path, HOT_FORMAT);
         write_zero_accounts(
             &tiered_storage,
             Ok(StoredAccountsInfo {
@@ -298,7 +331,7 @@ mod tests {
         let temp_dir = tempdir().unwrap();
         let tiered_storage_path = temp_dir.path().join("test_remove_on_drop");
         {
-            let tiered_storage = TieredStorage::new_writable(&tiered_storage_path);
+            let tiered_storage = TieredStorage::new_writable(&tiered_storage_path, HOT_FORMAT);
             write_zero_accounts(
                 &tiered_storage,
                 Ok(StoredAccountsInfo {
@@ -311,8 +344,10 @@ mod tests {
         assert!(!tiered_storage_path.try_exists().unwrap());
 
         {
-            let tiered_storage =
-                ManuallyDrop::new(TieredStorage::new_writable(&tiered_storage_path));
+            let tiered_storage = ManuallyDrop::new(TieredStorage::new_writable(
+                &tiered_storage_path,
+                HOT_FORMAT,
+            ));
             write_zero_accounts(
                 &tiered_storage,
                 Ok(StoredAccountsInfo {
@@ -340,7 +375,6 @@ mod tests {
     }
 
     /// The helper function for all write_accounts tests.
-    /// Currently only supports hot accounts.
     fn do_test_write_accounts(
         path_suffix: &str,
         account_data_sizes: &[u64],
@@ -361,8 +395,8 @@ mod tests {
 
         let temp_dir = tempdir().unwrap();
         let tiered_storage_path = temp_dir.path().join(path_suffix);
-        let tiered_storage = TieredStorage::new_writable(tiered_storage_path);
-        _ = tiered_storage.write_accounts(&storable_accounts, 0, &format);
+        let tiered_storage = TieredStorage::new_writable(tiered_storage_path, format);
+        _ = tiered_storage.write_accounts(&storable_accounts, 0);
 
         let reader = tiered_storage.reader().unwrap();
         let num_accounts = storable_accounts.len();
@@ -437,4 +471,167 @@ mod tests {
             HOT_FORMAT.clone(),
         );
     }
+
+    #[test]
+    fn test_write_accounts_small_accounts_cold() {
+        do_test_write_accounts(
+            "test_write_accounts_small_accounts_cold",
+            &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10],
+            COLD_FORMAT.clone(),
+        );
+    }
+
+    #[test]
+    fn test_write_accounts_one_max_len_cold() {
+        do_test_write_accounts(
+            "test_write_accounts_one_max_len_cold",
+            &[MAX_PERMITTED_DATA_LENGTH],
+            COLD_FORMAT.clone(),
+        );
+    }
+
+    #[test]
+    fn test_write_accounts_mixed_size_cold() {
+        do_test_write_accounts(
+            "test_write_accounts_mixed_size_cold",
+            &[
+                1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 1000, 2000, 3000, 4000, 9, 8, 7, 6, 5, 4, 3, 2, 1,
+            ],
+            COLD_FORMAT.clone(),
+        );
+    }
+
+    #[test]
+    fn test_new_readonly_format() {
+        for format in [HOT_FORMAT, COLD_FORMAT] {
+            let temp_dir = tempdir().unwrap();
+            let tiered_storage_path = temp_dir.path().join("test_new_readonly_format");
+            {
+                let tiered_storage = ManuallyDrop::new(TieredStorage::new_writable(
+                    &tiered_storage_path,
+                    format.clone(),
+                ));
+                write_zero_accounts(
+                    &tiered_storage,
+                    Ok(StoredAccountsInfo {
+                        offsets: vec![],
+                        size: 0,
+                    }),
+                );
+            }
+
+            let tiered_storage_readonly =
+                TieredStorage::new_readonly(&tiered_storage_path).unwrap();
+            assert_eq!(tiered_storage_readonly.format(), &format);
+            assert_eq!(
+                tiered_storage_readonly
+                    .footer()
+                    .unwrap()
+                    .account_block_format,
+                format.account_block_format,
+            );
+        }
+    }
+
+    #[test]
+    fn test_accounts_file_for_startup_format() {
+        let temp_dir = tempdir().unwrap();
+        let account = create_test_account(100);
+        let account_refs = [(&account.0.pubkey, &account.1)];
+        let storable_accounts = (Slot::MAX, &account_refs[..]);
+
+        for (id, provider) in [
+            AccountsFileProvider::AppendVec,
+            AccountsFileProvider::HotStorage,
+            AccountsFileProvider::ColdStorage,
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let path = temp_dir
+                .path()
+                .join(provider.file_name(0, id as AccountsFileId));
+            let current_len = {
+                let accounts_file = ManuallyDrop::new(provider.new_writable(
+                    &path,
+                    1024 * 1024,
+                    StorageAccess::File,
+                ));
+                accounts_file.write_accounts(&storable_accounts, 0).unwrap();
+                accounts_file.flush().unwrap();
+                accounts_file.len()
+            };
+
+            assert_eq!(
+                AccountsFile::is_tiered_storage_path(&path),
+                provider != AccountsFileProvider::AppendVec,
+            );
+            let accounts_file =
+                AccountsFile::new_for_startup(&path, current_len, StorageAccess::File).unwrap();
+            match (provider, &accounts_file) {
+                (AccountsFileProvider::AppendVec, AccountsFile::AppendVec(_)) => {}
+                (AccountsFileProvider::HotStorage, AccountsFile::TieredStorage(ts)) => {
+                    assert_eq!(ts.format(), &HOT_FORMAT);
+                }
+                (AccountsFileProvider::ColdStorage, AccountsFile::TieredStorage(ts)) => {
+                    assert_eq!(ts.format(), &COLD_FORMAT);
+                }
+                (provider, accounts_file) => {
+                    panic!("{provider:?} reopened as {accounts_file:?}")
+                }
+            }
+            assert_eq!(accounts_file.len(), current_len);
+            let mut pubkeys = vec![];
+            accounts_file
+                .scan_pubkeys(|pubkey| pubkeys.push(*pubkey))
+                .unwrap();
+            assert_eq!(pubkeys, vec![account.0.pubkey]);
+        }
+    }
+
+    #[test]
+    fn test_append_vec_ending_with_magic_number() {
+        // The last bytes of an append vec are the data of its last account, which is controlled
+        // by users, so an append vec may end with the magic number of tiered storages.
+        let temp_dir = tempdir().unwrap();
+        let pubkey = Pubkey::new_unique();
+        let account = AccountSharedData::from(Account {
+            lamports: 1,
+            data: FILE_MAGIC_NUMBER.to_le_bytes().to_vec(),
+            owner: Pubkey::new_unique(),
+            executable: false,
+            rent_epoch: 0,
+        });
+        let account_refs = [(&pubkey, &account)];
+        let storable_accounts = (Slot::MAX, &account_refs[..]);
+
+        let path = temp_dir
+            .path()
+            .join(AccountsFileProvider::AppendVec.file_name(0, 0));
+        let current_len = {
+            let accounts_file = ManuallyDrop::new(AccountsFileProvider::AppendVec.new_writable(
+                &path,
+                1024 * 1024,
+                StorageAccess::File,
+            ));
+            accounts_file.write_accounts(&storable_accounts, 0).unwrap();
+            accounts_file.flush().unwrap();
+            accounts_file.len()
+        };
+        let contents = fs::read(&path).unwrap();
+        assert_eq!(
+            contents[current_len - std::mem::size_of::<TieredStorageMagicNumber>()..current_len],
+            FILE_MAGIC_NUMBER.to_le_bytes(),
+        );
+
+        // The storage is still reopened as an append vec, as told by its file name
+        let accounts_file =
+            AccountsFile::new_for_startup(&path, current_len, StorageAccess::File).unwrap();
+        assert!(matches!(accounts_file, AccountsFile::AppendVec(_)));
+        let mut pubkeys = vec![];
+        accounts_file
+            .scan_pubkeys(|pubkey| pubkeys.push(*pubkey))
+            .unwrap();
+        assert_eq!(pubkeys, vec![pubkey]);
+    }
 }
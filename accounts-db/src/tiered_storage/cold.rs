@@ -0,0 +1,1224 @@
+//! The account meta and related structs for cold accounts.
+//!
+//! A cold accounts file trades read latency for a smaller footprint.  Instead
+//! of storing each account at its own aligned offset like hot storage does,
+//! consecutive accounts are packed into account blocks of roughly
+//! `COLD_ACCOUNT_BLOCK_SIZE` bytes, and each block is compressed with the
+//! AccountBlockFormat recorded in the footer.  A sparse block index maps each
+//! compressed block to its location in the file and to the first account it
+//! holds, so reading one account only requires decoding the block containing
+//! it.
+//!
+//! The layout of a cold accounts file is:
+//!
+//! * account blocks (compressed, each aligned to `COLD_BLOCK_ALIGNMENT`)
+//! * account index block (addresses, then the block of each account)
+//! * block index (one `ColdBlockEntry` per account block)
+//! * owners block
+//! * footer
+
+use {
+    crate::{
+        account_info::{AccountInfo, Offset},
+        account_storage::stored_account_info::{StoredAccountInfo, StoredAccountInfoWithoutData},
+        accounts_file::StoredAccountsInfo,
+        tiered_storage::{
+            byte_block::{self, ByteBlockReader, ByteBlockWriter},
+            file::{TieredReadableFile, TieredWritableFile},
+            footer::{AccountBlockFormat, AccountMetaFormat, TieredStorageFooter},
+            hot::RENT_EXEMPT_RENT_EPOCH,
+            index::{AccountIndexWriterEntry, AccountOffset, IndexBlockFormat, IndexOffset},
+            meta::{
+                AccountAddressRange, AccountMetaFlags, AccountMetaOptionalFields, TieredAccountMeta,
+            },
+            mmap_utils::{get_pod, get_slice},
+            owners::{OwnerOffset, OwnersBlockFormat, OwnersTable},
+            StorableAccounts, TieredStorageError, TieredStorageFormat, TieredStorageResult,
+        },
+    },
+    bytemuck_derive::{Pod, Zeroable},
+    memmap2::{Mmap, MmapOptions},
+    solana_account::{AccountSharedData, ReadableAccount, WritableAccount},
+    solana_clock::Epoch,
+    solana_pubkey::Pubkey,
+    std::{borrow::Cow, io::Write, path::Path},
+};
+
+pub const COLD_FORMAT: TieredStorageFormat = TieredStorageFormat {
+    meta_entry_size: std::mem::size_of::<ColdAccountMeta>(),
+    account_meta_format: AccountMetaFormat::Cold,
+    owners_block_format: OwnersBlockFormat::AddressesOnly,
+    index_block_format: IndexBlockFormat::AddressesThenOffsets,
+    account_block_format: AccountBlockFormat::Lz4,
+};
+
+/// The targeted size of an account block before compression.
+///
+/// Accounts are appended to the current account block until adding the next
+/// one would exceed this size.  An account whose entry alone is bigger than
+/// this size gets an account block of its own.
+pub(crate) const COLD_ACCOUNT_BLOCK_SIZE: usize = 64 * 1024;
+
+/// The byte alignment of each account entry inside a decoded account block.
+/// This allows the account meta and optional fields to be read in place.
+pub(crate) const COLD_ACCOUNT_ALIGNMENT: usize = 8;
+
+/// The alignment for the blocks inside a cold accounts file.  This allows the
+/// index block, block index and owners block to be readable under mmap.
+pub(crate) const COLD_BLOCK_ALIGNMENT: usize = 8;
+
+/// The buffer that is used for padding.
+const PADDING_BUFFER: [u8; 8] = [0u8; COLD_ACCOUNT_ALIGNMENT];
+
+/// A helper function that creates a new default footer for cold
+/// accounts storage.
+fn new_cold_footer() -> TieredStorageFooter {
+    TieredStorageFooter {
+        account_meta_format: COLD_FORMAT.account_meta_format,
+        account_meta_entry_size: COLD_FORMAT.meta_entry_size as u32,
+        account_block_format: COLD_FORMAT.account_block_format,
+        account_block_size: COLD_ACCOUNT_BLOCK_SIZE as u64,
+        index_block_format: COLD_FORMAT.index_block_format,
+        owners_block_format: COLD_FORMAT.owners_block_format,
+        ..TieredStorageFooter::default()
+    }
+}
+
+// returns the required number of padding
+fn padding_bytes(data_len: usize) -> u8 {
+    ((COLD_ACCOUNT_ALIGNMENT - (data_len % COLD_ACCOUNT_ALIGNMENT)) % COLD_ACCOUNT_ALIGNMENT) as u8
+}
+
+// returns the number of bytes needed to align `len` to COLD_BLOCK_ALIGNMENT
+fn block_padding_bytes(len: usize) -> usize {
+    (COLD_BLOCK_ALIGNMENT - (len % COLD_BLOCK_ALIGNMENT)) % COLD_BLOCK_ALIGNMENT
+}
+
+/// The offset to access a cold account, which is the position of the
+/// account block that holds the account inside the block index.
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Pod, Zeroable)]
+pub struct ColdAccountOffset(u32);
+
+// Ensure there are no implicit padding bytes
+const _: () = assert!(std::mem::size_of::<ColdAccountOffset>() == 4);
+
+impl AccountOffset for ColdAccountOffset {}
+
+impl ColdAccountOffset {
+    /// Creates a new ColdAccountOffset pointing to the specified block.
+    pub fn new(block: usize) -> TieredStorageResult<Self> {
+        if block > u32::MAX as usize {
+            return Err(TieredStorageError::OffsetOutOfBounds(
+                block,
+                u32::MAX as usize,
+            ));
+        }
+        Ok(Self(block as u32))
+    }
+
+    /// Returns the position of the account block in the block index.
+    fn block(&self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// An entry of the block index, which describes one compressed account block.
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Pod, Zeroable)]
+pub struct ColdBlockEntry {
+    /// The offset of the first byte of the encoded account block.
+    pub offset: u64,
+    /// The size of the account block after encoding.
+    pub encoded_size: u32,
+    /// The size of the account block after decoding.
+    pub decoded_size: u32,
+    /// The index of the first account inside this account block.
+    pub first_index: u32,
+    /// The number of accounts inside this account block.
+    pub num_accounts: u32,
+}
+
+// Ensure there are no implicit padding bytes
+const _: () = assert!(std::mem::size_of::<ColdBlockEntry>() == 8 + 4 + 4 + 4 + 4);
+
+/// The storage and in-memory representation of the metadata entry for a
+/// cold account.
+///
+/// Unlike HotAccountMeta, the data size is persisted explicitly as multiple
+/// cold accounts share the same account block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+#[repr(C)]
+pub struct ColdAccountMeta {
+    /// The balance of this account.
+    lamports: u64,
+    /// The size of the data of this account.
+    account_data_size: u64,
+    /// The index to the owner of this account inside an AccountsFile.
+    owner_offset: u32,
+    /// Stores boolean flags and existence of each optional field.
+    flags: AccountMetaFlags,
+}
+
+// Ensure there are no implicit padding bytes
+const _: () = assert!(std::mem::size_of::<ColdAccountMeta>() == 8 + 8 + 4 + 4);
+
+impl ColdAccountMeta {
+    /// Returns the size of the account entry, including this meta, the
+    /// account data, its padding and the optional fields.
+    fn entry_size(&self) -> usize {
+        // saturating, as the data size is read from a possibly corrupted block
+        std::mem::size_of::<Self>()
+            .saturating_add(self.account_data_size as usize)
+            .saturating_add(self.account_data_padding() as usize)
+            .saturating_add(AccountMetaOptionalFields::size_from_flags(&self.flags))
+    }
+}
+
+impl TieredAccountMeta for ColdAccountMeta {
+    /// Construct a ColdAccountMeta instance.
+    fn new() -> Self {
+        ColdAccountMeta {
+            lamports: 0,
+            account_data_size: 0,
+            owner_offset: 0,
+            flags: AccountMetaFlags::new(),
+        }
+    }
+
+    /// A builder function that initializes lamports.
+    fn with_lamports(mut self, lamports: u64) -> Self {
+        self.lamports = lamports;
+        self
+    }
+
+    /// A builder function that initializes the number of padding bytes
+    /// for the account data associated with the current meta.
+    fn with_account_data_padding(self, _padding: u8) -> Self {
+        // Cold meta does not store its padding as it derives the padding
+        // from its persisted account data size.
+        self
+    }
+
+    /// A builder function that initializes the owner's index.
+    fn with_owner_offset(mut self, owner_offset: OwnerOffset) -> Self {
+        self.owner_offset = owner_offset.0;
+        self
+    }
+
+    /// A builder function that initializes the account data size.
+    fn with_account_data_size(mut self, account_data_size: u64) -> Self {
+        self.account_data_size = account_data_size;
+        self
+    }
+
+    /// A builder function that initializes the AccountMetaFlags of the current
+    /// meta.
+    fn with_flags(mut self, flags: &AccountMetaFlags) -> Self {
+        self.flags = *flags;
+        self
+    }
+
+    /// Returns the balance of the lamports associated with the account.
+    fn lamports(&self) -> u64 {
+        self.lamports
+    }
+
+    /// Returns the number of padding bytes for the associated account data
+    fn account_data_padding(&self) -> u8 {
+        padding_bytes(self.account_data_size as usize)
+    }
+
+    /// Returns the index to the accounts' owner in the current AccountsFile.
+    fn owner_offset(&self) -> OwnerOffset {
+        OwnerOffset(self.owner_offset)
+    }
+
+    /// Returns the AccountMetaFlags of the current meta.
+    fn flags(&self) -> &AccountMetaFlags {
+        &self.flags
+    }
+
+    /// Always returns true as multiple ColdAccountMeta entries share the
+    /// same account block.
+    fn supports_shared_account_block() -> bool {
+        true
+    }
+
+    /// Returns the epoch that this account will next owe rent by parsing
+    /// the specified account block.  None will be returned if this account
+    /// does not persist this optional field.
+    fn rent_epoch(&self, account_block: &[u8]) -> Option<Epoch> {
+        self.flags()
+            .has_rent_epoch()
+            .then(|| {
+                let offset = self.optional_fields_offset(account_block)
+                    + AccountMetaOptionalFields::rent_epoch_offset(self.flags());
+                byte_block::read_pod::<Epoch>(account_block, offset).copied()
+            })
+            .flatten()
+    }
+
+    /// Returns the epoch that this account will next owe rent by parsing
+    /// the specified account block.  RENT_EXEMPT_RENT_EPOCH will be returned
+    /// if the account is rent-exempt.
+    ///
+    /// For a zero-lamport account, Epoch::default() will be returned to
+    /// default states of an AccountSharedData.
+    fn final_rent_epoch(&self, account_block: &[u8]) -> Epoch {
+        self.rent_epoch(account_block)
+            .unwrap_or(if self.lamports() != 0 {
+                RENT_EXEMPT_RENT_EPOCH
+            } else {
+                Epoch::default()
+            })
+    }
+
+    /// Returns the offset of the optional fields based on the specified account
+    /// block.
+    fn optional_fields_offset(&self, _account_block: &[u8]) -> usize {
+        self.account_data_size as usize + self.account_data_padding() as usize
+    }
+
+    /// Returns the length of the data associated to this account based on the
+    /// specified account block.
+    fn account_data_size(&self, _account_block: &[u8]) -> usize {
+        self.account_data_size as usize
+    }
+
+    /// Returns the data associated to this account based on the specified
+    /// account block.
+    fn account_data<'a>(&self, account_block: &'a [u8]) -> &'a [u8] {
+        &account_block[..self.account_data_size(account_block)]
+    }
+}
+
+/// Iterates over the account entries of a decoded account block.
+///
+/// Each item consists of the meta of the account and its account block, which
+/// is the part of the decoded block that follows the meta and ends with the
+/// account's optional fields.
+struct ColdAccountEntries<'a> {
+    decoded_block: &'a [u8],
+    cursor: usize,
+}
+
+impl<'a> ColdAccountEntries<'a> {
+    fn new(decoded_block: &'a [u8]) -> Self {
+        Self {
+            decoded_block,
+            cursor: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for ColdAccountEntries<'a> {
+    type Item = TieredStorageResult<(&'a ColdAccountMeta, &'a [u8])>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.cursor >= self.decoded_block.len() {
+            return None;
+        }
+        let Some(meta) = byte_block::read_pod::<ColdAccountMeta>(self.decoded_block, self.cursor)
+        else {
+            return Some(Err(TieredStorageError::InvalidColdAccountBlock(
+                self.cursor,
+            )));
+        };
+        let account_block_start = self.cursor + std::mem::size_of::<ColdAccountMeta>();
+        let entry_end = self.cursor.saturating_add(meta.entry_size());
+        if entry_end > self.decoded_block.len() {
+            return Some(Err(TieredStorageError::InvalidColdAccountBlock(
+                self.cursor,
+            )));
+        }
+        self.cursor = entry_end;
+        Some(Ok((
+            meta,
+            &self.decoded_block[account_block_start..entry_end],
+        )))
+    }
+}
+
+/// The reader to a cold accounts file.
+#[derive(Debug)]
+pub struct ColdStorageReader {
+    mmap: Mmap,
+    footer: TieredStorageFooter,
+}
+
+impl ColdStorageReader {
+    pub fn new(file: TieredReadableFile) -> TieredStorageResult<Self> {
+        let mmap = unsafe { MmapOptions::new().map(&file.0)? };
+        // Here we are copying the footer, as accessing any data in a
+        // TieredStorage instance requires accessing its Footer.
+        let footer = *TieredStorageFooter::new_from_mmap(&mmap)?;
+
+        Ok(Self { mmap, footer })
+    }
+
+    /// Returns the size of the underlying storage.
+    pub fn len(&self) -> usize {
+        self.mmap.len()
+    }
+
+    /// Returns whether the underlying storage is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> u64 {
+        self.len() as u64
+    }
+
+    /// Returns the footer of the underlying tiered-storage accounts file.
+    pub fn footer(&self) -> &TieredStorageFooter {
+        &self.footer
+    }
+
+    /// Returns the number of accounts inside the underlying tiered-storage
+    /// accounts file.
+    pub fn num_accounts(&self) -> usize {
+        self.footer.account_entry_count as usize
+    }
+
+    /// Returns the offset of the block index, which immediately follows the
+    /// (aligned) account index block.
+    fn block_index_offset(&self) -> usize {
+        let index_block_end = self.footer.index_block_offset as usize
+            + self
+                .footer
+                .index_block_format
+                .entry_size::<ColdAccountOffset>()
+                * self.num_accounts();
+        index_block_end + block_padding_bytes(index_block_end)
+    }
+
+    /// Returns the number of account blocks inside the accounts file.
+    pub fn num_blocks(&self) -> usize {
+        (self.footer.owners_block_offset as usize).saturating_sub(self.block_index_offset())
+            / std::mem::size_of::<ColdBlockEntry>()
+    }
+
+    /// Returns the block index entry of the specified account block.
+    fn get_block_entry(&self, block: usize) -> TieredStorageResult<&ColdBlockEntry> {
+        let offset = self.block_index_offset() + std::mem::size_of::<ColdBlockEntry>() * block;
+        debug_assert!(
+            offset.saturating_add(std::mem::size_of::<ColdBlockEntry>())
+                <= self.footer.owners_block_offset as usize,
+            "reading block ({block}) would exceed block index boundary ({}).",
+            self.footer.owners_block_offset,
+        );
+        let (entry, _) = get_pod::<ColdBlockEntry>(&self.mmap, offset)?;
+        Ok(entry)
+    }
+
+    /// Returns the decoded account block described by the specified entry.
+    fn decode_block(&self, entry: &ColdBlockEntry) -> TieredStorageResult<Cow<'_, [u8]>> {
+        let (encoded, _) = get_slice(
+            &self.mmap,
+            entry.offset as usize,
+            entry.encoded_size as usize,
+        )?;
+        match self.footer.account_block_format {
+            AccountBlockFormat::AlignedRaw => Ok(Cow::Borrowed(encoded)),
+            format => {
+                let decoded = ByteBlockReader::decode(format, encoded)?;
+                if decoded.len() != entry.decoded_size as usize {
+                    return Err(TieredStorageError::InvalidColdAccountBlock(
+                        entry.offset as usize,
+                    ));
+                }
+                Ok(Cow::Owned(decoded))
+            }
+        }
+    }
+
+    /// Returns the offset to the account given the specified index.
+    pub(super) fn get_account_offset(
+        &self,
+        index_offset: IndexOffset,
+    ) -> TieredStorageResult<ColdAccountOffset> {
+        self.footer
+            .index_block_format
+            .get_account_offset::<ColdAccountOffset>(&self.mmap, &self.footer, index_offset)
+    }
+
+    /// Returns the address of the account associated with the specified index.
+    fn get_account_address(&self, index: IndexOffset) -> TieredStorageResult<&Pubkey> {
+        self.footer
+            .index_block_format
+            .get_account_address(&self.mmap, &self.footer, index)
+    }
+
+    /// Returns the address of the account owner given the specified
+    /// owner_offset.
+    fn get_owner_address(&self, owner_offset: OwnerOffset) -> TieredStorageResult<&Pubkey> {
+        self.footer
+            .owners_block_format
+            .get_owner_address(&self.mmap, &self.footer, owner_offset)
+    }
+
+    /// Decodes the account block holding the account associated with the
+    /// specified index, and calls `callback` with the account's meta and
+    /// its account block.
+    fn with_account_entry<Ret>(
+        &self,
+        index_offset: IndexOffset,
+        callback: impl FnOnce(&ColdAccountMeta, &[u8]) -> TieredStorageResult<Ret>,
+    ) -> TieredStorageResult<Ret> {
+        let block = self.get_account_offset(index_offset)?.block();
+        let entry = self.get_block_entry(block)?;
+        let position = index_offset
+            .0
+            .checked_sub(entry.first_index)
+            .filter(|position| *position < entry.num_accounts)
+            .ok_or(TieredStorageError::InvalidColdAccountBlock(
+                entry.offset as usize,
+            ))?;
+        let decoded_block = self.decode_block(entry)?;
+        let (meta, account_block) = ColdAccountEntries::new(&decoded_block)
+            .nth(position as usize)
+            .ok_or(TieredStorageError::InvalidColdAccountBlock(
+                entry.offset as usize,
+            ))??;
+        callback(meta, account_block)
+    }
+
+    /// Calls `callback` with the stored account at `offset`.
+    ///
+    /// Returns `None` if there is no account at `offset`, otherwise returns the result of
+    /// `callback` in `Some`.
+    ///
+    /// This fn does *not* load the account's data, just the data length.  If the data is needed,
+    /// use `get_stored_account_callback()` instead.  However, prefer this fn when possible.
+    pub fn get_stored_account_without_data_callback<Ret>(
+        &self,
+        index_offset: IndexOffset,
+        mut callback: impl for<'local> FnMut(StoredAccountInfoWithoutData<'local>) -> Ret,
+    ) -> TieredStorageResult<Option<Ret>> {
+        if index_offset.0 >= self.footer.account_entry_count {
+            return Ok(None);
+        }
+
+        self.with_account_entry(index_offset, |meta, account_block| {
+            let stored_account = StoredAccountInfoWithoutData {
+                pubkey: self.get_account_address(index_offset)?,
+                lamports: meta.lamports(),
+                owner: self.get_owner_address(meta.owner_offset())?,
+                data_len: meta.account_data_size(account_block),
+                executable: meta.flags().executable(),
+                rent_epoch: meta.final_rent_epoch(account_block),
+            };
+            Ok(Some(callback(stored_account)))
+        })
+    }
+
+    /// Calls `callback` with the stored account at `offset`.
+    ///
+    /// Returns `None` if there is no account at `offset`, otherwise returns the result of
+    /// `callback` in `Some`.
+    ///
+    /// This fn *does* load the account's data.  If the data is not needed,
+    /// use `get_stored_account_without_data_callback()` instead.
+    pub fn get_stored_account_callback<Ret>(
+        &self,
+        index_offset: IndexOffset,
+        mut callback: impl for<'local> FnMut(StoredAccountInfo<'local>) -> Ret,
+    ) -> TieredStorageResult<Option<Ret>> {
+        if index_offset.0 >= self.footer.account_entry_count {
+            return Ok(None);
+        }
+
+        self.with_account_entry(index_offset, |meta, account_block| {
+            let stored_account = StoredAccountInfo {
+                pubkey: self.get_account_address(index_offset)?,
+                lamports: meta.lamports(),
+                owner: self.get_owner_address(meta.owner_offset())?,
+                data: meta.account_data(account_block),
+                executable: meta.flags().executable(),
+                rent_epoch: meta.final_rent_epoch(account_block),
+            };
+            Ok(Some(callback(stored_account)))
+        })
+    }
+
+    /// Returns the account located at the specified index offset.
+    pub fn get_account_shared_data(
+        &self,
+        index_offset: IndexOffset,
+    ) -> TieredStorageResult<Option<AccountSharedData>> {
+        if index_offset.0 >= self.footer.account_entry_count {
+            return Ok(None);
+        }
+
+        self.with_account_entry(index_offset, |meta, account_block| {
+            let lamports = meta.lamports();
+            let data = meta.account_data(account_block).to_vec();
+            let owner = *self.get_owner_address(meta.owner_offset())?;
+            let executable = meta.flags().executable();
+            let rent_epoch = meta.final_rent_epoch(account_block);
+            Ok(Some(AccountSharedData::create(
+                lamports, data, owner, executable, rent_epoch,
+            )))
+        })
+    }
+
+    /// iterate over all pubkeys
+    pub fn scan_pubkeys(&self, mut callback: impl FnMut(&Pubkey)) -> TieredStorageResult<()> {
+        for i in 0..self.footer.account_entry_count {
+            let address = self.get_account_address(IndexOffset(i))?;
+            callback(address);
+        }
+        Ok(())
+    }
+
+    /// Calculate the amount of storage required for an account with the passed
+    /// in data_len
+    pub(crate) fn calculate_stored_size(data_len: usize) -> usize {
+        stored_size(data_len)
+    }
+
+    /// for each offset in `sorted_offsets`, return the length of data stored in the account
+    pub(crate) fn get_account_data_lens(
+        &self,
+        sorted_offsets: &[usize],
+    ) -> TieredStorageResult<Vec<usize>> {
+        let mut result = Vec::with_capacity(sorted_offsets.len());
+        // As the offsets are sorted, accounts sharing the same account block
+        // are adjacent, which allows each account block to be decoded once.
+        let mut current_block: Option<(usize, Vec<usize>)> = None;
+        for &offset in sorted_offsets {
+            let index_offset = IndexOffset(AccountInfo::get_reduced_offset(offset));
+            let block = self.get_account_offset(index_offset)?.block();
+            let entry = self.get_block_entry(block)?;
+            if current_block.as_ref().map(|(b, _)| *b) != Some(block) {
+                let decoded_block = self.decode_block(entry)?;
+                let data_lens = ColdAccountEntries::new(&decoded_block)
+                    .map(|item| item.map(|(meta, _)| meta.account_data_size as usize))
+                    .collect::<TieredStorageResult<Vec<_>>>()?;
+                current_block = Some((block, data_lens));
+            }
+            let (_, data_lens) = current_block.as_ref().unwrap();
+            let data_len = index_offset
+                .0
+                .checked_sub(entry.first_index)
+                .and_then(|position| data_lens.get(position as usize))
+                .ok_or(TieredStorageError::InvalidColdAccountBlock(
+                    entry.offset as usize,
+                ))?;
+            result.push(*data_len);
+        }
+        Ok(result)
+    }
+
+    /// Decodes each account block once and calls `callback` with the meta,
+    /// account block and index of every account inside it.
+    fn scan_account_entries(
+        &self,
+        mut callback: impl FnMut(IndexOffset, &ColdAccountMeta, &[u8]) -> TieredStorageResult<()>,
+    ) -> TieredStorageResult<()> {
+        for block in 0..self.num_blocks() {
+            let entry = self.get_block_entry(block)?;
+            let decoded_block = self.decode_block(entry)?;
+            let mut num_accounts = 0;
+            for item in ColdAccountEntries::new(&decoded_block) {
+                let (meta, account_block) = item?;
+                callback(
+                    IndexOffset(entry.first_index + num_accounts),
+                    meta,
+                    account_block,
+                )?;
+                num_accounts += 1;
+            }
+            if num_accounts != entry.num_accounts {
+                return Err(TieredStorageError::InvalidColdAccountBlock(
+                    entry.offset as usize,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Iterate over all accounts and call `callback` with each account.
+    ///
+    /// `callback` parameters:
+    /// * Offset: the offset within the file of this account
+    /// * StoredAccountInfoWithoutData: the account itself, without account data
+    ///
+    /// Note that account data is not read/passed to the callback.
+    pub fn scan_accounts_without_data(
+        &self,
+        mut callback: impl for<'local> FnMut(Offset, StoredAccountInfoWithoutData<'local>),
+    ) -> TieredStorageResult<()> {
+        self.scan_account_entries(|index_offset, meta, account_block| {
+            let stored_account = StoredAccountInfoWithoutData {
+                pubkey: self.get_account_address(index_offset)?,
+                lamports: meta.lamports(),
+                owner: self.get_owner_address(meta.owner_offset())?,
+                data_len: meta.account_data_size(account_block),
+                executable: meta.flags().executable(),
+                rent_epoch: meta.final_rent_epoch(account_block),
+            };
+            callback(
+                AccountInfo::reduced_offset_to_offset(index_offset.0),
+                stored_account,
+            );
+            Ok(())
+        })
+    }
+
+    /// Iterate over all accounts and call `callback` with each account.
+    ///
+    /// `callback` parameters:
+    /// * Offset: the offset within the file of this account
+    /// * StoredAccountInfo: the account itself, with account data
+    ///
+    /// Prefer scan_accounts_without_data() when account data is not needed,
+    /// as it can potentially read less and be faster.
+    pub fn scan_accounts(
+        &self,
+        mut callback: impl for<'local> FnMut(Offset, StoredAccountInfo<'local>),
+    ) -> TieredStorageResult<()> {
+        self.scan_account_entries(|index_offset, meta, account_block| {
+            let stored_account = StoredAccountInfo {
+                pubkey: self.get_account_address(index_offset)?,
+                lamports: meta.lamports(),
+                owner: self.get_owner_address(meta.owner_offset())?,
+                data: meta.account_data(account_block),
+                executable: meta.flags().executable(),
+                rent_epoch: meta.final_rent_epoch(account_block),
+            };
+            callback(
+                AccountInfo::reduced_offset_to_offset(index_offset.0),
+                stored_account,
+            );
+            Ok(())
+        })
+    }
+
+    /// Returns a slice suitable for use when archiving cold storages
+    pub fn data_for_archive(&self) -> &[u8] {
+        self.mmap.as_ref()
+    }
+}
+
+/// return an approximation of the cost to store an account.
+/// Some fields like owner are shared across multiple accounts.
+///
+/// Note that this does not take compression into account, as the actual
+/// stored size of an account depends on the other accounts in its block.
+fn stored_size(data_len: usize) -> usize {
+    data_len + std::mem::size_of::<Pubkey>()
+}
+
+/// The in-progress account block of a ColdStorageWriter.
+#[derive(Debug)]
+struct ColdBlockWriter {
+    writer: ByteBlockWriter,
+    first_index: u32,
+    num_accounts: u32,
+}
+
+/// The writer that creates a cold accounts file.
+#[derive(Debug)]
+pub struct ColdStorageWriter {
+    storage: TieredWritableFile,
+    format: AccountBlockFormat,
+}
+
+impl ColdStorageWriter {
+    /// Create a new ColdStorageWriter with the specified path.
+    pub fn new(file_path: impl AsRef<Path>) -> TieredStorageResult<Self> {
+        Ok(Self {
+            storage: TieredWritableFile::new(file_path)?,
+            format: COLD_FORMAT.account_block_format,
+        })
+    }
+
+    /// Appends an account entry with the specified information to `block`
+    /// and returns the size of the entry before encoding.
+    fn write_account(
+        block: &mut ByteBlockWriter,
+        lamports: u64,
+        owner_offset: OwnerOffset,
+        account_data: &[u8],
+        executable: bool,
+        rent_epoch: Option<Epoch>,
+    ) -> TieredStorageResult<usize> {
+        let optional_fields = AccountMetaOptionalFields { rent_epoch };
+
+        let mut flags = AccountMetaFlags::new_from(&optional_fields);
+        flags.set_executable(executable);
+
+        let padding_len = padding_bytes(account_data.len());
+        let meta = ColdAccountMeta::new()
+            .with_lamports(lamports)
+            .with_owner_offset(owner_offset)
+            .with_account_data_size(account_data.len() as u64)
+            .with_account_data_padding(padding_len)
+            .with_flags(&flags);
+
+        let mut entry_size = 0;
+        entry_size += block.write_pod(&meta)?;
+        block.write(account_data)?;
+        block.write(&PADDING_BUFFER[0..(padding_len as usize)])?;
+        entry_size += account_data.len() + padding_len as usize;
+        entry_size += block.write_optional_fields(&optional_fields)?;
+
+        debug_assert_eq!(entry_size, meta.entry_size());
+        Ok(entry_size)
+    }
+
+    /// Encodes the specified account block, persists it at the current
+    /// cursor, and returns its block index entry together with the number of
+    /// bytes written (including the padding that aligns the next block).
+    fn write_block(
+        &mut self,
+        block: ColdBlockWriter,
+        cursor: usize,
+    ) -> TieredStorageResult<(ColdBlockEntry, usize)> {
+        let decoded_size = block.writer.raw_len();
+        let encoded = block.writer.finish()?;
+        let mut bytes_written = self.storage.write_bytes(&encoded)?;
+        bytes_written += self
+            .storage
+            .write_bytes(&PADDING_BUFFER[0..block_padding_bytes(encoded.len())])?;
+
+        let entry = ColdBlockEntry {
+            offset: cursor as u64,
+            encoded_size: encoded.len() as u32,
+            decoded_size: decoded_size as u32,
+            first_index: block.first_index,
+            num_accounts: block.num_accounts,
+        };
+        Ok((entry, bytes_written))
+    }
+
+    /// Persists `accounts` into the underlying cold accounts file associated
+    /// with this ColdStorageWriter.  The first `skip` number of accounts are
+    /// *not* persisted.
+    pub fn write_accounts<'a>(
+        &mut self,
+        accounts: &impl StorableAccounts<'a>,
+        skip: usize,
+    ) -> TieredStorageResult<StoredAccountsInfo> {
+        let mut footer = new_cold_footer();
+        footer.account_block_format = self.format;
+        let mut index = vec![];
+        let mut block_entries = vec![];
+        let mut owners_table = OwnersTable::default();
+        let mut cursor = 0;
+        let mut address_range = AccountAddressRange::default();
+        let mut current_block: Option<ColdBlockWriter> = None;
+
+        let len = accounts.len();
+        let total_input_accounts = len.saturating_sub(skip);
+        let mut offsets = Vec::with_capacity(total_input_accounts);
+
+        // writing accounts blocks
+        for i in skip..len {
+            accounts.account_default_if_zero_lamport::<TieredStorageResult<()>>(i, |account| {
+                address_range.update(account.pubkey());
+
+                // Obtain necessary fields from the account, or default fields
+                // for a zero-lamport account in the None case.
+                let (lamports, owner, data, executable, rent_epoch) = {
+                    (
+                        account.lamports(),
+                        account.owner(),
+                        account.data(),
+                        account.executable(),
+                        // only persist rent_epoch for those rent-paying accounts
+                        (account.rent_epoch() != RENT_EXEMPT_RENT_EPOCH)
+                            .then_some(account.rent_epoch()),
+                    )
+                };
+
+                // Start a new account block if the current one cannot hold
+                // this account.  A blob account that is bigger than the block
+                // size will end up with an account block of its own.
+                let entry_size = std::mem::size_of::<ColdAccountMeta>()
+                    + data.len()
+                    + padding_bytes(data.len()) as usize
+                    + AccountMetaOptionalFields { rent_epoch }.size();
+                if current_block.as_ref().is_some_and(|block| {
+                    block.writer.raw_len() + entry_size > COLD_ACCOUNT_BLOCK_SIZE
+                }) {
+                    let (entry, bytes_written) =
+                        self.write_block(current_block.take().unwrap(), cursor)?;
+                    block_entries.push(entry);
+                    cursor += bytes_written;
+                }
+                let block = current_block.get_or_insert_with(|| ColdBlockWriter {
+                    writer: ByteBlockWriter::new(self.format),
+                    first_index: index.len() as u32,
+                    num_accounts: 0,
+                });
+
+                let owner_offset = owners_table.insert(owner);
+                Self::write_account(
+                    &mut block.writer,
+                    lamports,
+                    owner_offset,
+                    data,
+                    executable,
+                    rent_epoch,
+                )?;
+                block.num_accounts += 1;
+
+                offsets.push(index.len());
+                index.push(AccountIndexWriterEntry {
+                    address: *account.pubkey(),
+                    offset: ColdAccountOffset::new(block_entries.len())?,
+                });
+                Ok(())
+            })?;
+        }
+        if let Some(block) = current_block.take() {
+            let (entry, bytes_written) = self.write_block(block, cursor)?;
+            block_entries.push(entry);
+            cursor += bytes_written;
+        }
+        footer.account_entry_count = total_input_accounts as u32;
+
+        // writing index block
+        // expect the offset of each block aligned.
+        assert!(cursor % COLD_BLOCK_ALIGNMENT == 0);
+        footer.index_block_offset = cursor as u64;
+        cursor += footer
+            .index_block_format
+            .write_index_block(&mut self.storage, &index)?;
+        cursor += self
+            .storage
+            .write_bytes(&PADDING_BUFFER[0..block_padding_bytes(cursor)])?;
+
+        // writing block index
+        assert!(cursor % COLD_BLOCK_ALIGNMENT == 0);
+        for entry in &block_entries {
+            cursor += self.storage.write_pod(entry)?;
+        }
+
+        // writing owners block
+        assert!(cursor % COLD_BLOCK_ALIGNMENT == 0);
+        footer.owners_block_offset = cursor as u64;
+        footer.owner_count = owners_table.len() as u32;
+        cursor += footer
+            .owners_block_format
+            .write_owners_block(&mut self.storage, &owners_table)?;
+
+        // writing footer
+        footer.min_account_address = address_range.min;
+        footer.max_account_address = address_range.max;
+        cursor += footer.write_footer_block(&mut self.storage)?;
+
+        Ok(StoredAccountsInfo {
+            offsets,
+            size: cursor,
+        })
+    }
+
+    /// Flushes any buffered data to the file
+    pub fn flush(&mut self) -> TieredStorageResult<()> {
+        self.storage
+            .0
+            .flush()
+            .map_err(TieredStorageError::FlushColdWriter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::tiered_storage::test_utils::{create_test_account, verify_test_account},
+        assert_matches::assert_matches,
+        memoffset::offset_of,
+        solana_clock::Slot,
+        tempfile::TempDir,
+    };
+
+    /// Writes the accounts with the specified data sizes into a new cold
+    /// accounts file, and returns the reader, the accounts, and the result
+    /// of the write.
+    fn write_test_file(
+        temp_dir: &TempDir,
+        account_data_sizes: &[u64],
+        format: AccountBlockFormat,
+    ) -> (
+        ColdStorageReader,
+        Vec<(Pubkey, AccountSharedData)>,
+        StoredAccountsInfo,
+    ) {
+        let accounts: Vec<_> = account_data_sizes
+            .iter()
+            .map(|size| create_test_account(*size))
+            .map(|(stored_meta, account)| (stored_meta.pubkey, account))
+            .collect();
+        let account_refs: Vec<_> = accounts
+            .iter()
+            .map(|(address, account)| (address, account))
+            .collect();
+        // Slot information is not used here
+        let storable_accounts = (Slot::MAX, &account_refs[..]);
+
+        let path = temp_dir.path().join("test_cold_storage");
+        let stored_accounts_info = {
+            let mut writer = ColdStorageWriter::new(&path).unwrap();
+            writer.format = format;
+            let stored_accounts_info = writer.write_accounts(&storable_accounts, 0).unwrap();
+            writer.flush().unwrap();
+            stored_accounts_info
+        };
+
+        let file = TieredReadableFile::new(&path).unwrap();
+        let reader = ColdStorageReader::new(file).unwrap();
+        let expected_accounts = accounts
+            .into_iter()
+            .map(|(address, account)| {
+                // zero-lamport accounts are persisted with default fields
+                if account.lamports() == 0 {
+                    (address, AccountSharedData::default())
+                } else {
+                    (address, account)
+                }
+            })
+            .collect();
+        (reader, expected_accounts, stored_accounts_info)
+    }
+
+    #[test]
+    fn test_cold_account_meta_layout() {
+        assert_eq!(offset_of!(ColdAccountMeta, lamports), 0x00);
+        assert_eq!(offset_of!(ColdAccountMeta, account_data_size), 0x08);
+        assert_eq!(offset_of!(ColdAccountMeta, owner_offset), 0x10);
+        assert_eq!(offset_of!(ColdAccountMeta, flags), 0x14);
+        assert_eq!(std::mem::size_of::<ColdAccountMeta>(), 24);
+    }
+
+    #[test]
+    fn test_cold_block_entry_layout() {
+        assert_eq!(offset_of!(ColdBlockEntry, offset), 0x00);
+        assert_eq!(offset_of!(ColdBlockEntry, encoded_size), 0x08);
+        assert_eq!(offset_of!(ColdBlockEntry, decoded_size), 0x0C);
+        assert_eq!(offset_of!(ColdBlockEntry, first_index), 0x10);
+        assert_eq!(offset_of!(ColdBlockEntry, num_accounts), 0x14);
+        assert_eq!(std::mem::size_of::<ColdBlockEntry>(), 24);
+    }
+
+    #[test]
+    fn test_cold_account_meta() {
+        const TEST_LAMPORTS: u64 = 2314232137;
+        const TEST_DATA_SIZE: u64 = 83;
+        const TEST_OWNER_OFFSET: OwnerOffset = OwnerOffset(0x1fef_1234);
+        const TEST_RENT_EPOCH: Epoch = 7;
+
+        let optional_fields = AccountMetaOptionalFields {
+            rent_epoch: Some(TEST_RENT_EPOCH),
+        };
+        let flags = AccountMetaFlags::new_from(&optional_fields);
+        let meta = ColdAccountMeta::new()
+            .with_lamports(TEST_LAMPORTS)
+            .with_account_data_size(TEST_DATA_SIZE)
+            .with_owner_offset(TEST_OWNER_OFFSET)
+            .with_flags(&flags);
+
+        assert_eq!(meta.lamports(), TEST_LAMPORTS);
+        assert_eq!(meta.account_data_padding(), 5);
+        assert_eq!(meta.owner_offset(), TEST_OWNER_OFFSET);
+        assert_eq!(*meta.flags(), flags);
+        assert_eq!(
+            meta.entry_size(),
+            std::mem::size_of::<ColdAccountMeta>() + 83 + 5 + std::mem::size_of::<Epoch>()
+        );
+
+        let mut writer = ByteBlockWriter::new(AccountBlockFormat::AlignedRaw);
+        let entry_size = ColdStorageWriter::write_account(
+            &mut writer,
+            TEST_LAMPORTS,
+            TEST_OWNER_OFFSET,
+            &[11u8; TEST_DATA_SIZE as usize],
+            false,
+            Some(TEST_RENT_EPOCH),
+        )
+        .unwrap();
+        let buffer = writer.finish().unwrap();
+        assert_eq!(entry_size, buffer.len());
+
+        let mut entries = ColdAccountEntries::new(&buffer);
+        let (read_meta, account_block) = entries.next().unwrap().unwrap();
+        assert_eq!(read_meta, &meta);
+        assert_eq!(read_meta.account_data(account_block), [11u8; 83]);
+        assert_eq!(read_meta.rent_epoch(account_block), Some(TEST_RENT_EPOCH));
+        assert!(entries.next().is_none());
+    }
+
+    #[test]
+    fn test_cold_account_entries_truncated() {
+        let mut writer = ByteBlockWriter::new(AccountBlockFormat::AlignedRaw);
+        ColdStorageWriter::write_account(&mut writer, 1, OwnerOffset(0), &[7u8; 40], false, None)
+            .unwrap();
+        let buffer = writer.finish().unwrap();
+
+        let mut entries = ColdAccountEntries::new(&buffer[..buffer.len() - 8]);
+        assert_matches!(
+            entries.next(),
+            Some(Err(TieredStorageError::InvalidColdAccountBlock(0)))
+        );
+    }
+
+    fn do_test_write_and_read_accounts(format: AccountBlockFormat) {
+        let temp_dir = TempDir::new().unwrap();
+        // Mix small accounts with blob accounts bigger than one account block.
+        let account_data_sizes: Vec<u64> = (0..500)
+            .map(|i| match i % 100 {
+                0 => 0,
+                50 => COLD_ACCOUNT_BLOCK_SIZE as u64 + 1000,
+                _ => i % 300,
+            })
+            .collect();
+        let (reader, accounts, stored_accounts_info) =
+            write_test_file(&temp_dir, &account_data_sizes, format);
+
+        assert_eq!(reader.num_accounts(), accounts.len());
+        assert!(reader.num_blocks() > 1);
+        assert!(reader.num_blocks() < accounts.len());
+        assert_eq!(reader.footer().account_block_format, format);
+        assert_eq!(reader.len(), stored_accounts_info.size);
+
+        for (i, (address, account)) in accounts.iter().enumerate() {
+            let index_offset = IndexOffset(stored_accounts_info.offsets[i] as u32);
+            reader
+                .get_stored_account_callback(index_offset, |stored_account| {
+                    verify_test_account(&stored_account, account, address);
+                })
+                .unwrap()
+                .unwrap();
+            reader
+                .get_stored_account_without_data_callback(index_offset, |stored_account| {
+                    assert_eq!(stored_account.pubkey(), address);
+                    assert_eq!(stored_account.data_len, account.data().len());
+                    assert_eq!(stored_account.lamports, account.lamports());
+                })
+                .unwrap()
+                .unwrap();
+            let account_shared_data = reader
+                .get_account_shared_data(index_offset)
+                .unwrap()
+                .unwrap();
+            assert_eq!(&account_shared_data, account);
+        }
+        // Make sure it returns None on num_accounts to allow termination on
+        // while loop in actual accounts-db read case.
+        assert_matches!(
+            reader.get_account_shared_data(IndexOffset(accounts.len() as u32)),
+            Ok(None)
+        );
+
+        // verify scans visit every account in order
+        let mut i = 0;
+        reader
+            .scan_accounts(|offset, stored_account| {
+                assert_eq!(offset, AccountInfo::reduced_offset_to_offset(i as u32));
+                verify_test_account(&stored_account, &accounts[i].1, &accounts[i].0);
+                i += 1;
+            })
+            .unwrap();
+        assert_eq!(i, accounts.len());
+
+        let mut i = 0;
+        reader
+            .scan_accounts_without_data(|_offset, stored_account| {
+                assert_eq!(stored_account.pubkey(), &accounts[i].0);
+                assert_eq!(stored_account.data_len, accounts[i].1.data().len());
+                i += 1;
+            })
+            .unwrap();
+        assert_eq!(i, accounts.len());
+
+        let mut pubkeys = vec![];
+        reader.scan_pubkeys(|pubkey| pubkeys.push(*pubkey)).unwrap();
+        assert!(pubkeys
+            .iter()
+            .zip(accounts.iter())
+            .all(|(pubkey, (address, _))| pubkey == address));
+
+        let sorted_offsets: Vec<_> = (0..accounts.len())
+            .map(|i| AccountInfo::reduced_offset_to_offset(i as u32))
+            .collect();
+        let data_lens = reader.get_account_data_lens(&sorted_offsets).unwrap();
+        assert!(data_lens
+            .iter()
+            .zip(accounts.iter())
+            .all(|(data_len, (_, account))| *data_len == account.data().len()));
+    }
+
+    #[test]
+    fn test_write_and_read_accounts_lz4() {
+        do_test_write_and_read_accounts(AccountBlockFormat::Lz4);
+    }
+
+    #[test]
+    fn test_write_and_read_accounts_raw() {
+        do_test_write_and_read_accounts(AccountBlockFormat::AlignedRaw);
+    }
+
+    #[test]
+    fn test_cold_storage_is_smaller_than_hot_storage() {
+        use crate::tiered_storage::hot::HotStorageWriter;
+
+        let temp_dir = TempDir::new().unwrap();
+        let account_data_sizes: Vec<u64> = (1..200).collect();
+        let (reader, _accounts, _stored_accounts_info) =
+            write_test_file(&temp_dir, &account_data_sizes, AccountBlockFormat::Lz4);
+
+        let accounts: Vec<_> = account_data_sizes
+            .iter()
+            .map(|size| create_test_account(*size))
+            .collect();
+        let account_refs: Vec<_> = accounts
+            .iter()
+            .map(|account| (&account.0.pubkey, &account.1))
+            .collect();
+        let hot_path = temp_dir.path().join("test_hot_storage");
+        let hot_size = {
+            let mut writer = HotStorageWriter::new(&hot_path).unwrap();
+            let stored_accounts_info = writer
+                .write_accounts(&(Slot::MAX, &account_refs[..]), 0)
+                .unwrap();
+            writer.flush().unwrap();
+            stored_accounts_info.size
+        };
+
+        assert!(reader.len() < hot_size);
+    }
+
+    #[test]
+    fn test_write_zero_accounts() {
+        let temp_dir = TempDir::new().unwrap();
+        let (reader, accounts, stored_accounts_info) =
+            write_test_file(&temp_dir, &[], AccountBlockFormat::Lz4);
+
+        assert!(accounts.is_empty());
+        assert!(stored_accounts_info.offsets.is_empty());
+        assert_eq!(reader.num_accounts(), 0);
+        assert_eq!(reader.num_blocks(), 0);
+        reader
+            .scan_accounts(|_, _| panic!("unexpected account"))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_cold_storage_writer_twice_on_same_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir
+            .path()
+            .join("test_cold_storage_writer_twice_on_same_path");
+
+        // Expect the first returns Ok
+        assert_matches!(ColdStorageWriter::new(&path), Ok(_));
+        // Expect the second call on the same path returns Err, as the
+        // ColdStorageWriter only writes once.
+        assert_matches!(ColdStorageWriter::new(&path), Err(_));
+    }
+}
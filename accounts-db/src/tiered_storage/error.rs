@@ -34,4 +34,10 @@ pub enum TieredStorageError {
 
     #[error("failed to flush hot storage writer: {0}")]
     FlushHotWriter(#[source] std::io::Error),
+
+    #[error("failed to flush cold storage writer: {0}")]
+    FlushColdWriter(#[source] std::io::Error),
+
+    #[error("InvalidColdAccountBlock: the account block at offset {0} is corrupted")]
+    InvalidColdAccountBlock(usize),
 }
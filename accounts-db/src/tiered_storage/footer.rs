@@ -42,8 +42,7 @@ pub const FOOTER_TAIL_SIZE: usize = 24;
 pub enum AccountMetaFormat {
     #[default]
     Hot = 0,
-    // Temporarily comment out to avoid unimplemented!() block
-    // Cold = 1,
+    Cold = 1,
 }
 
 #[repr(u16)]
@@ -8,8 +8,10 @@ use {
     solana_account::ReadableAccount,
     solana_accounts_db::accounts_file::{AccountsFile, StorageAccess},
     solana_pubkey::Pubkey,
+    solana_runtime::snapshot_utils::SeekableSnapshotArchive,
     solana_system_interface::MAX_PERMITTED_DATA_LENGTH,
     std::{
+        ffi::OsStr,
         fs, io,
         mem::ManuallyDrop,
         num::Saturating,
@@ -19,6 +21,7 @@ use {
 
 const CMD_INSPECT: &str = "inspect";
 const CMD_SEARCH: &str = "search";
+const CMD_EXTRACT: &str = "extract";
 
 fn main() {
     let matches = App::new(crate_name!())
@@ -55,7 +58,7 @@ fn main() {
                         .index(1)
                         .takes_value(true)
                         .value_name("PATH")
-                        .help("Account storage directory to search"),
+                        .help("Account storage directory, or seekable snapshot archive, to search"),
                 )
                 .arg(
                     Arg::with_name("addresses")
@@ -73,6 +76,39 @@ fn main() {
                         .help("Show additional account information"),
                 ),
         )
+        .subcommand(
+            SubCommand::with_name(CMD_EXTRACT)
+                .about("Extracts account storage files from a seekable snapshot archive")
+                .arg(
+                    Arg::with_name("archive")
+                        .index(1)
+                        .takes_value(true)
+                        .value_name("PATH")
+                        .required(true)
+                        .help("Seekable snapshot archive to extract from"),
+                )
+                .arg(
+                    Arg::with_name("storages")
+                        .index(2)
+                        .takes_value(true)
+                        .value_name("STORAGES")
+                        .value_delimiter(",")
+                        .help(
+                            "Name of one or more account storage files to extract, delimited by \
+                             commas. If not specified, lists the account storage files of the \
+                             archive",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("output_dir")
+                        .short("o")
+                        .long("output-dir")
+                        .takes_value(true)
+                        .value_name("DIR")
+                        .default_value(".")
+                        .help("Directory to extract the account storage files into"),
+                ),
+        )
         .get_matches();
 
     let subcommand = matches.subcommand();
@@ -80,6 +116,7 @@ fn main() {
     match subcommand {
         (CMD_INSPECT, Some(subcommand_matches)) => cmd_inspect(&matches, subcommand_matches),
         (CMD_SEARCH, Some(subcommand_matches)) => cmd_search(&matches, subcommand_matches),
+        (CMD_EXTRACT, Some(subcommand_matches)) => cmd_extract(&matches, subcommand_matches),
         _ => unreachable!(),
     }
     .unwrap_or_else(|err| {
@@ -108,6 +145,18 @@ fn cmd_search(
     do_search(path, addresses, verbose)
 }
 
+fn cmd_extract(
+    _app_matches: &ArgMatches<'_>,
+    subcommand_matches: &ArgMatches<'_>,
+) -> Result<(), String> {
+    let archive = value_t_or_exit!(subcommand_matches, "archive", String);
+    let storages = subcommand_matches
+        .values_of("storages")
+        .map(|storages| storages.map(String::from).collect());
+    let output_dir = value_t_or_exit!(subcommand_matches, "output_dir", String);
+    do_extract(archive, storages, output_dir)
+}
+
 fn do_inspect(file: impl AsRef<Path>, verbose: bool) -> Result<(), String> {
     let file_size = fs::metadata(&file)
         .map_err(|err| {
@@ -174,7 +223,7 @@ fn do_inspect(file: impl AsRef<Path>, verbose: bool) -> Result<(), String> {
 }
 
 fn do_search(
-    dir: impl AsRef<Path>,
+    path: impl AsRef<Path>,
     addresses: HashSet<Pubkey>,
     verbose: bool,
 ) -> Result<(), String> {
@@ -191,67 +240,150 @@ fn do_search(
         Ok(files)
     }
 
-    let files = get_files_in(&dir).map_err(|err| {
+    if path.as_ref().is_file() {
+        return do_search_archive(path, addresses, verbose);
+    }
+
+    let files = get_files_in(&path).map_err(|err| {
         format!(
             "failed to get files in dir '{}': {err}",
-            dir.as_ref().display(),
+            path.as_ref().display(),
         )
     })?;
-    files.par_iter().for_each(|file| {
-        let file_size = match fs::metadata(file) {
-            Ok(metadata) => metadata.len() as usize,
-            Err(err) => {
-                eprintln!("failed to get storage metadata '{}': {err}", file.display(),);
-                return;
-            }
-        };
-        let Ok((storage, _size)) =
-            AccountsFile::new_from_file(file, file_size, StorageAccess::default()).inspect_err(
-                |err| {
-                    eprintln!(
-                        "failed to open account storage file '{}': {err}",
-                        file.display(),
-                    )
-                },
+    files
+        .par_iter()
+        .for_each(|file| search_storage(file, &addresses, verbose));
+
+    Ok(())
+}
+
+/// Searches the account storage files of a seekable snapshot archive, one at a time
+fn do_search_archive(
+    archive: impl AsRef<Path>,
+    addresses: HashSet<Pubkey>,
+    verbose: bool,
+) -> Result<(), String> {
+    let mut archive = open_archive(archive)?;
+    let unpack_dir = tempfile::tempdir()
+        .map_err(|err| format!("failed to create temporary directory: {err}"))?;
+    let storages: Vec<_> = archive
+        .storages()
+        .into_iter()
+        .map(|(_slot, _id, path)| path.to_string())
+        .collect();
+    for storage in storages {
+        let file = archive
+            .unpack_storage(&storage, unpack_dir.path())
+            .map_err(|err| format!("failed to unpack account storage file '{storage}': {err}"))?;
+        search_storage(&file, &addresses, verbose);
+        fs::remove_file(&file).map_err(|err| {
+            format!(
+                "failed to remove unpacked account storage file '{}': {err}",
+                file.display(),
             )
-        else {
+        })?;
+    }
+
+    Ok(())
+}
+
+fn search_storage(file: &Path, addresses: &HashSet<Pubkey>, verbose: bool) {
+    let file_size = match fs::metadata(file) {
+        Ok(metadata) => metadata.len() as usize,
+        Err(err) => {
+            eprintln!("failed to get storage metadata '{}': {err}", file.display(),);
             return;
-        };
-        // By default, when the storage is dropped, the backing file will be removed.
-        // We do not want to remove the backing file here in the store-tool, so prevent dropping.
-        let storage = ManuallyDrop::new(storage);
+        }
+    };
+    let Ok((storage, _size)) =
+        AccountsFile::new_from_file(file, file_size, StorageAccess::default()).inspect_err(|err| {
+            eprintln!(
+                "failed to open account storage file '{}': {err}",
+                file.display(),
+            )
+        })
+    else {
+        return;
+    };
+    // By default, when the storage is dropped, the backing file will be removed.
+    // We do not want to remove the backing file here in the store-tool, so prevent dropping.
+    let storage = ManuallyDrop::new(storage);
 
-        let file_name = Path::new(file.file_name().expect("path is a file"));
-        storage
-            .scan_accounts_stored_meta(|account| {
-                if addresses.contains(account.pubkey()) {
-                    if verbose {
-                        println!("storage: {}, {account:?}", file_name.display());
-                    } else {
-                        println!(
-                            "storage: {}, offset: {}, pubkey: {}, owner: {}, data size: {}, \
-                             lamports: {}",
-                            file_name.display(),
-                            account.offset(),
-                            account.pubkey(),
-                            account.owner(),
-                            account.data_len(),
-                            account.lamports(),
-                        );
-                    }
+    let file_name = Path::new(file.file_name().expect("path is a file"));
+    storage
+        .scan_accounts_stored_meta(|account| {
+            if addresses.contains(account.pubkey()) {
+                if verbose {
+                    println!("storage: {}, {account:?}", file_name.display());
+                } else {
+                    println!(
+                        "storage: {}, offset: {}, pubkey: {}, owner: {}, data size: {}, lamports: \
+                         {}",
+                        file_name.display(),
+                        account.offset(),
+                        account.pubkey(),
+                        account.owner(),
+                        account.data_len(),
+                        account.lamports(),
+                    );
                 }
+            }
+        })
+        .unwrap_or_else(|err| {
+            eprintln!(
+                "failed to scan accounts in file '{}': {err}",
+                file.display()
+            )
+        });
+}
+
+fn do_extract(
+    archive: impl AsRef<Path>,
+    storages: Option<Vec<String>>,
+    output_dir: impl AsRef<Path>,
+) -> Result<(), String> {
+    let mut archive = open_archive(archive)?;
+    let Some(storages) = storages else {
+        for (slot, id, path) in archive.storages() {
+            println!("{path}: slot: {slot}, id: {id}");
+        }
+        return Ok(());
+    };
+
+    fs::create_dir_all(&output_dir).map_err(|err| {
+        format!(
+            "failed to create output dir '{}': {err}",
+            output_dir.as_ref().display(),
+        )
+    })?;
+    for storage in storages {
+        // Storages may be named with or without their directory in the archive
+        let path = archive
+            .storages()
+            .into_iter()
+            .find(|(_slot, _id, path)| {
+                *path == storage || Path::new(path).file_name() == Some(OsStr::new(&storage))
             })
-            .unwrap_or_else(|err| {
-                eprintln!(
-                    "failed to scan accounts in file '{}': {err}",
-                    file.display()
-                )
-            });
-    });
+            .map(|(_slot, _id, path)| path.to_string())
+            .ok_or_else(|| format!("account storage file '{storage}' not found in archive"))?;
+        let file = archive
+            .unpack_storage(&path, &output_dir)
+            .map_err(|err| format!("failed to extract account storage file '{path}': {err}"))?;
+        println!("{}", file.display());
+    }
 
     Ok(())
 }
 
+fn open_archive(archive: impl AsRef<Path>) -> Result<SeekableSnapshotArchive, String> {
+    SeekableSnapshotArchive::open(&archive).map_err(|err| {
+        format!(
+            "failed to open seekable snapshot archive '{}': {err}",
+            archive.as_ref().display(),
+        )
+    })
+}
+
 /// Returns the number of characters required to print `x` in base-10
 fn width10(x: u64) -> usize {
     (x as f64).log10().ceil() as usize
@@ -261,3 +393,64 @@ fn width10(x: u64) -> usize {
 fn width16(x: u64) -> usize {
     (x as f64).log(16.0).ceil() as usize
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_genesis_config::GenesisConfig,
+        solana_runtime::{
+            bank::Bank,
+            snapshot_archive_info::SnapshotArchiveInfoGetter,
+            snapshot_bank_utils::bank_to_full_snapshot_archive,
+            snapshot_utils::{ArchiveFormat, ZstdConfig},
+        },
+    };
+
+    #[test]
+    fn test_extract() {
+        let bank = Bank::new_for_tests(&GenesisConfig::default());
+        bank.fill_bank_with_ticks_for_tests();
+        let bank_snapshots_dir = tempfile::tempdir().unwrap();
+        let snapshot_archives_dir = tempfile::tempdir().unwrap();
+        let snapshot_archive_info = bank_to_full_snapshot_archive(
+            bank_snapshots_dir.path(),
+            &bank,
+            None,
+            snapshot_archives_dir.path(),
+            snapshot_archives_dir.path(),
+            ArchiveFormat::TarZstdSeekable {
+                config: ZstdConfig::default(),
+            },
+        )
+        .unwrap();
+        let archive_path = snapshot_archive_info.path();
+
+        let mut archive = open_archive(archive_path).unwrap();
+        let storage = archive.storages()[0].2.to_string();
+        let mut storage_contents = vec![];
+        archive
+            .unpack_file(&storage, &mut storage_contents)
+            .unwrap();
+
+        // storages may be named without their directory in the archive
+        let output_dir = tempfile::tempdir().unwrap();
+        let file_name = Path::new(&storage).file_name().unwrap().to_str().unwrap();
+        do_extract(
+            archive_path,
+            Some(vec![file_name.to_string()]),
+            output_dir.path(),
+        )
+        .unwrap();
+        let extracted = output_dir.path().join(file_name);
+        assert_eq!(fs::read(&extracted).unwrap(), storage_contents);
+        do_inspect(&extracted, false).unwrap();
+
+        assert!(do_extract(
+            archive_path,
+            Some(vec!["0.123456".to_string()]),
+            output_dir.path(),
+        )
+        .is_err());
+    }
+}
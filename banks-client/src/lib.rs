@@ -15,7 +15,7 @@ use {
     solana_account::{from_account, Account},
     solana_banks_interface::{
         BanksRequest, BanksResponse, BanksTransactionResultWithMetadata,
-        BanksTransactionResultWithSimulation,
+        BanksTransactionResultWithSimulation, BundleTransactionSimulation,
     },
     solana_clock::Slot,
     solana_commitment_config::CommitmentLevel,
@@ -158,6 +158,24 @@ impl BanksClient {
             .map_err(Into::into)
     }
 
+    pub async fn simulate_bundle_with_commitment_and_context(
+        &self,
+        ctx: Context,
+        transactions: Vec<impl Into<VersionedTransaction>>,
+        snapshot_accounts: Vec<Pubkey>,
+        commitment: CommitmentLevel,
+    ) -> Result<transaction::Result<Vec<BundleTransactionSimulation>>, BanksClientError> {
+        self.inner
+            .simulate_bundle_with_commitment_and_context(
+                ctx,
+                transactions.into_iter().map(Into::into).collect(),
+                snapshot_accounts,
+                commitment,
+            )
+            .await
+            .map_err(Into::into)
+    }
+
     pub async fn get_account_with_commitment_and_context(
         &self,
         ctx: Context,
@@ -334,6 +352,41 @@ impl BanksClient {
             .await
     }
 
+    /// Simulate a bundle of transactions at the given commitment level
+    ///
+    /// The transactions are simulated in order, each one seeing the account writes of the
+    /// previous ones. The state of `snapshot_accounts` is returned before and after every
+    /// transaction.
+    pub async fn simulate_bundle_with_commitment(
+        &self,
+        transactions: Vec<impl Into<VersionedTransaction>>,
+        snapshot_accounts: Vec<Pubkey>,
+        commitment: CommitmentLevel,
+    ) -> Result<Vec<BundleTransactionSimulation>, BanksClientError> {
+        self.simulate_bundle_with_commitment_and_context(
+            context::current(),
+            transactions,
+            snapshot_accounts,
+            commitment,
+        )
+        .await?
+        .map_err(Into::into)
+    }
+
+    /// Simulate a bundle of transactions at the default commitment level
+    pub async fn simulate_bundle(
+        &self,
+        transactions: Vec<impl Into<VersionedTransaction>>,
+        snapshot_accounts: Vec<Pubkey>,
+    ) -> Result<Vec<BundleTransactionSimulation>, BanksClientError> {
+        self.simulate_bundle_with_commitment(
+            transactions,
+            snapshot_accounts,
+            CommitmentLevel::default(),
+        )
+        .await
+    }
+
     /// Return the most recent rooted slot. All transactions at or below this slot
     /// are said to be finalized. The cluster will not fork to a higher slot.
     pub async fn get_root_slot(&self) -> Result<Slot, BanksClientError> {
@@ -593,6 +646,60 @@ mod tests {
         })
     }
 
+    #[test]
+    #[allow(clippy::result_large_err)]
+    fn test_banks_server_simulate_bundle() -> Result<(), BanksClientError> {
+        let genesis = create_genesis_config(10);
+        let bank = Bank::new_for_tests(&genesis.genesis_config);
+        let slot = bank.slot();
+        let block_commitment_cache = Arc::new(RwLock::new(
+            BlockCommitmentCache::new_for_tests_with_slots(slot, slot),
+        ));
+        let bank_forks = BankForks::new_rw_arc(bank);
+
+        let bob_pubkey = solana_pubkey::new_rand();
+        let mint_pubkey = genesis.mint_keypair.pubkey();
+
+        Runtime::new()?.block_on(async {
+            let client_transport =
+                start_local_server(bank_forks, block_commitment_cache, Duration::from_millis(1))
+                    .await;
+            let banks_client = start_client(client_transport).await?;
+
+            let recent_blockhash = banks_client.get_latest_blockhash().await?;
+            let transactions: Vec<_> = [2, 3]
+                .into_iter()
+                .map(|lamports| {
+                    let instruction =
+                        system_instruction::transfer(&mint_pubkey, &bob_pubkey, lamports);
+                    let message = Message::new(&[instruction], Some(&mint_pubkey));
+                    Transaction::new(&[&genesis.mint_keypair], message, recent_blockhash)
+                })
+                .collect();
+            let simulations = banks_client
+                .simulate_bundle(transactions, vec![bob_pubkey])
+                .await?;
+
+            // the second transfer sees the first one
+            let balances: Vec<_> = simulations
+                .iter()
+                .map(|simulation| {
+                    assert_eq!(simulation.result, Ok(()));
+                    let balance = |accounts: &[Option<Account>]| {
+                        accounts[0].as_ref().map(|account| account.lamports)
+                    };
+                    (
+                        balance(&simulation.pre_execution_accounts),
+                        balance(&simulation.post_execution_accounts),
+                    )
+                })
+                .collect();
+            assert_eq!(balances, vec![(None, Some(2)), (Some(2), Some(5))]);
+            assert_eq!(banks_client.get_balance(bob_pubkey).await?, 0);
+            Ok(())
+        })
+    }
+
     #[test]
     #[allow(clippy::result_large_err)]
     fn test_banks_server_transfer_via_client() -> Result<(), BanksClientError> {
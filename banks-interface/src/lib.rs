@@ -57,6 +57,18 @@ pub struct BanksTransactionResultWithSimulation {
     pub simulation_details: Option<TransactionSimulationDetails>,
 }
 
+/// Simulation result of a transaction of a bundle
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleTransactionSimulation {
+    pub result: transaction::Result<()>,
+    pub simulation_details: TransactionSimulationDetails,
+    /// State of the requested accounts before the transaction
+    pub pre_execution_accounts: Vec<Option<Account>>,
+    /// State of the requested accounts after the transaction
+    pub post_execution_accounts: Vec<Option<Account>>,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct BanksTransactionResultWithMetadata {
     pub result: transaction::Result<()>,
@@ -85,6 +97,11 @@ pub trait Banks {
         transaction: VersionedTransaction,
         commitment: CommitmentLevel,
     ) -> BanksTransactionResultWithSimulation;
+    async fn simulate_bundle_with_commitment_and_context(
+        transactions: Vec<VersionedTransaction>,
+        snapshot_accounts: Vec<Pubkey>,
+        commitment: CommitmentLevel,
+    ) -> transaction::Result<Vec<BundleTransactionSimulation>>;
     async fn get_account_with_commitment_and_context(
         address: Pubkey,
         commitment: CommitmentLevel,
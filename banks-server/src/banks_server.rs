@@ -5,8 +5,9 @@ use {
     solana_account::Account,
     solana_banks_interface::{
         Banks, BanksRequest, BanksResponse, BanksTransactionResultWithMetadata,
-        BanksTransactionResultWithSimulation, TransactionConfirmationStatus, TransactionMetadata,
-        TransactionSimulationDetails, TransactionStatus,
+        BanksTransactionResultWithSimulation, BundleTransactionSimulation,
+        TransactionConfirmationStatus, TransactionMetadata, TransactionSimulationDetails,
+        TransactionStatus,
     },
     solana_client::connection_cache::ConnectionCache,
     solana_clock::Slot,
@@ -15,7 +16,7 @@ use {
     solana_message::{Message, SanitizedMessage},
     solana_pubkey::Pubkey,
     solana_runtime::{
-        bank::{Bank, TransactionSimulationResult},
+        bank::{Bank, BundleTransactionSimulationResult, TransactionSimulationResult},
         bank_forks::BankForks,
         commitment::BlockCommitmentCache,
     },
@@ -198,6 +199,7 @@ fn simulate_transaction(
         post_balances: _,
         pre_token_balances: _,
         post_token_balances: _,
+        execution_trace: _,
     } = bank.simulate_transaction_unchecked(&sanitized_transaction, true);
 
     let simulation_details = TransactionSimulationDetails {
@@ -213,6 +215,67 @@ fn simulate_transaction(
     }
 }
 
+fn simulate_bundle(
+    bank: &Bank,
+    transactions: Vec<VersionedTransaction>,
+    snapshot_accounts: &[Pubkey],
+) -> transaction::Result<Vec<BundleTransactionSimulation>> {
+    let sanitized_transactions = transactions
+        .into_iter()
+        .map(|transaction| {
+            RuntimeTransaction::try_create(
+                transaction,
+                MessageHash::Compute,
+                Some(false), // is_simple_vote_tx
+                bank,
+                bank.get_reserved_account_keys(),
+                bank.feature_set
+                    .is_active(&agave_feature_set::static_instruction_limit::id()),
+            )
+        })
+        .collect::<transaction::Result<Vec<_>>>()?;
+
+    let simulations = bank
+        .simulate_bundle_unchecked(&sanitized_transactions, true, snapshot_accounts)
+        .into_iter()
+        .map(|simulation| {
+            let BundleTransactionSimulationResult {
+                simulation:
+                    TransactionSimulationResult {
+                        result,
+                        logs,
+                        units_consumed,
+                        loaded_accounts_data_size,
+                        return_data,
+                        inner_instructions,
+                        ..
+                    },
+                pre_execution_accounts,
+                post_execution_accounts,
+            } = simulation;
+            let into_accounts = |accounts: Vec<Option<_>>| {
+                accounts
+                    .into_iter()
+                    .map(|account| account.map(Account::from))
+                    .collect()
+            };
+            BundleTransactionSimulation {
+                result,
+                simulation_details: TransactionSimulationDetails {
+                    logs,
+                    units_consumed,
+                    loaded_accounts_data_size,
+                    return_data,
+                    inner_instructions,
+                },
+                pre_execution_accounts: into_accounts(pre_execution_accounts),
+                post_execution_accounts: into_accounts(post_execution_accounts),
+            }
+        })
+        .collect();
+    Ok(simulations)
+}
+
 #[tarpc::server]
 impl Banks for BanksServer {
     async fn send_transaction_with_context(self, _: Context, transaction: VersionedTransaction) {
@@ -310,6 +373,16 @@ impl Banks for BanksServer {
         simulate_transaction(&self.bank(commitment), transaction)
     }
 
+    async fn simulate_bundle_with_commitment_and_context(
+        self,
+        _: Context,
+        transactions: Vec<VersionedTransaction>,
+        snapshot_accounts: Vec<Pubkey>,
+        commitment: CommitmentLevel,
+    ) -> transaction::Result<Vec<BundleTransactionSimulation>> {
+        simulate_bundle(&self.bank(commitment), transactions, &snapshot_accounts)
+    }
+
     async fn process_transaction_with_commitment_and_context(
         self,
         _: Context,
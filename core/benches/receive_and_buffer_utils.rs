@@ -6,6 +6,7 @@ use {
     solana_compute_budget_interface::ComputeBudgetInstruction,
     solana_core::banking_stage::{
         decision_maker::BufferedPacketsDecision,
+        leader_slot_events::LeaderSlotEvents,
         packet_deserializer::PacketDeserializer,
         transaction_scheduler::{
             receive_and_buffer::{
@@ -145,6 +146,7 @@ impl ReceiveAndBufferCreator for TransactionViewReceiveAndBuffer {
         TransactionViewReceiveAndBuffer {
             receiver,
             bank_forks,
+            leader_slot_events: LeaderSlotEvents::default(),
         }
     }
 }
@@ -154,7 +156,11 @@ impl ReceiveAndBufferCreator for SanitizedTransactionReceiveAndBuffer {
         receiver: Receiver<Arc<Vec<PacketBatch>>>,
         bank_forks: Arc<RwLock<BankForks>>,
     ) -> Self {
-        SanitizedTransactionReceiveAndBuffer::new(PacketDeserializer::new(receiver), bank_forks)
+        SanitizedTransactionReceiveAndBuffer::new(
+            PacketDeserializer::new(receiver),
+            bank_forks,
+            LeaderSlotEvents::default(),
+        )
     }
 }
 
@@ -3,11 +3,13 @@ use {
         banking_stage::BankingStage,
         cluster_slots_service::cluster_slots::ClusterSlots,
         repair::{outstanding_requests::OutstandingRequests, serve_repair::ShredRepairType},
+        tpu_ingress_filter_manager::TpuIngressFilterManager,
     },
     solana_gossip::{cluster_info::ClusterInfo, node::NodeMultihoming},
     solana_pubkey::Pubkey,
     solana_quic_definitions::NotifyKeyUpdate,
     solana_runtime::bank_forks::BankForks,
+    solana_streamer::nonblocking::connection_qos::ConnectionQosRegistry,
     std::{
         collections::{HashMap, HashSet},
         net::UdpSocket,
@@ -81,4 +83,6 @@ pub struct AdminRpcRequestMetadataPostInit {
     pub cluster_slots: Arc<ClusterSlots>,
     pub node: Option<Arc<NodeMultihoming>>,
     pub banking_stage: Arc<RwLock<Option<BankingStage>>>,
+    pub tpu_connection_qos: Vec<Arc<ConnectionQosRegistry>>,
+    pub tpu_ingress_filter_manager: Arc<RwLock<TpuIngressFilterManager>>,
 }
@@ -3,6 +3,7 @@ use {
     crate::{
         banking_stage::{
             transaction_scheduler::scheduler_controller::SchedulerConfig,
+            unified_scheduler::ensure_banking_stage_setup,
             update_bank_forks_and_poh_recorder_for_new_tpu_bank, BankingStage, LikeClusterInfo,
         },
         banking_trace::{
@@ -12,6 +13,9 @@ use {
         validator::{BlockProductionMethod, TransactionStructure},
     },
     agave_banking_stage_ingress_types::BankingPacketBatch,
+    agave_geyser_plugin_interface::geyser_plugin_interface::{
+        BankingTransactionEvent, BankingTransactionEventKind,
+    },
     assert_matches::assert_matches,
     bincode::deserialize_from,
     crossbeam_channel::{unbounded, Sender},
@@ -19,6 +23,7 @@ use {
     log::*,
     solana_clock::{Slot, DEFAULT_MS_PER_SLOT, HOLD_TRANSACTIONS_SLOT_OFFSET},
     solana_genesis_config::GenesisConfig,
+    solana_geyser_plugin_manager::leader_slot_notifier_interface::LeaderSlotNotifier,
     solana_gossip::{cluster_info::ClusterInfo, contact_info::ContactInfoQuery, node::Node},
     solana_keypair::Keypair,
     solana_ledger::{
@@ -43,20 +48,25 @@ use {
     solana_signer::Signer,
     solana_streamer::socket::SocketAddrSpace,
     solana_turbine::broadcast_stage::{BroadcastStage, BroadcastStageType},
+    solana_unified_scheduler_logic::SchedulingMode,
+    solana_unified_scheduler_pool::DefaultSchedulerPool,
     std::{
         collections::BTreeMap,
         fmt::Display,
         fs::File,
         io::{self, BufRead, BufReader},
         net::{IpAddr, Ipv4Addr},
+        num::NonZeroUsize,
         path::PathBuf,
         sync::{
             atomic::{AtomicBool, Ordering},
-            Arc, RwLock,
+            Arc, Mutex, RwLock,
         },
         thread::{self, sleep, JoinHandle},
         time::{Duration, Instant, SystemTime},
     },
+    strum::VariantNames,
+    strum_macros::{Display, EnumString, EnumVariantNames, IntoStaticStr},
     thiserror::Error,
 };
 
@@ -128,6 +138,123 @@ pub enum SimulateError {
     DeserializeError(#[from] bincode::Error),
 }
 
+/// Scheduler implementation to produce the simulated blocks with
+#[derive(
+    Clone, Copy, Debug, EnumString, EnumVariantNames, IntoStaticStr, Display, PartialEq, Eq,
+)]
+#[strum(serialize_all = "kebab-case")]
+pub enum SimulatedScheduler {
+    GreedyScheduler,
+    PrioGraphScheduler,
+    UnifiedScheduler,
+}
+
+impl SimulatedScheduler {
+    pub const fn cli_names() -> &'static [&'static str] {
+        Self::VARIANTS
+    }
+
+    fn block_production_method(&self) -> Option<BlockProductionMethod> {
+        match self {
+            Self::GreedyScheduler => Some(BlockProductionMethod::CentralSchedulerGreedy),
+            Self::PrioGraphScheduler => Some(BlockProductionMethod::CentralScheduler),
+            Self::UnifiedScheduler => None,
+        }
+    }
+}
+
+impl From<BlockProductionMethod> for SimulatedScheduler {
+    fn from(method: BlockProductionMethod) -> Self {
+        match method {
+            BlockProductionMethod::CentralSchedulerGreedy => Self::GreedyScheduler,
+            BlockProductionMethod::CentralScheduler => Self::PrioGraphScheduler,
+        }
+    }
+}
+
+/// Knobs of the banking stage under simulation
+#[derive(Clone)]
+pub struct SimulationConfig {
+    pub scheduler: SimulatedScheduler,
+    pub transaction_struct: TransactionStructure,
+    /// Number of the banking stage workers, or the unified scheduler's handler threads
+    pub num_workers: NonZeroUsize,
+    /// Ignored by the unified scheduler
+    pub scheduler_config: SchedulerConfig,
+}
+
+/// What the simulated leader packed into one of its blocks
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SimulatedSlotStats {
+    pub collector_fees: u64,
+    pub block_cost: u64,
+    pub vote_cost: u64,
+    pub executed_transaction_count: u64,
+    /// Transactions dropped by the scheduler, for any reason. `None` for the unified scheduler,
+    /// which doesn't report them.
+    pub dropped_transaction_count: Option<usize>,
+}
+
+impl SimulatedSlotStats {
+    fn new(bank: &Bank) -> Self {
+        let (block_cost, vote_cost) = SimulatorLoopLogger::bank_costs(bank);
+        Self {
+            collector_fees: bank.collector_fees(),
+            block_cost,
+            vote_cost,
+            executed_transaction_count: bank.executed_transaction_count(),
+            dropped_transaction_count: None,
+        }
+    }
+}
+
+/// Result of a simulation run, keyed by the simulated leader's slots
+#[derive(Debug, Default)]
+pub struct SimulationReport {
+    pub slot_stats: BTreeMap<Slot, SimulatedSlotStats>,
+}
+
+/// Counts the transactions the central scheduler dropped in each leader slot, by listening to the
+/// same events as the geyser leader slot notifications.
+#[derive(Default)]
+struct DroppedTransactionCounter {
+    dropped_by_slot: Mutex<BTreeMap<Slot, usize>>,
+}
+
+impl DroppedTransactionCounter {
+    fn is_dropped(event: &BankingTransactionEvent) -> bool {
+        matches!(
+            event.kind,
+            BankingTransactionEventKind::DroppedForAccountLocks
+                | BankingTransactionEventKind::Dropped
+        )
+    }
+
+    fn dropped(&self, slot: Slot) -> usize {
+        self.dropped_by_slot
+            .lock()
+            .unwrap()
+            .get(&slot)
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+impl LeaderSlotNotifier for DroppedTransactionCounter {
+    fn notify_leader_slot(&self, slot: Slot, transaction_events: &[BankingTransactionEvent]) {
+        let dropped = transaction_events
+            .iter()
+            .filter(|event| Self::is_dropped(event))
+            .count();
+        *self
+            .dropped_by_slot
+            .lock()
+            .unwrap()
+            .entry(slot)
+            .or_default() += dropped;
+    }
+}
+
 // Defined to be enough to cover the holding phase prior to leader slots with some idling (+5 secs)
 const WARMUP_DURATION: Duration =
     Duration::from_millis(HOLD_TRANSACTIONS_SLOT_OFFSET * DEFAULT_MS_PER_SLOT + 5000);
@@ -419,6 +546,7 @@ struct SimulatorLoop {
     leader_schedule_cache: Arc<LeaderScheduleCache>,
     retransmit_slots_sender: Sender<Slot>,
     retracer: Arc<BankingTracer>,
+    scheduler: SimulatedScheduler,
 }
 
 impl SimulatorLoop {
@@ -426,7 +554,11 @@ impl SimulatorLoop {
         self,
         base_simulation_time: SystemTime,
         sender_thread: EventSenderThread,
-    ) -> (EventSenderThread, Sender<Slot>) {
+    ) -> (
+        EventSenderThread,
+        Sender<Slot>,
+        BTreeMap<Slot, SimulatedSlotStats>,
+    ) {
         sleep(WARMUP_DURATION);
         info!("warmup done!");
         self.start(base_simulation_time, sender_thread)
@@ -436,13 +568,18 @@ impl SimulatorLoop {
         mut self,
         base_simulation_time: SystemTime,
         sender_thread: EventSenderThread,
-    ) -> (EventSenderThread, Sender<Slot>) {
+    ) -> (
+        EventSenderThread,
+        Sender<Slot>,
+        BTreeMap<Slot, SimulatedSlotStats>,
+    ) {
         let logger = SimulatorLoopLogger {
             simulated_leader: self.simulated_leader,
             base_event_time: self.base_event_time,
             base_simulation_time,
             freeze_time_by_slot: self.freeze_time_by_slot,
         };
+        let mut slot_stats = BTreeMap::new();
         let (mut bank, mut bank_created) = (self.bank, Instant::now());
         loop {
             if self.poh_recorder.read().unwrap().bank().is_none() {
@@ -464,6 +601,9 @@ impl SimulatorLoop {
                     assert_matches!(result, Ok(()));
                 }
                 bank.freeze();
+                if *bank.collector_id() == self.simulated_leader {
+                    slot_stats.insert(bank.slot(), SimulatedSlotStats::new(&bank));
+                }
                 let new_slot = if bank.slot() == self.parent_slot {
                     info!("initial leader block!");
                     self.first_simulated_slot
@@ -497,11 +637,23 @@ impl SimulatorLoop {
                     logger.log_frozen_bank_cost(&bank, bank_created.elapsed());
                 }
                 self.retransmit_slots_sender.send(bank.slot()).unwrap();
-                update_bank_forks_and_poh_recorder_for_new_tpu_bank(
-                    &self.bank_forks,
-                    &mut self.poh_controller,
-                    new_bank,
-                );
+                if self.scheduler == SimulatedScheduler::UnifiedScheduler {
+                    let tpu_bank = self
+                        .bank_forks
+                        .write()
+                        .unwrap()
+                        .insert_with_scheduling_mode(SchedulingMode::BlockProduction, new_bank);
+                    self.poh_controller
+                        .set_bank_sync(tpu_bank.clone_with_scheduler())
+                        .unwrap();
+                    tpu_bank.unpause_new_block_production_scheduler();
+                } else {
+                    update_bank_forks_and_poh_recorder_for_new_tpu_bank(
+                        &self.bank_forks,
+                        &mut self.poh_controller,
+                        new_bank,
+                    );
+                }
                 (bank, bank_created) = (
                     self.bank_forks
                         .read()
@@ -517,20 +669,27 @@ impl SimulatorLoop {
             sleep(Duration::from_millis(10));
         }
 
-        (sender_thread, self.retransmit_slots_sender)
+        (sender_thread, self.retransmit_slots_sender, slot_stats)
     }
 }
 
 struct SimulatorThreads {
     poh_service: PohService,
-    banking_stage: BankingStage,
+    // None for the unified scheduler, whose handler threads are owned by the scheduler pool
+    banking_stage: Option<BankingStage>,
+    dropped_transaction_counter: Option<Arc<DroppedTransactionCounter>>,
     broadcast_stage: BroadcastStage,
     retracer_thread: TracerThread,
     exit: Arc<AtomicBool>,
 }
 
 impl SimulatorThreads {
-    fn finish(self, sender_thread: EventSenderThread, retransmit_slots_sender: Sender<Slot>) {
+    fn finish(
+        self,
+        sender_thread: EventSenderThread,
+        retransmit_slots_sender: Sender<Slot>,
+        mut slot_stats: BTreeMap<Slot, SimulatedSlotStats>,
+    ) -> SimulationReport {
         info!("Sleeping a bit before signaling exit");
         sleep(Duration::from_millis(100));
         self.exit.store(true, Ordering::Relaxed);
@@ -538,7 +697,9 @@ impl SimulatorThreads {
         // The order is important. Consuming sender_thread by joining will drop some channels. That
         // triggers termination of banking_stage, in turn retracer thread will be terminated.
         sender_thread.join().unwrap();
-        self.banking_stage.join().unwrap();
+        if let Some(banking_stage) = self.banking_stage {
+            banking_stage.join().unwrap();
+        }
         self.poh_service.join().unwrap();
         if let Some(retracer_thread) = self.retracer_thread {
             retracer_thread.join().unwrap().unwrap();
@@ -547,6 +708,15 @@ impl SimulatorThreads {
         info!("Joining broadcast stage...");
         drop(retransmit_slots_sender);
         self.broadcast_stage.join().unwrap();
+
+        // Dropped transactions are counted only after the banking stage has been joined, so that
+        // events reported late for the last leader slot are included.
+        if let Some(counter) = self.dropped_transaction_counter {
+            for (slot, stats) in slot_stats.iter_mut() {
+                stats.dropped_transaction_count = Some(counter.dropped(*slot));
+            }
+        }
+        SimulationReport { slot_stats }
     }
 }
 
@@ -691,8 +861,7 @@ impl BankingSimulator {
         genesis_config: GenesisConfig,
         bank_forks: Arc<RwLock<BankForks>>,
         blockstore: Arc<Blockstore>,
-        block_production_method: BlockProductionMethod,
-        transaction_struct: TransactionStructure,
+        config: SimulationConfig,
     ) -> (SenderLoop, SimulatorLoop, SimulatorThreads) {
         let parent_slot = self.parent_slot().unwrap();
         let mut packet_batches_by_time = self.banking_trace_events.packet_batches_by_time;
@@ -775,14 +944,8 @@ impl BankingSimulator {
         assert!(retracer.is_enabled());
         info!("Enabled banking retracer (dir_byte_limit: {BANKING_TRACE_DIR_DEFAULT_BYTE_LIMIT})",);
 
-        let Channels {
-            non_vote_sender,
-            non_vote_receiver,
-            tpu_vote_sender,
-            tpu_vote_receiver,
-            gossip_vote_sender,
-            gossip_vote_receiver,
-        } = retracer.create_channels(false);
+        let channels =
+            retracer.create_channels(config.scheduler == SimulatedScheduler::UnifiedScheduler);
 
         let (replay_vote_sender, _replay_vote_receiver) = unbounded();
         let (retransmit_slots_sender, retransmit_slots_receiver) = unbounded();
@@ -824,22 +987,57 @@ impl BankingSimulator {
 
         info!("Start banking stage!...");
         let prioritization_fee_cache = &Arc::new(PrioritizationFeeCache::new(0u64));
-        let banking_stage = BankingStage::new_num_threads(
-            block_production_method.clone(),
-            transaction_struct.clone(),
-            poh_recorder.clone(),
-            transaction_recorder,
-            non_vote_receiver,
-            tpu_vote_receiver,
-            gossip_vote_receiver,
-            BankingStage::default_num_workers(),
-            SchedulerConfig::default(),
-            None,
-            replay_vote_sender,
-            None,
-            bank_forks.clone(),
-            prioritization_fee_cache.clone(),
-        );
+        let (banking_stage, dropped_transaction_counter) =
+            if let Some(block_production_method) = config.scheduler.block_production_method() {
+                let dropped_transaction_counter = Arc::new(DroppedTransactionCounter::default());
+                let banking_stage = BankingStage::new_num_threads(
+                    block_production_method,
+                    config.transaction_struct,
+                    poh_recorder.clone(),
+                    transaction_recorder,
+                    channels.non_vote_receiver.clone(),
+                    channels.tpu_vote_receiver.clone(),
+                    channels.gossip_vote_receiver.clone(),
+                    config.num_workers,
+                    config.scheduler_config,
+                    None,
+                    replay_vote_sender,
+                    None,
+                    bank_forks.clone(),
+                    prioritization_fee_cache.clone(),
+                    Some(dropped_transaction_counter.clone()),
+                );
+                (Some(banking_stage), Some(dropped_transaction_counter))
+            } else {
+                // The scheduler pool can only be installed once, so the ledger must have been
+                // loaded without unified scheduler block verification.
+                let scheduler_pool = DefaultSchedulerPool::new(
+                    None,
+                    None,
+                    None,
+                    Some(replay_vote_sender),
+                    prioritization_fee_cache.clone(),
+                );
+                ensure_banking_stage_setup(
+                    &scheduler_pool,
+                    &bank_forks,
+                    &channels,
+                    &poh_recorder,
+                    transaction_recorder,
+                    config.num_workers,
+                );
+                bank_forks
+                    .write()
+                    .unwrap()
+                    .install_scheduler_pool(scheduler_pool);
+                (None, None)
+            };
+        let Channels {
+            non_vote_sender,
+            tpu_vote_sender,
+            gossip_vote_sender,
+            ..
+        } = channels;
 
         let (&_slot, &raw_base_event_time) = freeze_time_by_slot
             .range(parent_slot..)
@@ -894,11 +1092,13 @@ impl BankingSimulator {
             leader_schedule_cache,
             retransmit_slots_sender,
             retracer,
+            scheduler: config.scheduler,
         };
 
         let simulator_threads = SimulatorThreads {
             poh_service,
             banking_stage,
+            dropped_transaction_counter,
             broadcast_stage,
             retracer_thread,
             exit,
@@ -912,28 +1112,20 @@ impl BankingSimulator {
         genesis_config: GenesisConfig,
         bank_forks: Arc<RwLock<BankForks>>,
         blockstore: Arc<Blockstore>,
-        block_production_method: BlockProductionMethod,
-        transaction_struct: TransactionStructure,
-    ) -> Result<(), SimulateError> {
-        let (sender_loop, simulator_loop, simulator_threads) = self.prepare_simulation(
-            genesis_config,
-            bank_forks,
-            blockstore,
-            block_production_method,
-            transaction_struct,
-        );
+        config: SimulationConfig,
+    ) -> Result<SimulationReport, SimulateError> {
+        let (sender_loop, simulator_loop, simulator_threads) =
+            self.prepare_simulation(genesis_config, bank_forks, blockstore, config);
 
         sender_loop.log_starting();
         let base_simulation_time = SystemTime::now();
         // Spawning and entering these two loops must be done at the same time as they're timed.
         // So, all the mundane setup must be done in advance.
         let sender_thread = sender_loop.spawn(base_simulation_time)?;
-        let (sender_thread, retransmit_slots_sender) =
+        let (sender_thread, retransmit_slots_sender, slot_stats) =
             simulator_loop.enter(base_simulation_time, sender_thread);
 
-        simulator_threads.finish(sender_thread, retransmit_slots_sender);
-
-        Ok(())
+        Ok(simulator_threads.finish(sender_thread, retransmit_slots_sender, slot_stats))
     }
 
     pub fn event_file_name(index: usize) -> String {
@@ -944,3 +1136,49 @@ impl BankingSimulator {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*, crate::banking_stage::leader_slot_events::new_event, solana_signature::Signature,
+        solana_transaction_error::TransactionError,
+    };
+
+    #[test]
+    fn test_dropped_transaction_counter() {
+        let counter = DroppedTransactionCounter::default();
+        let event = |kind, reason| new_event(Signature::default(), kind, reason);
+
+        counter.notify_leader_slot(
+            1,
+            &[
+                event(BankingTransactionEventKind::Received, None),
+                event(
+                    BankingTransactionEventKind::DroppedForAccountLocks,
+                    Some(TransactionError::TooManyAccountLocks),
+                ),
+                event(
+                    BankingTransactionEventKind::Dropped,
+                    Some(TransactionError::BlockhashNotFound),
+                ),
+                // evicted from the full buffer
+                event(BankingTransactionEventKind::Dropped, None),
+                event(
+                    BankingTransactionEventKind::RetriedForCostLimit,
+                    Some(TransactionError::WouldExceedMaxBlockCostLimit),
+                ),
+            ],
+        );
+        // events reported late for the same slot are added up
+        counter.notify_leader_slot(
+            1,
+            &[event(
+                BankingTransactionEventKind::Dropped,
+                Some(TransactionError::InsufficientFundsForFee),
+            )],
+        );
+
+        assert_eq!(counter.dropped(1), 4);
+        assert_eq!(counter.dropped(2), 0);
+    }
+}
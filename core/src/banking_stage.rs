@@ -6,8 +6,13 @@
 use qualifier_attr::qualifiers;
 use {
     self::{
-        committer::Committer, consumer::Consumer, decision_maker::DecisionMaker,
-        packet_receiver::PacketReceiver, qos_service::QosService, vote_storage::VoteStorage,
+        committer::Committer,
+        consumer::Consumer,
+        decision_maker::DecisionMaker,
+        leader_slot_events::{LeaderSlotEvents, LeaderSlotEventsService},
+        packet_receiver::PacketReceiver,
+        qos_service::QosService,
+        vote_storage::VoteStorage,
     },
     crate::{
         banking_stage::{
@@ -27,6 +32,7 @@ use {
     conditional_mod::conditional_vis_mod,
     crossbeam_channel::{unbounded, Receiver, Sender},
     histogram::Histogram,
+    solana_geyser_plugin_manager::leader_slot_notifier_interface::LeaderSlotNotifierArc,
     solana_gossip::{cluster_info::ClusterInfo, contact_info::ContactInfoQuery},
     solana_ledger::blockstore_processor::TransactionStatusSender,
     solana_perf::packet::PACKETS_PER_BATCH,
@@ -73,6 +79,7 @@ mod vote_worker;
 conditional_vis_mod!(decision_maker, feature = "dev-context-only-utils", pub);
 mod immutable_deserialized_packet;
 mod latest_validator_vote_packet;
+conditional_vis_mod!(leader_slot_events, feature = "dev-context-only-utils", pub);
 mod leader_slot_timing_metrics;
 conditional_vis_mod!(packet_deserializer, feature = "dev-context-only-utils", pub);
 mod packet_receiver;
@@ -344,6 +351,7 @@ pub struct BankingStage {
     // Only None during final join of BankingStage.
     context: Option<BankingStageContext>,
     thread_hdls: Vec<JoinHandle<()>>,
+    leader_slot_events_service: Option<LeaderSlotEventsService>,
 }
 
 pub trait LikeClusterInfo: Send + Sync + 'static + Clone {
@@ -379,12 +387,16 @@ impl BankingStage {
         log_messages_bytes_limit: Option<usize>,
         bank_forks: Arc<RwLock<BankForks>>,
         prioritization_fee_cache: Arc<PrioritizationFeeCache>,
+        leader_slot_notifier: Option<LeaderSlotNotifierArc>,
     ) -> Self {
         let committer = Committer::new(
             transaction_status_sender,
             replay_vote_sender,
             prioritization_fee_cache,
         );
+        let (leader_slot_events_service, leader_slot_events) = leader_slot_notifier
+            .map(LeaderSlotEventsService::new)
+            .unzip();
 
         let context = BankingStageContext {
             exit_signal: Arc::new(AtomicBool::new(false)),
@@ -396,6 +408,7 @@ impl BankingStage {
             bank_forks,
             committer,
             log_messages_bytes_limit,
+            leader_slot_events: leader_slot_events.unwrap_or_default(),
         };
         // + 1 for vote worker
         // + 1 for the scheduler thread
@@ -419,6 +432,7 @@ impl BankingStage {
         Self {
             context: Some(context),
             thread_hdls,
+            leader_slot_events_service,
         }
     }
 
@@ -472,6 +486,7 @@ impl BankingStage {
                 let receive_and_buffer = SanitizedTransactionReceiveAndBuffer::new(
                     PacketDeserializer::new(context.non_vote_receiver.clone()),
                     context.bank_forks.clone(),
+                    context.leader_slot_events.clone(),
                 );
                 Self::spawn_scheduler_and_workers(
                     non_vote_thread_hdls,
@@ -486,6 +501,7 @@ impl BankingStage {
                 let receive_and_buffer = TransactionViewReceiveAndBuffer {
                     receiver: context.non_vote_receiver.clone(),
                     bank_forks: context.bank_forks.clone(),
+                    leader_slot_events: context.leader_slot_events.clone(),
                 };
                 Self::spawn_scheduler_and_workers(
                     non_vote_thread_hdls,
@@ -534,6 +550,7 @@ impl BankingStage {
                 ),
                 finished_work_sender.clone(),
                 context.poh_recorder.read().unwrap().shared_working_bank(),
+                context.leader_slot_events.clone(),
             );
 
             worker_metrics.push(consume_worker.metrics_handle());
@@ -554,6 +571,7 @@ impl BankingStage {
             ($scheduler:ident) => {
                 let exit = exit.clone();
                 let bank_forks = context.bank_forks.clone();
+                let leader_slot_events = context.leader_slot_events.clone();
                 non_vote_thread_hdls.push(
                     Builder::new()
                         .name("solBnkTxSched".to_string())
@@ -566,6 +584,7 @@ impl BankingStage {
                                 bank_forks,
                                 $scheduler,
                                 worker_metrics,
+                                leader_slot_events,
                             );
 
                             match scheduler_controller.run() {
@@ -651,6 +670,11 @@ impl BankingStage {
         for bank_thread_hdl in self.thread_hdls {
             bank_thread_hdl.join()?;
         }
+        // The service stops once the context and the joined threads dropped
+        // their `LeaderSlotEvents`.
+        if let Some(leader_slot_events_service) = self.leader_slot_events_service {
+            leader_slot_events_service.join()?;
+        }
         Ok(())
     }
 }
@@ -667,6 +691,7 @@ struct BankingStageContext {
     bank_forks: Arc<RwLock<BankForks>>,
     committer: Committer,
     log_messages_bytes_limit: Option<usize>,
+    leader_slot_events: LeaderSlotEvents,
 }
 
 #[cfg_attr(feature = "dev-context-only-utils", qualifiers(pub))]
@@ -780,6 +805,7 @@ mod tests {
             None,
             bank_forks,
             Arc::new(PrioritizationFeeCache::new(0u64)),
+            None,
         );
         drop(non_vote_sender);
         drop(tpu_vote_sender);
@@ -845,6 +871,7 @@ mod tests {
             None,
             bank_forks,
             Arc::new(PrioritizationFeeCache::new(0u64)),
+            None,
         );
         trace!("sending bank");
         drop(non_vote_sender);
@@ -919,6 +946,7 @@ mod tests {
             None,
             bank_forks.clone(), // keep a local-copy of bank-forks so worker threads do not lose weak access to bank-forks
             Arc::new(PrioritizationFeeCache::new(0u64)),
+            None,
         );
 
         // good tx, and no verify
@@ -1079,6 +1107,7 @@ mod tests {
                 None,
                 bank_forks,
                 Arc::new(PrioritizationFeeCache::new(0u64)),
+                None,
             );
 
             // wait for banking_stage to eat the packets
@@ -1275,6 +1304,7 @@ mod tests {
             None,
             bank_forks,
             Arc::new(PrioritizationFeeCache::new(0u64)),
+            None,
         );
 
         let keypairs = (0..100).map(|_| Keypair::new()).collect_vec();
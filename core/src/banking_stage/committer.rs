@@ -19,6 +19,7 @@ use {
         transaction_balances::BalanceCollector,
         transaction_commit_result::{TransactionCommitResult, TransactionCommitResultExtensions},
         transaction_processing_result::TransactionProcessingResult,
+        transaction_processor::ExecutionRecordingConfig,
     },
     solana_transaction_error::TransactionError,
     std::{num::Saturating, sync::Arc},
@@ -55,8 +56,11 @@ impl Committer {
         }
     }
 
-    pub(super) fn transaction_status_sender_enabled(&self) -> bool {
-        self.transaction_status_sender.is_some()
+    pub(super) fn execution_recording_config(&self) -> ExecutionRecordingConfig {
+        self.transaction_status_sender.as_ref().map_or(
+            ExecutionRecordingConfig::new_single_setting(false),
+            TransactionStatusSender::execution_recording_config,
+        )
     }
 
     pub(super) fn commit_transactions(
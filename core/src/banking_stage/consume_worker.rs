@@ -1,11 +1,15 @@
 use {
     super::{
+        committer::CommitTransactionDetails,
         consumer::{Consumer, ExecuteAndCommitTransactionsOutput, ProcessTransactionBatchOutput},
+        leader_slot_events::{event_for_not_committed, new_event, LeaderSlotEvents},
         leader_slot_timing_metrics::LeaderExecuteAndCommitTimings,
         scheduler_messages::{ConsumeWork, FinishedConsumeWork},
     },
     crate::banking_stage::consumer::RetryableIndex,
+    agave_geyser_plugin_interface::geyser_plugin_interface::BankingTransactionEventKind,
     crossbeam_channel::{Receiver, RecvError, SendError, Sender},
+    solana_clock::Slot,
     solana_measure::measure_us,
     solana_poh::poh_recorder::SharedWorkingBank,
     solana_runtime::bank::Bank,
@@ -38,6 +42,7 @@ pub(crate) struct ConsumeWorker<Tx> {
 
     shared_working_bank: SharedWorkingBank,
     metrics: Arc<ConsumeWorkerMetrics>,
+    leader_slot_events: LeaderSlotEvents,
 }
 
 impl<Tx: TransactionWithMeta> ConsumeWorker<Tx> {
@@ -48,6 +53,7 @@ impl<Tx: TransactionWithMeta> ConsumeWorker<Tx> {
         consumer: Consumer,
         consumed_sender: Sender<FinishedConsumeWork<Tx>>,
         shared_working_bank: SharedWorkingBank,
+        leader_slot_events: LeaderSlotEvents,
     ) -> Self {
         Self {
             exit,
@@ -56,6 +62,7 @@ impl<Tx: TransactionWithMeta> ConsumeWorker<Tx> {
             consumed_sender,
             shared_working_bank,
             metrics: Arc::new(ConsumeWorkerMetrics::new(id)),
+            leader_slot_events,
         }
     }
 
@@ -126,12 +133,27 @@ impl<Tx: TransactionWithMeta> ConsumeWorker<Tx> {
         bank: &Arc<Bank>,
         work: ConsumeWork<Tx>,
     ) -> Result<(), ConsumeWorkerError<Tx>> {
+        self.leader_slot_events.record(
+            bank.slot(),
+            work.transactions.iter().map(|transaction| {
+                new_event(
+                    *transaction.signature(),
+                    BankingTransactionEventKind::Scheduled,
+                    None,
+                )
+            }),
+        );
         let output = self.consumer.process_and_record_aged_transactions(
             bank,
             &work.transactions,
             &work.max_ages,
         );
 
+        self.record_leader_slot_events(
+            bank.slot(),
+            &work.transactions,
+            &output.execute_and_commit_transactions_output,
+        );
         self.metrics.update_for_consume(&output);
         self.metrics.has_data.store(true, Ordering::Relaxed);
 
@@ -144,6 +166,44 @@ impl<Tx: TransactionWithMeta> ConsumeWorker<Tx> {
         Ok(())
     }
 
+    /// Record the transactions of a consumed batch which were retried or
+    /// dropped, with the error the consumer reported for them.
+    fn record_leader_slot_events(
+        &self,
+        slot: Slot,
+        transactions: &[Tx],
+        output: &ExecuteAndCommitTransactionsOutput,
+    ) {
+        match &output.commit_transactions_result {
+            Ok(commit_transaction_details) => self.leader_slot_events.record(
+                slot,
+                commit_transaction_details
+                    .iter()
+                    .zip(transactions)
+                    .filter_map(|(details, transaction)| match details {
+                        CommitTransactionDetails::NotCommitted(err) => {
+                            event_for_not_committed(*transaction.signature(), err)
+                        }
+                        CommitTransactionDetails::Committed { .. } => None,
+                    }),
+            ),
+            // Recording failed, the retryable transactions carry no error of their own.
+            Err(_) => self.leader_slot_events.record(
+                slot,
+                output
+                    .retryable_transaction_indexes
+                    .iter()
+                    .map(|retryable_index| {
+                        new_event(
+                            *transactions[retryable_index.index].signature(),
+                            BankingTransactionEventKind::Retried,
+                            None,
+                        )
+                    }),
+            ),
+        }
+    }
+
     /// Get the current poh working bank with a timeout - if the Bank is
     /// not available within the timeout, return None.
     fn new_working_bank_with_timeout(&self, current_bank: Option<&Arc<Bank>>) -> Option<Arc<Bank>> {
@@ -791,6 +851,7 @@ mod tests {
         super::*,
         crate::banking_stage::{
             committer::Committer,
+            leader_slot_events::{tests::TestNotifier, LeaderSlotEventsService},
             qos_service::QosService,
             scheduler_messages::{MaxAge, TransactionBatchId},
             tests::{create_slow_genesis_config, sanitize_transactions, simulate_poh},
@@ -858,6 +919,19 @@ mod tests {
     ) -> (
         TestFrame,
         ConsumeWorker<RuntimeTransaction<SanitizedTransaction>>,
+    ) {
+        setup_test_frame_with_leader_slot_events(
+            relax_intrabatch_account_locks,
+            LeaderSlotEvents::default(),
+        )
+    }
+
+    fn setup_test_frame_with_leader_slot_events(
+        relax_intrabatch_account_locks: bool,
+        leader_slot_events: LeaderSlotEvents,
+    ) -> (
+        TestFrame,
+        ConsumeWorker<RuntimeTransaction<SanitizedTransaction>>,
     ) {
         let GenesisConfigInfo {
             genesis_config,
@@ -912,6 +986,7 @@ mod tests {
             consumer,
             consumed_sender,
             poh_recorder.read().unwrap().shared_working_bank(),
+            leader_slot_events,
         );
 
         (
@@ -1028,6 +1103,83 @@ mod tests {
         let _ = worker_thread.join().unwrap();
     }
 
+    #[test]
+    fn test_worker_consume_cost_limit() {
+        let notifier = Arc::new(TestNotifier::default());
+        let (leader_slot_events_service, leader_slot_events) =
+            LeaderSlotEventsService::new(notifier.clone());
+        let (test_frame, worker) =
+            setup_test_frame_with_leader_slot_events(true, leader_slot_events);
+        let TestFrame {
+            mint_keypair,
+            genesis_config,
+            bank,
+            poh_recorder,
+            consume_sender,
+            consumed_receiver,
+            ..
+        } = &test_frame;
+        let worker_thread = std::thread::spawn(move || worker.run());
+        poh_recorder
+            .write()
+            .unwrap()
+            .set_bank_for_test(bank.clone());
+        // leave no room in the block for the transaction
+        bank.write_cost_tracker()
+            .unwrap()
+            .set_limits(u64::MAX, 0, u64::MAX);
+        let slot = bank.slot();
+
+        let pubkey1 = Pubkey::new_unique();
+
+        let transactions = sanitize_transactions(vec![system_transaction::transfer(
+            mint_keypair,
+            &pubkey1,
+            1,
+            genesis_config.hash(),
+        )]);
+        let signature = *transactions[0].signature();
+        let bid = TransactionBatchId::new(0);
+        let id = 0;
+        let max_age = MaxAge {
+            sanitized_epoch: bank.epoch(),
+            alt_invalidation_slot: bank.slot(),
+        };
+        let work = ConsumeWork {
+            batch_id: bid,
+            ids: vec![id],
+            transactions,
+            max_ages: vec![max_age],
+        };
+        consume_sender.send(work).unwrap();
+        let consumed = consumed_receiver.recv().unwrap();
+        assert_eq!(consumed.work.batch_id, bid);
+        // handed back to the scheduler, which holds it for the next leader slot
+        assert_eq!(
+            consumed.retryable_indexes,
+            vec![RetryableIndex::new(0, false)]
+        );
+
+        drop(test_frame);
+        let _ = worker_thread.join().unwrap();
+        leader_slot_events_service.join().unwrap();
+
+        assert_eq!(
+            *notifier.notified.lock().unwrap(),
+            vec![(
+                slot,
+                vec![
+                    new_event(signature, BankingTransactionEventKind::Scheduled, None),
+                    new_event(
+                        signature,
+                        BankingTransactionEventKind::RetriedForCostLimit,
+                        Some(TransactionError::WouldExceedMaxBlockCostLimit),
+                    ),
+                ]
+            )]
+        );
+    }
+
     #[test_case(false; "old")]
     #[test_case(true; "simd83")]
     fn test_worker_consume_self_conflicting(relax_intrabatch_account_locks: bool) {
@@ -22,10 +22,9 @@ use {
     },
     solana_runtime_transaction::transaction_with_meta::TransactionWithMeta,
     solana_svm::{
-        account_loader::validate_fee_payer,
-        transaction_error_metrics::TransactionErrorMetrics,
+        account_loader::validate_fee_payer, transaction_error_metrics::TransactionErrorMetrics,
         transaction_processing_result::TransactionProcessingResultExtensions,
-        transaction_processor::{ExecutionRecordingConfig, TransactionProcessingConfig},
+        transaction_processor::TransactionProcessingConfig,
     },
     solana_transaction_error::TransactionError,
     std::num::Saturating,
@@ -245,7 +244,7 @@ impl Consumer {
         bank: &Bank,
         batch: &TransactionBatch<impl TransactionWithMeta>,
     ) -> ExecuteAndCommitTransactionsOutput {
-        let transaction_status_sender_enabled = self.committer.transaction_status_sender_enabled();
+        let recording_config = self.committer.execution_recording_config();
         let mut execute_and_commit_timings = LeaderExecuteAndCommitTimings::default();
 
         let min_max = batch
@@ -326,9 +325,7 @@ impl Consumer {
                     check_program_modification_slot: bank.check_program_modification_slot(),
                     log_messages_bytes_limit: self.log_messages_bytes_limit,
                     limit_to_load_programs: true,
-                    recording_config: ExecutionRecordingConfig::new_single_setting(
-                        transaction_status_sender_enabled
-                    ),
+                    recording_config,
                 }
             ));
         execute_and_commit_timings.load_execute_us = load_execute_us;
@@ -1705,6 +1702,7 @@ mod tests {
             Some(TransactionStatusSender {
                 sender: transaction_status_sender,
                 dependency_tracker: None,
+                enable_log_event_recording: false,
             }),
             replay_vote_sender,
             Arc::new(PrioritizationFeeCache::new(0u64)),
@@ -1853,6 +1851,7 @@ mod tests {
             Some(TransactionStatusSender {
                 sender: transaction_status_sender,
                 dependency_tracker: None,
+                enable_log_event_recording: false,
             }),
             replay_vote_sender,
             Arc::new(PrioritizationFeeCache::new(0u64)),
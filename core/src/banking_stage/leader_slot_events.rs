@@ -0,0 +1,316 @@
+//! Collects what the banking stage did with transactions during our leader
+//! slots, and reports it to the geyser plugins interested in it.
+
+use {
+    agave_geyser_plugin_interface::geyser_plugin_interface::{
+        BankingTransactionEvent, BankingTransactionEventKind,
+    },
+    crossbeam_channel::{bounded, Receiver, Sender, TrySendError},
+    solana_clock::Slot,
+    solana_geyser_plugin_manager::leader_slot_notifier_interface::LeaderSlotNotifierArc,
+    solana_signature::Signature,
+    solana_transaction_error::TransactionError,
+    std::{
+        collections::HashMap,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        thread::{self, Builder, JoinHandle},
+    },
+};
+
+/// Number of messages buffered for the notifier thread, past which the
+/// recorded events are dropped rather than blocking the banking stage.
+const LEADER_SLOT_EVENTS_CHANNEL_SIZE: usize = 4096;
+
+/// Number of events the notifier thread holds for the slots not reported
+/// yet, past which new events are dropped.
+const MAX_BUFFERED_EVENTS: usize = 1 << 20;
+
+enum LeaderSlotEventsMessage {
+    Record(Slot, Vec<BankingTransactionEvent>),
+    /// The events of every slot other than the current leader slot are
+    /// complete.
+    NotifyCompletedSlots(Option<Slot>),
+}
+
+/// Sends the banking stage events of the leader slots to the
+/// [`LeaderSlotEventsService`].
+///
+/// Cloned into the scheduler and the workers, which record events as they
+/// happen. When no notifier is set, recording does nothing.
+#[derive(Clone, Default)]
+pub struct LeaderSlotEvents {
+    inner: Option<LeaderSlotEventsSender>,
+}
+
+#[derive(Clone)]
+struct LeaderSlotEventsSender {
+    sender: Sender<LeaderSlotEventsMessage>,
+    num_dropped_events: Arc<AtomicUsize>,
+}
+
+impl LeaderSlotEvents {
+    pub fn is_enabled(&self) -> bool {
+        self.inner.is_some()
+    }
+
+    /// Records `events` for the leader slot `slot`
+    pub fn record(&self, slot: Slot, events: impl IntoIterator<Item = BankingTransactionEvent>) {
+        if let Some(inner) = &self.inner {
+            let events: Vec<_> = events.into_iter().collect();
+            if !events.is_empty() {
+                inner.send(LeaderSlotEventsMessage::Record(slot, events));
+            }
+        }
+    }
+
+    /// Reports the events of every slot other than `current_leader_slot`
+    ///
+    /// Events recorded for a slot after it was reported, e.g. by a worker
+    /// which was still executing transactions when the slot ended, are
+    /// reported by a later call.
+    pub fn notify_completed_slots(&self, current_leader_slot: Option<Slot>) {
+        if let Some(inner) = &self.inner {
+            inner.send(LeaderSlotEventsMessage::NotifyCompletedSlots(
+                current_leader_slot,
+            ));
+        }
+    }
+}
+
+impl LeaderSlotEventsSender {
+    fn send(&self, message: LeaderSlotEventsMessage) {
+        match self.sender.try_send(message) {
+            Ok(()) | Err(TrySendError::Disconnected(_)) => {}
+            Err(TrySendError::Full(LeaderSlotEventsMessage::Record(_, events))) => {
+                self.num_dropped_events
+                    .fetch_add(events.len(), Ordering::Relaxed);
+            }
+            // The slots are reported by the next notification instead.
+            Err(TrySendError::Full(LeaderSlotEventsMessage::NotifyCompletedSlots(_))) => {}
+        }
+    }
+}
+
+/// Buffers the events of the leader slots until they are complete and
+/// notifies the plugins of them, away from the banking stage threads.
+///
+/// Stops once every [`LeaderSlotEvents`] is dropped, after reporting the
+/// slots still buffered.
+pub struct LeaderSlotEventsService {
+    thread_hdl: JoinHandle<()>,
+}
+
+impl LeaderSlotEventsService {
+    pub fn new(notifier: LeaderSlotNotifierArc) -> (Self, LeaderSlotEvents) {
+        let (sender, receiver) = bounded(LEADER_SLOT_EVENTS_CHANNEL_SIZE);
+        let num_dropped_events = Arc::<AtomicUsize>::default();
+        let leader_slot_events = LeaderSlotEvents {
+            inner: Some(LeaderSlotEventsSender {
+                sender,
+                num_dropped_events: num_dropped_events.clone(),
+            }),
+        };
+        let thread_hdl = Builder::new()
+            .name("solLdrSlotEvts".to_string())
+            .spawn(move || Self::run(receiver, notifier, num_dropped_events))
+            .unwrap();
+        (Self { thread_hdl }, leader_slot_events)
+    }
+
+    fn run(
+        receiver: Receiver<LeaderSlotEventsMessage>,
+        notifier: LeaderSlotNotifierArc,
+        num_dropped_events: Arc<AtomicUsize>,
+    ) {
+        let mut events_by_slot = HashMap::<Slot, Vec<BankingTransactionEvent>>::new();
+        let mut num_buffered_events = 0;
+        for message in receiver.iter() {
+            match message {
+                LeaderSlotEventsMessage::Record(slot, mut events) => {
+                    if num_buffered_events + events.len() > MAX_BUFFERED_EVENTS {
+                        let num_kept = MAX_BUFFERED_EVENTS.saturating_sub(num_buffered_events);
+                        num_dropped_events.fetch_add(events.len() - num_kept, Ordering::Relaxed);
+                        events.truncate(num_kept);
+                    }
+                    num_buffered_events += events.len();
+                    events_by_slot.entry(slot).or_default().extend(events);
+                }
+                LeaderSlotEventsMessage::NotifyCompletedSlots(current_leader_slot) => {
+                    Self::notify_completed_slots(
+                        &notifier,
+                        &mut events_by_slot,
+                        current_leader_slot,
+                        &num_dropped_events,
+                    );
+                    num_buffered_events = events_by_slot.values().map(Vec::len).sum();
+                }
+            }
+        }
+        Self::notify_completed_slots(&notifier, &mut events_by_slot, None, &num_dropped_events);
+    }
+
+    fn notify_completed_slots(
+        notifier: &LeaderSlotNotifierArc,
+        events_by_slot: &mut HashMap<Slot, Vec<BankingTransactionEvent>>,
+        current_leader_slot: Option<Slot>,
+        num_dropped_events: &AtomicUsize,
+    ) {
+        let current = current_leader_slot.and_then(|slot| events_by_slot.remove_entry(&slot));
+        let mut completed_slots: Vec<_> = events_by_slot.drain().collect();
+        events_by_slot.extend(current);
+
+        completed_slots.sort_unstable_by_key(|(slot, _)| *slot);
+        for (slot, events) in completed_slots {
+            notifier.notify_leader_slot(slot, &events);
+        }
+        let num_dropped_events = num_dropped_events.swap(0, Ordering::Relaxed);
+        if num_dropped_events > 0 {
+            datapoint_warn!(
+                "leader_slot_events-dropped",
+                ("num_dropped_events", num_dropped_events, i64)
+            );
+        }
+    }
+
+    pub fn join(self) -> thread::Result<()> {
+        self.thread_hdl.join()
+    }
+}
+
+pub fn new_event(
+    signature: Signature,
+    kind: BankingTransactionEventKind,
+    reason: Option<TransactionError>,
+) -> BankingTransactionEvent {
+    BankingTransactionEvent {
+        signature,
+        kind,
+        reason,
+    }
+}
+
+/// Returns the event for a transaction which was not committed because of
+/// `err`, if the error is one the scheduler or the cost model reports.
+///
+/// The cost limit errors are reported as retries, as the consumer hands
+/// those transactions back to the scheduler as retryable.
+pub fn event_for_not_committed(
+    signature: Signature,
+    err: &TransactionError,
+) -> Option<BankingTransactionEvent> {
+    let kind = match err {
+        TransactionError::AccountInUse => BankingTransactionEventKind::Retried,
+        TransactionError::WouldExceedMaxBlockCostLimit
+        | TransactionError::WouldExceedMaxVoteCostLimit
+        | TransactionError::WouldExceedMaxAccountCostLimit
+        | TransactionError::WouldExceedAccountDataBlockLimit => {
+            BankingTransactionEventKind::RetriedForCostLimit
+        }
+        TransactionError::TooManyAccountLocks | TransactionError::AccountLoadedTwice => {
+            BankingTransactionEventKind::DroppedForAccountLocks
+        }
+        _ => return None,
+    };
+    Some(new_event(signature, kind, Some(err.clone())))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use {
+        super::*, solana_geyser_plugin_manager::leader_slot_notifier_interface::LeaderSlotNotifier,
+        std::sync::Mutex,
+    };
+
+    #[derive(Default)]
+    pub(crate) struct TestNotifier {
+        pub(crate) notified: Mutex<Vec<(Slot, Vec<BankingTransactionEvent>)>>,
+    }
+
+    impl LeaderSlotNotifier for TestNotifier {
+        fn notify_leader_slot(&self, slot: Slot, transaction_events: &[BankingTransactionEvent]) {
+            self.notified
+                .lock()
+                .unwrap()
+                .push((slot, transaction_events.to_vec()));
+        }
+    }
+
+    #[test]
+    fn test_notify_completed_slots() {
+        let notifier = Arc::new(TestNotifier::default());
+        let (service, leader_slot_events) = LeaderSlotEventsService::new(notifier.clone());
+        assert!(leader_slot_events.is_enabled());
+        let received =
+            |signature| new_event(signature, BankingTransactionEventKind::Received, None);
+        let signature1 = Signature::from([1; 64]);
+        let signature2 = Signature::from([2; 64]);
+        let signature3 = Signature::from([3; 64]);
+        let signature4 = Signature::from([4; 64]);
+
+        leader_slot_events.record(2, [received(signature2)]);
+        leader_slot_events.record(1, [received(signature1)]);
+        leader_slot_events.record(3, [received(signature3)]);
+
+        // the current leader slot is not reported
+        leader_slot_events.notify_completed_slots(Some(3));
+
+        // late events are reported with the next call
+        let retried = event_for_not_committed(signature1, &TransactionError::AccountInUse).unwrap();
+        leader_slot_events.record(1, [retried.clone()]);
+        leader_slot_events.notify_completed_slots(None);
+
+        // the slots still buffered are reported when the service stops
+        leader_slot_events.record(4, [received(signature4)]);
+        drop(leader_slot_events);
+        service.join().unwrap();
+
+        assert_eq!(
+            *notifier.notified.lock().unwrap(),
+            vec![
+                (1, vec![received(signature1)]),
+                (2, vec![received(signature2)]),
+                (1, vec![retried]),
+                (3, vec![received(signature3)]),
+                (4, vec![received(signature4)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_event_for_not_committed() {
+        let signature = Signature::default();
+        let kind = |err| event_for_not_committed(signature, &err).map(|event| event.kind);
+
+        assert_eq!(
+            kind(TransactionError::AccountInUse),
+            Some(BankingTransactionEventKind::Retried)
+        );
+        assert_eq!(
+            kind(TransactionError::WouldExceedMaxAccountCostLimit),
+            Some(BankingTransactionEventKind::RetriedForCostLimit)
+        );
+        assert_eq!(
+            kind(TransactionError::TooManyAccountLocks),
+            Some(BankingTransactionEventKind::DroppedForAccountLocks)
+        );
+        assert_eq!(kind(TransactionError::BlockhashNotFound), None);
+    }
+
+    #[test]
+    fn test_disabled() {
+        let leader_slot_events = LeaderSlotEvents::default();
+        assert!(!leader_slot_events.is_enabled());
+        leader_slot_events.record(
+            1,
+            [new_event(
+                Signature::default(),
+                BankingTransactionEventKind::Scheduled,
+                None,
+            )],
+        );
+        leader_slot_events.notify_completed_slots(None);
+    }
+}
@@ -10,12 +10,16 @@ use {
         },
     },
     crate::banking_stage::{
-        consumer::Consumer, decision_maker::BufferedPacketsDecision,
+        consumer::Consumer,
+        decision_maker::BufferedPacketsDecision,
         immutable_deserialized_packet::ImmutableDeserializedPacket,
-        packet_deserializer::PacketDeserializer, scheduler_messages::MaxAge,
+        leader_slot_events::{new_event, LeaderSlotEvents},
+        packet_deserializer::PacketDeserializer,
+        scheduler_messages::MaxAge,
         TransactionStateContainer,
     },
     agave_banking_stage_ingress_types::{BankingPacketBatch, BankingPacketReceiver},
+    agave_geyser_plugin_interface::geyser_plugin_interface::BankingTransactionEventKind,
     agave_transaction_view::{
         resolved_transaction_view::ResolvedTransactionView,
         transaction_version::TransactionVersion, transaction_view::SanitizedTransactionView,
@@ -35,8 +39,9 @@ use {
         runtime_transaction::RuntimeTransaction, transaction_meta::StaticMeta,
         transaction_with_meta::TransactionWithMeta,
     },
+    solana_signature::Signature,
     solana_svm::transaction_error_metrics::TransactionErrorMetrics,
-    solana_svm_transaction::svm_message::SVMMessage,
+    solana_svm_transaction::{svm_message::SVMMessage, svm_transaction::SVMTransaction},
     solana_transaction::{
         sanitized::{MessageHash, SanitizedTransaction},
         versioned::sanitized::SanitizedVersionedTransaction,
@@ -113,6 +118,7 @@ pub(crate) struct SanitizedTransactionReceiveAndBuffer {
     /// Packet/Transaction ingress.
     packet_receiver: PacketDeserializer,
     bank_forks: Arc<RwLock<BankForks>>,
+    leader_slot_events: LeaderSlotEvents,
 }
 
 impl ReceiveAndBuffer for SanitizedTransactionReceiveAndBuffer {
@@ -152,9 +158,11 @@ impl ReceiveAndBuffer for SanitizedTransactionReceiveAndBuffer {
                     let num_dropped_on_initial_parsing =
                         num_received - receive_packet_results.deserialized_packets.len();
 
-                    let (buffer_stats, buffer_time_us) = measure_us!(
-                        self.buffer_packets(container, receive_packet_results.deserialized_packets)
-                    );
+                    let (buffer_stats, buffer_time_us) = measure_us!(self.buffer_packets(
+                        container,
+                        receive_packet_results.deserialized_packets,
+                        decision.bank().map(|bank| bank.slot()),
+                    ));
                     Ok(ReceivingStats {
                         num_received,
                         num_dropped_without_parsing: 0,
@@ -219,18 +227,30 @@ struct BufferStats {
 }
 
 impl SanitizedTransactionReceiveAndBuffer {
-    pub fn new(packet_receiver: PacketDeserializer, bank_forks: Arc<RwLock<BankForks>>) -> Self {
+    pub fn new(
+        packet_receiver: PacketDeserializer,
+        bank_forks: Arc<RwLock<BankForks>>,
+        leader_slot_events: LeaderSlotEvents,
+    ) -> Self {
         Self {
             packet_receiver,
             bank_forks,
+            leader_slot_events,
         }
     }
 
+    /// Buffer `packets` into `container`. If `leader_slot` is set, the
+    /// transactions buffered or dropped are recorded as events of that slot.
+    /// Packets dropped before being sanitized carry no signature, and are
+    /// only counted.
     fn buffer_packets(
         &mut self,
         container: &mut TransactionStateContainer<RuntimeTransaction<SanitizedTransaction>>,
         packets: Vec<ImmutableDeserializedPacket>,
+        leader_slot: Option<Slot>,
     ) -> BufferStats {
+        let leader_slot = leader_slot.filter(|_| self.leader_slot_events.is_enabled());
+        let mut events = Vec::new();
         // Convert to Arcs
         let packets: Vec<_> = packets.into_iter().map(Arc::new).collect();
         // Sanitize packets, generate IDs, and insert into the container.
@@ -277,23 +297,37 @@ impl SanitizedTransactionReceiveAndBuffer {
                     continue;
                 };
 
-                if validate_account_locks(
+                if let Err(err) = validate_account_locks(
                     tx.message().account_keys(),
                     transaction_account_lock_limit,
-                )
-                .is_err()
-                {
+                ) {
                     num_dropped_on_lock_validation += 1;
+                    if leader_slot.is_some() {
+                        events.push(new_event(
+                            *tx.signature(),
+                            BankingTransactionEventKind::DroppedForAccountLocks,
+                            Some(err),
+                        ));
+                    }
                     continue;
                 }
 
-                let Ok(fee_budget_limits) = tx
+                let fee_budget_limits = match tx
                     .compute_budget_instruction_details()
                     .sanitize_and_convert_to_compute_budget_limits(&working_bank.feature_set)
-                    .map(|compute_budget| compute_budget.into())
-                else {
-                    num_dropped_on_compute_budget += 1;
-                    continue;
+                {
+                    Ok(compute_budget) => compute_budget.into(),
+                    Err(err) => {
+                        num_dropped_on_compute_budget += 1;
+                        if leader_slot.is_some() {
+                            events.push(new_event(
+                                *tx.signature(),
+                                BankingTransactionEventKind::Dropped,
+                                Some(err),
+                            ));
+                        }
+                        continue;
+                    }
                 };
 
                 transactions.push(tx);
@@ -318,42 +352,76 @@ impl SanitizedTransactionReceiveAndBuffer {
                 .zip(fee_budget_limits_vec.drain(..))
                 .zip(check_results)
             {
-                match check_result {
-                    Ok(_) => {}
-                    Err(err) => {
-                        match err {
-                            TransactionError::BlockhashNotFound => {
-                                num_dropped_on_age += 1;
-                            }
-                            TransactionError::AlreadyProcessed => {
-                                num_dropped_on_already_processed += 1;
-                            }
-                            _ => {}
+                if let Err(err) = check_result {
+                    match err {
+                        TransactionError::BlockhashNotFound => {
+                            num_dropped_on_age += 1;
                         }
-                        continue;
+                        TransactionError::AlreadyProcessed => {
+                            num_dropped_on_already_processed += 1;
+                        }
+                        _ => {}
+                    }
+                    if leader_slot.is_some() {
+                        events.push(new_event(
+                            *transaction.signature(),
+                            BankingTransactionEventKind::Dropped,
+                            Some(err),
+                        ));
                     }
+                    continue;
                 }
 
-                if Consumer::check_fee_payer_unlocked(
+                if let Err(err) = Consumer::check_fee_payer_unlocked(
                     &working_bank,
                     &transaction,
                     &mut error_counts,
-                )
-                .is_err()
-                {
+                ) {
                     num_dropped_on_fee_payer += 1;
+                    if leader_slot.is_some() {
+                        events.push(new_event(
+                            *transaction.signature(),
+                            BankingTransactionEventKind::Dropped,
+                            Some(err),
+                        ));
+                    }
                     continue;
                 }
 
                 let (priority, cost) =
                     calculate_priority_and_cost(&transaction, &fee_budget_limits, &working_bank);
                 num_buffered += 1;
-                if container.insert_new_transaction(transaction, max_age, priority, cost) {
+                if leader_slot.is_some() {
+                    events.push(new_event(
+                        *transaction.signature(),
+                        BankingTransactionEventKind::Received,
+                        None,
+                    ));
+                }
+                if container.insert_new_transaction_with_dropped(
+                    transaction,
+                    max_age,
+                    priority,
+                    cost,
+                    |dropped| {
+                        if leader_slot.is_some() {
+                            events.push(new_event(
+                                *dropped.signature(),
+                                BankingTransactionEventKind::Dropped,
+                                None,
+                            ));
+                        }
+                    },
+                ) {
                     num_dropped_on_capacity += 1;
                 }
             }
         }
 
+        if let Some(leader_slot) = leader_slot {
+            self.leader_slot_events.record(leader_slot, events);
+        }
+
         BufferStats {
             num_dropped_on_sanitization,
             num_dropped_on_lock_validation,
@@ -371,6 +439,7 @@ impl SanitizedTransactionReceiveAndBuffer {
 pub(crate) struct TransactionViewReceiveAndBuffer {
     pub receiver: BankingPacketReceiver,
     pub bank_forks: Arc<RwLock<BankForks>>,
+    pub leader_slot_events: LeaderSlotEvents,
 }
 
 impl ReceiveAndBuffer for TransactionViewReceiveAndBuffer {
@@ -508,8 +577,8 @@ fn total_num_locks(tx: &SanitizedVersionedTransaction) -> usize {
 
 enum PacketHandlingError {
     Sanitization,
-    LockValidation,
-    ComputeBudget,
+    LockValidation(Signature, TransactionError),
+    ComputeBudget(Signature, TransactionError),
 }
 
 impl TransactionViewReceiveAndBuffer {
@@ -525,6 +594,15 @@ impl TransactionViewReceiveAndBuffer {
         let start = Instant::now();
         // If outside holding window, do not parse.
         let should_parse = !matches!(decision, BufferedPacketsDecision::Forward);
+        // Only record events while leader, and if anyone is interested in them.
+        // Packets dropped before being sanitized carry no signature, and are
+        // only counted.
+        let leader_slot = decision
+            .bank()
+            .map(|bank| bank.slot())
+            .filter(|_| self.leader_slot_events.is_enabled());
+        let mut checked = Vec::new();
+        let mut dropped_on_handling = Vec::new();
 
         // Sanitize packets, generate IDs, and insert into the container.
         let alt_resolved_slot = root_bank.slot();
@@ -566,6 +644,9 @@ impl TransactionViewReceiveAndBuffer {
                     .iter_mut()
                     .zip(transaction_priority_ids.iter())
                 {
+                    let transaction = container
+                        .get_transaction(priority_id.id)
+                        .expect("transaction must exist");
                     if let Err(err) = result {
                         match err {
                             TransactionError::BlockhashNotFound => {
@@ -576,32 +657,59 @@ impl TransactionViewReceiveAndBuffer {
                             }
                             _ => {}
                         }
+                        if leader_slot.is_some() {
+                            checked.push(new_event(
+                                *transaction.signature(),
+                                BankingTransactionEventKind::Dropped,
+                                Some(err.clone()),
+                            ));
+                        }
                         container.remove_by_id(priority_id.id);
                         continue;
                     }
-                    let transaction = container
-                        .get_transaction(priority_id.id)
-                        .expect("transaction must exist");
                     if let Err(err) = Consumer::check_fee_payer_unlocked(
                         working_bank,
                         transaction,
                         &mut error_counters,
                     ) {
-                        *result = Err(err);
                         num_dropped_on_fee_payer += 1;
+                        if leader_slot.is_some() {
+                            checked.push(new_event(
+                                *transaction.signature(),
+                                BankingTransactionEventKind::Dropped,
+                                Some(err.clone()),
+                            ));
+                        }
+                        *result = Err(err);
                         container.remove_by_id(priority_id.id);
                         continue;
                     }
 
                     num_buffered += 1;
+                    if leader_slot.is_some() {
+                        checked.push(new_event(
+                            *transaction.signature(),
+                            BankingTransactionEventKind::Received,
+                            None,
+                        ));
+                    }
                 }
                 // Push non-errored transaction into queue.
-                num_dropped_on_capacity += container.push_ids_into_queue(
+                num_dropped_on_capacity += container.push_ids_into_queue_with_dropped(
                     check_results
                         .into_iter()
                         .zip(transaction_priority_ids.drain(..))
                         .filter(|(r, _)| r.is_ok())
                         .map(|(_, id)| id),
+                    |dropped| {
+                        if leader_slot.is_some() {
+                            checked.push(new_event(
+                                *dropped.signature(),
+                                BankingTransactionEventKind::Dropped,
+                                None,
+                            ));
+                        }
+                    },
                 );
             };
 
@@ -639,12 +747,26 @@ impl TransactionViewReceiveAndBuffer {
                                 num_dropped_on_parsing_and_sanitization += 1;
                                 Err(())
                             }
-                            Err(PacketHandlingError::LockValidation) => {
+                            Err(PacketHandlingError::LockValidation(signature, err)) => {
                                 num_dropped_on_lock_validation += 1;
+                                if leader_slot.is_some() {
+                                    dropped_on_handling.push(new_event(
+                                        signature,
+                                        BankingTransactionEventKind::DroppedForAccountLocks,
+                                        Some(err),
+                                    ));
+                                }
                                 Err(())
                             }
-                            Err(PacketHandlingError::ComputeBudget) => {
+                            Err(PacketHandlingError::ComputeBudget(signature, err)) => {
                                 num_dropped_on_compute_budget += 1;
+                                if leader_slot.is_some() {
+                                    dropped_on_handling.push(new_event(
+                                        signature,
+                                        BankingTransactionEventKind::Dropped,
+                                        Some(err),
+                                    ));
+                                }
                                 Err(())
                             }
                         }
@@ -668,6 +790,11 @@ impl TransactionViewReceiveAndBuffer {
         // Any remaining packets undergo status/age checks
         check_and_push_to_queue(container, &mut transaction_priority_ids);
 
+        if let Some(leader_slot) = leader_slot {
+            self.leader_slot_events
+                .record(leader_slot, dropped_on_handling.into_iter().chain(checked));
+        }
+
         ReceivingStats {
             num_received,
             num_dropped_without_parsing,
@@ -715,8 +842,13 @@ impl TransactionViewReceiveAndBuffer {
             return Err(PacketHandlingError::Sanitization);
         }
 
+        // Sanitized transactions always have a signature.
+        let signature = view.signatures()[0];
         if usize::from(view.total_num_accounts()) > transaction_account_lock_limit {
-            return Err(PacketHandlingError::LockValidation);
+            return Err(PacketHandlingError::LockValidation(
+                signature,
+                TransactionError::TooManyAccountLocks,
+            ));
         }
 
         // Load addresses for transaction.
@@ -740,16 +872,16 @@ impl TransactionViewReceiveAndBuffer {
             return Err(PacketHandlingError::Sanitization);
         };
 
-        if validate_account_locks(view.account_keys(), transaction_account_lock_limit).is_err() {
-            return Err(PacketHandlingError::LockValidation);
+        if let Err(err) =
+            validate_account_locks(view.account_keys(), transaction_account_lock_limit)
+        {
+            return Err(PacketHandlingError::LockValidation(signature, err));
         }
 
-        let Ok(compute_budget_limits) = view
+        let compute_budget_limits = view
             .compute_budget_instruction_details()
             .sanitize_and_convert_to_compute_budget_limits(&working_bank.feature_set)
-        else {
-            return Err(PacketHandlingError::ComputeBudget);
-        };
+            .map_err(|err| PacketHandlingError::ComputeBudget(signature, err))?;
 
         let max_age = calculate_max_age(sanitized_epoch, deactivation_slot, alt_resolved_slot);
         let fee_budget_limits = FeeBudgetLimits::from(compute_budget_limits);
@@ -830,7 +962,10 @@ fn calculate_max_age(
 mod tests {
     use {
         super::*,
-        crate::banking_stage::tests::create_slow_genesis_config,
+        crate::banking_stage::{
+            leader_slot_events::{tests::TestNotifier, LeaderSlotEventsService},
+            tests::create_slow_genesis_config,
+        },
         crossbeam_channel::{unbounded, Receiver},
         solana_hash::Hash,
         solana_keypair::Keypair,
@@ -867,10 +1002,26 @@ mod tests {
     ) -> (
         SanitizedTransactionReceiveAndBuffer,
         TransactionStateContainer<RuntimeTransaction<SanitizedTransaction>>,
+    ) {
+        setup_sanitized_transaction_receive_and_buffer_with_leader_slot_events(
+            receiver,
+            bank_forks,
+            LeaderSlotEvents::default(),
+        )
+    }
+
+    fn setup_sanitized_transaction_receive_and_buffer_with_leader_slot_events(
+        receiver: Receiver<BankingPacketBatch>,
+        bank_forks: Arc<RwLock<BankForks>>,
+        leader_slot_events: LeaderSlotEvents,
+    ) -> (
+        SanitizedTransactionReceiveAndBuffer,
+        TransactionStateContainer<RuntimeTransaction<SanitizedTransaction>>,
     ) {
         let receive_and_buffer = SanitizedTransactionReceiveAndBuffer {
             packet_receiver: PacketDeserializer::new(receiver),
             bank_forks,
+            leader_slot_events,
         };
         let container = TransactionStateContainer::with_capacity(TEST_CONTAINER_CAPACITY);
         (receive_and_buffer, container)
@@ -882,10 +1033,26 @@ mod tests {
     ) -> (
         TransactionViewReceiveAndBuffer,
         TransactionViewStateContainer,
+    ) {
+        setup_transaction_view_receive_and_buffer_with_leader_slot_events(
+            receiver,
+            bank_forks,
+            LeaderSlotEvents::default(),
+        )
+    }
+
+    fn setup_transaction_view_receive_and_buffer_with_leader_slot_events(
+        receiver: Receiver<BankingPacketBatch>,
+        bank_forks: Arc<RwLock<BankForks>>,
+        leader_slot_events: LeaderSlotEvents,
+    ) -> (
+        TransactionViewReceiveAndBuffer,
+        TransactionViewStateContainer,
     ) {
         let receive_and_buffer = TransactionViewReceiveAndBuffer {
             receiver,
             bank_forks,
+            leader_slot_events,
         };
         let container = TransactionViewStateContainer::with_capacity(TEST_CONTAINER_CAPACITY);
         (receive_and_buffer, container)
@@ -1165,6 +1332,77 @@ mod tests {
         verify_container(&mut container, 0);
     }
 
+    #[test_case(
+        setup_sanitized_transaction_receive_and_buffer_with_leader_slot_events;
+        "testcase-sdk"
+    )]
+    #[test_case(
+        setup_transaction_view_receive_and_buffer_with_leader_slot_events;
+        "testcase-view"
+    )]
+    fn test_receive_and_buffer_leader_slot_events<R: ReceiveAndBuffer>(
+        setup_receive_and_buffer: impl FnOnce(
+            Receiver<BankingPacketBatch>,
+            Arc<RwLock<BankForks>>,
+            LeaderSlotEvents,
+        ) -> (R, R::Container),
+    ) {
+        let notifier = Arc::new(TestNotifier::default());
+        let (leader_slot_events_service, leader_slot_events) =
+            LeaderSlotEventsService::new(notifier.clone());
+        let (sender, receiver) = unbounded();
+        let (bank_forks, mint_keypair) = test_bank_forks();
+        let (mut receive_and_buffer, mut container) =
+            setup_receive_and_buffer(receiver, bank_forks.clone(), leader_slot_events);
+
+        let bank = bank_forks.read().unwrap().working_bank();
+        let transfer_with_blockhash =
+            |blockhash| transfer(&mint_keypair, &Pubkey::new_unique(), 1, blockhash);
+        let transactions = [
+            transfer_with_blockhash(bank.last_blockhash()),
+            transfer_with_blockhash(Hash::new_unique()),
+        ];
+        let packet_batches = Arc::new(to_packet_batches(&transactions, 2));
+        sender.send(packet_batches).unwrap();
+
+        let ReceivingStats {
+            num_received,
+            num_dropped_on_age,
+            num_buffered,
+            ..
+        } = receive_and_buffer
+            .receive_and_buffer_packets(
+                &mut container,
+                &BufferedPacketsDecision::Consume(bank.clone()),
+            )
+            .unwrap();
+        assert_eq!(num_received, 2);
+        assert_eq!(num_dropped_on_age, 1);
+        assert_eq!(num_buffered, 1);
+        verify_container(&mut container, 1);
+
+        drop(receive_and_buffer);
+        leader_slot_events_service.join().unwrap();
+        assert_eq!(
+            *notifier.notified.lock().unwrap(),
+            vec![(
+                bank.slot(),
+                vec![
+                    new_event(
+                        transactions[0].signatures[0],
+                        BankingTransactionEventKind::Received,
+                        None,
+                    ),
+                    new_event(
+                        transactions[1].signatures[0],
+                        BankingTransactionEventKind::Dropped,
+                        Some(TransactionError::BlockhashNotFound),
+                    ),
+                ]
+            )]
+        );
+    }
+
     #[test_case(setup_sanitized_transaction_receive_and_buffer; "testcase-sdk")]
     #[test_case(setup_transaction_view_receive_and_buffer; "testcase-view")]
     fn test_receive_and_buffer_simple_transfer_unfunded_fee_payer<R: ReceiveAndBuffer>(
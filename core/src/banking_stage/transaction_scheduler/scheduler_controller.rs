@@ -13,6 +13,7 @@ use {
             consume_worker::ConsumeWorkerMetrics,
             consumer::Consumer,
             decision_maker::{BufferedPacketsDecision, DecisionMaker},
+            leader_slot_events::{new_event, LeaderSlotEvents},
             transaction_scheduler::{
                 receive_and_buffer::ReceivingStats, transaction_state_container::StateContainer,
             },
@@ -20,6 +21,7 @@ use {
         },
         validator::SchedulerPacing,
     },
+    agave_geyser_plugin_interface::geyser_plugin_interface::BankingTransactionEventKind,
     solana_clock::MAX_PROCESSING_AGE,
     solana_cost_model::cost_tracker::SharedBlockCost,
     solana_measure::measure_us,
@@ -81,6 +83,8 @@ where
     worker_metrics: Vec<Arc<ConsumeWorkerMetrics>>,
     /// Detailed scheduling metrics.
     scheduling_details: SchedulingDetails,
+    /// Banking stage events of leader slots, reported once the slot is over.
+    leader_slot_events: LeaderSlotEvents,
 }
 
 impl<R, S> SchedulerController<R, S>
@@ -96,6 +100,7 @@ where
         bank_forks: Arc<RwLock<BankForks>>,
         scheduler: S,
         worker_metrics: Vec<Arc<ConsumeWorkerMetrics>>,
+        leader_slot_events: LeaderSlotEvents,
     ) -> Self {
         Self {
             exit,
@@ -109,6 +114,7 @@ where
             timing_metrics: SchedulerTimingMetrics::default(),
             worker_metrics,
             scheduling_details: SchedulingDetails::default(),
+            leader_slot_events,
         }
     }
 
@@ -141,6 +147,8 @@ where
 
             if most_recent_leader_slot != new_leader_slot {
                 self.container.flush_held_transactions();
+                self.leader_slot_events
+                    .notify_completed_slots(new_leader_slot);
                 most_recent_leader_slot = new_leader_slot;
                 cost_pacer = decision.bank().map(|b| {
                     let cost_tracker = b.read_cost_tracker().unwrap();
@@ -211,11 +219,18 @@ where
                 let scheduling_budget = cost_pacer
                     .expect("cost pacer must be set for Consume")
                     .scheduling_budget(now);
+                let leader_slot_events = &self.leader_slot_events;
                 let (scheduling_summary, schedule_time_us) = measure_us!(self.scheduler.schedule(
                     &mut self.container,
                     scheduling_budget,
                     |txs, results| {
-                        Self::pre_graph_filter(txs, results, bank, MAX_PROCESSING_AGE)
+                        Self::pre_graph_filter(
+                            txs,
+                            results,
+                            bank,
+                            MAX_PROCESSING_AGE,
+                            leader_slot_events,
+                        )
                     },
                     |_| PreLockFilterAction::AttemptToSchedule // no pre-lock filter for now
                 )?);
@@ -253,11 +268,14 @@ where
         Ok(())
     }
 
+    /// Filters out the transactions which can no longer be processed, and
+    /// records them as dropped in the leader slot of `bank`.
     fn pre_graph_filter(
         transactions: &[&R::Transaction],
         results: &mut [bool],
         bank: &Bank,
         max_age: usize,
+        leader_slot_events: &LeaderSlotEvents,
     ) {
         let lock_results = vec![Ok(()); transactions.len()];
         let mut error_counters = TransactionErrorMetrics::default();
@@ -268,15 +286,26 @@ where
             &mut error_counters,
         );
 
+        let mut dropped = Vec::new();
         for ((check_result, tx), result) in check_results
             .into_iter()
             .zip(transactions)
             .zip(results.iter_mut())
         {
-            *result = check_result
-                .and_then(|_| Consumer::check_fee_payer_unlocked(bank, *tx, &mut error_counters))
-                .is_ok();
+            let check_result = check_result
+                .and_then(|_| Consumer::check_fee_payer_unlocked(bank, *tx, &mut error_counters));
+            *result = check_result.is_ok();
+            if let Err(err) = check_result {
+                if leader_slot_events.is_enabled() {
+                    dropped.push(new_event(
+                        *tx.signature(),
+                        BankingTransactionEventKind::Dropped,
+                        Some(err),
+                    ));
+                }
+            }
         }
+        leader_slot_events.record(bank.slot(), dropped);
     }
 
     /// Clears the transaction state container.
@@ -503,7 +532,11 @@ mod tests {
         receiver: BankingPacketReceiver,
         bank_forks: Arc<RwLock<BankForks>>,
     ) -> SanitizedTransactionReceiveAndBuffer {
-        SanitizedTransactionReceiveAndBuffer::new(PacketDeserializer::new(receiver), bank_forks)
+        SanitizedTransactionReceiveAndBuffer::new(
+            PacketDeserializer::new(receiver),
+            bank_forks,
+            LeaderSlotEvents::default(),
+        )
     }
 
     fn test_create_transaction_view_receive_and_buffer(
@@ -513,6 +546,7 @@ mod tests {
         TransactionViewReceiveAndBuffer {
             receiver,
             bank_forks,
+            leader_slot_events: LeaderSlotEvents::default(),
         }
     }
 
@@ -572,6 +606,7 @@ mod tests {
             bank_forks,
             scheduler,
             vec![], // no actual workers with metrics to report, this can be empty
+            LeaderSlotEvents::default(),
         );
 
         (test_frame, scheduler_controller)
@@ -100,6 +100,16 @@ pub(crate) trait StateContainer<Tx: TransactionWithMeta> {
     fn push_ids_into_queue(
         &mut self,
         priority_ids: impl Iterator<Item = TransactionPriorityId>,
+    ) -> usize {
+        self.push_ids_into_queue_with_dropped(priority_ids, |_| {})
+    }
+
+    /// Same as [`Self::push_ids_into_queue`], but calls `on_dropped` with
+    /// each transaction dropped because the container is full.
+    fn push_ids_into_queue_with_dropped(
+        &mut self,
+        priority_ids: impl Iterator<Item = TransactionPriorityId>,
+        on_dropped: impl FnMut(&Tx),
     ) -> usize;
 
     /// Hold the tarnsaction until the next flush (next slot).
@@ -159,9 +169,10 @@ impl<Tx: TransactionWithMeta> StateContainer<Tx> for TransactionStateContainer<T
             .map(|state| state.transaction())
     }
 
-    fn push_ids_into_queue(
+    fn push_ids_into_queue_with_dropped(
         &mut self,
         priority_ids: impl Iterator<Item = TransactionPriorityId>,
+        mut on_dropped: impl FnMut(&Tx),
     ) -> usize {
         for id in priority_ids {
             self.priority_queue.push(id);
@@ -178,7 +189,8 @@ impl<Tx: TransactionWithMeta> StateContainer<Tx> for TransactionStateContainer<T
 
         for _ in 0..num_dropped {
             let priority_id = self.priority_queue.pop_min().expect("queue is not empty");
-            self.id_to_transaction_state.remove(priority_id.id);
+            let state = self.id_to_transaction_state.remove(priority_id.id);
+            on_dropped(state.transaction());
         }
 
         num_dropped
@@ -225,6 +237,19 @@ impl<Tx: TransactionWithMeta> TransactionStateContainer<Tx> {
         max_age: MaxAge,
         priority: u64,
         cost: u64,
+    ) -> bool {
+        self.insert_new_transaction_with_dropped(transaction, max_age, priority, cost, |_| {})
+    }
+
+    /// Same as [`Self::insert_new_transaction`], but calls `on_dropped` with
+    /// the transaction dropped because the container is full, if any.
+    pub(crate) fn insert_new_transaction_with_dropped(
+        &mut self,
+        transaction: Tx,
+        max_age: MaxAge,
+        priority: u64,
+        cost: u64,
+        on_dropped: impl FnMut(&Tx),
     ) -> bool {
         let priority_id = {
             let entry = self.get_vacant_map_entry();
@@ -233,7 +258,7 @@ impl<Tx: TransactionWithMeta> TransactionStateContainer<Tx> {
             TransactionPriorityId::new(priority, transaction_id)
         };
 
-        self.push_ids_into_queue(std::iter::once(priority_id)) > 0
+        self.push_ids_into_queue_with_dropped(std::iter::once(priority_id), on_dropped) > 0
     }
 
     fn get_vacant_map_entry(&mut self) -> VacantEntry<TransactionState<Tx>> {
@@ -343,11 +368,13 @@ impl StateContainer<RuntimeTransactionView> for TransactionViewStateContainer {
     }
 
     #[inline]
-    fn push_ids_into_queue(
+    fn push_ids_into_queue_with_dropped(
         &mut self,
         priority_ids: impl Iterator<Item = TransactionPriorityId>,
+        on_dropped: impl FnMut(&RuntimeTransactionView),
     ) -> usize {
-        self.inner.push_ids_into_queue(priority_ids)
+        self.inner
+            .push_ids_into_queue_with_dropped(priority_ids, on_dropped)
     }
 
     #[inline]
@@ -38,6 +38,8 @@ pub mod stats_reporter_service;
 pub mod system_monitor_service;
 pub mod tpu;
 mod tpu_entry_notifier;
+pub mod tpu_ingress_filter_manager;
+pub mod tpu_ingress_filter_stage;
 pub mod tvu;
 pub mod unfrozen_gossip_verified_vote_hashes;
 pub mod validator;
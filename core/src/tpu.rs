@@ -23,12 +23,15 @@ use {
         sigverify_stage::SigVerifyStage,
         staked_nodes_updater_service::StakedNodesUpdaterService,
         tpu_entry_notifier::TpuEntryNotifier,
+        tpu_ingress_filter_manager::TpuIngressFilterManager,
+        tpu_ingress_filter_stage::TpuIngressFilterStage,
         validator::{BlockProductionMethod, GeneratorConfig, TransactionStructure},
         vortexor_receiver_adapter::VortexorReceiverAdapter,
     },
     bytes::Bytes,
     crossbeam_channel::{bounded, unbounded, Receiver},
     solana_clock::Slot,
+    solana_geyser_plugin_manager::leader_slot_notifier_interface::LeaderSlotNotifierArc,
     solana_gossip::cluster_info::ClusterInfo,
     solana_keypair::Keypair,
     solana_ledger::{
@@ -51,6 +54,7 @@ use {
         vote_sender_types::{ReplayVoteReceiver, ReplayVoteSender},
     },
     solana_streamer::{
+        nonblocking::connection_qos::ConnectionQosRegistry,
         quic::{spawn_server_with_cancel, QuicServerParams, SpawnServerResult},
         streamer::StakedNodes,
     },
@@ -84,15 +88,21 @@ pub struct TpuSockets {
 }
 
 /// The `SigVerifier` enum is used to determine whether to use a local or remote signature verifier.
+/// The local signature verifier is preceded by the TPU ingress filter stage, if it is enabled.
 enum SigVerifier {
-    Local(SigVerifyStage),
+    Local(Option<TpuIngressFilterStage>, SigVerifyStage),
     Remote(VortexorReceiverAdapter),
 }
 
 impl SigVerifier {
     fn join(self) -> thread::Result<()> {
         match self {
-            SigVerifier::Local(sig_verify_stage) => sig_verify_stage.join(),
+            SigVerifier::Local(tpu_ingress_filter_stage, sig_verify_stage) => {
+                if let Some(tpu_ingress_filter_stage) = tpu_ingress_filter_stage {
+                    tpu_ingress_filter_stage.join()?;
+                }
+                sig_verify_stage.join()
+            }
             SigVerifier::Remote(vortexor_receiver_adapter) => vortexor_receiver_adapter.join(),
         }
     }
@@ -112,6 +122,7 @@ pub struct Tpu {
     staked_nodes_updater_service: StakedNodesUpdaterService,
     tracer_thread_hdl: TracerThread,
     tpu_vote_quic_t: thread::JoinHandle<()>,
+    connection_qos: Vec<Arc<ConnectionQosRegistry>>,
 }
 
 impl Tpu {
@@ -153,6 +164,7 @@ impl Tpu {
         tpu_fwd_quic_server_config: QuicServerParams,
         vote_quic_server_config: QuicServerParams,
         prioritization_fee_cache: &Arc<PrioritizationFeeCache>,
+        leader_slot_notifier: Option<LeaderSlotNotifierArc>,
         block_production_method: BlockProductionMethod,
         block_production_num_workers: NonZeroUsize,
         block_production_scheduler_config: SchedulerConfig,
@@ -160,6 +172,7 @@ impl Tpu {
         enable_block_production_forwarding: bool,
         _generator_config: Option<GeneratorConfig>, /* vestigial code for replay invalidator */
         key_notifiers: Arc<RwLock<KeyUpdaters>>,
+        tpu_ingress_filter_manager: Arc<RwLock<TpuIngressFilterManager>>,
         cancel: CancellationToken,
     ) -> Self {
         let TpuSockets {
@@ -213,6 +226,7 @@ impl Tpu {
             endpoints: _,
             thread: tpu_vote_quic_t,
             key_updater: vote_streamer_key_updater,
+            connection_qos: vote_connection_qos,
         } = spawn_server_with_cancel(
             "solQuicTVo",
             "quic_streamer_tpu_vote",
@@ -224,6 +238,7 @@ impl Tpu {
             cancel.clone(),
         )
         .unwrap();
+        let mut connection_qos = vec![vote_connection_qos];
 
         let (tpu_quic_t, key_updater) = if vortexor_receivers.is_none() {
             // Streamer for TPU
@@ -231,6 +246,7 @@ impl Tpu {
                 endpoints: _,
                 thread: tpu_quic_t,
                 key_updater,
+                connection_qos: tpu_connection_qos,
             } = spawn_server_with_cancel(
                 "solQuicTpu",
                 "quic_streamer_tpu",
@@ -242,6 +258,7 @@ impl Tpu {
                 cancel.clone(),
             )
             .unwrap();
+            connection_qos.push(tpu_connection_qos);
             (Some(tpu_quic_t), Some(key_updater))
         } else {
             (None, None)
@@ -253,6 +270,7 @@ impl Tpu {
                 endpoints: _,
                 thread: tpu_forwards_quic_t,
                 key_updater: forwards_key_updater,
+                connection_qos: tpu_forwards_connection_qos,
             } = spawn_server_with_cancel(
                 "solQuicTpuFwd",
                 "quic_streamer_tpu_forwards",
@@ -264,6 +282,7 @@ impl Tpu {
                 cancel,
             )
             .unwrap();
+            connection_qos.push(tpu_forwards_connection_qos);
             (Some(tpu_forwards_quic_t), Some(forwards_key_updater))
         } else {
             (None, None)
@@ -288,12 +307,22 @@ impl Tpu {
                 non_vote_sender,
                 enable_block_production_forwarding.then(|| forward_stage_sender.clone()),
             );
-            SigVerifier::Local(SigVerifyStage::new(
-                packet_receiver,
-                verifier,
-                "solSigVerTpu",
-                "tpu-verifier",
-            ))
+            let (tpu_ingress_filter_stage, packet_receiver) =
+                if tpu_ingress_filter_manager.read().unwrap().is_enabled() {
+                    let (filtered_packet_sender, filtered_packet_receiver) = unbounded();
+                    let tpu_ingress_filter_stage = TpuIngressFilterStage::new(
+                        packet_receiver,
+                        filtered_packet_sender,
+                        tpu_ingress_filter_manager,
+                    );
+                    (Some(tpu_ingress_filter_stage), filtered_packet_receiver)
+                } else {
+                    (None, packet_receiver)
+                };
+            SigVerifier::Local(
+                tpu_ingress_filter_stage,
+                SigVerifyStage::new(packet_receiver, verifier, "solSigVerTpu", "tpu-verifier"),
+            )
         };
 
         let vote_sigverify_stage = {
@@ -339,6 +368,7 @@ impl Tpu {
             log_messages_bytes_limit,
             bank_forks.clone(),
             prioritization_fee_cache.clone(),
+            leader_slot_notifier,
         );
 
         let SpawnForwardingStageResult {
@@ -405,6 +435,7 @@ impl Tpu {
             staked_nodes_updater_service,
             tracer_thread_hdl,
             tpu_vote_quic_t,
+            connection_qos,
         }
     }
 
@@ -412,6 +443,11 @@ impl Tpu {
         self.banking_stage.clone()
     }
 
+    /// Returns the per-connection accounting of the QUIC servers of the TPU.
+    pub fn connection_qos(&self) -> Vec<Arc<ConnectionQosRegistry>> {
+        self.connection_qos.clone()
+    }
+
     pub fn join(self) -> thread::Result<()> {
         let results = vec![
             self.fetch_stage.join(),
@@ -0,0 +1,470 @@
+//! The TPU ingress filter manager loads the TPU ingress filters from dynamic
+//! libraries, in the same way Geyser plugins are loaded, and applies them to
+//! the packets received by the TPU.
+
+use {
+    agave_tpu_ingress_filter_interface::tpu_ingress_filter_interface::{
+        IngressFilterAction, IngressPacketInfo, IngressPacketInfoVersions, TpuIngressFilter,
+    },
+    libloading::Library,
+    std::{
+        ops::{Deref, DerefMut},
+        path::Path,
+    },
+    thiserror::Error,
+};
+
+#[derive(Debug)]
+pub struct LoadedTpuIngressFilter {
+    name: String,
+    filter: Box<dyn TpuIngressFilter>,
+    // NOTE: While we do not access the library, the filter we have loaded most
+    // certainly does. To ensure we don't SIGSEGV we must declare the library
+    // after the filter so the filter is dropped first.
+    #[allow(dead_code)]
+    library: Library,
+}
+
+impl LoadedTpuIngressFilter {
+    pub fn new(library: Library, filter: Box<dyn TpuIngressFilter>, name: Option<String>) -> Self {
+        Self {
+            name: name.unwrap_or_else(|| filter.name().to_owned()),
+            filter,
+            library,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Deref for LoadedTpuIngressFilter {
+    type Target = Box<dyn TpuIngressFilter>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.filter
+    }
+}
+
+impl DerefMut for LoadedTpuIngressFilter {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.filter
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum TpuIngressFilterManagerError {
+    #[error("Cannot open the filter config file: {0}")]
+    CannotOpenConfigFile(String),
+
+    #[error("Cannot read the filter config file: {0}")]
+    CannotReadConfigFile(String),
+
+    #[error("The config file is not in a valid Json5 format: {0}")]
+    InvalidConfigFileFormat(String),
+
+    #[error("Invalid filter path")]
+    InvalidFilterPath,
+
+    #[error("Cannot load filter shared library (error: {0})")]
+    FilterLoadError(String),
+
+    #[error("A filter named {0} is already loaded")]
+    FilterAlreadyLoaded(String),
+
+    #[error("The filter {0} is not loaded")]
+    FilterNotLoaded(String),
+
+    #[error("The setup_logger method of filter {0} failed (error: {1})")]
+    FilterSetupLoggerError(String, String),
+
+    #[error("The on_load method of filter {0} failed (error: {1})")]
+    FilterStartError(String, String),
+
+    #[error(
+        "The TPU ingress filter stage is not running, restart the validator with \
+         --tpu-ingress-filter-config to load filters"
+    )]
+    FilterStageNotRunning,
+}
+
+#[derive(Default, Debug)]
+pub struct TpuIngressFilterManager {
+    filters: Vec<LoadedTpuIngressFilter>,
+    /// Whether the TPU runs the filter stage, without which filters cannot be loaded
+    enabled: bool,
+}
+
+impl TpuIngressFilterManager {
+    /// The TPU only runs the filter stage for an `enabled` manager, so that the
+    /// packets of validators without filters reach signature verification
+    /// without going through an extra stage
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            filters: Vec::default(),
+            enabled,
+        }
+    }
+
+    /// Whether the TPU runs the filter stage, and filters can be loaded
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Whether no filter is loaded, in which case every packet is accepted
+    pub fn is_empty(&self) -> bool {
+        self.filters.is_empty()
+    }
+
+    /// Returns the names of the loaded filters
+    pub fn list_filters(&self) -> Vec<String> {
+        self.filters
+            .iter()
+            .map(|filter| filter.name().to_owned())
+            .collect()
+    }
+
+    /// Applies every loaded filter to `packet`. The packet is dropped if any
+    /// filter drops it, and deprioritized if any filter deprioritizes it.
+    pub fn filter_packet(&self, packet: &IngressPacketInfo) -> IngressFilterAction {
+        let mut action = IngressFilterAction::Accept;
+        for filter in &self.filters {
+            match filter.filter_packet(IngressPacketInfoVersions::V0_0_1(packet)) {
+                IngressFilterAction::Drop => return IngressFilterAction::Drop,
+                IngressFilterAction::Deprioritize => action = IngressFilterAction::Deprioritize,
+                IngressFilterAction::Accept => (),
+            }
+        }
+        action
+    }
+
+    /// # Safety
+    ///
+    /// This function loads the dynamically linked library specified in the config file.
+    /// The library must do necessary initializations.
+    ///
+    /// Returns the name of the loaded filter.
+    pub fn load_filter(
+        &mut self,
+        config_file: impl AsRef<Path>,
+    ) -> Result<String, TpuIngressFilterManagerError> {
+        if !self.enabled {
+            return Err(TpuIngressFilterManagerError::FilterStageNotRunning);
+        }
+        let (new_filter, new_config_file) = load_filter_from_config(config_file.as_ref())?;
+        self.start_filter(new_filter, new_config_file, false)
+    }
+
+    pub fn unload_filter(&mut self, name: &str) -> Result<(), TpuIngressFilterManagerError> {
+        let idx = self.filter_position(name)?;
+        self.drop_filter(idx);
+        Ok(())
+    }
+
+    /// Unloads the filter with the given `name`, then loads a new filter from
+    /// `config_file`. The filter is unloaded first in case it requires
+    /// exclusive access to a resource.
+    pub fn reload_filter(
+        &mut self,
+        name: &str,
+        config_file: impl AsRef<Path>,
+    ) -> Result<(), TpuIngressFilterManagerError> {
+        let idx = self.filter_position(name)?;
+        self.drop_filter(idx);
+
+        let (new_filter, new_config_file) = load_filter_from_config(config_file.as_ref())?;
+        self.start_filter(new_filter, new_config_file, true)
+            .map(|_| ())
+    }
+
+    /// Unloads all filters and their libraries, making sure to call their
+    /// `on_unload()` methods so they can do any necessary cleanup.
+    pub fn unload(&mut self) {
+        for mut filter in self.filters.drain(..) {
+            info!("Unloading TPU ingress filter {:?}", filter.name());
+            filter.on_unload();
+        }
+    }
+
+    fn start_filter(
+        &mut self,
+        mut new_filter: LoadedTpuIngressFilter,
+        config_file: &str,
+        is_reload: bool,
+    ) -> Result<String, TpuIngressFilterManagerError> {
+        let name = new_filter.name().to_owned();
+        if self.filters.iter().any(|filter| filter.name() == name) {
+            return Err(TpuIngressFilterManagerError::FilterAlreadyLoaded(name));
+        }
+
+        new_filter
+            .setup_logger(log::logger(), log::max_level())
+            .map_err(|err| {
+                TpuIngressFilterManagerError::FilterSetupLoggerError(name.clone(), err.to_string())
+            })?;
+        new_filter.on_load(config_file, is_reload).map_err(|err| {
+            TpuIngressFilterManagerError::FilterStartError(name.clone(), err.to_string())
+        })?;
+        info!("Loaded TPU ingress filter {name}");
+        self.filters.push(new_filter);
+
+        Ok(name)
+    }
+
+    fn filter_position(&self, name: &str) -> Result<usize, TpuIngressFilterManagerError> {
+        self.filters
+            .iter()
+            .position(|filter| filter.name() == name)
+            .ok_or_else(|| TpuIngressFilterManagerError::FilterNotLoaded(name.to_owned()))
+    }
+
+    fn drop_filter(&mut self, idx: usize) {
+        let mut filter = self.filters.remove(idx);
+        filter.on_unload();
+        info!("Unloaded TPU ingress filter {}", filter.name());
+    }
+}
+
+#[cfg(not(test))]
+#[derive(Deserialize)]
+struct TpuIngressFilterConfig {
+    libpath: String,
+    name: Option<String>,
+}
+
+/// # Safety
+///
+/// This function loads the dynamically linked library specified in the path. The library
+/// must do necessary initializations.
+///
+/// This returns the filter with its dynamic library, and the config file as a &str.
+/// (The filter interface requires a &str for the on_load method).
+#[cfg(not(test))]
+fn load_filter_from_config(
+    config_file: &Path,
+) -> Result<(LoadedTpuIngressFilter, &str), TpuIngressFilterManagerError> {
+    use {libloading::Symbol, std::fs};
+    type FilterConstructor = unsafe fn() -> *mut dyn TpuIngressFilter;
+
+    let contents = fs::read_to_string(config_file).map_err(|err| {
+        TpuIngressFilterManagerError::CannotReadConfigFile(format!("{config_file:?}: {err:?}"))
+    })?;
+    let config: TpuIngressFilterConfig = json5::from_str(&contents).map_err(|err| {
+        TpuIngressFilterManagerError::InvalidConfigFileFormat(format!("{config_file:?}: {err:?}"))
+    })?;
+
+    let mut libpath = Path::new(&config.libpath).to_path_buf();
+    if libpath.is_relative() {
+        let config_dir = config_file.parent().ok_or_else(|| {
+            TpuIngressFilterManagerError::CannotOpenConfigFile(format!(
+                "Failed to resolve parent of {config_file:?}"
+            ))
+        })?;
+        libpath = config_dir.join(libpath);
+    }
+
+    let config_file = config_file
+        .as_os_str()
+        .to_str()
+        .ok_or(TpuIngressFilterManagerError::InvalidFilterPath)?;
+
+    let (filter, library) = unsafe {
+        let library = Library::new(libpath)
+            .map_err(|err| TpuIngressFilterManagerError::FilterLoadError(err.to_string()))?;
+        let constructor: Symbol<FilterConstructor> = library
+            .get(b"_create_tpu_ingress_filter")
+            .map_err(|err| TpuIngressFilterManagerError::FilterLoadError(err.to_string()))?;
+        (Box::from_raw(constructor()), library)
+    };
+    Ok((
+        LoadedTpuIngressFilter::new(library, filter, config.name),
+        config_file,
+    ))
+}
+
+#[cfg(test)]
+const TEST_DROP_FILTER_CONFIG: &str = "TEST_DROP_FILTER_CONFIG";
+#[cfg(test)]
+const TEST_DEPRIORITIZE_FILTER_CONFIG: &str = "TEST_DEPRIORITIZE_FILTER_CONFIG";
+
+// This is mocked for tests to avoid having to do IO with a dynamically linked library
+// across different architectures at test time
+#[cfg(test)]
+fn load_filter_from_config(
+    config_file: &Path,
+) -> Result<(LoadedTpuIngressFilter, &str), TpuIngressFilterManagerError> {
+    if config_file.ends_with(TEST_DROP_FILTER_CONFIG) {
+        Ok(tests::dummy_filter_and_library(
+            tests::DropFilter,
+            TEST_DROP_FILTER_CONFIG,
+        ))
+    } else if config_file.ends_with(TEST_DEPRIORITIZE_FILTER_CONFIG) {
+        Ok(tests::dummy_filter_and_library(
+            tests::DeprioritizeFilter,
+            TEST_DEPRIORITIZE_FILTER_CONFIG,
+        ))
+    } else {
+        Err(TpuIngressFilterManagerError::CannotOpenConfigFile(
+            config_file.to_str().unwrap().to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use {
+        super::*,
+        std::net::{IpAddr, Ipv4Addr},
+    };
+
+    pub(super) fn dummy_filter_and_library<F: TpuIngressFilter>(
+        filter: F,
+        config_path: &'static str,
+    ) -> (LoadedTpuIngressFilter, &'static str) {
+        #[cfg(unix)]
+        let library = libloading::os::unix::Library::this();
+        #[cfg(windows)]
+        let library = libloading::os::windows::Library::this().unwrap();
+        (
+            LoadedTpuIngressFilter::new(Library::from(library), Box::new(filter), None),
+            config_path,
+        )
+    }
+
+    pub(crate) const DROP_FILTER_NAME: &str = "drop_filter";
+    const DEPRIORITIZE_FILTER_NAME: &str = "deprioritize_filter";
+
+    /// Drops the packets from unstaked nodes whose first byte is 0
+    #[derive(Debug)]
+    pub(crate) struct DropFilter;
+
+    impl TpuIngressFilter for DropFilter {
+        fn name(&self) -> &'static str {
+            DROP_FILTER_NAME
+        }
+
+        fn filter_packet(&self, packet: IngressPacketInfoVersions) -> IngressFilterAction {
+            let IngressPacketInfoVersions::V0_0_1(packet) = packet;
+            if !packet.from_staked_node && packet.data.first() == Some(&0) {
+                IngressFilterAction::Drop
+            } else {
+                IngressFilterAction::Accept
+            }
+        }
+    }
+
+    /// Deprioritizes all the packets from unstaked nodes
+    #[derive(Debug)]
+    pub(super) struct DeprioritizeFilter;
+
+    impl TpuIngressFilter for DeprioritizeFilter {
+        fn name(&self) -> &'static str {
+            DEPRIORITIZE_FILTER_NAME
+        }
+
+        fn filter_packet(&self, packet: IngressPacketInfoVersions) -> IngressFilterAction {
+            let IngressPacketInfoVersions::V0_0_1(packet) = packet;
+            if packet.from_staked_node {
+                IngressFilterAction::Accept
+            } else {
+                IngressFilterAction::Deprioritize
+            }
+        }
+    }
+
+    pub(crate) fn manager_with_filter(filter: impl TpuIngressFilter) -> TpuIngressFilterManager {
+        let (loaded_filter, _config) = dummy_filter_and_library(filter, "dummy_config");
+        TpuIngressFilterManager {
+            filters: vec![loaded_filter],
+            enabled: true,
+        }
+    }
+
+    fn packet_info(data: &[u8], from_staked_node: bool) -> IngressPacketInfo<'_> {
+        IngressPacketInfo {
+            data,
+            ip: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            port: 8000,
+            from_staked_node,
+            forwarded: false,
+        }
+    }
+
+    #[test]
+    fn test_filter_load_unload_reload() {
+        // Without the filter stage, filters cannot be loaded
+        let mut manager = TpuIngressFilterManager::default();
+        assert!(!manager.is_enabled());
+        assert_matches!(
+            manager.load_filter(TEST_DROP_FILTER_CONFIG),
+            Err(TpuIngressFilterManagerError::FilterStageNotRunning)
+        );
+
+        let mut manager = TpuIngressFilterManager::new(true);
+        assert!(manager.is_empty());
+        assert_matches!(
+            manager.load_filter("unknown_config"),
+            Err(TpuIngressFilterManagerError::CannotOpenConfigFile(_))
+        );
+
+        assert_eq!(
+            manager.load_filter(TEST_DROP_FILTER_CONFIG).unwrap(),
+            DROP_FILTER_NAME
+        );
+        assert_matches!(
+            manager.load_filter(TEST_DROP_FILTER_CONFIG),
+            Err(TpuIngressFilterManagerError::FilterAlreadyLoaded(_))
+        );
+        assert_eq!(manager.list_filters(), vec![DROP_FILTER_NAME.to_owned()]);
+
+        assert_matches!(
+            manager.reload_filter(DEPRIORITIZE_FILTER_NAME, TEST_DEPRIORITIZE_FILTER_CONFIG),
+            Err(TpuIngressFilterManagerError::FilterNotLoaded(_))
+        );
+        manager
+            .reload_filter(DROP_FILTER_NAME, TEST_DEPRIORITIZE_FILTER_CONFIG)
+            .unwrap();
+        assert_eq!(
+            manager.list_filters(),
+            vec![DEPRIORITIZE_FILTER_NAME.to_owned()]
+        );
+
+        assert_matches!(
+            manager.unload_filter(DROP_FILTER_NAME),
+            Err(TpuIngressFilterManagerError::FilterNotLoaded(_))
+        );
+        manager.unload_filter(DEPRIORITIZE_FILTER_NAME).unwrap();
+        assert!(manager.is_empty());
+    }
+
+    #[test]
+    fn test_filter_packet() {
+        let mut manager = TpuIngressFilterManager::new(true);
+        assert_eq!(
+            manager.filter_packet(&packet_info(&[0], false)),
+            IngressFilterAction::Accept
+        );
+
+        manager
+            .load_filter(TEST_DEPRIORITIZE_FILTER_CONFIG)
+            .unwrap();
+        manager.load_filter(TEST_DROP_FILTER_CONFIG).unwrap();
+        // Drop takes precedence over deprioritize
+        assert_eq!(
+            manager.filter_packet(&packet_info(&[0], false)),
+            IngressFilterAction::Drop
+        );
+        assert_eq!(
+            manager.filter_packet(&packet_info(&[1], false)),
+            IngressFilterAction::Deprioritize
+        );
+        assert_eq!(
+            manager.filter_packet(&packet_info(&[0], true)),
+            IngressFilterAction::Accept
+        );
+
+        manager.unload();
+        assert!(manager.is_empty());
+    }
+}
@@ -0,0 +1,201 @@
+//! The `tpu_ingress_filter_stage` applies the TPU ingress filters loaded in the
+//! [`TpuIngressFilterManager`] to the packets received by the TPU, before their
+//! signatures are verified. The packets dropped by a filter are marked as
+//! discarded, and the deprioritized ones are discarded only when the signature
+//! verification stage is falling behind.
+
+use {
+    crate::tpu_ingress_filter_manager::TpuIngressFilterManager,
+    agave_tpu_ingress_filter_interface::tpu_ingress_filter_interface::{
+        IngressFilterAction, IngressPacketInfo,
+    },
+    crossbeam_channel::{Receiver, RecvTimeoutError, Sender},
+    solana_measure::measure_us,
+    solana_perf::packet::PacketBatch,
+    std::{
+        sync::{Arc, RwLock},
+        thread::{self, Builder, JoinHandle},
+        time::{Duration, Instant},
+    },
+};
+
+const RECV_TIMEOUT: Duration = Duration::from_secs(1);
+const STATS_REPORT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Number of packet batches waiting for signature verification above which
+/// the deprioritized packets are discarded.
+const DEPRIORITIZED_BACKLOG_THRESHOLD: usize = 1_000;
+
+#[derive(Default)]
+struct TpuIngressFilterStats {
+    batches: usize,
+    packets: usize,
+    dropped: usize,
+    deprioritized: usize,
+    deprioritized_dropped: usize,
+    filter_us: u64,
+}
+
+impl TpuIngressFilterStats {
+    fn report(&self) {
+        datapoint_info!(
+            "tpu-ingress-filter-stage",
+            ("batches", self.batches, i64),
+            ("packets", self.packets, i64),
+            ("dropped", self.dropped, i64),
+            ("deprioritized", self.deprioritized, i64),
+            ("deprioritized_dropped", self.deprioritized_dropped, i64),
+            ("filter_us", self.filter_us, i64),
+        );
+    }
+}
+
+pub struct TpuIngressFilterStage {
+    thread_hdl: JoinHandle<()>,
+}
+
+impl TpuIngressFilterStage {
+    pub fn new(
+        packet_receiver: Receiver<PacketBatch>,
+        packet_sender: Sender<PacketBatch>,
+        filter_manager: Arc<RwLock<TpuIngressFilterManager>>,
+    ) -> Self {
+        let thread_hdl = Builder::new()
+            .name("solTpuIngFilter".to_string())
+            .spawn(move || {
+                let mut stats = TpuIngressFilterStats::default();
+                let mut last_report = Instant::now();
+                loop {
+                    match packet_receiver.recv_timeout(RECV_TIMEOUT) {
+                        Ok(mut packet_batch) => {
+                            let (_, filter_us) = measure_us!(Self::filter_batch(
+                                &mut packet_batch,
+                                &filter_manager,
+                                packet_sender.len() > DEPRIORITIZED_BACKLOG_THRESHOLD,
+                                &mut stats,
+                            ));
+                            stats.filter_us += filter_us;
+                            if packet_sender.send(packet_batch).is_err() {
+                                break;
+                            }
+                        }
+                        Err(RecvTimeoutError::Timeout) => (),
+                        Err(RecvTimeoutError::Disconnected) => break,
+                    }
+                    if last_report.elapsed() > STATS_REPORT_INTERVAL {
+                        if stats.batches > 0 {
+                            stats.report();
+                        }
+                        stats = TpuIngressFilterStats::default();
+                        last_report = Instant::now();
+                    }
+                }
+            })
+            .unwrap();
+        Self { thread_hdl }
+    }
+
+    fn filter_batch(
+        packet_batch: &mut PacketBatch,
+        filter_manager: &RwLock<TpuIngressFilterManager>,
+        drop_deprioritized: bool,
+        stats: &mut TpuIngressFilterStats,
+    ) {
+        stats.batches += 1;
+        stats.packets += packet_batch.len();
+        let filter_manager = filter_manager.read().unwrap();
+        if filter_manager.is_empty() {
+            return;
+        }
+        for mut packet in packet_batch.iter_mut() {
+            if packet.meta().discard() {
+                continue;
+            }
+            let action = {
+                let meta = packet.meta();
+                let Some(data) = packet.data(..) else {
+                    continue;
+                };
+                filter_manager.filter_packet(&IngressPacketInfo {
+                    data,
+                    ip: meta.addr,
+                    port: meta.port,
+                    from_staked_node: meta.is_from_staked_node(),
+                    forwarded: meta.forwarded(),
+                })
+            };
+            match action {
+                IngressFilterAction::Accept => (),
+                IngressFilterAction::Deprioritize => {
+                    stats.deprioritized += 1;
+                    if drop_deprioritized {
+                        stats.deprioritized_dropped += 1;
+                        packet.meta_mut().set_discard(true);
+                    }
+                }
+                IngressFilterAction::Drop => {
+                    stats.dropped += 1;
+                    packet.meta_mut().set_discard(true);
+                }
+            }
+        }
+    }
+
+    pub fn join(self) -> thread::Result<()> {
+        self.thread_hdl.join()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::tpu_ingress_filter_manager::tests::{manager_with_filter, DropFilter},
+        crossbeam_channel::unbounded,
+        solana_perf::packet::{BytesPacket, Meta},
+    };
+
+    fn packet_batch(packets: &[(u8, bool)]) -> PacketBatch {
+        let packets = packets
+            .iter()
+            .map(|(first_byte, from_staked_node)| {
+                let mut meta = Meta::default();
+                meta.set_from_staked_node(*from_staked_node);
+                BytesPacket::new(vec![*first_byte; 8].into(), meta)
+            })
+            .collect::<Vec<_>>();
+        PacketBatch::from(packets)
+    }
+
+    fn discarded(packet_batch: &PacketBatch) -> Vec<bool> {
+        packet_batch
+            .iter()
+            .map(|packet| packet.meta().discard())
+            .collect()
+    }
+
+    #[test]
+    fn test_tpu_ingress_filter_stage() {
+        let filter_manager = Arc::new(RwLock::new(TpuIngressFilterManager::default()));
+        let (sender, receiver) = unbounded();
+        let (filtered_sender, filtered_receiver) = unbounded();
+        let stage = TpuIngressFilterStage::new(receiver, filtered_sender, filter_manager.clone());
+
+        // No filter is loaded, every packet is accepted
+        sender
+            .send(packet_batch(&[(0, false), (1, false)]))
+            .unwrap();
+        let packet_batch_out = filtered_receiver.recv().unwrap();
+        assert_eq!(discarded(&packet_batch_out), vec![false, false]);
+
+        *filter_manager.write().unwrap() = manager_with_filter(DropFilter);
+        sender
+            .send(packet_batch(&[(0, false), (1, false), (0, true)]))
+            .unwrap();
+        let packet_batch_out = filtered_receiver.recv().unwrap();
+        assert_eq!(discarded(&packet_batch_out), vec![true, false, false]);
+
+        drop(sender);
+        stage.join().unwrap();
+    }
+}
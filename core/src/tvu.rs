@@ -47,7 +47,11 @@ use {
         vote_sender_types::ReplayVoteSender,
     },
     solana_streamer::evicting_sender::EvictingSender,
-    solana_turbine::{retransmit_stage::RetransmitStage, xdp::XdpSender},
+    solana_turbine::{
+        retransmit_stage::RetransmitStage,
+        shred_tap::{ShredTap, ShredTapConfig, SHRED_TAP_CHANNEL_SIZE},
+        xdp::XdpSender,
+    },
     std::{
         collections::HashSet,
         net::{SocketAddr, UdpSocket},
@@ -70,6 +74,7 @@ pub struct Tvu {
     fetch_stage: ShredFetchStage,
     shred_sigverify: JoinHandle<()>,
     retransmit_stage: RetransmitStage,
+    shred_tap: Option<ShredTap>,
     window_service: WindowService,
     cluster_slots_service: ClusterSlotsService,
     replay_stage: Option<ReplayStage>,
@@ -101,6 +106,8 @@ pub struct TvuConfig {
     pub replay_transactions_threads: NonZeroUsize,
     pub shred_sigverify_threads: NonZeroUsize,
     pub xdp_sender: Option<XdpSender>,
+    // Mirrors the verified shreds to local subscribers
+    pub shred_tap: Option<ShredTapConfig>,
 }
 
 impl Default for TvuConfig {
@@ -115,6 +122,7 @@ impl Default for TvuConfig {
             replay_transactions_threads: NonZeroUsize::new(1).expect("1 is non-zero"),
             shred_sigverify_threads: NonZeroUsize::new(1).expect("1 is non-zero"),
             xdp_sender: None,
+            shred_tap: None,
         }
     }
 }
@@ -209,6 +217,22 @@ impl Tvu {
         let (retransmit_sender, retransmit_receiver) =
             EvictingSender::new_bounded(CHANNEL_SIZE_RETRANSMIT_INGRESS);
 
+        let (shred_tap_sender, shred_tap) = match tvu_config.shred_tap {
+            Some(shred_tap_config) => {
+                let (shred_tap_sender, shred_tap_receiver) =
+                    EvictingSender::new_bounded(SHRED_TAP_CHANNEL_SIZE);
+                let shred_tap = ShredTap::new(
+                    shred_tap_config,
+                    shred_tap_receiver,
+                    &cluster_info.keypair(),
+                    bank_forks.clone(),
+                )
+                .map_err(|err| format!("Failed to start the shred tap: {err}"))?;
+                (Some(shred_tap_sender), Some(shred_tap))
+            }
+            None => (None, None),
+        };
+
         let shred_sigverify = solana_turbine::sigverify_shreds::spawn_shred_sigverify(
             cluster_info.clone(),
             bank_forks.clone(),
@@ -216,6 +240,7 @@ impl Tvu {
             fetch_receiver,
             retransmit_sender.clone(),
             verified_sender,
+            shred_tap_sender,
             tvu_config.shred_sigverify_threads,
         );
 
@@ -400,6 +425,7 @@ impl Tvu {
             fetch_stage,
             shred_sigverify,
             retransmit_stage,
+            shred_tap,
             window_service,
             cluster_slots_service,
             replay_stage,
@@ -418,6 +444,9 @@ impl Tvu {
         self.cluster_slots_service.join()?;
         self.fetch_stage.join()?;
         self.shred_sigverify.join()?;
+        if let Some(shred_tap) = self.shred_tap {
+            shred_tap.join()?;
+        }
         if self.blockstore_cleanup_service.is_some() {
             self.blockstore_cleanup_service.unwrap().join()?;
         }
@@ -29,6 +29,7 @@ use {
             verify_net_stats_access, SystemMonitorService, SystemMonitorStatsReportConfig,
         },
         tpu::{ForwardingClientOption, Tpu, TpuSockets, DEFAULT_TPU_COALESCE},
+        tpu_ingress_filter_manager::TpuIngressFilterManager,
         tvu::{Tvu, TvuConfig, TvuSockets},
     },
     anyhow::{anyhow, Context, Result},
@@ -135,6 +136,7 @@ use {
     solana_turbine::{
         self,
         broadcast_stage::BroadcastStageType,
+        shred_tap::ShredTapConfig,
         xdp::{XdpConfig, XdpRetransmitter},
     },
     solana_unified_scheduler_pool::DefaultSchedulerPool,
@@ -307,6 +309,8 @@ pub struct ValidatorConfig {
     /// Specifies which plugins to start up with
     pub on_start_geyser_plugin_config_files: Option<Vec<PathBuf>>,
     pub geyser_plugin_always_enabled: bool,
+    /// Specifies which TPU ingress filters to start up with
+    pub on_start_tpu_ingress_filter_config_files: Option<Vec<PathBuf>>,
     pub rpc_addrs: Option<(SocketAddr, SocketAddr)>, // (JsonRpc, JsonRpcPubSub)
     pub pubsub_config: PubSubConfig,
     pub snapshot_config: SnapshotConfig,
@@ -370,6 +374,7 @@ pub struct ValidatorConfig {
     pub delay_leader_block_for_pending_fork: bool,
     pub use_tpu_client_next: bool,
     pub retransmit_xdp: Option<XdpConfig>,
+    pub shred_tap: Option<ShredTapConfig>,
     pub repair_handler_type: RepairHandlerType,
 }
 
@@ -391,6 +396,7 @@ impl ValidatorConfig {
             rpc_config: JsonRpcConfig::default_for_test(),
             on_start_geyser_plugin_config_files: None,
             geyser_plugin_always_enabled: false,
+            on_start_tpu_ingress_filter_config_files: None,
             rpc_addrs: None,
             pubsub_config: PubSubConfig::default(),
             snapshot_config: SnapshotConfig::new_load_only(),
@@ -452,6 +458,7 @@ impl ValidatorConfig {
             delay_leader_block_for_pending_fork: false,
             use_tpu_client_next: true,
             retransmit_xdp: None,
+            shred_tap: None,
             repair_handler_type: RepairHandlerType::default(),
         }
     }
@@ -727,6 +734,23 @@ impl Validator {
                 None
             };
 
+        let mut tpu_ingress_filter_manager =
+            TpuIngressFilterManager::new(config.on_start_tpu_ingress_filter_config_files.is_some());
+        for config_file in config
+            .on_start_tpu_ingress_filter_config_files
+            .iter()
+            .flatten()
+        {
+            tpu_ingress_filter_manager
+                .load_filter(config_file)
+                .map_err(|err| {
+                    ValidatorError::Other(format!(
+                        "Failed to load the TPU ingress filter {config_file:?}: {err}"
+                    ))
+                })?;
+        }
+        let tpu_ingress_filter_manager = Arc::new(RwLock::new(tpu_ingress_filter_manager));
+
         if config.voting_disabled {
             warn!("voting disabled");
             authorized_voter_keypairs.write().unwrap().clear();
@@ -805,6 +829,7 @@ impl Validator {
             entry_notifier,
             block_metadata_notifier,
             slot_status_notifier,
+            leader_slot_notifier,
         ) = if let Some(service) = &geyser_plugin_service {
             (
                 service.get_accounts_update_notifier(),
@@ -812,9 +837,10 @@ impl Validator {
                 service.get_entry_notifier(),
                 service.get_block_metadata_notifier(),
                 service.get_slot_status_notifier(),
+                service.get_leader_slot_notifier(),
             )
         } else {
-            (None, None, None, None, None)
+            (None, None, None, None, None, None)
         };
 
         info!(
@@ -1625,6 +1651,7 @@ impl Validator {
                 replay_transactions_threads: config.replay_transactions_threads,
                 shred_sigverify_threads: config.tvu_shred_sigverify_threads,
                 xdp_sender: xdp_sender.clone(),
+                shred_tap: config.shred_tap.clone(),
             },
             &max_slots,
             block_metadata_notifier,
@@ -1731,6 +1758,7 @@ impl Validator {
             tpu_fwd_quic_server_config,
             vote_quic_server_config,
             &prioritization_fee_cache,
+            leader_slot_notifier,
             config.block_production_method.clone(),
             config.block_production_num_workers,
             config.block_production_scheduler_config.clone(),
@@ -1738,6 +1766,7 @@ impl Validator {
             config.enable_block_production_forwarding,
             config.generator_config.clone(),
             key_notifiers.clone(),
+            tpu_ingress_filter_manager.clone(),
             cancel,
         );
 
@@ -1774,6 +1803,8 @@ impl Validator {
             cluster_slots,
             node: Some(node_multihoming),
             banking_stage: tpu.banking_stage(),
+            tpu_connection_qos: tpu.connection_qos(),
+            tpu_ingress_filter_manager,
         });
 
         Ok(Self {
@@ -2191,6 +2222,7 @@ fn load_blockstore(
                 exit.clone(),
                 enable_rpc_transaction_history,
                 config.rpc_config.enable_extended_tx_metadata_storage,
+                config.rpc_config.enable_log_event_recording,
                 transaction_notifier,
                 dependency_tracker,
             )
@@ -2611,6 +2643,7 @@ fn initialize_rpc_transaction_history_services(
     exit: Arc<AtomicBool>,
     enable_rpc_transaction_history: bool,
     enable_extended_tx_metadata_storage: bool,
+    enable_log_event_recording: bool,
     transaction_notifier: Option<TransactionNotifierArc>,
     dependency_tracker: Option<Arc<DependencyTracker>>,
 ) -> TransactionHistoryServices {
@@ -2619,6 +2652,7 @@ fn initialize_rpc_transaction_history_services(
     let transaction_status_sender = Some(TransactionStatusSender {
         sender: transaction_status_sender,
         dependency_tracker: dependency_tracker.clone(),
+        enable_log_event_recording,
     });
     let transaction_status_service = Some(TransactionStatusService::new(
         transaction_status_receiver,
@@ -7,6 +7,7 @@ use {
     solana_hash::Hash,
     solana_signature::Signature,
     solana_transaction::{sanitized::SanitizedTransaction, versioned::VersionedTransaction},
+    solana_transaction_error::TransactionError,
     solana_transaction_status::{Reward, RewardsAndNumPartitions, TransactionStatusMeta},
     std::{any::Any, error, io},
     thiserror::Error,
@@ -296,6 +297,67 @@ pub enum ReplicaBlockInfoVersions<'a> {
     V0_0_4(&'a ReplicaBlockInfoV4<'a>),
 }
 
+/// What happened to a transaction in the banking stage while we were leader
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u32)]
+pub enum BankingTransactionEventKind {
+    /// The transaction was received and buffered for the leader slot
+    Received,
+    /// The transaction was handed to a worker to be executed
+    Scheduled,
+    /// The transaction could not be executed and was handed back to the
+    /// scheduler, e.g. because one of its accounts was in use
+    Retried,
+    /// The transaction did not fit in the remaining cost limits of the block
+    /// and was handed back to the scheduler to be retried
+    ///
+    /// Such transactions are not dropped: the consumer reports them as
+    /// retryable, so the scheduler holds them and schedules them again from
+    /// the start of our next leader slot.
+    RetriedForCostLimit,
+    /// The transaction's accounts could not be locked
+    DroppedForAccountLocks,
+    /// The transaction was dropped without being executed for any other
+    /// reason, e.g. because it expired, was already processed, its fee payer
+    /// could not pay for it or the buffer was full
+    Dropped,
+}
+
+/// A single banking stage event for a transaction
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct BankingTransactionEvent {
+    /// The first signature of the transaction, used for identifying the transaction.
+    pub signature: Signature,
+
+    /// What happened to the transaction.
+    pub kind: BankingTransactionEventKind,
+
+    /// The error the scheduler or cost model reported for the event, if any.
+    pub reason: Option<TransactionError>,
+}
+
+/// Information about what the banking stage did with transactions during one
+/// of our leader slots
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct ReplicaLeaderSlotInfo<'a> {
+    /// The leader slot the events happened in
+    pub slot: Slot,
+
+    /// The events, in the order they were recorded. A transaction may have
+    /// several events, e.g. when it is retried and scheduled again.
+    pub transaction_events: &'a [BankingTransactionEvent],
+}
+
+/// A wrapper to future-proof ReplicaLeaderSlotInfo handling. To make a change to the structure
+/// of ReplicaLeaderSlotInfo, add an new enum variant wrapping a newer version, which will force
+/// plugin implementations to handle the change.
+#[repr(u32)]
+pub enum ReplicaLeaderSlotInfoVersions<'a> {
+    V0_0_1(&'a ReplicaLeaderSlotInfo<'a>),
+}
+
 /// Errors returned by plugin calls
 #[derive(Error, Debug)]
 #[repr(u32)]
@@ -472,6 +534,14 @@ pub trait GeyserPlugin: Any + Send + Sync + std::fmt::Debug {
         Ok(())
     }
 
+    /// Called after one of our leader slots with the banking stage events of
+    /// that slot. Events for transactions still being executed when the slot
+    /// ended are reported with a later notification for the same slot.
+    #[allow(unused_variables)]
+    fn notify_leader_slot(&self, leader_slot_info: ReplicaLeaderSlotInfoVersions) -> Result<()> {
+        Ok(())
+    }
+
     /// Check if the plugin is interested in account data
     /// Default is true -- if the plugin is not interested in
     /// account data, please return false.
@@ -500,4 +570,12 @@ pub trait GeyserPlugin: Any + Send + Sync + std::fmt::Debug {
     fn entry_notifications_enabled(&self) -> bool {
         false
     }
+
+    /// Check if the plugin is interested in banking stage events of our
+    /// leader slots
+    /// Default is false -- if the plugin is interested in
+    /// leader slot data, return true.
+    fn leader_slot_notifications_enabled(&self) -> bool {
+        false
+    }
 }
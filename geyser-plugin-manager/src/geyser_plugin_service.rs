@@ -5,6 +5,8 @@ use {
         block_metadata_notifier_interface::BlockMetadataNotifierArc,
         entry_notifier::EntryNotifierImpl,
         geyser_plugin_manager::{GeyserPluginManager, GeyserPluginManagerRequest},
+        leader_slot_notifier::LeaderSlotNotifierImpl,
+        leader_slot_notifier_interface::LeaderSlotNotifierArc,
         slot_status_notifier::SlotStatusNotifierImpl,
         slot_status_observer::SlotStatusObserver,
         transaction_notifier::TransactionNotifierImpl,
@@ -39,6 +41,7 @@ pub struct GeyserPluginService {
     entry_notifier: Option<EntryNotifierArc>,
     block_metadata_notifier: Option<BlockMetadataNotifierArc>,
     slot_status_notifier: Option<SlotStatusNotifier>,
+    leader_slot_notifier: Option<LeaderSlotNotifierArc>,
 }
 
 impl GeyserPluginService {
@@ -93,6 +96,8 @@ impl GeyserPluginService {
             plugin_manager.transaction_notifications_enabled() || geyser_plugin_always_enabled;
         let entry_notifications_enabled =
             plugin_manager.entry_notifications_enabled() || geyser_plugin_always_enabled;
+        let leader_slot_notifications_enabled =
+            plugin_manager.leader_slot_notifications_enabled() || geyser_plugin_always_enabled;
         let plugin_manager = Arc::new(RwLock::new(plugin_manager));
 
         let accounts_update_notifier: Option<AccountsUpdateNotifier> =
@@ -121,6 +126,14 @@ impl GeyserPluginService {
             None
         };
 
+        let leader_slot_notifier: Option<LeaderSlotNotifierArc> =
+            if leader_slot_notifications_enabled {
+                let leader_slot_notifier = LeaderSlotNotifierImpl::new(plugin_manager.clone());
+                Some(Arc::new(leader_slot_notifier))
+            } else {
+                None
+            };
+
         let (slot_status_observer, block_metadata_notifier, slot_status_notifier): (
             Option<SlotStatusObserver>,
             Option<BlockMetadataNotifierArc>,
@@ -160,6 +173,7 @@ impl GeyserPluginService {
             entry_notifier,
             block_metadata_notifier,
             slot_status_notifier,
+            leader_slot_notifier,
         })
     }
 
@@ -193,6 +207,10 @@ impl GeyserPluginService {
         self.slot_status_notifier.clone()
     }
 
+    pub fn get_leader_slot_notifier(&self) -> Option<LeaderSlotNotifierArc> {
+        self.leader_slot_notifier.clone()
+    }
+
     pub fn join(self) -> thread::Result<()> {
         if let Some(mut slot_status_observer) = self.slot_status_observer {
             slot_status_observer.join()?;
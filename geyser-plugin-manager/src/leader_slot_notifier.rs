@@ -0,0 +1,73 @@
+/// Module responsible for notifying plugins about the banking stage events of leader slots
+use {
+    crate::{
+        geyser_plugin_manager::GeyserPluginManager,
+        leader_slot_notifier_interface::LeaderSlotNotifier,
+    },
+    agave_geyser_plugin_interface::geyser_plugin_interface::{
+        BankingTransactionEvent, ReplicaLeaderSlotInfo, ReplicaLeaderSlotInfoVersions,
+    },
+    log::*,
+    solana_clock::Slot,
+    solana_measure::measure::Measure,
+    solana_metrics::*,
+    std::sync::{Arc, RwLock},
+};
+
+pub(crate) struct LeaderSlotNotifierImpl {
+    plugin_manager: Arc<RwLock<GeyserPluginManager>>,
+}
+
+impl LeaderSlotNotifier for LeaderSlotNotifierImpl {
+    fn notify_leader_slot(&self, slot: Slot, transaction_events: &[BankingTransactionEvent]) {
+        let mut measure = Measure::start("geyser-plugin-notify_plugins_of_leader_slot_info");
+
+        let plugin_manager = self.plugin_manager.read().unwrap();
+        if plugin_manager.plugins.is_empty() {
+            return;
+        }
+
+        let leader_slot_info = ReplicaLeaderSlotInfo {
+            slot,
+            transaction_events,
+        };
+
+        for plugin in plugin_manager.plugins.iter() {
+            if !plugin.leader_slot_notifications_enabled() {
+                continue;
+            }
+            match plugin
+                .notify_leader_slot(ReplicaLeaderSlotInfoVersions::V0_0_1(&leader_slot_info))
+            {
+                Err(err) => {
+                    error!(
+                        "Failed to notify leader slot {}, error: ({}) to plugin {}",
+                        slot,
+                        err,
+                        plugin.name()
+                    )
+                }
+                Ok(_) => {
+                    trace!(
+                        "Successfully notified leader slot {} to plugin {}",
+                        slot,
+                        plugin.name()
+                    );
+                }
+            }
+        }
+        measure.stop();
+        inc_new_counter_debug!(
+            "geyser-plugin-notify_plugins_of_leader_slot_info-us",
+            measure.as_us() as usize,
+            10000,
+            10000
+        );
+    }
+}
+
+impl LeaderSlotNotifierImpl {
+    pub fn new(plugin_manager: Arc<RwLock<GeyserPluginManager>>) -> Self {
+        Self { plugin_manager }
+    }
+}
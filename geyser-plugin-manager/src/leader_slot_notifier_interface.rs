@@ -0,0 +1,12 @@
+use {
+    agave_geyser_plugin_interface::geyser_plugin_interface::BankingTransactionEvent,
+    solana_clock::Slot, std::sync::Arc,
+};
+
+/// Interface for notifying what the banking stage did during a leader slot
+pub trait LeaderSlotNotifier {
+    /// Notify the banking stage events of a leader slot
+    fn notify_leader_slot(&self, slot: Slot, transaction_events: &[BankingTransactionEvent]);
+}
+
+pub type LeaderSlotNotifierArc = Arc<dyn LeaderSlotNotifier + Sync + Send>;
@@ -4,7 +4,7 @@ use {
     solana_account_decoder::{UiAccountEncoding, UiDataSliceConfig},
     solana_accounts_db::{
         accounts_db::{AccountsDbConfig, DEFAULT_MEMLOCK_BUDGET_SIZE},
-        accounts_file::StorageAccess,
+        accounts_file::{AccountsFileProvider, StorageAccess},
         accounts_index::{AccountsIndexConfig, IndexLimitMb, ScanFilter},
     },
     solana_clap_utils::{
@@ -122,6 +122,13 @@ pub fn accounts_db_args<'a, 'b>() -> Box<[Arg<'a, 'b>]> {
             .takes_value(true)
             .help("The number of ancient storages the ancient slot combining should converge to.")
             .hidden(hidden_unless_forced()),
+        Arg::with_name("accounts_db_ancient_storage_format")
+            .long("accounts-db-ancient-storage-format")
+            .value_name("FORMAT")
+            .takes_value(true)
+            .possible_values(AccountsFileProvider::POSSIBLE_VALUES)
+            .help("The storage format used when combining ancient slots.")
+            .hidden(hidden_unless_forced()),
     ]
     .into_boxed_slice()
 }
@@ -270,6 +277,13 @@ pub fn get_accounts_db_config(
         })
         .unwrap_or_default();
 
+    let ancient_accounts_file_provider = value_t!(
+        arg_matches,
+        "accounts_db_ancient_storage_format",
+        AccountsFileProvider
+    )
+    .ok();
+
     let scan_filter_for_shrinking = arg_matches
         .value_of("accounts_db_scan_filter_for_shrinking")
         .map(|filter| match filter {
@@ -295,6 +309,7 @@ pub fn get_accounts_db_config(
         )
         .ok(),
         max_ancient_storages: value_t!(arg_matches, "accounts_db_max_ancient_storages", usize).ok(),
+        ancient_accounts_file_provider,
         exhaustively_verify_refcounts: arg_matches.is_present("accounts_db_verify_refcounts"),
         skip_initial_hash_calc: arg_matches.is_present("accounts_db_skip_initial_hash_calculation"),
         storage_access,
@@ -61,6 +61,7 @@ async fn upload(
     let bigtable = solana_storage_bigtable::LedgerStorage::new_with_config(config)
         .await
         .map_err(|err| format!("Failed to connect to storage: {err:?}"))?;
+    let bigtable = Arc::new(bigtable);
 
     let config = ConfirmedBlockUploadConfig {
         force_reupload,
@@ -109,7 +110,8 @@ async fn delete_slots(
         .await
         .map_err(|err| format!("Failed to connect to storage: {err:?}"))?;
 
-    solana_ledger::bigtable_delete::delete_confirmed_blocks(bigtable, slots, dry_run).await
+    solana_ledger::bigtable_delete::delete_confirmed_blocks(Arc::new(bigtable), slots, dry_run)
+        .await
 }
 
 async fn first_available_block(
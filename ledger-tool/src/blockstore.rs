@@ -3,6 +3,7 @@
 use {
     crate::{
         error::{LedgerToolError, Result},
+        export::export_blocks,
         ledger_path::canonicalize_ledger_path,
         ledger_utils::get_program_ids,
         output::{output_ledger, output_slot, CliDuplicateSlotProof, SlotBounds, SlotInfo},
@@ -341,6 +342,22 @@ pub fn blockstore_subcommands<'a, 'b>(hidden: bool) -> Vec<App<'a, 'b>> {
             .about("Print all the duplicate slots in the ledger")
             .settings(&hidden)
             .arg(&starting_slot_arg),
+        SubCommand::with_name("export")
+            .about(
+                "Export the rooted blocks in a slot range into Parquet files of blocks, \
+                 transactions, instructions, token balances and rewards",
+            )
+            .settings(&hidden)
+            .arg(&starting_slot_arg)
+            .arg(&ending_slot_arg)
+            .arg(
+                Arg::with_name("output_dir")
+                    .long("output-dir")
+                    .value_name("DIR")
+                    .takes_value(true)
+                    .required(true)
+                    .help("Directory to write the Parquet files into"),
+            ),
         SubCommand::with_name("latest-optimistic-slots")
             .about(
                 "Output up to the most recent <num-slots> optimistic slots with their hashes and \
@@ -706,6 +723,16 @@ fn do_blockstore_process_command(ledger_path: &Path, matches: &ArgMatches<'_>) -
                 }
             }
         }
+        ("export", Some(arg_matches)) => {
+            let starting_slot = value_t_or_exit!(arg_matches, "starting_slot", Slot);
+            let ending_slot = value_t!(arg_matches, "ending_slot", Slot).unwrap_or(Slot::MAX);
+            let output_dir = PathBuf::from(value_t_or_exit!(arg_matches, "output_dir", String));
+
+            let blockstore =
+                crate::open_blockstore(&ledger_path, arg_matches, AccessType::Secondary);
+            let num_blocks = export_blocks(&blockstore, starting_slot, ending_slot, &output_dir)?;
+            println!("Exported {num_blocks} blocks into {}", output_dir.display());
+        }
         ("latest-optimistic-slots", Some(arg_matches)) => {
             let blockstore =
                 crate::open_blockstore(&ledger_path, arg_matches, AccessType::Secondary);
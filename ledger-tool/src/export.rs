@@ -0,0 +1,1015 @@
+//! Export of rooted blocks into Parquet files for the `blockstore export` subcommand
+//!
+//! Each table is written into its own `<table>.parquet` file in the output directory:
+//! - `blocks`: one row per block
+//! - `transactions`: one row per transaction, with its status meta
+//! - `instructions`: one row per instruction, inner instructions included. Instructions of the
+//!   programs known to `solana-transaction-status` are decoded into JSON.
+//! - `token_balances`: one row per token account of a transaction, with its balance delta
+//! - `rewards`: one row per block reward
+
+use {
+    crate::error::Result,
+    arrow_array::{
+        builder::{
+            ArrayBuilder, BooleanBuilder, Decimal128Builder, Int64Builder, ListBuilder,
+            StringBuilder, UInt32Builder, UInt64Builder, UInt8Builder,
+        },
+        ArrayRef, RecordBatch,
+    },
+    arrow_schema::{DataType, Field, Schema, SchemaRef},
+    log::*,
+    parquet::{
+        arrow::ArrowWriter,
+        basic::{Compression, ZstdLevel},
+        file::properties::WriterProperties,
+    },
+    solana_clock::Slot,
+    solana_instruction::TRANSACTION_LEVEL_STACK_HEIGHT,
+    solana_ledger::blockstore::{Blockstore, BlockstoreError},
+    solana_message::{compiled_instruction::CompiledInstruction, AccountKeys, VersionedMessage},
+    solana_transaction_status::{
+        parse_instruction::parse, Reward, TransactionTokenBalance, VersionedConfirmedBlock,
+        VersionedTransactionWithStatusMeta,
+    },
+    std::{
+        collections::BTreeMap,
+        fs::{self, File},
+        path::Path,
+        sync::Arc,
+    },
+};
+
+/// Rows buffered for a table before they are written out as a record batch
+const ROWS_PER_BATCH: usize = 64 * 1024;
+
+/// Token amounts are u64, so their deltas always fit into 38 digits
+const TOKEN_DELTA_PRECISION: u8 = 38;
+
+trait Table: Default {
+    const NAME: &'static str;
+
+    fn schema() -> Schema;
+
+    fn num_rows(&self) -> usize;
+
+    /// Returns the buffered columns, in the order of `schema()`, and resets the table
+    fn finish(&mut self) -> Vec<ArrayRef>;
+}
+
+fn string_list() -> DataType {
+    DataType::List(Arc::new(Field::new_list_field(DataType::Utf8, true)))
+}
+
+#[derive(Default)]
+struct BlocksTable {
+    slot: UInt64Builder,
+    parent_slot: UInt64Builder,
+    blockhash: StringBuilder,
+    previous_blockhash: StringBuilder,
+    block_time: Int64Builder,
+    block_height: UInt64Builder,
+    transaction_count: UInt64Builder,
+}
+
+impl BlocksTable {
+    fn append(&mut self, slot: Slot, block: &VersionedConfirmedBlock) {
+        self.slot.append_value(slot);
+        self.parent_slot.append_value(block.parent_slot);
+        self.blockhash.append_value(&block.blockhash);
+        self.previous_blockhash
+            .append_value(&block.previous_blockhash);
+        self.block_time.append_option(block.block_time);
+        self.block_height.append_option(block.block_height);
+        self.transaction_count
+            .append_value(block.transactions.len() as u64);
+    }
+}
+
+impl Table for BlocksTable {
+    const NAME: &'static str = "blocks";
+
+    fn schema() -> Schema {
+        Schema::new(vec![
+            Field::new("slot", DataType::UInt64, false),
+            Field::new("parent_slot", DataType::UInt64, false),
+            Field::new("blockhash", DataType::Utf8, false),
+            Field::new("previous_blockhash", DataType::Utf8, false),
+            Field::new("block_time", DataType::Int64, true),
+            Field::new("block_height", DataType::UInt64, true),
+            Field::new("transaction_count", DataType::UInt64, false),
+        ])
+    }
+
+    fn num_rows(&self) -> usize {
+        self.slot.len()
+    }
+
+    fn finish(&mut self) -> Vec<ArrayRef> {
+        vec![
+            Arc::new(self.slot.finish()),
+            Arc::new(self.parent_slot.finish()),
+            Arc::new(self.blockhash.finish()),
+            Arc::new(self.previous_blockhash.finish()),
+            Arc::new(self.block_time.finish()),
+            Arc::new(self.block_height.finish()),
+            Arc::new(self.transaction_count.finish()),
+        ]
+    }
+}
+
+#[derive(Default)]
+struct TransactionsTable {
+    slot: UInt64Builder,
+    transaction_index: UInt32Builder,
+    signature: StringBuilder,
+    version: StringBuilder,
+    fee_payer: StringBuilder,
+    fee: UInt64Builder,
+    compute_units_consumed: UInt64Builder,
+    success: BooleanBuilder,
+    err: StringBuilder,
+    account_keys: ListBuilder<StringBuilder>,
+    log_messages: ListBuilder<StringBuilder>,
+}
+
+impl TransactionsTable {
+    fn append(
+        &mut self,
+        slot: Slot,
+        transaction_index: u32,
+        signature: &str,
+        transaction: &VersionedTransactionWithStatusMeta,
+        account_keys: &AccountKeys,
+    ) {
+        let meta = &transaction.meta;
+        self.slot.append_value(slot);
+        self.transaction_index.append_value(transaction_index);
+        self.signature.append_value(signature);
+        self.version
+            .append_value(match &transaction.transaction.message {
+                VersionedMessage::Legacy(_) => "legacy",
+                VersionedMessage::V0(_) => "0",
+            });
+        self.fee_payer
+            .append_option(account_keys.get(0).map(ToString::to_string));
+        self.fee.append_value(meta.fee);
+        self.compute_units_consumed
+            .append_option(meta.compute_units_consumed);
+        self.success.append_value(meta.status.is_ok());
+        self.err
+            .append_option(meta.status.as_ref().err().map(ToString::to_string));
+        for key in account_keys.iter() {
+            self.account_keys.values().append_value(key.to_string());
+        }
+        self.account_keys.append(true);
+        match &meta.log_messages {
+            Some(log_messages) => {
+                for log_message in log_messages {
+                    self.log_messages.values().append_value(log_message);
+                }
+                self.log_messages.append(true);
+            }
+            None => self.log_messages.append(false),
+        }
+    }
+}
+
+impl Table for TransactionsTable {
+    const NAME: &'static str = "transactions";
+
+    fn schema() -> Schema {
+        Schema::new(vec![
+            Field::new("slot", DataType::UInt64, false),
+            Field::new("transaction_index", DataType::UInt32, false),
+            Field::new("signature", DataType::Utf8, false),
+            Field::new("version", DataType::Utf8, false),
+            Field::new("fee_payer", DataType::Utf8, true),
+            Field::new("fee", DataType::UInt64, false),
+            Field::new("compute_units_consumed", DataType::UInt64, true),
+            Field::new("success", DataType::Boolean, false),
+            Field::new("err", DataType::Utf8, true),
+            Field::new("account_keys", string_list(), false),
+            Field::new("log_messages", string_list(), true),
+        ])
+    }
+
+    fn num_rows(&self) -> usize {
+        self.slot.len()
+    }
+
+    fn finish(&mut self) -> Vec<ArrayRef> {
+        vec![
+            Arc::new(self.slot.finish()),
+            Arc::new(self.transaction_index.finish()),
+            Arc::new(self.signature.finish()),
+            Arc::new(self.version.finish()),
+            Arc::new(self.fee_payer.finish()),
+            Arc::new(self.fee.finish()),
+            Arc::new(self.compute_units_consumed.finish()),
+            Arc::new(self.success.finish()),
+            Arc::new(self.err.finish()),
+            Arc::new(self.account_keys.finish()),
+            Arc::new(self.log_messages.finish()),
+        ]
+    }
+}
+
+#[derive(Default)]
+struct InstructionsTable {
+    slot: UInt64Builder,
+    transaction_index: UInt32Builder,
+    signature: StringBuilder,
+    instruction_index: UInt32Builder,
+    inner_instruction_index: UInt32Builder,
+    stack_height: UInt32Builder,
+    program_id: StringBuilder,
+    program: StringBuilder,
+    parsed: StringBuilder,
+    data: StringBuilder,
+    accounts: ListBuilder<StringBuilder>,
+}
+
+impl InstructionsTable {
+    #[allow(clippy::too_many_arguments)]
+    fn append(
+        &mut self,
+        slot: Slot,
+        transaction_index: u32,
+        signature: &str,
+        instruction_index: u32,
+        inner_instruction_index: Option<u32>,
+        stack_height: Option<u32>,
+        instruction: &CompiledInstruction,
+        account_keys: &AccountKeys,
+    ) {
+        let program_id = account_keys.get(usize::from(instruction.program_id_index));
+        let parsed = program_id
+            .and_then(|program_id| parse(program_id, instruction, account_keys, stack_height).ok());
+
+        self.slot.append_value(slot);
+        self.transaction_index.append_value(transaction_index);
+        self.signature.append_value(signature);
+        self.instruction_index.append_value(instruction_index);
+        self.inner_instruction_index
+            .append_option(inner_instruction_index);
+        self.stack_height.append_option(stack_height);
+        self.program_id
+            .append_option(program_id.map(ToString::to_string));
+        self.program
+            .append_option(parsed.as_ref().map(|parsed| &parsed.program));
+        self.parsed
+            .append_option(parsed.as_ref().map(|parsed| parsed.parsed.to_string()));
+        self.data
+            .append_value(bs58::encode(&instruction.data).into_string());
+        for account_index in &instruction.accounts {
+            self.accounts.values().append_option(
+                account_keys
+                    .get(usize::from(*account_index))
+                    .map(ToString::to_string),
+            );
+        }
+        self.accounts.append(true);
+    }
+}
+
+impl Table for InstructionsTable {
+    const NAME: &'static str = "instructions";
+
+    fn schema() -> Schema {
+        Schema::new(vec![
+            Field::new("slot", DataType::UInt64, false),
+            Field::new("transaction_index", DataType::UInt32, false),
+            Field::new("signature", DataType::Utf8, false),
+            Field::new("instruction_index", DataType::UInt32, false),
+            Field::new("inner_instruction_index", DataType::UInt32, true),
+            Field::new("stack_height", DataType::UInt32, true),
+            Field::new("program_id", DataType::Utf8, true),
+            Field::new("program", DataType::Utf8, true),
+            Field::new("parsed", DataType::Utf8, true),
+            Field::new("data", DataType::Utf8, false),
+            Field::new("accounts", string_list(), false),
+        ])
+    }
+
+    fn num_rows(&self) -> usize {
+        self.slot.len()
+    }
+
+    fn finish(&mut self) -> Vec<ArrayRef> {
+        vec![
+            Arc::new(self.slot.finish()),
+            Arc::new(self.transaction_index.finish()),
+            Arc::new(self.signature.finish()),
+            Arc::new(self.instruction_index.finish()),
+            Arc::new(self.inner_instruction_index.finish()),
+            Arc::new(self.stack_height.finish()),
+            Arc::new(self.program_id.finish()),
+            Arc::new(self.program.finish()),
+            Arc::new(self.parsed.finish()),
+            Arc::new(self.data.finish()),
+            Arc::new(self.accounts.finish()),
+        ]
+    }
+}
+
+struct TokenBalancesTable {
+    slot: UInt64Builder,
+    transaction_index: UInt32Builder,
+    signature: StringBuilder,
+    account: StringBuilder,
+    mint: StringBuilder,
+    owner: StringBuilder,
+    program_id: StringBuilder,
+    decimals: UInt8Builder,
+    pre_amount: UInt64Builder,
+    post_amount: UInt64Builder,
+    delta: Decimal128Builder,
+}
+
+impl Default for TokenBalancesTable {
+    fn default() -> Self {
+        Self {
+            slot: UInt64Builder::default(),
+            transaction_index: UInt32Builder::default(),
+            signature: StringBuilder::default(),
+            account: StringBuilder::default(),
+            mint: StringBuilder::default(),
+            owner: StringBuilder::default(),
+            program_id: StringBuilder::default(),
+            decimals: UInt8Builder::default(),
+            pre_amount: UInt64Builder::default(),
+            post_amount: UInt64Builder::default(),
+            delta: Decimal128Builder::default()
+                .with_data_type(DataType::Decimal128(TOKEN_DELTA_PRECISION, 0)),
+        }
+    }
+}
+
+impl TokenBalancesTable {
+    fn append(
+        &mut self,
+        slot: Slot,
+        transaction_index: u32,
+        signature: &str,
+        transaction: &VersionedTransactionWithStatusMeta,
+        account_keys: &AccountKeys,
+    ) {
+        let mut balances = BTreeMap::<u8, (Option<_>, Option<_>)>::new();
+        let meta = &transaction.meta;
+        for balance in meta.pre_token_balances.iter().flatten() {
+            balances.entry(balance.account_index).or_default().0 = Some(balance);
+        }
+        for balance in meta.post_token_balances.iter().flatten() {
+            balances.entry(balance.account_index).or_default().1 = Some(balance);
+        }
+
+        let amount = |balance: Option<&TransactionTokenBalance>| {
+            balance.and_then(|balance| balance.ui_token_amount.amount.parse::<u64>().ok())
+        };
+        for (account_index, (pre, post)) in balances {
+            // Either of them is present by construction
+            let Some(balance) = post.or(pre) else {
+                continue;
+            };
+            let (pre_amount, post_amount) = (amount(pre), amount(post));
+
+            self.slot.append_value(slot);
+            self.transaction_index.append_value(transaction_index);
+            self.signature.append_value(signature);
+            self.account.append_option(
+                account_keys
+                    .get(usize::from(account_index))
+                    .map(ToString::to_string),
+            );
+            self.mint.append_value(&balance.mint);
+            self.owner.append_value(&balance.owner);
+            self.program_id.append_value(&balance.program_id);
+            self.decimals.append_value(balance.ui_token_amount.decimals);
+            self.pre_amount.append_option(pre_amount);
+            self.post_amount.append_option(post_amount);
+            self.delta.append_value(
+                i128::from(post_amount.unwrap_or_default())
+                    - i128::from(pre_amount.unwrap_or_default()),
+            );
+        }
+    }
+}
+
+impl Table for TokenBalancesTable {
+    const NAME: &'static str = "token_balances";
+
+    fn schema() -> Schema {
+        Schema::new(vec![
+            Field::new("slot", DataType::UInt64, false),
+            Field::new("transaction_index", DataType::UInt32, false),
+            Field::new("signature", DataType::Utf8, false),
+            Field::new("account", DataType::Utf8, true),
+            Field::new("mint", DataType::Utf8, false),
+            Field::new("owner", DataType::Utf8, false),
+            Field::new("program_id", DataType::Utf8, false),
+            Field::new("decimals", DataType::UInt8, false),
+            Field::new("pre_amount", DataType::UInt64, true),
+            Field::new("post_amount", DataType::UInt64, true),
+            Field::new(
+                "delta",
+                DataType::Decimal128(TOKEN_DELTA_PRECISION, 0),
+                false,
+            ),
+        ])
+    }
+
+    fn num_rows(&self) -> usize {
+        self.slot.len()
+    }
+
+    fn finish(&mut self) -> Vec<ArrayRef> {
+        vec![
+            Arc::new(self.slot.finish()),
+            Arc::new(self.transaction_index.finish()),
+            Arc::new(self.signature.finish()),
+            Arc::new(self.account.finish()),
+            Arc::new(self.mint.finish()),
+            Arc::new(self.owner.finish()),
+            Arc::new(self.program_id.finish()),
+            Arc::new(self.decimals.finish()),
+            Arc::new(self.pre_amount.finish()),
+            Arc::new(self.post_amount.finish()),
+            Arc::new(self.delta.finish()),
+        ]
+    }
+}
+
+#[derive(Default)]
+struct RewardsTable {
+    slot: UInt64Builder,
+    pubkey: StringBuilder,
+    lamports: Int64Builder,
+    post_balance: UInt64Builder,
+    reward_type: StringBuilder,
+    commission: UInt8Builder,
+}
+
+impl RewardsTable {
+    fn append(&mut self, slot: Slot, reward: &Reward) {
+        self.slot.append_value(slot);
+        self.pubkey.append_value(&reward.pubkey);
+        self.lamports.append_value(reward.lamports);
+        self.post_balance.append_value(reward.post_balance);
+        self.reward_type.append_option(
+            reward
+                .reward_type
+                .map(|reward_type| reward_type.to_string()),
+        );
+        self.commission.append_option(reward.commission);
+    }
+}
+
+impl Table for RewardsTable {
+    const NAME: &'static str = "rewards";
+
+    fn schema() -> Schema {
+        Schema::new(vec![
+            Field::new("slot", DataType::UInt64, false),
+            Field::new("pubkey", DataType::Utf8, false),
+            Field::new("lamports", DataType::Int64, false),
+            Field::new("post_balance", DataType::UInt64, false),
+            Field::new("reward_type", DataType::Utf8, true),
+            Field::new("commission", DataType::UInt8, true),
+        ])
+    }
+
+    fn num_rows(&self) -> usize {
+        self.slot.len()
+    }
+
+    fn finish(&mut self) -> Vec<ArrayRef> {
+        vec![
+            Arc::new(self.slot.finish()),
+            Arc::new(self.pubkey.finish()),
+            Arc::new(self.lamports.finish()),
+            Arc::new(self.post_balance.finish()),
+            Arc::new(self.reward_type.finish()),
+            Arc::new(self.commission.finish()),
+        ]
+    }
+}
+
+/// Buffers the rows of a table and writes them into `<output_dir>/<table>.parquet`
+struct TableWriter<T> {
+    table: T,
+    schema: SchemaRef,
+    writer: ArrowWriter<File>,
+    rows_written: usize,
+}
+
+impl<T: Table> TableWriter<T> {
+    fn create(output_dir: &Path) -> Result<Self> {
+        let schema = Arc::new(T::schema());
+        let file = File::create(output_dir.join(format!("{}.parquet", T::NAME)))?;
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::ZSTD(ZstdLevel::default()))
+            .build();
+        let writer = ArrowWriter::try_new(file, schema.clone(), Some(properties))?;
+        Ok(Self {
+            table: T::default(),
+            schema,
+            writer,
+            rows_written: 0,
+        })
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        let num_rows = self.table.num_rows();
+        if num_rows > 0 {
+            let batch = RecordBatch::try_new(self.schema.clone(), self.table.finish())?;
+            self.writer.write(&batch)?;
+            self.rows_written += num_rows;
+        }
+        Ok(())
+    }
+
+    fn maybe_flush(&mut self) -> Result<()> {
+        if self.table.num_rows() >= ROWS_PER_BATCH {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    fn close(mut self) -> Result<usize> {
+        self.flush()?;
+        self.writer.close()?;
+        info!("Wrote {} rows into {}.parquet", self.rows_written, T::NAME);
+        Ok(self.rows_written)
+    }
+}
+
+struct BlockExporter {
+    blocks: TableWriter<BlocksTable>,
+    transactions: TableWriter<TransactionsTable>,
+    instructions: TableWriter<InstructionsTable>,
+    token_balances: TableWriter<TokenBalancesTable>,
+    rewards: TableWriter<RewardsTable>,
+}
+
+impl BlockExporter {
+    fn create(output_dir: &Path) -> Result<Self> {
+        fs::create_dir_all(output_dir)?;
+        Ok(Self {
+            blocks: TableWriter::create(output_dir)?,
+            transactions: TableWriter::create(output_dir)?,
+            instructions: TableWriter::create(output_dir)?,
+            token_balances: TableWriter::create(output_dir)?,
+            rewards: TableWriter::create(output_dir)?,
+        })
+    }
+
+    fn append_block(&mut self, slot: Slot, block: &VersionedConfirmedBlock) -> Result<()> {
+        self.blocks.table.append(slot, block);
+        for reward in &block.rewards {
+            self.rewards.table.append(slot, reward);
+        }
+        for (transaction_index, transaction) in block.transactions.iter().enumerate() {
+            self.append_transaction(slot, transaction_index as u32, transaction);
+        }
+
+        self.blocks.maybe_flush()?;
+        self.transactions.maybe_flush()?;
+        self.instructions.maybe_flush()?;
+        self.token_balances.maybe_flush()?;
+        self.rewards.maybe_flush()
+    }
+
+    fn append_transaction(
+        &mut self,
+        slot: Slot,
+        transaction_index: u32,
+        transaction: &VersionedTransactionWithStatusMeta,
+    ) {
+        let account_keys = transaction.account_keys();
+        let signature = transaction.transaction.signatures[0].to_string();
+        self.transactions.table.append(
+            slot,
+            transaction_index,
+            &signature,
+            transaction,
+            &account_keys,
+        );
+        self.token_balances.table.append(
+            slot,
+            transaction_index,
+            &signature,
+            transaction,
+            &account_keys,
+        );
+
+        let inner_instructions = transaction
+            .meta
+            .inner_instructions
+            .iter()
+            .flatten()
+            .map(|inner_instructions| (inner_instructions.index, inner_instructions))
+            .collect::<BTreeMap<_, _>>();
+        let instructions = transaction.transaction.message.instructions();
+        for (instruction_index, instruction) in instructions.iter().enumerate() {
+            self.instructions.table.append(
+                slot,
+                transaction_index,
+                &signature,
+                instruction_index as u32,
+                None,
+                Some(TRANSACTION_LEVEL_STACK_HEIGHT as u32),
+                instruction,
+                &account_keys,
+            );
+            let Some(inner_instructions) = inner_instructions.get(&(instruction_index as u8))
+            else {
+                continue;
+            };
+            for (inner_instruction_index, inner_instruction) in
+                inner_instructions.instructions.iter().enumerate()
+            {
+                self.instructions.table.append(
+                    slot,
+                    transaction_index,
+                    &signature,
+                    instruction_index as u32,
+                    Some(inner_instruction_index as u32),
+                    inner_instruction.stack_height,
+                    &inner_instruction.instruction,
+                    &account_keys,
+                );
+            }
+        }
+    }
+
+    fn close(self) -> Result<()> {
+        self.blocks.close()?;
+        self.transactions.close()?;
+        self.instructions.close()?;
+        self.token_balances.close()?;
+        self.rewards.close()?;
+        Ok(())
+    }
+}
+
+/// Exports the rooted blocks within `starting_slot..=ending_slot` into Parquet files in
+/// `output_dir`. Returns the number of exported blocks.
+pub fn export_blocks(
+    blockstore: &Blockstore,
+    starting_slot: Slot,
+    ending_slot: Slot,
+    output_dir: &Path,
+) -> Result<usize> {
+    let mut exporter = BlockExporter::create(output_dir)?;
+    let mut num_blocks = 0;
+    for slot in blockstore.rooted_slot_iterator(starting_slot)? {
+        if slot > ending_slot {
+            break;
+        }
+        let block = match blockstore.get_rooted_block(slot, false) {
+            Ok(block) => block,
+            Err(BlockstoreError::SlotUnavailable) => {
+                warn!("Skipping slot {slot}, which is rooted but not full");
+                continue;
+            }
+            Err(err) => return Err(err.into()),
+        };
+        exporter.append_block(slot, &block)?;
+        num_blocks += 1;
+    }
+    exporter.close()?;
+    Ok(num_blocks)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        arrow_array::{
+            cast::AsArray,
+            types::{
+                ArrowPrimitiveType, Decimal128Type, Int64Type, UInt32Type, UInt64Type, UInt8Type,
+            },
+        },
+        parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder,
+        solana_account_decoder::parse_token::UiTokenAmount,
+        solana_entry::entry::{create_ticks, next_entry_mut, Entry},
+        solana_hash::Hash,
+        solana_keypair::{Keypair, Signer},
+        solana_ledger::{blockstore::entries_to_test_shreds, get_tmp_ledger_path_auto_delete},
+        solana_pubkey::Pubkey,
+        solana_system_interface::{instruction as system_instruction, program as system_program},
+        solana_transaction::{Transaction, TransactionError},
+        solana_transaction_status::{RewardType, RewardsAndNumPartitions, TransactionStatusMeta},
+    };
+
+    fn token_balance(account_index: u8, amount: u64) -> TransactionTokenBalance {
+        TransactionTokenBalance {
+            account_index,
+            mint: "mint".to_string(),
+            ui_token_amount: UiTokenAmount {
+                ui_amount: None,
+                decimals: 2,
+                amount: amount.to_string(),
+                ui_amount_string: String::default(),
+            },
+            owner: "owner".to_string(),
+            program_id: "token_program".to_string(),
+        }
+    }
+
+    fn read_table(output_dir: &Path, table: &str) -> RecordBatch {
+        let file = File::open(output_dir.join(format!("{table}.parquet"))).unwrap();
+        let mut batches = ParquetRecordBatchReaderBuilder::try_new(file)
+            .unwrap()
+            .build()
+            .unwrap()
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(batches.len(), 1);
+        batches.pop().unwrap()
+    }
+
+    fn strings<'a>(batch: &'a RecordBatch, column: &str) -> Vec<Option<&'a str>> {
+        batch
+            .column_by_name(column)
+            .unwrap()
+            .as_string::<i32>()
+            .iter()
+            .collect()
+    }
+
+    fn primitives<T: ArrowPrimitiveType>(
+        batch: &RecordBatch,
+        column: &str,
+    ) -> Vec<Option<T::Native>> {
+        batch
+            .column_by_name(column)
+            .unwrap()
+            .as_primitive::<T>()
+            .iter()
+            .collect()
+    }
+
+    fn string_lists(batch: &RecordBatch, column: &str) -> Vec<Option<Vec<String>>> {
+        batch
+            .column_by_name(column)
+            .unwrap()
+            .as_list::<i32>()
+            .iter()
+            .map(|list| {
+                list.map(|list| {
+                    list.as_string::<i32>()
+                        .iter()
+                        .map(|value| value.unwrap().to_string())
+                        .collect()
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_export_blocks() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+
+        let payer = Keypair::new();
+        let to = Pubkey::new_unique();
+        let transfer = |lamports| {
+            Transaction::new_signed_with_payer(
+                &[system_instruction::transfer(&payer.pubkey(), &to, lamports)],
+                Some(&payer.pubkey()),
+                &[&payer],
+                Hash::default(),
+            )
+        };
+        // Slot 1 holds two successful transfers, slot 2 a failed one and slot 3 none
+        let transactions = [
+            vec![],
+            vec![transfer(1), transfer(2)],
+            vec![transfer(3)],
+            vec![],
+        ];
+        for (slot, transactions) in transactions.iter().enumerate() {
+            let slot = slot as Slot;
+            let mut hash = Hash::new_unique();
+            let mut entries: Vec<Entry> = transactions
+                .iter()
+                .map(|transaction| next_entry_mut(&mut hash, 1, vec![transaction.clone()]))
+                .collect();
+            entries.extend(create_ticks(1, 0, hash));
+            let shreds = entries_to_test_shreds(&entries, slot, slot.saturating_sub(1), true, 0);
+            blockstore.insert_shreds(shreds, None, false).unwrap();
+
+            for (transaction_index, transaction) in transactions.iter().enumerate() {
+                let meta = match (slot, transaction_index) {
+                    (1, 0) => TransactionStatusMeta {
+                        fee: 5000,
+                        compute_units_consumed: Some(150),
+                        log_messages: Some(vec!["log 1".to_string(), "log 2".to_string()]),
+                        pre_token_balances: Some(vec![token_balance(1, 100)]),
+                        post_token_balances: Some(vec![token_balance(1, 40)]),
+                        ..TransactionStatusMeta::default()
+                    },
+                    (1, _) => TransactionStatusMeta {
+                        fee: 5000,
+                        ..TransactionStatusMeta::default()
+                    },
+                    _ => TransactionStatusMeta {
+                        status: Err(TransactionError::InsufficientFundsForFee),
+                        ..TransactionStatusMeta::default()
+                    },
+                };
+                blockstore
+                    .write_transaction_status(
+                        slot,
+                        transaction.signatures[0],
+                        std::iter::empty(),
+                        meta,
+                        transaction_index,
+                    )
+                    .unwrap();
+            }
+        }
+        let reward_pubkey = Pubkey::new_unique();
+        blockstore
+            .write_rewards(
+                2,
+                RewardsAndNumPartitions {
+                    rewards: vec![Reward {
+                        pubkey: reward_pubkey.to_string(),
+                        lamports: 42,
+                        post_balance: 1042,
+                        reward_type: Some(RewardType::Staking),
+                        commission: Some(5),
+                    }],
+                    num_partitions: None,
+                },
+            )
+            .unwrap();
+        blockstore.set_roots([0, 1, 2, 3].iter()).unwrap();
+
+        let output_dir = tempfile::TempDir::new().unwrap();
+        assert_eq!(
+            export_blocks(&blockstore, 1, 3, output_dir.path()).unwrap(),
+            3
+        );
+        let output_dir = output_dir.path();
+
+        let blocks = read_table(output_dir, BlocksTable::NAME);
+        assert_eq!(
+            primitives::<UInt64Type>(&blocks, "slot"),
+            vec![Some(1), Some(2), Some(3)]
+        );
+        assert_eq!(
+            primitives::<UInt64Type>(&blocks, "parent_slot"),
+            vec![Some(0), Some(1), Some(2)]
+        );
+        assert_eq!(
+            primitives::<UInt64Type>(&blocks, "transaction_count"),
+            vec![Some(2), Some(1), Some(0)]
+        );
+
+        let signatures = [
+            transactions[1][0].signatures[0].to_string(),
+            transactions[1][1].signatures[0].to_string(),
+            transactions[2][0].signatures[0].to_string(),
+        ];
+        let transactions_table = read_table(output_dir, TransactionsTable::NAME);
+        assert_eq!(
+            primitives::<UInt64Type>(&transactions_table, "slot"),
+            vec![Some(1), Some(1), Some(2)]
+        );
+        assert_eq!(
+            primitives::<UInt32Type>(&transactions_table, "transaction_index"),
+            vec![Some(0), Some(1), Some(0)]
+        );
+        assert_eq!(
+            strings(&transactions_table, "signature"),
+            signatures
+                .iter()
+                .map(|signature| Some(signature.as_str()))
+                .collect::<Vec<_>>()
+        );
+        let payer_key = payer.pubkey().to_string();
+        assert_eq!(
+            strings(&transactions_table, "fee_payer"),
+            vec![Some(payer_key.as_str()); 3]
+        );
+        assert_eq!(
+            primitives::<UInt64Type>(&transactions_table, "fee"),
+            vec![Some(5000), Some(5000), Some(0)]
+        );
+        assert_eq!(
+            primitives::<UInt64Type>(&transactions_table, "compute_units_consumed"),
+            vec![Some(150), None, None]
+        );
+        assert_eq!(
+            transactions_table
+                .column_by_name("success")
+                .unwrap()
+                .as_boolean()
+                .iter()
+                .collect::<Vec<_>>(),
+            vec![Some(true), Some(true), Some(false)]
+        );
+        let err = TransactionError::InsufficientFundsForFee.to_string();
+        assert_eq!(
+            strings(&transactions_table, "err"),
+            vec![None, None, Some(err.as_str())]
+        );
+        let account_keys = vec![
+            payer_key.clone(),
+            to.to_string(),
+            system_program::id().to_string(),
+        ];
+        assert_eq!(
+            string_lists(&transactions_table, "account_keys"),
+            vec![Some(account_keys.clone()); 3]
+        );
+        assert_eq!(
+            string_lists(&transactions_table, "log_messages"),
+            vec![
+                Some(vec!["log 1".to_string(), "log 2".to_string()]),
+                None,
+                None
+            ]
+        );
+
+        let instructions = read_table(output_dir, InstructionsTable::NAME);
+        assert_eq!(
+            strings(&instructions, "signature"),
+            signatures
+                .iter()
+                .map(|signature| Some(signature.as_str()))
+                .collect::<Vec<_>>()
+        );
+        let system_program_id = system_program::id().to_string();
+        assert_eq!(
+            strings(&instructions, "program_id"),
+            vec![Some(system_program_id.as_str()); 3]
+        );
+        assert_eq!(strings(&instructions, "program"), vec![Some("system"); 3]);
+        assert_eq!(
+            primitives::<UInt32Type>(&instructions, "inner_instruction_index"),
+            vec![None; 3]
+        );
+        for (parsed, lamports) in strings(&instructions, "parsed").into_iter().zip(1u64..) {
+            let parsed: serde_json::Value = serde_json::from_str(parsed.unwrap()).unwrap();
+            assert_eq!(parsed["type"], "transfer");
+            assert_eq!(parsed["info"]["lamports"], lamports);
+        }
+        assert_eq!(
+            string_lists(&instructions, "accounts"),
+            vec![Some(account_keys[..2].to_vec()); 3]
+        );
+
+        let token_balances = read_table(output_dir, TokenBalancesTable::NAME);
+        assert_eq!(token_balances.num_rows(), 1);
+        let to_key = to.to_string();
+        assert_eq!(
+            strings(&token_balances, "signature"),
+            vec![Some(signatures[0].as_str())]
+        );
+        assert_eq!(
+            strings(&token_balances, "account"),
+            vec![Some(to_key.as_str())]
+        );
+        assert_eq!(strings(&token_balances, "mint"), vec![Some("mint")]);
+        assert_eq!(
+            primitives::<UInt8Type>(&token_balances, "decimals"),
+            vec![Some(2)]
+        );
+        assert_eq!(
+            primitives::<UInt64Type>(&token_balances, "pre_amount"),
+            vec![Some(100)]
+        );
+        assert_eq!(
+            primitives::<UInt64Type>(&token_balances, "post_amount"),
+            vec![Some(40)]
+        );
+        assert_eq!(
+            primitives::<Decimal128Type>(&token_balances, "delta"),
+            vec![Some(-60)]
+        );
+
+        let rewards = read_table(output_dir, RewardsTable::NAME);
+        let reward_key = reward_pubkey.to_string();
+        assert_eq!(primitives::<UInt64Type>(&rewards, "slot"), vec![Some(2)]);
+        assert_eq!(strings(&rewards, "pubkey"), vec![Some(reward_key.as_str())]);
+        assert_eq!(
+            primitives::<Int64Type>(&rewards, "lamports"),
+            vec![Some(42)]
+        );
+        assert_eq!(
+            primitives::<UInt64Type>(&rewards, "post_balance"),
+            vec![Some(1042)]
+        );
+        assert_eq!(strings(&rewards, "reward_type"), vec![Some("staking")]);
+        assert_eq!(
+            primitives::<UInt8Type>(&rewards, "commission"),
+            vec![Some(5)]
+        );
+    }
+}
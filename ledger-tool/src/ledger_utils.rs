@@ -325,6 +325,7 @@ pub fn load_and_process_ledger(
                 Some(TransactionStatusSender {
                     sender: transaction_status_sender,
                     dependency_tracker: None,
+                    enable_log_event_recording: false,
                 }),
                 Some(transaction_status_service),
             )
@@ -11,6 +11,9 @@ use {
             SlotBankHash,
         },
         program::*,
+        snapshot_diff::{
+            diff_accounts, load_bank_from_snapshot_archives, AccountDiffOutput, SnapshotArchives,
+        },
     },
     agave_feature_set::{self as feature_set, FeatureSet},
     agave_reserved_account_keys::ReservedAccountKeys,
@@ -34,9 +37,17 @@ use {
     solana_clock::{Epoch, Slot},
     solana_cluster_type::ClusterType,
     solana_core::{
-        banking_simulation::{BankingSimulator, BankingTraceEvents},
+        banking_simulation::{
+            BankingSimulator, BankingTraceEvents, SimulatedScheduler, SimulationConfig,
+            SimulationReport,
+        },
+        banking_stage::{
+            transaction_scheduler::scheduler_controller::SchedulerConfig, BankingStage,
+        },
         system_monitor_service::{SystemMonitorService, SystemMonitorStatsReportConfig},
-        validator::{BlockProductionMethod, BlockVerificationMethod, TransactionStructure},
+        validator::{
+            BlockProductionMethod, BlockVerificationMethod, SchedulerPacing, TransactionStructure,
+        },
     },
     solana_cost_model::{cost_model::CostModel, cost_tracker::CostTracker},
     solana_entry::entry::create_ticks,
@@ -67,7 +78,7 @@ use {
         snapshot_bank_utils,
         snapshot_minimizer::SnapshotMinimizer,
         snapshot_utils::{
-            ArchiveFormat, SnapshotVersion, DEFAULT_ARCHIVE_COMPRESSION,
+            ArchiveFormat, SeekableSnapshotArchive, SnapshotVersion, DEFAULT_ARCHIVE_COMPRESSION,
             SUPPORTED_ARCHIVE_COMPRESSION,
         },
     },
@@ -85,11 +96,12 @@ use {
         vote_state::{self, VoteStateV3},
     },
     std::{
-        collections::{HashMap, HashSet},
+        collections::{BTreeSet, HashMap, HashSet},
         ffi::{OsStr, OsString},
         fs::{read_dir, File},
         io::{self, Write},
         mem::swap,
+        num::NonZeroUsize,
         path::{Path, PathBuf},
         process::{exit, Command, Stdio},
         str::FromStr,
@@ -105,10 +117,13 @@ mod args;
 mod bigtable;
 mod blockstore;
 mod error;
+mod export;
 mod ledger_path;
 mod ledger_utils;
 mod output;
+mod profile;
 mod program;
+mod snapshot_diff;
 
 fn render_dot(dot: String, output_file: &str, output_format: &str) -> io::Result<()> {
     let mut child = Command::new("dot")
@@ -555,6 +570,47 @@ fn load_banking_trace_events_or_exit(ledger_path: &Path) -> BankingTraceEvents {
     }
 }
 
+fn print_simulation_reports(reports: &[(SimulatedScheduler, SimulationReport)]) {
+    let slots: BTreeSet<Slot> = reports
+        .iter()
+        .flat_map(|(_scheduler, report)| report.slot_stats.keys().copied())
+        .collect();
+
+    print!("{:>12}", "slot");
+    for (scheduler, _report) in reports {
+        print!(" | {:^55}", scheduler.to_string());
+    }
+    println!();
+    print!("{:>12}", "");
+    for _ in reports {
+        print!(
+            " | {:>16} {:>14} {:>12} {:>10}",
+            "fees", "cus", "txs", "dropped"
+        );
+    }
+    println!();
+
+    for slot in slots {
+        print!("{slot:>12}");
+        for (_scheduler, report) in reports {
+            match report.slot_stats.get(&slot) {
+                Some(stats) => print!(
+                    " | {:>16} {:>14} {:>12} {:>10}",
+                    stats.collector_fees,
+                    stats.block_cost,
+                    stats.executed_transaction_count,
+                    stats
+                        .dropped_transaction_count
+                        .map(|count| count.to_string())
+                        .unwrap_or_else(|| "-".to_string()),
+                ),
+                None => print!(" | {:^55}", "-"),
+            }
+        }
+        println!();
+    }
+}
+
 fn read_banking_trace_event_file_paths_or_exit(banking_trace_path: PathBuf) -> Vec<PathBuf> {
     info!("Using: banking trace events dir: {banking_trace_path:?}");
 
@@ -690,6 +746,7 @@ fn setup_slot_recording(
                     Some(TransactionStatusSender {
                         sender,
                         dependency_tracker: None,
+                        enable_log_event_recording: false,
                     }),
                     transaction_recorder,
                 )
@@ -951,6 +1008,9 @@ fn main() {
         .max(rent.minimum_balance(StakeStateV2::size_of()))
         .to_string();
     let default_graph_vote_account_mode = GraphVoteAccountMode::default();
+    let default_block_production_num_workers = &BankingStage::default_num_workers().to_string();
+    let default_block_production_pacing_fill_time_millis =
+        &BankingStage::default_fill_time_millis().to_string();
 
     let mut measure_total_execution_time = Measure::start("ledger tool");
 
@@ -1542,6 +1602,45 @@ fn main() {
                         .default_value(TransactionStructure::default().into())
                         .help(TransactionStructure::cli_message()),
                 )
+                .arg(
+                    Arg::with_name("scheduler")
+                        .long("scheduler")
+                        .value_name("SCHEDULER")
+                        .takes_value(true)
+                        .multiple(true)
+                        .number_of_values(1)
+                        .possible_values(SimulatedScheduler::cli_names())
+                        .help(
+                            "Switch transaction scheduler for simulating block production. Can be \
+                             specified multiple times to compare the schedulers side by side. \
+                             [default: the scheduler of --block-production-method]",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("block_production_num_workers")
+                        .long("block-production-num-workers")
+                        .value_name("NUMBER")
+                        .takes_value(true)
+                        .validator(|s| {
+                            is_within_range(s, 1..=BankingStage::max_num_workers().get())
+                        })
+                        .default_value(default_block_production_num_workers)
+                        .help(
+                            "Number of banking stage workers, or handler threads of the unified \
+                             scheduler",
+                        ),
+                )
+                .arg(
+                    Arg::with_name("block_production_pacing_fill_time_millis")
+                        .long("block-production-pacing-fill-time-millis")
+                        .value_name("MILLIS")
+                        .takes_value(true)
+                        .default_value(default_block_production_pacing_fill_time_millis)
+                        .help(
+                            "Pacing fill time in milliseconds for the central schedulers, or \
+                             'disabled'",
+                        ),
+                )
                 .arg(
                     Arg::with_name("first_simulated_slot")
                         .long("first-simulated-slot")
@@ -1610,6 +1709,65 @@ fn main() {
                         .validator(is_pubkey)
                         .conflicts_with("account")
                         .help("Limit output to accounts owned by the provided program pubkey"),
+                )
+                .arg(
+                    Arg::with_name("snapshot_archive")
+                        .long("snapshot-archive")
+                        .takes_value(true)
+                        .value_name("PATH")
+                        .requires("account")
+                        .help(
+                            "Read the accounts directly from this seekable snapshot archive, \
+                             instead of processing the ledger",
+                        ),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("snapshot-diff")
+                .about("Print the accounts which differ between two snapshots")
+                .arg(&load_genesis_config_arg)
+                .args(&accounts_db_config_args)
+                .arg(
+                    Arg::with_name("old_snapshot")
+                        .long("old-snapshot")
+                        .takes_value(true)
+                        .value_name("PATH")
+                        .required(true)
+                        .help("Full snapshot archive to diff from"),
+                )
+                .arg(
+                    Arg::with_name("old_incremental_snapshot")
+                        .long("old-incremental-snapshot")
+                        .takes_value(true)
+                        .value_name("PATH")
+                        .help("Incremental snapshot archive on top of --old-snapshot"),
+                )
+                .arg(
+                    Arg::with_name("new_snapshot")
+                        .long("new-snapshot")
+                        .takes_value(true)
+                        .value_name("PATH")
+                        .required(true)
+                        .help("Full snapshot archive to diff to"),
+                )
+                .arg(
+                    Arg::with_name("new_incremental_snapshot")
+                        .long("new-incremental-snapshot")
+                        .takes_value(true)
+                        .value_name("PATH")
+                        .help("Incremental snapshot archive on top of --new-snapshot"),
+                )
+                .arg(
+                    Arg::with_name("owner")
+                        .long("owner")
+                        .takes_value(true)
+                        .value_name("PUBKEY")
+                        .validator(is_pubkey)
+                        .multiple(true)
+                        .help(
+                            "Limit the diff to accounts owned by the specified program(s) in \
+                             either snapshot, may be specified multiple times",
+                        ),
                 ),
         )
         .subcommand(
@@ -1711,6 +1869,7 @@ fn main() {
         | ("copy", Some(_))
         | ("dead-slots", Some(_))
         | ("duplicate-slots", Some(_))
+        | ("export", Some(_))
         | ("latest-optimistic-slots", Some(_))
         | ("list-roots", Some(_))
         | ("parse_full_frozen", Some(_))
@@ -2056,7 +2215,9 @@ fn main() {
                             .unwrap_or_else(|| {
                                 panic!("Archive format not recognized: {archive_format_str}")
                             });
-                        if let ArchiveFormat::TarZstd { config } = &mut archive_format {
+                        if let ArchiveFormat::TarZstd { config }
+                        | ArchiveFormat::TarZstdSeekable { config } = &mut archive_format
+                        {
                             config.compression_level = value_t_or_exit!(
                                 arg_matches,
                                 "snapshot_zstd_compression_level",
@@ -2547,22 +2708,45 @@ fn main() {
                     }
                 }
                 ("simulate-block-production", Some(arg_matches)) => {
-                    let mut process_options = parse_process_options(&ledger_path, arg_matches);
-
-                    let banking_trace_events = load_banking_trace_events_or_exit(&ledger_path);
-                    process_options.hash_overrides =
-                        Some(banking_trace_events.hash_overrides().clone());
-
-                    let slot = value_t!(arg_matches, "first_simulated_slot", Slot).unwrap();
-                    let simulator = BankingSimulator::new(banking_trace_events, slot);
-                    let Some(parent_slot) = simulator.parent_slot() else {
+                    let schedulers = if arg_matches.is_present("scheduler") {
+                        values_t_or_exit!(arg_matches, "scheduler", SimulatedScheduler)
+                    } else {
+                        vec![value_t_or_exit!(
+                            arg_matches,
+                            "block_production_method",
+                            BlockProductionMethod
+                        )
+                        .into()]
+                    };
+                    let block_verification_method = value_t_or_exit!(
+                        arg_matches,
+                        "block_verification_method",
+                        BlockVerificationMethod
+                    );
+                    if schedulers.contains(&SimulatedScheduler::UnifiedScheduler)
+                        && matches!(
+                            block_verification_method,
+                            BlockVerificationMethod::UnifiedScheduler
+                        )
+                    {
                         eprintln!(
-                            "Couldn't determine parent_slot of first_simulated_slot: {slot} due \
-                             to missing banking_trace_event data."
+                            "Simulating with the unified scheduler requires \
+                             --block-verification-method blockstore-processor"
                         );
                         exit(1);
+                    }
+                    let transaction_struct =
+                        value_t_or_exit!(arg_matches, "transaction_struct", TransactionStructure);
+                    let num_workers =
+                        value_t_or_exit!(arg_matches, "block_production_num_workers", NonZeroUsize);
+                    let scheduler_config = SchedulerConfig {
+                        scheduler_pacing: value_t_or_exit!(
+                            arg_matches,
+                            "block_production_pacing_fill_time_millis",
+                            SchedulerPacing
+                        ),
                     };
-                    process_options.halt_at_slot = Some(parent_slot);
+                    let slot = value_t!(arg_matches, "first_simulated_slot", Slot).unwrap();
 
                     // PrimaryForMaintenance needed over Secondary to purge any
                     // existing simulated shreds from previous runs
@@ -2571,9 +2755,32 @@ fn main() {
                         arg_matches,
                         AccessType::PrimaryForMaintenance,
                     ));
-                    let genesis_config = open_genesis_config_by(&ledger_path, arg_matches);
-                    let LoadAndProcessLedgerOutput { bank_forks, .. } =
-                        load_and_process_ledger_or_exit(
+
+                    let mut reports = Vec::with_capacity(schedulers.len());
+                    for scheduler in schedulers {
+                        let mut process_options = parse_process_options(&ledger_path, arg_matches);
+
+                        let banking_trace_events = load_banking_trace_events_or_exit(&ledger_path);
+                        process_options.hash_overrides =
+                            Some(banking_trace_events.hash_overrides().clone());
+
+                        let simulator = BankingSimulator::new(banking_trace_events, slot);
+                        let Some(parent_slot) = simulator.parent_slot() else {
+                            eprintln!(
+                                "Couldn't determine parent_slot of first_simulated_slot: {slot} \
+                                 due to missing banking_trace_event data."
+                            );
+                            exit(1);
+                        };
+                        process_options.halt_at_slot = Some(parent_slot);
+
+                        // Every scheduler starts from the freshly replayed parent slot
+                        let genesis_config = open_genesis_config_by(&ledger_path, arg_matches);
+                        let LoadAndProcessLedgerOutput {
+                            bank_forks,
+                            accounts_background_service,
+                            ..
+                        } = load_and_process_ledger_or_exit(
                             arg_matches,
                             &genesis_config,
                             blockstore.clone(),
@@ -2581,51 +2788,37 @@ fn main() {
                             None, // transaction status sender
                         );
 
-                    let block_production_method = value_t_or_exit!(
-                        arg_matches,
-                        "block_production_method",
-                        BlockProductionMethod
-                    );
-                    let transaction_struct =
-                        value_t_or_exit!(arg_matches, "transaction_struct", TransactionStructure);
+                        info!(
+                            "Using: scheduler: {scheduler} transaction-structure: \
+                             {transaction_struct} num-workers: {num_workers}"
+                        );
 
-                    info!(
-                        "Using: block-production-method: {block_production_method} \
-                         transaction-structure: {transaction_struct}"
-                    );
+                        let config = SimulationConfig {
+                            scheduler,
+                            transaction_struct: transaction_struct.clone(),
+                            num_workers,
+                            scheduler_config: scheduler_config.clone(),
+                        };
+                        match simulator.start(
+                            genesis_config,
+                            bank_forks,
+                            blockstore.clone(),
+                            config,
+                        ) {
+                            Ok(report) => reports.push((scheduler, report)),
+                            Err(error) => {
+                                eprintln!("{error:?}");
+                                exit(1);
+                            }
+                        };
+                        // Let go of the accounts of this run before loading the next one
+                        accounts_background_service.join().unwrap();
+                    }
 
-                    match simulator.start(
-                        genesis_config,
-                        bank_forks,
-                        blockstore,
-                        block_production_method,
-                        transaction_struct,
-                    ) {
-                        Ok(()) => println!("Ok"),
-                        Err(error) => {
-                            eprintln!("{error:?}");
-                            exit(1);
-                        }
-                    };
+                    print_simulation_reports(&reports);
+                    println!("Ok");
                 }
                 ("accounts", Some(arg_matches)) => {
-                    let process_options = parse_process_options(&ledger_path, arg_matches);
-                    let genesis_config = open_genesis_config_by(&ledger_path, arg_matches);
-                    let blockstore = open_blockstore(
-                        &ledger_path,
-                        arg_matches,
-                        get_access_type(&process_options),
-                    );
-                    let LoadAndProcessLedgerOutput { bank_forks, .. } =
-                        load_and_process_ledger_or_exit(
-                            arg_matches,
-                            &genesis_config,
-                            Arc::new(blockstore),
-                            process_options,
-                            None,
-                        );
-                    let bank = bank_forks.read().unwrap().working_bank();
-
                     let include_sysvars = arg_matches.is_present("include_sysvars");
                     let output_config = if arg_matches.is_present("no_account_contents") {
                         None
@@ -2652,7 +2845,43 @@ fn main() {
                         OutputFormat::from_matches(arg_matches, "output_format", false);
 
                     let accounts_streamer =
-                        AccountsOutputStreamer::new(bank, output_format, config);
+                        if let Some(archive_path) = arg_matches.value_of("snapshot_archive") {
+                            let archive = SeekableSnapshotArchive::open(archive_path)
+                                .unwrap_or_else(|err| {
+                                    eprintln!(
+                                        "Failed to open seekable snapshot archive {archive_path}: \
+                                         {err}"
+                                    );
+                                    exit(1);
+                                });
+                            AccountsOutputStreamer::new_from_snapshot_archive(
+                                archive,
+                                output_format,
+                                config,
+                            )
+                            .unwrap_or_else(|err| {
+                                eprintln!("{err}");
+                                exit(1);
+                            })
+                        } else {
+                            let process_options = parse_process_options(&ledger_path, arg_matches);
+                            let genesis_config = open_genesis_config_by(&ledger_path, arg_matches);
+                            let blockstore = open_blockstore(
+                                &ledger_path,
+                                arg_matches,
+                                get_access_type(&process_options),
+                            );
+                            let LoadAndProcessLedgerOutput { bank_forks, .. } =
+                                load_and_process_ledger_or_exit(
+                                    arg_matches,
+                                    &genesis_config,
+                                    Arc::new(blockstore),
+                                    process_options,
+                                    None,
+                                );
+                            let bank = bank_forks.read().unwrap().working_bank();
+                            AccountsOutputStreamer::new(bank, output_format, config)
+                        };
                     let (_, scan_time) = measure_time!(
                         accounts_streamer
                             .output()
@@ -2661,6 +2890,71 @@ fn main() {
                     );
                     info!("{scan_time}");
                 }
+                ("snapshot-diff", Some(arg_matches)) => {
+                    let genesis_config = open_genesis_config_by(&ledger_path, arg_matches);
+                    let accounts_db_config = get_accounts_db_config(&ledger_path, arg_matches);
+                    let owners: HashSet<_> = pubkeys_of(arg_matches, "owner")
+                        .unwrap_or_default()
+                        .into_iter()
+                        .collect();
+                    let output_format =
+                        OutputFormat::from_matches(arg_matches, "output_format", false);
+
+                    let work_dir = ledger_path
+                        .join(LEDGER_TOOL_DIRECTORY)
+                        .join("snapshot_diff");
+                    if work_dir.exists() {
+                        std::fs::remove_dir_all(&work_dir).unwrap_or_else(|err| {
+                            eprintln!("Failed to clean {}: {err}", work_dir.display());
+                            exit(1);
+                        });
+                    }
+                    let load_bank = |name: &str| {
+                        let archives = SnapshotArchives {
+                            full: PathBuf::from(
+                                arg_matches.value_of(format!("{name}_snapshot")).unwrap(),
+                            ),
+                            incremental: arg_matches
+                                .value_of(format!("{name}_incremental_snapshot"))
+                                .map(PathBuf::from),
+                        };
+                        load_bank_from_snapshot_archives(
+                            &archives,
+                            &genesis_config,
+                            accounts_db_config.clone(),
+                            &work_dir.join(name),
+                        )
+                        .unwrap_or_else(|err| {
+                            eprintln!("Failed to load {name} snapshot: {err}");
+                            exit(1);
+                        })
+                    };
+                    let old_bank = load_bank("old");
+                    let new_bank = load_bank("new");
+
+                    let stats = diff_accounts(&old_bank, &new_bank, &owners, |pubkey, diff| {
+                        let diff = AccountDiffOutput {
+                            pubkey: *pubkey,
+                            diff,
+                        };
+                        match output_format {
+                            OutputFormat::Json | OutputFormat::JsonCompact => {
+                                println!("{}", serde_json::to_string(&diff).unwrap())
+                            }
+                            _ => println!("{diff}"),
+                        }
+                    })
+                    .unwrap_or_else(|err| {
+                        eprintln!("Failed to diff snapshots: {err}");
+                        exit(1);
+                    });
+                    match output_format {
+                        OutputFormat::Json | OutputFormat::JsonCompact => {
+                            println!("{}", serde_json::to_string(&stats).unwrap())
+                        }
+                        _ => println!("\n{stats:#?}"),
+                    }
+                }
                 ("capitalization", Some(arg_matches)) => {
                     let process_options = parse_process_options(&ledger_path, arg_matches);
                     let genesis_config = open_genesis_config_by(&ledger_path, arg_matches);
@@ -5,6 +5,7 @@ use {
     },
     chrono::{Local, TimeZone},
     itertools::Either,
+    log::error,
     pretty_hex::PrettyHex,
     serde::ser::{Impossible, SerializeSeq, SerializeStruct, Serializer},
     serde_derive::{Deserialize, Serialize},
@@ -25,7 +26,7 @@ use {
         shred::{Shred, ShredType},
     },
     solana_pubkey::Pubkey,
-    solana_runtime::bank::Bank,
+    solana_runtime::{bank::Bank, snapshot_utils::SeekableSnapshotArchive},
     solana_transaction::versioned::VersionedTransaction,
     solana_transaction_status::{
         BlockEncodingOptions, ConfirmedBlock, Encodable, EncodedConfirmedBlock,
@@ -773,9 +774,35 @@ pub struct AccountsOutputConfig {
 
 impl AccountsOutputStreamer {
     pub fn new(bank: Arc<Bank>, output_format: OutputFormat, config: AccountsOutputConfig) -> Self {
+        Self::new_with_source(AccountsSource::Bank(bank), output_format, config)
+    }
+
+    /// Streams accounts read directly from a seekable snapshot archive
+    ///
+    /// Only individual accounts can be looked up in an archive.
+    pub fn new_from_snapshot_archive(
+        archive: SeekableSnapshotArchive,
+        output_format: OutputFormat,
+        config: AccountsOutputConfig,
+    ) -> std::result::Result<Self, String> {
+        if !matches!(config.mode, AccountsOutputMode::Individual(_)) {
+            return Err("only individual accounts can be read from a snapshot archive".to_string());
+        }
+        Ok(Self::new_with_source(
+            AccountsSource::SnapshotArchive(RefCell::new(archive)),
+            output_format,
+            config,
+        ))
+    }
+
+    fn new_with_source(
+        source: AccountsSource,
+        output_format: OutputFormat,
+        config: AccountsOutputConfig,
+    ) -> Self {
         let total_accounts_stats = Rc::new(RefCell::new(TotalAccountsStats::default()));
         let account_scanner = AccountsScanner {
-            bank,
+            source,
             total_accounts_stats: total_accounts_stats.clone(),
             config,
         };
@@ -846,8 +873,13 @@ impl TotalAccountsStats {
     }
 }
 
+enum AccountsSource {
+    Bank(Arc<Bank>),
+    SnapshotArchive(RefCell<SeekableSnapshotArchive>),
+}
+
 struct AccountsScanner {
-    bank: Arc<Bank>,
+    source: AccountsSource,
     total_accounts_stats: Rc<RefCell<TotalAccountsStats>>,
     config: AccountsOutputConfig,
 }
@@ -902,13 +934,33 @@ impl AccountsScanner {
             }
         };
 
+        let bank = match &self.source {
+            AccountsSource::Bank(bank) => bank,
+            AccountsSource::SnapshotArchive(archive) => {
+                let AccountsOutputMode::Individual(pubkeys) = &self.config.mode else {
+                    unreachable!("only individual accounts are read from snapshot archives");
+                };
+                let mut archive = archive.borrow_mut();
+                for pubkey in pubkeys {
+                    match archive.get_account(pubkey) {
+                        Ok(Some((account, _slot))) if self.should_process_account(&account) => {
+                            total_accounts_stats.accumulate_account(&account);
+                            self.maybe_output_account(seq_serializer, pubkey, &account);
+                        }
+                        Ok(_) => {}
+                        Err(err) => error!("Failed to read account {pubkey} from archive: {err}"),
+                    }
+                }
+                return;
+            }
+        };
+
         match &self.config.mode {
             AccountsOutputMode::All => {
-                self.bank.scan_all_accounts(scan_func, true).unwrap();
+                bank.scan_all_accounts(scan_func, true).unwrap();
             }
             AccountsOutputMode::Individual(pubkeys) => pubkeys.iter().for_each(|pubkey| {
-                if let Some((account, _slot)) = self
-                    .bank
+                if let Some((account, _slot)) = bank
                     .get_account_modified_slot_with_fixed_root(pubkey)
                     .filter(|(account, _)| self.should_process_account(account))
                 {
@@ -916,8 +968,7 @@ impl AccountsScanner {
                     self.maybe_output_account(seq_serializer, pubkey, &account);
                 }
             }),
-            AccountsOutputMode::Program(program_pubkey) => self
-                .bank
+            AccountsOutputMode::Program(program_pubkey) => bank
                 .get_program_accounts(program_pubkey, &ScanConfig::new(ScanOrder::Sorted))
                 .unwrap()
                 .iter()
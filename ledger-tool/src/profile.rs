@@ -0,0 +1,467 @@
+//! Instruction level profiles of sBPF programs, built from the VM register traces
+//!
+//! Every executed instruction costs one compute unit. The compute units consumed by syscalls are
+//! attributed using the samples of the compute meter the VM takes before every syscall, see
+//! [`ComputeMeterSample`].
+
+use {
+    solana_program_runtime::invoke_context::{ComputeMeterSample, InvokeContext},
+    solana_sbpf::{
+        ebpf::{self, Insn},
+        elf::Executable,
+        static_analysis::Analysis,
+    },
+    std::{
+        collections::BTreeMap,
+        io::{self, Write},
+    },
+};
+
+/// Index of the program counter in a VM register trace entry
+const PC_REGISTER: usize = 11;
+
+#[derive(Default)]
+struct CallProfile {
+    count: u64,
+    /// Compute units consumed by the callee, including the calls it made
+    inclusive_cost: u64,
+}
+
+#[derive(Default)]
+struct FunctionProfile {
+    /// Compute units consumed by the function itself
+    self_cost: u64,
+    /// Instructions executed per basic block, keyed by the pc of their first instruction
+    basic_blocks: BTreeMap<usize, u64>,
+    /// Calls made by the function, keyed by the pc of the call instruction and the callee
+    calls: BTreeMap<(usize, String), CallProfile>,
+}
+
+struct Frame {
+    function: String,
+    /// Compute units consumed by the program when the function was entered
+    entered_at: u64,
+    /// pc of the instruction which called the function
+    call_pc: usize,
+}
+
+/// How an instruction transfers control, which depends on the SBPF version of the program
+enum ControlFlow {
+    /// Syscall, identified by the key of the syscall in the loader
+    Syscall(u32),
+    Call,
+    Return,
+    Other,
+}
+
+fn control_flow(executable: &Executable<InvokeContext>, insn: &Insn) -> ControlFlow {
+    let static_syscalls = executable.get_sbpf_version().static_syscalls();
+    let syscall_key = insn.imm as u32;
+    match insn.opc {
+        // Before SBPFv3, syscalls are calls whose immediate is the key of a syscall
+        ebpf::CALL_IMM
+            if !static_syscalls
+                && executable
+                    .get_loader()
+                    .get_function_registry()
+                    .lookup_by_key(syscall_key)
+                    .is_some() =>
+        {
+            ControlFlow::Syscall(syscall_key)
+        }
+        ebpf::CALL_IMM | ebpf::CALL_REG => ControlFlow::Call,
+        ebpf::SYSCALL if static_syscalls => ControlFlow::Syscall(syscall_key),
+        ebpf::RETURN if static_syscalls => ControlFlow::Return,
+        ebpf::EXIT if !static_syscalls => ControlFlow::Return,
+        _ => ControlFlow::Other,
+    }
+}
+
+/// Compute units consumed by programs, attributed to functions, basic blocks and syscalls
+#[derive(Default)]
+pub struct Profile {
+    /// Compute units consumed per call stack, rooted at the program id
+    stacks: BTreeMap<Vec<String>, u64>,
+    /// Profiles of the functions, keyed by program id and function name
+    functions: BTreeMap<(String, String), FunctionProfile>,
+}
+
+impl Profile {
+    /// Adds the register trace of one execution of a program
+    ///
+    /// `compute_meter_samples` are the samples taken by the VM which produced `register_trace`.
+    pub fn add_vm_trace(
+        &mut self,
+        program_id: &str,
+        executable: &Executable<InvokeContext>,
+        register_trace: &[[u64; 12]],
+        compute_meter_samples: &[ComputeMeterSample],
+    ) {
+        let Ok(analysis) = Analysis::from_executable(executable) else {
+            return;
+        };
+        let function_of = |pc: usize| {
+            analysis
+                .functions
+                .range(..=pc)
+                .next_back()
+                .map(|(_, (_, name))| name.clone())
+                .unwrap_or_else(|| format!("function_{pc}"))
+        };
+        let basic_block_of = |pc: usize| {
+            analysis
+                .cfg_nodes
+                .range(..=pc)
+                .next_back()
+                .map(|(start, _)| *start)
+                .unwrap_or(pc)
+        };
+
+        let Some(entry_pc) = register_trace
+            .first()
+            .map(|registers| registers[PC_REGISTER])
+        else {
+            return;
+        };
+        let mut stack = vec![Frame {
+            function: function_of(entry_pc as usize),
+            entered_at: 0,
+            call_pc: 0,
+        }];
+        let mut consumed = 0u64;
+        let mut syscall_index = 0usize;
+
+        for (index, registers) in register_trace.iter().enumerate() {
+            let pc = registers[PC_REGISTER] as usize;
+            let Ok(insn_index) = analysis
+                .instructions
+                .binary_search_by_key(&pc, |insn| insn.ptr)
+            else {
+                continue;
+            };
+            let insn = &analysis.instructions[insn_index];
+            let next_pc = register_trace
+                .get(index.saturating_add(1))
+                .map(|registers| registers[PC_REGISTER] as usize);
+
+            let current = (
+                program_id.to_string(),
+                stack.last().unwrap().function.clone(),
+            );
+            self.add_cost(program_id, &stack, None, 1);
+            let function = self.functions.entry(current.clone()).or_default();
+            *function.basic_blocks.entry(basic_block_of(pc)).or_default() += 1;
+            consumed = consumed.saturating_add(1);
+
+            match control_flow(executable, insn) {
+                ControlFlow::Syscall(key) => {
+                    let syscall = executable
+                        .get_loader()
+                        .get_function_registry()
+                        .lookup_by_key(key)
+                        .map(|(name, _)| String::from_utf8_lossy(name).into_owned())
+                        .unwrap_or_else(|| format!("syscall_{key:#x}"));
+                    let cost = syscall_cost(compute_meter_samples, syscall_index);
+                    syscall_index = syscall_index.saturating_add(1);
+                    self.add_cost(program_id, &stack, Some(&syscall), cost);
+                    let call = self
+                        .functions
+                        .entry(current)
+                        .or_default()
+                        .calls
+                        .entry((pc, syscall.clone()))
+                        .or_default();
+                    call.count = call.count.saturating_add(1);
+                    call.inclusive_cost = call.inclusive_cost.saturating_add(cost);
+                    let syscall = self
+                        .functions
+                        .entry((program_id.to_string(), syscall))
+                        .or_default();
+                    syscall.self_cost = syscall.self_cost.saturating_add(cost);
+                    consumed = consumed.saturating_add(cost);
+                }
+                ControlFlow::Call => {
+                    if let Some(next_pc) = next_pc {
+                        stack.push(Frame {
+                            function: function_of(next_pc),
+                            entered_at: consumed,
+                            call_pc: pc,
+                        });
+                    }
+                }
+                ControlFlow::Return if stack.len() > 1 => {
+                    let frame = stack.pop().unwrap();
+                    self.add_call(program_id, &stack, frame, consumed);
+                }
+                ControlFlow::Return | ControlFlow::Other => {}
+            }
+        }
+        // Unwind the calls which did not return, e.g. because the program aborted
+        while stack.len() > 1 {
+            let frame = stack.pop().unwrap();
+            self.add_call(program_id, &stack, frame, consumed);
+        }
+    }
+
+    fn add_cost(&mut self, program_id: &str, stack: &[Frame], syscall: Option<&str>, cost: u64) {
+        let path = std::iter::once(program_id)
+            .chain(stack.iter().map(|frame| frame.function.as_str()))
+            .chain(syscall)
+            .map(str::to_string)
+            .collect();
+        let stack_cost = self.stacks.entry(path).or_default();
+        *stack_cost = stack_cost.saturating_add(cost);
+        if syscall.is_none() {
+            if let Some(frame) = stack.last() {
+                let function = self
+                    .functions
+                    .entry((program_id.to_string(), frame.function.clone()))
+                    .or_default();
+                function.self_cost = function.self_cost.saturating_add(cost);
+            }
+        }
+    }
+
+    fn add_call(&mut self, program_id: &str, callers: &[Frame], frame: Frame, consumed: u64) {
+        let Some(caller) = callers.last() else {
+            return;
+        };
+        let call = self
+            .functions
+            .entry((program_id.to_string(), caller.function.clone()))
+            .or_default()
+            .calls
+            .entry((frame.call_pc, frame.function))
+            .or_default();
+        call.count = call.count.saturating_add(1);
+        call.inclusive_cost = call
+            .inclusive_cost
+            .saturating_add(consumed.saturating_sub(frame.entered_at));
+    }
+
+    /// Writes the compute units per call stack in the folded stacks format of flamegraph tools
+    pub fn write_folded_stacks(&self, out: &mut impl Write) -> io::Result<()> {
+        for (stack, cost) in &self.stacks {
+            writeln!(out, "{} {cost}", stack.join(";"))?;
+        }
+        Ok(())
+    }
+
+    /// Writes the profile in the callgrind format, using the program ids as object names and the
+    /// pc of the instructions as line numbers
+    pub fn write_callgrind(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "# callgrind format")?;
+        writeln!(out, "version: 1")?;
+        writeln!(out, "creator: agave-ledger-tool")?;
+        writeln!(out, "events: ComputeUnits")?;
+        for ((program_id, name), function) in &self.functions {
+            writeln!(out)?;
+            writeln!(out, "ob={program_id}")?;
+            writeln!(out, "fn={name}")?;
+            if function.basic_blocks.is_empty() {
+                // Syscalls
+                writeln!(out, "0 {}", function.self_cost)?;
+            }
+            for (pc, count) in &function.basic_blocks {
+                writeln!(out, "{pc} {count}")?;
+            }
+            for ((pc, callee), call) in &function.calls {
+                writeln!(out, "cfn={callee}")?;
+                writeln!(out, "calls={} 0", call.count)?;
+                writeln!(out, "{pc} {}", call.inclusive_cost)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the compute units charged by the syscall made after the sample at `syscall_index`
+///
+/// The VM takes a sample before every syscall, the next sample is taken before the next syscall
+/// or when the program exits.
+fn syscall_cost(compute_meter_samples: &[ComputeMeterSample], syscall_index: usize) -> u64 {
+    let (Some(sample), Some(next_sample)) = (
+        compute_meter_samples.get(syscall_index),
+        compute_meter_samples.get(syscall_index.saturating_add(1)),
+    ) else {
+        return 0;
+    };
+    sample
+        .remaining
+        .saturating_sub(sample.consumed)
+        .saturating_sub(next_sample.remaining)
+}
+
+/// Keeps only the compute meter samples taken by the VM of the instruction at `index_in_trace`
+pub fn samples_of_instruction(
+    invoke_context: &InvokeContext,
+    index_in_trace: usize,
+) -> Vec<ComputeMeterSample> {
+    invoke_context
+        .get_compute_meter_samples()
+        .iter()
+        .filter(|sample| sample.index_in_trace == index_in_trace)
+        .copied()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        agave_syscalls::SyscallLog,
+        solana_sbpf::{
+            assembler::assemble,
+            program::{BuiltinProgram, SBPFVersion},
+            vm::Config,
+        },
+        std::sync::Arc,
+    };
+
+    const PROGRAM: &str = "
+        entrypoint:
+            call function_foo
+            syscall sol_log_
+            exit
+        function_foo:
+            mov64 r0, 0
+            exit";
+
+    fn assemble_program(sbpf_version: SBPFVersion) -> Executable<InvokeContext<'static>> {
+        let mut loader = BuiltinProgram::new_loader(Config {
+            enabled_sbpf_versions: sbpf_version..=sbpf_version,
+            ..Config::default()
+        });
+        loader
+            .register_function("sol_log_", SyscallLog::vm)
+            .unwrap();
+        assemble(PROGRAM, Arc::new(loader)).unwrap()
+    }
+
+    fn register_trace(pcs: &[u64]) -> Vec<[u64; 12]> {
+        pcs.iter()
+            .map(|pc| {
+                let mut registers = [0; 12];
+                registers[PC_REGISTER] = *pc;
+                registers
+            })
+            .collect()
+    }
+
+    fn sample(remaining: u64, consumed: u64) -> ComputeMeterSample {
+        ComputeMeterSample {
+            index_in_trace: 0,
+            remaining,
+            consumed,
+        }
+    }
+
+    fn new_profile(sbpf_version: SBPFVersion, program_ids: &[&str]) -> Profile {
+        let executable = assemble_program(sbpf_version);
+        let mut profile = Profile::default();
+        for program_id in program_ids {
+            // the call, the 2 instructions of `function_foo`, a syscall of 100 CUs and the exit
+            profile.add_vm_trace(
+                program_id,
+                &executable,
+                &register_trace(&[0, 3, 4, 1, 2]),
+                &[sample(1_000, 4), sample(896, 1)],
+            );
+        }
+        profile
+    }
+
+    #[test]
+    fn test_syscall_cost() {
+        let sample = |remaining, consumed| ComputeMeterSample {
+            index_in_trace: 0,
+            remaining,
+            consumed,
+        };
+        // 10 instructions, a syscall of 100 CUs, 5 instructions and the exit
+        let samples = [sample(1_000, 10), sample(890, 5)];
+        assert_eq!(syscall_cost(&samples, 0), 100);
+        assert_eq!(syscall_cost(&samples, 1), 0);
+        assert_eq!(syscall_cost(&[], 0), 0);
+    }
+
+    #[test]
+    fn test_write_folded_stacks() {
+        for sbpf_version in [SBPFVersion::V0, SBPFVersion::V3] {
+            let profile = new_profile(sbpf_version, &["program"]);
+            let mut out = vec![];
+            profile.write_folded_stacks(&mut out).unwrap();
+            assert_eq!(
+                String::from_utf8(out).unwrap(),
+                concat!(
+                    "program;entrypoint 3\n",
+                    "program;entrypoint;function_foo 2\n",
+                    "program;entrypoint;sol_log_ 100\n",
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn test_write_callgrind() {
+        let functions = |program_id| {
+            format!(
+                concat!(
+                    "\n",
+                    "ob={program_id}\n",
+                    "fn=entrypoint\n",
+                    "0 1\n",
+                    "1 2\n",
+                    "cfn=function_foo\n",
+                    "calls=1 0\n",
+                    "0 2\n",
+                    "cfn=sol_log_\n",
+                    "calls=1 0\n",
+                    "1 100\n",
+                    "\n",
+                    "ob={program_id}\n",
+                    "fn=function_foo\n",
+                    "3 2\n",
+                    "\n",
+                    "ob={program_id}\n",
+                    "fn=sol_log_\n",
+                    "0 100\n",
+                ),
+                program_id = program_id,
+            )
+        };
+        for sbpf_version in [SBPFVersion::V0, SBPFVersion::V3] {
+            // functions of different programs are kept apart, even with the same name
+            let profile = new_profile(sbpf_version, &["program_a", "program_b"]);
+            let mut out = vec![];
+            profile.write_callgrind(&mut out).unwrap();
+            assert_eq!(
+                String::from_utf8(out).unwrap(),
+                format!(
+                    concat!(
+                        "# callgrind format\n",
+                        "version: 1\n",
+                        "creator: agave-ledger-tool\n",
+                        "events: ComputeUnits\n",
+                        "{}{}",
+                    ),
+                    functions("program_a"),
+                    functions("program_b"),
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn test_add_vm_trace_unwinds_aborted_calls() {
+        let executable = assemble_program(SBPFVersion::V3);
+        let mut profile = Profile::default();
+        // aborted in `function_foo`
+        profile.add_vm_trace("program", &executable, &register_trace(&[0, 3]), &[]);
+        let entrypoint = &profile.functions[&("program".to_string(), "entrypoint".to_string())];
+        assert_eq!(entrypoint.self_cost, 1);
+        let call = &entrypoint.calls[&(0, "function_foo".to_string())];
+        assert_eq!(call.count, 1);
+        assert_eq!(call.inclusive_cost, 1);
+    }
+}
@@ -1,5 +1,10 @@
 use {
-    crate::{args::*, canonicalize_ledger_path, ledger_utils::*},
+    crate::{
+        args::*,
+        canonicalize_ledger_path,
+        ledger_utils::*,
+        profile::{samples_of_instruction, Profile},
+    },
     agave_syscalls::create_program_runtime_environment_v1,
     clap::{App, AppSettings, Arg, ArgMatches, SubCommand},
     log::*,
@@ -14,6 +19,7 @@ use {
     solana_ledger::blockstore_options::AccessType,
     solana_loader_v3_interface::state::UpgradeableLoaderState,
     solana_program_runtime::{
+        execution_trace::InvocationTrace,
         invoke_context::InvokeContext,
         loaded_programs::{
             LoadProgramMetrics, ProgramCacheEntryType, DELAY_VISIBILITY_SLOT_OFFSET,
@@ -22,14 +28,16 @@ use {
         with_mock_invoke_context,
     },
     solana_pubkey::Pubkey,
-    solana_runtime::bank::Bank,
+    solana_runtime::bank::{Bank, TransactionSimulationConfig},
     solana_sbpf::{
         assembler::assemble, ebpf::MM_INPUT_START, elf::Executable, static_analysis::Analysis,
         verifier::RequisiteVerifier,
     },
     solana_sdk_ids::{bpf_loader_upgradeable, sysvar},
+    solana_transaction::{versioned::VersionedTransaction, TransactionVerificationMode},
     solana_transaction_context::{IndexOfAccount, InstructionAccount, InstructionContext},
     std::{
+        cell::RefCell,
         collections::HashMap,
         fmt::{self, Debug, Formatter},
         fs::File,
@@ -198,6 +206,18 @@ and the following fields are required
                         .value_name("PORT")
                         .default_value("9001"),
                 )
+                .arg(
+                    Arg::with_name("profile")
+                        .help(
+                            "Profile the compute units consumed by every function, basic block \
+                             and syscall. The profile is written to FILE.folded, in the folded \
+                             stacks format of flamegraph tools, and to FILE.callgrind, in the \
+                             callgrind format.",
+                        )
+                        .long("profile")
+                        .takes_value(true)
+                        .value_name("FILE"),
+                )
                 .arg(
                     Arg::with_name("trace")
                         .help("Output instruction trace")
@@ -208,6 +228,21 @@ and the following fields are required
                 )
                 .arg(&program_arg)
         )
+        .subcommand(
+            SubCommand::with_name("trace")
+                .about(
+                    "Simulates a transaction on top of the ledger and outputs the tree of \
+                     program invocations it makes.",
+                )
+                .arg(
+                    Arg::with_name("TRANSACTION")
+                        .help("Base58 encoded, bincode serialized transaction to simulate")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(&load_genesis_config_arg)
+                .args(&snapshot_config_args)
+        )
         )
     }
 }
@@ -236,6 +271,141 @@ impl fmt::Display for Output {
 impl QuietDisplay for Output {}
 impl VerboseDisplay for Output {}
 
+#[derive(Serialize)]
+struct TraceOutput {
+    result: String,
+    units_consumed: u64,
+    log: Vec<String>,
+    invocations: Vec<InvocationOutput>,
+}
+
+#[derive(Serialize)]
+struct InvocationOutput {
+    program_id: String,
+    stack_height: usize,
+    compute_units_consumed: u64,
+    result: String,
+    accounts: Vec<InvocationAccountOutput>,
+    return_data: Option<Vec<u8>>,
+    inner_invocations: Vec<InvocationOutput>,
+}
+
+#[derive(Serialize)]
+struct InvocationAccountOutput {
+    pubkey: String,
+    is_writable: bool,
+    pre_lamports: u64,
+    post_lamports: u64,
+    pre_owner: String,
+    post_owner: String,
+    pre_data_len: usize,
+    post_data_len: usize,
+    data_diff: Vec<(usize, usize)>,
+}
+
+impl From<InvocationTrace> for InvocationOutput {
+    fn from(invocation: InvocationTrace) -> Self {
+        Self {
+            program_id: invocation.program_id.to_string(),
+            stack_height: invocation.stack_height,
+            compute_units_consumed: invocation.compute_units_consumed,
+            result: format!("{:?}", invocation.result),
+            accounts: invocation
+                .accounts
+                .into_iter()
+                .map(|account| InvocationAccountOutput {
+                    pubkey: account.pubkey.to_string(),
+                    is_writable: account.is_writable,
+                    pre_lamports: account.pre_lamports,
+                    post_lamports: account.post_lamports,
+                    pre_owner: account.pre_owner.to_string(),
+                    post_owner: account.post_owner.to_string(),
+                    pre_data_len: account.pre_data_len,
+                    post_data_len: account.post_data_len,
+                    data_diff: account
+                        .data_diff
+                        .into_iter()
+                        .map(|range| (range.start, range.end))
+                        .collect(),
+                })
+                .collect(),
+            return_data: invocation.return_data.map(|return_data| return_data.data),
+            inner_invocations: invocation
+                .inner_invocations
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        }
+    }
+}
+
+impl InvocationOutput {
+    fn write_tree(&self, f: &mut Formatter<'_>, depth: usize) -> std::fmt::Result {
+        let indent = "  ".repeat(depth);
+        writeln!(
+            f,
+            "{indent}Program {} [{}] consumed {} CUs: {}",
+            self.program_id, self.stack_height, self.compute_units_consumed, self.result,
+        )?;
+        for account in &self.accounts {
+            write!(
+                f,
+                "{indent}  Account {}{}: lamports {} -> {}",
+                account.pubkey,
+                if account.is_writable {
+                    " (writable)"
+                } else {
+                    ""
+                },
+                account.pre_lamports,
+                account.post_lamports,
+            )?;
+            if account.pre_owner != account.post_owner {
+                write!(f, ", owner {} -> {}", account.pre_owner, account.post_owner)?;
+            }
+            if account.pre_data_len != account.post_data_len {
+                write!(
+                    f,
+                    ", data len {} -> {}",
+                    account.pre_data_len, account.post_data_len
+                )?;
+            }
+            if !account.data_diff.is_empty() {
+                write!(f, ", modified data")?;
+                for (start, end) in &account.data_diff {
+                    write!(f, " [{start}..{end})")?;
+                }
+            }
+            writeln!(f)?;
+        }
+        if let Some(return_data) = &self.return_data {
+            writeln!(f, "{indent}  Return data: {return_data:?}")?;
+        }
+        for inner_invocation in &self.inner_invocations {
+            inner_invocation.write_tree(f, depth.saturating_add(1))?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for TraceOutput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Transaction trace:")?;
+        writeln!(f, "Result: {}", self.result)?;
+        writeln!(f, "Units consumed: {}", self.units_consumed)?;
+        for invocation in &self.invocations {
+            invocation.write_tree(f, 0)?;
+        }
+        for line in &self.log {
+            writeln!(f, "{line}")?;
+        }
+        Ok(())
+    }
+}
+
+impl QuietDisplay for TraceOutput {}
+impl VerboseDisplay for TraceOutput {}
+
 // Replace with std::lazy::Lazy when stabilized.
 // https://github.com/rust-lang/rust/issues/74465
 struct LazyAnalysis<'a, 'b> {
@@ -357,6 +527,48 @@ fn process_static_action(action: Action, matches: &ArgMatches<'_>) {
     };
 }
 
+fn trace_transaction(ledger_path: &Path, matches: &ArgMatches<'_>) {
+    let transaction = matches.value_of("TRANSACTION").unwrap();
+    let transaction = bs58::decode(transaction)
+        .into_vec()
+        .ok()
+        .and_then(|bytes| bincode::deserialize::<VersionedTransaction>(&bytes).ok())
+        .unwrap_or_else(|| {
+            eprintln!("Invalid transaction {transaction}");
+            exit(1);
+        });
+    let ledger_path = canonicalize_ledger_path(ledger_path);
+    let bank = load_blockstore(&ledger_path, matches);
+    let transaction = bank
+        .verify_transaction(transaction, TransactionVerificationMode::HashOnly)
+        .unwrap_or_else(|err| {
+            eprintln!("Failed to sanitize transaction: {err}");
+            exit(1);
+        });
+    // The working bank may not be frozen if the ledger ends in an incomplete slot
+    let simulation_result = bank.simulate_transaction_unchecked_with_config(
+        &transaction,
+        TransactionSimulationConfig {
+            enable_execution_trace: true,
+            ..TransactionSimulationConfig::default()
+        },
+    );
+
+    let output = TraceOutput {
+        result: format!("{:?}", simulation_result.result),
+        units_consumed: simulation_result.units_consumed,
+        log: simulation_result.logs,
+        invocations: simulation_result
+            .execution_trace
+            .unwrap_or_default()
+            .into_iter()
+            .map(Into::into)
+            .collect(),
+    };
+    let output_format = OutputFormat::from_matches(matches, "output_format", false);
+    println!("{}", output_format.formatted_string(&output));
+}
+
 pub fn program(ledger_path: &Path, matches: &ArgMatches<'_>) {
     let matches = match matches.subcommand() {
         ("cfg", Some(arg_matches)) => {
@@ -368,6 +580,10 @@ pub fn program(ledger_path: &Path, matches: &ArgMatches<'_>) {
             return;
         }
         ("run", Some(arg_matches)) => arg_matches,
+        ("trace", Some(arg_matches)) => {
+            trace_transaction(ledger_path, arg_matches);
+            return;
+        }
         _ => unreachable!(),
     };
     let ledger_path = canonicalize_ledger_path(ledger_path);
@@ -520,6 +736,16 @@ pub fn program(ledger_path: &Path, matches: &ArgMatches<'_>) {
         )
         .unwrap();
 
+    let profile_path = matches.value_of("profile");
+    if profile_path.is_some() {
+        invoke_context.enable_compute_meter_samples();
+    }
+    let index_in_trace = invoke_context
+        .transaction_context
+        .get_current_instruction_context()
+        .unwrap()
+        .get_index_in_trace();
+
     let program = matches.value_of("PROGRAM").unwrap();
     let verified_executable = load_program(Path::new(program), program_id, &invoke_context);
     create_vm!(
@@ -580,8 +806,42 @@ pub fn program(ledger_path: &Path, matches: &ArgMatches<'_>) {
             },
         );
     }
+    let register_trace = std::mem::take(&mut vm.register_trace);
     drop(vm);
 
+    if let Some(profile_path) = profile_path {
+        let mut profile = Profile::default();
+        profile.add_vm_trace(
+            &program_id.to_string(),
+            &verified_executable,
+            &register_trace,
+            &samples_of_instruction(&invoke_context, index_in_trace),
+        );
+        // Programs invoked through CPI
+        let profile = RefCell::new(profile);
+        invoke_context.iterate_vm_traces(
+            &|instruction_context: InstructionContext, executable, register_trace| {
+                let Ok(program_id) = instruction_context.get_program_key() else {
+                    return;
+                };
+                profile.borrow_mut().add_vm_trace(
+                    &program_id.to_string(),
+                    executable,
+                    register_trace,
+                    &samples_of_instruction(
+                        &invoke_context,
+                        instruction_context.get_index_in_trace(),
+                    ),
+                );
+            },
+        );
+        let profile = profile.into_inner();
+        let mut folded_stacks = File::create(format!("{profile_path}.folded")).unwrap();
+        profile.write_folded_stacks(&mut folded_stacks).unwrap();
+        let mut callgrind = File::create(format!("{profile_path}.callgrind")).unwrap();
+        profile.write_callgrind(&mut callgrind).unwrap();
+    }
+
     let output = Output {
         result: format!("{result:?}"),
         instruction_count,
@@ -0,0 +1,428 @@
+//! Account level diff of two snapshots, for the `snapshot-diff` subcommand
+//!
+//! Both snapshots are loaded into banks of their own. The accounts of both banks are scanned in
+//! pubkey order, each by a thread of its own, and merged as they are scanned, so that neither
+//! bank's accounts are held in memory. Changed accounts are reported in pubkey order.
+
+use {
+    crate::error::{LedgerToolError, Result},
+    crossbeam_channel::{bounded, IntoIter, Sender},
+    serde::Serializer,
+    serde_derive::Serialize,
+    solana_account::{AccountSharedData, ReadableAccount},
+    solana_accounts_db::{
+        accounts_db::AccountsDbConfig, utils::create_all_accounts_run_and_snapshot_dirs,
+    },
+    solana_clock::Slot,
+    solana_genesis_config::GenesisConfig,
+    solana_hash::Hash,
+    solana_pubkey::Pubkey,
+    solana_runtime::{
+        bank::Bank,
+        runtime_config::RuntimeConfig,
+        snapshot_archive_info::{FullSnapshotArchiveInfo, IncrementalSnapshotArchiveInfo},
+        snapshot_bank_utils,
+    },
+    std::{
+        cmp::Ordering,
+        collections::HashSet,
+        fmt::{self, Display, Formatter},
+        iter::Peekable,
+        path::{Path, PathBuf},
+        sync::{atomic::AtomicBool, Arc},
+        thread,
+    },
+};
+
+/// A full snapshot archive, and optionally an incremental snapshot archive on top of it
+pub struct SnapshotArchives {
+    pub full: PathBuf,
+    pub incremental: Option<PathBuf>,
+}
+
+/// Loads the bank of `archives`, using `work_dir` for its accounts and bank snapshot
+pub fn load_bank_from_snapshot_archives(
+    archives: &SnapshotArchives,
+    genesis_config: &GenesisConfig,
+    mut accounts_db_config: AccountsDbConfig,
+    work_dir: &Path,
+) -> Result<Bank> {
+    let full_snapshot_archive_info = FullSnapshotArchiveInfo::new_from_path(archives.full.clone())
+        .map_err(|err| {
+            LedgerToolError::BadArgument(format!(
+                "invalid full snapshot archive {}: {err}",
+                archives.full.display()
+            ))
+        })?;
+    let incremental_snapshot_archive_info = archives
+        .incremental
+        .as_ref()
+        .map(|path| {
+            IncrementalSnapshotArchiveInfo::new_from_path(path.clone()).map_err(|err| {
+                LedgerToolError::BadArgument(format!(
+                    "invalid incremental snapshot archive {}: {err}",
+                    path.display()
+                ))
+            })
+        })
+        .transpose()?;
+
+    // Both banks are loaded at once, so they must not share any directory
+    accounts_db_config.base_working_path = Some(work_dir.to_path_buf());
+    if let Some(index) = accounts_db_config.index.as_mut() {
+        index.drives = Some(vec![work_dir.join("accounts_index")]);
+    }
+    let (account_paths, _account_snapshot_paths) =
+        create_all_accounts_run_and_snapshot_dirs(&[work_dir.join("accounts")])?;
+    let bank_snapshots_dir = work_dir.join("snapshots");
+    std::fs::create_dir_all(&bank_snapshots_dir)?;
+
+    snapshot_bank_utils::bank_from_snapshot_archives(
+        &account_paths,
+        &bank_snapshots_dir,
+        &full_snapshot_archive_info,
+        incremental_snapshot_archive_info.as_ref(),
+        genesis_config,
+        &RuntimeConfig::default(),
+        None,
+        None,
+        true,
+        false,
+        false,
+        accounts_db_config,
+        None,
+        Arc::new(AtomicBool::new(false)),
+    )
+    .map_err(|err| {
+        LedgerToolError::Generic(format!(
+            "failed to load bank from snapshot archive {}: {err}",
+            archives.full.display()
+        ))
+    })
+}
+
+/// The fields of an account compared by the diff
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountState {
+    pub lamports: u64,
+    #[serde(serialize_with = "serialize_display")]
+    pub owner: Pubkey,
+    pub executable: bool,
+    pub data_len: usize,
+    #[serde(serialize_with = "serialize_display")]
+    pub data_hash: Hash,
+}
+
+impl AccountState {
+    pub fn new(account: &AccountSharedData) -> Self {
+        Self {
+            lamports: account.lamports(),
+            owner: *account.owner(),
+            executable: account.executable(),
+            data_len: account.data().len(),
+            data_hash: solana_sha256_hasher::hash(account.data()),
+        }
+    }
+}
+
+impl Display for AccountState {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "lamports: {}, owner: {}, executable: {}, data len: {}, data hash: {}",
+            self.lamports, self.owner, self.executable, self.data_len, self.data_hash
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase", tag = "change")]
+pub enum AccountDiff {
+    Added {
+        new: AccountState,
+    },
+    Removed {
+        old: AccountState,
+    },
+    Modified {
+        old: AccountState,
+        new: AccountState,
+    },
+}
+
+impl AccountDiff {
+    /// Returns the diff of an account from `old` to `new`, if it changed
+    fn new(old: Option<AccountState>, new: Option<AccountState>) -> Option<Self> {
+        match (old, new) {
+            (None, None) => None,
+            (None, Some(new)) => Some(Self::Added { new }),
+            (Some(old), None) => Some(Self::Removed { old }),
+            (Some(old), Some(new)) => (old != new).then_some(Self::Modified { old, new }),
+        }
+    }
+}
+
+/// An account of the diff, as output by the `snapshot-diff` subcommand
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountDiffOutput<'a> {
+    #[serde(serialize_with = "serialize_display")]
+    pub pubkey: Pubkey,
+    #[serde(flatten)]
+    pub diff: &'a AccountDiff,
+}
+
+impl Display for AccountDiffOutput<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.diff {
+            AccountDiff::Added { new } => write!(f, "+ {}: {new}", self.pubkey),
+            AccountDiff::Removed { old } => write!(f, "- {}: {old}", self.pubkey),
+            AccountDiff::Modified { old, new } => {
+                write!(f, "~ {}:", self.pubkey)?;
+                let mut changes = vec![];
+                if old.lamports != new.lamports {
+                    changes.push(format!("lamports: {} -> {}", old.lamports, new.lamports));
+                }
+                if old.owner != new.owner {
+                    changes.push(format!("owner: {} -> {}", old.owner, new.owner));
+                }
+                if old.executable != new.executable {
+                    changes.push(format!(
+                        "executable: {} -> {}",
+                        old.executable, new.executable
+                    ));
+                }
+                if old.data_len != new.data_len {
+                    changes.push(format!("data len: {} -> {}", old.data_len, new.data_len));
+                }
+                if old.data_hash != new.data_hash {
+                    changes.push(format!("data hash: {} -> {}", old.data_hash, new.data_hash));
+                }
+                write!(f, " {}", changes.join(", "))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotDiffStats {
+    pub old_slot: Slot,
+    pub new_slot: Slot,
+    pub num_added: usize,
+    pub num_removed: usize,
+    pub num_modified: usize,
+    pub num_unchanged: usize,
+}
+
+/// Number of scanned accounts buffered between a scan and the merge of both scans
+const SCAN_CHANNEL_SIZE: usize = 1024;
+
+type ScannedAccounts = Peekable<IntoIter<(Pubkey, AccountSharedData)>>;
+
+/// Scans the accounts of `bank` with lamports, in pubkey order, into `sender`
+fn scan_accounts(bank: &Bank, sender: Sender<(Pubkey, AccountSharedData)>) -> Result<()> {
+    bank.scan_all_accounts(
+        |account| {
+            if let Some((pubkey, account, _slot)) = account {
+                if account.lamports() != 0 {
+                    // Only fails if the merge panicked
+                    let _ = sender.send((*pubkey, account));
+                }
+            }
+        },
+        true,
+    )
+    .map_err(|err| {
+        LedgerToolError::Generic(format!(
+            "failed to scan the accounts of the bank at slot {}: {err}",
+            bank.slot()
+        ))
+    })
+}
+
+/// Returns the next pubkey of the merged scans, along with its account in either scan
+fn next_account(
+    old_accounts: &mut ScannedAccounts,
+    new_accounts: &mut ScannedAccounts,
+) -> Option<(Pubkey, Option<AccountSharedData>, Option<AccountSharedData>)> {
+    let order = match (old_accounts.peek(), new_accounts.peek()) {
+        (None, None) => return None,
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (Some((old_pubkey, _)), Some((new_pubkey, _))) => old_pubkey.cmp(new_pubkey),
+    };
+    Some(match order {
+        Ordering::Less => {
+            let (pubkey, old) = old_accounts.next().unwrap();
+            (pubkey, Some(old), None)
+        }
+        Ordering::Greater => {
+            let (pubkey, new) = new_accounts.next().unwrap();
+            (pubkey, None, Some(new))
+        }
+        Ordering::Equal => {
+            let (pubkey, old) = old_accounts.next().unwrap();
+            let (_pubkey, new) = new_accounts.next().unwrap();
+            (pubkey, Some(old), Some(new))
+        }
+    })
+}
+
+/// Compares the accounts of `old_bank` and `new_bank`, and calls `output` for every account
+/// which was added, removed or modified
+///
+/// If `owners` is not empty, only accounts owned by one of them, in either bank, are compared.
+pub fn diff_accounts(
+    old_bank: &Bank,
+    new_bank: &Bank,
+    owners: &HashSet<Pubkey>,
+    mut output: impl FnMut(&Pubkey, &AccountDiff),
+) -> Result<SnapshotDiffStats> {
+    let is_included =
+        |account: &AccountSharedData| owners.is_empty() || owners.contains(account.owner());
+
+    let mut stats = SnapshotDiffStats {
+        old_slot: old_bank.slot(),
+        new_slot: new_bank.slot(),
+        ..SnapshotDiffStats::default()
+    };
+
+    thread::scope(|scope| {
+        let (old_sender, old_receiver) = bounded(SCAN_CHANNEL_SIZE);
+        let (new_sender, new_receiver) = bounded(SCAN_CHANNEL_SIZE);
+        let old_scan = thread::Builder::new()
+            .name("solSnapDiffOld".to_string())
+            .spawn_scoped(scope, || scan_accounts(old_bank, old_sender))?;
+        let new_scan = thread::Builder::new()
+            .name("solSnapDiffNew".to_string())
+            .spawn_scoped(scope, || scan_accounts(new_bank, new_sender))?;
+
+        let mut old_accounts = old_receiver.into_iter().peekable();
+        let mut new_accounts = new_receiver.into_iter().peekable();
+        while let Some((pubkey, old, new)) = next_account(&mut old_accounts, &mut new_accounts) {
+            // An account is compared if it is included in either bank, e.g. when its owner changed
+            if !old.iter().chain(new.iter()).any(is_included) {
+                continue;
+            }
+            let old = old.map(|account| AccountState::new(&account));
+            let new = new.map(|account| AccountState::new(&account));
+            match AccountDiff::new(old, new) {
+                None => stats.num_unchanged += 1,
+                Some(diff) => {
+                    match diff {
+                        AccountDiff::Added { .. } => stats.num_added += 1,
+                        AccountDiff::Removed { .. } => stats.num_removed += 1,
+                        AccountDiff::Modified { .. } => stats.num_modified += 1,
+                    }
+                    output(&pubkey, &diff);
+                }
+            }
+        }
+        old_scan.join().unwrap()?;
+        new_scan.join().unwrap()
+    })?;
+
+    Ok(stats)
+}
+
+fn serialize_display<T: Display, S: Serializer>(
+    value: &T,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*, solana_account::WritableAccount,
+        solana_runtime::genesis_utils::create_genesis_config,
+    };
+
+    #[test]
+    fn test_diff_accounts() {
+        let genesis_config = create_genesis_config(1_000_000_000).genesis_config;
+        let old_bank = Bank::new_for_tests(&genesis_config);
+        let new_bank = Bank::new_for_tests(&genesis_config);
+        let program = Pubkey::new_unique();
+        let other_program = Pubkey::new_unique();
+
+        let unchanged = Pubkey::new_unique();
+        let added = Pubkey::new_unique();
+        let removed = Pubkey::new_unique();
+        let modified = Pubkey::new_unique();
+        let reassigned = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+
+        let account = |lamports, owner: &Pubkey, data: &[u8]| {
+            AccountSharedData::create(lamports, data.to_vec(), *owner, false, u64::MAX)
+        };
+        for bank in [&old_bank, &new_bank] {
+            bank.store_account(&unchanged, &account(10, &program, &[1, 2]));
+        }
+        old_bank.store_account(&removed, &account(20, &program, &[]));
+        old_bank.store_account(&modified, &account(30, &program, &[3]));
+        old_bank.store_account(&reassigned, &account(40, &other_program, &[]));
+        old_bank.store_account(&other, &account(50, &other_program, &[]));
+        new_bank.store_account(&added, &account(60, &program, &[]));
+        new_bank.store_account(&modified, &account(30, &program, &[4]));
+        new_bank.store_account(&reassigned, &account(40, &program, &[]));
+        new_bank.store_account(&other, &account(51, &other_program, &[]));
+
+        let mut diffs = vec![];
+        let stats = diff_accounts(
+            &old_bank,
+            &new_bank,
+            &HashSet::from([program]),
+            |pubkey, diff| diffs.push((*pubkey, diff.clone())),
+        )
+        .unwrap();
+
+        let state =
+            |lamports, owner, data: &[u8]| AccountState::new(&account(lamports, owner, data));
+        // reported in pubkey order
+        let mut expected = vec![
+            (
+                added,
+                AccountDiff::Added {
+                    new: state(60, &program, &[]),
+                },
+            ),
+            (
+                removed,
+                AccountDiff::Removed {
+                    old: state(20, &program, &[]),
+                },
+            ),
+            (
+                modified,
+                AccountDiff::Modified {
+                    old: state(30, &program, &[3]),
+                    new: state(30, &program, &[4]),
+                },
+            ),
+            (
+                reassigned,
+                AccountDiff::Modified {
+                    old: state(40, &other_program, &[]),
+                    new: state(40, &program, &[]),
+                },
+            ),
+        ];
+        expected.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        assert_eq!(diffs, expected);
+        assert_eq!(
+            stats,
+            SnapshotDiffStats {
+                old_slot: 0,
+                new_slot: 0,
+                num_added: 1,
+                num_removed: 1,
+                num_modified: 2,
+                num_unchanged: 1,
+            }
+        );
+    }
+}
@@ -5,6 +5,7 @@ use {
         genesis_utils::create_genesis_config, get_tmp_ledger_path_auto_delete,
     },
     std::{
+        fs,
         path::Path,
         process::{Command, Output},
     },
@@ -87,3 +88,41 @@ fn ledger_tool_copy_test() {
         assert!(!src_slot_output.stdout.is_empty());
     }
 }
+
+#[test]
+fn ledger_tool_accounts_from_seekable_snapshot_archive() {
+    let genesis_config = create_genesis_config(100).genesis_config;
+    let (ledger_path, _blockhash) = create_new_tmp_ledger_auto_delete!(&genesis_config);
+    let ledger_path = ledger_path.path().to_str().unwrap();
+
+    let snapshot_archives_dir = tempfile::tempdir().unwrap();
+    let output = run_ledger_tool(&[
+        "-l",
+        ledger_path,
+        "create-snapshot",
+        "0",
+        snapshot_archives_dir.path().to_str().unwrap(),
+        "--snapshot-archive-format",
+        "zstd-seekable",
+    ]);
+    assert!(output.status.success());
+    let archive_path = fs::read_dir(snapshot_archives_dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .find(|path| path.to_str().unwrap().ends_with(".seekable.tar.zst"))
+        .unwrap();
+
+    let (pubkey, _account) = genesis_config.accounts.iter().next().unwrap();
+    let pubkey = pubkey.to_string();
+    let output = run_ledger_tool(&[
+        "-l",
+        ledger_path,
+        "accounts",
+        "--snapshot-archive",
+        archive_path.to_str().unwrap(),
+        "--account",
+        &pubkey,
+    ]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains(&pubkey));
+}
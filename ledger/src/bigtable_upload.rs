@@ -4,6 +4,7 @@ use {
     log::*,
     solana_clock::Slot,
     solana_measure::measure::Measure,
+    solana_storage_bigtable::LongTermStorage,
     std::{
         cmp::{max, min},
         collections::HashSet,
@@ -46,7 +47,7 @@ struct BlockstoreLoadStats {
 /// ending_slot]` are found in Blockstore, this value is equal to `ending_slot`.
 pub async fn upload_confirmed_blocks(
     blockstore: Arc<Blockstore>,
-    bigtable: solana_storage_bigtable::LedgerStorage,
+    bigtable: Arc<dyn LongTermStorage>,
     starting_slot: Slot,
     ending_slot: Slot,
     config: ConfirmedBlockUploadConfig,
@@ -8021,6 +8021,8 @@ pub mod tests {
                     return_data: Some(TransactionReturnData::default()),
                     compute_units_consumed,
                     cost_units,
+                    log_events: None,
+                    syscall_compute_units: None,
                 }
                 .into();
                 blockstore
@@ -8041,6 +8043,8 @@ pub mod tests {
                     return_data: Some(TransactionReturnData::default()),
                     compute_units_consumed,
                     cost_units,
+                    log_events: None,
+                    syscall_compute_units: None,
                 }
                 .into();
                 blockstore
@@ -8061,6 +8065,8 @@ pub mod tests {
                     return_data: Some(TransactionReturnData::default()),
                     compute_units_consumed,
                     cost_units,
+                    log_events: None,
+                    syscall_compute_units: None,
                 }
                 .into();
                 blockstore
@@ -8083,6 +8089,8 @@ pub mod tests {
                         return_data: Some(TransactionReturnData::default()),
                         compute_units_consumed,
                         cost_units,
+                        log_events: None,
+                        syscall_compute_units: None,
                     },
                 }
             })
@@ -8233,6 +8241,8 @@ pub mod tests {
             return_data: Some(test_return_data.clone()),
             compute_units_consumed: compute_units_consumed_1,
             cost_units: cost_units_1,
+            log_events: None,
+            syscall_compute_units: None,
         }
         .into();
         assert!(transaction_status_cf
@@ -8254,6 +8264,8 @@ pub mod tests {
             return_data,
             compute_units_consumed,
             cost_units,
+            log_events,
+            syscall_compute_units,
         } = transaction_status_cf
             .get_protobuf((Signature::default(), 0))
             .unwrap()
@@ -8273,6 +8285,8 @@ pub mod tests {
         assert_eq!(return_data.unwrap(), test_return_data);
         assert_eq!(compute_units_consumed, compute_units_consumed_1);
         assert_eq!(cost_units, cost_units_1);
+        assert_eq!(log_events, None);
+        assert_eq!(syscall_compute_units, None);
 
         // insert value
         let status = TransactionStatusMeta {
@@ -8289,6 +8303,8 @@ pub mod tests {
             return_data: Some(test_return_data.clone()),
             compute_units_consumed: compute_units_consumed_2,
             cost_units: cost_units_2,
+            log_events: None,
+            syscall_compute_units: None,
         }
         .into();
         assert!(transaction_status_cf
@@ -8310,6 +8326,8 @@ pub mod tests {
             return_data,
             compute_units_consumed,
             cost_units,
+            log_events,
+            syscall_compute_units,
         } = transaction_status_cf
             .get_protobuf((Signature::from([2u8; 64]), 9))
             .unwrap()
@@ -8331,6 +8349,8 @@ pub mod tests {
         assert_eq!(return_data.unwrap(), test_return_data);
         assert_eq!(compute_units_consumed, compute_units_consumed_2);
         assert_eq!(cost_units, cost_units_2);
+        assert_eq!(log_events, None);
+        assert_eq!(syscall_compute_units, None);
     }
 
     #[test]
@@ -8428,6 +8448,8 @@ pub mod tests {
             return_data: Some(TransactionReturnData::default()),
             compute_units_consumed: Some(42u64),
             cost_units: Some(1234),
+            log_events: None,
+            syscall_compute_units: None,
         }
         .into();
 
@@ -8605,6 +8627,8 @@ pub mod tests {
             return_data: Some(TransactionReturnData::default()),
             compute_units_consumed: Some(42u64),
             cost_units: Some(1234),
+            log_events: None,
+            syscall_compute_units: None,
         }
         .into();
 
@@ -8733,6 +8757,8 @@ pub mod tests {
             return_data: Some(TransactionReturnData::default()),
             compute_units_consumed: Some(42u64),
             cost_units: Some(1234),
+            log_events: None,
+            syscall_compute_units: None,
         }
         .into();
 
@@ -8902,6 +8928,8 @@ pub mod tests {
                     return_data: return_data.clone(),
                     compute_units_consumed: Some(42),
                     cost_units: Some(1234),
+                    log_events: None,
+                    syscall_compute_units: None,
                 }
                 .into();
                 blockstore
@@ -8924,6 +8952,8 @@ pub mod tests {
                         return_data,
                         compute_units_consumed: Some(42),
                         cost_units: Some(1234),
+                        log_events: None,
+                        syscall_compute_units: None,
                     },
                 }
             })
@@ -9025,6 +9055,8 @@ pub mod tests {
                     return_data: return_data.clone(),
                     compute_units_consumed: Some(42u64),
                     cost_units: Some(1234),
+                    log_events: None,
+                    syscall_compute_units: None,
                 }
                 .into();
                 blockstore
@@ -9047,6 +9079,8 @@ pub mod tests {
                         return_data,
                         compute_units_consumed: Some(42u64),
                         cost_units: Some(1234),
+                        log_events: None,
+                        syscall_compute_units: None,
                     },
                 }
             })
@@ -9722,6 +9756,8 @@ pub mod tests {
                 return_data: Some(TransactionReturnData::default()),
                 compute_units_consumed: None,
                 cost_units: None,
+                log_events: None,
+                syscall_compute_units: None,
             }
             .into();
             transaction_status_cf
@@ -10525,6 +10561,8 @@ pub mod tests {
             }),
             compute_units_consumed: Some(23456),
             cost_units: Some(5678),
+            log_events: None,
+            syscall_compute_units: None,
         };
         let deprecated_status: StoredTransactionStatusMeta = status.clone().try_into().unwrap();
         let protobuf_status: generated::TransactionStatusMeta = status.into();
@@ -224,7 +224,10 @@ pub fn execute_batch<'a>(
         .load_execute_and_commit_transactions_with_pre_commit_callback(
             batch,
             MAX_PROCESSING_AGE,
-            ExecutionRecordingConfig::new_single_setting(transaction_status_sender.is_some()),
+            transaction_status_sender.map_or(
+                ExecutionRecordingConfig::new_single_setting(false),
+                TransactionStatusSender::execution_recording_config,
+            ),
             timings,
             log_messages_bytes_limit,
             pre_commit_callback,
@@ -2278,9 +2281,19 @@ pub struct TransactionStatusBatch {
 pub struct TransactionStatusSender {
     pub sender: Sender<TransactionStatusMessage>,
     pub dependency_tracker: Option<Arc<DependencyTracker>>,
+    /// Record typed log events alongside the log messages of the transactions
+    pub enable_log_event_recording: bool,
 }
 
 impl TransactionStatusSender {
+    /// What to record while executing the transactions whose statuses are sent
+    pub fn execution_recording_config(&self) -> ExecutionRecordingConfig {
+        ExecutionRecordingConfig {
+            enable_log_event_recording: self.enable_log_event_recording,
+            ..ExecutionRecordingConfig::new_single_setting(true)
+        }
+    }
+
     pub fn send_transaction_status_batch(
         &self,
         slot: Slot,
@@ -4969,6 +4982,7 @@ pub mod tests {
         let transaction_status_sender = TransactionStatusSender {
             sender: transaction_status_sender,
             dependency_tracker: None,
+            enable_log_event_recording: false,
         };
 
         let blockhash = bank.last_blockhash();
@@ -5230,6 +5244,7 @@ pub mod tests {
             Some(&TransactionStatusSender {
                 sender,
                 dependency_tracker: None,
+                enable_log_event_recording: false,
             }),
             None,
             &mut timing,
@@ -0,0 +1,396 @@
+#![cfg_attr(not(feature = "agave-unstable-api"), allow(dead_code))]
+//! The [`Deshredder`] reconstructs the entries of a slot from its shreds
+//! without a [`Blockstore`](crate::blockstore::Blockstore).
+//!
+//! Shreds can be inserted in any order. Each shred is verified against the
+//! slot leader, which also verifies its merkle proof since the leader signs
+//! the merkle root of the erasure batch. Missing data shreds are recovered
+//! from the coding shreds of their erasure batch, and every data set ending
+//! with a `DATA_COMPLETE_SHRED` is emitted as soon as all of its data shreds
+//! are available.
+
+use {
+    crate::{
+        shred::{self, Payload, Shred, ShredId},
+        shredder::{ReedSolomonCache, Shredder},
+    },
+    solana_clock::Slot,
+    solana_entry::entry::Entry,
+    solana_pubkey::Pubkey,
+    std::{
+        collections::{btree_map::Entry as BTreeMapEntry, BTreeMap, HashMap},
+        ops::Range,
+    },
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum DeshredderError {
+    #[error(transparent)]
+    InvalidShred(#[from] shred::Error),
+    #[error("Erasure config mismatch: slot: {0}, fec_set_index: {1}")]
+    ErasureMismatch(Slot, u32),
+    #[error("Invalid signature or merkle proof: {0:?}")]
+    InvalidSignature(ShredId),
+    #[error("Slot {0} is older than the purged slots")]
+    SlotPurged(Slot),
+    #[error("Unknown leader for slot {0}")]
+    UnknownLeader(Slot),
+}
+
+/// Entries of a data set, i.e. of a range of data shreds of which the last
+/// one has the `DATA_COMPLETE_SHRED` flag set.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompletedDataSet {
+    pub slot: Slot,
+    /// Data shreds' indices in this set.
+    pub indices: Range<u32>,
+    /// Indices of the erasure batches spanned by this set, i.e. the index of
+    /// the first data shred of each batch, in ascending order.
+    pub fec_set_indices: Vec<u32>,
+    /// Whether this is the last data set of the slot.
+    pub last_in_slot: bool,
+    pub entries: Vec<Entry>,
+}
+
+#[derive(Default)]
+struct ErasureSet {
+    // Coding shreds keyed by their index.
+    code: BTreeMap<u32, Shred>,
+    // Number of data shreds in the erasure batch, known once a coding shred
+    // is received.
+    num_data: Option<u32>,
+    // Set once all the data shreds of the batch are available.
+    complete: bool,
+}
+
+#[derive(Default)]
+struct SlotState {
+    // Data shreds keyed by their index.
+    data: BTreeMap<u32, Shred>,
+    // Erasure batches keyed by their fec_set_index.
+    erasure_sets: HashMap<u32, ErasureSet>,
+    // Index of the first data shred of the next data set to emit.
+    start: u32,
+    // Index of the first missing data shred at or after start.
+    consumed: u32,
+    // Set once the data set with the LAST_SHRED_IN_SLOT flag is emitted.
+    full: bool,
+}
+
+/// Reconstructs the entries of slots from their shreds, received in any
+/// order, without a blockstore.
+///
+/// The shreds of a slot are kept until the slot is purged with
+/// [`Deshredder::purge_slots_older_than`], so that late coding shreds can
+/// still be used to recover the data shreds of their erasure batch.
+pub struct Deshredder<F> {
+    // Returns the leader of the slot, which signs its shreds.
+    leader_for_slot: F,
+    slots: HashMap<Slot, SlotState>,
+    // Shreds of slots older than this are rejected.
+    min_slot: Slot,
+    reed_solomon_cache: ReedSolomonCache,
+}
+
+impl<F> Deshredder<F>
+where
+    F: Fn(Slot) -> Option<Pubkey>,
+{
+    pub fn new(leader_for_slot: F) -> Self {
+        Self {
+            leader_for_slot,
+            slots: HashMap::new(),
+            min_slot: 0,
+            reed_solomon_cache: ReedSolomonCache::default(),
+        }
+    }
+
+    /// Verifies and inserts a serialized shred, and returns the data sets it
+    /// completed, either directly or by allowing the data shreds missing from
+    /// its erasure batch to be recovered.
+    ///
+    /// Shreds already inserted, or received after the data set they belong to
+    /// was emitted, are ignored. If the leader equivocates, the first version
+    /// of a shred wins.
+    pub fn insert_shred<T>(&mut self, shred: T) -> Result<Vec<CompletedDataSet>, DeshredderError>
+    where
+        T: AsRef<[u8]> + Into<Payload>,
+        Payload: From<T>,
+    {
+        let shred = Shred::new_from_serialized_shred(shred)?;
+        shred.sanitize()?;
+        let slot = shred.slot();
+        if slot < self.min_slot {
+            return Err(DeshredderError::SlotPurged(slot));
+        }
+        let leader = (self.leader_for_slot)(slot).ok_or(DeshredderError::UnknownLeader(slot))?;
+        if !shred.verify(&leader) {
+            return Err(DeshredderError::InvalidSignature(shred.id()));
+        }
+        let state = self.slots.entry(slot).or_default();
+        let fec_set_index = shred.fec_set_index();
+        if shred.is_data() {
+            if state.full || shred.index() < state.start {
+                return Ok(Vec::new());
+            }
+            if let BTreeMapEntry::Vacant(entry) = state.data.entry(shred.index()) {
+                entry.insert(shred);
+            } else {
+                return Ok(Vec::new());
+            }
+        } else {
+            let erasure_set = state.erasure_sets.entry(fec_set_index).or_default();
+            if let Some(other) = erasure_set.code.values().next() {
+                if shred.erasure_mismatch(other)? {
+                    return Err(DeshredderError::ErasureMismatch(slot, fec_set_index));
+                }
+            }
+            erasure_set.num_data = Some(u32::from(shred.num_data_shreds()?));
+            erasure_set.code.entry(shred.index()).or_insert(shred);
+        }
+        state.try_recovery(fec_set_index, &self.reed_solomon_cache);
+        Ok(state.completed_data_sets(slot))
+    }
+
+    /// Drops the shreds of the slots older than `slot`, and rejects the shreds
+    /// of these slots from now on.
+    pub fn purge_slots_older_than(&mut self, slot: Slot) {
+        self.min_slot = self.min_slot.max(slot);
+        self.slots.retain(|&other, _| other >= self.min_slot);
+    }
+}
+
+impl SlotState {
+    // Recovers the missing data shreds of the erasure batch if enough of its
+    // shreds were received.
+    fn try_recovery(&mut self, fec_set_index: u32, reed_solomon_cache: &ReedSolomonCache) {
+        let Some(erasure_set) = self.erasure_sets.get_mut(&fec_set_index) else {
+            return;
+        };
+        let Some(num_data) = erasure_set.num_data else {
+            return;
+        };
+        if erasure_set.complete {
+            return;
+        }
+        let data_indices = fec_set_index..fec_set_index.saturating_add(num_data);
+        let num_data_received = self.data.range(data_indices.clone()).count();
+        if num_data_received == num_data as usize {
+            erasure_set.complete = true;
+            return;
+        }
+        if num_data_received + erasure_set.code.len() < num_data as usize {
+            return;
+        }
+        let shreds = self
+            .data
+            .range(data_indices)
+            .map(|(_, shred)| shred)
+            .chain(erasure_set.code.values())
+            .cloned()
+            .collect::<Vec<_>>();
+        let recovered = match shred::recover(shreds, reed_solomon_cache) {
+            Ok(recovered) => recovered,
+            Err(err) => {
+                debug!("deshredder recovery error: {err:?}");
+                return;
+            }
+        };
+        for shred in recovered {
+            match shred {
+                Ok(shred) if shred.is_data() => {
+                    self.data.entry(shred.index()).or_insert(shred);
+                }
+                Ok(_) => (),
+                Err(err) => debug!("deshredder recovery error: {err:?}"),
+            }
+        }
+        erasure_set.complete = true;
+    }
+
+    // Emits the data sets whose data shreds are all available.
+    fn completed_data_sets(&mut self, slot: Slot) -> Vec<CompletedDataSet> {
+        let mut completed_data_sets = Vec::new();
+        while !self.full {
+            let Some(shred) = self.data.get(&self.consumed) else {
+                break;
+            };
+            self.consumed += 1;
+            if !shred.data_complete() && !shred.last_in_slot() {
+                continue;
+            }
+            let last_in_slot = shred.last_in_slot();
+            let indices = self.start..self.consumed;
+            self.start = self.consumed;
+            self.full = last_in_slot;
+            let shreds: Vec<_> = self
+                .data
+                .range(indices.clone())
+                .map(|(_, shred)| shred)
+                .collect();
+            let mut fec_set_indices: Vec<_> =
+                shreds.iter().map(|shred| shred.fec_set_index()).collect();
+            fec_set_indices.dedup();
+            let entries = Shredder::deshred(shreds.iter().map(|shred| shred.payload()))
+                .map_err(|err| format!("could not reconstruct entries buffer from shreds: {err:?}"))
+                .and_then(|payload| {
+                    bincode::deserialize::<Vec<Entry>>(&payload)
+                        .map_err(|err| format!("could not reconstruct entries: {err:?}"))
+                });
+            match entries {
+                Ok(entries) => completed_data_sets.push(CompletedDataSet {
+                    slot,
+                    indices,
+                    fec_set_indices,
+                    last_in_slot,
+                    entries,
+                }),
+                Err(err) => warn!("deshredder slot: {slot}, indices: {indices:?}, {err}"),
+            }
+        }
+        completed_data_sets
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*, crate::shred::ProcessShredsStats, assert_matches::assert_matches,
+        rand::seq::SliceRandom, solana_entry::entry::create_ticks, solana_hash::Hash,
+        solana_keypair::Keypair, solana_signer::Signer,
+    };
+
+    const SLOT: Slot = 7;
+
+    // Returns the entries of two data sets and their data and coding shreds.
+    fn make_shreds(keypair: &Keypair) -> (Vec<Vec<Entry>>, Vec<Shred>, Vec<Shred>) {
+        let shredder = Shredder::new(SLOT, SLOT - 1, 0, 0).unwrap();
+        let reed_solomon_cache = ReedSolomonCache::default();
+        let entries = vec![
+            create_ticks(50, 1, Hash::new_unique()),
+            create_ticks(30, 1, Hash::new_unique()),
+        ];
+        let (mut data_shreds, mut coding_shreds) = shredder.entries_to_merkle_shreds_for_tests(
+            keypair,
+            &entries[0],
+            false,
+            Hash::new_unique(),
+            0,
+            0,
+            &reed_solomon_cache,
+            &mut ProcessShredsStats::default(),
+        );
+        let (data, code) = shredder.entries_to_merkle_shreds_for_tests(
+            keypair,
+            &entries[1],
+            true,
+            data_shreds.last().unwrap().merkle_root().unwrap(),
+            data_shreds.len() as u32,
+            coding_shreds.len() as u32,
+            &reed_solomon_cache,
+            &mut ProcessShredsStats::default(),
+        );
+        data_shreds.extend(data);
+        coding_shreds.extend(code);
+        (entries, data_shreds, coding_shreds)
+    }
+
+    fn insert_shreds<F: Fn(Slot) -> Option<Pubkey>>(
+        deshredder: &mut Deshredder<F>,
+        shreds: Vec<Shred>,
+    ) -> Vec<CompletedDataSet> {
+        shreds
+            .into_iter()
+            .flat_map(|shred| deshredder.insert_shred(shred.into_payload()).unwrap())
+            .collect()
+    }
+
+    fn check_data_sets(
+        data_sets: &[CompletedDataSet],
+        entries: &[Vec<Entry>],
+        data_shreds: &[Shred],
+    ) {
+        assert_eq!(data_sets.len(), entries.len());
+        for (data_set, entries) in data_sets.iter().zip(entries) {
+            assert_eq!(data_set.slot, SLOT);
+            assert_eq!(&data_set.entries, entries);
+            let shreds =
+                &data_shreds[data_set.indices.start as usize..data_set.indices.end as usize];
+            assert!(shreds.last().unwrap().data_complete());
+            let mut fec_set_indices: Vec<_> = shreds.iter().map(Shred::fec_set_index).collect();
+            fec_set_indices.dedup();
+            assert_eq!(data_set.fec_set_indices, fec_set_indices);
+        }
+        assert_eq!(data_sets[0].indices.start, 0);
+        assert_eq!(data_sets[0].indices.end, data_sets[1].indices.start);
+        assert!(!data_sets[0].last_in_slot);
+        assert!(data_sets[1].last_in_slot);
+    }
+
+    #[test]
+    fn test_deshredder_out_of_order() {
+        let keypair = Keypair::new();
+        let (entries, data_shreds, coding_shreds) = make_shreds(&keypair);
+        let leader = keypair.pubkey();
+        let mut deshredder = Deshredder::new(|_| Some(leader));
+        let mut shreds = data_shreds.clone();
+        shreds.shuffle(&mut rand::thread_rng());
+        let data_sets = insert_shreds(&mut deshredder, shreds);
+        check_data_sets(&data_sets, &entries, &data_shreds);
+        // Shreds of the emitted data sets are ignored.
+        assert!(insert_shreds(&mut deshredder, data_shreds.clone()).is_empty());
+        assert!(insert_shreds(&mut deshredder, coding_shreds).is_empty());
+    }
+
+    #[test]
+    fn test_deshredder_recovery() {
+        let keypair = Keypair::new();
+        let (entries, data_shreds, coding_shreds) = make_shreds(&keypair);
+        let leader = keypair.pubkey();
+        let mut deshredder = Deshredder::new(|_| Some(leader));
+        // Drop every other data shred.
+        let mut shreds: Vec<_> = data_shreds
+            .iter()
+            .step_by(2)
+            .cloned()
+            .chain(coding_shreds)
+            .collect();
+        shreds.shuffle(&mut rand::thread_rng());
+        let data_sets = insert_shreds(&mut deshredder, shreds);
+        check_data_sets(&data_sets, &entries, &data_shreds);
+    }
+
+    #[test]
+    fn test_deshredder_rejects_shreds() {
+        let keypair = Keypair::new();
+        let (_, data_shreds, _) = make_shreds(&keypair);
+        let shred = data_shreds[0].clone();
+
+        let other = Keypair::new().pubkey();
+        let mut deshredder = Deshredder::new(|_| Some(other));
+        assert_matches!(
+            deshredder.insert_shred(shred.clone().into_payload()),
+            Err(DeshredderError::InvalidSignature(id)) if id == shred.id()
+        );
+
+        let mut deshredder = Deshredder::new(|_| None);
+        assert_matches!(
+            deshredder.insert_shred(shred.clone().into_payload()),
+            Err(DeshredderError::UnknownLeader(SLOT))
+        );
+
+        let leader = keypair.pubkey();
+        let mut deshredder = Deshredder::new(|_| Some(leader));
+        deshredder.purge_slots_older_than(SLOT + 1);
+        assert_matches!(
+            deshredder.insert_shred(shred.into_payload()),
+            Err(DeshredderError::SlotPurged(SLOT))
+        );
+
+        assert_matches!(
+            deshredder.insert_shred(vec![0u8; 16]),
+            Err(DeshredderError::InvalidShred(_))
+        );
+    }
+}
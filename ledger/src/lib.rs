@@ -18,6 +18,7 @@ pub mod blockstore_metric_report_service;
 pub mod blockstore_metrics;
 pub mod blockstore_options;
 pub mod blockstore_processor;
+conditional_mod::conditional_vis_mod!(deshredder, feature="agave-unstable-api", pub,pub(crate));
 pub mod entry_notifier_interface;
 pub mod entry_notifier_service;
 pub mod genesis_utils;
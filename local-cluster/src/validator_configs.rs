@@ -16,6 +16,9 @@ pub fn safe_clone_config(config: &ValidatorConfig) -> ValidatorConfig {
         rpc_config: config.rpc_config.clone(),
         on_start_geyser_plugin_config_files: config.on_start_geyser_plugin_config_files.clone(),
         geyser_plugin_always_enabled: config.geyser_plugin_always_enabled,
+        on_start_tpu_ingress_filter_config_files: config
+            .on_start_tpu_ingress_filter_config_files
+            .clone(),
         rpc_addrs: config.rpc_addrs,
         pubsub_config: config.pubsub_config.clone(),
         snapshot_config: config.snapshot_config.clone(),
@@ -81,6 +84,7 @@ pub fn safe_clone_config(config: &ValidatorConfig) -> ValidatorConfig {
         delay_leader_block_for_pending_fork: config.delay_leader_block_for_pending_fork,
         use_tpu_client_next: config.use_tpu_client_next,
         retransmit_xdp: config.retransmit_xdp.clone(),
+        shred_tap: config.shred_tap.clone(),
         repair_handler_type: config.repair_handler_type.clone(),
     }
 }
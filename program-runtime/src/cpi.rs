@@ -16,7 +16,7 @@ use {
     solana_stable_layout::stable_instruction::StableInstruction,
     solana_svm_log_collector::ic_msg,
     solana_svm_measure::measure::Measure,
-    solana_svm_timings::ExecuteTimings,
+    solana_svm_timings::{ExecuteTimings, SyscallCategory},
     solana_transaction_context::{
         vm_slice::VmSlice, BorrowedInstructionAccount, IndexOfAccount,
         MAX_ACCOUNTS_PER_INSTRUCTION, MAX_INSTRUCTION_DATA_LEN,
@@ -354,7 +354,8 @@ impl<'a> CallerAccount<'a> {
                 )?;
             }
 
-            invoke_context.consume_checked(
+            invoke_context.consume_checked_for_syscall(
+                SyscallCategory::Cpi,
                 (data.len() as u64)
                     .checked_div(invoke_context.get_execution_cost().cpi_bytes_per_unit)
                     .unwrap_or(u64::MAX),
@@ -453,7 +454,8 @@ impl<'a> CallerAccount<'a> {
             check_aligned,
         )?;
 
-        invoke_context.consume_checked(
+        invoke_context.consume_checked_for_syscall(
+            SyscallCategory::Cpi,
             account_info
                 .data_len
                 .checked_div(invoke_context.get_execution_cost().cpi_bytes_per_unit)
@@ -1049,7 +1051,7 @@ where
 }
 
 fn consume_compute_meter(invoke_context: &InvokeContext, amount: u64) -> Result<(), Error> {
-    invoke_context.consume_checked(amount)?;
+    invoke_context.consume_checked_for_syscall(SyscallCategory::Cpi, amount)?;
     Ok(())
 }
 
@@ -0,0 +1,221 @@
+//! Structured trace of the program invocations of a transaction
+//!
+//! Recording the trace is opt-in, see `InvokeContext::enable_execution_trace()`, as it copies
+//! the data of every instruction account at the start of every invocation.
+
+use {
+    solana_account::ReadableAccount,
+    solana_instruction::error::InstructionError,
+    solana_pubkey::Pubkey,
+    solana_transaction_context::{IndexOfAccount, TransactionContext, TransactionReturnData},
+    std::ops::Range,
+};
+
+/// Trace of a program invocation, and of the invocations it made
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InvocationTrace {
+    pub program_id: Pubkey,
+    /// Height in the invocation stack, top level instructions are at height
+    /// `solana_instruction::TRANSACTION_LEVEL_STACK_HEIGHT`
+    pub stack_height: usize,
+    /// Compute units consumed by the invocation, including its inner invocations
+    pub compute_units_consumed: u64,
+    /// State of the instruction accounts, without duplicates
+    pub accounts: Vec<AccountTrace>,
+    /// Return data of the transaction when the invocation returned, if any
+    pub return_data: Option<TransactionReturnData>,
+    pub result: Result<(), InstructionError>,
+    pub inner_invocations: Vec<InvocationTrace>,
+}
+
+/// State of an instruction account before and after an invocation
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccountTrace {
+    pub pubkey: Pubkey,
+    pub is_writable: bool,
+    pub pre_lamports: u64,
+    pub post_lamports: u64,
+    pub pre_owner: Pubkey,
+    pub post_owner: Pubkey,
+    pub pre_data_len: usize,
+    pub post_data_len: usize,
+    /// Byte ranges of the account data which changed, including the bytes which were added or
+    /// removed by a resize
+    pub data_diff: Vec<Range<usize>>,
+}
+
+impl AccountTrace {
+    pub fn is_modified(&self) -> bool {
+        self.pre_lamports != self.post_lamports
+            || self.pre_owner != self.post_owner
+            || !self.data_diff.is_empty()
+    }
+}
+
+struct AccountSnapshot {
+    index_in_transaction: IndexOfAccount,
+    is_writable: bool,
+    lamports: u64,
+    owner: Pubkey,
+    data: Vec<u8>,
+}
+
+struct InvocationFrame {
+    program_id: Pubkey,
+    stack_height: usize,
+    pre_remaining_units: u64,
+    pre_accounts: Vec<AccountSnapshot>,
+    inner_invocations: Vec<InvocationTrace>,
+}
+
+/// Builds the trace tree while the invocations are pushed and popped
+#[derive(Default)]
+pub(crate) struct ExecutionTracer {
+    frames: Vec<InvocationFrame>,
+    top_level_invocations: Vec<InvocationTrace>,
+}
+
+impl ExecutionTracer {
+    /// Starts tracing the invocation which was just pushed onto the invocation stack
+    pub(crate) fn start_invocation(
+        &mut self,
+        transaction_context: &TransactionContext,
+        remaining_units: u64,
+    ) {
+        let Ok(instruction_context) = transaction_context.get_current_instruction_context() else {
+            return;
+        };
+        let Ok(program_id) = instruction_context.get_program_key() else {
+            return;
+        };
+        let pre_accounts = (0..instruction_context.get_number_of_instruction_accounts())
+            .filter(|index_in_instruction| {
+                matches!(
+                    instruction_context.is_instruction_account_duplicate(*index_in_instruction),
+                    Ok(None)
+                )
+            })
+            .filter_map(|index_in_instruction| {
+                let index_in_transaction = instruction_context
+                    .get_index_of_instruction_account_in_transaction(index_in_instruction)
+                    .ok()?;
+                let is_writable = instruction_context
+                    .is_instruction_account_writable(index_in_instruction)
+                    .ok()?;
+                let account = transaction_context
+                    .accounts()
+                    .try_borrow(index_in_transaction)
+                    .ok()?;
+                Some(AccountSnapshot {
+                    index_in_transaction,
+                    is_writable,
+                    lamports: account.lamports(),
+                    owner: *account.owner(),
+                    data: account.data().to_vec(),
+                })
+            })
+            .collect();
+        self.frames.push(InvocationFrame {
+            program_id: *program_id,
+            stack_height: transaction_context.get_instruction_stack_height(),
+            pre_remaining_units: remaining_units,
+            pre_accounts,
+            inner_invocations: Vec::new(),
+        });
+    }
+
+    /// Completes the trace of the invocation which is about to be popped off the invocation stack
+    pub(crate) fn end_invocation(
+        &mut self,
+        transaction_context: &TransactionContext,
+        remaining_units: u64,
+        result: &Result<(), InstructionError>,
+    ) {
+        let Some(frame) = self.frames.pop() else {
+            return;
+        };
+        let accounts = frame
+            .pre_accounts
+            .into_iter()
+            .filter_map(|pre| {
+                let pubkey = *transaction_context
+                    .get_key_of_account_at_index(pre.index_in_transaction)
+                    .ok()?;
+                let post = transaction_context
+                    .accounts()
+                    .try_borrow(pre.index_in_transaction)
+                    .ok()?;
+                Some(AccountTrace {
+                    pubkey,
+                    is_writable: pre.is_writable,
+                    pre_lamports: pre.lamports,
+                    post_lamports: post.lamports(),
+                    pre_owner: pre.owner,
+                    post_owner: *post.owner(),
+                    pre_data_len: pre.data.len(),
+                    post_data_len: post.data().len(),
+                    data_diff: data_diff(&pre.data, post.data()),
+                })
+            })
+            .collect();
+        let (return_data_program_id, return_data) = transaction_context.get_return_data();
+        let trace = InvocationTrace {
+            program_id: frame.program_id,
+            stack_height: frame.stack_height,
+            compute_units_consumed: frame.pre_remaining_units.saturating_sub(remaining_units),
+            accounts,
+            return_data: (!return_data.is_empty()).then(|| TransactionReturnData {
+                program_id: *return_data_program_id,
+                data: return_data.to_vec(),
+            }),
+            result: result.clone(),
+            inner_invocations: frame.inner_invocations,
+        };
+        match self.frames.last_mut() {
+            Some(caller) => caller.inner_invocations.push(trace),
+            None => self.top_level_invocations.push(trace),
+        }
+    }
+
+    /// Returns the traces of the top level instructions
+    pub(crate) fn into_trace(self) -> Vec<InvocationTrace> {
+        self.top_level_invocations
+    }
+}
+
+/// Returns the byte ranges which differ between `pre` and `post`
+fn data_diff(pre: &[u8], post: &[u8]) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    let mut push_range = |range: Range<usize>| match ranges.last_mut() {
+        Some(last) if last.end == range.start => last.end = range.end,
+        _ => ranges.push(range),
+    };
+    for (offset, (pre_byte, post_byte)) in pre.iter().zip(post).enumerate() {
+        if pre_byte != post_byte {
+            push_range(offset..offset.saturating_add(1));
+        }
+    }
+    if pre.len() != post.len() {
+        push_range(pre.len().min(post.len())..pre.len().max(post.len()));
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_data_diff() {
+        assert_eq!(data_diff(&[], &[]), Vec::<Range<usize>>::new());
+        assert_eq!(
+            data_diff(&[1, 2, 3], &[1, 2, 3]),
+            Vec::<Range<usize>>::new()
+        );
+        assert_eq!(data_diff(&[1, 2, 3, 4], &[0, 2, 0, 0]), vec![0..1, 2..4]);
+        // resizes extend the last modified range
+        assert_eq!(data_diff(&[1, 2], &[1, 0, 5, 6]), vec![1..4]);
+        assert_eq!(data_diff(&[1, 2, 3, 4], &[1, 2]), vec![2..4]);
+        assert_eq!(data_diff(&[1, 2, 3, 4], &[0, 2]), vec![0..1, 2..4]);
+    }
+}
@@ -1,6 +1,7 @@
 use {
     crate::{
         execution_budget::{SVMTransactionExecutionBudget, SVMTransactionExecutionCost},
+        execution_trace::{ExecutionTracer, InvocationTrace},
         loaded_programs::{
             ProgramCacheEntry, ProgramCacheEntryType, ProgramCacheForTxBatch,
             ProgramRuntimeEnvironments,
@@ -29,7 +30,9 @@ use {
     solana_svm_feature_set::SVMFeatureSet,
     solana_svm_log_collector::{ic_msg, LogCollector},
     solana_svm_measure::measure::Measure,
-    solana_svm_timings::{ExecuteDetailsTimings, ExecuteTimings},
+    solana_svm_timings::{
+        ExecuteDetailsTimings, ExecuteTimings, SyscallCategory, SyscallComputeUnits,
+    },
     solana_svm_transaction::{instruction::SVMInstruction, svm_message::SVMMessage},
     solana_svm_type_overrides::sync::Arc,
     solana_transaction_context::{
@@ -88,6 +91,9 @@ macro_rules! declare_process_instruction {
 
 impl ContextObject for InvokeContext<'_> {
     fn consume(&mut self, amount: u64) {
+        if self.compute_meter_samples.is_some() {
+            self.sample_compute_meter(amount);
+        }
         // 1 to 1 instruction to compute unit mapping
         // ignore overflow, Ebpf will bail if exceeded
         let mut compute_meter = self.compute_meter.borrow_mut();
@@ -160,6 +166,18 @@ impl<'a> EnvironmentConfig<'a> {
     }
 }
 
+/// State of the compute meter when the VM charges the instructions it executed, which it does
+/// before every syscall and when the program exits
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ComputeMeterSample {
+    /// Index in TX instruction trace of the instruction executed by the VM
+    pub index_in_trace: usize,
+    /// Remaining compute units before the instructions were charged
+    pub remaining: u64,
+    /// Compute units charged for the instructions executed since the previous sample
+    pub consumed: u64,
+}
+
 pub struct SyscallContext {
     pub allocator: BpfAllocator,
     pub accounts_metadata: Vec<SerializedAccountMetadata>,
@@ -196,6 +214,12 @@ pub struct InvokeContext<'a> {
     pub syscall_context: Vec<Option<SyscallContext>>,
     /// Pairs of index in TX instruction trace and VM register trace
     register_traces: Vec<(usize, Vec<[u64; 12]>)>,
+    /// Records the trace of the program invocations, if enabled
+    execution_tracer: Option<ExecutionTracer>,
+    /// Samples of the compute meter taken by the VMs, if enabled
+    compute_meter_samples: Option<Vec<ComputeMeterSample>>,
+    /// Compute units charged by the syscalls, if enabled
+    syscall_compute_units: RefCell<Option<SyscallComputeUnits>>,
 }
 
 impl<'a> InvokeContext<'a> {
@@ -220,6 +244,9 @@ impl<'a> InvokeContext<'a> {
             timings: ExecuteDetailsTimings::default(),
             syscall_context: Vec::new(),
             register_traces: Vec::new(),
+            execution_tracer: None,
+            compute_meter_samples: None,
+            syscall_compute_units: RefCell::new(None),
         }
     }
 
@@ -492,10 +519,12 @@ impl<'a> InvokeContext<'a> {
     ) -> Result<(), InstructionError> {
         *compute_units_consumed = 0;
         self.push()?;
-        self.process_executable_chain(compute_units_consumed, timings)
-            // MUST pop if and only if `push` succeeded, independent of `result`.
-            // Thus, the `.and()` instead of an `.and_then()`.
-            .and(self.pop())
+        self.trace_invocation_start();
+        let result = self.process_executable_chain(compute_units_consumed, timings);
+        self.trace_invocation_end(&result);
+        // MUST pop if and only if `push` succeeded, independent of `result`.
+        // Thus, the `.and()` instead of an `.and_then()`.
+        result.and(self.pop())
     }
 
     /// Processes a precompile instruction
@@ -506,12 +535,15 @@ impl<'a> InvokeContext<'a> {
         message_instruction_datas_iter: impl Iterator<Item = &'ix_data [u8]>,
     ) -> Result<(), InstructionError> {
         self.push()?;
+        self.trace_invocation_start();
         let instruction_datas: Vec<_> = message_instruction_datas_iter.collect();
-        self.environment_config
+        let result = self
+            .environment_config
             .epoch_stake_callback
             .process_precompile(program_id, instruction_data, instruction_datas)
-            .map_err(InstructionError::from)
-            .and(self.pop())
+            .map_err(InstructionError::from);
+        self.trace_invocation_end(&result);
+        result.and(self.pop())
     }
 
     /// Calls the instruction's program entrypoint method
@@ -628,6 +660,22 @@ impl<'a> InvokeContext<'a> {
         Ok(())
     }
 
+    /// Consume the compute units charged by a syscall of `category`
+    ///
+    /// The compute units are attributed to `category` if recording them is enabled, see
+    /// [`InvokeContext::enable_syscall_compute_units()`].
+    pub fn consume_checked_for_syscall(
+        &self,
+        category: SyscallCategory,
+        amount: u64,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(syscall_compute_units) = self.syscall_compute_units.borrow_mut().as_mut() {
+            // Exceeding the budget consumes the remaining compute units
+            syscall_compute_units.add(category, amount.min(self.get_remaining()));
+        }
+        self.consume_checked(amount)
+    }
+
     /// Set compute units
     ///
     /// Only use for tests and benchmarks
@@ -741,6 +789,78 @@ impl<'a> InvokeContext<'a> {
             .push((instruction_context.get_index_in_trace(), register_trace));
     }
 
+    /// Enables recording the trace of the program invocations, see
+    /// [`InvokeContext::take_execution_trace()`]
+    pub fn enable_execution_trace(&mut self) {
+        self.execution_tracer
+            .get_or_insert_with(ExecutionTracer::default);
+    }
+
+    /// Returns the traces of the top level instructions which were processed, if recording the
+    /// trace was enabled
+    pub fn take_execution_trace(&mut self) -> Option<Vec<InvocationTrace>> {
+        self.execution_tracer
+            .take()
+            .map(ExecutionTracer::into_trace)
+    }
+
+    fn trace_invocation_start(&mut self) {
+        let remaining_units = self.get_remaining();
+        if let Some(execution_tracer) = self.execution_tracer.as_mut() {
+            execution_tracer.start_invocation(self.transaction_context, remaining_units);
+        }
+    }
+
+    fn trace_invocation_end(&mut self, result: &Result<(), InstructionError>) {
+        let remaining_units = self.get_remaining();
+        if let Some(execution_tracer) = self.execution_tracer.as_mut() {
+            execution_tracer.end_invocation(self.transaction_context, remaining_units, result);
+        }
+    }
+
+    /// Enables recording the compute units charged by the syscalls per category, see
+    /// [`InvokeContext::take_syscall_compute_units()`]
+    pub fn enable_syscall_compute_units(&mut self) {
+        self.syscall_compute_units
+            .get_mut()
+            .get_or_insert_with(SyscallComputeUnits::default);
+    }
+
+    /// Returns the compute units charged by the syscalls of the transaction, if recording them
+    /// was enabled
+    pub fn take_syscall_compute_units(&mut self) -> Option<SyscallComputeUnits> {
+        self.syscall_compute_units.get_mut().take()
+    }
+
+    /// Enables sampling the compute meter whenever a VM charges the instructions it executed, see
+    /// [`ComputeMeterSample`]
+    ///
+    /// Together with the VM register traces, the samples attribute the compute units consumed by
+    /// every syscall.
+    pub fn enable_compute_meter_samples(&mut self) {
+        self.compute_meter_samples.get_or_insert_with(Vec::new);
+    }
+
+    /// Returns the compute meter samples in the order they were taken
+    pub fn get_compute_meter_samples(&self) -> &[ComputeMeterSample] {
+        self.compute_meter_samples.as_deref().unwrap_or_default()
+    }
+
+    fn sample_compute_meter(&mut self, consumed: u64) {
+        let Ok(instruction_context) = self.transaction_context.get_current_instruction_context()
+        else {
+            return;
+        };
+        let sample = ComputeMeterSample {
+            index_in_trace: instruction_context.get_index_in_trace(),
+            remaining: self.get_remaining(),
+            consumed,
+        };
+        if let Some(compute_meter_samples) = self.compute_meter_samples.as_mut() {
+            compute_meter_samples.push(sample);
+        }
+    }
+
     /// Iterates over all VM register traces (including CPI)
     pub fn iterate_vm_traces(
         &self,
@@ -854,22 +974,25 @@ macro_rules! with_mock_invoke_context {
     };
 }
 
+/// Like [`mock_process_instruction_with_feature_set`], but returns the result of the instruction
+/// instead of asserting it, along with the accounts
+///
+/// `post_adjustments` is called with the result before the accounts are collected.
 #[allow(clippy::too_many_arguments)]
-pub fn mock_process_instruction_with_feature_set<
+pub fn mock_process_instruction_with_result<
     F: FnMut(&mut InvokeContext),
-    G: FnMut(&mut InvokeContext),
+    G: FnMut(&mut InvokeContext, &Result<(), InstructionError>),
 >(
     loader_id: &Pubkey,
     program_index: Option<IndexOfAccount>,
     instruction_data: &[u8],
     mut transaction_accounts: Vec<KeyedAccountSharedData>,
     instruction_account_metas: Vec<AccountMeta>,
-    expected_result: Result<(), InstructionError>,
     builtin_function: BuiltinFunctionWithContext,
     mut pre_adjustments: F,
     mut post_adjustments: G,
     feature_set: &SVMFeatureSet,
-) -> Vec<AccountSharedData> {
+) -> (Result<(), InstructionError>, Vec<AccountSharedData>) {
     let mut instruction_accounts: Vec<InstructionAccount> =
         Vec::with_capacity(instruction_account_metas.len());
     for account_meta in instruction_account_metas.iter() {
@@ -926,13 +1049,45 @@ pub fn mock_process_instruction_with_feature_set<
         )
         .unwrap();
     let result = invoke_context.process_instruction(&mut 0, &mut ExecuteTimings::default());
-    assert_eq!(result, expected_result);
-    post_adjustments(&mut invoke_context);
+    post_adjustments(&mut invoke_context, &result);
     let mut transaction_accounts = transaction_context.deconstruct_without_keys().unwrap();
     if pop_epoch_schedule_account {
         transaction_accounts.pop();
     }
     transaction_accounts.pop();
+    (result, transaction_accounts)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn mock_process_instruction_with_feature_set<
+    F: FnMut(&mut InvokeContext),
+    G: FnMut(&mut InvokeContext),
+>(
+    loader_id: &Pubkey,
+    program_index: Option<IndexOfAccount>,
+    instruction_data: &[u8],
+    transaction_accounts: Vec<KeyedAccountSharedData>,
+    instruction_account_metas: Vec<AccountMeta>,
+    expected_result: Result<(), InstructionError>,
+    builtin_function: BuiltinFunctionWithContext,
+    pre_adjustments: F,
+    mut post_adjustments: G,
+    feature_set: &SVMFeatureSet,
+) -> Vec<AccountSharedData> {
+    let (_result, transaction_accounts) = mock_process_instruction_with_result(
+        loader_id,
+        program_index,
+        instruction_data,
+        transaction_accounts,
+        instruction_account_metas,
+        builtin_function,
+        pre_adjustments,
+        |invoke_context, result| {
+            assert_eq!(result, &expected_result);
+            post_adjustments(invoke_context);
+        },
+        feature_set,
+    );
     transaction_accounts
 }
 
@@ -1313,6 +1468,79 @@ mod tests {
         invoke_context.pop().unwrap();
     }
 
+    #[test]
+    fn test_execution_trace() {
+        let callee_program_id = solana_pubkey::new_rand();
+        let owned_account = AccountSharedData::new(42, 1, &callee_program_id);
+        let not_owned_account = AccountSharedData::new(84, 1, &solana_pubkey::new_rand());
+        let loader_account = AccountSharedData::new(0, 1, &native_loader::id());
+        let mut program_account = AccountSharedData::new(1, 1, &native_loader::id());
+        program_account.set_executable(true);
+        let transaction_accounts = vec![
+            (solana_pubkey::new_rand(), owned_account),
+            (solana_pubkey::new_rand(), not_owned_account),
+            (callee_program_id, program_account),
+            (solana_pubkey::new_rand(), loader_account),
+        ];
+        let metas = vec![
+            AccountMeta::new(transaction_accounts.first().unwrap().0, false),
+            AccountMeta::new_readonly(transaction_accounts.get(1).unwrap().0, false),
+            // duplicates are traced once
+            AccountMeta::new(transaction_accounts.first().unwrap().0, false),
+        ];
+        with_mock_invoke_context!(invoke_context, transaction_context, transaction_accounts);
+        let mut program_cache_for_tx_batch = ProgramCacheForTxBatch::default();
+        program_cache_for_tx_batch.replenish(
+            callee_program_id,
+            Arc::new(ProgramCacheEntry::new_builtin(0, 1, MockBuiltin::vm)),
+        );
+        invoke_context.program_cache_for_tx_batch = &mut program_cache_for_tx_batch;
+        assert_eq!(invoke_context.take_execution_trace(), None);
+        invoke_context.enable_execution_trace();
+
+        // The caller is not traced, as it is pushed without being processed
+        let instruction_accounts = (0..3)
+            .map(|instruction_account_index| {
+                InstructionAccount::new(instruction_account_index, false, true)
+            })
+            .collect::<Vec<_>>();
+        invoke_context
+            .transaction_context
+            .configure_next_instruction_for_tests(3, instruction_accounts, vec![])
+            .unwrap();
+        invoke_context.push().unwrap();
+        let instruction =
+            Instruction::new_with_bincode(callee_program_id, &MockInstruction::ModifyOwned, metas);
+        invoke_context
+            .prepare_next_instruction(instruction, &[])
+            .unwrap();
+        invoke_context
+            .process_instruction(&mut 0, &mut ExecuteTimings::default())
+            .unwrap();
+        invoke_context.pop().unwrap();
+
+        let trace = invoke_context.take_execution_trace().unwrap();
+        assert_eq!(trace.len(), 1);
+        let invocation = trace.first().unwrap();
+        assert_eq!(invocation.program_id, callee_program_id);
+        assert_eq!(invocation.stack_height, 2);
+        assert_eq!(
+            invocation.compute_units_consumed,
+            MOCK_BUILTIN_COMPUTE_UNIT_COST
+        );
+        assert_eq!(invocation.result, Ok(()));
+        assert_eq!(invocation.return_data, None);
+        assert!(invocation.inner_invocations.is_empty());
+        assert_eq!(invocation.accounts.len(), 2);
+        let owned_account = invocation.accounts.first().unwrap();
+        assert!(owned_account.is_writable);
+        assert!(owned_account.is_modified());
+        assert_eq!(owned_account.data_diff, vec![0..1]);
+        let not_owned_account = invocation.accounts.get(1).unwrap();
+        assert!(!not_owned_account.is_writable);
+        assert!(!not_owned_account.is_modified());
+    }
+
     #[test]
     fn test_invoke_context_compute_budget() {
         let transaction_accounts = vec![(solana_pubkey::new_rand(), AccountSharedData::default())];
@@ -1333,6 +1561,83 @@ mod tests {
         invoke_context.pop().unwrap();
     }
 
+    #[test]
+    fn test_compute_meter_samples() {
+        let transaction_accounts = vec![(solana_pubkey::new_rand(), AccountSharedData::default())];
+        with_mock_invoke_context!(invoke_context, transaction_context, transaction_accounts);
+        invoke_context.mock_set_remaining(100);
+        invoke_context
+            .transaction_context
+            .configure_next_instruction_for_tests(0, vec![], vec![])
+            .unwrap();
+        invoke_context.push().unwrap();
+
+        // Disabled by default
+        invoke_context.consume(10);
+        assert!(invoke_context.get_compute_meter_samples().is_empty());
+
+        invoke_context.enable_compute_meter_samples();
+        invoke_context.consume(20);
+        invoke_context.consume_checked(5).unwrap();
+        invoke_context.consume(30);
+        assert_eq!(
+            invoke_context.get_compute_meter_samples(),
+            &[
+                ComputeMeterSample {
+                    index_in_trace: 0,
+                    remaining: 90,
+                    consumed: 20,
+                },
+                // syscalls charging compute units are not sampled
+                ComputeMeterSample {
+                    index_in_trace: 0,
+                    remaining: 65,
+                    consumed: 30,
+                },
+            ]
+        );
+        invoke_context.pop().unwrap();
+    }
+
+    #[test]
+    fn test_syscall_compute_units() {
+        let transaction_accounts = vec![(solana_pubkey::new_rand(), AccountSharedData::default())];
+        with_mock_invoke_context!(invoke_context, transaction_context, transaction_accounts);
+        invoke_context.mock_set_remaining(100);
+
+        // Disabled by default
+        invoke_context
+            .consume_checked_for_syscall(SyscallCategory::Hashing, 10)
+            .unwrap();
+        assert_eq!(invoke_context.take_syscall_compute_units(), None);
+
+        invoke_context.enable_syscall_compute_units();
+        invoke_context
+            .consume_checked_for_syscall(SyscallCategory::Hashing, 20)
+            .unwrap();
+        invoke_context
+            .consume_checked_for_syscall(SyscallCategory::Logging, 30)
+            .unwrap();
+        invoke_context
+            .consume_checked_for_syscall(SyscallCategory::Hashing, 5)
+            .unwrap();
+        // Only the remaining compute units are consumed when the budget is exceeded
+        assert!(invoke_context
+            .consume_checked_for_syscall(SyscallCategory::Cpi, 100)
+            .is_err());
+        assert_eq!(invoke_context.get_remaining(), 0);
+        assert_eq!(
+            invoke_context.take_syscall_compute_units(),
+            Some(SyscallComputeUnits {
+                hashing: 25,
+                logging: 30,
+                cpi: 35,
+                ..SyscallComputeUnits::default()
+            })
+        );
+        assert_eq!(invoke_context.take_syscall_compute_units(), None);
+    }
+
     #[test_case(0; "Resize the account to *the same size*, so not consuming any additional size")]
     #[test_case(1; "Resize the account larger")]
     #[test_case(-1; "Resize the account smaller")]
@@ -5,6 +5,7 @@
 pub use solana_sbpf;
 pub mod cpi;
 pub mod execution_budget;
+pub mod execution_trace;
 pub mod invoke_context;
 pub mod loaded_programs;
 pub mod mem_pool;
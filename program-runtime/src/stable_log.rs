@@ -2,11 +2,14 @@
 //!
 //! The format of these log messages should not be modified to avoid breaking downstream consumers
 //! of program logging
+//!
+//! Every message is also recorded as a typed [`LogEvent`], which consumers can use instead of
+//! parsing the messages.
 use {
     base64::{prelude::BASE64_STANDARD, Engine},
     itertools::Itertools,
     solana_pubkey::Pubkey,
-    solana_svm_log_collector::{ic_logger_msg, LogCollector},
+    solana_svm_log_collector::{ic_logger_event, ic_logger_msg, LogCollector, LogEvent},
     std::{cell::RefCell, rc::Rc},
 };
 
@@ -28,6 +31,13 @@ pub fn program_invoke(
         program_id,
         invoke_depth
     );
+    ic_logger_event!(
+        log_collector,
+        LogEvent::Invoke {
+            program_id: *program_id,
+            stack_height: invoke_depth,
+        }
+    );
 }
 
 /// Log a message from the program itself.
@@ -41,6 +51,12 @@ pub fn program_invoke(
 /// That is, any program-generated output is guaranteed to be prefixed by "Program log: "
 pub fn program_log(log_collector: &Option<Rc<RefCell<LogCollector>>>, message: &str) {
     ic_logger_msg!(log_collector, "Program log: {}", message);
+    ic_logger_event!(
+        log_collector,
+        LogEvent::Log {
+            message: message.to_string(),
+        }
+    );
 }
 
 /// Emit a program data.
@@ -58,6 +74,12 @@ pub fn program_data(log_collector: &Option<Rc<RefCell<LogCollector>>>, data: &[&
         "Program data: {}",
         data.iter().map(|v| BASE64_STANDARD.encode(v)).join(" ")
     );
+    ic_logger_event!(
+        log_collector,
+        LogEvent::Data {
+            data: data.iter().map(|v| v.to_vec()).collect(),
+        }
+    );
 }
 
 /// Log return data as from the program itself. This line will not be present if no return
@@ -81,6 +103,43 @@ pub fn program_return(
         program_id,
         BASE64_STANDARD.encode(data)
     );
+    ic_logger_event!(
+        log_collector,
+        LogEvent::Return {
+            program_id: *program_id,
+            data: data.to_vec(),
+        }
+    );
+}
+
+/// Log the compute units consumed by a program, out of the ones available when it was invoked.
+///
+/// The general form is:
+///
+/// ```notrust
+/// "Program <address> consumed <consumed> of <available> compute units"
+/// ```
+pub fn program_consumed(
+    log_collector: &Option<Rc<RefCell<LogCollector>>>,
+    program_id: &Pubkey,
+    consumed: u64,
+    available: u64,
+) {
+    ic_logger_msg!(
+        log_collector,
+        "Program {} consumed {} of {} compute units",
+        program_id,
+        consumed,
+        available
+    );
+    ic_logger_event!(
+        log_collector,
+        LogEvent::ComputeUnits {
+            program_id: *program_id,
+            consumed,
+            available,
+        }
+    );
 }
 
 /// Log successful program execution.
@@ -92,6 +151,12 @@ pub fn program_return(
 /// ```
 pub fn program_success(log_collector: &Option<Rc<RefCell<LogCollector>>>, program_id: &Pubkey) {
     ic_logger_msg!(log_collector, "Program {} success", program_id);
+    ic_logger_event!(
+        log_collector,
+        LogEvent::Success {
+            program_id: *program_id,
+        }
+    );
 }
 
 /// Log program execution failure
@@ -107,4 +172,11 @@ pub fn program_failure<E: std::fmt::Display>(
     err: &E,
 ) {
     ic_logger_msg!(log_collector, "Program {} failed: {}", program_id, err);
+    ic_logger_event!(
+        log_collector,
+        LogEvent::Failure {
+            program_id: *program_id,
+            error: err.to_string(),
+        }
+    );
 }
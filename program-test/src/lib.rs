@@ -9,12 +9,16 @@ use {
     base64::{prelude::BASE64_STANDARD, Engine},
     chrono_humanize::{Accuracy, HumanTime, Tense},
     log::*,
-    solana_account::{create_account_shared_data_for_test, Account, AccountSharedData},
+    solana_account::{
+        create_account_shared_data_for_test, Account, AccountSharedData, ReadableAccount,
+    },
     solana_account_info::AccountInfo,
-    solana_accounts_db::accounts_db::ACCOUNTS_DB_CONFIG_FOR_TESTING,
+    solana_accounts_db::{
+        accounts_db::ACCOUNTS_DB_CONFIG_FOR_TESTING, utils::create_accounts_run_and_snapshot_dirs,
+    },
     solana_banks_client::start_client,
     solana_banks_server::banks_server::start_local_server,
-    solana_clock::{Epoch, Slot},
+    solana_clock::{Clock, Epoch, Slot},
     solana_cluster_type::ClusterType,
     solana_compute_budget::compute_budget::ComputeBudget,
     solana_fee_calculator::{FeeRateGovernor, DEFAULT_TARGET_LAMPORTS_PER_SIGNATURE},
@@ -42,6 +46,8 @@ use {
         commitment::BlockCommitmentCache,
         genesis_utils::{create_genesis_config_with_leader_ex, GenesisConfigInfo},
         runtime_config::RuntimeConfig,
+        snapshot_archive_info::{FullSnapshotArchiveInfo, IncrementalSnapshotArchiveInfo},
+        snapshot_bank_utils,
     },
     solana_signer::Signer,
     solana_svm_log_collector::ic_msg,
@@ -60,10 +66,11 @@ use {
         path::{Path, PathBuf},
         sync::{
             atomic::{AtomicBool, Ordering},
-            Arc, RwLock,
+            Arc, RwLock, Weak,
         },
         time::{Duration, Instant},
     },
+    tempfile::TempDir,
     thiserror::Error,
     tokio::task::JoinHandle,
 };
@@ -85,6 +92,9 @@ pub enum ProgramTestError {
     /// The chosen warp slot is not in the future, so warp is not performed
     #[error("Warp slot not in the future")]
     InvalidWarpSlot,
+    /// The epoch of the clock is not the epoch of its slot, so the clock is not set
+    #[error("Clock epoch does not match the epoch of its slot")]
+    InvalidClockEpoch,
 }
 
 thread_local! {
@@ -468,7 +478,30 @@ pub fn read_file<P: AsRef<Path>>(path: P) -> Vec<u8> {
     file_data
 }
 
+/// Stores `account` in `bank`, adding the lamports it mints to the capitalization, or removing
+/// the ones it burns
+fn store_account_and_update_capitalization(
+    bank: &Bank,
+    address: &Pubkey,
+    account: &AccountSharedData,
+) {
+    let old_lamports = bank
+        .get_account(address)
+        .map(|old_account| old_account.lamports())
+        .unwrap_or_default();
+    bank.set_capitalization_for_tests(bank.capitalization() - old_lamports + account.lamports());
+    bank.store_account(address, account);
+}
+
+/// Snapshot archives the test environment is forked from, see [`ProgramTest::fork_from_snapshot`]
+struct ForkedSnapshot {
+    genesis_config: GenesisConfig,
+    full_snapshot_archive: PathBuf,
+    incremental_snapshot_archive: Option<PathBuf>,
+}
+
 pub struct ProgramTest {
+    forked_snapshot: Option<ForkedSnapshot>,
     accounts: Vec<(Pubkey, AccountSharedData)>,
     genesis_accounts: Vec<(Pubkey, AccountSharedData)>,
     builtin_programs: Vec<(Pubkey, &'static str, ProgramCacheEntry)>,
@@ -500,6 +533,7 @@ impl Default for ProgramTest {
             std::env::var("BPF_OUT_DIR").is_ok() || std::env::var("SBF_OUT_DIR").is_ok();
 
         Self {
+            forked_snapshot: None,
             accounts: vec![],
             genesis_accounts: vec![],
             builtin_programs: vec![],
@@ -529,6 +563,34 @@ impl ProgramTest {
         me
     }
 
+    /// Create a `ProgramTest` forked from the state of a cluster.
+    ///
+    /// The bank of the test environment is loaded from the full snapshot archive, and the
+    /// incremental snapshot archive on top of it if provided, instead of being created from a
+    /// synthetic genesis config. `genesis_config` must be the genesis config of the cluster which
+    /// produced the snapshots, e.g. loaded from its ledger with [`GenesisConfig::load`].
+    ///
+    /// Accounts and programs added with [`add_account`] and [`add_program`] override the ones of
+    /// the snapshot. Genesis accounts and feature deactivations do not apply, the features are the
+    /// ones active in the snapshot. The payer is a new account funded by the test environment.
+    ///
+    /// [`add_account`]: #method.add_account
+    /// [`add_program`]: #method.add_program
+    pub fn fork_from_snapshot(
+        genesis_config: GenesisConfig,
+        full_snapshot_archive: impl Into<PathBuf>,
+        incremental_snapshot_archive: Option<PathBuf>,
+    ) -> Self {
+        Self {
+            forked_snapshot: Some(ForkedSnapshot {
+                genesis_config,
+                full_snapshot_archive: full_snapshot_archive.into(),
+                incremental_snapshot_archive,
+            }),
+            ..Self::default()
+        }
+    }
+
     /// Override default SBF program selection
     pub fn prefer_bpf(&mut self, prefer_bpf: bool) {
         self.prefer_bpf = prefer_bpf;
@@ -770,23 +832,26 @@ impl ProgramTest {
         self.deactivate_feature_set.insert(feature_id);
     }
 
-    fn setup_bank(
-        &mut self,
-    ) -> (
-        Arc<RwLock<BankForks>>,
-        Arc<RwLock<BlockCommitmentCache>>,
-        Hash,
-        GenesisConfigInfo,
-    ) {
-        {
-            use std::sync::Once;
-            static ONCE: Once = Once::new();
+    /// The compute budget overridden by [`set_compute_max_units`], depending on whether
+    /// `raise_cpi_nesting_limit_to_8` is active
+    ///
+    /// [`set_compute_max_units`]: #method.set_compute_max_units
+    fn compute_budget(&self, simd_0268_active: bool) -> Option<ComputeBudget> {
+        self.compute_max_units.map(|max_units| ComputeBudget {
+            compute_unit_limit: max_units,
+            ..ComputeBudget::new_with_defaults(simd_0268_active)
+        })
+    }
 
-            ONCE.call_once(|| {
-                solana_sysvar::program_stubs::set_syscall_stubs(Box::new(SyscallStubs {}));
-            });
-        }
+    fn runtime_config(&self, compute_budget: Option<ComputeBudget>) -> Arc<RuntimeConfig> {
+        Arc::new(RuntimeConfig {
+            compute_budget,
+            transaction_account_lock_limit: self.transaction_account_lock_limit,
+            ..RuntimeConfig::default()
+        })
+    }
 
+    fn bank_from_genesis(&mut self) -> (Bank, GenesisConfigInfo) {
         let rent = Rent::default();
         let fee_rate_governor = FeeRateGovernor {
             // Initialize with a non-zero fee
@@ -840,18 +905,13 @@ impl ProgramTest {
 
         let bank = Bank::new_from_genesis(
             &genesis_config,
-            Arc::new(RuntimeConfig {
-                compute_budget: self.compute_max_units.map(|max_units| ComputeBudget {
-                    compute_unit_limit: max_units,
-                    ..ComputeBudget::new_with_defaults(
-                        genesis_config
-                            .accounts
-                            .contains_key(&raise_cpi_nesting_limit_to_8::id()),
-                    )
-                }),
-                transaction_account_lock_limit: self.transaction_account_lock_limit,
-                ..RuntimeConfig::default()
-            }),
+            self.runtime_config(
+                self.compute_budget(
+                    genesis_config
+                        .accounts
+                        .contains_key(&raise_cpi_nesting_limit_to_8::id()),
+                ),
+            ),
             Vec::default(),
             None,
             ACCOUNTS_DB_CONFIG_FOR_TESTING,
@@ -875,6 +935,155 @@ impl ProgramTest {
         {
             bank.store_account(program_id, account);
         }
+        bank.set_capitalization_for_tests(bank.calculate_capitalization_for_tests());
+
+        (
+            bank,
+            GenesisConfigInfo {
+                genesis_config,
+                mint_keypair,
+                voting_keypair,
+                validator_pubkey: bootstrap_validator_pubkey,
+            },
+        )
+    }
+
+    fn bank_from_snapshot(
+        &self,
+        forked_snapshot: ForkedSnapshot,
+        snapshot_dir: &TempDir,
+    ) -> (Bank, GenesisConfigInfo) {
+        let ForkedSnapshot {
+            mut genesis_config,
+            full_snapshot_archive,
+            incremental_snapshot_archive,
+        } = forked_snapshot;
+
+        if !self.genesis_accounts.is_empty() {
+            warn!("Genesis accounts are ignored when forking from a snapshot");
+        }
+        if !self.deactivate_feature_set.is_empty() {
+            warn!("Feature deactivations are ignored when forking from a snapshot");
+        }
+
+        let full_snapshot_archive_info = FullSnapshotArchiveInfo::new_from_path(
+            full_snapshot_archive.clone(),
+        )
+        .unwrap_or_else(|err| {
+            panic!(
+                "Invalid full snapshot archive {}: {err}",
+                full_snapshot_archive.display()
+            )
+        });
+        let incremental_snapshot_archive_info =
+            incremental_snapshot_archive.map(|incremental_snapshot_archive| {
+                IncrementalSnapshotArchiveInfo::new_from_path(incremental_snapshot_archive.clone())
+                    .unwrap_or_else(|err| {
+                        panic!(
+                            "Invalid incremental snapshot archive {}: {err}",
+                            incremental_snapshot_archive.display()
+                        )
+                    })
+            });
+        let (_, account_dir) =
+            create_accounts_run_and_snapshot_dirs(snapshot_dir.path().join("accounts"))
+                .unwrap_or_else(|err| panic!("Failed to create accounts directory: {err}"));
+        let bank_snapshots_dir = snapshot_dir.path().join("snapshots");
+        std::fs::create_dir_all(&bank_snapshots_dir)
+            .unwrap_or_else(|err| panic!("Failed to create bank snapshots directory: {err}"));
+
+        let snapshot_bank = snapshot_bank_utils::bank_from_snapshot_archives(
+            &[account_dir],
+            &bank_snapshots_dir,
+            &full_snapshot_archive_info,
+            incremental_snapshot_archive_info.as_ref(),
+            &genesis_config,
+            // The compute budget depends on the features of the snapshot
+            &self.runtime_config(None),
+            None,
+            None,
+            false,
+            false,
+            false,
+            ACCOUNTS_DB_CONFIG_FOR_TESTING,
+            None,
+            Arc::default(),
+        )
+        .unwrap_or_else(|err| panic!("Failed to load bank from snapshot archives: {err}"));
+        debug!("Snapshot bank slot: {}", snapshot_bank.slot());
+
+        // The snapshot bank is frozen, accounts are overridden in its child
+        let snapshot_bank = Arc::new(snapshot_bank);
+        let mut bank = Bank::new_from_parent(
+            snapshot_bank.clone(),
+            snapshot_bank.collector_id(),
+            snapshot_bank.slot() + 1,
+        );
+        bank.set_compute_budget_for_tests(
+            self.compute_budget(
+                bank.feature_set
+                    .is_active(&raise_cpi_nesting_limit_to_8::id()),
+            ),
+        );
+
+        let mint_keypair = Keypair::new();
+        debug!("Payer address: {}", mint_keypair.pubkey());
+        store_account_and_update_capitalization(
+            &bank,
+            &mint_keypair.pubkey(),
+            &AccountSharedData::new(
+                1_000_000 * LAMPORTS_PER_SOL,
+                0,
+                &solana_system_interface::program::id(),
+            ),
+        );
+
+        // The bank is loaded, only the ticks of the test environment are shortened
+        genesis_config.poh_config = PohConfig::new_sleep(Duration::from_micros(100));
+
+        (
+            bank,
+            GenesisConfigInfo {
+                genesis_config,
+                mint_keypair,
+                voting_keypair: Keypair::new(),
+                validator_pubkey: *snapshot_bank.collector_id(),
+            },
+        )
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn setup_bank(
+        &mut self,
+    ) -> (
+        Arc<RwLock<BankForks>>,
+        Arc<RwLock<BlockCommitmentCache>>,
+        Hash,
+        GenesisConfigInfo,
+        Option<TempDir>,
+    ) {
+        {
+            use std::sync::Once;
+            static ONCE: Once = Once::new();
+
+            ONCE.call_once(|| {
+                solana_sysvar::program_stubs::set_syscall_stubs(Box::new(SyscallStubs {}));
+            });
+        }
+
+        let (bank, genesis_config_info, snapshot_dir) = match self.forked_snapshot.take() {
+            Some(forked_snapshot) => {
+                let snapshot_dir = TempDir::new()
+                    .unwrap_or_else(|err| panic!("Failed to create snapshot directory: {err}"));
+                let (bank, genesis_config_info) =
+                    self.bank_from_snapshot(forked_snapshot, &snapshot_dir);
+                (bank, genesis_config_info, Some(snapshot_dir))
+            }
+            None => {
+                let (bank, genesis_config_info) = self.bank_from_genesis();
+                (bank, genesis_config_info, None)
+            }
+        };
 
         // User-supplied additional builtins
         let mut builtin_programs = Vec::new();
@@ -887,9 +1096,8 @@ impl ProgramTest {
             if bank.get_account(address).is_some() {
                 info!("Overriding account at {address}");
             }
-            bank.store_account(address, account);
+            store_account_and_update_capitalization(&bank, address, account);
         }
-        bank.set_capitalization_for_tests(bank.calculate_capitalization_for_tests());
         // Advance beyond slot 0 for a slightly more realistic test environment
         let bank = {
             let bank = Arc::new(bank);
@@ -909,17 +1117,14 @@ impl ProgramTest {
             bank_forks,
             block_commitment_cache,
             last_blockhash,
-            GenesisConfigInfo {
-                genesis_config,
-                mint_keypair,
-                voting_keypair,
-                validator_pubkey: bootstrap_validator_pubkey,
-            },
+            genesis_config_info,
+            snapshot_dir,
         )
     }
 
     pub async fn start(mut self) -> (BanksClient, Keypair, Hash) {
-        let (bank_forks, block_commitment_cache, last_blockhash, gci) = self.setup_bank();
+        let (bank_forks, block_commitment_cache, last_blockhash, gci, snapshot_dir) =
+            self.setup_bank();
         let target_tick_duration = gci.genesis_config.poh_config.target_tick_duration;
         let target_slot_duration = target_tick_duration * gci.genesis_config.ticks_per_slot as u32;
         let transport = start_local_server(
@@ -936,6 +1141,8 @@ impl ProgramTest {
         // are required when sending multiple otherwise identical transactions in series from a
         // test
         tokio::spawn(async move {
+            // The accounts of a forked snapshot are stored in `snapshot_dir`
+            let _snapshot_dir = snapshot_dir;
             loop {
                 tokio::time::sleep(target_slot_duration).await;
                 bank_forks
@@ -954,7 +1161,8 @@ impl ProgramTest {
     /// Returns a `BanksClient` interface into the test environment as well as a payer `Keypair`
     /// with SOL for sending transactions
     pub async fn start_with_context(mut self) -> ProgramTestContext {
-        let (bank_forks, block_commitment_cache, last_blockhash, gci) = self.setup_bank();
+        let (bank_forks, block_commitment_cache, last_blockhash, gci, snapshot_dir) =
+            self.setup_bank();
         let target_tick_duration = gci.genesis_config.poh_config.target_tick_duration;
         let transport = start_local_server(
             bank_forks.clone(),
@@ -972,6 +1180,7 @@ impl ProgramTest {
             banks_client,
             last_blockhash,
             gci,
+            snapshot_dir,
         )
     }
 }
@@ -1023,6 +1232,44 @@ impl<T> Drop for DroppableTask<T> {
     }
 }
 
+/// State of the test environment captured by [`ProgramTestContext::checkpoint`]
+#[derive(Clone)]
+pub struct ProgramTestCheckpoint {
+    bank: Arc<Bank>,
+}
+
+impl ProgramTestCheckpoint {
+    /// Slot of the bank the checkpoint was taken from
+    pub fn slot(&self) -> Slot {
+        self.bank.slot()
+    }
+}
+
+/// Returns the highest slot among `bank` and its parents which is also a parent of all the live
+/// checkpoints, so that rooting it keeps the checkpoints restorable
+fn root_slot_keeping_checkpoints(
+    bank: &Arc<Bank>,
+    checkpoints: &mut Vec<Weak<Bank>>,
+) -> Option<Slot> {
+    checkpoints.retain(|checkpoint| checkpoint.strong_count() > 0);
+    let checkpoint_slots = checkpoints
+        .iter()
+        .filter_map(Weak::upgrade)
+        .map(|checkpoint| {
+            checkpoint
+                .parents_inclusive()
+                .iter()
+                .map(|bank| bank.slot())
+                .collect::<HashSet<_>>()
+        })
+        .collect::<Vec<_>>();
+    bank.clone()
+        .parents_inclusive()
+        .iter()
+        .map(|bank| bank.slot())
+        .find(|slot| checkpoint_slots.iter().all(|slots| slots.contains(slot)))
+}
+
 pub struct ProgramTestContext {
     pub banks_client: BanksClient,
     pub last_blockhash: Hash,
@@ -1031,6 +1278,11 @@ pub struct ProgramTestContext {
     bank_forks: Arc<RwLock<BankForks>>,
     block_commitment_cache: Arc<RwLock<BlockCommitmentCache>>,
     _bank_task: DroppableTask<()>,
+    /// Banks of the checkpoints which may still be restored
+    checkpoints: Vec<Weak<Bank>>,
+    /// Accounts directory of the bank forked from a snapshot, see
+    /// [`ProgramTest::fork_from_snapshot`]
+    _snapshot_dir: Option<TempDir>,
 }
 
 impl ProgramTestContext {
@@ -1040,6 +1292,7 @@ impl ProgramTestContext {
         banks_client: BanksClient,
         last_blockhash: Hash,
         genesis_config_info: GenesisConfigInfo,
+        snapshot_dir: Option<TempDir>,
     ) -> Self {
         // Run a simulated PohService to provide the client with new blockhashes.  New blockhashes
         // are required when sending multiple otherwise identical transactions in series from a
@@ -1077,6 +1330,8 @@ impl ProgramTestContext {
             bank_forks,
             block_commitment_cache,
             _bank_task: bank_task,
+            checkpoints: Vec::new(),
+            _snapshot_dir: snapshot_dir,
         }
     }
 
@@ -1162,13 +1417,18 @@ impl ProgramTestContext {
                 .clone_without_scheduler()
         };
 
-        bank_forks
-            .set_root(
-                pre_warp_slot,
-                None, // snapshots are disabled
-                Some(pre_warp_slot),
-            )
-            .unwrap();
+        // Banks of the live checkpoints must not be pruned
+        if let Some(root_slot) = root_slot_keeping_checkpoints(&warp_bank, &mut self.checkpoints)
+            .filter(|root_slot| *root_slot > bank_forks.root())
+        {
+            bank_forks
+                .set_root(
+                    root_slot,
+                    None, // snapshots are disabled
+                    Some(root_slot),
+                )
+                .unwrap();
+        }
 
         // warp_bank is frozen so go forward to get unfrozen bank at warp_slot
         bank_forks.insert(Bank::new_from_parent(
@@ -1209,13 +1469,18 @@ impl ProgramTestContext {
         bank.fill_bank_with_ticks_for_tests();
         let pre_warp_slot = bank.slot();
 
-        bank_forks
-            .set_root(
-                pre_warp_slot,
-                None, // snapshot_controller
-                Some(pre_warp_slot),
-            )
-            .unwrap();
+        // Banks of the live checkpoints must not be pruned
+        if let Some(root_slot) = root_slot_keeping_checkpoints(&bank, &mut self.checkpoints)
+            .filter(|root_slot| *root_slot > bank_forks.root())
+        {
+            bank_forks
+                .set_root(
+                    root_slot,
+                    None, // snapshot_controller
+                    Some(root_slot),
+                )
+                .unwrap();
+        }
 
         // warp_bank is frozen so go forward to get unfrozen bank at warp_slot
         let warp_slot = pre_warp_slot + 1;
@@ -1238,6 +1503,103 @@ impl ProgramTestContext {
         Ok(())
     }
 
+    /// Set the `Clock` sysvar, warping the working bank to `clock.slot` first if it is not the
+    /// current slot
+    ///
+    /// Warping keeps the slot hashes and the blockhash queue consistent with the new slot, the
+    /// other fields of the clock, e.g. `unix_timestamp`, are set as provided.
+    pub fn set_clock(&mut self, clock: &Clock) -> Result<(), ProgramTestError> {
+        let bank = self.bank_forks.read().unwrap().working_bank();
+        if clock.epoch != bank.epoch_schedule().get_epoch(clock.slot) {
+            return Err(ProgramTestError::InvalidClockEpoch);
+        }
+        if clock.slot != bank.slot() {
+            self.warp_to_slot(clock.slot)?;
+        }
+        self.set_sysvar(clock);
+        Ok(())
+    }
+
+    /// Capture the state of the test environment in memory
+    ///
+    /// The working bank is frozen and continued in a new bank at the next slot. The state of the
+    /// frozen bank can be restored any number of times with [`restore`], as long as the returned
+    /// checkpoint or one of its clones is alive.
+    ///
+    /// [`restore`]: #method.restore
+    pub fn checkpoint(&mut self) -> ProgramTestCheckpoint {
+        let mut bank_forks = self.bank_forks.write().unwrap();
+        let bank = bank_forks.working_bank();
+
+        // Fill ticks until a new blockhash is recorded, otherwise retried transactions will have
+        // the same signature
+        bank.fill_bank_with_ticks_for_tests();
+
+        // The child bank freezes the checkpoint bank
+        let slot = bank.slot() + 1;
+        bank_forks.insert(Bank::new_from_parent(
+            bank.clone(),
+            &Pubkey::default(),
+            slot,
+        ));
+        self.checkpoints.push(Arc::downgrade(&bank));
+
+        self.block_commitment_cache
+            .write()
+            .unwrap()
+            .set_all_slots(slot, slot);
+        self.last_blockhash = bank_forks.working_bank().last_blockhash();
+        ProgramTestCheckpoint { bank }
+    }
+
+    /// Roll back the test environment to the state captured by `checkpoint`
+    ///
+    /// The new working bank is a child of the checkpoint bank. It is created at a slot after all
+    /// the existing banks, so the `Clock` sysvar does not go back in time, see [`set_clock`]. The
+    /// banks of the discarded fork are dropped, except the ones of the live checkpoints.
+    ///
+    /// [`set_clock`]: #method.set_clock
+    pub fn restore(&mut self, checkpoint: &ProgramTestCheckpoint) {
+        let mut bank_forks = self.bank_forks.write().unwrap();
+        let slot = bank_forks.highest_slot() + 1;
+        bank_forks.insert(Bank::new_from_parent(
+            checkpoint.bank.clone(),
+            &Pubkey::default(),
+            slot,
+        ));
+
+        // Rooting cannot prune the discarded fork, as it descends from the checkpoint bank like
+        // the new working bank
+        self.checkpoints
+            .retain(|checkpoint| checkpoint.strong_count() > 0);
+        let kept_slots = self
+            .checkpoints
+            .iter()
+            .filter_map(Weak::upgrade)
+            .chain([bank_forks.working_bank()])
+            .flat_map(|bank| bank.parents_inclusive())
+            .map(|bank| bank.slot())
+            .collect::<HashSet<_>>();
+        let root = bank_forks.root();
+        let mut discarded_slots = bank_forks
+            .banks()
+            .keys()
+            .copied()
+            .filter(|slot| *slot > root && !kept_slots.contains(slot))
+            .collect::<Vec<_>>();
+        // Children first, so that every removed bank is a leaf
+        discarded_slots.sort_unstable_by(|a, b| b.cmp(a));
+        for discarded_slot in discarded_slots {
+            bank_forks.remove(discarded_slot);
+        }
+
+        self.block_commitment_cache
+            .write()
+            .unwrap()
+            .set_all_slots(slot, slot);
+        self.last_blockhash = bank_forks.working_bank().last_blockhash();
+    }
+
     /// Get a new latest blockhash, similar in spirit to RpcClient::get_latest_blockhash()
     pub async fn get_new_latest_blockhash(&mut self) -> io::Result<Hash> {
         let blockhash = self
@@ -0,0 +1,147 @@
+use {
+    solana_clock::Clock,
+    solana_keypair::Keypair,
+    solana_program_test::{ProgramTest, ProgramTestContext, ProgramTestError},
+    solana_pubkey::Pubkey,
+    solana_signer::Signer,
+    solana_system_interface::instruction as system_instruction,
+    solana_sysvar::slot_hashes::SlotHashes,
+    solana_transaction::Transaction,
+};
+
+async fn transfer(context: &mut ProgramTestContext, recipient: &Pubkey, lamports: u64) {
+    let transaction = Transaction::new_signed_with_payer(
+        &[system_instruction::transfer(
+            &context.payer.pubkey(),
+            recipient,
+            lamports,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+}
+
+async fn balance(context: &mut ProgramTestContext, address: &Pubkey) -> u64 {
+    context.banks_client.get_balance(*address).await.unwrap()
+}
+
+#[tokio::test]
+async fn checkpoint_restore() {
+    let mut context = ProgramTest::default().start_with_context().await;
+    let recipient = Keypair::new().pubkey();
+
+    transfer(&mut context, &recipient, 1_000_000).await;
+    let checkpoint = context.checkpoint();
+    let payer = context.payer.pubkey();
+    let payer_balance = balance(&mut context, &payer).await;
+
+    // Every restore discards the changes made since the checkpoint, including warps. The
+    // transactions are the same after every restore, as the blockhash is the one of the
+    // checkpoint.
+    for _ in 0..3 {
+        transfer(&mut context, &recipient, 2_000_000).await;
+        assert_eq!(balance(&mut context, &recipient).await, 3_000_000);
+        let slot = context
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .slot;
+        context.warp_to_slot(slot + 1_000).unwrap();
+        transfer(&mut context, &recipient, 3_000_000).await;
+        assert_eq!(balance(&mut context, &recipient).await, 6_000_000);
+
+        context.restore(&checkpoint);
+        assert_eq!(balance(&mut context, &recipient).await, 1_000_000);
+        assert_eq!(balance(&mut context, &payer).await, payer_balance);
+    }
+}
+
+#[tokio::test]
+async fn checkpoint_restore_discarded_fork() {
+    let mut context = ProgramTest::default().start_with_context().await;
+    let recipient = Keypair::new().pubkey();
+
+    let first_checkpoint = context.checkpoint();
+    transfer(&mut context, &recipient, 1_000_000).await;
+    let second_checkpoint = context.checkpoint();
+    transfer(&mut context, &recipient, 2_000_000).await;
+
+    // The fork of the second checkpoint is discarded, but the second checkpoint stays restorable
+    // while it is alive
+    context.restore(&first_checkpoint);
+    assert_eq!(balance(&mut context, &recipient).await, 0);
+    context.restore(&second_checkpoint);
+    assert_eq!(balance(&mut context, &recipient).await, 1_000_000);
+    transfer(&mut context, &recipient, 2_000_000).await;
+    assert_eq!(balance(&mut context, &recipient).await, 3_000_000);
+
+    drop(second_checkpoint);
+    context.restore(&first_checkpoint);
+    assert_eq!(balance(&mut context, &recipient).await, 0);
+    let slot = context
+        .banks_client
+        .get_sysvar::<Clock>()
+        .await
+        .unwrap()
+        .slot;
+    context.warp_to_slot(slot + 10).unwrap();
+    assert_eq!(balance(&mut context, &recipient).await, 0);
+}
+
+#[tokio::test]
+async fn set_clock() {
+    let mut context = ProgramTest::default().start_with_context().await;
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+
+    let slot = clock.slot + 100;
+    let new_clock = Clock {
+        slot,
+        epoch: context.genesis_config().epoch_schedule.get_epoch(slot),
+        unix_timestamp: clock.unix_timestamp + 3_600,
+        ..clock.clone()
+    };
+    context.set_clock(&new_clock).unwrap();
+    assert_eq!(
+        context.banks_client.get_sysvar::<Clock>().await.unwrap(),
+        new_clock
+    );
+    let slot_hashes = context
+        .banks_client
+        .get_sysvar::<SlotHashes>()
+        .await
+        .unwrap();
+    assert_eq!(slot_hashes.first().unwrap().0, new_clock.slot - 1);
+
+    // The clock can be set at the current slot
+    let new_clock = Clock {
+        unix_timestamp: new_clock.unix_timestamp + 60,
+        ..new_clock.clone()
+    };
+    context.set_clock(&new_clock).unwrap();
+    assert_eq!(
+        context.banks_client.get_sysvar::<Clock>().await.unwrap(),
+        new_clock
+    );
+
+    assert_eq!(
+        context.set_clock(&Clock {
+            epoch: new_clock.epoch + 1,
+            ..new_clock.clone()
+        }),
+        Err(ProgramTestError::InvalidClockEpoch)
+    );
+    assert_eq!(
+        context.set_clock(&Clock {
+            slot: new_clock.slot - 1,
+            ..new_clock
+        }),
+        Err(ProgramTestError::InvalidWarpSlot)
+    );
+}
@@ -0,0 +1,253 @@
+use {
+    solana_account::Account,
+    solana_account_info::AccountInfo,
+    solana_clock::Clock,
+    solana_instruction::Instruction,
+    solana_msg::msg,
+    solana_native_token::LAMPORTS_PER_SOL,
+    solana_program_error::ProgramResult,
+    solana_program_test::{processor, ProgramTest},
+    solana_pubkey::Pubkey,
+    solana_runtime::{
+        bank::Bank, genesis_utils::create_genesis_config,
+        snapshot_archive_info::SnapshotArchiveInfoGetter, snapshot_bank_utils,
+        snapshot_config::SnapshotConfig,
+    },
+    solana_signer::Signer,
+    solana_system_interface::instruction as system_instruction,
+    solana_transaction::Transaction,
+    tempfile::TempDir,
+};
+
+fn process_instruction(
+    _program_id: &Pubkey,
+    _accounts: &[AccountInfo],
+    _input: &[u8],
+) -> ProgramResult {
+    msg!("Overriding program");
+    Ok(())
+}
+
+#[tokio::test]
+async fn fork_from_snapshot() {
+    let genesis_config_info = create_genesis_config(1_000 * LAMPORTS_PER_SOL);
+    let genesis_config = genesis_config_info.genesis_config;
+    let snapshot_account = Pubkey::new_unique();
+    let overridden_account = Pubkey::new_unique();
+
+    // Take a snapshot of a bank with two funded accounts
+    let (bank, _bank_forks) = Bank::new_with_bank_forks_for_tests(&genesis_config);
+    bank.transfer(
+        LAMPORTS_PER_SOL,
+        &genesis_config_info.mint_keypair,
+        &snapshot_account,
+    )
+    .unwrap();
+    bank.transfer(
+        LAMPORTS_PER_SOL,
+        &genesis_config_info.mint_keypair,
+        &overridden_account,
+    )
+    .unwrap();
+    bank.fill_bank_with_ticks_for_tests();
+
+    let snapshot_config = SnapshotConfig::default();
+    let bank_snapshots_dir = TempDir::new().unwrap();
+    let snapshot_archives_dir = TempDir::new().unwrap();
+    let snapshot = snapshot_bank_utils::bank_to_full_snapshot_archive(
+        &bank_snapshots_dir,
+        &bank,
+        Some(snapshot_config.snapshot_version),
+        &snapshot_archives_dir,
+        &snapshot_archives_dir,
+        snapshot_config.archive_format,
+    )
+    .unwrap();
+
+    let mut program_test =
+        ProgramTest::fork_from_snapshot(genesis_config, snapshot.path().clone(), None);
+    let overriding_account = Account::new(42, 0, &solana_system_interface::program::id());
+    program_test.add_account(overridden_account, overriding_account.clone());
+    let mut context = program_test.start_with_context().await;
+
+    // Accounts of the snapshot are loaded, unless overridden
+    assert_eq!(
+        context
+            .banks_client
+            .get_balance(snapshot_account)
+            .await
+            .unwrap(),
+        LAMPORTS_PER_SOL
+    );
+    assert_eq!(
+        context
+            .banks_client
+            .get_account(overridden_account)
+            .await
+            .unwrap(),
+        Some(overriding_account)
+    );
+
+    // The payer can send transactions on top of the snapshot state
+    let recipient = Pubkey::new_unique();
+    let transaction = Transaction::new_signed_with_payer(
+        &[system_instruction::transfer(
+            &context.payer.pubkey(),
+            &recipient,
+            LAMPORTS_PER_SOL,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+    assert_eq!(
+        context.banks_client.get_balance(recipient).await.unwrap(),
+        LAMPORTS_PER_SOL
+    );
+
+    let warp_slot = snapshot.slot() + 1_000;
+    context.warp_to_slot(warp_slot).unwrap();
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    assert_eq!(clock.slot, warp_slot);
+}
+
+#[tokio::test]
+async fn fork_from_incremental_snapshot() {
+    let genesis_config_info = create_genesis_config(1_000 * LAMPORTS_PER_SOL);
+    let genesis_config = genesis_config_info.genesis_config;
+    let full_snapshot_account = Pubkey::new_unique();
+    let incremental_snapshot_account = Pubkey::new_unique();
+    let snapshot_config = SnapshotConfig::default();
+    let bank_snapshots_dir = TempDir::new().unwrap();
+    let snapshot_archives_dir = TempDir::new().unwrap();
+
+    let (bank, bank_forks) = Bank::new_with_bank_forks_for_tests(&genesis_config);
+    bank.transfer(
+        LAMPORTS_PER_SOL,
+        &genesis_config_info.mint_keypair,
+        &full_snapshot_account,
+    )
+    .unwrap();
+    bank.fill_bank_with_ticks_for_tests();
+    let full_snapshot = snapshot_bank_utils::bank_to_full_snapshot_archive(
+        &bank_snapshots_dir,
+        &bank,
+        Some(snapshot_config.snapshot_version),
+        &snapshot_archives_dir,
+        &snapshot_archives_dir,
+        snapshot_config.archive_format,
+    )
+    .unwrap();
+
+    let bank = bank_forks
+        .write()
+        .unwrap()
+        .insert(Bank::new_from_parent(
+            bank,
+            &Pubkey::default(),
+            full_snapshot.slot() + 1,
+        ))
+        .clone_without_scheduler();
+    bank.transfer(
+        2 * LAMPORTS_PER_SOL,
+        &genesis_config_info.mint_keypair,
+        &incremental_snapshot_account,
+    )
+    .unwrap();
+    bank.fill_bank_with_ticks_for_tests();
+    let incremental_snapshot = snapshot_bank_utils::bank_to_incremental_snapshot_archive(
+        &bank_snapshots_dir,
+        &bank,
+        full_snapshot.slot(),
+        Some(snapshot_config.snapshot_version),
+        &snapshot_archives_dir,
+        &snapshot_archives_dir,
+        snapshot_config.archive_format,
+    )
+    .unwrap();
+
+    let context = ProgramTest::fork_from_snapshot(
+        genesis_config,
+        full_snapshot.path().clone(),
+        Some(incremental_snapshot.path().clone()),
+    )
+    .start_with_context()
+    .await;
+
+    // The accounts of both snapshots are loaded, at the slot of the incremental snapshot
+    assert_eq!(
+        context
+            .banks_client
+            .get_balance(full_snapshot_account)
+            .await
+            .unwrap(),
+        LAMPORTS_PER_SOL
+    );
+    assert_eq!(
+        context
+            .banks_client
+            .get_balance(incremental_snapshot_account)
+            .await
+            .unwrap(),
+        2 * LAMPORTS_PER_SOL
+    );
+    let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    assert!(clock.slot > incremental_snapshot.slot());
+}
+
+#[tokio::test]
+async fn fork_from_snapshot_program_override() {
+    let genesis_config_info = create_genesis_config(1_000 * LAMPORTS_PER_SOL);
+    let genesis_config = genesis_config_info.genesis_config;
+    let program_id = Pubkey::new_unique();
+
+    // The snapshot has a non-executable account at the address of the program
+    let (bank, _bank_forks) = Bank::new_with_bank_forks_for_tests(&genesis_config);
+    bank.transfer(
+        LAMPORTS_PER_SOL,
+        &genesis_config_info.mint_keypair,
+        &program_id,
+    )
+    .unwrap();
+    bank.fill_bank_with_ticks_for_tests();
+    let snapshot_config = SnapshotConfig::default();
+    let bank_snapshots_dir = TempDir::new().unwrap();
+    let snapshot_archives_dir = TempDir::new().unwrap();
+    let snapshot = snapshot_bank_utils::bank_to_full_snapshot_archive(
+        &bank_snapshots_dir,
+        &bank,
+        Some(snapshot_config.snapshot_version),
+        &snapshot_archives_dir,
+        &snapshot_archives_dir,
+        snapshot_config.archive_format,
+    )
+    .unwrap();
+
+    let mut program_test =
+        ProgramTest::fork_from_snapshot(genesis_config, snapshot.path().clone(), None);
+    program_test.prefer_bpf(false);
+    program_test.add_program(
+        "overriding_program",
+        program_id,
+        processor!(process_instruction),
+    );
+    program_test.set_compute_max_units(100_000);
+    let context = program_test.start_with_context().await;
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[Instruction::new_with_bytes(program_id, &[], vec![])],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context
+        .banks_client
+        .process_transaction(transaction)
+        .await
+        .unwrap();
+}
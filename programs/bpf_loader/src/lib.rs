@@ -1532,12 +1532,11 @@ fn execute<'a, 'b: 'a>(
             invoke_context.timings.execute_us += execute_time.as_us();
         }
 
-        ic_logger_msg!(
-            log_collector,
-            "Program {} consumed {} of {} compute units",
+        stable_log::program_consumed(
+            &log_collector,
             &program_id,
             compute_units_consumed,
-            compute_meter_prev
+            compute_meter_prev,
         );
         let (_returned_from_program_id, return_data) =
             invoke_context.transaction_context.get_return_data();
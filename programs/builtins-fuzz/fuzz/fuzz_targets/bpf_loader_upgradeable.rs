@@ -0,0 +1,8 @@
+#![no_main]
+
+use {
+    libfuzzer_sys::fuzz_target,
+    solana_builtins_fuzz::{run, Builtin},
+};
+
+fuzz_target!(|data: &[u8]| run(Builtin::BpfLoaderUpgradeable, data));
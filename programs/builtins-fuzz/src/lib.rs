@@ -0,0 +1,336 @@
+//! Fuzzing harness for the builtin programs
+//!
+//! Every input is decoded from raw bytes with [`arbitrary`] into an instruction of one of the
+//! builtins and the accounts it runs on, see [`FuzzInput`]. The `cargo fuzz` targets in `fuzz/`,
+//! the regression corpora and the deterministic runs of the tests all execute their inputs with
+//! [`run`], which checks the invariants every builtin must uphold:
+//!
+//! - The builtin returns an error rather than panicking.
+//! - The lamports of the accounts are conserved by successful instructions.
+//! - The accounts owned by the builtin do not become rent-paying when their data is written,
+//!   except for the system program which leaves that check to the runtime.
+//!
+//! The stake program is not covered, it is no longer a builtin.
+
+use {
+    arbitrary::Unstructured,
+    log::*,
+    solana_account::{
+        create_account_shared_data_for_test, AccountSharedData, ReadableAccount, WritableAccount,
+    },
+    solana_clock::Clock,
+    solana_epoch_schedule::EpochSchedule,
+    solana_hash::Hash,
+    solana_instruction::{error::InstructionError, AccountMeta},
+    solana_program_runtime::invoke_context::{
+        mock_process_instruction_with_result, BuiltinFunctionWithContext,
+    },
+    solana_pubkey::Pubkey,
+    solana_rent::Rent,
+    solana_sdk_ids::{bpf_loader, bpf_loader_upgradeable, loader_v4, native_loader, sysvar},
+    solana_slot_hashes::SlotHashes,
+    solana_svm::rent_calculator::{get_account_rent_state, transition_allowed},
+    solana_svm_feature_set::SVMFeatureSet,
+    std::{
+        fs,
+        path::{Path, PathBuf},
+    },
+};
+
+/// Most accounts an input has besides the sysvars
+const MAX_ACCOUNTS: usize = 6;
+/// Most accounts an instruction of an input references
+const MAX_INSTRUCTION_ACCOUNTS: usize = 8;
+/// Largest account data generated, enough for small programs
+const MAX_ACCOUNT_DATA_LEN: usize = 16 * 1024;
+/// Number of slots in the slot hashes sysvar, the clock is at the slot after them
+const SLOT_HASHES_LEN: u64 = 8;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Builtin {
+    System,
+    Vote,
+    LoaderV4,
+    BpfLoader,
+    BpfLoaderUpgradeable,
+}
+
+impl Builtin {
+    pub const ALL: [Builtin; 5] = [
+        Builtin::System,
+        Builtin::Vote,
+        Builtin::LoaderV4,
+        Builtin::BpfLoader,
+        Builtin::BpfLoaderUpgradeable,
+    ];
+
+    /// Name of the fuzz target and of the regression corpus directory of the builtin
+    pub fn name(&self) -> &'static str {
+        match self {
+            Builtin::System => "system",
+            Builtin::Vote => "vote",
+            Builtin::LoaderV4 => "loader_v4",
+            Builtin::BpfLoader => "bpf_loader",
+            Builtin::BpfLoaderUpgradeable => "bpf_loader_upgradeable",
+        }
+    }
+
+    pub fn program_id(&self) -> Pubkey {
+        match self {
+            Builtin::System => solana_sdk_ids::system_program::id(),
+            Builtin::Vote => solana_sdk_ids::vote::id(),
+            Builtin::LoaderV4 => loader_v4::id(),
+            Builtin::BpfLoader => bpf_loader::id(),
+            Builtin::BpfLoaderUpgradeable => bpf_loader_upgradeable::id(),
+        }
+    }
+
+    fn entrypoint(&self) -> BuiltinFunctionWithContext {
+        match self {
+            Builtin::System => solana_system_program::system_processor::Entrypoint::vm,
+            Builtin::Vote => solana_vote_program::vote_processor::Entrypoint::vm as _,
+            Builtin::LoaderV4 => solana_loader_v4_program::Entrypoint::vm as _,
+            Builtin::BpfLoader | Builtin::BpfLoaderUpgradeable => {
+                solana_bpf_loader_program::Entrypoint::vm as _
+            }
+        }
+    }
+
+    /// Bound of the bincode enum discriminants generated for the instruction data
+    ///
+    /// It is past the last instruction of the builtin, so that unknown instructions are covered
+    /// as well.
+    fn max_discriminant(&self) -> u32 {
+        match self {
+            Builtin::System => 16,
+            Builtin::Vote => 20,
+            Builtin::LoaderV4 => 10,
+            Builtin::BpfLoader => 3,
+            Builtin::BpfLoaderUpgradeable => 12,
+        }
+    }
+
+    /// Whether the builtin checks that the accounts it writes stay rent-exempt
+    fn checks_rent(&self) -> bool {
+        !matches!(self, Builtin::System)
+    }
+}
+
+/// Instruction of a builtin and the accounts it runs on
+#[derive(Debug)]
+pub struct FuzzInput {
+    /// Accounts of the transaction, followed by the sysvars
+    pub accounts: Vec<(Pubkey, AccountSharedData)>,
+    pub instruction_accounts: Vec<AccountMeta>,
+    pub instruction_data: Vec<u8>,
+}
+
+impl FuzzInput {
+    pub fn arbitrary(builtin: Builtin, u: &mut Unstructured) -> arbitrary::Result<Self> {
+        let mut accounts = Vec::new();
+        for index in 0..u.int_in_range(0..=MAX_ACCOUNTS)? {
+            let address = Pubkey::new_from_array([(index as u8).saturating_add(1); 32]);
+            accounts.push((address, arbitrary_account(builtin, u)?));
+        }
+        accounts.extend(sysvar_accounts());
+
+        // The program account is added by `mock_process_instruction_with_result` after the
+        // other accounts
+        let instruction_accounts = (0..u.int_in_range(0..=MAX_INSTRUCTION_ACCOUNTS)?)
+            .map(|_| {
+                let index = u.choose_index(accounts.len().saturating_add(1))?;
+                let account = accounts.get(index);
+                let is_signer = u.arbitrary()?;
+                let is_writable = u.arbitrary()?;
+                Ok(match account {
+                    Some((address, _)) => AccountMeta {
+                        pubkey: *address,
+                        is_signer,
+                        is_writable,
+                    },
+                    // Transactions demote the builtins to read-only, like all reserved accounts
+                    None => AccountMeta {
+                        pubkey: builtin.program_id(),
+                        is_signer,
+                        is_writable: false,
+                    },
+                })
+            })
+            .collect::<arbitrary::Result<_>>()?;
+
+        let discriminant = u.int_in_range(0..=builtin.max_discriminant())?;
+        let mut instruction_data = discriminant.to_le_bytes().to_vec();
+        instruction_data.extend_from_slice(u.bytes(u.len())?);
+
+        Ok(Self {
+            accounts,
+            instruction_accounts,
+            instruction_data,
+        })
+    }
+
+    /// Executes the instruction and checks the invariants, panicking if one does not hold
+    pub fn execute(&self, builtin: Builtin) -> Result<(), InstructionError> {
+        let (result, post_accounts) = mock_process_instruction_with_result(
+            &builtin.program_id(),
+            None,
+            &self.instruction_data,
+            self.accounts.clone(),
+            self.instruction_accounts.clone(),
+            builtin.entrypoint(),
+            |_invoke_context| {},
+            |_invoke_context, _result| {},
+            &SVMFeatureSet::all_enabled(),
+        );
+        if result.is_ok() {
+            self.check_invariants(builtin, &post_accounts);
+        }
+        result
+    }
+
+    fn check_invariants(&self, builtin: Builtin, post_accounts: &[AccountSharedData]) {
+        assert_eq!(self.accounts.len(), post_accounts.len());
+        let pre_lamports: u128 = self
+            .accounts
+            .iter()
+            .map(|(_, account)| account.lamports() as u128)
+            .sum();
+        let post_lamports: u128 = post_accounts
+            .iter()
+            .map(|account| account.lamports() as u128)
+            .sum();
+        assert_eq!(
+            pre_lamports, post_lamports,
+            "{builtin:?} did not conserve lamports",
+        );
+
+        if !builtin.checks_rent() {
+            return;
+        }
+        let rent = Rent::default();
+        for ((address, pre_account), post_account) in self.accounts.iter().zip(post_accounts) {
+            if post_account.owner() != &builtin.program_id()
+                || pre_account.data() == post_account.data()
+            {
+                continue;
+            }
+            assert!(
+                transition_allowed(
+                    &get_account_rent_state(&rent, pre_account),
+                    &get_account_rent_state(&rent, post_account),
+                ),
+                "{builtin:?} left {address} rent-paying",
+            );
+        }
+    }
+}
+
+fn arbitrary_account(
+    builtin: Builtin,
+    u: &mut Unstructured,
+) -> arbitrary::Result<AccountSharedData> {
+    let owner = *u.choose(&[
+        builtin.program_id(),
+        solana_sdk_ids::system_program::id(),
+        solana_sdk_ids::vote::id(),
+        loader_v4::id(),
+        bpf_loader_upgradeable::id(),
+        bpf_loader::id(),
+        native_loader::id(),
+        Pubkey::new_from_array([1; 32]),
+    ])?;
+    let data_len = u.int_in_range(0..=MAX_ACCOUNT_DATA_LEN)?;
+    let data = if u.arbitrary()? {
+        vec![0; data_len]
+    } else {
+        u.bytes(data_len.min(u.len()))?.to_vec()
+    };
+    let lamports = match u.int_in_range(0..=3)? {
+        0 => 0,
+        1 => Rent::default().minimum_balance(data.len()),
+        2 => u.int_in_range(0..=Rent::default().minimum_balance(data.len()))?,
+        _ => u.arbitrary()?,
+    };
+    Ok(AccountSharedData::create(
+        lamports,
+        data,
+        owner,
+        u.ratio(1, 8)?,
+        u64::MAX,
+    ))
+}
+
+fn sysvar_accounts() -> [(Pubkey, AccountSharedData); 4] {
+    let slot_hashes = (0..SLOT_HASHES_LEN)
+        .rev()
+        .map(|slot| (slot, Hash::new_from_array([slot as u8; 32])))
+        .collect::<Vec<_>>();
+    let clock = Clock {
+        slot: SLOT_HASHES_LEN,
+        ..Clock::default()
+    };
+    [
+        (
+            sysvar::clock::id(),
+            create_account_shared_data_for_test(&clock),
+        ),
+        (
+            sysvar::rent::id(),
+            create_account_shared_data_for_test(&Rent::default()),
+        ),
+        (
+            sysvar::epoch_schedule::id(),
+            create_account_shared_data_for_test(&EpochSchedule::default()),
+        ),
+        (
+            sysvar::slot_hashes::id(),
+            create_account_shared_data_for_test(&SlotHashes::new(&slot_hashes)),
+        ),
+    ]
+}
+
+/// Runs the input decoded from `data` on `builtin`, see the invariants in the crate docs
+///
+/// The data the input could not be decoded from is ignored.
+pub fn run(builtin: Builtin, data: &[u8]) {
+    let mut u = Unstructured::new(data);
+    if let Ok(input) = FuzzInput::arbitrary(builtin, &mut u) {
+        let _ = input.execute(builtin);
+    }
+}
+
+/// Directory of the regression corpus of `builtin`
+///
+/// It contains the inputs minimized with `cargo fuzz tmin` and `cargo fuzz cmin`, which are
+/// replayed by the tests.
+pub fn regressions_dir(builtin: Builtin) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("fuzz")
+        .join("regressions")
+        .join(builtin.name())
+}
+
+/// Runs every input of the regression corpus of `builtin`, returning how many were run
+///
+/// Panics if the corpus is missing, as the corpus of every builtin is seeded with inputs reaching
+/// each of its instructions.
+pub fn replay_regressions(builtin: Builtin) -> usize {
+    let dir = regressions_dir(builtin);
+    let entries = fs::read_dir(&dir).unwrap_or_else(|err| {
+        panic!(
+            "missing regression corpus of {builtin:?} at {}: {err}",
+            dir.display()
+        )
+    });
+    let mut paths = entries
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.is_file())
+        .collect::<Vec<_>>();
+    paths.sort();
+    for path in &paths {
+        let data = fs::read(path).unwrap();
+        debug!("Replaying {}", path.display());
+        run(builtin, &data);
+    }
+    paths.len()
+}
@@ -0,0 +1,30 @@
+use {
+    rand::{rngs::StdRng, Rng, SeedableRng},
+    solana_builtins_fuzz::{replay_regressions, run, Builtin},
+};
+
+/// Inputs generated per builtin by the deterministic runs
+const RUNS: usize = 2_000;
+/// Longest input generated by the deterministic runs
+const MAX_INPUT_LEN: usize = 1024;
+
+#[test]
+fn test_deterministic_runs() {
+    for builtin in Builtin::ALL {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut data = vec![0; MAX_INPUT_LEN];
+        for _ in 0..RUNS {
+            let len = rng.gen_range(0..=MAX_INPUT_LEN);
+            rng.fill(&mut data[..len]);
+            run(builtin, &data[..len]);
+        }
+    }
+}
+
+#[test]
+fn test_regressions() {
+    for builtin in Builtin::ALL {
+        let count = replay_regressions(builtin);
+        assert!(count > 0, "empty regression corpus of {builtin:?}");
+    }
+}
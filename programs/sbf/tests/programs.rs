@@ -108,6 +108,9 @@ fn load_execute_and_commit_transaction(bank: &Bank, tx: Transaction) -> Transact
                 enable_log_recording: true,
                 enable_return_data_recording: false,
                 enable_transaction_balance_recording: false,
+                enable_execution_trace: false,
+                enable_syscall_compute_units_recording: false,
+                enable_log_event_recording: false,
             },
             &mut ExecuteTimings::default(),
             None,
@@ -4428,6 +4431,79 @@ fn test_program_sbf_deplete_cost_meter_with_divide_by_zero() {
     assert_eq!(result.executed_units, u64::from(compute_unit_limit));
 }
 
+#[test]
+#[cfg(feature = "sbf_rust")]
+fn test_program_sbf_syscall_compute_units() {
+    solana_logger::setup();
+
+    let GenesisConfigInfo {
+        genesis_config,
+        mint_keypair,
+        ..
+    } = create_genesis_config(50);
+
+    let (bank, bank_forks) = Bank::new_with_bank_forks_for_tests(&genesis_config);
+    let mut bank_client = BankClient::new_shared(bank);
+    let authority_keypair = Keypair::new();
+    let (_bank, invoke_and_return) = load_program_of_loader_v4(
+        &mut bank_client,
+        &bank_forks,
+        &mint_keypair,
+        &authority_keypair,
+        "solana_sbf_rust_invoke_and_return",
+    );
+    let (bank, sha_program_id) = load_program_of_loader_v4(
+        &mut bank_client,
+        &bank_forks,
+        &mint_keypair,
+        &authority_keypair,
+        "solana_sbf_rust_sha",
+    );
+
+    // The hashes and the log are charged to the invoked program, the CPI to the caller
+    let instruction = Instruction::new_with_bytes(
+        invoke_and_return,
+        &[],
+        vec![AccountMeta::new_readonly(sha_program_id, false)],
+    );
+    let message = Message::new(&[instruction], Some(&mint_keypair.pubkey()));
+    let tx = Transaction::new(&[&mint_keypair], message, bank.last_blockhash());
+    let tx_batch = bank.prepare_batch_for_tests(vec![tx]);
+    let commit_result = bank
+        .load_execute_and_commit_transactions(
+            &tx_batch,
+            MAX_PROCESSING_AGE,
+            ExecutionRecordingConfig {
+                enable_cpi_recording: false,
+                enable_log_recording: false,
+                enable_return_data_recording: false,
+                enable_transaction_balance_recording: false,
+                enable_execution_trace: false,
+                enable_syscall_compute_units_recording: true,
+                enable_log_event_recording: false,
+            },
+            &mut ExecuteTimings::default(),
+            None,
+        )
+        .0
+        .pop()
+        .unwrap()
+        .unwrap();
+    assert_eq!(commit_result.status, Ok(()));
+
+    let syscall_compute_units = commit_result.syscall_compute_units.unwrap();
+    // sha256, keccak256 and blake3 of two slices of at most 16 bytes: base cost and
+    // `mem_op_base_cost` per slice
+    assert_eq!(syscall_compute_units.hashing, 3 * (85 + 2 * 10));
+    // `msg!("sha")`
+    assert_eq!(syscall_compute_units.logging, 100);
+    assert_eq!(syscall_compute_units.curve_ops, 0);
+    assert_eq!(syscall_compute_units.sysvars, 0);
+    // The invoked program's compute units are not included in the CPI
+    assert!(syscall_compute_units.cpi > 0);
+    assert!(syscall_compute_units.total() < commit_result.executed_units);
+}
+
 #[test]
 #[cfg(feature = "sbf_rust")]
 fn test_deny_access_beyond_current_length() {
@@ -5282,6 +5358,9 @@ fn test_function_call_args() {
                 enable_log_recording: false,
                 enable_return_data_recording: true,
                 enable_transaction_balance_recording: false,
+                enable_execution_trace: false,
+                enable_syscall_compute_units_recording: false,
+                enable_log_event_recording: false,
             },
             &mut ExecuteTimings::default(),
             None,
@@ -73,6 +73,7 @@ mod tests {
             endpoints: _,
             thread: t,
             key_updater: _,
+            connection_qos: _,
         } = solana_streamer::quic::spawn_server_with_cancel(
             "solQuicTest",
             "quic_streamer_test",
@@ -154,6 +155,7 @@ mod tests {
             stats: _,
             thread: t,
             max_concurrent_connections: _,
+            connection_qos: _,
         } = solana_streamer::nonblocking::quic::spawn_server_with_cancel(
             "quic_streamer_test",
             vec![s.try_clone().unwrap()],
@@ -211,6 +213,7 @@ mod tests {
             endpoints: request_recv_endpoints,
             thread: request_recv_thread,
             key_updater: _,
+            connection_qos: _,
         } = solana_streamer::quic::spawn_server_with_cancel(
             "solQuicTest",
             "quic_streamer_test",
@@ -235,6 +238,7 @@ mod tests {
             endpoints: mut response_recv_endpoints,
             thread: response_recv_thread,
             key_updater: _,
+            connection_qos: _,
         } = solana_streamer::quic::spawn_server_with_cancel(
             "solQuicTest",
             "quic_streamer_test",
@@ -322,6 +326,7 @@ mod tests {
             stats: _,
             thread: t,
             max_concurrent_connections: _,
+            connection_qos: _,
         } = solana_streamer::nonblocking::quic::spawn_server_with_cancel(
             "quic_streamer_test",
             vec![s.try_clone().unwrap()],
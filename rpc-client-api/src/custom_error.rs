@@ -27,6 +27,7 @@ pub const JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED: i64 = -32016;
 pub const JSON_RPC_SERVER_ERROR_EPOCH_REWARDS_PERIOD_ACTIVE: i64 = -32017;
 pub const JSON_RPC_SERVER_ERROR_SLOT_NOT_EPOCH_BOUNDARY: i64 = -32018;
 pub const JSON_RPC_SERVER_ERROR_LONG_TERM_STORAGE_UNREACHABLE: i64 = -32019;
+pub const JSON_RPC_SERVER_ERROR_CURSOR_EXPIRED: i64 = -32020;
 
 #[derive(Error, Debug)]
 #[allow(clippy::large_enum_variant)]
@@ -79,6 +80,8 @@ pub enum RpcCustomError {
     SlotNotEpochBoundary { slot: Slot },
     #[error("LongTermStorageUnreachable")]
     LongTermStorageUnreachable,
+    #[error("CursorExpired")]
+    CursorExpired { slot: Slot },
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -258,6 +261,16 @@ impl From<RpcCustomError> for Error {
                 message: "Failed to query long-term storage; please try again".to_string(),
                 data: None,
             },
+            RpcCustomError::CursorExpired { slot } => Self {
+                code: ErrorCode::ServerError(JSON_RPC_SERVER_ERROR_CURSOR_EXPIRED),
+                message: format!(
+                    "Cursor expired: slot {slot} is no longer available; restart from the first \
+                     page"
+                ),
+                data: Some(serde_json::json!({
+                    "slot": slot,
+                })),
+            },
         }
     }
 }
@@ -32,6 +32,19 @@ pub struct RpcSimulateTransactionAccountsConfig {
     pub addresses: Vec<String>,
 }
 
+/// Replaces the state of an account for a simulation; the fields which are not set keep the
+/// state of the account in the bank, or of an empty account if it does not exist
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAccountOverride {
+    pub address: String,
+    pub lamports: Option<u64>,
+    pub owner: Option<String>,
+    /// Base64 encoded account data
+    pub data: Option<String>,
+    pub executable: Option<bool>,
+}
+
 #[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcSimulateTransactionConfig {
@@ -46,6 +59,28 @@ pub struct RpcSimulateTransactionConfig {
     pub min_context_slot: Option<Slot>,
     #[serde(default)]
     pub inner_instructions: bool,
+    pub account_overrides: Option<Vec<RpcAccountOverride>>,
+    /// Record the tree of program invocations, with the compute units consumed and the accounts
+    /// modified by each of them
+    #[serde(default)]
+    pub execution_trace: bool,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSimulateBundleConfig {
+    #[serde(default)]
+    pub sig_verify: bool,
+    #[serde(default)]
+    pub replace_recent_blockhash: bool,
+    #[serde(flatten)]
+    pub commitment: Option<CommitmentConfig>,
+    pub encoding: Option<UiTransactionEncoding>,
+    /// Accounts to return the state of before and after every transaction
+    pub accounts: Option<RpcSimulateTransactionAccountsConfig>,
+    pub min_context_slot: Option<Slot>,
+    #[serde(default)]
+    pub inner_instructions: bool,
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -166,6 +201,10 @@ pub struct RpcProgramAccountsConfig {
     pub account_config: RpcAccountInfoConfig,
     pub with_context: Option<bool>,
     pub sort_results: Option<bool>,
+    /// Return at most this many accounts, ordered by pubkey, along with a cursor to the next page
+    pub limit: Option<usize>,
+    /// The `nextCursor` of the previous page
+    pub cursor: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -1,13 +1,17 @@
 use {
     base64::{prelude::BASE64_STANDARD, Engine},
-    serde::Deserialize,
-    std::borrow::Cow,
+    serde::{de, Deserialize, Deserializer, Serialize, Serializer},
+    std::{borrow::Cow, fmt},
     thiserror::Error,
 };
 
 const MAX_DATA_SIZE: usize = 128;
 const MAX_DATA_BASE58_SIZE: usize = 175;
 const MAX_DATA_BASE64_SIZE: usize = 172;
+/// Maximum number of filters in an `Or` filter
+pub const MAX_OR_FILTERS: usize = 4;
+/// Maximum nesting of `Or` and `Not` filters
+pub const MAX_FILTER_DEPTH: usize = 3;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -15,10 +19,28 @@ pub enum RpcFilterType {
     DataSize(u64),
     Memcmp(Memcmp),
     TokenAccountState,
+    /// Account data length within an inclusive range
+    DataSizeRange(ValueRange<u64>),
+    /// Account lamports within an inclusive range
+    Lamports(ValueRange<u64>),
+    /// Little-endian u64 at an offset of the account data within an inclusive range
+    U64Range(DataRange<u64>),
+    /// Little-endian i64 at an offset of the account data within an inclusive range
+    I64Range(DataRange<i64>),
+    /// Little-endian u128 at an offset of the account data within an inclusive range
+    U128Range(DataRange<StringU128>),
+    /// Matches if any of the filters match
+    Or(Vec<RpcFilterType>),
+    /// Matches if the filter does not match
+    Not(Box<RpcFilterType>),
 }
 
 impl RpcFilterType {
     pub fn verify(&self) -> Result<(), RpcFilterError> {
+        self.verify_with_depth(0)
+    }
+
+    fn verify_with_depth(&self, depth: usize) -> Result<(), RpcFilterError> {
         match self {
             RpcFilterType::DataSize(_) => Ok(()),
             RpcFilterType::Memcmp(compare) => {
@@ -55,7 +77,111 @@ impl RpcFilterType {
                 }
             }
             RpcFilterType::TokenAccountState => Ok(()),
+            RpcFilterType::DataSizeRange(range) | RpcFilterType::Lamports(range) => range.verify(),
+            RpcFilterType::U64Range(range) => range.verify(),
+            RpcFilterType::I64Range(range) => range.verify(),
+            RpcFilterType::U128Range(range) => range.verify(),
+            RpcFilterType::Or(filters) => {
+                if depth >= MAX_FILTER_DEPTH {
+                    return Err(RpcFilterError::NestingTooDeep);
+                }
+                if filters.is_empty() || filters.len() > MAX_OR_FILTERS {
+                    return Err(RpcFilterError::InvalidOrFilter);
+                }
+                filters
+                    .iter()
+                    .try_for_each(|filter| filter.verify_with_depth(depth + 1))
+            }
+            RpcFilterType::Not(filter) => {
+                if depth >= MAX_FILTER_DEPTH {
+                    return Err(RpcFilterError::NestingTooDeep);
+                }
+                filter.verify_with_depth(depth + 1)
+            }
+        }
+    }
+}
+
+/// An inclusive range of values, where a missing bound is unbounded
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ValueRange<T> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<T>,
+}
+
+impl<T: PartialOrd> ValueRange<T> {
+    pub fn new(min: Option<T>, max: Option<T>) -> Self {
+        Self { min, max }
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.min.as_ref().is_none_or(|min| value >= min)
+            && self.max.as_ref().is_none_or(|max| value <= max)
+    }
+
+    fn verify(&self) -> Result<(), RpcFilterError> {
+        match (&self.min, &self.max) {
+            (Some(min), Some(max)) if min > max => Err(RpcFilterError::InvalidRange),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// An inclusive range of a little-endian integer stored at `offset` of the account data
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DataRange<T> {
+    /// Data offset of the integer
+    pub offset: usize,
+    #[serde(flatten)]
+    pub range: ValueRange<T>,
+}
+
+impl<T: PartialOrd> DataRange<T> {
+    pub fn new(offset: usize, min: Option<T>, max: Option<T>) -> Self {
+        Self {
+            offset,
+            range: ValueRange::new(min, max),
+        }
+    }
+
+    fn verify(&self) -> Result<(), RpcFilterError> {
+        self.range.verify()
+    }
+}
+
+/// A u128 that is serialized as a decimal string, since JSON numbers cannot represent every u128
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct StringU128(pub u128);
+
+impl Serialize for StringU128 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for StringU128 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct StringU128Visitor;
+
+        impl de::Visitor<'_> for StringU128Visitor {
+            type Value = StringU128;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a u128 as a decimal string or an integer")
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+                Ok(StringU128(value.into()))
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                value.parse().map(StringU128).map_err(E::custom)
+            }
         }
+
+        deserializer.deserialize_any(StringU128Visitor)
     }
 }
 
@@ -67,6 +193,12 @@ pub enum RpcFilterError {
     Base58DecodeError(#[from] bs58::decode::Error),
     #[error("base64 decode error")]
     Base64DecodeError(#[from] base64::DecodeError),
+    #[error("range minimum is greater than its maximum")]
+    InvalidRange,
+    #[error("or filters must contain between 1 and {MAX_OR_FILTERS} filters")]
+    InvalidOrFilter,
+    #[error("filters may not be nested more than {MAX_FILTER_DEPTH} levels deep")]
+    NestingTooDeep,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
@@ -441,4 +573,88 @@ mod tests {
             serde_json::from_str::<Value>(BYTES_FILTER_WITH_ENCODING).unwrap()
         );
     }
+
+    #[test]
+    fn test_range_filter_serde() {
+        let filter = RpcFilterType::Or(vec![
+            RpcFilterType::Lamports(ValueRange::new(Some(1), None)),
+            RpcFilterType::Not(Box::new(RpcFilterType::DataSizeRange(ValueRange::new(
+                Some(10),
+                Some(20),
+            )))),
+            RpcFilterType::I64Range(DataRange::new(8, Some(-5), Some(5))),
+            RpcFilterType::U128Range(DataRange::new(16, None, Some(StringU128(u128::MAX)))),
+        ]);
+        let expected = json!({
+            "or": [
+                {"lamports": {"min": 1}},
+                {"not": {"dataSizeRange": {"min": 10, "max": 20}}},
+                {"i64Range": {"offset": 8, "min": -5, "max": 5}},
+                {"u128Range": {"offset": 16, "max": u128::MAX.to_string()}},
+            ]
+        });
+        assert_eq!(json!(filter), expected);
+        assert_eq!(
+            serde_json::from_value::<RpcFilterType>(expected).unwrap(),
+            filter
+        );
+
+        // u128 bounds may also be given as JSON numbers
+        assert_eq!(
+            serde_json::from_value::<RpcFilterType>(json!({"u128Range": {"offset": 0, "min": 7}}))
+                .unwrap(),
+            RpcFilterType::U128Range(DataRange::new(0, Some(StringU128(7)), None))
+        );
+        assert!(serde_json::from_value::<RpcFilterType>(
+            json!({"u128Range": {"offset": 0, "min": "-7"}})
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_value_range_contains() {
+        let range = ValueRange::new(Some(2u64), Some(4));
+        assert!(!range.contains(&1));
+        assert!(range.contains(&2));
+        assert!(range.contains(&4));
+        assert!(!range.contains(&5));
+        assert!(ValueRange::<u64>::default().contains(&u64::MAX));
+        assert!(ValueRange::new(None, Some(-1i64)).contains(&i64::MIN));
+    }
+
+    #[test]
+    fn test_verify_range_and_combinator_filters() {
+        let lamports = || RpcFilterType::Lamports(ValueRange::new(Some(1), Some(2)));
+        assert_eq!(lamports().verify(), Ok(()));
+        assert_eq!(
+            RpcFilterType::U64Range(DataRange::new(0, Some(2), Some(1))).verify(),
+            Err(RpcFilterError::InvalidRange)
+        );
+        assert_eq!(
+            RpcFilterType::Or(vec![]).verify(),
+            Err(RpcFilterError::InvalidOrFilter)
+        );
+        assert_eq!(
+            RpcFilterType::Or(vec![lamports(); MAX_OR_FILTERS + 1]).verify(),
+            Err(RpcFilterError::InvalidOrFilter)
+        );
+
+        // nested filters are verified
+        assert_eq!(
+            RpcFilterType::Not(Box::new(RpcFilterType::DataSizeRange(ValueRange::new(
+                Some(2),
+                Some(1)
+            ))))
+            .verify(),
+            Err(RpcFilterError::InvalidRange)
+        );
+
+        let mut filter = lamports();
+        for _ in 0..MAX_FILTER_DEPTH {
+            filter = RpcFilterType::Not(Box::new(filter));
+        }
+        assert_eq!(filter.verify(), Ok(()));
+        let filter = RpcFilterType::Or(vec![filter]);
+        assert_eq!(filter.verify(), Err(RpcFilterError::NestingTooDeep));
+    }
 }
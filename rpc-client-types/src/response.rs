@@ -18,7 +18,8 @@ pub use {
         UiAccountEncoding, // used in UiAccountData
     },
     solana_fee_calculator::{FeeCalculator, FeeRateGovernor},
-    solana_reward_info::RewardType,    // used in Reward
+    solana_instruction_error::InstructionError, // used in RpcInvocationTrace
+    solana_reward_info::RewardType,             // used in Reward
     solana_transaction as transaction, // used in EncodedTransaction (may as well re-export the whole crate)
     solana_transaction_error::{TransactionError, TransactionResult},
     solana_transaction_status_client_types::{
@@ -37,9 +38,11 @@ pub use {
         UiInnerInstructions,
         UiInstruction, // used in UiInnerInstructions
         UiLoadedAddresses,
+        UiLogEvent,                    // used in UiTransactionStatusMeta
         UiParsedInstruction,           // used in UiInstruction
         UiPartiallyDecodedInstruction, // used in UiParsedInstruction
         UiReturnDataEncoding,          // used in UiTransactionReturnData
+        UiSyscallComputeUnits,         // used in UiTransactionStatusMeta
         UiTransactionError,
         UiTransactionReturnData,
         UiTransactionStatusMeta, // used in EncodedTransactionWithStatusMeta
@@ -197,6 +200,36 @@ pub struct RpcKeyedAccount {
     pub account: UiAccount,
 }
 
+/// One page of `getProgramAccounts` results
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcKeyedAccountsPage {
+    pub accounts: Vec<RpcKeyedAccount>,
+    /// Cursor of the following page, `None` once all accounts have been returned
+    pub next_cursor: Option<String>,
+}
+
+/// The result of `getProgramAccounts`, which is paged when the request sets a `limit`
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum RpcProgramAccounts {
+    Accounts(Vec<RpcKeyedAccount>),
+    Page(RpcKeyedAccountsPage),
+}
+
+impl RpcProgramAccounts {
+    /// The accounts along with the cursor of the following page, if any
+    pub fn into_page(self) -> (Vec<RpcKeyedAccount>, Option<String>) {
+        match self {
+            Self::Accounts(accounts) => (accounts, None),
+            Self::Page(RpcKeyedAccountsPage {
+                accounts,
+                next_cursor,
+            }) => (accounts, next_cursor),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
 pub struct SlotInfo {
     pub slot: Slot,
@@ -445,6 +478,61 @@ pub struct RpcSimulateTransactionResult {
     pub pre_token_balances: Option<Vec<UiTransactionTokenBalance>>,
     pub post_token_balances: Option<Vec<UiTransactionTokenBalance>>,
     pub loaded_addresses: Option<UiLoadedAddresses>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execution_trace: Option<Vec<RpcInvocationTrace>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcInvocationTrace {
+    pub program_id: String,
+    pub stack_height: usize,
+    pub compute_units_consumed: u64,
+    pub accounts: Vec<RpcInvocationTraceAccount>,
+    pub return_data: Option<UiTransactionReturnData>,
+    pub err: Option<InstructionError>,
+    pub inner_invocations: Vec<RpcInvocationTrace>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcInvocationTraceAccount {
+    pub pubkey: String,
+    pub writable: bool,
+    pub pre_lamports: u64,
+    pub post_lamports: u64,
+    pub pre_owner: String,
+    pub post_owner: String,
+    pub pre_data_len: usize,
+    pub post_data_len: usize,
+    /// Modified byte ranges of the account data, as `[start, end)` pairs
+    pub data_diff: Vec<(usize, usize)>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSimulateBundleTransactionResult {
+    pub err: Option<UiTransactionError>,
+    pub logs: Option<Vec<String>>,
+    pub pre_execution_accounts: Option<Vec<Option<UiAccount>>>,
+    pub post_execution_accounts: Option<Vec<Option<UiAccount>>>,
+    pub units_consumed: Option<u64>,
+    pub loaded_accounts_data_size: Option<u32>,
+    pub return_data: Option<UiTransactionReturnData>,
+    pub inner_instructions: Option<Vec<UiInnerInstructions>>,
+    pub fee: Option<u64>,
+    pub pre_balances: Option<Vec<u64>>,
+    pub post_balances: Option<Vec<u64>>,
+    pub pre_token_balances: Option<Vec<UiTransactionTokenBalance>>,
+    pub post_token_balances: Option<Vec<UiTransactionTokenBalance>>,
+    pub loaded_addresses: Option<UiLoadedAddresses>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSimulateBundleResult {
+    pub transaction_results: Vec<RpcSimulateBundleTransactionResult>,
+    pub replacement_blockhash: Option<RpcBlockhash>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
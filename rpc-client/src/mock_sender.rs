@@ -247,6 +247,8 @@ impl RpcSender for MockSender {
                             return_data: OptionSerializer::Skip,
                             compute_units_consumed: OptionSerializer::Skip,
                             cost_units: OptionSerializer::Skip,
+                            log_events: OptionSerializer::Skip,
+                            syscall_compute_units: OptionSerializer::Skip,
                         }),
                 },
                 block_time: Some(1628633791),
@@ -362,6 +364,7 @@ impl RpcSender for MockSender {
                     pre_token_balances: None,
                     post_token_balances: None,
                     loaded_addresses: None,
+                    execution_trace: None,
                 }
             })?,
             "getMinimumBalanceForRentExemption" => json![20],
@@ -1400,6 +1400,41 @@ impl RpcClient {
         .await
     }
 
+    /// Simulates sending a bundle of transactions, in order.
+    ///
+    /// Every transaction is simulated against the state left by the previous
+    /// ones, which is not committed. The state of the accounts listed in the
+    /// [`accounts`] field of the [`RpcSimulateBundleConfig`] argument is
+    /// returned before and after every transaction.
+    ///
+    /// [`accounts`]: solana_rpc_client_api::config::RpcSimulateBundleConfig::accounts
+    ///
+    /// # RPC Reference
+    ///
+    /// This method is built on the `simulateBundle` RPC method.
+    pub async fn simulate_bundle_with_config(
+        &self,
+        transactions: &[impl SerializableTransaction],
+        config: RpcSimulateBundleConfig,
+    ) -> RpcResult<RpcSimulateBundleResult> {
+        let encoding = config.encoding.unwrap_or(UiTransactionEncoding::Base64);
+        let commitment = config.commitment.unwrap_or_default();
+        let config = RpcSimulateBundleConfig {
+            encoding: Some(encoding),
+            commitment: Some(commitment),
+            ..config
+        };
+        let serialized_encoded = transactions
+            .iter()
+            .map(|transaction| serialize_and_encode(transaction, encoding))
+            .collect::<ClientResult<Vec<_>>>()?;
+        self.send(
+            RpcRequest::SimulateBundle,
+            json!([serialized_encoded, config]),
+        )
+        .await
+    }
+
     /// Returns the highest slot information that the node has snapshots for.
     ///
     /// This will find the highest full snapshot slot, and the highest incremental snapshot slot
@@ -4130,13 +4165,14 @@ impl RpcClient {
             .unwrap_or_else(|| self.commitment());
         config.account_config.commitment = Some(commitment);
 
-        let accounts = self
-            .send::<OptionalContext<Vec<RpcKeyedAccount>>>(
+        let (accounts, _next_cursor) = self
+            .send::<OptionalContext<RpcProgramAccounts>>(
                 RpcRequest::GetProgramAccounts,
                 json!([pubkey.to_string(), config]),
             )
             .await?
-            .parse_value();
+            .parse_value()
+            .into_page();
         #[allow(deprecated)]
         parse_keyed_accounts(accounts, RpcRequest::GetProgramAccounts)
     }
@@ -4189,6 +4225,8 @@ impl RpcClient {
     ///     },
     ///     with_context: Some(false),
     ///     sort_results: Some(true),
+    ///     limit: None,
+    ///     cursor: None,
     /// };
     /// let ui_accounts = rpc_client.get_program_ui_accounts_with_config(
     ///     &alice.pubkey(),
@@ -4201,25 +4239,47 @@ impl RpcClient {
     pub async fn get_program_ui_accounts_with_config(
         &self,
         pubkey: &Pubkey,
-        mut config: RpcProgramAccountsConfig,
+        config: RpcProgramAccountsConfig,
     ) -> ClientResult<Vec<(Pubkey, UiAccount)>> {
+        let (accounts, _next_cursor) = self.get_program_ui_accounts_page(pubkey, config).await?;
+        Ok(accounts)
+    }
+
+    /// Returns one page of the accounts owned by the provided program pubkey.
+    ///
+    /// Set `limit` in the config to page through the accounts in pubkey order, and pass the
+    /// returned cursor back as `cursor` to fetch the following page. The cursor is `None` once
+    /// all accounts have been returned.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method is built on the [`getProgramAccounts`] RPC method.
+    ///
+    /// [`getProgramAccounts`]: https://solana.com/docs/rpc/http/getprogramaccounts
+    pub async fn get_program_ui_accounts_page(
+        &self,
+        pubkey: &Pubkey,
+        mut config: RpcProgramAccountsConfig,
+    ) -> ClientResult<(Vec<(Pubkey, UiAccount)>, Option<String>)> {
         let commitment = config
             .account_config
             .commitment
             .unwrap_or_else(|| self.commitment());
         config.account_config.commitment = Some(commitment);
 
-        let accounts = self
-            .send::<OptionalContext<Vec<RpcKeyedAccount>>>(
+        let (accounts, next_cursor) = self
+            .send::<OptionalContext<RpcProgramAccounts>>(
                 RpcRequest::GetProgramAccounts,
                 json!([pubkey.to_string(), config]),
             )
             .await?
-            .parse_value();
-        pubkey_ui_account_client_result_from_keyed_accounts(
+            .parse_value()
+            .into_page();
+        let accounts = pubkey_ui_account_client_result_from_keyed_accounts(
             accounts,
             RpcRequest::GetProgramAccounts,
-        )
+        )?;
+        Ok((accounts, next_cursor))
     }
 
     /// Returns the stake minimum delegation, in lamports.
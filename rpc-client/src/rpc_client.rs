@@ -1236,6 +1236,26 @@ impl RpcClient {
         )
     }
 
+    /// Simulates sending a bundle of transactions, in order.
+    ///
+    /// Every transaction is simulated against the state left by the previous
+    /// ones, which is not committed. The state of the accounts listed in the
+    /// [`accounts`] field of the [`RpcSimulateBundleConfig`] argument is
+    /// returned before and after every transaction.
+    ///
+    /// [`accounts`]: solana_rpc_client_api::config::RpcSimulateBundleConfig::accounts
+    ///
+    /// # RPC Reference
+    ///
+    /// This method is built on the `simulateBundle` RPC method.
+    pub fn simulate_bundle_with_config(
+        &self,
+        transactions: &[impl SerializableTransaction],
+        config: RpcSimulateBundleConfig,
+    ) -> RpcResult<RpcSimulateBundleResult> {
+        self.invoke((self.rpc_client.as_ref()).simulate_bundle_with_config(transactions, config))
+    }
+
     /// Returns the highest slot information that the node has snapshots for.
     ///
     /// This will find the highest full snapshot slot, and the highest incremental snapshot slot
@@ -3463,6 +3483,8 @@ impl RpcClient {
     ///     },
     ///     with_context: Some(false),
     ///     sort_results: Some(true),
+    ///     limit: None,
+    ///     cursor: None,
     /// };
     /// let ui_accounts = rpc_client.get_program_ui_accounts_with_config(
     ///     &alice.pubkey(),
@@ -3478,6 +3500,26 @@ impl RpcClient {
         self.invoke((self.rpc_client.as_ref()).get_program_ui_accounts_with_config(pubkey, config))
     }
 
+    /// Returns one page of the accounts owned by the provided program pubkey.
+    ///
+    /// Set `limit` in the config to page through the accounts in pubkey order, and pass the
+    /// returned cursor back as `cursor` to fetch the following page. The cursor is `None` once
+    /// all accounts have been returned.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method is built on the [`getProgramAccounts`] RPC method.
+    ///
+    /// [`getProgramAccounts`]: https://solana.com/docs/rpc/http/getprogramaccounts
+    #[allow(clippy::type_complexity)]
+    pub fn get_program_ui_accounts_page(
+        &self,
+        pubkey: &Pubkey,
+        config: RpcProgramAccountsConfig,
+    ) -> ClientResult<(Vec<(Pubkey, UiAccount)>, Option<String>)> {
+        self.invoke((self.rpc_client.as_ref()).get_program_ui_accounts_page(pubkey, config))
+    }
+
     /// Returns the stake minimum delegation, in lamports.
     ///
     /// # RPC Reference
@@ -4123,6 +4165,8 @@ mod tests {
                         },
                         with_context: None,
                         sort_results: None,
+                        limit: None,
+                        cursor: None,
                     },
                 )
                 .unwrap();
@@ -4159,6 +4203,8 @@ mod tests {
                         },
                         with_context: Some(true),
                         sort_results: None,
+                        limit: None,
+                        cursor: None,
                     },
                 )
                 .unwrap();
@@ -4245,6 +4291,8 @@ mod tests {
                         },
                         with_context: Some(true),
                         sort_results: None,
+                        limit: None,
+                        cursor: None,
                     },
                 )
                 .unwrap();
@@ -4265,6 +4313,8 @@ mod tests {
                         },
                         with_context: Some(true),
                         sort_results: None,
+                        limit: None,
+                        cursor: None,
                     },
                 )
                 .unwrap();
@@ -4285,6 +4335,8 @@ mod tests {
                         },
                         with_context: Some(true),
                         sort_results: None,
+                        limit: None,
+                        cursor: None,
                     },
                 )
                 .unwrap();
@@ -4305,6 +4357,8 @@ mod tests {
                         },
                         with_context: Some(true),
                         sort_results: None,
+                        limit: None,
+                        cursor: None,
                     },
                 )
                 .unwrap();
@@ -4357,6 +4411,8 @@ mod tests {
                         },
                         with_context: None,
                         sort_results: None,
+                        limit: None,
+                        cursor: None,
                     },
                 )
                 .unwrap();
@@ -4392,6 +4448,8 @@ mod tests {
                         },
                         with_context: Some(true),
                         sort_results: None,
+                        limit: None,
+                        cursor: None,
                     },
                 )
                 .unwrap();
@@ -4477,6 +4535,8 @@ mod tests {
                         },
                         with_context: Some(true),
                         sort_results: None,
+                        limit: None,
+                        cursor: None,
                     },
                 )
                 .unwrap();
@@ -4496,6 +4556,8 @@ mod tests {
                         },
                         with_context: Some(true),
                         sort_results: None,
+                        limit: None,
+                        cursor: None,
                     },
                 )
                 .unwrap();
@@ -4515,6 +4577,8 @@ mod tests {
                         },
                         with_context: Some(true),
                         sort_results: None,
+                        limit: None,
+                        cursor: None,
                     },
                 )
                 .unwrap();
@@ -4534,6 +4598,8 @@ mod tests {
                         },
                         with_context: Some(true),
                         sort_results: None,
+                        limit: None,
+                        cursor: None,
                     },
                 )
                 .unwrap();
@@ -4546,6 +4612,79 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_get_program_ui_accounts_page() {
+        let program_id = Pubkey::new_unique();
+        let pubkey = Pubkey::new_unique();
+        let account = UiAccount {
+            lamports: 1_000_000,
+            data: UiAccountData::Binary("".to_string(), UiAccountEncoding::Base64),
+            owner: program_id.to_string(),
+            executable: false,
+            rent_epoch: 0,
+            space: Some(0),
+        };
+        let page = RpcKeyedAccountsPage {
+            accounts: vec![RpcKeyedAccount {
+                pubkey: pubkey.to_string(),
+                account: account.clone(),
+            }],
+            next_cursor: Some("cursor".to_string()),
+        };
+        let config = RpcProgramAccountsConfig {
+            limit: Some(1),
+            ..RpcProgramAccountsConfig::default()
+        };
+
+        let mut mocks = MocksMap::default();
+        mocks.insert(
+            RpcRequest::GetProgramAccounts,
+            serde_json::to_value(OptionalContext::NoContext(RpcProgramAccounts::Page(
+                page.clone(),
+            )))
+            .unwrap(),
+        );
+        mocks.insert(
+            RpcRequest::GetProgramAccounts,
+            serde_json::to_value(OptionalContext::Context(Response {
+                context: RpcResponseContext {
+                    slot: 1,
+                    api_version: None,
+                },
+                value: RpcProgramAccounts::Page(RpcKeyedAccountsPage {
+                    next_cursor: None,
+                    ..page.clone()
+                }),
+            }))
+            .unwrap(),
+        );
+        mocks.insert(
+            RpcRequest::GetProgramAccounts,
+            serde_json::to_value(OptionalContext::NoContext(RpcProgramAccounts::Page(page)))
+                .unwrap(),
+        );
+        let rpc_client = RpcClient::new_mock_with_mocks_map("mock_client".to_string(), mocks);
+
+        // Pages are parsed with and without context
+        let result = rpc_client
+            .get_program_ui_accounts_page(&program_id, config.clone())
+            .unwrap();
+        assert_eq!(
+            result,
+            (vec![(pubkey, account.clone())], Some("cursor".to_string()))
+        );
+        let result = rpc_client
+            .get_program_ui_accounts_page(&program_id, config.clone())
+            .unwrap();
+        assert_eq!(result, (vec![(pubkey, account.clone())], None));
+
+        // Paged responses are accepted by the unpaged method too
+        let result = rpc_client
+            .get_program_ui_accounts_with_config(&program_id, config)
+            .unwrap();
+        assert_eq!(result, vec![(pubkey, account)]);
+    }
+
     #[test_case(LegacyMessage {
         header: MessageHeader {
             num_required_signatures: 1,
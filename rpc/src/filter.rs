@@ -1,6 +1,6 @@
 use {
     solana_account::{AccountSharedData, ReadableAccount},
-    solana_rpc_client_api::filter::RpcFilterType,
+    solana_rpc_client_api::filter::{DataRange, RpcFilterType, StringU128},
     spl_generic_token::{token::GenericTokenAccount, token_2022::Account},
 };
 
@@ -9,5 +9,145 @@ pub fn filter_allows(filter: &RpcFilterType, account: &AccountSharedData) -> boo
         RpcFilterType::DataSize(size) => account.data().len() as u64 == *size,
         RpcFilterType::Memcmp(compare) => compare.bytes_match(account.data()),
         RpcFilterType::TokenAccountState => Account::valid_account_data(account.data()),
+        RpcFilterType::DataSizeRange(range) => range.contains(&(account.data().len() as u64)),
+        RpcFilterType::Lamports(range) => range.contains(&account.lamports()),
+        RpcFilterType::U64Range(range) => {
+            data_range_allows(range, account.data(), u64::from_le_bytes)
+        }
+        RpcFilterType::I64Range(range) => {
+            data_range_allows(range, account.data(), i64::from_le_bytes)
+        }
+        RpcFilterType::U128Range(range) => data_range_allows(range, account.data(), |bytes| {
+            StringU128(u128::from_le_bytes(bytes))
+        }),
+        RpcFilterType::Or(filters) => filters.iter().any(|filter| filter_allows(filter, account)),
+        RpcFilterType::Not(filter) => !filter_allows(filter, account),
+    }
+}
+
+/// Reads the integer at `range.offset` of `data` with `from_le_bytes`, and checks it is within
+/// `range`
+///
+/// Accounts whose data is too short to hold the integer are not allowed.
+fn data_range_allows<T: PartialOrd, const N: usize>(
+    range: &DataRange<T>,
+    data: &[u8],
+    from_le_bytes: impl FnOnce([u8; N]) -> T,
+) -> bool {
+    range
+        .offset
+        .checked_add(N)
+        .and_then(|end| data.get(range.offset..end))
+        .and_then(|bytes| <[u8; N]>::try_from(bytes).ok())
+        .is_some_and(|bytes| range.range.contains(&from_le_bytes(bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_pubkey::Pubkey,
+        solana_rpc_client_api::filter::{Memcmp, ValueRange},
+    };
+
+    fn account_with_data(lamports: u64, data: Vec<u8>) -> AccountSharedData {
+        AccountSharedData::create(lamports, data, Pubkey::new_unique(), false, 0)
+    }
+
+    #[test]
+    fn test_size_and_lamports_ranges() {
+        let account = account_with_data(100, vec![0; 10]);
+        let allows = |filter| filter_allows(&filter, &account);
+
+        assert!(allows(RpcFilterType::DataSizeRange(ValueRange::new(
+            Some(10),
+            Some(10)
+        ))));
+        assert!(allows(RpcFilterType::DataSizeRange(ValueRange::new(
+            None,
+            Some(20)
+        ))));
+        assert!(!allows(RpcFilterType::DataSizeRange(ValueRange::new(
+            Some(11),
+            None
+        ))));
+        assert!(allows(RpcFilterType::Lamports(ValueRange::new(
+            Some(100),
+            None
+        ))));
+        assert!(!allows(RpcFilterType::Lamports(ValueRange::new(
+            None,
+            Some(99)
+        ))));
+    }
+
+    #[test]
+    fn test_integer_ranges() {
+        let mut data = vec![0xff; 4];
+        data.extend_from_slice(&1_000u64.to_le_bytes());
+        data.extend_from_slice(&(-1_000i64).to_le_bytes());
+        data.extend_from_slice(&(u128::MAX - 1).to_le_bytes());
+        let account = account_with_data(1, data);
+        let allows = |filter| filter_allows(&filter, &account);
+
+        assert!(allows(RpcFilterType::U64Range(DataRange::new(
+            4,
+            Some(1_000),
+            Some(1_000)
+        ))));
+        assert!(!allows(RpcFilterType::U64Range(DataRange::new(
+            4,
+            Some(1_001),
+            None
+        ))));
+        assert!(allows(RpcFilterType::I64Range(DataRange::new(
+            12,
+            Some(-1_000),
+            Some(0)
+        ))));
+        assert!(!allows(RpcFilterType::I64Range(DataRange::new(
+            12,
+            Some(-999),
+            None
+        ))));
+        assert!(allows(RpcFilterType::U128Range(DataRange::new(
+            20,
+            Some(StringU128(u128::MAX - 1)),
+            None
+        ))));
+        assert!(!allows(RpcFilterType::U128Range(DataRange::new(
+            20,
+            None,
+            Some(StringU128(u128::MAX - 2))
+        ))));
+
+        // integers past the end of the data never match
+        assert!(!allows(RpcFilterType::U64Range(DataRange::new(
+            30, None, None
+        ))));
+        assert!(!allows(RpcFilterType::U64Range(DataRange::new(
+            usize::MAX,
+            None,
+            None
+        ))));
+    }
+
+    #[test]
+    fn test_or_and_not() {
+        let account = account_with_data(5, vec![1, 2, 3]);
+        let allows = |filter| filter_allows(&filter, &account);
+        let matching = || RpcFilterType::Memcmp(Memcmp::new_raw_bytes(1, vec![2, 3]));
+        let not_matching = || RpcFilterType::DataSize(4);
+
+        assert!(allows(RpcFilterType::Or(vec![not_matching(), matching()])));
+        assert!(!allows(RpcFilterType::Or(vec![
+            not_matching(),
+            not_matching()
+        ])));
+        assert!(allows(RpcFilterType::Not(Box::new(not_matching()))));
+        assert!(!allows(RpcFilterType::Not(Box::new(matching()))));
+        assert!(allows(RpcFilterType::Not(Box::new(RpcFilterType::Or(
+            vec![not_matching()]
+        )))));
     }
 }
@@ -16,7 +16,7 @@ use {
         BoxFuture, Error, Metadata, Result,
     },
     jsonrpc_derive::rpc,
-    solana_account::{AccountSharedData, ReadableAccount},
+    solana_account::{AccountSharedData, ReadableAccount, WritableAccount},
     solana_account_decoder::{
         encode_ui_account,
         parse_account_data::SplTokenAdditionalDataV2,
@@ -49,6 +49,7 @@ use {
     solana_metrics::inc_new_counter_info,
     solana_perf::packet::PACKET_DATA_SIZE,
     solana_program_pack::Pack,
+    solana_program_runtime::execution_trace::InvocationTrace,
     solana_pubkey::{Pubkey, PUBKEY_BYTES},
     solana_rpc_client_api::{
         config::*,
@@ -57,14 +58,18 @@ use {
         request::{
             TokenAccountsFilter, DELINQUENT_VALIDATOR_SLOT_DISTANCE,
             MAX_GET_CONFIRMED_BLOCKS_RANGE, MAX_GET_CONFIRMED_SIGNATURES_FOR_ADDRESS2_LIMIT,
-            MAX_GET_PROGRAM_ACCOUNT_FILTERS, MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS,
-            MAX_GET_SLOT_LEADERS, MAX_MULTIPLE_ACCOUNTS,
-            MAX_RPC_VOTE_ACCOUNT_INFO_EPOCH_CREDITS_HISTORY, NUM_LARGEST_ACCOUNTS,
+            MAX_GET_PROGRAM_ACCOUNTS_LIMIT, MAX_GET_PROGRAM_ACCOUNT_FILTERS,
+            MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS, MAX_GET_SLOT_LEADERS, MAX_MULTIPLE_ACCOUNTS,
+            MAX_RPC_VOTE_ACCOUNT_INFO_EPOCH_CREDITS_HISTORY, MAX_SIMULATE_BUNDLE_TRANSACTIONS,
+            NUM_LARGEST_ACCOUNTS,
         },
         response::{Response as RpcResponse, *},
     },
     solana_runtime::{
-        bank::{Bank, TransactionSimulationResult},
+        bank::{
+            Bank, BundleTransactionSimulationResult, TransactionSimulationConfig,
+            TransactionSimulationResult,
+        },
         bank_forks::BankForks,
         commitment::{BlockCommitmentArray, BlockCommitmentCache},
         non_circulating_supply::{calculate_non_circulating_supply, NonCirculatingSupply},
@@ -77,7 +82,7 @@ use {
     solana_signature::Signature,
     solana_signer::Signer,
     solana_stake_program,
-    solana_storage_bigtable::Error as StorageError,
+    solana_storage_bigtable::{Error as StorageError, LongTermStorage},
     solana_transaction::{
         sanitized::{MessageHash, SanitizedTransaction, MAX_TX_ACCOUNT_LOCKS},
         versioned::VersionedTransaction,
@@ -110,6 +115,8 @@ use {
         collections::{BinaryHeap, HashMap, HashSet},
         convert::TryFrom,
         net::SocketAddr,
+        path::PathBuf,
+        slice,
         str::FromStr,
         sync::{
             atomic::{AtomicBool, AtomicU64, Ordering},
@@ -163,6 +170,8 @@ fn is_finalized(
 pub struct JsonRpcConfig {
     pub enable_rpc_transaction_history: bool,
     pub enable_extended_tx_metadata_storage: bool,
+    /// Record typed program log events alongside the logs of the transactions
+    pub enable_log_event_recording: bool,
     pub faucet_addr: Option<SocketAddr>,
     pub health_check_slot_distance: u64,
     pub skip_preflight_health_check: bool,
@@ -184,6 +193,7 @@ impl Default for JsonRpcConfig {
         Self {
             enable_rpc_transaction_history: Default::default(),
             enable_extended_tx_metadata_storage: Default::default(),
+            enable_log_event_recording: Default::default(),
             faucet_addr: Option::default(),
             health_check_slot_distance: Default::default(),
             skip_preflight_health_check: bool::default(),
@@ -218,6 +228,8 @@ pub struct RpcBigtableConfig {
     pub bigtable_app_profile_id: String,
     pub timeout: Option<Duration>,
     pub max_message_size: usize,
+    /// Keep the long-term ledger storage in this local directory instead of BigTable
+    pub local_ledger_storage_path: Option<PathBuf>,
 }
 
 impl Default for RpcBigtableConfig {
@@ -230,6 +242,7 @@ impl Default for RpcBigtableConfig {
             bigtable_app_profile_id,
             timeout: None,
             max_message_size: solana_storage_bigtable::DEFAULT_MAX_MESSAGE_SIZE,
+            local_ledger_storage_path: None,
         }
     }
 }
@@ -247,7 +260,7 @@ pub struct JsonRpcRequestProcessor {
     cluster_info: Arc<ClusterInfo>,
     genesis_hash: Hash,
     transaction_sender: Sender<TransactionInfo>,
-    bigtable_ledger_storage: Option<solana_storage_bigtable::LedgerStorage>,
+    bigtable_ledger_storage: Option<Arc<dyn LongTermStorage>>,
     optimistically_confirmed_bank: Arc<RwLock<OptimisticallyConfirmedBank>>,
     largest_accounts_cache: Arc<RwLock<LargestAccountsCache>>,
     max_slots: Arc<MaxSlots>,
@@ -310,18 +323,16 @@ impl JsonRpcRequestProcessor {
         program_id: &Pubkey,
         filters: Vec<RpcFilterType>,
         sort_results: bool,
+        page: Option<AccountsPage>,
     ) -> ScanResult<Vec<KeyedAccountSharedData>> {
-        let scan_order = if sort_results {
-            ScanOrder::Sorted
-        } else {
-            ScanOrder::Unsorted
-        };
+        let config = accounts_scan_config(sort_results, page);
+        let limit = page.map_or(usize::MAX, |page| page.limit);
         let bank = Arc::clone(bank);
         let index_key = index_key.to_owned();
         let program_id = program_id.to_owned();
         self.runtime
             .spawn_blocking(move || {
-                bank.get_filtered_indexed_accounts(
+                bank.get_filtered_indexed_accounts_page(
                     &index_key,
                     |account| {
                         // The program-id account index checks for Account owner on inclusion.
@@ -334,7 +345,8 @@ impl JsonRpcRequestProcessor {
                                 .iter()
                                 .all(|filter_type| filter_allows(filter_type, account))
                     },
-                    &ScanConfig::new(scan_order),
+                    limit,
+                    &config,
                     bank.byte_limit_for_scans(),
                 )
             })
@@ -410,7 +422,7 @@ impl JsonRpcRequestProcessor {
         health: Arc<RpcHealth>,
         cluster_info: Arc<ClusterInfo>,
         genesis_hash: Hash,
-        bigtable_ledger_storage: Option<solana_storage_bigtable::LedgerStorage>,
+        bigtable_ledger_storage: Option<Arc<dyn LongTermStorage>>,
         optimistically_confirmed_bank: Arc<RwLock<OptimisticallyConfirmedBank>>,
         largest_accounts_cache: Arc<RwLock<LargestAccountsCache>>,
         max_slots: Arc<MaxSlots>,
@@ -604,17 +616,34 @@ impl JsonRpcRequestProcessor {
         mut filters: Vec<RpcFilterType>,
         with_context: bool,
         sort_results: bool,
-    ) -> Result<OptionalContext<Vec<RpcKeyedAccount>>> {
+        limit: Option<usize>,
+        cursor: Option<String>,
+    ) -> Result<OptionalContext<RpcProgramAccounts>> {
         let RpcAccountInfoConfig {
             encoding,
             data_slice: data_slice_config,
             commitment,
             min_context_slot,
         } = config.unwrap_or_default();
-        let bank = self.get_bank_with_config(RpcContextConfig {
-            commitment,
-            min_context_slot,
-        })?;
+        let cursor = verify_program_accounts_page(limit, cursor.as_deref())?;
+        let bank = match cursor {
+            // Later pages are loaded from the bank of the first one, so that all pages are
+            // consistent for as long as that bank is available
+            Some(cursor) => self
+                .bank_forks
+                .read()
+                .unwrap()
+                .get(cursor.slot)
+                .ok_or(RpcCustomError::CursorExpired { slot: cursor.slot })?,
+            None => self.get_bank_with_config(RpcContextConfig {
+                commitment,
+                min_context_slot,
+            })?,
+        };
+        let page = limit.map(|limit| AccountsPage {
+            start_after: cursor.map(|cursor| cursor.last_pubkey),
+            limit,
+        });
         let encoding = encoding.unwrap_or(UiAccountEncoding::Binary);
         optimize_filters(&mut filters);
         let keyed_accounts = {
@@ -625,6 +654,7 @@ impl JsonRpcRequestProcessor {
                     owner,
                     filters,
                     sort_results,
+                    page,
                 )
                 .await?
             } else if let Some(mint) = get_spl_token_mint_filter(&program_id, &filters) {
@@ -634,6 +664,7 @@ impl JsonRpcRequestProcessor {
                     mint,
                     filters,
                     sort_results,
+                    page,
                 )
                 .await?
             } else {
@@ -642,10 +673,22 @@ impl JsonRpcRequestProcessor {
                     program_id,
                     filters,
                     sort_results,
+                    page,
                 )
                 .await?
             }
         };
+        // Only a full page can be followed by more accounts
+        let next_cursor = page
+            .filter(|page| keyed_accounts.len() >= page.limit)
+            .and_then(|_| keyed_accounts.last())
+            .map(|(last_pubkey, _)| {
+                ProgramAccountsCursor {
+                    slot: bank.slot(),
+                    last_pubkey: *last_pubkey,
+                }
+                .encode()
+            });
         let accounts = if is_known_spl_token_id(&program_id)
             && encoding == UiAccountEncoding::JsonParsed
         {
@@ -661,6 +704,13 @@ impl JsonRpcRequestProcessor {
                 })
                 .collect::<Result<Vec<_>>>()?
         };
+        let accounts = match page {
+            Some(_) => RpcProgramAccounts::Page(RpcKeyedAccountsPage {
+                accounts,
+                next_cursor,
+            }),
+            None => RpcProgramAccounts::Accounts(accounts),
+        };
         Ok(match with_context {
             true => OptionalContext::Context(new_response(&bank, accounts)),
             false => OptionalContext::NoContext(accounts),
@@ -2042,6 +2092,7 @@ impl JsonRpcRequestProcessor {
                 mint,
                 vec![],
                 true,
+                None,
             )
             .await?
         {
@@ -2112,6 +2163,7 @@ impl JsonRpcRequestProcessor {
                 owner,
                 filters,
                 sort_results,
+                None,
             )
             .await?;
         let accounts = if encoding == UiAccountEncoding::JsonParsed {
@@ -2167,6 +2219,7 @@ impl JsonRpcRequestProcessor {
                 mint,
                 filters,
                 sort_results,
+                None,
             )
             .await?
         } else {
@@ -2177,6 +2230,7 @@ impl JsonRpcRequestProcessor {
                 token_program_id,
                 filters,
                 sort_results,
+                None,
             )
             .await?
         };
@@ -2203,8 +2257,30 @@ impl JsonRpcRequestProcessor {
         program_id: Pubkey,
         mut filters: Vec<RpcFilterType>,
         sort_results: bool,
+        page: Option<AccountsPage>,
     ) -> RpcCustomResult<Vec<(Pubkey, AccountSharedData)>> {
         optimize_filters(&mut filters);
+        // A data slice index only holds the accounts matching one of the filters, so prefer it
+        // over the program-id index
+        if let Some(index_key) =
+            data_slice_index_key(&self.config.account_indexes, &program_id, &filters)
+        {
+            if self.config.account_indexes.include_key(&program_id) {
+                return self
+                    .get_filtered_indexed_accounts(
+                        &bank,
+                        &index_key,
+                        &program_id,
+                        filters,
+                        sort_results,
+                        page,
+                    )
+                    .await
+                    .map_err(|e| RpcCustomError::ScanError {
+                        message: e.to_string(),
+                    });
+            }
+        }
         if self
             .config
             .account_indexes
@@ -2221,6 +2297,7 @@ impl JsonRpcRequestProcessor {
                 &program_id,
                 filters,
                 sort_results,
+                page,
             )
             .await
             .map_err(|e| RpcCustomError::ScanError {
@@ -2228,21 +2305,19 @@ impl JsonRpcRequestProcessor {
             })
         } else {
             // this path does not need to provide a mb limit because we only want to support secondary indexes
-            let scan_order = if sort_results {
-                ScanOrder::Sorted
-            } else {
-                ScanOrder::Unsorted
-            };
+            let config = accounts_scan_config(sort_results, page);
+            let limit = page.map_or(usize::MAX, |page| page.limit);
             self.runtime
                 .spawn_blocking(move || {
-                    bank.get_filtered_program_accounts(
+                    bank.get_filtered_program_accounts_page(
                         &program_id,
                         |account: &AccountSharedData| {
                             filters
                                 .iter()
                                 .all(|filter_type| filter_allows(filter_type, account))
                         },
-                        &ScanConfig::new(scan_order),
+                        limit,
+                        &config,
                     )
                     .map_err(|e| RpcCustomError::ScanError {
                         message: e.to_string(),
@@ -2261,6 +2336,7 @@ impl JsonRpcRequestProcessor {
         owner_key: Pubkey,
         mut filters: Vec<RpcFilterType>,
         sort_results: bool,
+        page: Option<AccountsPage>,
     ) -> RpcCustomResult<Vec<(Pubkey, AccountSharedData)>> {
         // The by-owner accounts index checks for Token Account state and Owner address on
         // inclusion. However, due to the current AccountsDb implementation, an account may remain
@@ -2291,13 +2367,14 @@ impl JsonRpcRequestProcessor {
                 &program_id,
                 filters,
                 sort_results,
+                page,
             )
             .await
             .map_err(|e| RpcCustomError::ScanError {
                 message: e.to_string(),
             })
         } else {
-            self.get_filtered_program_accounts(bank, program_id, filters, sort_results)
+            self.get_filtered_program_accounts(bank, program_id, filters, sort_results, page)
                 .await
         }
     }
@@ -2310,6 +2387,7 @@ impl JsonRpcRequestProcessor {
         mint_key: Pubkey,
         mut filters: Vec<RpcFilterType>,
         sort_results: bool,
+        page: Option<AccountsPage>,
     ) -> RpcCustomResult<Vec<(Pubkey, AccountSharedData)>> {
         // The by-mint accounts index checks for Token Account state and Mint address on inclusion.
         // However, due to the current AccountsDb implementation, an account may remain in storage
@@ -2339,13 +2417,14 @@ impl JsonRpcRequestProcessor {
                 &program_id,
                 filters,
                 sort_results,
+                page,
             )
             .await
             .map_err(|e| RpcCustomError::ScanError {
                 message: e.to_string(),
             })
         } else {
-            self.get_filtered_program_accounts(bank, program_id, filters, sort_results)
+            self.get_filtered_program_accounts(bank, program_id, filters, sort_results, page)
                 .await
         }
     }
@@ -2401,14 +2480,127 @@ impl JsonRpcRequestProcessor {
 }
 
 pub(crate) fn optimize_filters(filters: &mut [RpcFilterType]) {
-    filters.iter_mut().for_each(|filter_type| {
-        if let RpcFilterType::Memcmp(compare) = filter_type {
-            if let Err(err) = compare.convert_to_raw_bytes() {
-                // All filters should have been previously verified
-                warn!("Invalid filter: bytes could not be decoded, {err}");
+    for filter_type in filters {
+        match filter_type {
+            RpcFilterType::Memcmp(compare) => {
+                if let Err(err) = compare.convert_to_raw_bytes() {
+                    // All filters should have been previously verified
+                    warn!("Invalid filter: bytes could not be decoded, {err}");
+                }
             }
+            RpcFilterType::Or(filters) => optimize_filters(filters),
+            RpcFilterType::Not(filter) => optimize_filters(slice::from_mut(filter.as_mut())),
+            _ => {}
         }
-    })
+    }
+}
+
+/// Returns the key to scan a data slice index of `program_id` with, if there is one covering
+/// a memcmp filter in `filters`
+///
+/// Filters covering all the indexed bytes are preferred, since they are exact lookups.
+/// Expects `filters` to have been optimized with `optimize_filters()`.
+fn data_slice_index_key(
+    account_indexes: &AccountSecondaryIndexes,
+    program_id: &Pubkey,
+    filters: &[RpcFilterType],
+) -> Option<IndexKey> {
+    filters
+        .iter()
+        .filter_map(|filter| match filter {
+            RpcFilterType::Memcmp(memcmp) => Some((memcmp.offset(), memcmp.raw_bytes_as_ref()?)),
+            _ => None,
+        })
+        .flat_map(|(offset, bytes)| {
+            account_indexes
+                .indexes
+                .iter()
+                .filter_map(move |account_index| match account_index {
+                    AccountIndex::DataSlice(index) if index.program_id == *program_id => {
+                        let range = index.covered_range(offset, bytes.len())?;
+                        Some((range.len() == index.length, index, range.start, bytes))
+                    }
+                    _ => None,
+                })
+        })
+        .max_by_key(|(is_exact, ..)| *is_exact)
+        .map(|(_, index, offset, bytes)| IndexKey::DataSlice {
+            index: index.clone(),
+            offset,
+            bytes: bytes.to_vec(),
+        })
+}
+
+/// A page of accounts to load, in pubkey order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccountsPage {
+    /// Only load accounts whose pubkey is greater than this
+    pub start_after: Option<Pubkey>,
+    /// Stop loading once this many accounts have been found
+    pub limit: usize,
+}
+
+fn accounts_scan_config(sort_results: bool, page: Option<AccountsPage>) -> ScanConfig {
+    // Pages can only be resumed when accounts are visited in pubkey order
+    let scan_order = if sort_results || page.is_some() {
+        ScanOrder::Sorted
+    } else {
+        ScanOrder::Unsorted
+    };
+    ScanConfig {
+        start_after: page.and_then(|page| page.start_after),
+        ..ScanConfig::new(scan_order)
+    }
+}
+
+/// Where a paged `getProgramAccounts` request resumes
+///
+/// Clients treat the encoded cursor as opaque.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ProgramAccountsCursor {
+    /// The slot of the bank all pages are loaded from
+    slot: Slot,
+    /// The last pubkey returned so far
+    last_pubkey: Pubkey,
+}
+
+impl ProgramAccountsCursor {
+    fn encode(&self) -> String {
+        let mut bytes = Vec::with_capacity(std::mem::size_of::<Slot>() + PUBKEY_BYTES);
+        bytes.extend_from_slice(&self.slot.to_le_bytes());
+        bytes.extend_from_slice(self.last_pubkey.as_ref());
+        bs58::encode(bytes).into_string()
+    }
+
+    fn decode(cursor: &str) -> Result<Self> {
+        let invalid_cursor = || Error::invalid_params("Invalid param: invalid cursor");
+        let bytes = bs58::decode(cursor)
+            .into_vec()
+            .map_err(|_| invalid_cursor())?;
+        let (slot, last_pubkey) = bytes.split_first_chunk().ok_or_else(invalid_cursor)?;
+        Ok(Self {
+            slot: Slot::from_le_bytes(*slot),
+            last_pubkey: Pubkey::try_from(last_pubkey).map_err(|_| invalid_cursor())?,
+        })
+    }
+}
+
+fn verify_program_accounts_page(
+    limit: Option<usize>,
+    cursor: Option<&str>,
+) -> Result<Option<ProgramAccountsCursor>> {
+    match limit {
+        Some(limit) if limit == 0 || limit > MAX_GET_PROGRAM_ACCOUNTS_LIMIT => {
+            Err(Error::invalid_params(format!(
+                "Invalid limit; max {MAX_GET_PROGRAM_ACCOUNTS_LIMIT}"
+            )))
+        }
+        Some(_) => cursor.map(ProgramAccountsCursor::decode).transpose(),
+        None if cursor.is_some() => Err(Error::invalid_params(
+            "Invalid param: cursor requires a limit",
+        )),
+        None => Ok(None),
+    }
 }
 
 pub(crate) fn verify_filters(filters: &[RpcFilterType]) -> Result<()> {
@@ -2435,6 +2627,56 @@ pub fn verify_pubkey(input: &str) -> Result<Pubkey> {
         .map_err(|e| Error::invalid_params(format!("Invalid param: {e:?}")))
 }
 
+fn verify_account_overrides(
+    bank: &Bank,
+    account_overrides: Vec<RpcAccountOverride>,
+) -> Result<HashMap<Pubkey, AccountSharedData>> {
+    if account_overrides.len() > MAX_MULTIPLE_ACCOUNTS {
+        return Err(Error::invalid_params(format!(
+            "Too many account overrides provided; max {MAX_MULTIPLE_ACCOUNTS}"
+        )));
+    }
+    let mut accounts = HashMap::with_capacity(account_overrides.len());
+    for RpcAccountOverride {
+        address,
+        lamports,
+        owner,
+        data,
+        executable,
+    } in account_overrides
+    {
+        let pubkey = verify_pubkey(&address)?;
+        let mut account = bank.get_account(&pubkey).unwrap_or_default();
+        if let Some(lamports) = lamports {
+            account.set_lamports(lamports);
+        }
+        if let Some(owner) = owner {
+            account.set_owner(verify_pubkey(&owner)?);
+        }
+        if let Some(data) = data {
+            let data = BASE64_STANDARD
+                .decode(data)
+                .map_err(|e| Error::invalid_params(format!("invalid base64 encoding: {e:?}")))?;
+            if data.len() as u64 > solana_system_interface::MAX_PERMITTED_DATA_LENGTH {
+                return Err(Error::invalid_params(format!(
+                    "Invalid account override data length for {pubkey}; max {}",
+                    solana_system_interface::MAX_PERMITTED_DATA_LENGTH
+                )));
+            }
+            account.set_data(data);
+        }
+        if let Some(executable) = executable {
+            account.set_executable(executable);
+        }
+        if accounts.insert(pubkey, account).is_some() {
+            return Err(Error::invalid_params(format!(
+                "Duplicate account override: {pubkey}"
+            )));
+        }
+    }
+    Ok(accounts)
+}
+
 fn verify_hash(input: &str) -> Result<Hash> {
     input
         .parse()
@@ -2515,6 +2757,83 @@ fn get_encoded_account(
     }
 }
 
+/// Encodes the state of the `addresses` at some point of a simulation
+fn encode_simulated_accounts(
+    bank: &Bank,
+    addresses: &[Pubkey],
+    accounts: Vec<Option<AccountSharedData>>,
+    encoding: UiAccountEncoding,
+) -> Result<Vec<Option<UiAccount>>> {
+    let simulated_accounts: HashMap<_, _> = addresses
+        .iter()
+        .zip(&accounts)
+        .filter_map(|(pubkey, account)| Some((*pubkey, account.clone()?)))
+        .collect();
+    addresses
+        .iter()
+        .zip(accounts)
+        .map(|(pubkey, account)| {
+            account
+                .map(|account| {
+                    if is_known_spl_token_id(account.owner())
+                        && encoding == UiAccountEncoding::JsonParsed
+                    {
+                        Ok(get_parsed_token_account(
+                            bank,
+                            pubkey,
+                            account,
+                            Some(&simulated_accounts),
+                        ))
+                    } else {
+                        encode_account(&account, pubkey, encoding, None)
+                    }
+                })
+                .transpose()
+        })
+        .collect()
+}
+
+fn rpc_invocation_trace(invocation: InvocationTrace) -> RpcInvocationTrace {
+    let InvocationTrace {
+        program_id,
+        stack_height,
+        compute_units_consumed,
+        accounts,
+        return_data,
+        result,
+        inner_invocations,
+    } = invocation;
+    RpcInvocationTrace {
+        program_id: program_id.to_string(),
+        stack_height,
+        compute_units_consumed,
+        accounts: accounts
+            .into_iter()
+            .map(|account| RpcInvocationTraceAccount {
+                pubkey: account.pubkey.to_string(),
+                writable: account.is_writable,
+                pre_lamports: account.pre_lamports,
+                post_lamports: account.post_lamports,
+                pre_owner: account.pre_owner.to_string(),
+                post_owner: account.post_owner.to_string(),
+                pre_data_len: account.pre_data_len,
+                post_data_len: account.post_data_len,
+                data_diff: account
+                    .data_diff
+                    .into_iter()
+                    .map(|range| (range.start, range.end))
+                    .collect(),
+            })
+            .collect(),
+        return_data: return_data.map(|return_data| return_data.into()),
+        err: result.err(),
+        inner_invocations: inner_invocations
+            .into_iter()
+            .map(rpc_invocation_trace)
+            .collect(),
+    }
+}
+
 fn encode_account<T: ReadableAccount>(
     account: &T,
     pubkey: &Pubkey,
@@ -2575,6 +2894,8 @@ fn get_spl_token_owner_filter(program_id: &Pubkey, filters: &[RpcFilterType]) ->
                 }
             }
             RpcFilterType::TokenAccountState => token_account_state_filter = true,
+            // Only the filters above are used to select the index
+            _ => {}
         }
     }
     if data_size_filter == Some(account_packed_len as u64)
@@ -2625,6 +2946,8 @@ fn get_spl_token_mint_filter(program_id: &Pubkey, filters: &[RpcFilterType]) ->
                 }
             }
             RpcFilterType::TokenAccountState => token_account_state_filter = true,
+            // Only the filters above are used to select the index
+            _ => {}
         }
     }
     if data_size_filter == Some(account_packed_len as u64)
@@ -3292,7 +3615,7 @@ pub mod rpc_accounts_scan {
             meta: Self::Metadata,
             program_id_str: String,
             config: Option<RpcProgramAccountsConfig>,
-        ) -> BoxFuture<Result<OptionalContext<Vec<RpcKeyedAccount>>>>;
+        ) -> BoxFuture<Result<OptionalContext<RpcProgramAccounts>>>;
 
         #[rpc(meta, name = "getLargestAccounts")]
         fn get_largest_accounts(
@@ -3348,23 +3671,34 @@ pub mod rpc_accounts_scan {
             meta: Self::Metadata,
             program_id_str: String,
             config: Option<RpcProgramAccountsConfig>,
-        ) -> BoxFuture<Result<OptionalContext<Vec<RpcKeyedAccount>>>> {
+        ) -> BoxFuture<Result<OptionalContext<RpcProgramAccounts>>> {
             debug!("get_program_accounts rpc request received: {program_id_str:?}");
             async move {
                 let program_id = verify_pubkey(&program_id_str)?;
-                let (config, filters, with_context, sort_results) = if let Some(config) = config {
-                    (
-                        Some(config.account_config),
-                        config.filters.unwrap_or_default(),
-                        config.with_context.unwrap_or_default(),
-                        config.sort_results.unwrap_or(true),
-                    )
-                } else {
-                    (None, vec![], false, true)
-                };
+                let (config, filters, with_context, sort_results, limit, cursor) =
+                    if let Some(config) = config {
+                        (
+                            Some(config.account_config),
+                            config.filters.unwrap_or_default(),
+                            config.with_context.unwrap_or_default(),
+                            config.sort_results.unwrap_or(true),
+                            config.limit,
+                            config.cursor,
+                        )
+                    } else {
+                        (None, vec![], false, true, None, None)
+                    };
                 verify_filters(&filters)?;
-                meta.get_program_accounts(program_id, config, filters, with_context, sort_results)
-                    .await
+                meta.get_program_accounts(
+                    program_id,
+                    config,
+                    filters,
+                    with_context,
+                    sort_results,
+                    limit,
+                    cursor,
+                )
+                .await
             }
             .boxed()
         }
@@ -3506,6 +3840,14 @@ pub mod rpc_full {
             config: Option<RpcSimulateTransactionConfig>,
         ) -> Result<RpcResponse<RpcSimulateTransactionResult>>;
 
+        #[rpc(meta, name = "simulateBundle")]
+        fn simulate_bundle(
+            &self,
+            meta: Self::Metadata,
+            data: Vec<String>,
+            config: Option<RpcSimulateBundleConfig>,
+        ) -> Result<RpcResponse<RpcSimulateBundleResult>>;
+
         #[rpc(meta, name = "minimumLedgerSlot")]
         fn minimum_ledger_slot(&self, meta: Self::Metadata) -> Result<Slot>;
 
@@ -3894,6 +4236,7 @@ pub mod rpc_full {
                     post_balances: _,
                     pre_token_balances: _,
                     post_token_balances: _,
+                    execution_trace: _,
                 } = simulation_result
                 {
                     match err {
@@ -3921,6 +4264,7 @@ pub mod rpc_full {
                             pre_token_balances: None,
                             post_token_balances: None,
                             loaded_addresses: None,
+                            execution_trace: None,
                         },
                     }
                     .into());
@@ -3954,6 +4298,8 @@ pub mod rpc_full {
                 accounts: config_accounts,
                 min_context_slot,
                 inner_instructions: enable_cpi_recording,
+                account_overrides,
+                execution_trace: enable_execution_trace,
             } = config.unwrap_or_default();
             let tx_encoding = encoding.unwrap_or(UiTransactionEncoding::Base58);
             let binary_encoding = tx_encoding.into_binary_encoding().ok_or_else(|| {
@@ -3968,6 +4314,10 @@ pub mod rpc_full {
                 commitment,
                 min_context_slot,
             })?;
+            let account_overrides = account_overrides
+                .map(|account_overrides| verify_account_overrides(bank, account_overrides))
+                .transpose()?
+                .unwrap_or_default();
             let mut blockhash: Option<RpcBlockhash> = None;
             if replace_recent_blockhash {
                 if sig_verify {
@@ -4005,7 +4355,14 @@ pub mod rpc_full {
             let simulation_result = if let Some(err) = verification_error {
                 TransactionSimulationResult::new_error(err)
             } else {
-                bank.simulate_transaction(&transaction, enable_cpi_recording)
+                bank.simulate_transaction_with_config(
+                    &transaction,
+                    TransactionSimulationConfig {
+                        enable_cpi_recording,
+                        account_overrides: Some(&account_overrides),
+                        enable_execution_trace,
+                    },
+                )
             };
 
             let TransactionSimulationResult {
@@ -4021,6 +4378,7 @@ pub mod rpc_full {
                 post_balances,
                 pre_token_balances,
                 post_token_balances,
+                execution_trace,
             } = simulation_result;
 
             let account_keys = transaction.message().account_keys();
@@ -4046,7 +4404,7 @@ pub mod rpc_full {
                 if result.is_err() {
                     Some(vec![None; config_accounts.addresses.len()])
                 } else {
-                    let mut post_simulation_accounts_map = HashMap::new();
+                    let mut post_simulation_accounts_map = account_overrides;
                     for (pubkey, data) in post_simulation_accounts {
                         post_simulation_accounts_map.insert(pubkey, data);
                     }
@@ -4099,6 +4457,211 @@ pub mod rpc_full {
                         balances.into_iter().map(|balance| solana_ledger::transaction_balances::svm_token_info_to_token_balance(balance).into()).collect()
                     }),
                     loaded_addresses: Some(UiLoadedAddresses::from(&transaction.get_loaded_addresses())),
+                    execution_trace: execution_trace.map(|execution_trace| {
+                        execution_trace.into_iter().map(rpc_invocation_trace).collect()
+                    }),
+                },
+            ))
+        }
+
+        fn simulate_bundle(
+            &self,
+            meta: Self::Metadata,
+            data: Vec<String>,
+            config: Option<RpcSimulateBundleConfig>,
+        ) -> Result<RpcResponse<RpcSimulateBundleResult>> {
+            debug!("simulate_bundle rpc request received");
+            let RpcSimulateBundleConfig {
+                sig_verify,
+                replace_recent_blockhash,
+                commitment,
+                encoding,
+                accounts: config_accounts,
+                min_context_slot,
+                inner_instructions: enable_cpi_recording,
+            } = config.unwrap_or_default();
+            if data.is_empty() {
+                return Err(Error::invalid_params("No transactions provided"));
+            }
+            if data.len() > MAX_SIMULATE_BUNDLE_TRANSACTIONS {
+                return Err(Error::invalid_params(format!(
+                    "Too many transactions provided; max {MAX_SIMULATE_BUNDLE_TRANSACTIONS}"
+                )));
+            }
+            if replace_recent_blockhash && sig_verify {
+                return Err(Error::invalid_params(
+                    "sigVerify may not be used with replaceRecentBlockhash",
+                ));
+            }
+            let tx_encoding = encoding.unwrap_or(UiTransactionEncoding::Base58);
+            let binary_encoding = tx_encoding.into_binary_encoding().ok_or_else(|| {
+                Error::invalid_params(format!(
+                    "unsupported encoding: {tx_encoding}. Supported encodings: base58, base64"
+                ))
+            })?;
+            let mut unsanitized_txs = data
+                .into_iter()
+                .map(|data| {
+                    decode_and_deserialize::<VersionedTransaction>(data, binary_encoding)
+                        .map(|(_, unsanitized_tx)| unsanitized_tx)
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            let (snapshot_addresses, accounts_encoding) =
+                if let Some(config_accounts) = config_accounts {
+                    let accounts_encoding = config_accounts
+                        .encoding
+                        .unwrap_or(UiAccountEncoding::Base64);
+
+                    if accounts_encoding == UiAccountEncoding::Binary
+                        || accounts_encoding == UiAccountEncoding::Base58
+                    {
+                        return Err(Error::invalid_params("base58 encoding not supported"));
+                    }
+
+                    if config_accounts.addresses.len() > MAX_MULTIPLE_ACCOUNTS {
+                        return Err(Error::invalid_params(format!(
+                            "Too many accounts provided; max {MAX_MULTIPLE_ACCOUNTS}"
+                        )));
+                    }
+
+                    let addresses = config_accounts
+                        .addresses
+                        .iter()
+                        .map(|address_str| verify_pubkey(address_str))
+                        .collect::<Result<Vec<_>>>()?;
+                    (addresses, Some(accounts_encoding))
+                } else {
+                    (vec![], None)
+                };
+
+            let bank = &*meta.get_bank_with_config(RpcContextConfig {
+                commitment,
+                min_context_slot,
+            })?;
+            let mut blockhash: Option<RpcBlockhash> = None;
+            if replace_recent_blockhash {
+                let recent_blockhash = bank.last_blockhash();
+                for unsanitized_tx in &mut unsanitized_txs {
+                    unsanitized_tx
+                        .message
+                        .set_recent_blockhash(recent_blockhash);
+                }
+                let last_valid_block_height = bank
+                    .get_blockhash_last_valid_block_height(&recent_blockhash)
+                    .expect("bank blockhash queue should contain blockhash");
+                blockhash.replace(RpcBlockhash {
+                    blockhash: recent_blockhash.to_string(),
+                    last_valid_block_height,
+                });
+            }
+
+            let enable_static_instruction_limit = bank
+                .feature_set
+                .is_active(&agave_feature_set::static_instruction_limit::id());
+            let transactions = unsanitized_txs
+                .into_iter()
+                .map(|unsanitized_tx| {
+                    sanitize_transaction(
+                        unsanitized_tx,
+                        bank,
+                        bank.get_reserved_account_keys(),
+                        enable_static_instruction_limit,
+                    )
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            if sig_verify
+                && transactions
+                    .iter()
+                    .any(|transaction| transaction.verify().is_err())
+            {
+                return Err(RpcCustomError::TransactionSignatureVerificationFailure.into());
+            }
+
+            let simulation_results =
+                bank.simulate_bundle(&transactions, enable_cpi_recording, &snapshot_addresses);
+
+            let transaction_results = transactions
+                .iter()
+                .zip(simulation_results)
+                .map(|(transaction, bundle_result)| {
+                    let BundleTransactionSimulationResult {
+                        simulation:
+                            TransactionSimulationResult {
+                                result,
+                                logs,
+                                units_consumed,
+                                loaded_accounts_data_size,
+                                return_data,
+                                inner_instructions,
+                                fee,
+                                pre_balances,
+                                post_balances,
+                                pre_token_balances,
+                                post_token_balances,
+                                ..
+                            },
+                        pre_execution_accounts,
+                        post_execution_accounts,
+                    } = bundle_result;
+
+                    let encode_accounts = |accounts| {
+                        accounts_encoding
+                            .map(|encoding| {
+                                encode_simulated_accounts(
+                                    bank,
+                                    &snapshot_addresses,
+                                    accounts,
+                                    encoding,
+                                )
+                            })
+                            .transpose()
+                    };
+                    let account_keys = transaction.message().account_keys();
+                    let inner_instructions = inner_instructions.map(|info| {
+                        map_inner_instructions(info)
+                            .map(|converted| parse_ui_inner_instructions(converted, &account_keys))
+                            .collect()
+                    });
+                    let token_balances = |balances: Vec<_>| -> Vec<UiTransactionTokenBalance> {
+                        balances
+                            .into_iter()
+                            .map(|balance| {
+                                solana_ledger::transaction_balances::svm_token_info_to_token_balance(
+                                    balance,
+                                )
+                                .into()
+                            })
+                            .collect()
+                    };
+
+                    Ok(RpcSimulateBundleTransactionResult {
+                        err: result.err().map(Into::into),
+                        logs: Some(logs),
+                        pre_execution_accounts: encode_accounts(pre_execution_accounts)?,
+                        post_execution_accounts: encode_accounts(post_execution_accounts)?,
+                        units_consumed: Some(units_consumed),
+                        loaded_accounts_data_size: Some(loaded_accounts_data_size),
+                        return_data: return_data.map(|return_data| return_data.into()),
+                        inner_instructions,
+                        fee,
+                        pre_balances,
+                        post_balances,
+                        pre_token_balances: pre_token_balances.map(token_balances),
+                        post_token_balances: post_token_balances.map(token_balances),
+                        loaded_addresses: Some(UiLoadedAddresses::from(
+                            &transaction.get_loaded_addresses(),
+                        )),
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            Ok(new_response(
+                bank,
+                RpcSimulateBundleResult {
+                    transaction_results,
+                    replacement_blockhash: blockhash,
                 },
             ))
         }
@@ -4497,6 +5060,7 @@ pub fn populate_blockstore_for_tests(
                 &solana_ledger::blockstore_processor::TransactionStatusSender {
                     sender: transaction_status_sender,
                     dependency_tracker: None,
+                    enable_log_event_recording: false,
                 },
             ),
             Some(&replay_vote_sender),
@@ -4526,7 +5090,10 @@ pub mod tests {
         jsonrpc_core_client::transports::local,
         serde::de::DeserializeOwned,
         solana_account::{Account, WritableAccount},
-        solana_accounts_db::accounts_db::{AccountsDbConfig, ACCOUNTS_DB_CONFIG_FOR_TESTING},
+        solana_accounts_db::{
+            accounts_db::{AccountsDbConfig, ACCOUNTS_DB_CONFIG_FOR_TESTING},
+            accounts_index::DataSliceIndex,
+        },
         solana_address_lookup_table_interface::{
             self as address_lookup_table,
             state::{AddressLookupTable, LookupTableMeta},
@@ -4538,6 +5105,8 @@ pub mod tests {
         solana_instruction::{error::InstructionError, AccountMeta, Instruction},
         solana_keypair::Keypair,
         solana_ledger::{
+            bigtable_upload::ConfirmedBlockUploadConfig,
+            bigtable_upload_service::BigTableUploadService,
             blockstore_meta::PerfSampleV2,
             blockstore_processor::fill_blockstore_slot_with_ticks,
             genesis_utils::{create_genesis_config, GenesisConfigInfo},
@@ -4556,7 +5125,7 @@ pub mod tests {
         },
         solana_rpc_client_api::{
             custom_error::{
-                JSON_RPC_SERVER_ERROR_BLOCK_NOT_AVAILABLE,
+                JSON_RPC_SERVER_ERROR_BLOCK_NOT_AVAILABLE, JSON_RPC_SERVER_ERROR_CURSOR_EXPIRED,
                 JSON_RPC_SERVER_ERROR_TRANSACTION_HISTORY_NOT_AVAILABLE,
                 JSON_RPC_SERVER_ERROR_UNSUPPORTED_TRANSACTION_VERSION,
             },
@@ -4574,6 +5143,7 @@ pub mod tests {
         },
         solana_sha256_hasher::hash,
         solana_signer::Signer,
+        solana_storage_bigtable::LocalLedgerStorage,
         solana_svm::account_loader::TRANSACTION_ACCOUNT_BASE_SIZE,
         solana_svm_log_collector::ic_logger_msg,
         solana_system_interface::{instruction as system_instruction, program as system_program},
@@ -4600,7 +5170,7 @@ pub mod tests {
             },
             state::{AccountState as TokenAccountState, Mint},
         },
-        std::{borrow::Cow, collections::HashMap, net::Ipv4Addr},
+        std::{borrow::Cow, collections::HashMap, net::Ipv4Addr, thread::sleep, time::Instant},
         test_case::test_case,
     };
 
@@ -4781,6 +5351,13 @@ pub mod tests {
         }
 
         fn start_with_config(config: JsonRpcConfig) -> Self {
+            Self::start_with_config_and_ledger_storage(config, None)
+        }
+
+        fn start_with_config_and_ledger_storage(
+            config: JsonRpcConfig,
+            bigtable_ledger_storage: Option<Arc<dyn LongTermStorage>>,
+        ) -> Self {
             let (bank_forks, mint_keypair, leader_vote_keypair) =
                 new_bank_forks_with_config(BankTestConfig {
                     accounts_db_config: AccountsDbConfig {
@@ -4825,7 +5402,7 @@ pub mod tests {
                 RpcHealth::stub(optimistically_confirmed_bank.clone(), blockstore.clone()),
                 cluster_info,
                 Hash::default(),
-                None,
+                bigtable_ledger_storage,
                 optimistically_confirmed_bank,
                 Arc::new(RwLock::new(LargestAccountsCache::new(30))),
                 max_slots.clone(),
@@ -5982,6 +6559,147 @@ pub mod tests {
         assert_eq!(result.len(), 0);
     }
 
+    #[test]
+    fn test_rpc_get_program_accounts_pages() {
+        let rpc = RpcHandler::start();
+        let bank = rpc.working_bank();
+
+        let program_id = Pubkey::new_unique();
+        let mut expected_pubkeys = (0..5)
+            .map(|_| {
+                let pubkey = Pubkey::new_unique();
+                bank.store_account(&pubkey, &AccountSharedData::new(42, 0, &program_id));
+                pubkey.to_string()
+            })
+            .collect::<Vec<_>>();
+        expected_pubkeys.sort_by_key(|pubkey| Pubkey::from_str(pubkey).unwrap());
+
+        let mut cursor = None;
+        let mut pubkeys = vec![];
+        loop {
+            let request = create_test_request(
+                "getProgramAccounts",
+                Some(json!([
+                    program_id.to_string(),
+                    {"limit": 2, "cursor": cursor},
+                ])),
+            );
+            let page: RpcKeyedAccountsPage = parse_success_result(rpc.handle_request_sync(request));
+            assert!(page.accounts.len() <= 2);
+            pubkeys.extend(page.accounts.into_iter().map(|account| account.pubkey));
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+        assert_eq!(pubkeys, expected_pubkeys);
+
+        // Pages are also returned with context
+        let request = create_test_request(
+            "getProgramAccounts",
+            Some(json!([
+                program_id.to_string(),
+                {"limit": 5, "withContext": true},
+            ])),
+        );
+        let result: RpcResponse<RpcKeyedAccountsPage> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result.value.accounts.len(), 5);
+        let cursor = result.value.next_cursor.unwrap();
+        assert_eq!(
+            ProgramAccountsCursor::decode(&cursor).unwrap(),
+            ProgramAccountsCursor {
+                slot: result.context.slot,
+                last_pubkey: Pubkey::from_str(&expected_pubkeys[4]).unwrap(),
+            }
+        );
+
+        for (config, expected_message) in [
+            (
+                json!({"limit": 0}),
+                format!("Invalid limit; max {MAX_GET_PROGRAM_ACCOUNTS_LIMIT}"),
+            ),
+            (
+                json!({"limit": MAX_GET_PROGRAM_ACCOUNTS_LIMIT + 1}),
+                format!("Invalid limit; max {MAX_GET_PROGRAM_ACCOUNTS_LIMIT}"),
+            ),
+            (
+                json!({"cursor": cursor}),
+                "Invalid param: cursor requires a limit".to_string(),
+            ),
+            (
+                json!({"limit": 1, "cursor": "invalid"}),
+                "Invalid param: invalid cursor".to_string(),
+            ),
+        ] {
+            let request = create_test_request(
+                "getProgramAccounts",
+                Some(json!([program_id.to_string(), config])),
+            );
+            let response = parse_failure_response(rpc.handle_request_sync(request));
+            assert_eq!(
+                response,
+                (ErrorCode::InvalidParams.code(), expected_message)
+            );
+        }
+
+        // Later pages are loaded from the slot of the first one, which does not see the accounts
+        // stored in later banks
+        let first_page_slot = ProgramAccountsCursor::decode(&cursor).unwrap().slot;
+        let bank = rpc.advance_bank_to_confirmed_slot(bank.slot() + 1);
+        let new_pubkey = Pubkey::new_from_array([u8::MAX; 32]);
+        bank.store_account(&new_pubkey, &AccountSharedData::new(42, 0, &program_id));
+        let request = create_test_request(
+            "getProgramAccounts",
+            Some(json!([
+                program_id.to_string(),
+                {"limit": 5, "cursor": cursor, "withContext": true},
+            ])),
+        );
+        let result: RpcResponse<RpcKeyedAccountsPage> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result.context.slot, first_page_slot);
+        assert_eq!(
+            result.value,
+            RpcKeyedAccountsPage {
+                accounts: vec![],
+                next_cursor: None,
+            }
+        );
+
+        // A new first page is loaded from the bank of the requested commitment
+        let request = create_test_request(
+            "getProgramAccounts",
+            Some(json!([
+                program_id.to_string(),
+                {"limit": 10, "withContext": true, "commitment": "processed"},
+            ])),
+        );
+        let result: RpcResponse<RpcKeyedAccountsPage> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result.context.slot, bank.slot());
+        assert_eq!(
+            result.value.accounts.last().unwrap().pubkey,
+            new_pubkey.to_string()
+        );
+
+        // Cursors expire once their bank is no longer available
+        let expired_cursor = ProgramAccountsCursor {
+            slot: bank.slot() + 100,
+            last_pubkey: Pubkey::new_unique(),
+        }
+        .encode();
+        let request = create_test_request(
+            "getProgramAccounts",
+            Some(json!([
+                program_id.to_string(),
+                {"limit": 1, "cursor": expired_cursor},
+            ])),
+        );
+        let (code, _message) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, JSON_RPC_SERVER_ERROR_CURSOR_EXPIRED);
+    }
+
     #[test]
     fn test_rpc_simulate_transaction() {
         let rpc = RpcHandler::start();
@@ -6324,6 +7042,234 @@ pub mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_rpc_simulate_transaction_account_overrides() {
+        let rpc = RpcHandler::start();
+        let bank = rpc.working_bank();
+        let rent_exempt_amount = bank.get_minimum_balance_for_rent_exemption(0);
+        let recent_blockhash = bank.confirmed_last_blockhash();
+
+        // Alice does not exist, unless overridden
+        let alice = Keypair::new();
+        let bob_pubkey = solana_pubkey::new_rand();
+        let tx =
+            system_transaction::transfer(&alice, &bob_pubkey, rent_exempt_amount, recent_blockhash);
+        let tx_serialized_encoded = BASE64_STANDARD.encode(serialize(&tx).unwrap());
+
+        // Simulation bank must be frozen
+        bank.freeze();
+
+        let request = create_test_request(
+            "simulateTransaction",
+            Some(json!([tx_serialized_encoded, {"encoding": "base64"}])),
+        );
+        let result: RpcResponse<RpcSimulateTransactionResult> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(
+            result.value.err,
+            Some(TransactionError::AccountNotFound.into())
+        );
+
+        let alice_lamports = 2 * rent_exempt_amount;
+        let request = create_test_request(
+            "simulateTransaction",
+            Some(json!([
+                tx_serialized_encoded,
+                {
+                    "encoding": "base64",
+                    "accountOverrides": [{
+                        "address": alice.pubkey().to_string(),
+                        "lamports": alice_lamports,
+                    }],
+                    "accounts": {
+                        "addresses": [alice.pubkey().to_string(), bob_pubkey.to_string()],
+                    },
+                },
+            ])),
+        );
+        let result: RpcResponse<RpcSimulateTransactionResult> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result.value.err, None);
+        let fee = result.value.fee.unwrap();
+        assert_eq!(result.value.pre_balances, Some(vec![alice_lamports, 0, 1]));
+        let lamports = result
+            .value
+            .accounts
+            .unwrap()
+            .into_iter()
+            .map(|account| account.map(|account| account.lamports))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lamports,
+            vec![
+                Some(alice_lamports - rent_exempt_amount - fee),
+                Some(rent_exempt_amount)
+            ]
+        );
+        assert_eq!(bank.get_balance(&alice.pubkey()), 0);
+
+        // Invalid override data
+        let request = create_test_request(
+            "simulateTransaction",
+            Some(json!([
+                tx_serialized_encoded,
+                {
+                    "encoding": "base64",
+                    "accountOverrides": [{
+                        "address": alice.pubkey().to_string(),
+                        "data": "not base64!",
+                    }],
+                },
+            ])),
+        );
+        let (code, _) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidParams.code());
+    }
+
+    #[test]
+    fn test_rpc_simulate_transaction_execution_trace() {
+        let rpc = RpcHandler::start();
+        let bank = rpc.working_bank();
+        let rent_exempt_amount = bank.get_minimum_balance_for_rent_exemption(0);
+        let recent_blockhash = bank.confirmed_last_blockhash();
+        let bob_pubkey = solana_pubkey::new_rand();
+        let tx = system_transaction::transfer(
+            &rpc.mint_keypair,
+            &bob_pubkey,
+            rent_exempt_amount,
+            recent_blockhash,
+        );
+        let tx_serialized_encoded = BASE64_STANDARD.encode(serialize(&tx).unwrap());
+
+        // Simulation bank must be frozen
+        bank.freeze();
+
+        let request = create_test_request(
+            "simulateTransaction",
+            Some(json!([tx_serialized_encoded, {"encoding": "base64"}])),
+        );
+        let result: RpcResponse<RpcSimulateTransactionResult> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result.value.execution_trace, None);
+
+        let request = create_test_request(
+            "simulateTransaction",
+            Some(json!([
+                tx_serialized_encoded,
+                {"encoding": "base64", "executionTrace": true},
+            ])),
+        );
+        let result: RpcResponse<RpcSimulateTransactionResult> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result.value.err, None);
+        let execution_trace = result.value.execution_trace.unwrap();
+        assert_eq!(execution_trace.len(), 1);
+        let invocation = &execution_trace[0];
+        assert_eq!(
+            invocation.program_id,
+            solana_system_interface::program::id().to_string()
+        );
+        assert_eq!(invocation.stack_height, 1);
+        assert_eq!(invocation.err, None);
+        assert!(invocation.inner_invocations.is_empty());
+        let bob = invocation
+            .accounts
+            .iter()
+            .find(|account| account.pubkey == bob_pubkey.to_string())
+            .unwrap();
+        assert!(bob.writable);
+        assert_eq!(bob.pre_lamports, 0);
+        assert_eq!(bob.post_lamports, rent_exempt_amount);
+        assert!(bob.data_diff.is_empty());
+    }
+
+    #[test]
+    fn test_rpc_simulate_bundle() {
+        let rpc = RpcHandler::start();
+        let bank = rpc.working_bank();
+        let rent_exempt_amount = bank.get_minimum_balance_for_rent_exemption(0);
+        let recent_blockhash = bank.confirmed_last_blockhash();
+
+        // Bob can only be funded by Alice once the first transaction has funded her
+        let alice = Keypair::new();
+        let bob_pubkey = solana_pubkey::new_rand();
+        let alice_lamports = 2 * rent_exempt_amount;
+        let transactions = [
+            system_transaction::transfer(
+                &rpc.mint_keypair,
+                &alice.pubkey(),
+                alice_lamports,
+                recent_blockhash,
+            ),
+            system_transaction::transfer(&alice, &bob_pubkey, rent_exempt_amount, recent_blockhash),
+        ];
+        let encoded_transactions: Vec<_> = transactions
+            .iter()
+            .map(|tx| BASE64_STANDARD.encode(serialize(tx).unwrap()))
+            .collect();
+
+        // Simulation bank must be frozen
+        bank.freeze();
+
+        let request = create_test_request(
+            "simulateBundle",
+            Some(json!([
+                encoded_transactions,
+                {
+                    "encoding": "base64",
+                    "sigVerify": true,
+                    "accounts": {
+                        "encoding": "base64",
+                        "addresses": [alice.pubkey().to_string(), bob_pubkey.to_string()],
+                    },
+                },
+            ])),
+        );
+        let result: RpcResponse<RpcSimulateBundleResult> =
+            parse_success_result(rpc.handle_request_sync(request));
+        let lamports = |accounts: &Option<Vec<Option<UiAccount>>>| {
+            accounts
+                .as_ref()
+                .unwrap()
+                .iter()
+                .map(|account| account.as_ref().map(|account| account.lamports))
+                .collect::<Vec<_>>()
+        };
+        let transaction_results = result.value.transaction_results;
+        assert_eq!(transaction_results.len(), 2);
+        assert_eq!(transaction_results[0].err, None);
+        assert_eq!(transaction_results[1].err, None);
+        assert_eq!(
+            lamports(&transaction_results[0].pre_execution_accounts),
+            vec![None, None]
+        );
+        assert_eq!(
+            lamports(&transaction_results[0].post_execution_accounts),
+            vec![Some(alice_lamports), None]
+        );
+        assert_eq!(
+            lamports(&transaction_results[1].pre_execution_accounts),
+            vec![Some(alice_lamports), None]
+        );
+        assert_eq!(
+            lamports(&transaction_results[1].post_execution_accounts),
+            vec![
+                Some(alice_lamports - rent_exempt_amount - transaction_results[1].fee.unwrap()),
+                Some(rent_exempt_amount)
+            ]
+        );
+        assert_eq!(bank.get_balance(&alice.pubkey()), 0);
+
+        // Empty bundle
+        let request = create_test_request("simulateBundle", Some(json!([[]])));
+        let response = parse_failure_response(rpc.handle_request_sync(request));
+        let expected = (
+            ErrorCode::InvalidParams.code(),
+            String::from("No transactions provided"),
+        );
+        assert_eq!(response, expected);
+    }
+
     #[test]
     fn test_rpc_simulate_transaction_with_parsing_token_accounts() {
         let rpc = RpcHandler::start();
@@ -6790,6 +7736,72 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn test_rpc_long_term_storage_fallback() {
+        let ledger_storage_path = tempfile::TempDir::new().unwrap();
+        let local_ledger_storage: Arc<dyn LongTermStorage> =
+            Arc::new(LocalLedgerStorage::open(ledger_storage_path.path()).unwrap());
+
+        // upload the rooted slot 0 into the local long-term storage
+        let rpc = RpcHandler::start();
+        let signatures = rpc.create_test_transactions_and_populate_blockstore();
+        let slot = rpc.working_bank().slot();
+        rpc.block_commitment_cache
+            .write()
+            .unwrap()
+            .set_all_slots(slot + 1, slot + 1);
+        rpc.max_complete_transaction_status_slot
+            .store(slot + 1, Ordering::SeqCst);
+        let runtime = Arc::new(Runtime::new().unwrap());
+        let exit = Arc::new(AtomicBool::new(false));
+        let upload_service = BigTableUploadService::new_with_config(
+            runtime.clone(),
+            local_ledger_storage.clone(),
+            rpc.blockstore.clone(),
+            rpc.block_commitment_cache.clone(),
+            rpc.max_complete_transaction_status_slot.clone(),
+            ConfirmedBlockUploadConfig::default(),
+            exit.clone(),
+        );
+        let start = Instant::now();
+        while !runtime
+            .block_on(local_ledger_storage.confirmed_block_exists(slot))
+            .unwrap()
+        {
+            assert!(start.elapsed() < Duration::from_secs(30));
+            sleep(Duration::from_millis(100));
+        }
+        exit.store(true, Ordering::Relaxed);
+        upload_service.join().unwrap();
+
+        // a node with an empty blockstore serves the transaction from the long-term storage
+        let rpc = RpcHandler::start_with_config_and_ledger_storage(
+            JsonRpcConfig {
+                enable_rpc_transaction_history: true,
+                ..JsonRpcConfig::default()
+            },
+            Some(local_ledger_storage),
+        );
+        let request = create_test_request(
+            "getTransaction",
+            Some(json!([signatures[0].to_string(), "json"])),
+        );
+        let result: EncodedConfirmedTransactionWithStatusMeta =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result.slot, slot);
+
+        let request = create_test_request(
+            "getSignatureStatuses",
+            Some(json!([
+                [signatures[0].to_string()],
+                {"searchTransactionHistory": true}
+            ])),
+        );
+        let result: RpcResponse<Vec<Option<TransactionStatus>>> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result.value[0].as_ref().unwrap().slot, slot);
+    }
+
     #[test]
     fn test_rpc_fail_request_airdrop() {
         let RpcHandler { meta, io, .. } = RpcHandler::start();
@@ -6923,7 +7935,7 @@ pub mod tests {
         assert_eq!(
             res,
             Some(
-                r#"{"jsonrpc":"2.0","error":{"code":-32002,"message":"Transaction simulation failed: Blockhash not found","data":{"accounts":null,"err":"BlockhashNotFound","fee":null,"innerInstructions":null,"loadedAccountsDataSize":0,"loadedAddresses":null,"logs":[],"postBalances":null,"postTokenBalances":null,"preBalances":null,"preTokenBalances":null,"replacementBlockhash":null,"returnData":null,"unitsConsumed":0}},"id":1}"#.to_string(),
+                r#"{"jsonrpc":"2.0","error":{"code":-32002,"message":"Transaction simulation failed: Blockhash not found","data":{"accounts":null,"err":"BlockhashNotFound","executionTrace":null,"fee":null,"innerInstructions":null,"loadedAccountsDataSize":0,"loadedAddresses":null,"logs":[],"postBalances":null,"postTokenBalances":null,"preBalances":null,"preTokenBalances":null,"replacementBlockhash":null,"returnData":null,"unitsConsumed":0}},"id":1}"#.to_string(),
             )
         );
 
@@ -7057,6 +8069,37 @@ pub mod tests {
         assert!(verify_filter(&filter).is_err());
     }
 
+    #[test]
+    fn test_optimize_nested_filters() {
+        let bytes = vec![1, 2, 3];
+        let base58_memcmp = || {
+            RpcFilterType::Memcmp(Memcmp::new(
+                0,
+                MemcmpEncodedBytes::Base58(bs58::encode(&bytes).into_string()),
+            ))
+        };
+        let raw_memcmp = || RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, bytes.clone()));
+
+        let mut filters = vec![
+            base58_memcmp(),
+            RpcFilterType::Or(vec![
+                RpcFilterType::DataSize(3),
+                RpcFilterType::Not(Box::new(base58_memcmp())),
+            ]),
+        ];
+        optimize_filters(&mut filters);
+        assert_eq!(
+            filters,
+            vec![
+                raw_memcmp(),
+                RpcFilterType::Or(vec![
+                    RpcFilterType::DataSize(3),
+                    RpcFilterType::Not(Box::new(raw_memcmp())),
+                ]),
+            ]
+        );
+    }
+
     #[test]
     fn test_rpc_verify_pubkey() {
         let pubkey = solana_pubkey::new_rand();
@@ -8777,6 +9820,83 @@ pub mod tests {
         .is_none());
     }
 
+    #[test]
+    fn test_data_slice_index_key() {
+        let program_id = Pubkey::new_unique();
+        let index = DataSliceIndex {
+            program_id,
+            offset: 8,
+            length: 32,
+        };
+        let account_indexes = AccountSecondaryIndexes {
+            keys: None,
+            indexes: HashSet::from([AccountIndex::DataSlice(index.clone())]),
+        };
+        let authority = Pubkey::new_unique().to_bytes().to_vec();
+
+        // Exact match on the indexed bytes
+        let index_key = data_slice_index_key(
+            &account_indexes,
+            &program_id,
+            &[
+                RpcFilterType::DataSize(64),
+                RpcFilterType::Memcmp(Memcmp::new_raw_bytes(8, authority.clone())),
+            ],
+        );
+        assert!(matches!(
+            index_key,
+            Some(IndexKey::DataSlice { index: ref key_index, offset: 0, ref bytes })
+                if *key_index == index && *bytes == authority
+        ));
+
+        // Exact matches are preferred over prefixes
+        let index_key = data_slice_index_key(
+            &account_indexes,
+            &program_id,
+            &[
+                RpcFilterType::Memcmp(Memcmp::new_raw_bytes(8, authority.clone())),
+                RpcFilterType::Memcmp(Memcmp::new_raw_bytes(12, authority[4..8].to_vec())),
+            ],
+        );
+        assert!(matches!(
+            index_key,
+            Some(IndexKey::DataSlice { offset: 0, ref bytes, .. }) if *bytes == authority
+        ));
+
+        // Sub-range of the indexed bytes
+        let index_key = data_slice_index_key(
+            &account_indexes,
+            &program_id,
+            &[RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                12,
+                authority[4..8].to_vec(),
+            ))],
+        );
+        assert!(matches!(
+            index_key,
+            Some(IndexKey::DataSlice { offset: 4, ref bytes, .. }) if *bytes == authority[4..8]
+        ));
+
+        // Memcmp not within the indexed bytes
+        assert!(data_slice_index_key(
+            &account_indexes,
+            &program_id,
+            &[RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                0,
+                authority.clone()
+            ))],
+        )
+        .is_none());
+
+        // Wrong program id
+        assert!(data_slice_index_key(
+            &account_indexes,
+            &Pubkey::new_unique(),
+            &[RpcFilterType::Memcmp(Memcmp::new_raw_bytes(8, authority))],
+        )
+        .is_none());
+    }
+
     #[test]
     fn test_get_spl_token_mint_filter() {
         // Filtering on token-v3 length
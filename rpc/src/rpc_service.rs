@@ -47,7 +47,7 @@ use {
         send_transaction_service::{self, SendTransactionService},
         transaction_client::{ConnectionCacheClient, TpuClientNextClient, TransactionClient},
     },
-    solana_storage_bigtable::CredentialType,
+    solana_storage_bigtable::{CredentialType, LocalLedgerStorage, LongTermStorage},
     solana_validator_exit::Exit,
     std::{
         net::SocketAddr,
@@ -724,23 +724,34 @@ impl JsonRpcService {
                 ref bigtable_app_profile_id,
                 timeout,
                 max_message_size,
+                ref local_ledger_storage_path,
             }) = config.rpc_bigtable_config
             {
-                let bigtable_config = solana_storage_bigtable::LedgerStorageConfig {
-                    read_only: !enable_bigtable_ledger_upload,
-                    timeout,
-                    credential_type: CredentialType::Filepath(None),
-                    instance_name: bigtable_instance_name.clone(),
-                    app_profile_id: bigtable_app_profile_id.clone(),
-                    max_message_size,
+                let bigtable_ledger_storage = if let Some(path) = local_ledger_storage_path {
+                    LocalLedgerStorage::open(path).map(|local_ledger_storage| {
+                        info!("Local ledger storage initialized at {}", path.display());
+                        Arc::new(local_ledger_storage) as Arc<dyn LongTermStorage>
+                    })
+                } else {
+                    let bigtable_config = solana_storage_bigtable::LedgerStorageConfig {
+                        read_only: !enable_bigtable_ledger_upload,
+                        timeout,
+                        credential_type: CredentialType::Filepath(None),
+                        instance_name: bigtable_instance_name.clone(),
+                        app_profile_id: bigtable_app_profile_id.clone(),
+                        max_message_size,
+                    };
+                    runtime
+                        .block_on(solana_storage_bigtable::LedgerStorage::new_with_config(
+                            bigtable_config,
+                        ))
+                        .map(|bigtable_ledger_storage| {
+                            info!("BigTable ledger storage initialized");
+                            Arc::new(bigtable_ledger_storage) as Arc<dyn LongTermStorage>
+                        })
                 };
-                runtime
-                    .block_on(solana_storage_bigtable::LedgerStorage::new_with_config(
-                        bigtable_config,
-                    ))
+                bigtable_ledger_storage
                     .map(|bigtable_ledger_storage| {
-                        info!("BigTable ledger storage initialized");
-
                         let bigtable_ledger_upload_service = if enable_bigtable_ledger_upload {
                             Some(Arc::new(BigTableUploadService::new_with_config(
                                 runtime.clone(),
@@ -761,7 +772,7 @@ impl JsonRpcService {
                         )
                     })
                     .unwrap_or_else(|err| {
-                        error!("Failed to initialize BigTable ledger storage: {err:?}");
+                        error!("Failed to initialize long-term ledger storage: {err:?}");
                         (None, None)
                     })
             } else {
@@ -18,8 +18,8 @@ use {
     },
     solana_svm::transaction_commit_result::CommittedTransaction,
     solana_transaction_status::{
-        extract_and_fmt_memos, map_inner_instructions, Reward, RewardsAndNumPartitions,
-        TransactionStatusMeta,
+        extract_and_fmt_memos, map_inner_instructions, map_log_events, map_syscall_compute_units,
+        Reward, RewardsAndNumPartitions, TransactionStatusMeta,
     },
     std::{
         sync::{
@@ -173,10 +173,12 @@ impl TransactionStatusService {
                     let CommittedTransaction {
                         status,
                         log_messages,
+                        log_events,
                         inner_instructions,
                         return_data,
                         executed_units,
                         fee_details,
+                        syscall_compute_units,
                         ..
                     } = committed_tx;
 
@@ -184,6 +186,10 @@ impl TransactionStatusService {
                     let inner_instructions = inner_instructions.map(|inner_instructions| {
                         map_inner_instructions(inner_instructions).collect()
                     });
+                    let log_events =
+                        log_events.map(|log_events| map_log_events(log_events).collect());
+                    let syscall_compute_units =
+                        syscall_compute_units.map(map_syscall_compute_units);
 
                     let pre_token_balances = Some(pre_token_balances);
                     let post_token_balances = Some(post_token_balances);
@@ -203,6 +209,8 @@ impl TransactionStatusService {
                         return_data,
                         compute_units_consumed: Some(executed_units),
                         cost_units: cost,
+                        log_events,
+                        syscall_compute_units,
                     };
 
                     if let Some(transaction_notifier) = transaction_notifier.as_ref() {
@@ -223,6 +231,8 @@ impl TransactionStatusService {
 
                     if !(enable_extended_tx_metadata_storage || transaction_notifier.is_some()) {
                         transaction_status_meta.log_messages.take();
+                        transaction_status_meta.log_events.take();
+                        transaction_status_meta.syscall_compute_units.take();
                         transaction_status_meta.inner_instructions.take();
                         transaction_status_meta.return_data.take();
                     }
@@ -462,12 +472,14 @@ pub(crate) mod tests {
         let commit_result = Ok(CommittedTransaction {
             status: Ok(()),
             log_messages: None,
+            log_events: None,
             inner_instructions: None,
             return_data: None,
             executed_units: 0,
             fee_details: FeeDetails::default(),
             loaded_account_stats: TransactionLoadedAccountsStats::default(),
             fee_payer_post_balance: 0,
+            syscall_compute_units: None,
         });
 
         let balances = TransactionBalancesSet {
@@ -593,12 +605,14 @@ pub(crate) mod tests {
         let commit_result = Ok(CommittedTransaction {
             status: Ok(()),
             log_messages: None,
+            log_events: None,
             inner_instructions: None,
             return_data: None,
             executed_units: 0,
             fee_details: FeeDetails::default(),
             loaded_account_stats: TransactionLoadedAccountsStats::default(),
             fee_payer_post_balance: 0,
+            syscall_compute_units: None,
         });
 
         let balances = TransactionBalancesSet {
@@ -193,10 +193,13 @@ mod tests {
                 execution_details: TransactionExecutionDetails {
                     status,
                     log_messages: None,
+                    log_events: None,
                     inner_instructions: None,
                     return_data: None,
                     executed_units: 0,
                     accounts_data_len_delta: 0,
+                    execution_trace: None,
+                    syscall_compute_units: None,
                 },
                 loaded_transaction,
                 programs_modified_by_tx: HashMap::new(),
@@ -111,6 +111,7 @@ use {
     solana_packet::PACKET_DATA_SIZE,
     solana_precompile_error::PrecompileError,
     solana_program_runtime::{
+        execution_trace::InvocationTrace,
         invoke_context::BuiltinFunctionWithContext,
         loaded_programs::{ProgramCacheEntry, ProgramRuntimeEnvironment},
     },
@@ -179,6 +180,10 @@ use {
         time::{Duration, Instant},
     },
 };
+pub use {
+    bundle_simulation::BundleTransactionSimulationResult,
+    partitioned_epoch_rewards::KeyedRewardsAndNumPartitions, solana_reward_info::RewardType,
+};
 #[cfg(feature = "dev-context-only-utils")]
 use {
     dashmap::DashSet,
@@ -190,7 +195,6 @@ use {
     solana_nonce_account::{get_system_account_kind, SystemAccountKind},
     solana_program_runtime::{loaded_programs::ProgramCacheForTxBatch, sysvar_cache::SysvarCache},
 };
-pub use {partitioned_epoch_rewards::KeyedRewardsAndNumPartitions, solana_reward_info::RewardType};
 
 /// params to `verify_accounts_hash`
 struct VerifyAccountsHashConfig {
@@ -201,6 +205,7 @@ mod accounts_lt_hash;
 mod address_lookup_table;
 pub mod bank_hash_details;
 pub mod builtins;
+mod bundle_simulation;
 mod check_transactions;
 mod fee_distribution;
 mod metrics;
@@ -319,11 +324,33 @@ pub struct TransactionSimulationResult {
     pub post_balances: Option<Vec<u64>>,
     pub pre_token_balances: Option<Vec<SvmTokenInfo>>,
     pub post_token_balances: Option<Vec<SvmTokenInfo>>,
+    pub execution_trace: Option<Vec<InvocationTrace>>,
+}
+
+/// Options of a transaction simulation
+#[derive(Debug, Default)]
+pub struct TransactionSimulationConfig<'a> {
+    /// Record the inner instructions of the transaction
+    pub enable_cpi_recording: bool,
+    /// Accounts to load instead of the ones stored in the bank
+    pub account_overrides: Option<&'a HashMap<Pubkey, AccountSharedData>>,
+    /// Record the trace of the program invocations, see
+    /// [`solana_program_runtime::execution_trace`]
+    pub enable_execution_trace: bool,
 }
 
+/// Native and token balances of a simulated transaction, before and after its execution
+type SimulationBalances = (
+    Option<Vec<u64>>,
+    Option<Vec<u64>>,
+    Option<Vec<SvmTokenInfo>>,
+    Option<Vec<SvmTokenInfo>>,
+);
+
 impl TransactionSimulationResult {
     pub fn new_error(err: TransactionError) -> Self {
         Self {
+            execution_trace: None,
             fee: None,
             inner_instructions: None,
             loaded_accounts_data_size: 0,
@@ -3012,6 +3039,24 @@ impl Bank {
         )
     }
 
+    /// Prepare a transaction batch from a list of transactions without locking accounts
+    pub fn prepare_unlocked_batch<'a, 'b, Tx: SVMMessage>(
+        &'a self,
+        transactions: &'b [Tx],
+    ) -> TransactionBatch<'a, 'b, Tx> {
+        let tx_account_lock_limit = self.get_transaction_account_lock_limit();
+        let lock_results = transactions
+            .iter()
+            .map(|transaction| {
+                validate_account_locks(transaction.account_keys(), tx_account_lock_limit)
+            })
+            .collect();
+        let mut batch =
+            TransactionBatch::new(lock_results, self, OwnedOrBorrowed::Borrowed(transactions));
+        batch.set_needs_unlock(false);
+        batch
+    }
+
     /// Prepare a transaction batch from a single transaction without locking accounts
     pub fn prepare_unlocked_batch_from_single_tx<'a, Tx: SVMMessage>(
         &'a self,
@@ -3047,6 +3092,17 @@ impl Bank {
         self.simulate_transaction_unchecked(transaction, enable_cpi_recording)
     }
 
+    /// Run transactions against a frozen bank without committing the results
+    pub fn simulate_transaction_with_config(
+        &self,
+        transaction: &impl TransactionWithMeta,
+        config: TransactionSimulationConfig,
+    ) -> TransactionSimulationResult {
+        assert!(self.is_frozen(), "simulation bank must be frozen");
+
+        self.simulate_transaction_unchecked_with_config(transaction, config)
+    }
+
     /// Run transactions against a bank without committing the results; does not check if the bank
     /// is frozen, enabling use in single-Bank test frameworks
     pub fn simulate_transaction_unchecked(
@@ -3054,9 +3110,33 @@ impl Bank {
         transaction: &impl TransactionWithMeta,
         enable_cpi_recording: bool,
     ) -> TransactionSimulationResult {
+        self.simulate_transaction_unchecked_with_config(
+            transaction,
+            TransactionSimulationConfig {
+                enable_cpi_recording,
+                ..TransactionSimulationConfig::default()
+            },
+        )
+    }
+
+    /// Run transactions against a bank without committing the results; does not check if the bank
+    /// is frozen
+    pub fn simulate_transaction_unchecked_with_config(
+        &self,
+        transaction: &impl TransactionWithMeta,
+        config: TransactionSimulationConfig,
+    ) -> TransactionSimulationResult {
+        let TransactionSimulationConfig {
+            enable_cpi_recording,
+            account_overrides,
+            enable_execution_trace,
+        } = config;
         let account_keys = transaction.account_keys();
         let number_of_accounts = account_keys.len();
-        let account_overrides = self.get_account_overrides_for_simulation(&account_keys);
+        let mut overrides = self.get_account_overrides_for_simulation(&account_keys);
+        for (pubkey, account) in account_overrides.into_iter().flatten() {
+            overrides.set_account(pubkey, Some(account.clone()));
+        }
         let batch = self.prepare_unlocked_batch_from_single_tx(transaction);
         let mut timings = ExecuteTimings::default();
 
@@ -3073,7 +3153,7 @@ impl Bank {
             &mut timings,
             &mut TransactionErrorMetrics::default(),
             TransactionProcessingConfig {
-                account_overrides: Some(&account_overrides),
+                account_overrides: Some(&overrides),
                 check_program_modification_slot: self.check_program_modification_slot,
                 log_messages_bytes_limit: None,
                 limit_to_load_programs: true,
@@ -3082,6 +3162,9 @@ impl Bank {
                     enable_log_recording: true,
                     enable_return_data_recording: true,
                     enable_transaction_balance_recording: true,
+                    enable_execution_trace,
+                    enable_syscall_compute_units_recording: false,
+                    enable_log_event_recording: false,
                 },
             },
         );
@@ -3091,6 +3174,32 @@ impl Bank {
         let processing_result = processing_results
             .pop()
             .unwrap_or(Err(TransactionError::InvalidProgramForExecution));
+        let balances = match balance_collector {
+            Some(balance_collector) => {
+                let (mut native_pre, mut native_post, mut token_pre, mut token_post) =
+                    balance_collector.into_vecs();
+
+                (
+                    native_pre.pop(),
+                    native_post.pop(),
+                    token_pre.pop(),
+                    token_post.pop(),
+                )
+            }
+            None => (None, None, None, None),
+        };
+
+        Self::transaction_simulation_result(processing_result, number_of_accounts, balances)
+    }
+
+    /// Converts the processing result of a simulated transaction, and its balances, into its
+    /// simulation result
+    fn transaction_simulation_result(
+        processing_result: TransactionProcessingResult,
+        number_of_accounts: usize,
+        balances: SimulationBalances,
+    ) -> TransactionSimulationResult {
+        let (pre_balances, post_balances, pre_token_balances, post_token_balances) = balances;
         let (
             post_simulation_accounts,
             result,
@@ -3100,6 +3209,7 @@ impl Bank {
             inner_instructions,
             units_consumed,
             loaded_accounts_data_size,
+            execution_trace,
         ) = match processing_result {
             Ok(processed_tx) => {
                 let executed_units = processed_tx.executed_units();
@@ -3123,6 +3233,7 @@ impl Bank {
                             details.inner_instructions,
                             executed_units,
                             loaded_accounts_data_size,
+                            details.execution_trace,
                         )
                     }
                     ProcessedTransaction::FeesOnly(fees_only_tx) => (
@@ -3134,29 +3245,14 @@ impl Bank {
                         None,
                         executed_units,
                         loaded_accounts_data_size,
+                        None,
                     ),
                 }
             }
-            Err(error) => (vec![], Err(error), None, None, None, None, 0, 0),
+            Err(error) => (vec![], Err(error), None, None, None, None, 0, 0, None),
         };
         let logs = logs.unwrap_or_default();
 
-        let (pre_balances, post_balances, pre_token_balances, post_token_balances) =
-            match balance_collector {
-                Some(balance_collector) => {
-                    let (mut native_pre, mut native_post, mut token_pre, mut token_post) =
-                        balance_collector.into_vecs();
-
-                    (
-                        native_pre.pop(),
-                        native_post.pop(),
-                        token_pre.pop(),
-                        token_post.pop(),
-                    )
-                }
-                None => (None, None, None, None),
-            };
-
         TransactionSimulationResult {
             result,
             logs,
@@ -3170,6 +3266,7 @@ impl Bank {
             post_balances,
             pre_token_balances,
             post_token_balances,
+            execution_trace,
         }
     }
 
@@ -3673,6 +3770,7 @@ impl Bank {
                         Ok(CommittedTransaction {
                             status: execution_details.status,
                             log_messages: execution_details.log_messages,
+                            log_events: execution_details.log_events,
                             inner_instructions: execution_details.inner_instructions,
                             return_data: execution_details.return_data,
                             executed_units,
@@ -3682,11 +3780,13 @@ impl Bank {
                                 loaded_accounts_data_size,
                             },
                             fee_payer_post_balance,
+                            syscall_compute_units: execution_details.syscall_compute_units,
                         })
                     }
                     ProcessedTransaction::FeesOnly(fees_only_tx) => Ok(CommittedTransaction {
                         status: Err(fees_only_tx.load_error),
                         log_messages: None,
+                        log_events: None,
                         inner_instructions: None,
                         return_data: None,
                         executed_units,
@@ -3700,6 +3800,7 @@ impl Bank {
                             .fee_payer()
                             .1
                             .lamports(),
+                        syscall_compute_units: None,
                     }),
                 }
             })
@@ -3848,6 +3949,9 @@ impl Bank {
                 enable_log_recording: true,
                 enable_return_data_recording: true,
                 enable_transaction_balance_recording: false,
+                enable_execution_trace: false,
+                enable_syscall_compute_units_recording: false,
+                enable_log_event_recording: false,
             },
             &mut ExecuteTimings::default(),
             Some(1000 * 1000),
@@ -4235,6 +4339,26 @@ impl Bank {
         )
     }
 
+    /// Returns at most `limit` accounts owned by `program_id` that pass `filter`
+    ///
+    /// See `Accounts::load_page_by_program_with_filter()`.
+    pub fn get_filtered_program_accounts_page<F: Fn(&AccountSharedData) -> bool>(
+        &self,
+        program_id: &Pubkey,
+        filter: F,
+        limit: usize,
+        config: &ScanConfig,
+    ) -> ScanResult<Vec<KeyedAccountSharedData>> {
+        self.rc.accounts.load_page_by_program_with_filter(
+            &self.ancestors,
+            self.bank_id,
+            program_id,
+            filter,
+            limit,
+            config,
+        )
+    }
+
     pub fn get_filtered_indexed_accounts<F: Fn(&AccountSharedData) -> bool>(
         &self,
         index_key: &IndexKey,
@@ -4252,6 +4376,28 @@ impl Bank {
         )
     }
 
+    /// Returns at most `limit` accounts found through `index_key` that pass `filter`
+    ///
+    /// See `Accounts::load_page_by_index_key_with_filter()`.
+    pub fn get_filtered_indexed_accounts_page<F: Fn(&AccountSharedData) -> bool>(
+        &self,
+        index_key: &IndexKey,
+        filter: F,
+        limit: usize,
+        config: &ScanConfig,
+        byte_limit_for_scan: Option<usize>,
+    ) -> ScanResult<Vec<KeyedAccountSharedData>> {
+        self.rc.accounts.load_page_by_index_key_with_filter(
+            &self.ancestors,
+            self.bank_id,
+            index_key,
+            filter,
+            limit,
+            config,
+            byte_limit_for_scan,
+        )
+    }
+
     pub fn account_indexes_include_key(&self, key: &Pubkey) -> bool {
         self.rc.accounts.account_indexes_include_key(key)
     }
@@ -4700,6 +4846,18 @@ impl Bank {
         self.capitalization.store(capitalization, Relaxed);
     }
 
+    /// Sets the compute budget, e.g. once the features of a bank loaded from a snapshot are known.
+    ///
+    /// Only intended to be called by tests.
+    /// (cannot be made DCOU due to solana-program-test)
+    pub fn set_compute_budget_for_tests(&mut self, compute_budget: Option<ComputeBudget>) {
+        self.compute_budget = compute_budget;
+        if let Some(compute_budget) = &self.compute_budget {
+            self.transaction_processor
+                .set_execution_cost(compute_budget.to_cost());
+        }
+    }
+
     /// Returns the `SnapshotHash` for this bank's slot
     ///
     /// This fn is used at startup to verify the bank was rebuilt correctly.
@@ -0,0 +1,274 @@
+use {
+    super::{Bank, LoadAndExecuteTransactionsOutput, TransactionSimulationResult},
+    log::debug,
+    solana_account::{AccountSharedData, ReadableAccount},
+    solana_clock::{MAX_PROCESSING_AGE, MAX_TRANSACTION_FORWARDING_DELAY},
+    solana_message::AccountKeys,
+    solana_pubkey::Pubkey,
+    solana_runtime_transaction::transaction_with_meta::TransactionWithMeta,
+    solana_svm::{
+        account_loader::LoadedTransaction,
+        transaction_error_metrics::TransactionErrorMetrics,
+        transaction_processing_result::ProcessedTransaction,
+        transaction_processor::{ExecutionRecordingConfig, TransactionProcessingConfig},
+    },
+    solana_svm_timings::ExecuteTimings,
+    solana_svm_transaction::svm_message::SVMMessage,
+    std::collections::HashMap,
+};
+
+/// Simulation result of a transaction of a bundle
+#[derive(Debug, PartialEq)]
+pub struct BundleTransactionSimulationResult {
+    pub simulation: TransactionSimulationResult,
+    /// State of the requested accounts before the transaction, `None` if they do not exist
+    pub pre_execution_accounts: Vec<Option<AccountSharedData>>,
+    /// State of the requested accounts after the transaction, `None` if they do not exist
+    pub post_execution_accounts: Vec<Option<AccountSharedData>>,
+}
+
+impl Bank {
+    /// Run a bundle of transactions against a frozen bank without committing the results
+    ///
+    /// The transactions are processed in order, as if they were in a block: every transaction
+    /// sees the account writes of the previous ones. Transactions which fail only have their fee
+    /// charged, and do not prevent the next ones from being processed.
+    ///
+    /// The state of the `snapshot_accounts` is returned before and after every transaction.
+    pub fn simulate_bundle(
+        &self,
+        transactions: &[impl TransactionWithMeta],
+        enable_cpi_recording: bool,
+        snapshot_accounts: &[Pubkey],
+    ) -> Vec<BundleTransactionSimulationResult> {
+        assert!(self.is_frozen(), "simulation bank must be frozen");
+
+        self.simulate_bundle_unchecked(transactions, enable_cpi_recording, snapshot_accounts)
+    }
+
+    /// Run a bundle of transactions against a bank without committing the results; does not
+    /// check if the bank is frozen, enabling use in single-Bank test frameworks
+    pub fn simulate_bundle_unchecked(
+        &self,
+        transactions: &[impl TransactionWithMeta],
+        enable_cpi_recording: bool,
+        snapshot_accounts: &[Pubkey],
+    ) -> Vec<BundleTransactionSimulationResult> {
+        let bundle_account_keys: Vec<_> = transactions
+            .iter()
+            .flat_map(|transaction| transaction.account_keys().iter().copied())
+            .collect();
+        let account_overrides = self
+            .get_account_overrides_for_simulation(&AccountKeys::new(&bundle_account_keys, None));
+        let batch = self.prepare_unlocked_batch(transactions);
+        let mut timings = ExecuteTimings::default();
+
+        let LoadAndExecuteTransactionsOutput {
+            processing_results,
+            balance_collector,
+            ..
+        } = self.load_and_execute_transactions(
+            &batch,
+            // See `simulate_transaction_unchecked()`
+            MAX_PROCESSING_AGE - MAX_TRANSACTION_FORWARDING_DELAY,
+            &mut timings,
+            &mut TransactionErrorMetrics::default(),
+            TransactionProcessingConfig {
+                account_overrides: Some(&account_overrides),
+                check_program_modification_slot: self.check_program_modification_slot,
+                log_messages_bytes_limit: None,
+                limit_to_load_programs: true,
+                recording_config: ExecutionRecordingConfig {
+                    enable_cpi_recording,
+                    enable_log_recording: true,
+                    enable_return_data_recording: true,
+                    enable_transaction_balance_recording: true,
+                    enable_execution_trace: false,
+                    enable_syscall_compute_units_recording: false,
+                    enable_log_event_recording: false,
+                },
+            },
+        );
+
+        debug!("simulate_bundle: {timings:?}");
+
+        let mut balances = balance_collector
+            .map(|balance_collector| {
+                let (native_pre, native_post, token_pre, token_post) =
+                    balance_collector.into_vecs();
+                (
+                    native_pre.into_iter(),
+                    native_post.into_iter(),
+                    token_pre.into_iter(),
+                    token_post.into_iter(),
+                )
+            })
+            .unwrap_or_default();
+
+        // The accounts written by the transactions processed so far
+        let mut bundle_accounts = HashMap::new();
+        let load_snapshot_accounts =
+            |bundle_accounts: &HashMap<Pubkey, AccountSharedData>| -> Vec<_> {
+                snapshot_accounts
+                    .iter()
+                    .map(|pubkey| {
+                        bundle_accounts
+                            .get(pubkey)
+                            .cloned()
+                            .or_else(|| self.get_account(pubkey))
+                            .filter(|account| account.lamports() > 0)
+                    })
+                    .collect()
+            };
+
+        transactions
+            .iter()
+            .zip(processing_results)
+            .map(|(transaction, processing_result)| {
+                let pre_execution_accounts = load_snapshot_accounts(&bundle_accounts);
+                if let Ok(processed_transaction) = &processing_result {
+                    for (pubkey, account) in
+                        Self::accounts_written_by_transaction(transaction, processed_transaction)
+                    {
+                        bundle_accounts.insert(*pubkey, account.clone());
+                    }
+                }
+                let post_execution_accounts = load_snapshot_accounts(&bundle_accounts);
+
+                let simulation = Self::transaction_simulation_result(
+                    processing_result,
+                    transaction.account_keys().len(),
+                    (
+                        balances.0.next(),
+                        balances.1.next(),
+                        balances.2.next(),
+                        balances.3.next(),
+                    ),
+                );
+                BundleTransactionSimulationResult {
+                    simulation,
+                    pre_execution_accounts,
+                    post_execution_accounts,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the accounts the next transactions of a batch see as written by `transaction`
+    fn accounts_written_by_transaction<'a>(
+        transaction: &'a impl SVMMessage,
+        processed_transaction: &'a ProcessedTransaction,
+    ) -> Box<dyn Iterator<Item = (&'a Pubkey, &'a AccountSharedData)> + 'a> {
+        match processed_transaction {
+            ProcessedTransaction::Executed(executed_transaction)
+                if executed_transaction.was_successful() =>
+            {
+                let LoadedTransaction { accounts, .. } = &executed_transaction.loaded_transaction;
+                Box::new(
+                    accounts
+                        .iter()
+                        .take(transaction.account_keys().len())
+                        .enumerate()
+                        .filter(|(index, _)| {
+                            transaction.is_writable(*index)
+                                && (!transaction.is_invoked(*index)
+                                    || transaction.is_instruction_account(*index))
+                        })
+                        .map(|(_, (pubkey, account))| (pubkey, account)),
+                )
+            }
+            ProcessedTransaction::Executed(executed_transaction) => Box::new(
+                executed_transaction
+                    .loaded_transaction
+                    .rollback_accounts
+                    .iter()
+                    .map(|(pubkey, account)| (pubkey, account)),
+            ),
+            ProcessedTransaction::FeesOnly(fees_only_transaction) => Box::new(
+                fees_only_transaction
+                    .rollback_accounts
+                    .iter()
+                    .map(|(pubkey, account)| (pubkey, account)),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::genesis_utils::{create_genesis_config_with_leader, GenesisConfigInfo},
+        solana_keypair::Keypair,
+        solana_runtime_transaction::runtime_transaction::RuntimeTransaction,
+        solana_signer::Signer,
+        solana_system_transaction as system_transaction,
+        solana_transaction_error::TransactionError,
+    };
+
+    #[test]
+    fn test_simulate_bundle() {
+        let GenesisConfigInfo {
+            genesis_config,
+            mint_keypair,
+            ..
+        } = create_genesis_config_with_leader(1_000_000_000, &Pubkey::new_unique(), 42);
+        let (bank, _bank_forks) = Bank::new_with_bank_forks_for_tests(&genesis_config);
+        let blockhash = bank.last_blockhash();
+        let rent_exempt_minimum = bank.get_minimum_balance_for_rent_exemption(0);
+
+        // the second transaction spends lamports which only exist after the first one
+        let alice = Keypair::new();
+        let bob = Pubkey::new_unique();
+        let transactions = [
+            system_transaction::transfer(&mint_keypair, &alice.pubkey(), 10_000_000, blockhash),
+            system_transaction::transfer(&alice, &bob, rent_exempt_minimum, blockhash),
+            system_transaction::transfer(&alice, &bob, 1_000_000_000, blockhash),
+        ]
+        .map(RuntimeTransaction::from_transaction_for_tests);
+
+        let results = bank.simulate_bundle_unchecked(&transactions, false, &[alice.pubkey(), bob]);
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].simulation.result, Ok(()));
+        assert_eq!(results[1].simulation.result, Ok(()));
+        assert!(matches!(
+            results[2].simulation.result,
+            Err(TransactionError::InstructionError(..))
+        ));
+
+        let lamports = |accounts: &[Option<AccountSharedData>]| {
+            accounts
+                .iter()
+                .map(|account| account.as_ref().map(|account| account.lamports()))
+                .collect::<Vec<_>>()
+        };
+        let fee = results[1].simulation.fee.unwrap();
+        assert_eq!(
+            lamports(&results[0].pre_execution_accounts),
+            vec![None, None]
+        );
+        assert_eq!(
+            lamports(&results[0].post_execution_accounts),
+            vec![Some(10_000_000), None]
+        );
+        assert_eq!(
+            lamports(&results[1].post_execution_accounts),
+            vec![
+                Some(10_000_000 - rent_exempt_minimum - fee),
+                Some(rent_exempt_minimum)
+            ]
+        );
+        // failed transactions only charge their fee
+        assert_eq!(
+            lamports(&results[2].post_execution_accounts),
+            vec![
+                Some(10_000_000 - rent_exempt_minimum - 2 * fee),
+                Some(rent_exempt_minimum)
+            ]
+        );
+
+        // nothing was committed
+        assert_eq!(bank.get_balance(&alice.pubkey()), 0);
+        assert_eq!(bank.get_balance(&bob), 0);
+    }
+}
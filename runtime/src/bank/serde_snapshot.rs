@@ -55,7 +55,7 @@ mod tests {
         for storage_entry in storage_entries.into_iter() {
             // Copy file to new directory
             let storage_path = storage_entry.path();
-            let file_name = AccountsFile::file_name(storage_entry.slot(), storage_entry.id());
+            let file_name = storage_entry.file_name();
             let output_path = output_dir.as_ref().join(file_name);
             std::fs::copy(storage_path, &output_path)?;
 
@@ -101,7 +101,8 @@ use {
         transaction_commit_result::TransactionCommitResultExtensions,
         transaction_execution_result::ExecutedTransaction,
     },
-    solana_svm_timings::ExecuteTimings,
+    solana_svm_log_collector::LogEvent,
+    solana_svm_timings::{ExecuteTimings, SyscallComputeUnits},
     solana_svm_transaction::svm_message::SVMMessage,
     solana_system_interface::{
         error::SystemError,
@@ -253,10 +254,13 @@ fn new_executed_processing_result(
             execution_details: TransactionExecutionDetails {
                 status,
                 log_messages: None,
+                log_events: None,
                 inner_instructions: None,
                 return_data: None,
                 executed_units: 0,
                 accounts_data_len_delta: 0,
+                execution_trace: None,
+                syscall_compute_units: None,
             },
             programs_modified_by_tx: HashMap::new(),
         },
@@ -1944,6 +1948,7 @@ fn test_load_and_execute_commit_transactions_fees_only() {
         vec![Ok(CommittedTransaction {
             status: Err(TransactionError::ProgramAccountNotFound),
             log_messages: None,
+            log_events: None,
             inner_instructions: None,
             return_data: None,
             executed_units: 0,
@@ -1953,6 +1958,7 @@ fn test_load_and_execute_commit_transactions_fees_only() {
                 loaded_accounts_data_size: nonce_size as u32,
             },
             fee_payer_post_balance: genesis_config.rent.minimum_balance(0) - 1 - 5000,
+            syscall_compute_units: None,
         })]
     );
 }
@@ -1999,7 +2005,10 @@ fn test_load_and_execute_commit_transactions_failure() {
         .load_execute_and_commit_transactions(
             &batch,
             MAX_PROCESSING_AGE,
-            ExecutionRecordingConfig::new_single_setting(true),
+            ExecutionRecordingConfig {
+                enable_log_event_recording: true,
+                ..ExecutionRecordingConfig::new_single_setting(true)
+            },
             &mut ExecuteTimings::default(),
             None,
         )
@@ -2019,6 +2028,23 @@ fn test_load_and_execute_commit_transactions_failure() {
                 "Program 11111111111111111111111111111111 failed: invalid instruction data"
                     .to_string()
             ]),
+            log_events: Some(vec![
+                LogEvent::Invoke {
+                    program_id: system_program::id(),
+                    stack_height: 1,
+                },
+                LogEvent::Success {
+                    program_id: system_program::id(),
+                },
+                LogEvent::Invoke {
+                    program_id: system_program::id(),
+                    stack_height: 1,
+                },
+                LogEvent::Failure {
+                    program_id: system_program::id(),
+                    error: "invalid instruction data".to_string(),
+                },
+            ]),
             inner_instructions: Some(vec![vec![], vec![]]),
             return_data: None,
             executed_units: 300,
@@ -2028,6 +2054,7 @@ fn test_load_and_execute_commit_transactions_failure() {
                 loaded_accounts_data_size: 142, // size of system account (initially recipient does not exist)
             },
             fee_payer_post_balance: starting_balance - 5000,
+            syscall_compute_units: Some(SyscallComputeUnits::default()),
         })]
     );
 }
@@ -2073,7 +2100,10 @@ fn test_load_and_execute_commit_transactions_success() {
         .load_execute_and_commit_transactions(
             &batch,
             MAX_PROCESSING_AGE,
-            ExecutionRecordingConfig::new_single_setting(true),
+            ExecutionRecordingConfig {
+                enable_log_event_recording: true,
+                ..ExecutionRecordingConfig::new_single_setting(true)
+            },
             &mut ExecuteTimings::default(),
             None,
         )
@@ -2087,6 +2117,15 @@ fn test_load_and_execute_commit_transactions_success() {
                 "Program 11111111111111111111111111111111 invoke [1]".to_string(),
                 "Program 11111111111111111111111111111111 success".to_string(),
             ]),
+            log_events: Some(vec![
+                LogEvent::Invoke {
+                    program_id: system_program::id(),
+                    stack_height: 1,
+                },
+                LogEvent::Success {
+                    program_id: system_program::id(),
+                },
+            ]),
             inner_instructions: Some(vec![vec![]]),
             return_data: None,
             executed_units: 150,
@@ -2096,6 +2135,7 @@ fn test_load_and_execute_commit_transactions_success() {
                 loaded_accounts_data_size: 142, // size of system account (initially recipient does not exist)
             },
             fee_payer_post_balance: starting_balance - 5000 - transfer_amount,
+            syscall_compute_units: Some(SyscallComputeUnits::default()),
         })]
     );
 }
@@ -4776,6 +4816,9 @@ fn test_pre_post_transaction_balances() {
             enable_log_recording: false,
             enable_return_data_recording: false,
             enable_transaction_balance_recording: true,
+            enable_execution_trace: false,
+            enable_syscall_compute_units_recording: false,
+            enable_log_event_recording: false,
         },
         &mut ExecuteTimings::default(),
         None,
@@ -8422,6 +8465,9 @@ fn test_tx_log_order(relax_intrabatch_account_locks: bool) {
                 enable_log_recording: true,
                 enable_return_data_recording: false,
                 enable_transaction_balance_recording: false,
+                enable_execution_trace: false,
+                enable_syscall_compute_units_recording: false,
+                enable_log_event_recording: false,
             },
             &mut ExecuteTimings::default(),
             None,
@@ -8534,6 +8580,9 @@ fn test_tx_return_data() {
                     enable_log_recording: false,
                     enable_return_data_recording: true,
                     enable_transaction_balance_recording: false,
+                    enable_execution_trace: false,
+                    enable_syscall_compute_units_recording: false,
+                    enable_log_event_recording: false,
                 },
                 &mut ExecuteTimings::default(),
                 None,
@@ -11638,10 +11687,101 @@ fn test_failed_simulation_load_error() {
             post_balances: Some(vec![mint_balance, 0]),
             pre_token_balances: Some(vec![]),
             post_token_balances: Some(vec![]),
+            execution_trace: None,
         }
     );
 }
 
+/// Test that simulations load the overridden accounts instead of the stored ones
+#[test]
+fn test_simulation_account_overrides() {
+    let (genesis_config, _mint_keypair) = create_genesis_config(LAMPORTS_PER_SOL);
+    let (bank, _bank_forks) = Bank::new_with_bank_forks_for_tests(&genesis_config);
+    let alice = Keypair::new();
+    let bob = Pubkey::new_unique();
+    let transaction =
+        system_transaction::transfer(&alice, &bob, LAMPORTS_PER_SOL, bank.last_blockhash());
+
+    bank.freeze();
+    let sanitized = RuntimeTransaction::from_transaction_for_tests(transaction);
+    let simulation = bank.simulate_transaction(&sanitized, false);
+    assert_eq!(simulation.result, Err(TransactionError::AccountNotFound));
+
+    // Alice is only funded for the simulation
+    let account_overrides = HashMap::from([(
+        alice.pubkey(),
+        AccountSharedData::new(2 * LAMPORTS_PER_SOL, 0, &system_program::id()),
+    )]);
+    let simulation = bank.simulate_transaction_with_config(
+        &sanitized,
+        TransactionSimulationConfig {
+            account_overrides: Some(&account_overrides),
+            ..TransactionSimulationConfig::default()
+        },
+    );
+    assert_eq!(simulation.result, Ok(()));
+    let fee = simulation.fee.unwrap();
+    assert_eq!(
+        simulation.pre_balances,
+        Some(vec![2 * LAMPORTS_PER_SOL, 0, 1])
+    );
+    assert_eq!(
+        simulation.post_balances,
+        Some(vec![LAMPORTS_PER_SOL - fee, LAMPORTS_PER_SOL, 1])
+    );
+    assert_eq!(bank.get_balance(&alice.pubkey()), 0);
+}
+
+/// Test that simulations record the trace of the program invocations when enabled
+#[test]
+fn test_simulation_execution_trace() {
+    let (genesis_config, mint_keypair) = create_genesis_config(LAMPORTS_PER_SOL);
+    let (bank, _bank_forks) = Bank::new_with_bank_forks_for_tests(&genesis_config);
+    let bob = Pubkey::new_unique();
+    let amount = bank.get_minimum_balance_for_rent_exemption(0);
+    let transaction =
+        system_transaction::transfer(&mint_keypair, &bob, amount, bank.last_blockhash());
+
+    bank.freeze();
+    let sanitized = RuntimeTransaction::from_transaction_for_tests(transaction);
+    let simulation = bank.simulate_transaction(&sanitized, false);
+    assert_eq!(simulation.execution_trace, None);
+
+    let simulation = bank.simulate_transaction_with_config(
+        &sanitized,
+        TransactionSimulationConfig {
+            enable_execution_trace: true,
+            ..TransactionSimulationConfig::default()
+        },
+    );
+    assert_eq!(simulation.result, Ok(()));
+    let execution_trace = simulation.execution_trace.unwrap();
+    assert_eq!(execution_trace.len(), 1);
+    let invocation = &execution_trace[0];
+    assert_eq!(invocation.program_id, system_program::id());
+    assert_eq!(invocation.stack_height, 1);
+    assert_eq!(invocation.compute_units_consumed, simulation.units_consumed);
+    assert_eq!(invocation.result, Ok(()));
+    assert!(invocation.inner_invocations.is_empty());
+    let lamports: Vec<_> = invocation
+        .accounts
+        .iter()
+        .map(|account| (account.pubkey, account.pre_lamports, account.post_lamports))
+        .collect();
+    let fee = simulation.fee.unwrap();
+    assert_eq!(
+        lamports,
+        vec![
+            (
+                mint_keypair.pubkey(),
+                LAMPORTS_PER_SOL - fee,
+                LAMPORTS_PER_SOL - fee - amount
+            ),
+            (bob, 0, amount),
+        ]
+    );
+}
+
 #[test]
 fn test_filter_program_errors_and_collect_fee_details() {
     // TX  | PROCESSING RESULT           | COLLECT            | COLLECT
@@ -913,7 +913,12 @@ pub(crate) fn remap_append_vec_file(
             break (remapped_append_vec_id, remapped_append_vec_path);
         }
 
-        let remapped_file_name = AccountsFile::file_name(slot, remapped_append_vec_id);
+        // Keep the format of the storage, which is told by its file name
+        let remapped_file_name = if AccountsFile::is_tiered_storage_path(append_vec_path) {
+            AccountsFile::tiered_storage_file_name(slot, remapped_append_vec_id)
+        } else {
+            AccountsFile::file_name(slot, remapped_append_vec_id)
+        };
         remapped_append_vec_path = append_vec_path.parent().unwrap().join(remapped_file_name);
 
         #[cfg(all(target_os = "linux", target_env = "gnu"))]
@@ -132,7 +132,7 @@ mod serde_snapshot_tests {
         let mut next_append_vec_id = 0;
         for storage_entry in storage_entries.into_iter() {
             // Copy file to new directory
-            let file_name = AccountsFile::file_name(storage_entry.slot(), storage_entry.id());
+            let file_name = storage_entry.file_name();
             let output_path = output_dir.as_ref().join(file_name);
             let mut reader = AccountStorageReader::new(&storage_entry, None).unwrap();
             let mut writer = File::create(&output_path)?;
@@ -817,8 +817,8 @@ mod tests {
                 get_highest_loadable_bank_snapshot, purge_all_bank_snapshots, purge_bank_snapshot,
                 purge_bank_snapshots_older_than_slot, purge_incomplete_bank_snapshots,
                 purge_old_bank_snapshots, purge_old_bank_snapshots_at_startup,
-                snapshot_storage_rebuilder::get_slot_and_append_vec_id,
-                SNAPSHOT_FULL_SNAPSHOT_SLOT_FILENAME,
+                snapshot_storage_rebuilder::get_slot_and_append_vec_id, SeekableSnapshotArchive,
+                ZstdConfig, SNAPSHOT_FULL_SNAPSHOT_SLOT_FILENAME,
             },
             status_cache::Status,
         },
@@ -1012,6 +1012,87 @@ mod tests {
         assert_eq!(*bank1, roundtrip_bank);
     }
 
+    /// Test roundtrip of bank to a full snapshot in the seekable archive format, then back again,
+    /// and read single accounts from the archive through its account index.
+    #[test]
+    fn test_roundtrip_bank_to_and_from_seekable_full_snapshot() {
+        let collector = Pubkey::new_unique();
+        let key1 = Keypair::new();
+        let key2 = Keypair::new();
+
+        let (genesis_config, mint_keypair) = create_genesis_config(1_000_000 * LAMPORTS_PER_SOL);
+        let (bank0, bank_forks) = Bank::new_with_bank_forks_for_tests(&genesis_config);
+        bank0
+            .transfer(LAMPORTS_PER_SOL, &mint_keypair, &key1.pubkey())
+            .unwrap();
+        bank0
+            .transfer(2 * LAMPORTS_PER_SOL, &mint_keypair, &key2.pubkey())
+            .unwrap();
+        bank0.fill_bank_with_ticks_for_tests();
+        bank0.squash();
+        bank0.force_flush_accounts_cache();
+
+        // key1 is stored again in slot 1, the archive must return its latest version
+        let slot = 1;
+        let bank1 =
+            new_bank_from_parent_with_bank_forks(bank_forks.as_ref(), bank0, &collector, slot);
+        bank1
+            .transfer(LAMPORTS_PER_SOL, &mint_keypair, &key1.pubkey())
+            .unwrap();
+        bank1.fill_bank_with_ticks_for_tests();
+
+        let (_tmp_dir, accounts_dir) = create_tmp_accounts_dir_for_tests();
+        let bank_snapshots_dir = tempfile::TempDir::new().unwrap();
+        let snapshot_archives_dir = tempfile::TempDir::new().unwrap();
+        let snapshot_archive_format = ArchiveFormat::TarZstdSeekable {
+            config: ZstdConfig::default(),
+        };
+
+        let full_snapshot_archive_info = bank_to_full_snapshot_archive(
+            bank_snapshots_dir.path(),
+            &bank1,
+            None,
+            snapshot_archives_dir.path(),
+            snapshot_archives_dir.path(),
+            snapshot_archive_format,
+        )
+        .unwrap();
+        assert_eq!(
+            full_snapshot_archive_info.archive_format(),
+            snapshot_archive_format
+        );
+
+        let roundtrip_bank = bank_from_snapshot_archives(
+            &[accounts_dir],
+            bank_snapshots_dir.path(),
+            &full_snapshot_archive_info,
+            None,
+            &genesis_config,
+            &RuntimeConfig::default(),
+            None,
+            None,
+            false,
+            false,
+            false,
+            ACCOUNTS_DB_CONFIG_FOR_TESTING,
+            None,
+            Arc::default(),
+        )
+        .unwrap();
+        assert_eq!(*bank1, roundtrip_bank);
+
+        let mut archive = SeekableSnapshotArchive::open(full_snapshot_archive_info.path()).unwrap();
+        assert_eq!(
+            archive.get_account(&key1.pubkey()).unwrap(),
+            Some((bank1.get_account(&key1.pubkey()).unwrap(), 1))
+        );
+        assert_eq!(
+            archive.get_account(&key2.pubkey()).unwrap(),
+            Some((bank1.get_account(&key2.pubkey()).unwrap(), 0))
+        );
+        assert_eq!(archive.get_account(&Pubkey::new_unique()).unwrap(), None);
+    }
+
     /// Test roundtrip of bank to a full snapshot, then back again.  This test is more involved
     /// than the simple version above; creating multiple banks over multiple slots and doing
     /// multiple transfers.  So this full snapshot should contain more data.
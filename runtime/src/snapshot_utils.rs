@@ -15,8 +15,9 @@ use {
         snapshot_config::SnapshotConfig,
         snapshot_hash::SnapshotHash,
         snapshot_package::{SnapshotKind, SnapshotPackage},
-        snapshot_utils::snapshot_storage_rebuilder::{
-            get_slot_and_append_vec_id, SnapshotStorageRebuilder,
+        snapshot_utils::{
+            seekable_archive::{ArchiveWriter, SeekableZstdEncoder},
+            snapshot_storage_rebuilder::{get_slot_and_append_vec_id, SnapshotStorageRebuilder},
         },
     },
     crossbeam_channel::{Receiver, Sender},
@@ -27,7 +28,7 @@ use {
         account_storage::{AccountStorageMap, AccountStoragesOrderer},
         account_storage_reader::AccountStorageReader,
         accounts_db::{AccountStorageEntry, AccountsDbConfig, AtomicAccountsFileId},
-        accounts_file::{AccountsFile, AccountsFileError, StorageAccess},
+        accounts_file::{AccountsFileError, StorageAccess},
         hardened_unpack::{self, UnpackError},
         utils::{move_and_async_delete_path, ACCOUNTS_RUN_DIR, ACCOUNTS_SNAPSHOT_DIR},
     },
@@ -54,9 +55,14 @@ use {
 };
 
 mod archive_format;
+mod seekable_archive;
 mod snapshot_interval;
 pub mod snapshot_storage_rebuilder;
-pub use {archive_format::*, snapshot_interval::SnapshotInterval};
+pub use {
+    archive_format::*,
+    seekable_archive::{SeekableArchiveEntry, SeekableSnapshotArchive},
+    snapshot_interval::SnapshotInterval,
+};
 
 pub const SNAPSHOT_STATUS_CACHE_FILENAME: &str = "status_cache";
 pub const SNAPSHOT_VERSION_FILENAME: &str = "version";
@@ -72,6 +78,8 @@ pub const SNAPSHOT_FULL_SNAPSHOT_SLOT_FILENAME: &str = "full_snapshot_slot";
 /// Specifically in `BANK_SNAPSHOTS_DIR/SLOT/`.
 /// This is also where the bank state is located in the snapshot archive.
 pub const BANK_SNAPSHOTS_DIR: &str = "snapshots";
+/// Directory of the account storage files in the snapshot archive
+const ACCOUNTS_DIR: &str = "accounts";
 pub const MAX_SNAPSHOT_DATA_FILE_SIZE: u64 = 32 * 1024 * 1024 * 1024; // 32 GiB
 const MAX_SNAPSHOT_VERSION_FILE_SIZE: u64 = 8; // byte
 const SNAPSHOT_FASTBOOT_VERSION: Version = Version::new(1, 0, 0);
@@ -85,9 +93,8 @@ pub const DEFAULT_MAX_FULL_SNAPSHOT_ARCHIVES_TO_RETAIN: NonZeroUsize =
     NonZeroUsize::new(2).unwrap();
 pub const DEFAULT_MAX_INCREMENTAL_SNAPSHOT_ARCHIVES_TO_RETAIN: NonZeroUsize =
     NonZeroUsize::new(4).unwrap();
-pub const FULL_SNAPSHOT_ARCHIVE_FILENAME_REGEX: &str =
-    r"^snapshot-(?P<slot>[[:digit:]]+)-(?P<hash>[[:alnum:]]+)\.(?P<ext>tar\.zst|tar\.lz4)$";
-pub const INCREMENTAL_SNAPSHOT_ARCHIVE_FILENAME_REGEX: &str = r"^incremental-snapshot-(?P<base>[[:digit:]]+)-(?P<slot>[[:digit:]]+)-(?P<hash>[[:alnum:]]+)\.(?P<ext>tar\.zst|tar\.lz4)$";
+pub const FULL_SNAPSHOT_ARCHIVE_FILENAME_REGEX: &str = r"^snapshot-(?P<slot>[[:digit:]]+)-(?P<hash>[[:alnum:]]+)\.(?P<ext>tar\.zst|tar\.lz4|seekable\.tar\.zst)$";
+pub const INCREMENTAL_SNAPSHOT_ARCHIVE_FILENAME_REGEX: &str = r"^incremental-snapshot-(?P<base>[[:digit:]]+)-(?P<slot>[[:digit:]]+)-(?P<hash>[[:alnum:]]+)\.(?P<ext>tar\.zst|tar\.lz4|seekable\.tar\.zst)$";
 
 // Allows scheduling a large number of reads such that temporary disk access delays
 // shouldn't block decompression (unless read bandwidth is saturated).
@@ -1060,7 +1067,6 @@ fn archive_snapshot(
     archive_format: ArchiveFormat,
 ) -> Result<SnapshotArchiveInfo> {
     use ArchiveSnapshotPackageError as E;
-    const ACCOUNTS_DIR: &str = "accounts";
     info!("Generating snapshot archive for slot {snapshot_slot}, kind: {snapshot_kind:?}");
 
     let mut timer = Measure::start("snapshot_package-package_snapshots");
@@ -1120,7 +1126,7 @@ fn archive_snapshot(
         let archive_file = fs::File::create(&staging_archive_path)
             .map_err(|err| E::CreateArchiveFile(err, staging_archive_path.clone()))?;
 
-        let do_archive_files = |encoder: &mut dyn Write| -> std::result::Result<(), E> {
+        let do_archive_files = |encoder: &mut dyn ArchiveWriter| -> std::result::Result<(), E> {
             let mut archive = tar::Builder::new(encoder);
             // Disable sparse file handling.  This seems to be the root cause of an issue when
             // upgrading v2.0 to v2.1, and the tar crate from 0.4.41 to 0.4.42.
@@ -1133,9 +1139,17 @@ fn archive_snapshot(
             archive.sparse(false);
             // Serialize the version and snapshots files before accounts so we can quickly determine the version
             // and other bank fields. This is necessary if we want to interleave unpacking with reconstruction
+            archive
+                .get_mut()
+                .start_entry(Path::new(SNAPSHOT_VERSION_FILENAME))
+                .map_err(E::ArchiveVersionFile)?;
             archive
                 .append_path_with_name(&staging_version_file, SNAPSHOT_VERSION_FILENAME)
                 .map_err(E::ArchiveVersionFile)?;
+            archive
+                .get_mut()
+                .start_entry(Path::new(BANK_SNAPSHOTS_DIR))
+                .map_err(E::ArchiveSnapshotsDir)?;
             archive
                 .append_dir_all(BANK_SNAPSHOTS_DIR, &staging_snapshots_dir)
                 .map_err(E::ArchiveSnapshotsDir)?;
@@ -1145,8 +1159,16 @@ fn archive_snapshot(
                 INTERLEAVE_TAR_ENTRIES_SMALL_TO_LARGE_RATIO,
             );
             for storage in storages_orderer.iter() {
-                let path_in_archive = Path::new(ACCOUNTS_DIR)
-                    .join(AccountsFile::file_name(storage.slot(), storage.id()));
+                let path_in_archive = Path::new(ACCOUNTS_DIR).join(storage.file_name());
+                archive
+                    .get_mut()
+                    .start_entry(&path_in_archive)
+                    .map_err(|err| {
+                        E::ArchiveAccountStorageFile(err, storage.path().to_path_buf())
+                    })?;
+                archive.get_mut().index_storage(storage).map_err(|err| {
+                    E::ArchiveAccountStorageFile(err, storage.path().to_path_buf())
+                })?;
 
                 let reader =
                     AccountStorageReader::new(storage, Some(snapshot_slot)).map_err(|err| {
@@ -1175,6 +1197,11 @@ fn archive_snapshot(
                 do_archive_files(&mut encoder)?;
                 encoder.finish().map_err(E::FinishEncoder)?;
             }
+            ArchiveFormat::TarZstdSeekable { config } => {
+                let mut encoder = SeekableZstdEncoder::new(archive_file, config.compression_level);
+                do_archive_files(&mut encoder)?;
+                encoder.finish().map_err(E::FinishEncoder)?;
+            }
             ArchiveFormat::TarLz4 => {
                 let mut encoder = lz4::EncoderBuilder::new()
                     .level(1)
@@ -1529,7 +1556,7 @@ pub fn hard_link_storages_to_snapshot(
         )?;
         // The appendvec could be recycled, so its filename may not be consistent to the slot and id.
         // Use the storage slot and id to compose a consistent file name for the hard-link file.
-        let hardlink_filename = AccountsFile::file_name(storage.slot(), storage.id());
+        let hardlink_filename = storage.file_name();
         let hard_link_path = snapshot_hardlink_dir.join(hardlink_filename);
         fs::hard_link(storage_path, &hard_link_path).map_err(|err| {
             HardLinkStoragesToSnapshotError::HardLinkStorage(
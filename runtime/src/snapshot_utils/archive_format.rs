@@ -5,17 +5,25 @@ use {
 
 // SUPPORTED_ARCHIVE_COMPRESSION lists the compression types that can be
 // specified on the command line.
-pub const SUPPORTED_ARCHIVE_COMPRESSION: &[&str] = &["zstd", "lz4"];
+pub const SUPPORTED_ARCHIVE_COMPRESSION: &[&str] = &["zstd", "lz4", "zstd-seekable"];
 pub const DEFAULT_ARCHIVE_COMPRESSION: &str = "zstd";
 
 pub const TAR_ZSTD_EXTENSION: &str = "tar.zst";
 pub const TAR_LZ4_EXTENSION: &str = "tar.lz4";
+pub const TAR_ZSTD_SEEKABLE_EXTENSION: &str = "seekable.tar.zst";
 
 /// The different archive formats used for snapshots
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Display)]
 pub enum ArchiveFormat {
-    TarZstd { config: ZstdConfig },
+    TarZstd {
+        config: ZstdConfig,
+    },
     TarLz4,
+    /// A zstd archive where every file is compressed in its own frames, and whose frames are
+    /// indexed, see [`SeekableSnapshotArchive`](super::SeekableSnapshotArchive)
+    TarZstdSeekable {
+        config: ZstdConfig,
+    },
 }
 
 impl ArchiveFormat {
@@ -24,6 +32,7 @@ impl ArchiveFormat {
         match self {
             ArchiveFormat::TarZstd { .. } => TAR_ZSTD_EXTENSION,
             ArchiveFormat::TarLz4 => TAR_LZ4_EXTENSION,
+            ArchiveFormat::TarZstdSeekable { .. } => TAR_ZSTD_SEEKABLE_EXTENSION,
         }
     }
 
@@ -33,6 +42,9 @@ impl ArchiveFormat {
                 config: ZstdConfig::default(),
             }),
             "lz4" => Some(ArchiveFormat::TarLz4),
+            "zstd-seekable" => Some(ArchiveFormat::TarZstdSeekable {
+                config: ZstdConfig::default(),
+            }),
             _ => None,
         }
     }
@@ -49,6 +61,9 @@ impl TryFrom<&str> for ArchiveFormat {
                 config: ZstdConfig::default(),
             }),
             TAR_LZ4_EXTENSION => Ok(ArchiveFormat::TarLz4),
+            TAR_ZSTD_SEEKABLE_EXTENSION => Ok(ArchiveFormat::TarZstdSeekable {
+                config: ZstdConfig::default(),
+            }),
             _ => Err(ParseError::InvalidExtension(extension.to_string())),
         }
     }
@@ -70,7 +85,8 @@ pub enum ArchiveFormatDecompressor<R> {
 impl<R: std::io::BufRead> ArchiveFormatDecompressor<R> {
     pub fn new(format: ArchiveFormat, input: R) -> std::io::Result<Self> {
         Ok(match format {
-            ArchiveFormat::TarZstd { .. } => {
+            // The index of seekable archives is in a skippable frame, which zstd ignores
+            ArchiveFormat::TarZstd { .. } | ArchiveFormat::TarZstdSeekable { .. } => {
                 Self::Zstd(zstd::stream::read::Decoder::with_buffer(input)?)
             }
             ArchiveFormat::TarLz4 => {
@@ -126,6 +142,13 @@ mod tests {
             TAR_ZSTD_EXTENSION
         );
         assert_eq!(ArchiveFormat::TarLz4.extension(), TAR_LZ4_EXTENSION);
+        assert_eq!(
+            ArchiveFormat::TarZstdSeekable {
+                config: ZstdConfig::default(),
+            }
+            .extension(),
+            TAR_ZSTD_SEEKABLE_EXTENSION
+        );
     }
 
     #[test]
@@ -140,6 +163,12 @@ mod tests {
             ArchiveFormat::try_from(TAR_LZ4_EXTENSION),
             Ok(ArchiveFormat::TarLz4)
         );
+        assert_eq!(
+            ArchiveFormat::try_from(TAR_ZSTD_SEEKABLE_EXTENSION),
+            Ok(ArchiveFormat::TarZstdSeekable {
+                config: ZstdConfig::default(),
+            })
+        );
         assert_eq!(
             ArchiveFormat::try_from(INVALID_EXTENSION),
             Err(ParseError::InvalidExtension(INVALID_EXTENSION.to_string()))
@@ -171,6 +200,9 @@ mod tests {
                 config: ZstdConfig::default(),
             }),
             Some(ArchiveFormat::TarLz4),
+            Some(ArchiveFormat::TarZstdSeekable {
+                config: ZstdConfig::default(),
+            }),
         ];
 
         for (arg, expected) in zip(SUPPORTED_ARCHIVE_COMPRESSION.iter(), golden.into_iter()) {
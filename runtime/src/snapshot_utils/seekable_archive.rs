@@ -0,0 +1,527 @@
+//! Snapshot archives made of seekable zstd frames
+//!
+//! A seekable archive is the usual tar stream of a snapshot compressed with zstd, except that
+//! every archived file starts a new zstd frame. The archive ends with a zstd skippable frame
+//! holding an index of the offset of the frames of every file. Any file, e.g. a single account
+//! storage, can then be read by seeking to its frames, without decompressing the whole archive.
+//!
+//! The account storage holding the latest version of every account is indexed as well, in
+//! skippable frames of pubkey sorted records, so that a single account is read by decompressing
+//! only the storage holding it.
+//!
+//! zstd decoders ignore skippable frames, so a seekable archive also unpacks like a `tar.zst` one.
+
+use {
+    super::{snapshot_storage_rebuilder::get_slot_and_append_vec_id, ACCOUNTS_DIR},
+    serde::{Deserialize, Serialize},
+    solana_account::{AccountSharedData, ReadableAccount},
+    solana_accounts_db::{
+        accounts_db::{AccountStorageEntry, AccountsFileId},
+        accounts_file::{AccountsFile, StorageAccess},
+    },
+    solana_clock::Slot,
+    solana_pubkey::Pubkey,
+    std::{
+        fs::{self, File},
+        io::{self, BufReader, Read, Seek, SeekFrom, Write},
+        path::{Path, PathBuf},
+    },
+};
+
+/// Magic number of the zstd skippable frame holding the index
+const SKIPPABLE_FRAME_MAGIC: u32 = 0x184D2A5E;
+/// Magic number at the very end of seekable archives
+const INDEX_FOOTER_MAGIC: [u8; 8] = *b"SNAPIDX1";
+/// Size of the index length and of the footer magic number, which end the archive
+const INDEX_FOOTER_SIZE: u64 = (size_of::<u32>() + INDEX_FOOTER_MAGIC.len()) as u64;
+/// Index larger than this are rejected, to not allocate arbitrary amounts of memory
+const MAX_INDEX_SIZE: u64 = 1024 * 1024 * 1024;
+/// Size of the skippable frame magic number and frame size
+const SKIPPABLE_FRAME_HEADER_SIZE: u64 = 2 * size_of::<u32>() as u64;
+/// Size of an account index record: the pubkey and the index of the entry of its storage
+const ACCOUNT_RECORD_SIZE: usize = size_of::<Pubkey>() + size_of::<u32>();
+/// Number of account index records in each of the skippable frames holding them
+const ACCOUNT_RECORDS_PER_CHUNK: usize = 64 * 1024;
+
+/// Location of the frames of an archived file or directory
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeekableArchiveEntry {
+    /// Path of the file or directory in the archive
+    pub path: String,
+    /// Offset of the first frame of the entry in the archive
+    pub frame_offset: u64,
+    /// Compressed size of the frames of the entry
+    pub frames_len: u64,
+}
+
+/// Location of a chunk of the account index records, which are sorted by pubkey
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct AccountIndexChunk {
+    /// Pubkey of the first record of the chunk
+    first_pubkey: Pubkey,
+    /// Offset of the records in the archive
+    offset: u64,
+    num_records: u32,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SeekableArchiveIndex {
+    entries: Vec<SeekableArchiveEntry>,
+    account_chunks: Vec<AccountIndexChunk>,
+}
+
+/// Writer of the snapshot archive tar stream
+pub(crate) trait ArchiveWriter: Write {
+    /// Called before the file or directory at `path` is appended to the tar stream
+    fn start_entry(&mut self, _path: &Path) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Called with the account storage of the entry started last
+    fn index_storage(&mut self, _storage: &AccountStorageEntry) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<W: Write> ArchiveWriter for zstd::stream::write::Encoder<'_, W> {}
+
+impl<W: Write> ArchiveWriter for lz4::Encoder<W> {}
+
+/// zstd encoder which compresses every archived file in new frames, and indexes them
+pub(crate) struct SeekableZstdEncoder<W: Write + Seek> {
+    // Only one of `encoder` and `writer` is set. Frames are only started once written to, so
+    // directly starting a new entry does not leave an empty frame behind.
+    encoder: Option<zstd::stream::write::Encoder<'static, W>>,
+    writer: Option<W>,
+    compression_level: i32,
+    index: SeekableArchiveIndex,
+    /// Pubkey, slot and entry index of every indexed account
+    accounts: Vec<(Pubkey, Slot, u32)>,
+}
+
+impl<W: Write + Seek> SeekableZstdEncoder<W> {
+    pub(crate) fn new(writer: W, compression_level: i32) -> Self {
+        Self {
+            encoder: None,
+            writer: Some(writer),
+            compression_level,
+            index: SeekableArchiveIndex::default(),
+            accounts: Vec::new(),
+        }
+    }
+
+    /// Ends the current frame, and returns the offset of the next one
+    fn end_frame(&mut self) -> io::Result<u64> {
+        if let Some(encoder) = self.encoder.take() {
+            self.writer = Some(encoder.finish()?);
+        }
+        let writer = self
+            .writer
+            .as_mut()
+            .expect("writer is set once the frame ended");
+        let offset = writer.stream_position()?;
+        if let Some(last_entry) = self.index.entries.last_mut() {
+            last_entry.frames_len = offset - last_entry.frame_offset;
+        }
+        Ok(offset)
+    }
+
+    /// Ends the last frame and writes the index
+    pub(crate) fn finish(mut self) -> io::Result<W> {
+        self.end_frame()?;
+        let mut writer = self
+            .writer
+            .take()
+            .expect("writer is set once the frame ended");
+
+        // Only the newest storage of every account is indexed
+        let mut accounts = std::mem::take(&mut self.accounts);
+        accounts.sort_unstable_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)));
+        accounts.dedup_by_key(|(pubkey, _slot, _entry_index)| *pubkey);
+        for chunk in accounts.chunks(ACCOUNT_RECORDS_PER_CHUNK) {
+            let chunk_size = (chunk.len() * ACCOUNT_RECORD_SIZE) as u32;
+            writer.write_all(&SKIPPABLE_FRAME_MAGIC.to_le_bytes())?;
+            writer.write_all(&chunk_size.to_le_bytes())?;
+            self.index.account_chunks.push(AccountIndexChunk {
+                first_pubkey: chunk[0].0,
+                offset: writer.stream_position()?,
+                num_records: chunk.len() as u32,
+            });
+            for (pubkey, _slot, entry_index) in chunk {
+                writer.write_all(pubkey.as_ref())?;
+                writer.write_all(&entry_index.to_le_bytes())?;
+            }
+        }
+
+        let index = bincode::serialize(&self.index).map_err(io::Error::other)?;
+        let index_len = u32::try_from(index.len()).map_err(io::Error::other)?;
+        let frame_size = index_len
+            .checked_add(INDEX_FOOTER_SIZE as u32)
+            .ok_or_else(|| io::Error::other("seekable archive index is too large"))?;
+        writer.write_all(&SKIPPABLE_FRAME_MAGIC.to_le_bytes())?;
+        writer.write_all(&frame_size.to_le_bytes())?;
+        writer.write_all(&index)?;
+        writer.write_all(&index_len.to_le_bytes())?;
+        writer.write_all(&INDEX_FOOTER_MAGIC)?;
+        writer.flush()?;
+        Ok(writer)
+    }
+}
+
+impl<W: Write + Seek> Write for SeekableZstdEncoder<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.encoder.is_none() {
+            let writer = self
+                .writer
+                .take()
+                .expect("writer is set when no frame is started");
+            self.encoder = Some(zstd::stream::write::Encoder::new(
+                writer,
+                self.compression_level,
+            )?);
+        }
+        self.encoder.as_mut().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match (&mut self.encoder, &mut self.writer) {
+            (Some(encoder), _) => encoder.flush(),
+            (None, Some(writer)) => writer.flush(),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+impl<W: Write + Seek> ArchiveWriter for SeekableZstdEncoder<W> {
+    fn start_entry(&mut self, path: &Path) -> io::Result<()> {
+        let path = path
+            .to_str()
+            .ok_or_else(|| io::Error::other(format!("invalid archive path: {}", path.display())))?;
+        let frame_offset = self.end_frame()?;
+        self.index.entries.push(SeekableArchiveEntry {
+            path: path.to_string(),
+            frame_offset,
+            frames_len: 0,
+        });
+        Ok(())
+    }
+
+    fn index_storage(&mut self, storage: &AccountStorageEntry) -> io::Result<()> {
+        let entry_index = self
+            .index
+            .entries
+            .len()
+            .checked_sub(1)
+            .ok_or_else(|| io::Error::other("account storage indexed before its entry"))?;
+        let entry_index = u32::try_from(entry_index).map_err(io::Error::other)?;
+        let slot = storage.slot();
+        storage
+            .accounts
+            .scan_pubkeys(|pubkey| self.accounts.push((*pubkey, slot, entry_index)))
+            .map_err(io::Error::other)
+    }
+}
+
+/// A snapshot archive of the `TarZstdSeekable` format, whose files can be read individually
+pub struct SeekableSnapshotArchive {
+    file: File,
+    entries: Vec<SeekableArchiveEntry>,
+    account_chunks: Vec<AccountIndexChunk>,
+}
+
+impl SeekableSnapshotArchive {
+    /// Opens the archive at `path` and reads its index
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let invalid_archive = |reason: &str| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "invalid seekable snapshot archive '{}': {reason}",
+                    path.display()
+                ),
+            )
+        };
+
+        let mut file = File::open(path)?;
+        let archive_len = file.metadata()?.len();
+        if archive_len < INDEX_FOOTER_SIZE {
+            return Err(invalid_archive("too small"));
+        }
+        file.seek(SeekFrom::End(-(INDEX_FOOTER_SIZE as i64)))?;
+        let mut index_len = [0; size_of::<u32>()];
+        file.read_exact(&mut index_len)?;
+        let mut footer_magic = [0; INDEX_FOOTER_MAGIC.len()];
+        file.read_exact(&mut footer_magic)?;
+        if footer_magic != INDEX_FOOTER_MAGIC {
+            return Err(invalid_archive("index not found"));
+        }
+        let index_len = u64::from(u32::from_le_bytes(index_len));
+        if index_len > MAX_INDEX_SIZE || index_len + INDEX_FOOTER_SIZE > archive_len {
+            return Err(invalid_archive("invalid index length"));
+        }
+
+        file.seek(SeekFrom::End(-((index_len + INDEX_FOOTER_SIZE) as i64)))?;
+        let mut index = vec![0; index_len as usize];
+        file.read_exact(&mut index)?;
+        let SeekableArchiveIndex {
+            entries,
+            account_chunks,
+        } = bincode::deserialize(&index).map_err(|_| invalid_archive("corrupt index"))?;
+        let is_valid_chunk = |chunk: &AccountIndexChunk| {
+            chunk.num_records as usize <= ACCOUNT_RECORDS_PER_CHUNK
+                && chunk.offset >= SKIPPABLE_FRAME_HEADER_SIZE
+                && chunk.offset + chunk.num_records as u64 * ACCOUNT_RECORD_SIZE as u64
+                    <= archive_len
+        };
+        if !account_chunks.iter().all(is_valid_chunk) {
+            return Err(invalid_archive("corrupt account index"));
+        }
+        Ok(Self {
+            file,
+            entries,
+            account_chunks,
+        })
+    }
+
+    /// Returns the archived files and directories, in archive order
+    pub fn entries(&self) -> &[SeekableArchiveEntry] {
+        &self.entries
+    }
+
+    /// Returns the slot, id and path of the archived account storage files, newest slot first
+    pub fn storages(&self) -> Vec<(Slot, AccountsFileId, &str)> {
+        let mut storages: Vec<_> = self
+            .entries
+            .iter()
+            .filter_map(|entry| {
+                let file_name = entry.path.strip_prefix(ACCOUNTS_DIR)?.strip_prefix('/')?;
+                let (slot, id) = get_slot_and_append_vec_id(file_name).ok()?;
+                Some((slot, id as AccountsFileId, entry.path.as_str()))
+            })
+            .collect();
+        storages.sort_unstable_by(|a, b| b.cmp(a));
+        storages
+    }
+
+    /// Writes the contents of the archived file at `path` to `dst`
+    ///
+    /// Only the frames of the entry holding the file are decompressed. Returns the size of the
+    /// file.
+    pub fn unpack_file(&mut self, path: &str, dst: &mut impl Write) -> io::Result<u64> {
+        let entry = self
+            .entries
+            .iter()
+            .find(|entry| {
+                path == entry.path
+                    || path
+                        .strip_prefix(&entry.path)
+                        .is_some_and(|path| path.starts_with('/'))
+            })
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("file not found in archive: {path}"),
+                )
+            })?;
+
+        self.file.seek(SeekFrom::Start(entry.frame_offset))?;
+        let frames = BufReader::new((&mut self.file).take(entry.frames_len));
+        let mut archive = tar::Archive::new(zstd::stream::read::Decoder::with_buffer(frames)?);
+        for tar_entry in archive.entries()? {
+            let mut tar_entry = tar_entry?;
+            if tar_entry.path()? == Path::new(path) {
+                return io::copy(&mut tar_entry, dst);
+            }
+        }
+        Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("file not found in archive frames: {path}"),
+        ))
+    }
+
+    /// Unpacks the archived account storage file at `path` into `dst_dir`
+    ///
+    /// Returns the path of the unpacked file.
+    pub fn unpack_storage(&mut self, path: &str, dst_dir: impl AsRef<Path>) -> io::Result<PathBuf> {
+        let file_name = Path::new(path)
+            .file_name()
+            .ok_or_else(|| io::Error::other(format!("invalid storage path: {path}")))?;
+        let dst_path = dst_dir.as_ref().join(file_name);
+        let mut dst = File::create(&dst_path)?;
+        self.unpack_file(path, &mut dst)?;
+        dst.flush()?;
+        Ok(dst_path)
+    }
+
+    /// Returns the path of the archived account storage file holding the latest version of the
+    /// account of `pubkey`, as found in the account index
+    pub fn find_account_storage(&mut self, pubkey: &Pubkey) -> io::Result<Option<&str>> {
+        let chunk_index = self
+            .account_chunks
+            .partition_point(|chunk| chunk.first_pubkey <= *pubkey);
+        let Some(chunk) = chunk_index
+            .checked_sub(1)
+            .map(|chunk_index| &self.account_chunks[chunk_index])
+        else {
+            return Ok(None);
+        };
+
+        self.file.seek(SeekFrom::Start(chunk.offset))?;
+        let mut records = vec![0; chunk.num_records as usize * ACCOUNT_RECORD_SIZE];
+        self.file.read_exact(&mut records)?;
+        let records: Vec<_> = records.chunks_exact(ACCOUNT_RECORD_SIZE).collect();
+        let Ok(record_index) =
+            records.binary_search_by(|record| record[..size_of::<Pubkey>()].cmp(pubkey.as_ref()))
+        else {
+            return Ok(None);
+        };
+        let entry_index = u32::from_le_bytes(
+            records[record_index][size_of::<Pubkey>()..]
+                .try_into()
+                .unwrap(),
+        );
+        self.entries
+            .get(entry_index as usize)
+            .map(|entry| Some(entry.path.as_str()))
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "corrupt account index"))
+    }
+
+    /// Returns the latest version of the account of `pubkey` in the archive, and its slot
+    ///
+    /// Only the account storage holding the account, as found in the account index, is
+    /// unpacked into a temporary directory. Accounts which were deleted, i.e. whose latest
+    /// version has zero lamports, are returned as is.
+    pub fn get_account(
+        &mut self,
+        pubkey: &Pubkey,
+    ) -> io::Result<Option<(AccountSharedData, Slot)>> {
+        let Some(path) = self.find_account_storage(pubkey)?.map(str::to_string) else {
+            return Ok(None);
+        };
+        let file_name = Path::new(&path)
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .ok_or_else(|| io::Error::other(format!("invalid storage path: {path}")))?;
+        let (slot, _id) = get_slot_and_append_vec_id(file_name).map_err(io::Error::other)?;
+
+        let unpack_dir = tempfile::tempdir()?;
+        let storage_path = self.unpack_storage(&path, unpack_dir.path())?;
+        let storage_len = fs::metadata(&storage_path)?.len() as usize;
+        // The storage file is removed when `storage` is dropped
+        let storage =
+            AccountsFile::new_for_startup(&storage_path, storage_len, StorageAccess::File)
+                .map_err(io::Error::other)?;
+
+        // Accounts may be stored more than once in a storage, the last one is the latest
+        let mut latest_offset = None;
+        storage
+            .scan_accounts_without_data(|offset, account| {
+                if account.pubkey() == pubkey {
+                    latest_offset = Some(offset);
+                }
+            })
+            .map_err(io::Error::other)?;
+        Ok(latest_offset
+            .and_then(|offset| {
+                storage
+                    .get_stored_account_callback(offset, |account| account.to_account_shared_data())
+            })
+            .map(|account| (account, slot)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::io::Cursor};
+
+    #[test]
+    fn test_seekable_archive() {
+        let files = [
+            ("version", b"1.2.0".to_vec()),
+            ("accounts/3.1", vec![3; 10_000]),
+            ("accounts/5.2", vec![5; 1000]),
+        ];
+
+        let mut encoder = SeekableZstdEncoder::new(Cursor::new(vec![]), 0);
+        {
+            let mut archive = tar::Builder::new(&mut encoder);
+            for (path, contents) in &files {
+                archive.get_mut().start_entry(Path::new(path)).unwrap();
+                let mut header = tar::Header::new_gnu();
+                header.set_path(path).unwrap();
+                header.set_size(contents.len() as u64);
+                header.set_cksum();
+                archive.append(&header, contents.as_slice()).unwrap();
+            }
+            archive.finish().unwrap();
+        }
+        let archive_bytes = encoder.finish().unwrap().into_inner();
+
+        // the archive unpacks like a regular tar.zst one
+        let mut archive =
+            tar::Archive::new(zstd::stream::read::Decoder::new(&*archive_bytes).unwrap());
+        let unpacked: Vec<_> = archive
+            .entries()
+            .unwrap()
+            .map(|entry| {
+                let mut entry = entry.unwrap();
+                let path = entry.path().unwrap().to_str().unwrap().to_string();
+                let mut contents = vec![];
+                entry.read_to_end(&mut contents).unwrap();
+                (path, contents)
+            })
+            .collect();
+        assert_eq!(
+            unpacked,
+            files
+                .iter()
+                .map(|(path, contents)| (path.to_string(), contents.clone()))
+                .collect::<Vec<_>>()
+        );
+
+        let archive_dir = tempfile::TempDir::new().unwrap();
+        let archive_path = archive_dir.path().join("snapshot.seekable.tar.zst");
+        fs::write(&archive_path, &archive_bytes).unwrap();
+        let mut archive = SeekableSnapshotArchive::open(&archive_path).unwrap();
+        assert_eq!(
+            archive
+                .entries()
+                .iter()
+                .map(|entry| entry.path.as_str())
+                .collect::<Vec<_>>(),
+            vec!["version", "accounts/3.1", "accounts/5.2"]
+        );
+        assert_eq!(
+            archive.storages(),
+            vec![(5, 2, "accounts/5.2"), (3, 1, "accounts/3.1")]
+        );
+
+        // every file is read from its own frames
+        for (path, contents) in &files {
+            let mut unpacked = vec![];
+            assert_eq!(
+                archive.unpack_file(path, &mut unpacked).unwrap(),
+                contents.len() as u64
+            );
+            assert_eq!(&unpacked, contents);
+        }
+        assert_eq!(
+            archive
+                .unpack_file("accounts/4.1", &mut vec![])
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::NotFound
+        );
+
+        // archives without index are rejected
+        fs::write(&archive_path, &archive_bytes[..archive_bytes.len() - 1]).unwrap();
+        assert_eq!(
+            SeekableSnapshotArchive::open(&archive_path)
+                .err()
+                .unwrap()
+                .kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+}
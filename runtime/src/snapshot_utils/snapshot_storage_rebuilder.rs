@@ -17,7 +17,7 @@ use {
     solana_accounts_db::{
         account_storage::AccountStorageMap,
         accounts_db::{AccountsFileId, AtomicAccountsFileId},
-        accounts_file::StorageAccess,
+        accounts_file::{StorageAccess, TIERED_STORAGE_FILE_EXTENSION},
     },
     solana_clock::Slot,
     solana_nohash_hasher::BuildNoHashHasher,
@@ -327,9 +327,13 @@ impl SnapshotStorageRebuilder {
     }
 }
 
-/// Get the slot and append vec id from the filename
+/// Get the slot and append vec id from the filename, of an append vec or of a tiered storage
 pub(crate) fn get_slot_and_append_vec_id(filename: &str) -> Result<(Slot, usize), SnapshotError> {
-    let mut parts = filename.splitn(2, '.');
+    let name = filename
+        .strip_suffix(TIERED_STORAGE_FILE_EXTENSION)
+        .and_then(|name| name.strip_suffix('.'))
+        .unwrap_or(filename);
+    let mut parts = name.splitn(2, '.');
     let slot = parts.next().and_then(|s| Slot::from_str(s).ok());
     let id = parts.next().and_then(|s| usize::from_str(s).ok());
 
@@ -350,5 +354,15 @@ mod tests {
                 .unwrap();
         assert_eq!(expected_slot, slot);
         assert_eq!(expected_id as usize, id);
+
+        let (slot, id) = get_slot_and_append_vec_id(&AccountsFile::tiered_storage_file_name(
+            expected_slot,
+            expected_id,
+        ))
+        .unwrap();
+        assert_eq!(expected_slot, slot);
+        assert_eq!(expected_id as usize, id);
+
+        assert!(get_slot_and_append_vec_id("12345.9987.cold").is_err());
     }
 }
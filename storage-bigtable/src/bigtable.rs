@@ -838,7 +838,7 @@ impl<F: FnMut(Request<()>) -> InterceptedRequestResult> BigTable<F> {
         let mut bytes_written = 0;
         let mut new_row_data = vec![];
         for (row_key, data) in cells {
-            let data = compress_best(&bincode::serialize(&data).unwrap())?;
+            let data = serialize_bincode_cell_data(data)?;
             bytes_written += data.len();
             new_row_data.push((row_key, vec![("bin".to_string(), data)]));
         }
@@ -858,9 +858,7 @@ impl<F: FnMut(Request<()>) -> InterceptedRequestResult> BigTable<F> {
         let mut bytes_written = 0;
         let mut new_row_data = vec![];
         for (row_key, data) in cells {
-            let mut buf = Vec::with_capacity(data.encoded_len());
-            data.encode(&mut buf).unwrap();
-            let data = compress_best(&buf)?;
+            let data = serialize_protobuf_cell_data(data)?;
             bytes_written += data.len();
             new_row_data.push((row_key, vec![("proto".to_string(), data)]));
         }
@@ -960,6 +958,22 @@ where
     })
 }
 
+pub(crate) fn serialize_protobuf_cell_data<T>(data: &T) -> Result<CellValue>
+where
+    T: prost::Message,
+{
+    let mut buf = Vec::with_capacity(data.encoded_len());
+    data.encode(&mut buf).unwrap();
+    Ok(compress_best(&buf)?)
+}
+
+pub(crate) fn serialize_bincode_cell_data<T>(data: &T) -> Result<CellValue>
+where
+    T: serde::ser::Serialize,
+{
+    Ok(compress_best(&bincode::serialize(data).unwrap())?)
+}
+
 #[cfg(test)]
 mod tests {
     use {
@@ -1026,6 +1040,8 @@ mod tests {
                 return_data: Some(TransactionReturnData::default()),
                 compute_units_consumed: Some(1234),
                 cost_units: Some(5678),
+                log_events: None,
+                syscall_compute_units: None,
             },
         });
         let expected_block = ConfirmedBlock {
@@ -1087,6 +1103,8 @@ mod tests {
                 meta.return_data = None; // Legacy bincode implementation does not support return data
                 meta.compute_units_consumed = None; // Legacy bincode implementation does not support CU consumed
                 meta.cost_units = None; // Legacy bincode implementation does not support CU
+                meta.log_events = None; // Legacy bincode implementation does not support log events
+                meta.syscall_compute_units = None; // Legacy bincode implementation does not support syscall CUs
             }
             assert_eq!(block, bincode_block.into());
         } else {
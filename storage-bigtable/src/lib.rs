@@ -3,6 +3,7 @@
 use {
     crate::bigtable::RowKey,
     agave_reserved_account_keys::ReservedAccountKeys,
+    async_trait::async_trait,
     log::*,
     serde::{Deserialize, Serialize},
     solana_clock::{Slot, UnixTimestamp},
@@ -44,8 +45,11 @@ extern crate serde_derive;
 mod access_token;
 mod bigtable;
 mod compression;
+mod local_storage;
 mod root_ca_certificate;
 
+pub use crate::local_storage::LocalLedgerStorage;
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("BigTable: {0}")]
@@ -258,6 +262,8 @@ impl From<StoredConfirmedBlockTransactionStatusMeta> for TransactionStatusMeta {
             return_data: None,
             compute_units_consumed: None,
             cost_units: None,
+            log_events: None,
+            syscall_compute_units: None,
         }
     }
 }
@@ -381,6 +387,297 @@ impl From<LegacyTransactionByAddrInfo> for TransactionByAddrInfo {
     }
 }
 
+// The cells written to each table for a block, see `upload_confirmed_block_with_entries()`
+struct BlockCells {
+    tx_cells: Vec<(RowKey, TransactionInfo)>,
+    tx_by_addr_cells: Vec<(RowKey, tx_by_addr::TransactionByAddr)>,
+    entry_cell: Option<(RowKey, entries::Entries)>,
+    num_entries: usize,
+    blocks_cell: (RowKey, generated::ConfirmedBlock),
+}
+
+impl BlockCells {
+    fn new(slot: Slot, confirmed_block: VersionedConfirmedBlockWithEntries) -> Self {
+        let mut by_addr: HashMap<&Pubkey, Vec<TransactionByAddrInfo>> = HashMap::new();
+        let VersionedConfirmedBlockWithEntries {
+            block: confirmed_block,
+            entries,
+        } = confirmed_block;
+
+        let reserved_account_keys = ReservedAccountKeys::new_all_activated();
+        let mut tx_cells = Vec::with_capacity(confirmed_block.transactions.len());
+        for (index, transaction_with_meta) in confirmed_block.transactions.iter().enumerate() {
+            let VersionedTransactionWithStatusMeta { meta, transaction } = transaction_with_meta;
+            let err = meta.status.clone().err();
+            let index = index as u32;
+            let signature = transaction.signatures[0];
+            let memo = extract_and_fmt_memos(transaction_with_meta);
+
+            for address in transaction_with_meta.account_keys().iter() {
+                // Historical note that previously only a set of sysvar ids were
+                // skipped from being uploaded. Now we skip uploaded for the set
+                // of all reserved account keys which will continue to grow in
+                // the future.
+                if !reserved_account_keys.is_reserved(address) {
+                    by_addr
+                        .entry(address)
+                        .or_default()
+                        .push(TransactionByAddrInfo {
+                            signature,
+                            err: err.clone(),
+                            index,
+                            memo: memo.clone(),
+                            block_time: confirmed_block.block_time,
+                        });
+                }
+            }
+
+            tx_cells.push((
+                signature.to_string(),
+                TransactionInfo {
+                    slot,
+                    index,
+                    err,
+                    memo,
+                },
+            ));
+        }
+
+        let tx_by_addr_cells: Vec<_> = by_addr
+            .into_iter()
+            .map(|(address, transaction_info_by_addr)| {
+                (
+                    format!("{}/{}", address, slot_to_tx_by_addr_key(slot)),
+                    tx_by_addr::TransactionByAddr {
+                        tx_by_addrs: transaction_info_by_addr
+                            .into_iter()
+                            .map(|by_addr| by_addr.into())
+                            .collect(),
+                    },
+                )
+            })
+            .collect();
+
+        let num_entries = entries.len();
+        let entry_cell = (num_entries > 0).then(|| {
+            (
+                slot_to_entries_key(slot),
+                entries::Entries {
+                    entries: entries.into_iter().enumerate().map(Into::into).collect(),
+                },
+            )
+        });
+
+        Self {
+            tx_cells,
+            tx_by_addr_cells,
+            entry_cell,
+            num_entries,
+            blocks_cell: (slot_to_blocks_key(slot), confirmed_block.into()),
+        }
+    }
+}
+
+fn confirmed_block_from_cell_data(
+    slot: Slot,
+    block_cell_data: bigtable::CellData<StoredConfirmedBlock, generated::ConfirmedBlock>,
+) -> Result<ConfirmedBlock> {
+    Ok(match block_cell_data {
+        bigtable::CellData::Bincode(block) => block.into(),
+        bigtable::CellData::Protobuf(block) => block.try_into().map_err(|_err| {
+            bigtable::Error::ObjectCorrupt(format!("blocks/{}", slot_to_blocks_key(slot)))
+        })?,
+    })
+}
+
+// Return the transaction at `index` of `block`, if it is the one with `signature`
+fn confirmed_transaction_from_block(
+    signature: &Signature,
+    slot: Slot,
+    index: u32,
+    block: ConfirmedBlock,
+) -> Option<ConfirmedTransactionWithStatusMeta> {
+    match block.transactions.into_iter().nth(index as usize) {
+        None => {
+            // report this somewhere actionable?
+            warn!("Transaction info for {signature} is corrupt");
+            None
+        }
+        Some(tx_with_meta) => {
+            if tx_with_meta.transaction_signature() != signature {
+                warn!("Transaction info or confirmed block for {signature} is corrupt");
+                None
+            } else {
+                Some(ConfirmedTransactionWithStatusMeta {
+                    slot,
+                    tx_with_meta,
+                    block_time: block.block_time,
+                })
+            }
+        }
+    }
+}
+
+// Collect the signatures of the `tx-by-addr` rows listed from `(first_slot,
+// before_transaction_index)` down to `(last_slot, until_transaction_index)`, both excluded
+fn signatures_for_address_from_rows(
+    address_prefix: &str,
+    tx_by_addr_data: Vec<(RowKey, bigtable::RowData)>,
+    (first_slot, before_transaction_index): (Slot, u32),
+    (last_slot, until_transaction_index): (Slot, u32),
+    limit: usize,
+) -> Result<
+    Vec<(
+        ConfirmedTransactionStatusWithSignature,
+        u32, /*slot index*/
+    )>,
+> {
+    let mut infos = vec![];
+
+    'outer: for (row_key, data) in tx_by_addr_data {
+        let slot = !key_to_slot(&row_key[address_prefix.len()..]).ok_or_else(|| {
+            bigtable::Error::ObjectCorrupt(format!(
+                "Failed to convert key to slot: tx-by-addr/{row_key}"
+            ))
+        })?;
+
+        let deserialized_cell_data = bigtable::deserialize_protobuf_or_bincode_cell_data::<
+            Vec<LegacyTransactionByAddrInfo>,
+            tx_by_addr::TransactionByAddr,
+        >(&data, "tx-by-addr", row_key.clone())?;
+
+        let mut cell_data: Vec<TransactionByAddrInfo> = match deserialized_cell_data {
+            bigtable::CellData::Bincode(tx_by_addr) => {
+                tx_by_addr.into_iter().map(|legacy| legacy.into()).collect()
+            }
+            bigtable::CellData::Protobuf(tx_by_addr) => tx_by_addr.try_into().map_err(|error| {
+                bigtable::Error::ObjectCorrupt(format!(
+                    "Failed to deserialize: {}: tx-by-addr/{}",
+                    error,
+                    row_key.clone()
+                ))
+            })?,
+        };
+
+        cell_data.reverse();
+        for tx_by_addr_info in cell_data.into_iter() {
+            // Filter out records before `before_transaction_index`
+            if slot == first_slot && tx_by_addr_info.index >= before_transaction_index {
+                continue;
+            }
+            // Filter out records after `until_transaction_index`
+            if slot == last_slot && tx_by_addr_info.index <= until_transaction_index {
+                continue;
+            }
+            infos.push((
+                ConfirmedTransactionStatusWithSignature {
+                    signature: tx_by_addr_info.signature,
+                    slot,
+                    err: tx_by_addr_info.err,
+                    memo: tx_by_addr_info.memo,
+                    block_time: tx_by_addr_info.block_time,
+                },
+                tx_by_addr_info.index,
+            ));
+            // Respect limit
+            if infos.len() >= limit {
+                break 'outer;
+            }
+        }
+    }
+    Ok(infos)
+}
+
+// Return the `tx-by-addr` row keys and the expected `tx` rows of a block, to delete it
+fn block_rows(
+    slot: Slot,
+    confirmed_block: &ConfirmedBlock,
+) -> (Vec<RowKey>, HashMap<String, UploadedTransaction>) {
+    let mut addresses: HashSet<&Pubkey> = HashSet::new();
+    let mut expected_tx_infos: HashMap<String, UploadedTransaction> = HashMap::new();
+    for (index, transaction_with_meta) in confirmed_block.transactions.iter().enumerate() {
+        match transaction_with_meta {
+            TransactionWithStatusMeta::MissingMetadata(transaction) => {
+                let signature = transaction.signatures[0];
+                let index = index as u32;
+                let err = None;
+
+                for address in transaction.message.account_keys.iter() {
+                    // We could skip deleting addresses that are known
+                    // reserved keys but it's hard to be sure whether we
+                    // previously uploaded rows for reserved keys or not. So
+                    // to ensure everything is deleted properly, we attempt
+                    // to delete rows for all addresses even if they might
+                    // not have been uploaded.
+                    addresses.insert(address);
+                }
+
+                expected_tx_infos.insert(
+                    signature.to_string(),
+                    UploadedTransaction { slot, index, err },
+                );
+            }
+            TransactionWithStatusMeta::Complete(tx_with_meta) => {
+                let VersionedTransactionWithStatusMeta { transaction, meta } = tx_with_meta;
+                let signature = transaction.signatures[0];
+                let index = index as u32;
+                let err = meta.status.clone().err();
+
+                for address in tx_with_meta.account_keys().iter() {
+                    // We could skip deleting addresses that are known
+                    // reserved keys but it's hard to be sure whether we
+                    // previously uploaded rows for reserved keys or not. So
+                    // to ensure everything is deleted properly, we attempt
+                    // to delete rows for all addresses even if they might
+                    // not have been uploaded.
+                    addresses.insert(address);
+                }
+
+                expected_tx_infos.insert(
+                    signature.to_string(),
+                    UploadedTransaction { slot, index, err },
+                );
+            }
+        }
+    }
+
+    let address_slot_rows = addresses
+        .into_iter()
+        .map(|address| format!("{}/{}", address, slot_to_tx_by_addr_key(slot)))
+        .collect();
+    (address_slot_rows, expected_tx_infos)
+}
+
+// Return the `tx` rows which still hold the transaction info expected for them
+fn tx_rows_to_delete(
+    expected_tx_infos: HashMap<String, UploadedTransaction>,
+    fetched_tx_infos: HashMap<String, bigtable::Result<UploadedTransaction>>,
+) -> Vec<RowKey> {
+    let mut deletion_rows = Vec::with_capacity(expected_tx_infos.len());
+    for (signature, expected_tx_info) in expected_tx_infos {
+        match fetched_tx_infos.get(&signature) {
+            Some(Ok(fetched_tx_info)) if fetched_tx_info == &expected_tx_info => {
+                deletion_rows.push(signature);
+            }
+            Some(Ok(fetched_tx_info)) => {
+                warn!(
+                    "skipped tx row {} because the bigtable entry ({:?}) did not match to {:?}",
+                    signature, fetched_tx_info, &expected_tx_info,
+                );
+            }
+            Some(Err(err)) => {
+                warn!(
+                    "skipped tx row {signature} because the bigtable entry was corrupted: {err:?}"
+                );
+            }
+            None => {
+                warn!("skipped tx row {signature} because it was not found");
+            }
+        }
+    }
+    deletion_rows
+}
+
 pub const DEFAULT_INSTANCE_NAME: &str = "solana-ledger";
 pub const DEFAULT_APP_PROFILE_ID: &str = "default";
 pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 64 * 1024 * 1024; // 64MB
@@ -585,12 +882,7 @@ impl LedgerStorage {
                 bigtable::Error::RowNotFound => Error::BlockNotFound(slot),
                 _ => err.into(),
             })?;
-        Ok(match block_cell_data {
-            bigtable::CellData::Bincode(block) => block.into(),
-            bigtable::CellData::Protobuf(block) => block.try_into().map_err(|_err| {
-                bigtable::Error::ObjectCorrupt(format!("blocks/{}", slot_to_blocks_key(slot)))
-            })?,
-        })
+        confirmed_block_from_cell_data(slot, block_cell_data)
     }
 
     /// Does the confirmed block exist in the Bigtable
@@ -715,25 +1007,9 @@ impl LedgerStorage {
 
         // Load the block and return the transaction
         let block = self.get_confirmed_block(slot).await?;
-        match block.transactions.into_iter().nth(index as usize) {
-            None => {
-                // report this somewhere actionable?
-                warn!("Transaction info for {signature} is corrupt");
-                Ok(None)
-            }
-            Some(tx_with_meta) => {
-                if tx_with_meta.transaction_signature() != signature {
-                    warn!("Transaction info or confirmed block for {signature} is corrupt");
-                    Ok(None)
-                } else {
-                    Ok(Some(ConfirmedTransactionWithStatusMeta {
-                        slot,
-                        tx_with_meta,
-                        block_time: block.block_time,
-                    }))
-                }
-            }
-        }
+        Ok(confirmed_transaction_from_block(
+            signature, slot, index, block,
+        ))
     }
 
     /// Get confirmed signatures for the provided address, in descending ledger order
@@ -791,8 +1067,6 @@ impl LedgerStorage {
             }
         };
 
-        let mut infos = vec![];
-
         let starting_slot_tx_len = bigtable
             .get_protobuf_or_bincode_cell::<Vec<LegacyTransactionByAddrInfo>, tx_by_addr::TransactionByAddr>(
                 "tx-by-addr",
@@ -826,60 +1100,13 @@ impl LedgerStorage {
             )
             .await?;
 
-        'outer: for (row_key, data) in tx_by_addr_data {
-            let slot = !key_to_slot(&row_key[address_prefix.len()..]).ok_or_else(|| {
-                bigtable::Error::ObjectCorrupt(format!(
-                    "Failed to convert key to slot: tx-by-addr/{row_key}"
-                ))
-            })?;
-
-            let deserialized_cell_data = bigtable::deserialize_protobuf_or_bincode_cell_data::<
-                Vec<LegacyTransactionByAddrInfo>,
-                tx_by_addr::TransactionByAddr,
-            >(&data, "tx-by-addr", row_key.clone())?;
-
-            let mut cell_data: Vec<TransactionByAddrInfo> = match deserialized_cell_data {
-                bigtable::CellData::Bincode(tx_by_addr) => {
-                    tx_by_addr.into_iter().map(|legacy| legacy.into()).collect()
-                }
-                bigtable::CellData::Protobuf(tx_by_addr) => {
-                    tx_by_addr.try_into().map_err(|error| {
-                        bigtable::Error::ObjectCorrupt(format!(
-                            "Failed to deserialize: {}: tx-by-addr/{}",
-                            error,
-                            row_key.clone()
-                        ))
-                    })?
-                }
-            };
-
-            cell_data.reverse();
-            for tx_by_addr_info in cell_data.into_iter() {
-                // Filter out records before `before_transaction_index`
-                if slot == first_slot && tx_by_addr_info.index >= before_transaction_index {
-                    continue;
-                }
-                // Filter out records after `until_transaction_index`
-                if slot == last_slot && tx_by_addr_info.index <= until_transaction_index {
-                    continue;
-                }
-                infos.push((
-                    ConfirmedTransactionStatusWithSignature {
-                        signature: tx_by_addr_info.signature,
-                        slot,
-                        err: tx_by_addr_info.err,
-                        memo: tx_by_addr_info.memo,
-                        block_time: tx_by_addr_info.block_time,
-                    },
-                    tx_by_addr_info.index,
-                ));
-                // Respect limit
-                if infos.len() >= limit {
-                    break 'outer;
-                }
-            }
-        }
-        Ok(infos)
+        signatures_for_address_from_rows(
+            &address_prefix,
+            tx_by_addr_data,
+            (first_slot, before_transaction_index),
+            (last_slot, until_transaction_index),
+            limit,
+        )
     }
 
     /// Upload a new confirmed block and associated meta data.
@@ -905,73 +1132,14 @@ impl LedgerStorage {
         confirmed_block: VersionedConfirmedBlockWithEntries,
     ) -> Result<()> {
         trace!("LedgerStorage::upload_confirmed_block_with_entries request received: {slot:?}");
-        let mut by_addr: HashMap<&Pubkey, Vec<TransactionByAddrInfo>> = HashMap::new();
-        let VersionedConfirmedBlockWithEntries {
-            block: confirmed_block,
-            entries,
-        } = confirmed_block;
-
-        let reserved_account_keys = ReservedAccountKeys::new_all_activated();
-        let mut tx_cells = Vec::with_capacity(confirmed_block.transactions.len());
-        for (index, transaction_with_meta) in confirmed_block.transactions.iter().enumerate() {
-            let VersionedTransactionWithStatusMeta { meta, transaction } = transaction_with_meta;
-            let err = meta.status.clone().err();
-            let index = index as u32;
-            let signature = transaction.signatures[0];
-            let memo = extract_and_fmt_memos(transaction_with_meta);
-
-            for address in transaction_with_meta.account_keys().iter() {
-                // Historical note that previously only a set of sysvar ids were
-                // skipped from being uploaded. Now we skip uploaded for the set
-                // of all reserved account keys which will continue to grow in
-                // the future.
-                if !reserved_account_keys.is_reserved(address) {
-                    by_addr
-                        .entry(address)
-                        .or_default()
-                        .push(TransactionByAddrInfo {
-                            signature,
-                            err: err.clone(),
-                            index,
-                            memo: memo.clone(),
-                            block_time: confirmed_block.block_time,
-                        });
-                }
-            }
-
-            tx_cells.push((
-                signature.to_string(),
-                TransactionInfo {
-                    slot,
-                    index,
-                    err,
-                    memo,
-                },
-            ));
-        }
-
-        let tx_by_addr_cells: Vec<_> = by_addr
-            .into_iter()
-            .map(|(address, transaction_info_by_addr)| {
-                (
-                    format!("{}/{}", address, slot_to_tx_by_addr_key(slot)),
-                    tx_by_addr::TransactionByAddr {
-                        tx_by_addrs: transaction_info_by_addr
-                            .into_iter()
-                            .map(|by_addr| by_addr.into())
-                            .collect(),
-                    },
-                )
-            })
-            .collect();
-
-        let num_entries = entries.len();
-        let entry_cell = (
-            slot_to_entries_key(slot),
-            entries::Entries {
-                entries: entries.into_iter().enumerate().map(Into::into).collect(),
-            },
-        );
+        let BlockCells {
+            tx_cells,
+            tx_by_addr_cells,
+            entry_cell,
+            num_entries,
+            blocks_cell,
+        } = BlockCells::new(slot, confirmed_block);
+        let num_transactions = tx_cells.len();
 
         let mut tasks = vec![];
 
@@ -994,7 +1162,7 @@ impl LedgerStorage {
             }));
         }
 
-        if num_entries > 0 {
+        if let Some(entry_cell) = entry_cell {
             let conn = self.connection.clone();
             tasks.push(tokio::spawn(async move {
                 conn.put_protobuf_cells_with_retry::<entries::Entries>("entries", &[entry_cell])
@@ -1028,15 +1196,12 @@ impl LedgerStorage {
             return Err(err);
         }
 
-        let num_transactions = confirmed_block.transactions.len();
-
         // Store the block itself last, after all other metadata about the block has been
         // successfully stored.  This avoids partial uploaded blocks from becoming visible to
         // `get_confirmed_block()` and `get_confirmed_blocks()`
-        let blocks_cells = [(slot_to_blocks_key(slot), confirmed_block.into())];
         bytes_written += self
             .connection
-            .put_protobuf_cells_with_retry::<generated::ConfirmedBlock>("blocks", &blocks_cells)
+            .put_protobuf_cells_with_retry::<generated::ConfirmedBlock>("blocks", &[blocks_cell])
             .await?;
         datapoint_info!(
             "storage-bigtable-upload-block",
@@ -1050,59 +1215,8 @@ impl LedgerStorage {
 
     // Delete a confirmed block and associated meta data.
     pub async fn delete_confirmed_block(&self, slot: Slot, dry_run: bool) -> Result<()> {
-        let mut addresses: HashSet<&Pubkey> = HashSet::new();
-        let mut expected_tx_infos: HashMap<String, UploadedTransaction> = HashMap::new();
         let confirmed_block = self.get_confirmed_block(slot).await?;
-        for (index, transaction_with_meta) in confirmed_block.transactions.iter().enumerate() {
-            match transaction_with_meta {
-                TransactionWithStatusMeta::MissingMetadata(transaction) => {
-                    let signature = transaction.signatures[0];
-                    let index = index as u32;
-                    let err = None;
-
-                    for address in transaction.message.account_keys.iter() {
-                        // We could skip deleting addresses that are known
-                        // reserved keys but it's hard to be sure whether we
-                        // previously uploaded rows for reserved keys or not. So
-                        // to ensure everything is deleted properly, we attempt
-                        // to delete rows for all addresses even if they might
-                        // not have been uploaded.
-                        addresses.insert(address);
-                    }
-
-                    expected_tx_infos.insert(
-                        signature.to_string(),
-                        UploadedTransaction { slot, index, err },
-                    );
-                }
-                TransactionWithStatusMeta::Complete(tx_with_meta) => {
-                    let VersionedTransactionWithStatusMeta { transaction, meta } = tx_with_meta;
-                    let signature = transaction.signatures[0];
-                    let index = index as u32;
-                    let err = meta.status.clone().err();
-
-                    for address in tx_with_meta.account_keys().iter() {
-                        // We could skip deleting addresses that are known
-                        // reserved keys but it's hard to be sure whether we
-                        // previously uploaded rows for reserved keys or not. So
-                        // to ensure everything is deleted properly, we attempt
-                        // to delete rows for all addresses even if they might
-                        // not have been uploaded.
-                        addresses.insert(address);
-                    }
-
-                    expected_tx_infos.insert(
-                        signature.to_string(),
-                        UploadedTransaction { slot, index, err },
-                    );
-                }
-            }
-        }
-
-        let address_slot_rows: Vec<_> = addresses
-            .into_iter()
-            .map(|address| format!("{}/{}", address, slot_to_tx_by_addr_key(slot)))
-            .collect();
+        let (address_slot_rows, expected_tx_infos) = block_rows(slot, &confirmed_block);
 
         let tx_deletion_rows = if !expected_tx_infos.is_empty() {
             let signatures = expected_tx_infos.keys().cloned().collect::<Vec<_>>();
@@ -1114,31 +1228,7 @@ impl LedgerStorage {
                     .map(|(signature, tx_info_res)| (signature, tx_info_res.map(Into::into)))
                     .collect::<HashMap<_, _>>();
 
-            let mut deletion_rows = Vec::with_capacity(expected_tx_infos.len());
-            for (signature, expected_tx_info) in expected_tx_infos {
-                match fetched_tx_infos.get(&signature) {
-                    Some(Ok(fetched_tx_info)) if fetched_tx_info == &expected_tx_info => {
-                        deletion_rows.push(signature);
-                    }
-                    Some(Ok(fetched_tx_info)) => {
-                        warn!(
-                            "skipped tx row {} because the bigtable entry ({:?}) did not match to \
-                             {:?}",
-                            signature, fetched_tx_info, &expected_tx_info,
-                        );
-                    }
-                    Some(Err(err)) => {
-                        warn!(
-                            "skipped tx row {signature} because the bigtable entry was corrupted: \
-                             {err:?}"
-                        );
-                    }
-                    None => {
-                        warn!("skipped tx row {signature} because it was not found");
-                    }
-                }
-            }
-            deletion_rows
+            tx_rows_to_delete(expected_tx_infos, fetched_tx_infos)
         } else {
             vec![]
         };
@@ -1188,6 +1278,179 @@ impl LedgerStorage {
     }
 }
 
+/// Long-term storage of the confirmed ledger history
+///
+/// Implemented by [`LedgerStorage`] for Google BigTable and by [`LocalLedgerStorage`] for a local
+/// directory, which both store the same rows.
+#[async_trait]
+pub trait LongTermStorage: Send + Sync {
+    /// Return the available slot that contains a block
+    async fn get_first_available_block(&self) -> Result<Option<Slot>>;
+
+    /// Fetch the next slots after the provided slot that contains a block
+    ///
+    /// start_slot: slot to start the search from (inclusive)
+    /// limit: stop after this many slots have been found
+    async fn get_confirmed_blocks(&self, start_slot: Slot, limit: usize) -> Result<Vec<Slot>>;
+
+    /// Fetch the confirmed blocks of the provided slots which exist
+    async fn get_confirmed_blocks_with_data(
+        &self,
+        slots: &[Slot],
+    ) -> Result<Vec<(Slot, ConfirmedBlock)>>;
+
+    /// Fetch the confirmed block from the desired slot
+    async fn get_confirmed_block(&self, slot: Slot) -> Result<ConfirmedBlock>;
+
+    /// Does the confirmed block exist in the storage
+    async fn confirmed_block_exists(&self, slot: Slot) -> Result<bool>;
+
+    /// Fetch the entries of the block from the desired slot
+    async fn get_entries(&self, slot: Slot) -> Result<Vec<EntrySummary>>;
+
+    async fn get_signature_status(&self, signature: &Signature) -> Result<TransactionStatus>;
+
+    /// Fetch the confirmed transactions of the provided signatures which exist
+    async fn get_confirmed_transactions(
+        &self,
+        signatures: &[Signature],
+    ) -> Result<Vec<ConfirmedTransactionWithStatusMeta>>;
+
+    /// Fetch a confirmed transaction
+    async fn get_confirmed_transaction(
+        &self,
+        signature: &Signature,
+    ) -> Result<Option<ConfirmedTransactionWithStatusMeta>>;
+
+    /// Get confirmed signatures for the provided address, in descending ledger order
+    ///
+    /// address: address to search for
+    /// before_signature: start with the first signature older than this one
+    /// until_signature: end with the last signature more recent than this one
+    /// limit: stop after this many signatures
+    async fn get_confirmed_signatures_for_address(
+        &self,
+        address: &Pubkey,
+        before_signature: Option<&Signature>,
+        until_signature: Option<&Signature>,
+        limit: usize,
+    ) -> Result<
+        Vec<(
+            ConfirmedTransactionStatusWithSignature,
+            u32, /*slot index*/
+        )>,
+    >;
+
+    /// Upload a new confirmed block and associated meta data.
+    async fn upload_confirmed_block(
+        &self,
+        slot: Slot,
+        confirmed_block: VersionedConfirmedBlock,
+    ) -> Result<()> {
+        self.upload_confirmed_block_with_entries(
+            slot,
+            VersionedConfirmedBlockWithEntries {
+                block: confirmed_block,
+                entries: vec![],
+            },
+        )
+        .await
+    }
+
+    async fn upload_confirmed_block_with_entries(
+        &self,
+        slot: Slot,
+        confirmed_block: VersionedConfirmedBlockWithEntries,
+    ) -> Result<()>;
+
+    /// Delete a confirmed block and associated meta data.
+    async fn delete_confirmed_block(&self, slot: Slot, dry_run: bool) -> Result<()>;
+}
+
+#[async_trait]
+impl LongTermStorage for LedgerStorage {
+    async fn get_first_available_block(&self) -> Result<Option<Slot>> {
+        LedgerStorage::get_first_available_block(self).await
+    }
+
+    async fn get_confirmed_blocks(&self, start_slot: Slot, limit: usize) -> Result<Vec<Slot>> {
+        LedgerStorage::get_confirmed_blocks(self, start_slot, limit).await
+    }
+
+    async fn get_confirmed_blocks_with_data(
+        &self,
+        slots: &[Slot],
+    ) -> Result<Vec<(Slot, ConfirmedBlock)>> {
+        Ok(LedgerStorage::get_confirmed_blocks_with_data(self, slots)
+            .await?
+            .collect())
+    }
+
+    async fn get_confirmed_block(&self, slot: Slot) -> Result<ConfirmedBlock> {
+        LedgerStorage::get_confirmed_block(self, slot).await
+    }
+
+    async fn confirmed_block_exists(&self, slot: Slot) -> Result<bool> {
+        LedgerStorage::confirmed_block_exists(self, slot).await
+    }
+
+    async fn get_entries(&self, slot: Slot) -> Result<Vec<EntrySummary>> {
+        Ok(LedgerStorage::get_entries(self, slot).await?.collect())
+    }
+
+    async fn get_signature_status(&self, signature: &Signature) -> Result<TransactionStatus> {
+        LedgerStorage::get_signature_status(self, signature).await
+    }
+
+    async fn get_confirmed_transactions(
+        &self,
+        signatures: &[Signature],
+    ) -> Result<Vec<ConfirmedTransactionWithStatusMeta>> {
+        LedgerStorage::get_confirmed_transactions(self, signatures).await
+    }
+
+    async fn get_confirmed_transaction(
+        &self,
+        signature: &Signature,
+    ) -> Result<Option<ConfirmedTransactionWithStatusMeta>> {
+        LedgerStorage::get_confirmed_transaction(self, signature).await
+    }
+
+    async fn get_confirmed_signatures_for_address(
+        &self,
+        address: &Pubkey,
+        before_signature: Option<&Signature>,
+        until_signature: Option<&Signature>,
+        limit: usize,
+    ) -> Result<
+        Vec<(
+            ConfirmedTransactionStatusWithSignature,
+            u32, /*slot index*/
+        )>,
+    > {
+        LedgerStorage::get_confirmed_signatures_for_address(
+            self,
+            address,
+            before_signature,
+            until_signature,
+            limit,
+        )
+        .await
+    }
+
+    async fn upload_confirmed_block_with_entries(
+        &self,
+        slot: Slot,
+        confirmed_block: VersionedConfirmedBlockWithEntries,
+    ) -> Result<()> {
+        LedgerStorage::upload_confirmed_block_with_entries(self, slot, confirmed_block).await
+    }
+
+    async fn delete_confirmed_block(&self, slot: Slot, dry_run: bool) -> Result<()> {
+        LedgerStorage::delete_confirmed_block(self, slot, dry_run).await
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
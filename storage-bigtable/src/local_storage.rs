@@ -0,0 +1,816 @@
+//! Long-term ledger storage kept in a local directory
+//!
+//! Every table is a directory under the storage root, and every row is a file named after its
+//! row key, with the name of its cell as extension. The file holds the same compressed bincode or
+//! protobuf cell that is written to BigTable. Row keys containing a `/`, like the `tx-by-addr`
+//! ones, are stored in nested directories. The slot keys of the `blocks` and `entries` tables are
+//! split in directories of four hex digits each, e.g. `blocks/0000/0000/0000/002a.proto`, so that
+//! listing a range of blocks only reads the directories overlapping that range.
+//!
+//! This makes it possible to run the BigTable upload service and the RPC long-term storage
+//! fallback without Google credentials, e.g. for development and testing.
+
+use {
+    crate::{
+        bigtable::{self, RowData, RowKey},
+        block_rows, confirmed_block_from_cell_data, confirmed_transaction_from_block, key_to_slot,
+        signatures_for_address_from_rows, slot_to_blocks_key, slot_to_entries_key,
+        slot_to_tx_by_addr_key, tx_rows_to_delete, BlockCells, Error, LegacyTransactionByAddrInfo,
+        LongTermStorage, Result, StoredConfirmedBlock, TransactionInfo, UploadedTransaction,
+    },
+    async_trait::async_trait,
+    log::*,
+    solana_clock::Slot,
+    solana_pubkey::Pubkey,
+    solana_signature::Signature,
+    solana_storage_proto::convert::{entries, generated, tx_by_addr},
+    solana_transaction_status::{
+        ConfirmedBlock, ConfirmedTransactionStatusWithSignature,
+        ConfirmedTransactionWithStatusMeta, EntrySummary, TransactionStatus,
+        VersionedConfirmedBlockWithEntries,
+    },
+    std::{
+        borrow::Cow,
+        collections::{hash_map::Entry, HashMap},
+        fs, io,
+        path::{Path, PathBuf},
+        sync::Arc,
+    },
+};
+
+const CELL_NAMES: [&str; 2] = ["proto", "bin"];
+
+/// Tables keyed by slot, whose row keys are split in nested directories
+const SLOT_KEYED_TABLES: [&str; 2] = ["blocks", "entries"];
+
+/// Number of hex digits of a slot key in each directory level
+const SLOT_KEY_DIGITS_PER_LEVEL: usize = 4;
+
+#[derive(Clone)]
+pub struct LocalLedgerStorage {
+    store: Arc<LocalStore>,
+}
+
+impl LocalLedgerStorage {
+    /// Open the storage kept in the `path` directory, which is created if it does not exist
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let root = path.into();
+        fs::create_dir_all(&root)?;
+        Ok(Self {
+            store: Arc::new(LocalStore { root }),
+        })
+    }
+
+    // Run `f` on the blocking thread pool, as the store does synchronous file system I/O
+    async fn with_store<T, F>(&self, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&LocalStore) -> Result<T> + Send + 'static,
+    {
+        let store = self.store.clone();
+        tokio::task::spawn_blocking(move || f(&store))
+            .await
+            .map_err(Error::TokioJoinError)?
+    }
+}
+
+#[async_trait]
+impl LongTermStorage for LocalLedgerStorage {
+    async fn get_first_available_block(&self) -> Result<Option<Slot>> {
+        trace!("LocalLedgerStorage::get_first_available_block request received");
+        self.with_store(|store| {
+            let blocks = store.get_row_keys("blocks", None, None, 1)?;
+            Ok(blocks.first().and_then(|key| key_to_slot(key)))
+        })
+        .await
+    }
+
+    async fn get_confirmed_blocks(&self, start_slot: Slot, limit: usize) -> Result<Vec<Slot>> {
+        trace!(
+            "LocalLedgerStorage::get_confirmed_blocks request received: {start_slot:?} {limit:?}"
+        );
+        self.with_store(move |store| {
+            let start_at = slot_to_blocks_key(start_slot);
+            let blocks = store.get_row_keys("blocks", Some(&start_at), None, limit)?;
+            Ok(blocks.into_iter().filter_map(|s| key_to_slot(&s)).collect())
+        })
+        .await
+    }
+
+    async fn get_confirmed_blocks_with_data(
+        &self,
+        slots: &[Slot],
+    ) -> Result<Vec<(Slot, ConfirmedBlock)>> {
+        trace!("LocalLedgerStorage::get_confirmed_blocks_with_data request received: {slots:?}");
+        let slots = slots.to_vec();
+        self.with_store(move |store| {
+            let mut blocks = vec![];
+            for slot in slots {
+                if let Some(block) = store.get_confirmed_block(slot)? {
+                    blocks.push((slot, block));
+                }
+            }
+            Ok(blocks)
+        })
+        .await
+    }
+
+    async fn get_confirmed_block(&self, slot: Slot) -> Result<ConfirmedBlock> {
+        trace!("LocalLedgerStorage::get_confirmed_block request received: {slot:?}");
+        self.with_store(move |store| {
+            store
+                .get_confirmed_block(slot)?
+                .ok_or(Error::BlockNotFound(slot))
+        })
+        .await
+    }
+
+    async fn confirmed_block_exists(&self, slot: Slot) -> Result<bool> {
+        trace!("LocalLedgerStorage::confirmed_block_exists request received: {slot:?}");
+        self.with_store(move |store| store.row_key_exists("blocks", &slot_to_blocks_key(slot)))
+            .await
+    }
+
+    async fn get_entries(&self, slot: Slot) -> Result<Vec<EntrySummary>> {
+        trace!("LocalLedgerStorage::get_entries request received: {slot:?}");
+        self.with_store(move |store| {
+            let key = slot_to_entries_key(slot);
+            let row_data = store
+                .get_row_data("entries", &key)?
+                .ok_or(Error::BlockNotFound(slot))?;
+            let entry_cell_data: entries::Entries =
+                bigtable::deserialize_protobuf_cell_data(&row_data, "entries", key)?;
+            Ok(entry_cell_data
+                .entries
+                .into_iter()
+                .map(Into::into)
+                .collect())
+        })
+        .await
+    }
+
+    async fn get_signature_status(&self, signature: &Signature) -> Result<TransactionStatus> {
+        trace!("LocalLedgerStorage::get_signature_status request received: {signature:?}");
+        let signature = *signature;
+        self.with_store(move |store| Ok(store.get_transaction_info(&signature)?.into()))
+            .await
+    }
+
+    async fn get_confirmed_transactions(
+        &self,
+        signatures: &[Signature],
+    ) -> Result<Vec<ConfirmedTransactionWithStatusMeta>> {
+        trace!("LocalLedgerStorage::get_confirmed_transactions request received: {signatures:?}");
+        let signatures = signatures.to_vec();
+        self.with_store(move |store| {
+            let mut blocks = HashMap::new();
+            let mut transactions = vec![];
+            for signature in signatures {
+                let TransactionInfo { slot, index, .. } =
+                    match store.get_transaction_info(&signature) {
+                        Ok(transaction_info) => transaction_info,
+                        Err(Error::SignatureNotFound) => continue,
+                        Err(err) => return Err(err),
+                    };
+                let block = match blocks.entry(slot) {
+                    Entry::Occupied(entry) => entry.into_mut(),
+                    Entry::Vacant(entry) => entry.insert(store.get_confirmed_block(slot)?),
+                };
+                let Some(block) = block.clone() else {
+                    continue;
+                };
+                transactions.extend(confirmed_transaction_from_block(
+                    &signature, slot, index, block,
+                ));
+            }
+            Ok(transactions)
+        })
+        .await
+    }
+
+    async fn get_confirmed_transaction(
+        &self,
+        signature: &Signature,
+    ) -> Result<Option<ConfirmedTransactionWithStatusMeta>> {
+        trace!("LocalLedgerStorage::get_confirmed_transaction request received: {signature:?}");
+        let signature = *signature;
+        self.with_store(move |store| {
+            // Figure out which block the transaction is located in
+            let TransactionInfo { slot, index, .. } = store.get_transaction_info(&signature)?;
+
+            // Load the block and return the transaction
+            let block = store
+                .get_confirmed_block(slot)?
+                .ok_or(Error::BlockNotFound(slot))?;
+            Ok(confirmed_transaction_from_block(
+                &signature, slot, index, block,
+            ))
+        })
+        .await
+    }
+
+    async fn get_confirmed_signatures_for_address(
+        &self,
+        address: &Pubkey,
+        before_signature: Option<&Signature>,
+        until_signature: Option<&Signature>,
+        limit: usize,
+    ) -> Result<
+        Vec<(
+            ConfirmedTransactionStatusWithSignature,
+            u32, /*slot index*/
+        )>,
+    > {
+        trace!(
+            "LocalLedgerStorage::get_confirmed_signatures_for_address request received: \
+             {address:?}"
+        );
+        let address_prefix = format!("{address}/");
+        let before_signature = before_signature.copied();
+        let until_signature = until_signature.copied();
+        self.with_store(move |store| {
+            // Figure out where to start listing from based on `before_signature`
+            let (first_slot, before_transaction_index) = match before_signature {
+                None => (Slot::MAX, 0),
+                Some(before_signature) => {
+                    let TransactionInfo { slot, index, .. } =
+                        store.get_transaction_info(&before_signature)?;
+                    (slot, index)
+                }
+            };
+
+            // Figure out where to end listing from based on `until_signature`
+            let (last_slot, until_transaction_index) = match until_signature {
+                None => (0, u32::MAX),
+                Some(until_signature) => {
+                    let TransactionInfo { slot, index, .. } =
+                        store.get_transaction_info(&until_signature)?;
+                    (slot, index)
+                }
+            };
+
+            let start_at = format!("{}{}", address_prefix, slot_to_tx_by_addr_key(first_slot));
+            let starting_slot_tx_len = match store.get_row_data("tx-by-addr", &start_at)? {
+                None => 0,
+                Some(row_data) => bigtable::deserialize_protobuf_or_bincode_cell_data::<
+                    Vec<LegacyTransactionByAddrInfo>,
+                    tx_by_addr::TransactionByAddr,
+                >(&row_data, "tx-by-addr", start_at.clone())
+                .map(|cell_data| match cell_data {
+                    bigtable::CellData::Bincode(tx_by_addr) => tx_by_addr.len(),
+                    bigtable::CellData::Protobuf(tx_by_addr) => tx_by_addr.tx_by_addrs.len(),
+                })
+                .unwrap_or(0),
+            };
+
+            // Return the next tx-by-addr data of amount `limit` plus extra to account for the
+            // largest number that might be filtered out
+            let end_at = format!("{}{}", address_prefix, slot_to_tx_by_addr_key(last_slot));
+            let tx_by_addr_data = store
+                .get_row_keys(
+                    "tx-by-addr",
+                    Some(&start_at),
+                    Some(&end_at),
+                    limit.saturating_add(starting_slot_tx_len),
+                )?
+                .into_iter()
+                .map(|row_key| {
+                    let row_data = store.get_row_data("tx-by-addr", &row_key)?;
+                    Ok((row_key, row_data.unwrap_or_default()))
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            signatures_for_address_from_rows(
+                &address_prefix,
+                tx_by_addr_data,
+                (first_slot, before_transaction_index),
+                (last_slot, until_transaction_index),
+                limit,
+            )
+        })
+        .await
+    }
+
+    async fn upload_confirmed_block_with_entries(
+        &self,
+        slot: Slot,
+        confirmed_block: VersionedConfirmedBlockWithEntries,
+    ) -> Result<()> {
+        trace!(
+            "LocalLedgerStorage::upload_confirmed_block_with_entries request received: {slot:?}"
+        );
+        let BlockCells {
+            tx_cells,
+            tx_by_addr_cells,
+            entry_cell,
+            num_entries: _,
+            blocks_cell,
+        } = BlockCells::new(slot, confirmed_block);
+        self.with_store(move |store| {
+            for (row_key, transaction_info) in tx_cells {
+                let data = bigtable::serialize_bincode_cell_data(&transaction_info)?;
+                store.put_cell("tx", &row_key, "bin", &data)?;
+            }
+            for (row_key, tx_by_addr) in tx_by_addr_cells {
+                let data = bigtable::serialize_protobuf_cell_data(&tx_by_addr)?;
+                store.put_cell("tx-by-addr", &row_key, "proto", &data)?;
+            }
+            if let Some((row_key, entries)) = entry_cell {
+                let data = bigtable::serialize_protobuf_cell_data(&entries)?;
+                store.put_cell("entries", &row_key, "proto", &data)?;
+            }
+
+            // Store the block itself last, so that partially uploaded blocks are not visible,
+            // like `LedgerStorage::upload_confirmed_block_with_entries()` does
+            let (row_key, block) = blocks_cell;
+            let data = bigtable::serialize_protobuf_cell_data::<generated::ConfirmedBlock>(&block)?;
+            store.put_cell("blocks", &row_key, "proto", &data)
+        })
+        .await
+    }
+
+    async fn delete_confirmed_block(&self, slot: Slot, dry_run: bool) -> Result<()> {
+        let confirmed_block = LongTermStorage::get_confirmed_block(self, slot).await?;
+        self.with_store(move |store| {
+            let (address_slot_rows, expected_tx_infos) = block_rows(slot, &confirmed_block);
+
+            let mut fetched_tx_infos = HashMap::new();
+            for signature in expected_tx_infos.keys() {
+                if let Some(row_data) = store.get_row_data("tx", signature)? {
+                    let tx_info_res = bigtable::deserialize_bincode_cell_data::<TransactionInfo>(
+                        &row_data,
+                        "tx",
+                        signature.clone(),
+                    )
+                    .map(UploadedTransaction::from);
+                    fetched_tx_infos.insert(signature.clone(), tx_info_res);
+                }
+            }
+            let tx_deletion_rows = tx_rows_to_delete(expected_tx_infos, fetched_tx_infos);
+
+            let entries_exist = store.row_key_exists("entries", &slot_to_entries_key(slot))?;
+
+            if !dry_run {
+                store.delete_rows("tx-by-addr", &address_slot_rows)?;
+                store.delete_rows("tx", &tx_deletion_rows)?;
+                store.delete_rows("entries", &[slot_to_entries_key(slot)])?;
+                store.delete_rows("blocks", &[slot_to_blocks_key(slot)])?;
+            }
+
+            info!(
+                "{}deleted local ledger data for slot {}: {} transaction rows, {} address slot \
+                 rows, {} entry row",
+                if dry_run { "[dry run] " } else { "" },
+                slot,
+                tx_deletion_rows.len(),
+                address_slot_rows.len(),
+                if entries_exist { "with" } else { "WITHOUT" }
+            );
+            Ok(())
+        })
+        .await
+    }
+}
+
+struct LocalStore {
+    root: PathBuf,
+}
+
+impl LocalStore {
+    fn cell_path(&self, table_name: &str, row_key: &str, cell_name: &str) -> PathBuf {
+        let row_path = row_key_to_path(table_name, row_key);
+        self.root
+            .join(table_name)
+            .join(format!("{row_path}.{cell_name}"))
+    }
+
+    /// Get the keys of the `table` rows from `start_at` to `end_at`, both included, in order
+    ///
+    /// The walk starts at the directory of the `/`-separated prefix shared by `start_at` and
+    /// `end_at`, skips the subdirectories outside of the range and stops once `rows_limit` keys
+    /// are found. If `rows_limit` is zero, this method will return an empty array.
+    fn get_row_keys(
+        &self,
+        table_name: &str,
+        start_at: Option<&str>,
+        end_at: Option<&str>,
+        rows_limit: usize,
+    ) -> Result<Vec<RowKey>> {
+        if rows_limit == 0 {
+            return Ok(vec![]);
+        }
+        // The walk is done on the row paths, which sort like the row keys
+        let start_at = start_at.map(|start_at| row_key_to_path(table_name, start_at));
+        let end_at = end_at.map(|end_at| row_key_to_path(table_name, end_at));
+        let (start_at, end_at) = (start_at.as_deref(), end_at.as_deref());
+        let prefix = match (start_at, end_at) {
+            (Some(start_at), Some(end_at)) => {
+                let prefix = start_at.rfind('/').map_or("", |i| &start_at[..=i]);
+                if end_at.starts_with(prefix) {
+                    prefix
+                } else {
+                    ""
+                }
+            }
+            _ => "",
+        };
+
+        let mut row_keys = vec![];
+        list_row_keys(
+            &self.root.join(table_name).join(prefix),
+            prefix,
+            start_at,
+            end_at,
+            rows_limit,
+            &mut row_keys,
+        )?;
+        Ok(row_keys
+            .into_iter()
+            .map(|row_path| row_path_to_key(table_name, row_path))
+            .collect())
+    }
+
+    fn row_key_exists(&self, table_name: &str, row_key: &str) -> Result<bool> {
+        Ok(CELL_NAMES.iter().any(|cell_name| {
+            self.cell_path(table_name, row_key, cell_name)
+                .try_exists()
+                .unwrap_or(false)
+        }))
+    }
+
+    /// Get the data of a single row of `table`, if that row exists
+    fn get_row_data(&self, table_name: &str, row_key: &str) -> Result<Option<RowData>> {
+        let mut row_data = vec![];
+        for cell_name in CELL_NAMES {
+            match fs::read(self.cell_path(table_name, row_key, cell_name)) {
+                Ok(value) => row_data.push((cell_name.to_string(), value)),
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Ok((!row_data.is_empty()).then_some(row_data))
+    }
+
+    /// Store a cell of a `table` row, replacing it atomically if it already exists
+    fn put_cell(
+        &self,
+        table_name: &str,
+        row_key: &str,
+        cell_name: &str,
+        value: &[u8],
+    ) -> Result<()> {
+        let path = self.cell_path(table_name, row_key, cell_name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp_path = path.with_extension(format!("{cell_name}.tmp"));
+        fs::write(&tmp_path, value)?;
+        fs::rename(&tmp_path, &path)?;
+        Ok(())
+    }
+
+    /// Delete one or more `table` rows
+    fn delete_rows(&self, table_name: &str, row_keys: &[RowKey]) -> Result<()> {
+        for row_key in row_keys {
+            for cell_name in CELL_NAMES {
+                match fs::remove_file(self.cell_path(table_name, row_key, cell_name)) {
+                    Ok(()) => {}
+                    Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                    Err(err) => return Err(err.into()),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn get_confirmed_block(&self, slot: Slot) -> Result<Option<ConfirmedBlock>> {
+        let key = slot_to_blocks_key(slot);
+        let Some(row_data) = self.get_row_data("blocks", &key)? else {
+            return Ok(None);
+        };
+        let block_cell_data = bigtable::deserialize_protobuf_or_bincode_cell_data::<
+            StoredConfirmedBlock,
+            generated::ConfirmedBlock,
+        >(&row_data, "blocks", key)?;
+        confirmed_block_from_cell_data(slot, block_cell_data).map(Some)
+    }
+
+    fn get_transaction_info(&self, signature: &Signature) -> Result<TransactionInfo> {
+        let key = signature.to_string();
+        let row_data = self
+            .get_row_data("tx", &key)?
+            .ok_or(Error::SignatureNotFound)?;
+        Ok(bigtable::deserialize_bincode_cell_data(
+            &row_data, "tx", key,
+        )?)
+    }
+}
+
+// Get the path of the `table` row `row_key`, relative to the table directory and without the cell
+// name. Slot keys of the `SLOT_KEYED_TABLES` are split in directories, which keeps them in order
+// since they all have the same length.
+fn row_key_to_path<'a>(table_name: &str, row_key: &'a str) -> Cow<'a, str> {
+    let is_slot_key = row_key.len() == 16 && row_key.bytes().all(|b| b.is_ascii_hexdigit());
+    if is_slot_key && SLOT_KEYED_TABLES.contains(&table_name) {
+        Cow::Owned(
+            (0..row_key.len())
+                .step_by(SLOT_KEY_DIGITS_PER_LEVEL)
+                .map(|i| &row_key[i..i + SLOT_KEY_DIGITS_PER_LEVEL])
+                .collect::<Vec<_>>()
+                .join("/"),
+        )
+    } else {
+        Cow::Borrowed(row_key)
+    }
+}
+
+// Inverse of `row_key_to_path()`
+fn row_path_to_key(table_name: &str, row_path: String) -> RowKey {
+    if SLOT_KEYED_TABLES.contains(&table_name) {
+        row_path.replace('/', "")
+    } else {
+        row_path
+    }
+}
+
+// Collect, in order, the keys of the rows stored under `dir` whose keys start with `prefix`, are
+// in the `start_at..=end_at` range, until `row_keys` holds `rows_limit` keys. Returns whether the
+// walk should stop, i.e. the limit was reached or a key past `end_at` was found.
+fn list_row_keys(
+    dir: &Path,
+    prefix: &str,
+    start_at: Option<&str>,
+    end_at: Option<&str>,
+    rows_limit: usize,
+    row_keys: &mut Vec<RowKey>,
+) -> Result<bool> {
+    let read_dir = match fs::read_dir(dir) {
+        Ok(read_dir) => read_dir,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(false),
+        Err(err) => return Err(err.into()),
+    };
+    // Files are sorted by row key and subdirectories by the prefix of their row keys, which keeps
+    // the row keys in order, as the row keys of the files in `dir` have no further `/`
+    let mut entries = vec![];
+    for entry in read_dir {
+        let entry = entry?;
+        let Ok(file_name) = entry.file_name().into_string() else {
+            continue;
+        };
+        if entry.file_type()?.is_dir() {
+            entries.push((format!("{prefix}{file_name}/"), true));
+        } else if let Some((row_key, cell_name)) = file_name.rsplit_once('.') {
+            if CELL_NAMES.contains(&cell_name) {
+                entries.push((format!("{prefix}{row_key}"), false));
+            }
+        }
+    }
+    entries.sort_unstable();
+    entries.dedup();
+
+    for (key, is_dir) in entries {
+        if end_at.is_some_and(|end_at| key.as_str() > end_at) {
+            return Ok(true);
+        }
+        if is_dir {
+            let before_start = start_at
+                .is_some_and(|start_at| start_at > key.as_str() && !start_at.starts_with(&key));
+            if !before_start {
+                let dir = dir.join(&key[prefix.len()..key.len() - 1]);
+                if list_row_keys(&dir, &key, start_at, end_at, rows_limit, row_keys)? {
+                    return Ok(true);
+                }
+            }
+        } else if start_at.is_none_or(|start_at| key.as_str() >= start_at) {
+            row_keys.push(key);
+            if row_keys.len() == rows_limit {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_hash::Hash,
+        solana_keypair::Keypair,
+        solana_message::v0::LoadedAddresses,
+        solana_system_transaction as system_transaction,
+        solana_transaction::versioned::VersionedTransaction,
+        solana_transaction_status::{
+            TransactionStatusMeta, TransactionWithStatusMeta, VersionedConfirmedBlock,
+            VersionedTransactionWithStatusMeta,
+        },
+    };
+
+    fn new_block(slot: Slot, from: &Keypair, recipient: &Pubkey) -> VersionedConfirmedBlock {
+        let transaction = system_transaction::transfer(from, recipient, slot, Hash::default());
+        VersionedConfirmedBlock {
+            previous_blockhash: Hash::default().to_string(),
+            blockhash: Hash::default().to_string(),
+            parent_slot: slot - 1,
+            transactions: vec![VersionedTransactionWithStatusMeta {
+                transaction: VersionedTransaction::from(transaction),
+                meta: TransactionStatusMeta {
+                    status: Ok(()),
+                    fee: 5000,
+                    pre_balances: vec![100_000, 0, 1],
+                    post_balances: vec![95_000 - slot, slot, 1],
+                    loaded_addresses: LoadedAddresses::default(),
+                    ..TransactionStatusMeta::default()
+                },
+            }],
+            rewards: vec![],
+            num_partitions: None,
+            block_time: Some(1_234_567_890),
+            block_height: Some(slot),
+        }
+    }
+
+    async fn slots_for_address(
+        storage: &LocalLedgerStorage,
+        address: &Pubkey,
+        before: Option<&Signature>,
+        until: Option<&Signature>,
+        limit: usize,
+    ) -> Vec<Slot> {
+        storage
+            .get_confirmed_signatures_for_address(address, before, until, limit)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(status, _index)| status.slot)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_local_ledger_storage() {
+        let ledger_path = tempfile::TempDir::new().unwrap();
+        let storage = LocalLedgerStorage::open(ledger_path.path()).unwrap();
+        let from = Keypair::new();
+        let recipient = Pubkey::new_unique();
+
+        assert_eq!(storage.get_first_available_block().await.unwrap(), None);
+
+        let mut signatures = vec![];
+        for slot in [3, 5, 8] {
+            let block = new_block(slot, &from, &recipient);
+            signatures.push(block.transactions[0].transaction.signatures[0]);
+            storage.upload_confirmed_block(slot, block).await.unwrap();
+        }
+
+        assert_eq!(storage.get_first_available_block().await.unwrap(), Some(3));
+        assert_eq!(
+            storage.get_confirmed_blocks(4, 10).await.unwrap(),
+            vec![5, 8]
+        );
+        assert!(storage.confirmed_block_exists(5).await.unwrap());
+        assert!(!storage.confirmed_block_exists(6).await.unwrap());
+        assert!(matches!(
+            storage.get_confirmed_block(6).await,
+            Err(Error::BlockNotFound(6))
+        ));
+
+        let block = storage.get_confirmed_block(5).await.unwrap();
+        assert_eq!(block.parent_slot, 4);
+        assert_eq!(
+            block.transactions[0].transaction_signature(),
+            &signatures[1]
+        );
+        assert!(matches!(
+            &block.transactions[0],
+            TransactionWithStatusMeta::Complete(_)
+        ));
+
+        let status = storage.get_signature_status(&signatures[2]).await.unwrap();
+        assert_eq!(status.slot, 8);
+        let transaction = storage
+            .get_confirmed_transaction(&signatures[0])
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(transaction.slot, 3);
+        assert_eq!(
+            storage
+                .get_confirmed_transactions(&[signatures[2], Signature::default(), signatures[0]])
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|transaction| transaction.slot)
+                .collect::<Vec<_>>(),
+            vec![8, 3]
+        );
+
+        // newest first, bounded by `before` and `until`
+        assert_eq!(
+            slots_for_address(&storage, &recipient, None, None, 10).await,
+            vec![8, 5, 3]
+        );
+        assert_eq!(
+            slots_for_address(&storage, &recipient, None, None, 2).await,
+            vec![8, 5]
+        );
+        assert_eq!(
+            slots_for_address(&storage, &recipient, Some(&signatures[2]), None, 10).await,
+            vec![5, 3]
+        );
+        assert_eq!(
+            slots_for_address(&storage, &recipient, None, Some(&signatures[0]), 10).await,
+            vec![8, 5]
+        );
+
+        storage.delete_confirmed_block(5, true).await.unwrap();
+        assert!(storage.confirmed_block_exists(5).await.unwrap());
+        storage.delete_confirmed_block(5, false).await.unwrap();
+        assert!(!storage.confirmed_block_exists(5).await.unwrap());
+        assert!(matches!(
+            storage.get_signature_status(&signatures[1]).await,
+            Err(Error::SignatureNotFound)
+        ));
+        assert_eq!(
+            slots_for_address(&storage, &recipient, None, None, 10).await,
+            vec![8, 3]
+        );
+    }
+
+    #[test]
+    fn test_get_row_keys() {
+        let ledger_path = tempfile::TempDir::new().unwrap();
+        let store = LocalStore {
+            root: ledger_path.path().to_path_buf(),
+        };
+        for row_key in ["a", "a/1", "a/2", "a-b", "b/1/x", "b/2", "c"] {
+            store.put_cell("table", row_key, "bin", &[]).unwrap();
+        }
+        store.put_cell("table", "a/1", "proto", &[]).unwrap();
+
+        let get_row_keys = |start_at, end_at, rows_limit| {
+            store
+                .get_row_keys("table", start_at, end_at, rows_limit)
+                .unwrap()
+        };
+        assert_eq!(
+            get_row_keys(None, None, 10),
+            vec!["a", "a-b", "a/1", "a/2", "b/1/x", "b/2", "c"]
+        );
+        assert_eq!(get_row_keys(None, None, 3), vec!["a", "a-b", "a/1"]);
+        assert!(get_row_keys(None, None, 0).is_empty());
+        assert_eq!(
+            get_row_keys(Some("a/2"), Some("b/2"), 10),
+            vec!["a/2", "b/1/x", "b/2"]
+        );
+        assert_eq!(
+            get_row_keys(Some("a/"), Some("a/~"), 10),
+            vec!["a/1", "a/2"]
+        );
+        assert_eq!(get_row_keys(Some("b"), Some("b/1/~"), 10), vec!["b/1/x"]);
+        assert_eq!(get_row_keys(Some("b/3"), None, 10), vec!["c"]);
+    }
+
+    #[test]
+    fn test_get_row_keys_slot_keyed() {
+        let ledger_path = tempfile::TempDir::new().unwrap();
+        let store = LocalStore {
+            root: ledger_path.path().to_path_buf(),
+        };
+        let slots = [3, 0x2a, 0x1_0000, 0x1_0001, u64::MAX];
+        for slot in slots {
+            store
+                .put_cell("blocks", &slot_to_blocks_key(slot), "proto", &[])
+                .unwrap();
+        }
+        assert!(ledger_path
+            .path()
+            .join("blocks/0000/0000/0000/002a.proto")
+            .is_file());
+        assert!(ledger_path
+            .path()
+            .join("blocks/0000/0000/0001/0001.proto")
+            .is_file());
+        assert!(store
+            .row_key_exists("blocks", &slot_to_blocks_key(0x1_0000))
+            .unwrap());
+
+        let get_slots = |start_slot: Option<Slot>, end_slot: Option<Slot>, rows_limit| {
+            let start_at = start_slot.map(slot_to_blocks_key);
+            let end_at = end_slot.map(slot_to_blocks_key);
+            store
+                .get_row_keys("blocks", start_at.as_deref(), end_at.as_deref(), rows_limit)
+                .unwrap()
+                .iter()
+                .map(|key| key_to_slot(key).unwrap())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(get_slots(None, None, 10), slots);
+        assert_eq!(get_slots(None, None, 1), vec![3]);
+        assert_eq!(get_slots(Some(4), None, 2), vec![0x2a, 0x1_0000]);
+        assert_eq!(
+            get_slots(Some(0x2a), Some(0x1_0001), 10),
+            vec![0x2a, 0x1_0000, 0x1_0001]
+        );
+        assert_eq!(get_slots(Some(0x1_0002), None, 10), vec![u64::MAX]);
+    }
+}
@@ -16,9 +16,9 @@ use {
     solana_transaction_error::TransactionError,
     solana_transaction_status::{
         ConfirmedBlock, EntrySummary, InnerInstruction, InnerInstructions, Reward, RewardType,
-        RewardsAndNumPartitions, TransactionByAddrInfo, TransactionStatusMeta,
-        TransactionTokenBalance, TransactionWithStatusMeta, VersionedConfirmedBlock,
-        VersionedTransactionWithStatusMeta,
+        RewardsAndNumPartitions, TransactionByAddrInfo, TransactionLogEvent, TransactionStatusMeta,
+        TransactionSyscallComputeUnits, TransactionTokenBalance, TransactionWithStatusMeta,
+        VersionedConfirmedBlock, VersionedTransactionWithStatusMeta,
     },
     std::{
         convert::{TryFrom, TryInto},
@@ -422,6 +422,8 @@ impl From<TransactionStatusMeta> for generated::TransactionStatusMeta {
             return_data,
             compute_units_consumed,
             cost_units,
+            log_events,
+            syscall_compute_units,
         } = value;
         let err = match status {
             Ok(()) => None,
@@ -464,6 +466,13 @@ impl From<TransactionStatusMeta> for generated::TransactionStatusMeta {
             .collect();
         let return_data_none = return_data.is_none();
         let return_data = return_data.map(|return_data| return_data.into());
+        let log_events_present = log_events.is_some();
+        let log_events = log_events
+            .unwrap_or_default()
+            .into_iter()
+            .map(|event| event.into())
+            .collect();
+        let syscall_compute_units = syscall_compute_units.map(|units| units.into());
 
         Self {
             err,
@@ -483,6 +492,9 @@ impl From<TransactionStatusMeta> for generated::TransactionStatusMeta {
             return_data_none,
             compute_units_consumed,
             cost_units,
+            log_events,
+            log_events_present,
+            syscall_compute_units,
         }
     }
 }
@@ -516,6 +528,9 @@ impl TryFrom<generated::TransactionStatusMeta> for TransactionStatusMeta {
             return_data_none,
             compute_units_consumed,
             cost_units,
+            log_events,
+            log_events_present,
+            syscall_compute_units,
         } = value;
         let status = match &err {
             None => Ok(()),
@@ -572,6 +587,18 @@ impl TryFrom<generated::TransactionStatusMeta> for TransactionStatusMeta {
         } else {
             return_data.map(|return_data| return_data.into())
         };
+        // Rows written before log events were recorded decode to `None`
+        let log_events = if log_events_present {
+            Some(
+                log_events
+                    .into_iter()
+                    .map(TransactionLogEvent::try_from)
+                    .collect::<Result<_, _>>()?,
+            )
+        } else {
+            None
+        };
+        let syscall_compute_units = syscall_compute_units.map(|units| units.into());
         Ok(Self {
             status,
             fee,
@@ -586,6 +613,8 @@ impl TryFrom<generated::TransactionStatusMeta> for TransactionStatusMeta {
             return_data,
             compute_units_consumed,
             cost_units,
+            log_events,
+            syscall_compute_units,
         })
     }
 }
@@ -692,6 +721,151 @@ impl From<generated::ReturnData> for TransactionReturnData {
     }
 }
 
+impl From<TransactionLogEvent> for generated::LogEvent {
+    fn from(value: TransactionLogEvent) -> Self {
+        let program_id = |program_id: Pubkey| <Pubkey as AsRef<[u8]>>::as_ref(&program_id).into();
+        match value {
+            TransactionLogEvent::Invoke {
+                program_id: id,
+                stack_height,
+            } => Self {
+                event_type: generated::LogEventType::Invoke as i32,
+                program_id: program_id(id),
+                stack_height: stack_height as u64,
+                ..Self::default()
+            },
+            TransactionLogEvent::Log { message } => Self {
+                event_type: generated::LogEventType::Log as i32,
+                message,
+                ..Self::default()
+            },
+            TransactionLogEvent::Data { data } => Self {
+                event_type: generated::LogEventType::Data as i32,
+                data,
+                ..Self::default()
+            },
+            TransactionLogEvent::Return {
+                program_id: id,
+                data,
+            } => Self {
+                event_type: generated::LogEventType::Return as i32,
+                program_id: program_id(id),
+                data: vec![data],
+                ..Self::default()
+            },
+            TransactionLogEvent::ComputeUnits {
+                program_id: id,
+                consumed,
+                available,
+            } => Self {
+                event_type: generated::LogEventType::ComputeUnits as i32,
+                program_id: program_id(id),
+                consumed,
+                available,
+                ..Self::default()
+            },
+            TransactionLogEvent::Success { program_id: id } => Self {
+                event_type: generated::LogEventType::Success as i32,
+                program_id: program_id(id),
+                ..Self::default()
+            },
+            TransactionLogEvent::Failure {
+                program_id: id,
+                error,
+            } => Self {
+                event_type: generated::LogEventType::Failure as i32,
+                program_id: program_id(id),
+                error,
+                ..Self::default()
+            },
+            TransactionLogEvent::Truncated => Self {
+                event_type: generated::LogEventType::Truncated as i32,
+                ..Self::default()
+            },
+        }
+    }
+}
+
+impl TryFrom<generated::LogEvent> for TransactionLogEvent {
+    type Error = bincode::Error;
+
+    fn try_from(value: generated::LogEvent) -> std::result::Result<Self, Self::Error> {
+        let generated::LogEvent {
+            event_type,
+            program_id,
+            stack_height,
+            message,
+            mut data,
+            consumed,
+            available,
+            error,
+        } = value;
+        let program_id = || {
+            Pubkey::try_from(program_id).map_err(|err| {
+                let err = format!("Invalid log event program id: {err:?}");
+                Self::Error::new(bincode::ErrorKind::Custom(err))
+            })
+        };
+        Ok(match event_type {
+            0 => Self::Invoke {
+                program_id: program_id()?,
+                stack_height: stack_height as usize,
+            },
+            1 => Self::Log { message },
+            2 => Self::Data { data },
+            3 => Self::Return {
+                program_id: program_id()?,
+                data: data.pop().unwrap_or_default(),
+            },
+            4 => Self::ComputeUnits {
+                program_id: program_id()?,
+                consumed,
+                available,
+            },
+            5 => Self::Success {
+                program_id: program_id()?,
+            },
+            6 => Self::Failure {
+                program_id: program_id()?,
+                error,
+            },
+            7 => Self::Truncated,
+            _ => {
+                let err = format!("Invalid log event type: {event_type}");
+                return Err(Self::Error::new(bincode::ErrorKind::Custom(err)));
+            }
+        })
+    }
+}
+
+impl From<TransactionSyscallComputeUnits> for generated::SyscallComputeUnits {
+    fn from(value: TransactionSyscallComputeUnits) -> Self {
+        Self {
+            hashing: value.hashing,
+            curve_ops: value.curve_ops,
+            cpi: value.cpi,
+            logging: value.logging,
+            memory_ops: value.memory_ops,
+            sysvars: value.sysvars,
+            other: value.other,
+        }
+    }
+}
+
+impl From<generated::SyscallComputeUnits> for TransactionSyscallComputeUnits {
+    fn from(value: generated::SyscallComputeUnits) -> Self {
+        Self {
+            hashing: value.hashing,
+            curve_ops: value.curve_ops,
+            cpi: value.cpi,
+            logging: value.logging,
+            memory_ops: value.memory_ops,
+            sysvars: value.sysvars,
+            other: value.other,
+        }
+    }
+}
+
 impl From<CompiledInstruction> for generated::CompiledInstruction {
     fn from(value: CompiledInstruction) -> Self {
         Self {
@@ -1329,6 +1503,86 @@ mod test {
         assert_eq!(info, tx_by_addr_transaction_info.try_into().unwrap());
     }
 
+    #[test]
+    fn test_log_event_encode() {
+        let program_id = Pubkey::new_from_array([7; 32]);
+        let events = vec![
+            TransactionLogEvent::Invoke {
+                program_id,
+                stack_height: 1,
+            },
+            TransactionLogEvent::Log {
+                message: "message".to_string(),
+            },
+            TransactionLogEvent::Data {
+                data: vec![vec![1, 2], vec![], vec![3]],
+            },
+            TransactionLogEvent::Return {
+                program_id,
+                data: vec![4, 5, 6],
+            },
+            TransactionLogEvent::ComputeUnits {
+                program_id,
+                consumed: 150,
+                available: 200_000,
+            },
+            TransactionLogEvent::Success { program_id },
+            TransactionLogEvent::Failure {
+                program_id,
+                error: "custom program error: 0x1".to_string(),
+            },
+            TransactionLogEvent::Truncated,
+        ];
+        for event in events {
+            let gen_event: generated::LogEvent = event.clone().into();
+            assert_eq!(event, gen_event.try_into().unwrap());
+        }
+
+        let gen_event = generated::LogEvent {
+            event_type: 8,
+            ..generated::LogEvent::default()
+        };
+        assert!(TransactionLogEvent::try_from(gen_event).is_err());
+
+        // Metas stored before log events were recorded have none
+        let gen_meta = generated::TransactionStatusMeta::from(TransactionStatusMeta::default());
+        assert!(!gen_meta.log_events_present);
+        let meta = TransactionStatusMeta::try_from(gen_meta).unwrap();
+        assert_eq!(meta.log_events, None);
+
+        let gen_meta = generated::TransactionStatusMeta::from(TransactionStatusMeta {
+            log_events: Some(vec![]),
+            ..TransactionStatusMeta::default()
+        });
+        assert!(gen_meta.log_events_present);
+        let meta = TransactionStatusMeta::try_from(gen_meta).unwrap();
+        assert_eq!(meta.log_events, Some(vec![]));
+    }
+
+    #[test]
+    fn test_syscall_compute_units_encode() {
+        let meta = TransactionStatusMeta {
+            syscall_compute_units: Some(TransactionSyscallComputeUnits {
+                hashing: 185,
+                curve_ops: 2_000,
+                cpi: 1_000,
+                logging: 100,
+                memory_ops: 10,
+                sysvars: 117,
+                other: 0,
+            }),
+            ..TransactionStatusMeta::default()
+        };
+        let gen_meta: generated::TransactionStatusMeta = meta.clone().into();
+        let decoded = TransactionStatusMeta::try_from(gen_meta).unwrap();
+        assert_eq!(decoded.syscall_compute_units, meta.syscall_compute_units);
+
+        let gen_meta: generated::TransactionStatusMeta = TransactionStatusMeta::default().into();
+        assert_eq!(gen_meta.syscall_compute_units, None);
+        let decoded = TransactionStatusMeta::try_from(gen_meta).unwrap();
+        assert_eq!(decoded.syscall_compute_units, None);
+    }
+
     #[test]
     fn test_transaction_error_encode() {
         let transaction_error = TransactionError::AccountBorrowOutstanding;
@@ -231,6 +231,8 @@ impl From<StoredTransactionStatusMeta> for TransactionStatusMeta {
             return_data,
             compute_units_consumed,
             cost_units,
+            log_events: None,
+            syscall_compute_units: None,
         }
     }
 }
@@ -252,6 +254,11 @@ impl TryFrom<TransactionStatusMeta> for StoredTransactionStatusMeta {
             return_data,
             compute_units_consumed,
             cost_units,
+            // Deprecated bincode serialized status metadata doesn't support
+            // log events, they are only kept in the protobuf encoding.
+            log_events: _,
+            // Nor the compute units charged by the syscalls
+            syscall_compute_units: _,
         } = value;
 
         if !loaded_addresses.is_empty() {
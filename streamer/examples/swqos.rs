@@ -111,6 +111,7 @@ async fn main() -> anyhow::Result<()> {
         stats,
         thread: run_thread,
         max_concurrent_connections: _,
+        connection_qos: _,
     } = solana_streamer::nonblocking::quic::spawn_server_with_cancel(
         "quic_streamer_test",
         [socket.try_clone()?],
@@ -0,0 +1,380 @@
+//! Per-connection quality of service accounting of the QUIC server.
+//!
+//! Every connection handled by the server is registered in the
+//! [`ConnectionQosRegistry`] of the server while it is open, so that the bytes,
+//! streams, chunk latencies and malformed packets of each peer can be queried,
+//! e.g. through the admin RPC of the validator. The connections whose
+//! accounting exceeds the thresholds of [`SlowPeerEvictionConfig`] are evicted
+//! by the server to free their slot for better behaving peers.
+
+use {
+    crate::nonblocking::quic::ConnectionPeerType,
+    solana_pubkey::Pubkey,
+    std::{
+        collections::{HashMap, VecDeque},
+        net::SocketAddr,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc, Mutex, RwLock,
+        },
+        time::{Duration, Instant},
+    },
+};
+
+/// Number of evicted connections whose accounting is kept in the registry.
+const MAX_EVICTED_CONNECTIONS: usize = 256;
+
+pub const DEFAULT_SLOW_PEER_MIN_STREAMS: u64 = 64;
+pub const DEFAULT_SLOW_PEER_MAX_MEAN_CHUNK_LATENCY: Duration = Duration::from_millis(250);
+pub const DEFAULT_SLOW_PEER_MAX_TIMED_OUT_STREAMS_PERCENT: u64 = 10;
+
+/// Thresholds above which a connection is evicted as a slow peer.
+///
+/// The thresholds are only checked once the connection received `min_streams`
+/// streams, so that a few slow streams of a new connection do not evict it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SlowPeerEvictionConfig {
+    /// Number of streams a connection must receive before it can be evicted.
+    pub min_streams: u64,
+    /// Maximum mean time to wait for the chunks of the streams of a connection.
+    pub max_mean_chunk_latency: Duration,
+    /// Maximum percentage of the streams of a connection which time out
+    /// waiting for their chunks.
+    pub max_timed_out_streams_percent: u64,
+    /// Whether the connections of staked peers are evicted too, by default only
+    /// the connections of unstaked peers are.
+    pub evict_staked: bool,
+}
+
+impl Default for SlowPeerEvictionConfig {
+    fn default() -> Self {
+        Self {
+            min_streams: DEFAULT_SLOW_PEER_MIN_STREAMS,
+            max_mean_chunk_latency: DEFAULT_SLOW_PEER_MAX_MEAN_CHUNK_LATENCY,
+            max_timed_out_streams_percent: DEFAULT_SLOW_PEER_MAX_TIMED_OUT_STREAMS_PERCENT,
+            evict_staked: false,
+        }
+    }
+}
+
+impl SlowPeerEvictionConfig {
+    pub(crate) fn should_evict(&self, peer_type: ConnectionPeerType, qos: &ConnectionQos) -> bool {
+        if peer_type.is_staked() && !self.evict_staked {
+            return false;
+        }
+        let streams = qos.streams.load(Ordering::Relaxed);
+        if streams == 0 || streams < self.min_streams {
+            return false;
+        }
+        let timed_out_streams = qos.timed_out_streams.load(Ordering::Relaxed);
+        timed_out_streams.saturating_mul(100) / streams > self.max_timed_out_streams_percent
+            || qos.mean_chunk_latency() > self.max_mean_chunk_latency
+    }
+}
+
+/// Accounting of a connection, updated by the task handling the connection.
+#[derive(Debug)]
+pub(crate) struct ConnectionQos {
+    connected_at: Instant,
+    bytes: AtomicU64,
+    streams: AtomicU64,
+    timed_out_streams: AtomicU64,
+    chunks: AtomicU64,
+    chunk_latency_us_sum: AtomicU64,
+    chunk_latency_us_max: AtomicU64,
+    malformed_packets_dropped: AtomicU64,
+}
+
+impl Default for ConnectionQos {
+    fn default() -> Self {
+        Self {
+            connected_at: Instant::now(),
+            bytes: AtomicU64::default(),
+            streams: AtomicU64::default(),
+            timed_out_streams: AtomicU64::default(),
+            chunks: AtomicU64::default(),
+            chunk_latency_us_sum: AtomicU64::default(),
+            chunk_latency_us_max: AtomicU64::default(),
+            malformed_packets_dropped: AtomicU64::default(),
+        }
+    }
+}
+
+impl ConnectionQos {
+    pub(crate) fn record_stream(&self) {
+        self.streams.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_stream_timeout(&self) {
+        self.timed_out_streams.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records `num_chunks` chunks of `bytes` in total, which were received
+    /// after waiting for `latency`.
+    pub(crate) fn record_chunks(&self, num_chunks: usize, bytes: usize, latency: Duration) {
+        let latency_us = latency.as_micros() as u64;
+        self.chunks.fetch_add(num_chunks as u64, Ordering::Relaxed);
+        self.bytes.fetch_add(bytes as u64, Ordering::Relaxed);
+        self.chunk_latency_us_sum
+            .fetch_add(latency_us, Ordering::Relaxed);
+        self.chunk_latency_us_max
+            .fetch_max(latency_us, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_malformed_packet(&self) {
+        self.malformed_packets_dropped
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn mean_chunk_latency(&self) -> Duration {
+        let chunks = self.chunks.load(Ordering::Relaxed);
+        if chunks == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_micros(self.chunk_latency_us_sum.load(Ordering::Relaxed) / chunks)
+    }
+}
+
+/// Accounting of a connection at the time [`ConnectionQosRegistry`] is queried.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConnectionQosSnapshot {
+    pub server: &'static str,
+    pub remote_address: SocketAddr,
+    pub remote_pubkey: Option<Pubkey>,
+    pub stake: u64,
+    pub connected_for: Duration,
+    pub bytes: u64,
+    pub streams: u64,
+    pub timed_out_streams: u64,
+    pub chunks: u64,
+    pub mean_chunk_latency: Duration,
+    pub max_chunk_latency: Duration,
+    pub malformed_packets_dropped: u64,
+}
+
+#[derive(Debug)]
+struct RegisteredConnection {
+    remote_address: SocketAddr,
+    remote_pubkey: Option<Pubkey>,
+    peer_type: ConnectionPeerType,
+    qos: Arc<ConnectionQos>,
+}
+
+/// Registry of the accounting of the open connections of a QUIC server, along
+/// with the accounting of the connections it recently evicted.
+#[derive(Debug)]
+pub struct ConnectionQosRegistry {
+    server: &'static str,
+    next_id: AtomicU64,
+    connections: RwLock<HashMap<u64, RegisteredConnection>>,
+    evicted: Mutex<VecDeque<ConnectionQosSnapshot>>,
+}
+
+impl ConnectionQosRegistry {
+    pub fn new(server: &'static str) -> Self {
+        Self {
+            server,
+            next_id: AtomicU64::default(),
+            connections: RwLock::default(),
+            evicted: Mutex::default(),
+        }
+    }
+
+    /// Name of the server, as used for its metrics.
+    pub fn server(&self) -> &'static str {
+        self.server
+    }
+
+    /// Returns the accounting of the open connections.
+    pub fn connections(&self) -> Vec<ConnectionQosSnapshot> {
+        self.connections
+            .read()
+            .unwrap()
+            .values()
+            .map(|connection| self.snapshot(connection))
+            .collect()
+    }
+
+    /// Returns the accounting of the connections recently evicted as slow
+    /// peers, the most recently evicted last.
+    pub fn evicted_connections(&self) -> Vec<ConnectionQosSnapshot> {
+        self.evicted.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// Registers a connection, it is unregistered when the returned guard is
+    /// dropped.
+    pub(crate) fn register(
+        self: &Arc<Self>,
+        remote_address: SocketAddr,
+        remote_pubkey: Option<Pubkey>,
+        peer_type: ConnectionPeerType,
+    ) -> ConnectionQosGuard {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let qos = Arc::<ConnectionQos>::default();
+        self.connections.write().unwrap().insert(
+            id,
+            RegisteredConnection {
+                remote_address,
+                remote_pubkey,
+                peer_type,
+                qos: qos.clone(),
+            },
+        );
+        ConnectionQosGuard {
+            registry: self.clone(),
+            id,
+            qos,
+        }
+    }
+
+    fn snapshot(&self, connection: &RegisteredConnection) -> ConnectionQosSnapshot {
+        let RegisteredConnection {
+            remote_address,
+            remote_pubkey,
+            peer_type,
+            qos,
+        } = connection;
+        ConnectionQosSnapshot {
+            server: self.server,
+            remote_address: *remote_address,
+            remote_pubkey: *remote_pubkey,
+            stake: match peer_type {
+                ConnectionPeerType::Unstaked => 0,
+                ConnectionPeerType::Staked(stake) => *stake,
+            },
+            connected_for: qos.connected_at.elapsed(),
+            bytes: qos.bytes.load(Ordering::Relaxed),
+            streams: qos.streams.load(Ordering::Relaxed),
+            timed_out_streams: qos.timed_out_streams.load(Ordering::Relaxed),
+            chunks: qos.chunks.load(Ordering::Relaxed),
+            mean_chunk_latency: qos.mean_chunk_latency(),
+            max_chunk_latency: Duration::from_micros(
+                qos.chunk_latency_us_max.load(Ordering::Relaxed),
+            ),
+            malformed_packets_dropped: qos.malformed_packets_dropped.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Registration of a connection in a [`ConnectionQosRegistry`].
+pub(crate) struct ConnectionQosGuard {
+    registry: Arc<ConnectionQosRegistry>,
+    id: u64,
+    qos: Arc<ConnectionQos>,
+}
+
+impl ConnectionQosGuard {
+    pub(crate) fn qos(&self) -> &ConnectionQos {
+        &self.qos
+    }
+
+    /// Keeps the accounting of the connection in the evicted connections of the
+    /// registry.
+    pub(crate) fn record_eviction(&self) {
+        let Some(snapshot) = self
+            .registry
+            .connections
+            .read()
+            .unwrap()
+            .get(&self.id)
+            .map(|connection| self.registry.snapshot(connection))
+        else {
+            return;
+        };
+        let mut evicted = self.registry.evicted.lock().unwrap();
+        if evicted.len() >= MAX_EVICTED_CONNECTIONS {
+            evicted.pop_front();
+        }
+        evicted.push_back(snapshot);
+    }
+}
+
+impl Drop for ConnectionQosGuard {
+    fn drop(&mut self) {
+        self.registry.connections.write().unwrap().remove(&self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::net::Ipv4Addr};
+
+    fn remote_address() -> SocketAddr {
+        SocketAddr::from((Ipv4Addr::LOCALHOST, 8000))
+    }
+
+    #[test]
+    fn test_connection_qos_registry() {
+        let registry = Arc::new(ConnectionQosRegistry::new("test"));
+        let guard = registry.register(remote_address(), None, ConnectionPeerType::Unstaked);
+        guard.qos().record_stream();
+        guard
+            .qos()
+            .record_chunks(2, 1000, Duration::from_millis(10));
+        guard.qos().record_chunks(1, 200, Duration::from_millis(30));
+        guard.qos().record_malformed_packet();
+
+        let connections = registry.connections();
+        assert_eq!(connections.len(), 1);
+        let connection = &connections[0];
+        assert_eq!(connection.server, "test");
+        assert_eq!(connection.remote_address, remote_address());
+        assert_eq!(connection.stake, 0);
+        assert_eq!(connection.bytes, 1200);
+        assert_eq!(connection.streams, 1);
+        assert_eq!(connection.chunks, 3);
+        assert_eq!(connection.mean_chunk_latency, Duration::from_micros(13_333));
+        assert_eq!(connection.max_chunk_latency, Duration::from_millis(30));
+        assert_eq!(connection.malformed_packets_dropped, 1);
+
+        guard.record_eviction();
+        drop(guard);
+        assert!(registry.connections().is_empty());
+        let evicted = registry.evicted_connections();
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(evicted[0].bytes, 1200);
+    }
+
+    #[test]
+    fn test_slow_peer_eviction() {
+        let config = SlowPeerEvictionConfig {
+            min_streams: 4,
+            max_mean_chunk_latency: Duration::from_millis(100),
+            max_timed_out_streams_percent: 25,
+            evict_staked: false,
+        };
+        let registry = Arc::new(ConnectionQosRegistry::new("test"));
+        let guard = registry.register(remote_address(), None, ConnectionPeerType::Unstaked);
+        let qos = guard.qos();
+
+        // Slow chunks do not evict the connection until it received enough streams
+        for _ in 0..3 {
+            qos.record_stream();
+            qos.record_chunks(1, 100, Duration::from_millis(200));
+        }
+        assert!(!config.should_evict(ConnectionPeerType::Unstaked, qos));
+        qos.record_stream();
+        assert!(config.should_evict(ConnectionPeerType::Unstaked, qos));
+        // Staked peers are only evicted if configured so
+        assert!(!config.should_evict(ConnectionPeerType::Staked(1), qos));
+        let config_staked = SlowPeerEvictionConfig {
+            evict_staked: true,
+            ..config.clone()
+        };
+        assert!(config_staked.should_evict(ConnectionPeerType::Staked(1), qos));
+
+        let guard = registry.register(remote_address(), None, ConnectionPeerType::Unstaked);
+        let qos = guard.qos();
+        for _ in 0..8 {
+            qos.record_stream();
+            qos.record_chunks(1, 100, Duration::from_millis(1));
+        }
+        assert!(!config.should_evict(ConnectionPeerType::Unstaked, qos));
+        // 2 out of 8 streams timing out is at the threshold
+        qos.record_stream_timeout();
+        qos.record_stream_timeout();
+        assert!(!config.should_evict(ConnectionPeerType::Unstaked, qos));
+        qos.record_stream_timeout();
+        assert!(config.should_evict(ConnectionPeerType::Unstaked, qos));
+    }
+}
@@ -1,3 +1,4 @@
+pub mod connection_qos;
 pub mod connection_rate_limiter;
 pub mod quic;
 #[cfg(feature = "dev-context-only-utils")]
@@ -1,6 +1,7 @@
 use {
     crate::{
         nonblocking::{
+            connection_qos::{ConnectionQosRegistry, SlowPeerEvictionConfig},
             connection_rate_limiter::{ConnectionRateLimiter, TotalConnectionRateLimiter},
             stream_throttle::{
                 ConnectionStreamCounter, StakedStreamLoadEMA, STREAM_THROTTLING_INTERVAL,
@@ -84,6 +85,9 @@ const CONNECTION_CLOSE_REASON_TOO_MANY: &[u8] = b"too_many";
 const CONNECTION_CLOSE_CODE_INVALID_STREAM: u32 = 5;
 const CONNECTION_CLOSE_REASON_INVALID_STREAM: &[u8] = b"invalid_stream";
 
+const CONNECTION_CLOSE_CODE_SLOW_PEER: u32 = 6;
+const CONNECTION_CLOSE_REASON_SLOW_PEER: &[u8] = b"slow_peer";
+
 /// Total new connection counts per second. Heuristically taken from
 /// the default staked and unstaked connection limits. Might be adjusted
 /// later.
@@ -137,6 +141,7 @@ pub struct SpawnNonBlockingServerResult {
     pub stats: Arc<StreamerStats>,
     pub thread: JoinHandle<()>,
     pub max_concurrent_connections: usize,
+    pub connection_qos: Arc<ConnectionQosRegistry>,
 }
 
 #[deprecated(since = "3.0.0", note = "Use spawn_server instead")]
@@ -223,6 +228,7 @@ pub fn spawn_server_with_cancel(
         })
         .collect::<Result<Vec<_>, _>>()?;
     let stats = Arc::<StreamerStats>::default();
+    let connection_qos = Arc::new(ConnectionQosRegistry::new(name));
     let (packet_batch_sender, packet_batch_receiver) =
         bounded(quic_server_params.coalesce_channel_size);
     task::spawn_blocking({
@@ -243,6 +249,7 @@ pub fn spawn_server_with_cancel(
     let handle = tokio::spawn({
         let endpoints = endpoints.clone();
         let stats = stats.clone();
+        let connection_qos = connection_qos.clone();
         async move {
             let tasks = run_server(
                 name,
@@ -250,6 +257,7 @@ pub fn spawn_server_with_cancel(
                 packet_batch_sender,
                 staked_nodes,
                 stats.clone(),
+                connection_qos,
                 quic_server_params,
                 cancel,
             )
@@ -264,6 +272,7 @@ pub fn spawn_server_with_cancel(
         stats,
         thread: handle,
         max_concurrent_connections,
+        connection_qos,
     })
 }
 
@@ -319,6 +328,7 @@ async fn run_server(
     packet_batch_sender: Sender<PacketAccumulator>,
     staked_nodes: Arc<RwLock<StakedNodes>>,
     stats: Arc<StreamerStats>,
+    connection_qos: Arc<ConnectionQosRegistry>,
     quic_server_params: QuicServerParams,
     cancel: CancellationToken,
 ) -> TaskTracker {
@@ -429,6 +439,7 @@ async fn run_server(
                         staked_nodes.clone(),
                         stats.clone(),
                         stream_load_ema.clone(),
+                        connection_qos.clone(),
                         quic_server_params.clone(),
                         tasks.clone(),
                     ));
@@ -535,6 +546,8 @@ struct NewConnectionHandlerParams {
     min_stake: u64,
     max_connections: usize,
     wait_for_chunk_timeout: Duration,
+    connection_qos: Arc<ConnectionQosRegistry>,
+    slow_peer_eviction: Option<SlowPeerEvictionConfig>,
 }
 
 impl NewConnectionHandlerParams {
@@ -544,6 +557,8 @@ impl NewConnectionHandlerParams {
         stats: Arc<StreamerStats>,
         wait_for_chunk_timeout: Duration,
         max_connections: usize,
+        connection_qos: Arc<ConnectionQosRegistry>,
+        slow_peer_eviction: Option<SlowPeerEvictionConfig>,
     ) -> NewConnectionHandlerParams {
         NewConnectionHandlerParams {
             packet_sender,
@@ -556,6 +571,8 @@ impl NewConnectionHandlerParams {
             min_stake: 0,
             max_connections,
             wait_for_chunk_timeout,
+            connection_qos,
+            slow_peer_eviction,
         }
     }
 }
@@ -735,6 +752,7 @@ async fn setup_connection(
     staked_nodes: Arc<RwLock<StakedNodes>>,
     stats: Arc<StreamerStats>,
     stream_load_ema: Arc<StakedStreamLoadEMA>,
+    connection_qos: Arc<ConnectionQosRegistry>,
     quic_server_params: QuicServerParams,
     tasks: TaskTracker,
 ) {
@@ -783,6 +801,8 @@ async fn setup_connection(
                         stats.clone(),
                         quic_server_params.wait_for_chunk_timeout,
                         quic_server_params.max_unstaked_connections,
+                        connection_qos.clone(),
+                        quic_server_params.slow_peer_eviction.clone(),
                     ),
                     |(pubkey, stake, total_stake, max_stake, min_stake)| {
                         // The heuristic is that the stake should be large engouh to have 1 stream pass throuh within one throttle
@@ -809,6 +829,8 @@ async fn setup_connection(
                             min_stake,
                             wait_for_chunk_timeout: quic_server_params.wait_for_chunk_timeout,
                             max_connections: quic_server_params.max_staked_connections,
+                            connection_qos,
+                            slow_peer_eviction: quic_server_params.slow_peer_eviction.clone(),
                         }
                     },
                 );
@@ -1108,6 +1130,8 @@ async fn handle_connection(
         remote_pubkey,
         stats,
         total_stake,
+        connection_qos,
+        slow_peer_eviction,
         ..
     } = params;
 
@@ -1118,6 +1142,7 @@ async fn handle_connection(
         stats.total_connections.load(Ordering::Relaxed),
     );
     stats.total_connections.fetch_add(1, Ordering::Relaxed);
+    let connection_qos = connection_qos.register(remote_addr, remote_pubkey, peer_type);
 
     'conn: loop {
         // Wait for new streams. If the peer is disconnected we get a cancellation signal and stop
@@ -1171,6 +1196,7 @@ async fn handle_connection(
         stream_counter.stream_count.fetch_add(1, Ordering::Relaxed);
         stats.active_streams.fetch_add(1, Ordering::Relaxed);
         stats.total_new_streams.fetch_add(1, Ordering::Relaxed);
+        connection_qos.qos().record_stream();
 
         let mut meta = Meta::default();
         meta.set_socket_addr(&remote_addr);
@@ -1191,6 +1217,7 @@ async fn handle_connection(
             // Read the next chunks, waiting up to `wait_for_chunk_timeout`. If we don't get chunks
             // before then, we assume the stream is dead. This can only happen if there's severe
             // packet loss or the peer stops sending for whatever reason.
+            let chunk_wait_start = Instant::now();
             let n_chunks = match tokio::select! {
                 chunk = tokio::time::timeout(
                     params.wait_for_chunk_timeout,
@@ -1215,9 +1242,17 @@ async fn handle_connection(
                     stats
                         .total_stream_read_timeouts
                         .fetch_add(1, Ordering::Relaxed);
+                    connection_qos.qos().record_stream_timeout();
                     break;
                 }
             };
+            if n_chunks > 0 {
+                connection_qos.qos().record_chunks(
+                    n_chunks,
+                    chunks.iter().take(n_chunks).map(Bytes::len).sum(),
+                    chunk_wait_start.elapsed(),
+                );
+            }
 
             match handle_chunks(
                 // Bytes::clone() is a cheap atomic inc
@@ -1235,6 +1270,7 @@ async fn handle_connection(
                 // The stream is still active, continue reading.
                 Ok(StreamState::Receiving) => {}
                 Err(_) => {
+                    connection_qos.qos().record_malformed_packet();
                     // Disconnect peers that send invalid streams.
                     connection.close(
                         CONNECTION_CLOSE_CODE_INVALID_STREAM.into(),
@@ -1249,6 +1285,23 @@ async fn handle_connection(
 
         stats.active_streams.fetch_sub(1, Ordering::Relaxed);
         stream_load_ema.update_ema_if_needed();
+
+        // Free the slot of peers which keep streams open without delivering their chunks in time.
+        if slow_peer_eviction
+            .as_ref()
+            .is_some_and(|config| config.should_evict(peer_type, connection_qos.qos()))
+        {
+            debug!("Evicting slow peer {remote_addr:?}, peer type: {peer_type:?}");
+            connection_qos.record_eviction();
+            connection.close(
+                CONNECTION_CLOSE_CODE_SLOW_PEER.into(),
+                CONNECTION_CLOSE_REASON_SLOW_PEER,
+            );
+            stats
+                .connections_evicted_slow_peer
+                .fetch_add(1, Ordering::Relaxed);
+            break;
+        }
     }
 
     let stable_id = connection.stable_id();
@@ -2076,6 +2129,7 @@ pub mod test {
             stats: _,
             thread: t,
             max_concurrent_connections: _,
+            connection_qos: _,
         } = spawn_server_with_cancel(
             "quic_streamer_test",
             [s],
@@ -2109,6 +2163,7 @@ pub mod test {
             stats,
             thread: t,
             max_concurrent_connections: _,
+            connection_qos: _,
         } = spawn_server_with_cancel(
             "quic_streamer_test",
             [s],
@@ -2560,4 +2615,50 @@ pub mod test {
         cancel.cancel();
         join_handle.await.unwrap();
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_client_connection_close_slow_peer() {
+        let SpawnTestServerResult {
+            join_handle,
+            server_address,
+            stats,
+            cancel,
+            ..
+        } = setup_quic_server(
+            None,
+            QuicServerParams {
+                wait_for_chunk_timeout: Duration::from_millis(100),
+                slow_peer_eviction: Some(SlowPeerEvictionConfig {
+                    min_streams: 2,
+                    max_timed_out_streams_percent: 0,
+                    ..SlowPeerEvictionConfig::default()
+                }),
+                ..QuicServerParams::default_for_tests()
+            },
+        );
+
+        let client_connection = make_client_endpoint(&server_address, None).await;
+
+        // Start streams without finishing them, so that they all time out
+        let mut send_streams = vec![];
+        for _ in 0..2 {
+            let mut send_stream = client_connection.open_uni().await.unwrap();
+            send_stream.write_all(&[42]).await.unwrap();
+            send_streams.push(send_stream);
+        }
+        match client_connection.closed().await {
+            ConnectionError::ApplicationClosed(ApplicationClose { error_code, reason }) => {
+                assert_eq!(error_code, CONNECTION_CLOSE_CODE_SLOW_PEER.into());
+                assert_eq!(reason, CONNECTION_CLOSE_REASON_SLOW_PEER);
+            }
+            _ => panic!("unexpected close"),
+        }
+        assert_eq!(stats.total_stream_read_timeouts.load(Ordering::Relaxed), 2);
+        assert_eq!(
+            stats.connections_evicted_slow_peer.load(Ordering::Relaxed),
+            1
+        );
+        cancel.cancel();
+        join_handle.await.unwrap();
+    }
 }
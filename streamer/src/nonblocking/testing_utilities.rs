@@ -99,6 +99,7 @@ pub fn setup_quic_server_with_sockets(
         stats,
         thread: handle,
         max_concurrent_connections: _,
+        connection_qos: _,
     } = spawn_server_with_cancel(
         "quic_streamer_test",
         sockets,
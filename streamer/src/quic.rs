@@ -1,6 +1,9 @@
 use {
     crate::{
-        nonblocking::quic::{ALPN_TPU_PROTOCOL_ID, DEFAULT_WAIT_FOR_CHUNK_TIMEOUT},
+        nonblocking::{
+            connection_qos::{ConnectionQosRegistry, SlowPeerEvictionConfig},
+            quic::{ALPN_TPU_PROTOCOL_ID, DEFAULT_WAIT_FOR_CHUNK_TIMEOUT},
+        },
         streamer::StakedNodes,
     },
     crossbeam_channel::Sender,
@@ -67,6 +70,7 @@ pub struct SpawnServerResult {
     pub endpoints: Vec<Endpoint>,
     pub thread: thread::JoinHandle<()>,
     pub key_updater: Arc<EndpointKeyUpdater>,
+    pub connection_qos: Arc<ConnectionQosRegistry>,
 }
 
 /// Controls the the channel size for the PacketBatch coalesce
@@ -174,6 +178,7 @@ pub struct StreamerStats {
     pub(crate) total_chunks_processed_by_batcher: AtomicUsize,
     pub(crate) total_stream_read_errors: AtomicUsize,
     pub(crate) total_stream_read_timeouts: AtomicUsize,
+    pub(crate) connections_evicted_slow_peer: AtomicUsize,
     pub(crate) num_evictions_staked: AtomicUsize,
     pub(crate) num_evictions_unstaked: AtomicUsize,
     pub(crate) connection_added_from_staked_peer: AtomicUsize,
@@ -486,6 +491,12 @@ impl StreamerStats {
                 self.total_stream_read_timeouts.swap(0, Ordering::Relaxed),
                 i64
             ),
+            (
+                "connections_evicted_slow_peer",
+                self.connections_evicted_slow_peer
+                    .swap(0, Ordering::Relaxed),
+                i64
+            ),
             (
                 "throttled_streams",
                 self.throttled_streams.swap(0, Ordering::Relaxed),
@@ -630,6 +641,8 @@ pub struct QuicServerParams {
     pub coalesce: Duration,
     pub coalesce_channel_size: usize,
     pub num_threads: NonZeroUsize,
+    /// Thresholds to evict slow peers, `None` disables the eviction.
+    pub slow_peer_eviction: Option<SlowPeerEvictionConfig>,
 }
 
 impl Default for QuicServerParams {
@@ -644,6 +657,7 @@ impl Default for QuicServerParams {
             coalesce: DEFAULT_TPU_COALESCE,
             coalesce_channel_size: DEFAULT_MAX_COALESCE_CHANNEL_SIZE,
             num_threads: NonZeroUsize::new(num_cpus::get().min(1)).expect("1 is non-zero"),
+            slow_peer_eviction: None,
         }
     }
 }
@@ -741,6 +755,7 @@ pub fn spawn_server_with_cancel(
         endpoints: result.endpoints,
         thread: handle,
         key_updater: Arc::new(updater),
+        connection_qos: result.connection_qos,
     })
 }
 
@@ -777,6 +792,7 @@ mod test {
             endpoints: _,
             thread: t,
             key_updater: _,
+            connection_qos: _,
         } = spawn_server_with_cancel(
             "solQuicTest",
             "quic_streamer_test",
@@ -832,6 +848,7 @@ mod test {
             endpoints: _,
             thread: t,
             key_updater: _,
+            connection_qos: _,
         } = spawn_server_with_cancel(
             "solQuicTest",
             "quic_streamer_test",
@@ -877,6 +894,7 @@ mod test {
             endpoints: _,
             thread: t,
             key_updater: _,
+            connection_qos: _,
         } = spawn_server_with_cancel(
             "solQuicTest",
             "quic_streamer_test",
@@ -1,13 +1,69 @@
 pub use log;
-use std::{cell::RefCell, rc::Rc};
+use {
+    solana_pubkey::Pubkey,
+    std::{cell::RefCell, rc::Rc},
+};
 
 const LOG_MESSAGES_BYTES_LIMIT: usize = 10 * 1000;
 
+/// Typed counterpart of the stable program log messages
+///
+/// Events are recorded alongside the log messages once enabled with
+/// [`LogCollector::record_events`], see `solana_program_runtime::stable_log`.
+/// Only the payloads of the `Log`, `Data` and `Return` events count towards
+/// the bytes limit, so that the invocation structure of a transaction is
+/// always complete.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LogEvent {
+    /// A program was invoked, `stack_height` is its height in the invocation stack
+    Invoke {
+        program_id: Pubkey,
+        stack_height: usize,
+    },
+    /// Message logged by the program
+    Log { message: String },
+    /// Data emitted by the program with `sol_log_data`
+    Data { data: Vec<Vec<u8>> },
+    /// Return data set by the program
+    Return { program_id: Pubkey, data: Vec<u8> },
+    /// Compute units consumed by the program, out of the ones available when it was invoked
+    ComputeUnits {
+        program_id: Pubkey,
+        consumed: u64,
+        available: u64,
+    },
+    /// The program completed successfully
+    Success { program_id: Pubkey },
+    /// The program failed, `error` describes why
+    Failure { program_id: Pubkey, error: String },
+    /// The payload events past this one were dropped because of the bytes limit
+    Truncated,
+}
+
+impl LogEvent {
+    fn payload_len(&self) -> usize {
+        match self {
+            Self::Log { message } => message.len(),
+            Self::Data { data } => data.iter().map(Vec::len).sum(),
+            Self::Return { data, .. } => data.len(),
+            Self::Invoke { .. }
+            | Self::ComputeUnits { .. }
+            | Self::Success { .. }
+            | Self::Failure { .. }
+            | Self::Truncated => 0,
+        }
+    }
+}
+
 pub struct LogCollector {
     pub messages: Vec<String>,
     pub bytes_written: usize,
     pub bytes_limit: Option<usize>,
     pub limit_warning: bool,
+    /// `None` unless the events are recorded
+    pub events: Option<Vec<LogEvent>>,
+    pub event_bytes_written: usize,
+    pub event_limit_warning: bool,
 }
 
 impl Default for LogCollector {
@@ -17,6 +73,9 @@ impl Default for LogCollector {
             bytes_written: 0,
             bytes_limit: Some(LOG_MESSAGES_BYTES_LIMIT),
             limit_warning: false,
+            events: None,
+            event_bytes_written: 0,
+            event_limit_warning: false,
         }
     }
 }
@@ -40,10 +99,41 @@ impl LogCollector {
         }
     }
 
+    /// Records the [`LogEvent`]s logged from now on, which are dropped otherwise
+    pub fn record_events(&mut self) {
+        self.events.get_or_insert_with(Vec::new);
+    }
+
+    pub fn log_event(&mut self, event: LogEvent) {
+        let Some(events) = self.events.as_mut() else {
+            return;
+        };
+        let payload_len = event.payload_len();
+        let Some(limit) = self.bytes_limit.filter(|_| payload_len > 0) else {
+            events.push(event);
+            return;
+        };
+
+        let event_bytes_written = self.event_bytes_written.saturating_add(payload_len);
+        if event_bytes_written >= limit {
+            if !self.event_limit_warning {
+                self.event_limit_warning = true;
+                events.push(LogEvent::Truncated);
+            }
+        } else {
+            self.event_bytes_written = event_bytes_written;
+            events.push(event);
+        }
+    }
+
     pub fn get_recorded_content(&self) -> &[String] {
         self.messages.as_slice()
     }
 
+    pub fn get_recorded_events(&self) -> &[LogEvent] {
+        self.events.as_deref().unwrap_or_default()
+    }
+
     pub fn new_ref() -> Rc<RefCell<Self>> {
         Rc::new(RefCell::new(Self::default()))
     }
@@ -58,6 +148,10 @@ impl LogCollector {
     pub fn into_messages(self) -> Vec<String> {
         self.messages
     }
+
+    pub fn into_messages_and_events(self) -> (Vec<String>, Option<Vec<LogEvent>>) {
+        (self.messages, self.events)
+    }
 }
 
 /// Convenience macro to log a message with an `Option<Rc<RefCell<LogCollector>>>`
@@ -89,6 +183,18 @@ macro_rules! ic_logger_msg {
     };
 }
 
+/// Convenience macro to record a [`LogEvent`] with an `Option<Rc<RefCell<LogCollector>>>`
+#[macro_export]
+macro_rules! ic_logger_event {
+    ($log_collector:expr, $event:expr) => {
+        if let Some(log_collector) = $log_collector.as_ref() {
+            if let Ok(mut log_collector) = log_collector.try_borrow_mut() {
+                log_collector.log_event($event);
+            }
+        }
+    };
+}
+
 /// Convenience macro to log a message with an `InvokeContext`
 #[macro_export]
 macro_rules! ic_msg {
@@ -119,4 +225,51 @@ pub(crate) mod tests {
         }
         assert_eq!(logs.last(), Some(&"Log truncated".to_string()));
     }
+
+    #[test]
+    fn test_log_events_bytes_limit() {
+        let mut lc = LogCollector {
+            bytes_limit: Some(10),
+            ..LogCollector::default()
+        };
+        let program_id = Pubkey::new_from_array([1; 32]);
+
+        // Events are dropped unless recorded
+        lc.log_event(LogEvent::Success { program_id });
+        assert!(lc.events.is_none());
+
+        lc.record_events();
+        lc.log_event(LogEvent::Invoke {
+            program_id,
+            stack_height: 1,
+        });
+        for _ in 0..5 {
+            lc.log_event(LogEvent::Data {
+                data: vec![vec![0; 3]],
+            });
+        }
+        lc.log_event(LogEvent::Success { program_id });
+
+        // Events without payload are not truncated
+        assert_eq!(
+            lc.get_recorded_events(),
+            &[
+                LogEvent::Invoke {
+                    program_id,
+                    stack_height: 1,
+                },
+                LogEvent::Data {
+                    data: vec![vec![0; 3]],
+                },
+                LogEvent::Data {
+                    data: vec![vec![0; 3]],
+                },
+                LogEvent::Data {
+                    data: vec![vec![0; 3]],
+                },
+                LogEvent::Truncated,
+                LogEvent::Success { program_id },
+            ]
+        );
+    }
 }
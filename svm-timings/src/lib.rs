@@ -434,6 +434,64 @@ impl ExecuteDetailsTimings {
     }
 }
 
+/// Categories of syscalls, the compute units they charge are attributed to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SyscallCategory {
+    /// Hash functions and program address derivation
+    Hashing,
+    /// Elliptic curve operations and signature recovery
+    CurveOps,
+    /// Cross-program invocations, not including the compute units consumed by the callee
+    Cpi,
+    Logging,
+    MemoryOps,
+    /// Sysvars and epoch stakes
+    Sysvars,
+    Other,
+}
+
+/// Compute units charged by the syscalls of a transaction, per [`SyscallCategory`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SyscallComputeUnits {
+    pub hashing: u64,
+    pub curve_ops: u64,
+    pub cpi: u64,
+    pub logging: u64,
+    pub memory_ops: u64,
+    pub sysvars: u64,
+    pub other: u64,
+}
+
+impl SyscallComputeUnits {
+    pub fn add(&mut self, category: SyscallCategory, units: u64) {
+        let category_units = match category {
+            SyscallCategory::Hashing => &mut self.hashing,
+            SyscallCategory::CurveOps => &mut self.curve_ops,
+            SyscallCategory::Cpi => &mut self.cpi,
+            SyscallCategory::Logging => &mut self.logging,
+            SyscallCategory::MemoryOps => &mut self.memory_ops,
+            SyscallCategory::Sysvars => &mut self.sysvars,
+            SyscallCategory::Other => &mut self.other,
+        };
+        *category_units = category_units.saturating_add(units);
+    }
+
+    /// Compute units charged by all the syscalls
+    pub fn total(&self) -> u64 {
+        [
+            self.hashing,
+            self.curve_ops,
+            self.cpi,
+            self.logging,
+            self.memory_ops,
+            self.sysvars,
+            self.other,
+        ]
+        .into_iter()
+        .fold(0, u64::saturating_add)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -530,4 +588,23 @@ mod tests {
         let check_us = timings.metrics.index(ExecuteTimingType::CheckUs);
         assert_eq!(3, check_us.0);
     }
+
+    #[test]
+    fn test_syscall_compute_units_add() {
+        let mut syscall_compute_units = SyscallComputeUnits::default();
+        syscall_compute_units.add(SyscallCategory::Hashing, 85);
+        syscall_compute_units.add(SyscallCategory::Logging, 100);
+        syscall_compute_units.add(SyscallCategory::Hashing, 15);
+        syscall_compute_units.add(SyscallCategory::Cpi, u64::MAX);
+        assert_eq!(
+            syscall_compute_units,
+            SyscallComputeUnits {
+                hashing: 100,
+                logging: 100,
+                cpi: u64::MAX,
+                ..SyscallComputeUnits::default()
+            }
+        );
+        assert_eq!(syscall_compute_units.total(), u64::MAX);
+    }
 }
@@ -28,10 +28,7 @@ use {
     },
     solana_pubkey::Pubkey,
     solana_rent::Rent,
-    solana_sdk_ids::{
-        bpf_loader_upgradeable, native_loader,
-        sysvar::{self, slot_history},
-    },
+    solana_sdk_ids::{bpf_loader_upgradeable, native_loader, sysvar},
     solana_svm_callback::{AccountState, TransactionProcessingCallback},
     solana_svm_feature_set::SVMFeatureSet,
     solana_svm_transaction::svm_message::SVMMessage,
@@ -176,12 +173,15 @@ impl<'a, CB: TransactionProcessingCallback> AccountLoader<'a, CB> {
     ) -> AccountLoader<'a, CB> {
         let mut loaded_accounts = AHashMap::with_capacity(capacity);
 
-        // SlotHistory may be overridden for simulation.
-        // No other uses of AccountOverrides are expected.
-        if let Some(slot_history) =
-            account_overrides.and_then(|overrides| overrides.get(&slot_history::id()))
-        {
-            loaded_accounts.insert(slot_history::id(), slot_history.clone());
+        // SlotHistory, and any account a simulation caller asks for, may be overridden for
+        // simulation. The overrides are seen by every transaction of the batch, until one of
+        // them writes the account.
+        if let Some(account_overrides) = account_overrides {
+            loaded_accounts.extend(
+                account_overrides
+                    .iter()
+                    .map(|(pubkey, account)| (*pubkey, account.clone())),
+            );
         }
 
         Self {
@@ -1298,16 +1298,21 @@ mod tests {
 
         let keypair = Keypair::new();
         let account = AccountSharedData::new(1_000_000, 0, &Pubkey::default());
+        let overridden_pubkey = Pubkey::new_unique();
+        account_overrides.set_account(
+            &overridden_pubkey,
+            Some(AccountSharedData::new(7, 3, &Pubkey::default())),
+        );
 
         let mut program_account = AccountSharedData::default();
         program_account.set_lamports(1);
         program_account.set_executable(true);
         program_account.set_owner(native_loader::id());
 
-        let instructions = vec![CompiledInstruction::new(2, &(), vec![0])];
+        let instructions = vec![CompiledInstruction::new(3, &(), vec![0, 2])];
         let tx = Transaction::new_with_compiled_instructions(
             &[&keypair],
-            &[slot_history_id],
+            &[slot_history_id, overridden_pubkey],
             Hash::default(),
             vec![bpf_loader::id()],
             instructions,
@@ -1326,6 +1331,9 @@ mod tests {
                 assert_eq!(loaded_transaction.accounts[0].0, keypair.pubkey());
                 assert_eq!(loaded_transaction.accounts[1].0, slot_history_id);
                 assert_eq!(loaded_transaction.accounts[1].1.lamports(), 42);
+                assert_eq!(loaded_transaction.accounts[2].0, overridden_pubkey);
+                assert_eq!(loaded_transaction.accounts[2].1.lamports(), 7);
+                assert_eq!(loaded_transaction.accounts[2].1.data().len(), 3);
             }
             TransactionLoadResult::FeesOnly(fees_only_tx) => panic!("{}", fees_only_tx.load_error),
             TransactionLoadResult::NotLoaded(e) => panic!("{e}"),
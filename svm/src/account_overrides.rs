@@ -13,7 +13,10 @@ pub struct AccountOverrides {
 
 impl AccountOverrides {
     /// Insert or remove an account with a given pubkey to/from the list of overrides.
-    fn set_account(&mut self, pubkey: &Pubkey, account: Option<AccountSharedData>) {
+    ///
+    /// Note: overriding an executable account does not change the program which is loaded into
+    /// the program cache for it
+    pub fn set_account(&mut self, pubkey: &Pubkey, account: Option<AccountSharedData>) {
         match account {
             Some(account) => self.accounts.insert(*pubkey, account),
             None => self.accounts.remove(pubkey),
@@ -28,9 +31,15 @@ impl AccountOverrides {
     }
 
     /// Gets the account if it's found in the list of overrides
+    #[cfg(test)]
     pub(crate) fn get(&self, pubkey: &Pubkey) -> Option<&AccountSharedData> {
         self.accounts.get(pubkey)
     }
+
+    /// Iterates over all overridden accounts
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&Pubkey, &AccountSharedData)> {
+        self.accounts.iter()
+    }
 }
 
 #[cfg(test)]
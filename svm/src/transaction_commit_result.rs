@@ -1,6 +1,7 @@
 use {
     crate::transaction_execution_result::TransactionLoadedAccountsStats,
     solana_fee_structure::FeeDetails, solana_message::inner_instruction::InnerInstructionsList,
+    solana_svm_log_collector::LogEvent, solana_svm_timings::SyscallComputeUnits,
     solana_transaction_context::TransactionReturnData, solana_transaction_error::TransactionResult,
 };
 
@@ -11,12 +12,14 @@ pub type TransactionCommitResult = TransactionResult<CommittedTransaction>;
 pub struct CommittedTransaction {
     pub status: TransactionResult<()>,
     pub log_messages: Option<Vec<String>>,
+    pub log_events: Option<Vec<LogEvent>>,
     pub inner_instructions: Option<InnerInstructionsList>,
     pub return_data: Option<TransactionReturnData>,
     pub executed_units: u64,
     pub fee_details: FeeDetails,
     pub loaded_account_stats: TransactionLoadedAccountsStats,
     pub fee_payer_post_balance: u64,
+    pub syscall_compute_units: Option<SyscallComputeUnits>,
 }
 
 pub trait TransactionCommitResultExtensions {
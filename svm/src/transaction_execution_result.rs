@@ -1,8 +1,12 @@
 use {
     crate::account_loader::LoadedTransaction,
     solana_message::inner_instruction::InnerInstructionsList,
-    solana_program_runtime::loaded_programs::ProgramCacheEntry,
+    solana_program_runtime::{
+        execution_trace::InvocationTrace, loaded_programs::ProgramCacheEntry,
+    },
     solana_pubkey::Pubkey,
+    solana_svm_log_collector::LogEvent,
+    solana_svm_timings::SyscallComputeUnits,
     solana_transaction_context::TransactionReturnData,
     solana_transaction_error::TransactionResult,
     std::{collections::HashMap, sync::Arc},
@@ -31,12 +35,18 @@ impl ExecutedTransaction {
 pub struct TransactionExecutionDetails {
     pub status: TransactionResult<()>,
     pub log_messages: Option<Vec<String>>,
+    /// Typed counterpart of `log_messages`
+    pub log_events: Option<Vec<LogEvent>>,
     pub inner_instructions: Option<InnerInstructionsList>,
     pub return_data: Option<TransactionReturnData>,
     pub executed_units: u64,
     /// The change in accounts data len for this transaction.
     /// NOTE: This value is valid IFF `status` is `Ok`.
     pub accounts_data_len_delta: i64,
+    /// Trace of the program invocations, if recording it was enabled
+    pub execution_trace: Option<Vec<InvocationTrace>>,
+    /// Compute units charged by the syscalls per category, if recording them was enabled
+    pub syscall_compute_units: Option<SyscallComputeUnits>,
 }
 
 impl TransactionExecutionDetails {
@@ -88,15 +88,27 @@ pub struct ExecutionRecordingConfig {
     pub enable_log_recording: bool,
     pub enable_return_data_recording: bool,
     pub enable_transaction_balance_recording: bool,
+    /// Record the trace of the program invocations, see
+    /// [`solana_program_runtime::execution_trace`]
+    pub enable_execution_trace: bool,
+    /// Record the compute units charged by the syscalls per category
+    pub enable_syscall_compute_units_recording: bool,
+    /// Record typed log events alongside the log messages, requires `enable_log_recording`
+    pub enable_log_event_recording: bool,
 }
 
 impl ExecutionRecordingConfig {
+    /// Note: the execution trace, which is expensive to record, and the log events, which
+    /// duplicate the log messages, are never enabled
     pub fn new_single_setting(option: bool) -> Self {
         ExecutionRecordingConfig {
             enable_return_data_recording: option,
             enable_log_recording: option,
             enable_cpi_recording: option,
             enable_transaction_balance_recording: option,
+            enable_execution_trace: false,
+            enable_syscall_compute_units_recording: option,
+            enable_log_event_recording: false,
         }
     }
 }
@@ -859,12 +871,16 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
             TransactionAccountStateInfo::new(&transaction_context, tx, &environment.rent);
 
         let log_collector = if config.recording_config.enable_log_recording {
-            match config.log_messages_bytes_limit {
-                None => Some(LogCollector::new_ref()),
-                Some(log_messages_bytes_limit) => Some(LogCollector::new_ref_with_limit(Some(
-                    log_messages_bytes_limit,
-                ))),
+            let log_collector = match config.log_messages_bytes_limit {
+                None => LogCollector::new_ref(),
+                Some(log_messages_bytes_limit) => {
+                    LogCollector::new_ref_with_limit(Some(log_messages_bytes_limit))
+                }
+            };
+            if config.recording_config.enable_log_event_recording {
+                log_collector.borrow_mut().record_events();
             }
+            Some(log_collector)
         } else {
             None
         };
@@ -886,6 +902,15 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
             compute_budget,
             self.execution_cost,
         );
+        if config.recording_config.enable_execution_trace {
+            invoke_context.enable_execution_trace();
+        }
+        if config
+            .recording_config
+            .enable_syscall_compute_units_recording
+        {
+            invoke_context.enable_syscall_compute_units();
+        }
 
         let mut process_message_time = Measure::start("process_message_time");
         let process_result = process_message(
@@ -897,6 +922,8 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
         );
         process_message_time.stop();
 
+        let execution_trace = invoke_context.take_execution_trace();
+        let syscall_compute_units = invoke_context.take_syscall_compute_units();
         drop(invoke_context);
 
         execute_timings.execute_accessories.process_message_us += process_message_time.as_us();
@@ -928,12 +955,14 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
                 err
             });
 
-        let log_messages: Option<TransactionLogMessages> =
-            log_collector.and_then(|log_collector| {
+        let (log_messages, log_events): (Option<TransactionLogMessages>, _) = log_collector
+            .and_then(|log_collector| {
                 Rc::try_unwrap(log_collector)
-                    .map(|log_collector| log_collector.into_inner().into_messages())
+                    .map(|log_collector| log_collector.into_inner().into_messages_and_events())
                     .ok()
-            });
+            })
+            .unzip();
+        let log_events = log_events.flatten();
 
         let (execution_record, inner_instructions) = Self::deconstruct_transaction(
             transaction_context,
@@ -972,10 +1001,13 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
             execution_details: TransactionExecutionDetails {
                 status,
                 log_messages,
+                log_events,
                 inner_instructions,
                 return_data,
                 executed_units,
                 accounts_data_len_delta,
+                execution_trace,
+                syscall_compute_units,
             },
             loaded_transaction,
             programs_modified_by_tx: program_cache_for_tx_batch.drain_modified_entries(),
@@ -257,6 +257,9 @@ fn svm_concurrent() {
                     enable_return_data_recording: false,
                     enable_cpi_recording: false,
                     enable_transaction_balance_recording: false,
+                    enable_execution_trace: false,
+                    enable_syscall_compute_units_recording: false,
+                    enable_log_event_recording: false,
                 },
                 ..Default::default()
             };
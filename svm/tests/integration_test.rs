@@ -150,6 +150,9 @@ impl SvmTestEnvironment<'_> {
                 enable_return_data_recording: true,
                 enable_cpi_recording: false,
                 enable_transaction_balance_recording: false,
+                enable_execution_trace: false,
+                enable_syscall_compute_units_recording: false,
+                enable_log_event_recording: false,
             },
             ..Default::default()
         };
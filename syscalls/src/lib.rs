@@ -46,6 +46,7 @@ use {
     solana_sha256_hasher::Hasher,
     solana_svm_feature_set::SVMFeatureSet,
     solana_svm_log_collector::{ic_logger_msg, ic_msg},
+    solana_svm_timings::SyscallCategory,
     solana_svm_type_overrides::sync::Arc,
     solana_sysvar::SysvarSerialize,
     solana_transaction_context::vm_slice::VmSlice,
@@ -260,8 +261,12 @@ impl HasherImpl for Keccak256Hasher {
     }
 }
 
-fn consume_compute_meter(invoke_context: &InvokeContext, amount: u64) -> Result<(), Error> {
-    invoke_context.consume_checked(amount)?;
+fn consume_compute_meter(
+    invoke_context: &InvokeContext,
+    category: SyscallCategory,
+    amount: u64,
+) -> Result<(), Error> {
+    invoke_context.consume_checked_for_syscall(category, amount)?;
     Ok(())
 }
 
@@ -716,7 +721,7 @@ declare_builtin_function!(
         _arg5: u64,
         memory_mapping: &mut MemoryMapping,
     ) -> Result<u64, Error> {
-        consume_compute_meter(invoke_context, len)?;
+        consume_compute_meter(invoke_context, SyscallCategory::Logging, len)?;
 
         translate_string_and_do(
             memory_mapping,
@@ -806,7 +811,7 @@ declare_builtin_function!(
         let cost = invoke_context
             .get_execution_cost()
             .create_program_address_units;
-        consume_compute_meter(invoke_context, cost)?;
+        consume_compute_meter(invoke_context, SyscallCategory::Hashing, cost)?;
 
         let (seeds, program_id) = translate_and_check_program_address_inputs(
             seeds_addr,
@@ -844,7 +849,7 @@ declare_builtin_function!(
         let cost = invoke_context
             .get_execution_cost()
             .create_program_address_units;
-        consume_compute_meter(invoke_context, cost)?;
+        consume_compute_meter(invoke_context, SyscallCategory::Hashing, cost)?;
 
         let (seeds, program_id) = translate_and_check_program_address_inputs(
             seeds_addr,
@@ -875,7 +880,7 @@ declare_builtin_function!(
                 }
             }
             bump_seed[0] = bump_seed[0].saturating_sub(1);
-            consume_compute_meter(invoke_context, cost)?;
+            consume_compute_meter(invoke_context, SyscallCategory::Hashing, cost)?;
         }
         Ok(1)
     }
@@ -894,7 +899,7 @@ declare_builtin_function!(
         memory_mapping: &mut MemoryMapping,
     ) -> Result<u64, Error> {
         let cost = invoke_context.get_execution_cost().secp256k1_recover_cost;
-        consume_compute_meter(invoke_context, cost)?;
+        consume_compute_meter(invoke_context, SyscallCategory::CurveOps, cost)?;
 
         translate_mut!(
             memory_mapping,
@@ -959,7 +964,7 @@ declare_builtin_function!(
                 let cost = invoke_context
                     .get_execution_cost()
                     .curve25519_edwards_validate_point_cost;
-                consume_compute_meter(invoke_context, cost)?;
+                consume_compute_meter(invoke_context, SyscallCategory::CurveOps, cost)?;
 
                 let point = translate_type::<edwards::PodEdwardsPoint>(
                     memory_mapping,
@@ -977,7 +982,7 @@ declare_builtin_function!(
                 let cost = invoke_context
                     .get_execution_cost()
                     .curve25519_ristretto_validate_point_cost;
-                consume_compute_meter(invoke_context, cost)?;
+                consume_compute_meter(invoke_context, SyscallCategory::CurveOps, cost)?;
 
                 let point = translate_type::<ristretto::PodRistrettoPoint>(
                     memory_mapping,
@@ -1028,7 +1033,7 @@ declare_builtin_function!(
                     let cost = invoke_context
                         .get_execution_cost()
                         .curve25519_edwards_add_cost;
-                    consume_compute_meter(invoke_context, cost)?;
+                    consume_compute_meter(invoke_context, SyscallCategory::CurveOps, cost)?;
 
                     let left_point = translate_type::<PodEdwardsPoint>(
                         memory_mapping,
@@ -1057,7 +1062,7 @@ declare_builtin_function!(
                     let cost = invoke_context
                         .get_execution_cost()
                         .curve25519_edwards_subtract_cost;
-                    consume_compute_meter(invoke_context, cost)?;
+                    consume_compute_meter(invoke_context, SyscallCategory::CurveOps, cost)?;
 
                     let left_point = translate_type::<PodEdwardsPoint>(
                         memory_mapping,
@@ -1086,7 +1091,7 @@ declare_builtin_function!(
                     let cost = invoke_context
                         .get_execution_cost()
                         .curve25519_edwards_multiply_cost;
-                    consume_compute_meter(invoke_context, cost)?;
+                    consume_compute_meter(invoke_context, SyscallCategory::CurveOps, cost)?;
 
                     let scalar = translate_type::<scalar::PodScalar>(
                         memory_mapping,
@@ -1125,7 +1130,7 @@ declare_builtin_function!(
                     let cost = invoke_context
                         .get_execution_cost()
                         .curve25519_ristretto_add_cost;
-                    consume_compute_meter(invoke_context, cost)?;
+                    consume_compute_meter(invoke_context, SyscallCategory::CurveOps, cost)?;
 
                     let left_point = translate_type::<PodRistrettoPoint>(
                         memory_mapping,
@@ -1154,7 +1159,7 @@ declare_builtin_function!(
                     let cost = invoke_context
                         .get_execution_cost()
                         .curve25519_ristretto_subtract_cost;
-                    consume_compute_meter(invoke_context, cost)?;
+                    consume_compute_meter(invoke_context, SyscallCategory::CurveOps, cost)?;
 
                     let left_point = translate_type::<PodRistrettoPoint>(
                         memory_mapping,
@@ -1185,7 +1190,7 @@ declare_builtin_function!(
                     let cost = invoke_context
                         .get_execution_cost()
                         .curve25519_ristretto_multiply_cost;
-                    consume_compute_meter(invoke_context, cost)?;
+                    consume_compute_meter(invoke_context, SyscallCategory::CurveOps, cost)?;
 
                     let scalar = translate_type::<scalar::PodScalar>(
                         memory_mapping,
@@ -1266,7 +1271,7 @@ declare_builtin_function!(
                             .curve25519_edwards_msm_incremental_cost
                             .saturating_mul(points_len.saturating_sub(1)),
                     );
-                consume_compute_meter(invoke_context, cost)?;
+                consume_compute_meter(invoke_context, SyscallCategory::CurveOps, cost)?;
 
                 let scalars = translate_slice::<scalar::PodScalar>(
                     memory_mapping,
@@ -1305,7 +1310,7 @@ declare_builtin_function!(
                             .curve25519_ristretto_msm_incremental_cost
                             .saturating_mul(points_len.saturating_sub(1)),
                     );
-                consume_compute_meter(invoke_context, cost)?;
+                consume_compute_meter(invoke_context, SyscallCategory::CurveOps, cost)?;
 
                 let scalars = translate_slice::<scalar::PodScalar>(
                     memory_mapping,
@@ -1365,7 +1370,7 @@ declare_builtin_function!(
             .checked_div(execution_cost.cpi_bytes_per_unit)
             .unwrap_or(u64::MAX)
             .saturating_add(execution_cost.syscall_base_cost);
-        consume_compute_meter(invoke_context, cost)?;
+        consume_compute_meter(invoke_context, SyscallCategory::Other, cost)?;
 
         if len > MAX_RETURN_DATA as u64 {
             return Err(SyscallError::ReturnDataTooLarge(len, MAX_RETURN_DATA as u64).into());
@@ -1409,7 +1414,11 @@ declare_builtin_function!(
     ) -> Result<u64, Error> {
         let execution_cost = invoke_context.get_execution_cost();
 
-        consume_compute_meter(invoke_context, execution_cost.syscall_base_cost)?;
+        consume_compute_meter(
+            invoke_context,
+            SyscallCategory::Other,
+            execution_cost.syscall_base_cost,
+        )?;
 
         let (program_id, return_data) = invoke_context.transaction_context.get_return_data();
         let length = length.min(return_data.len() as u64);
@@ -1418,7 +1427,7 @@ declare_builtin_function!(
                 .saturating_add(size_of::<Pubkey>() as u64)
                 .checked_div(execution_cost.cpi_bytes_per_unit)
                 .unwrap_or(u64::MAX);
-            consume_compute_meter(invoke_context, cost)?;
+            consume_compute_meter(invoke_context, SyscallCategory::Other, cost)?;
 
             translate_mut!(
                 memory_mapping,
@@ -1457,7 +1466,11 @@ declare_builtin_function!(
     ) -> Result<u64, Error> {
         let execution_cost = invoke_context.get_execution_cost();
 
-        consume_compute_meter(invoke_context, execution_cost.syscall_base_cost)?;
+        consume_compute_meter(
+            invoke_context,
+            SyscallCategory::Other,
+            execution_cost.syscall_base_cost,
+        )?;
 
         // Reverse iterate through the instruction trace,
         // ignoring anything except instructions on the same level
@@ -1545,7 +1558,11 @@ declare_builtin_function!(
     ) -> Result<u64, Error> {
         let execution_cost = invoke_context.get_execution_cost();
 
-        consume_compute_meter(invoke_context, execution_cost.syscall_base_cost)?;
+        consume_compute_meter(
+            invoke_context,
+            SyscallCategory::Other,
+            execution_cost.syscall_base_cost,
+        )?;
 
         Ok(invoke_context.get_stack_height() as u64)
     }
@@ -1595,7 +1612,7 @@ declare_builtin_function!(
             }
         };
 
-        consume_compute_meter(invoke_context, cost)?;
+        consume_compute_meter(invoke_context, SyscallCategory::CurveOps, cost)?;
 
         translate_mut!(
             memory_mapping,
@@ -1657,6 +1674,7 @@ declare_builtin_function!(
         // the compute units are calculated by the quadratic equation `0.5 input_len^2 + 190`
         consume_compute_meter(
             invoke_context,
+            SyscallCategory::Other,
             execution_cost.syscall_base_cost.saturating_add(
                 input_len
                     .saturating_mul(input_len)
@@ -1732,7 +1750,7 @@ declare_builtin_function!(
             );
             return Err(SyscallError::ArithmeticOverflow.into());
         };
-        consume_compute_meter(invoke_context, cost.to_owned())?;
+        consume_compute_meter(invoke_context, SyscallCategory::Hashing, cost.to_owned())?;
 
         translate_mut!(
             memory_mapping,
@@ -1785,7 +1803,11 @@ declare_builtin_function!(
         _memory_mapping: &mut MemoryMapping,
     ) -> Result<u64, Error> {
         let execution_cost = invoke_context.get_execution_cost();
-        consume_compute_meter(invoke_context, execution_cost.syscall_base_cost)?;
+        consume_compute_meter(
+            invoke_context,
+            SyscallCategory::Other,
+            execution_cost.syscall_base_cost,
+        )?;
 
         use solana_sbpf::vm::ContextObject;
         Ok(invoke_context.get_remaining())
@@ -1831,7 +1853,7 @@ declare_builtin_function!(
             }
         };
 
-        consume_compute_meter(invoke_context, cost)?;
+        consume_compute_meter(invoke_context, SyscallCategory::CurveOps, cost)?;
 
         translate_mut!(
             memory_mapping,
@@ -1939,7 +1961,7 @@ declare_builtin_function!(
             return Err(SyscallError::TooManySlices.into());
         }
 
-        consume_compute_meter(invoke_context, hash_base_cost)?;
+        consume_compute_meter(invoke_context, SyscallCategory::Hashing, hash_base_cost)?;
 
         translate_mut!(
             memory_mapping,
@@ -1964,7 +1986,7 @@ declare_builtin_function!(
                             .expect("div by non-zero literal"),
                     ),
                 );
-                consume_compute_meter(invoke_context, cost)?;
+                consume_compute_meter(invoke_context, SyscallCategory::Hashing, cost)?;
                 hasher.hash(bytes);
             }
         }
@@ -1996,7 +2018,7 @@ declare_builtin_function!(
             // syscall_base
             // ```
             let compute_units = compute_cost.syscall_base_cost;
-            consume_compute_meter(invoke_context, compute_units)?;
+            consume_compute_meter(invoke_context, SyscallCategory::Sysvars, compute_units)?;
             //
             // Control flow:
             //
@@ -2021,7 +2043,7 @@ declare_builtin_function!(
                         .unwrap_or(u64::MAX),
                 )
                 .saturating_add(compute_cost.mem_op_base_cost);
-            consume_compute_meter(invoke_context, compute_units)?;
+            consume_compute_meter(invoke_context, SyscallCategory::Sysvars, compute_units)?;
             //
             // Control flow:
             //
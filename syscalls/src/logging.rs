@@ -18,7 +18,7 @@ declare_builtin_function!(
             .get_execution_cost()
             .syscall_base_cost
             .max(len);
-        consume_compute_meter(invoke_context, cost)?;
+        consume_compute_meter(invoke_context, SyscallCategory::Logging, cost)?;
 
         translate_string_and_do(
             memory_mapping,
@@ -47,7 +47,7 @@ declare_builtin_function!(
         _memory_mapping: &mut MemoryMapping,
     ) -> Result<u64, Error> {
         let cost = invoke_context.get_execution_cost().log_64_units;
-        consume_compute_meter(invoke_context, cost)?;
+        consume_compute_meter(invoke_context, SyscallCategory::Logging, cost)?;
 
         stable_log::program_log(
             &invoke_context.get_log_collector(),
@@ -70,7 +70,7 @@ declare_builtin_function!(
         _memory_mapping: &mut MemoryMapping,
     ) -> Result<u64, Error> {
         let cost = invoke_context.get_execution_cost().syscall_base_cost;
-        consume_compute_meter(invoke_context, cost)?;
+        consume_compute_meter(invoke_context, SyscallCategory::Logging, cost)?;
 
         ic_logger_msg!(
             invoke_context.get_log_collector(),
@@ -94,7 +94,7 @@ declare_builtin_function!(
         memory_mapping: &mut MemoryMapping,
     ) -> Result<u64, Error> {
         let cost = invoke_context.get_execution_cost().log_pubkey_units;
-        consume_compute_meter(invoke_context, cost)?;
+        consume_compute_meter(invoke_context, SyscallCategory::Logging, cost)?;
 
         let pubkey = translate_type::<Pubkey>(
             memory_mapping,
@@ -120,7 +120,11 @@ declare_builtin_function!(
     ) -> Result<u64, Error> {
         let execution_cost = invoke_context.get_execution_cost();
 
-        consume_compute_meter(invoke_context, execution_cost.syscall_base_cost)?;
+        consume_compute_meter(
+            invoke_context,
+            SyscallCategory::Logging,
+            execution_cost.syscall_base_cost,
+        )?;
 
         let untranslated_fields = translate_slice::<VmSlice<u8>>(
             memory_mapping,
@@ -131,12 +135,14 @@ declare_builtin_function!(
 
         consume_compute_meter(
             invoke_context,
+            SyscallCategory::Logging,
             execution_cost
                 .syscall_base_cost
                 .saturating_mul(untranslated_fields.len() as u64),
         )?;
         consume_compute_meter(
             invoke_context,
+            SyscallCategory::Logging,
             untranslated_fields
                 .iter()
                 .fold(0, |total, e| total.saturating_add(e.len())),
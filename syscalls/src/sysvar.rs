@@ -12,6 +12,7 @@ fn get_sysvar<T: std::fmt::Debug + SysvarSerialize + Clone>(
 ) -> Result<u64, Error> {
     consume_compute_meter(
         invoke_context,
+        SyscallCategory::Sysvars,
         invoke_context
             .get_execution_cost()
             .sysvar_base_cost
@@ -206,6 +207,7 @@ declare_builtin_function!(
         let sysvar_buf_cost = length.checked_div(cpi_bytes_per_unit).unwrap_or(0);
         consume_compute_meter(
             invoke_context,
+            SyscallCategory::Sysvars,
             sysvar_base_cost
                 .saturating_add(sysvar_id_cost)
                 .saturating_add(std::cmp::max(sysvar_buf_cost, mem_op_base_cost)),
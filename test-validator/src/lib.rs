@@ -4,10 +4,12 @@ use {
     base64::{prelude::BASE64_STANDARD, Engine},
     crossbeam_channel::Receiver,
     log::*,
+    serde_derive::{Deserialize, Serialize},
     solana_account::{Account, AccountSharedData, ReadableAccount, WritableAccount},
     solana_accounts_db::{
-        accounts_db::AccountsDbConfig, accounts_index::AccountsIndexConfig,
-        hardened_unpack::MAX_GENESIS_ARCHIVE_UNPACKED_SIZE,
+        accounts_db::AccountsDbConfig,
+        accounts_index::AccountsIndexConfig,
+        hardened_unpack::{open_genesis_config, MAX_GENESIS_ARCHIVE_UNPACKED_SIZE},
         utils::create_accounts_run_and_snapshot_dirs,
     },
     solana_cli_output::CliAccount,
@@ -21,6 +23,7 @@ use {
     },
     solana_epoch_schedule::EpochSchedule,
     solana_fee_calculator::FeeRateGovernor,
+    solana_genesis_config::GenesisConfig,
     solana_geyser_plugin_manager::{
         geyser_plugin_manager::GeyserPluginManager, GeyserPluginManagerRequest,
     },
@@ -44,13 +47,21 @@ use {
     solana_rent::Rent,
     solana_rpc::{rpc::JsonRpcConfig, rpc_pubsub_service::PubSubConfig},
     solana_rpc_client::{nonblocking, rpc_client::RpcClient},
-    solana_rpc_client_api::request::MAX_MULTIPLE_ACCOUNTS,
+    solana_rpc_client_api::{request::MAX_MULTIPLE_ACCOUNTS, response::RpcKeyedAccount},
     solana_runtime::{
+        bank::Bank,
         bank_forks::BankForks,
         genesis_utils::{self, create_genesis_config_with_leader_ex_no_features},
         runtime_config::RuntimeConfig,
+        snapshot_archive_info::{
+            FullSnapshotArchiveInfo, IncrementalSnapshotArchiveInfo, SnapshotArchiveInfoGetter,
+        },
+        snapshot_bank_utils,
         snapshot_config::SnapshotConfig,
-        snapshot_utils::{SnapshotInterval, BANK_SNAPSHOTS_DIR},
+        snapshot_utils::{
+            get_highest_full_snapshot_archive_info, get_highest_incremental_snapshot_archive_info,
+            SnapshotInterval, BANK_SNAPSHOTS_DIR,
+        },
     },
     solana_sdk_ids::address_lookup_table,
     solana_signer::Signer,
@@ -59,7 +70,8 @@ use {
     solana_transaction::Transaction,
     solana_validator_exit::Exit,
     std::{
-        collections::{HashMap, HashSet},
+        cell::RefCell,
+        collections::{BTreeMap, HashMap, HashSet},
         ffi::OsStr,
         fmt::Display,
         fs::{self, remove_dir_all, File},
@@ -71,6 +83,7 @@ use {
         sync::{Arc, RwLock},
         time::Duration,
     },
+    tempfile::TempDir,
     tokio::time::sleep,
 };
 
@@ -215,6 +228,262 @@ fn try_transform_program_data(
     }
 }
 
+/// Source of the accounts cloned into the test environment
+///
+/// Cloning from an [`RpcClient`] fetches the accounts from a live cluster. The same accounts can
+/// be cloned without network access from a [`SnapshotAccounts`], or from an [`AccountsBundle`]
+/// recorded from the cluster beforehand.
+pub trait AccountsSource {
+    /// Fetch the accounts at `addresses`, in order, `None` for the ones that do not exist
+    fn fetch_accounts(&self, addresses: &[Pubkey]) -> Result<Vec<Option<Account>>, String>;
+}
+
+impl AccountsSource for RpcClient {
+    fn fetch_accounts(&self, addresses: &[Pubkey]) -> Result<Vec<Option<Account>>, String> {
+        self.get_multiple_accounts(addresses)
+            .map_err(|err| format!("Failed to fetch: {err}"))
+    }
+}
+
+/// Accounts of a bank loaded from snapshot archives
+pub struct SnapshotAccounts {
+    bank: Bank,
+    /// Accounts and bank snapshot directories of `bank`
+    _work_dir: TempDir,
+}
+
+impl SnapshotAccounts {
+    /// Load the bank of the full snapshot archive, and of the incremental snapshot archive on top
+    /// of it if provided. `genesis_config` must be the one of the cluster the snapshots are from.
+    pub fn from_archives(
+        genesis_config: &GenesisConfig,
+        full_snapshot_archive: &Path,
+        incremental_snapshot_archive: Option<&Path>,
+    ) -> Result<Self, String> {
+        let full_snapshot_archive_info = FullSnapshotArchiveInfo::new_from_path(
+            full_snapshot_archive.to_path_buf(),
+        )
+        .map_err(|err| {
+            format!(
+                "Invalid full snapshot archive {}: {err}",
+                full_snapshot_archive.display()
+            )
+        })?;
+        let incremental_snapshot_archive_info = incremental_snapshot_archive
+            .map(|path| {
+                IncrementalSnapshotArchiveInfo::new_from_path(path.to_path_buf()).map_err(|err| {
+                    format!(
+                        "Invalid incremental snapshot archive {}: {err}",
+                        path.display()
+                    )
+                })
+            })
+            .transpose()?;
+        Self::load(
+            genesis_config,
+            &full_snapshot_archive_info,
+            incremental_snapshot_archive_info.as_ref(),
+        )
+    }
+
+    /// Load the bank of the highest snapshot archives in `ledger_path`, with the genesis config of
+    /// the ledger
+    pub fn from_ledger(ledger_path: &Path) -> Result<Self, String> {
+        let genesis_config = open_genesis_config(ledger_path, MAX_GENESIS_ARCHIVE_UNPACKED_SIZE)
+            .map_err(|err| format!("Failed to open genesis config: {err}"))?;
+        let full_snapshot_archive_info = get_highest_full_snapshot_archive_info(ledger_path)
+            .ok_or_else(|| {
+                format!(
+                    "No full snapshot archive found in {}",
+                    ledger_path.display()
+                )
+            })?;
+        let incremental_snapshot_archive_info = get_highest_incremental_snapshot_archive_info(
+            ledger_path,
+            full_snapshot_archive_info.slot(),
+        );
+        Self::load(
+            &genesis_config,
+            &full_snapshot_archive_info,
+            incremental_snapshot_archive_info.as_ref(),
+        )
+    }
+
+    fn load(
+        genesis_config: &GenesisConfig,
+        full_snapshot_archive_info: &FullSnapshotArchiveInfo,
+        incremental_snapshot_archive_info: Option<&IncrementalSnapshotArchiveInfo>,
+    ) -> Result<Self, String> {
+        let work_dir = TempDir::new()
+            .map_err(|err| format!("Failed to create snapshot work directory: {err}"))?;
+        let (account_dir, _) =
+            create_accounts_run_and_snapshot_dirs(work_dir.path().join("accounts"))
+                .map_err(|err| format!("Failed to create accounts directory: {err}"))?;
+        let bank_snapshots_dir = work_dir.path().join(BANK_SNAPSHOTS_DIR);
+        fs::create_dir_all(&bank_snapshots_dir)
+            .map_err(|err| format!("Failed to create bank snapshots directory: {err}"))?;
+
+        info!(
+            "Loading accounts from snapshot archive {}...",
+            full_snapshot_archive_info.path().display()
+        );
+        let bank = snapshot_bank_utils::bank_from_snapshot_archives(
+            &[account_dir],
+            &bank_snapshots_dir,
+            full_snapshot_archive_info,
+            incremental_snapshot_archive_info,
+            genesis_config,
+            &RuntimeConfig::default(),
+            None,
+            None,
+            false,
+            false,
+            false,
+            AccountsDbConfig::default(),
+            None,
+            Arc::default(),
+        )
+        .map_err(|err| format!("Failed to load snapshot archives: {err}"))?;
+        Ok(Self {
+            bank,
+            _work_dir: work_dir,
+        })
+    }
+
+    /// Slot of the loaded bank
+    pub fn slot(&self) -> Slot {
+        self.bank.slot()
+    }
+}
+
+impl AccountsSource for SnapshotAccounts {
+    fn fetch_accounts(&self, addresses: &[Pubkey]) -> Result<Vec<Option<Account>>, String> {
+        Ok(addresses
+            .iter()
+            .map(|address| self.bank.get_account(address).map(Account::from))
+            .collect())
+    }
+}
+
+/// Serialized form of an [`AccountsBundle`]
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AccountsBundleFile {
+    accounts: Vec<RpcKeyedAccount>,
+    /// Addresses of the accounts which did not exist
+    missing: Vec<String>,
+}
+
+/// Accounts recorded from another source, which can be saved to a file and cloned from later
+///
+/// The bundle also records the accounts which did not exist, so that cloning from it behaves like
+/// cloning from the recorded source. Fetching an account which was not recorded fails.
+#[derive(Default)]
+pub struct AccountsBundle {
+    accounts: BTreeMap<Pubkey, Option<Account>>,
+}
+
+impl AccountsBundle {
+    /// Load a bundle saved with [`AccountsBundle::save`]
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let file =
+            File::open(path).map_err(|err| format!("Unable to open {}: {err}", path.display()))?;
+        let bundle_file: AccountsBundleFile = serde_json::from_reader(file)
+            .map_err(|err| format!("Unable to deserialize {}: {err}", path.display()))?;
+
+        let mut accounts = BTreeMap::new();
+        for keyed_account in bundle_file.accounts {
+            let address = Pubkey::from_str(&keyed_account.pubkey)
+                .map_err(|err| format!("Invalid address {}: {err}", keyed_account.pubkey))?;
+            let account = keyed_account
+                .account
+                .decode::<Account>()
+                .ok_or_else(|| format!("Unable to decode account {address}"))?;
+            accounts.insert(address, Some(account));
+        }
+        for address in bundle_file.missing {
+            let address = Pubkey::from_str(&address)
+                .map_err(|err| format!("Invalid address {address}: {err}"))?;
+            accounts.insert(address, None);
+        }
+        Ok(Self { accounts })
+    }
+
+    /// Save the bundle as JSON, the account data is base64 encoded
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let mut bundle_file = AccountsBundleFile {
+            accounts: vec![],
+            missing: vec![],
+        };
+        for (address, account) in &self.accounts {
+            match account {
+                Some(account) => bundle_file
+                    .accounts
+                    .push(CliAccount::new(address, account, false).keyed_account),
+                None => bundle_file.missing.push(address.to_string()),
+            }
+        }
+        let file = File::create(path)
+            .map_err(|err| format!("Unable to create {}: {err}", path.display()))?;
+        serde_json::to_writer_pretty(file, &bundle_file)
+            .map_err(|err| format!("Unable to write {}: {err}", path.display()))
+    }
+
+    /// Record the accounts fetched from `source` into a new bundle
+    pub fn record<S: AccountsSource + ?Sized>(source: &S) -> AccountsBundleRecorder<'_, S> {
+        AccountsBundleRecorder {
+            source,
+            bundle: RefCell::default(),
+        }
+    }
+
+    /// Number of recorded accounts, including the ones which did not exist
+    pub fn len(&self) -> usize {
+        self.accounts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty()
+    }
+}
+
+impl AccountsSource for AccountsBundle {
+    fn fetch_accounts(&self, addresses: &[Pubkey]) -> Result<Vec<Option<Account>>, String> {
+        addresses
+            .iter()
+            .map(|address| {
+                self.accounts
+                    .get(address)
+                    .cloned()
+                    .ok_or_else(|| format!("Account {address} is not in the bundle"))
+            })
+            .collect()
+    }
+}
+
+/// Source recording the accounts fetched from another source, see [`AccountsBundle::record`]
+pub struct AccountsBundleRecorder<'a, S: ?Sized> {
+    source: &'a S,
+    bundle: RefCell<AccountsBundle>,
+}
+
+impl<S: ?Sized> AccountsBundleRecorder<'_, S> {
+    pub fn into_bundle(self) -> AccountsBundle {
+        self.bundle.into_inner()
+    }
+}
+
+impl<S: AccountsSource + ?Sized> AccountsSource for AccountsBundleRecorder<'_, S> {
+    fn fetch_accounts(&self, addresses: &[Pubkey]) -> Result<Vec<Option<Account>>, String> {
+        let accounts = self.source.fetch_accounts(addresses)?;
+        self.bundle
+            .borrow_mut()
+            .accounts
+            .extend(addresses.iter().copied().zip(accounts.iter().cloned()));
+        Ok(accounts)
+    }
+}
+
 impl TestValidatorGenesis {
     /// Adds features to deactivate to a set, eliminating redundancies
     /// during `initialize_ledger`, if member of the set is not a Feature
@@ -334,23 +603,22 @@ impl TestValidatorGenesis {
         self
     }
 
-    fn clone_accounts_and_transform<T, F>(
+    fn clone_accounts_and_transform<T, S, F>(
         &mut self,
         addresses: T,
-        rpc_client: &RpcClient,
+        source: &S,
         skip_missing: bool,
         transform: F,
     ) -> Result<&mut Self, String>
     where
         T: IntoIterator<Item = Pubkey>,
+        S: AccountsSource + ?Sized,
         F: Fn(&Pubkey, Account) -> Result<AccountSharedData, String>,
     {
         let addresses: Vec<Pubkey> = addresses.into_iter().collect();
         for chunk in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
-            info!("Fetching {chunk:?} over RPC...");
-            let responses = rpc_client
-                .get_multiple_accounts(chunk)
-                .map_err(|err| format!("Failed to fetch: {err}"))?;
+            info!("Fetching {chunk:?}...");
+            let responses = source.fetch_accounts(chunk)?;
             for (address, res) in chunk.iter().zip(responses) {
                 if let Some(account) = res {
                     self.add_account(*address, transform(address, account)?);
@@ -364,45 +632,40 @@ impl TestValidatorGenesis {
         Ok(self)
     }
 
-    pub fn clone_accounts<T>(
+    pub fn clone_accounts<T, S>(
         &mut self,
         addresses: T,
-        rpc_client: &RpcClient,
+        source: &S,
         skip_missing: bool,
     ) -> Result<&mut Self, String>
     where
         T: IntoIterator<Item = Pubkey>,
+        S: AccountsSource + ?Sized,
     {
-        self.clone_accounts_and_transform(
-            addresses,
-            rpc_client,
-            skip_missing,
-            |address, account| {
-                let mut account_shared_data = AccountSharedData::from(account);
-                // ignore the error
-                try_transform_program_data(address, &mut account_shared_data).ok();
-                Ok(account_shared_data)
-            },
-        )
+        self.clone_accounts_and_transform(addresses, source, skip_missing, |address, account| {
+            let mut account_shared_data = AccountSharedData::from(account);
+            // ignore the error
+            try_transform_program_data(address, &mut account_shared_data).ok();
+            Ok(account_shared_data)
+        })
     }
 
-    pub fn deep_clone_address_lookup_table_accounts<T>(
+    pub fn deep_clone_address_lookup_table_accounts<T, S>(
         &mut self,
         addresses: T,
-        rpc_client: &RpcClient,
+        source: &S,
     ) -> Result<&mut Self, String>
     where
         T: IntoIterator<Item = Pubkey>,
+        S: AccountsSource + ?Sized,
     {
         const LOOKUP_TABLE_META_SIZE: usize = 56;
         let addresses: Vec<Pubkey> = addresses.into_iter().collect();
         let mut alt_entries: Vec<Pubkey> = Vec::new();
 
         for chunk in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
-            info!("Fetching {chunk:?} over RPC...");
-            let responses = rpc_client
-                .get_multiple_accounts(chunk)
-                .map_err(|err| format!("Failed to fetch: {err}"))?;
+            info!("Fetching {chunk:?}...");
+            let responses = source.fetch_accounts(chunk)?;
             for (address, res) in chunk.iter().zip(responses) {
                 if let Some(account) = res {
                     if address_lookup_table::check_id(account.owner()) {
@@ -432,40 +695,37 @@ impl TestValidatorGenesis {
             }
         }
 
-        self.clone_accounts(alt_entries, rpc_client, true)
+        self.clone_accounts(alt_entries, source, true)
     }
 
-    pub fn clone_programdata_accounts<T>(
+    pub fn clone_programdata_accounts<T, S>(
         &mut self,
         addresses: T,
-        rpc_client: &RpcClient,
+        source: &S,
         skip_missing: bool,
     ) -> Result<&mut Self, String>
     where
         T: IntoIterator<Item = Pubkey>,
+        S: AccountsSource + ?Sized,
     {
-        self.clone_accounts_and_transform(
-            addresses,
-            rpc_client,
-            skip_missing,
-            |address, account| {
-                let mut account_shared_data = AccountSharedData::from(account);
-                try_transform_program_data(address, &mut account_shared_data)?;
-                Ok(account_shared_data)
-            },
-        )
+        self.clone_accounts_and_transform(addresses, source, skip_missing, |address, account| {
+            let mut account_shared_data = AccountSharedData::from(account);
+            try_transform_program_data(address, &mut account_shared_data)?;
+            Ok(account_shared_data)
+        })
     }
 
-    pub fn clone_upgradeable_programs<T>(
+    pub fn clone_upgradeable_programs<T, S>(
         &mut self,
         addresses: T,
-        rpc_client: &RpcClient,
+        source: &S,
     ) -> Result<&mut Self, String>
     where
         T: IntoIterator<Item = Pubkey>,
+        S: AccountsSource + ?Sized,
     {
         let addresses: Vec<Pubkey> = addresses.into_iter().collect();
-        self.clone_accounts(addresses.clone(), rpc_client, false)?;
+        self.clone_accounts(addresses.clone(), source, false)?;
 
         let mut programdata_addresses: HashSet<Pubkey> = HashSet::new();
         for address in addresses {
@@ -483,21 +743,23 @@ impl TestValidatorGenesis {
             }
         }
 
-        self.clone_programdata_accounts(programdata_addresses, rpc_client, false)?;
+        self.clone_programdata_accounts(programdata_addresses, source, false)?;
 
         Ok(self)
     }
 
-    pub fn clone_feature_set(&mut self, rpc_client: &RpcClient) -> Result<&mut Self, String> {
+    pub fn clone_feature_set<S>(&mut self, source: &S) -> Result<&mut Self, String>
+    where
+        S: AccountsSource + ?Sized,
+    {
         for feature_ids in FEATURE_NAMES
             .keys()
             .cloned()
             .collect::<Vec<Pubkey>>()
             .chunks(MAX_MULTIPLE_ACCOUNTS)
         {
-            rpc_client
-                .get_multiple_accounts(feature_ids)
-                .map_err(|err| format!("Failed to fetch: {err}"))?
+            source
+                .fetch_accounts(feature_ids)?
                 .into_iter()
                 .zip(feature_ids)
                 .for_each(|(maybe_account, feature_id)| {
@@ -1537,4 +1799,135 @@ mod test {
         assert_eq!(account.owner, solana_sdk_ids::bpf_loader_upgradeable::id());
         assert!(account.executable);
     }
+
+    #[test]
+    fn test_clone_from_bundle() {
+        let address = Pubkey::new_unique();
+        let missing_address = Pubkey::new_unique();
+        let account =
+            AccountSharedData::new_data(1_000_000, &[1u8; 10], &Pubkey::new_unique()).unwrap();
+        let (test_validator, _payer) = TestValidatorGenesis::default()
+            .add_account(address, account.clone())
+            .start();
+        let rpc_client = test_validator.get_rpc_client();
+
+        let recorder = AccountsBundle::record(&rpc_client);
+        let mut genesis = TestValidatorGenesis::default();
+        genesis
+            .clone_accounts([address, missing_address], &recorder, true)
+            .unwrap();
+        let bundle = recorder.into_bundle();
+        assert_eq!(bundle.len(), 2);
+
+        let bundle_dir = TempDir::new().unwrap();
+        let bundle_path = bundle_dir.path().join("bundle.json");
+        bundle.save(&bundle_path).unwrap();
+        drop(test_validator);
+
+        let bundle = AccountsBundle::load(&bundle_path).unwrap();
+        let mut genesis = TestValidatorGenesis::default();
+        genesis
+            .clone_accounts([address, missing_address], &bundle, true)
+            .unwrap();
+        let cloned_account = genesis.accounts.get(&address).unwrap();
+        assert_eq!(cloned_account.lamports(), account.lamports());
+        assert_eq!(cloned_account.data(), account.data());
+        assert_eq!(cloned_account.owner(), account.owner());
+        assert!(!genesis.accounts.contains_key(&missing_address));
+        assert!(genesis
+            .clone_accounts([missing_address], &bundle, false)
+            .is_err());
+        assert!(genesis
+            .clone_accounts([Pubkey::new_unique()], &bundle, true)
+            .is_err());
+    }
+
+    #[test]
+    fn test_clone_from_snapshot() {
+        let address = Pubkey::new_unique();
+        let missing_address = Pubkey::new_unique();
+        let program_id = Pubkey::new_unique();
+        let (programdata_address, _) = Pubkey::find_program_address(
+            &[program_id.as_ref()],
+            &solana_sdk_ids::bpf_loader_upgradeable::id(),
+        );
+        let account =
+            AccountSharedData::new_data(1_000_000, &[1u8; 10], &Pubkey::new_unique()).unwrap();
+        let program_account = AccountSharedData::new_data(
+            1_000_000,
+            &UpgradeableLoaderState::Program {
+                programdata_address,
+            },
+            &solana_sdk_ids::bpf_loader_upgradeable::id(),
+        )
+        .unwrap();
+        let mut programdata = bincode::serialize(&UpgradeableLoaderState::ProgramData {
+            slot: 42,
+            upgrade_authority_address: Some(Pubkey::new_unique()),
+        })
+        .unwrap();
+        programdata.extend_from_slice(&[1u8; 10]);
+        let programdata_account = AccountSharedData::from(Account {
+            lamports: 1_000_000,
+            data: programdata,
+            owner: solana_sdk_ids::bpf_loader_upgradeable::id(),
+            executable: false,
+            rent_epoch: 0,
+        });
+
+        // Write the genesis and a snapshot archive of a bank with the accounts to a ledger
+        let mut genesis_config = genesis_utils::create_genesis_config(1_000_000_000).genesis_config;
+        genesis_config.add_account(address, account.clone());
+        genesis_config.add_account(program_id, program_account.clone());
+        genesis_config.add_account(programdata_address, programdata_account.clone());
+        let inactive_feature = agave_feature_set::deprecate_rewards_sysvar::id();
+        let active_feature = agave_feature_set::disable_fees_sysvar::id();
+        assert!(genesis_config.accounts.remove(&inactive_feature).is_some());
+        let ledger_dir = TempDir::new().unwrap();
+        genesis_config.write(ledger_dir.path()).unwrap();
+        let (bank, _bank_forks) = Bank::new_with_bank_forks_for_tests(&genesis_config);
+        bank.fill_bank_with_ticks_for_tests();
+        let snapshot_config = SnapshotConfig::default();
+        let bank_snapshots_dir = TempDir::new().unwrap();
+        snapshot_bank_utils::bank_to_full_snapshot_archive(
+            &bank_snapshots_dir,
+            &bank,
+            Some(snapshot_config.snapshot_version),
+            ledger_dir.path(),
+            ledger_dir.path(),
+            snapshot_config.archive_format,
+        )
+        .unwrap();
+        drop(bank);
+
+        let snapshot_accounts = SnapshotAccounts::from_ledger(ledger_dir.path()).unwrap();
+        assert_eq!(snapshot_accounts.slot(), 0);
+        let mut genesis = TestValidatorGenesis::default();
+        genesis
+            .clone_accounts([address, missing_address], &snapshot_accounts, true)
+            .unwrap()
+            .clone_upgradeable_programs([program_id], &snapshot_accounts)
+            .unwrap()
+            .clone_feature_set(&snapshot_accounts)
+            .unwrap();
+
+        assert_eq!(genesis.accounts.get(&address), Some(&account));
+        assert!(!genesis.accounts.contains_key(&missing_address));
+        assert_eq!(genesis.accounts.get(&program_id), Some(&program_account));
+        let cloned_programdata = genesis.accounts.get(&programdata_address).unwrap();
+        assert_eq!(
+            cloned_programdata.data()[UpgradeableLoaderState::size_of_programdata_metadata()..],
+            programdata_account.data()[UpgradeableLoaderState::size_of_programdata_metadata()..]
+        );
+        // The deployment slot of cloned programs is reset
+        assert!(matches!(
+            cloned_programdata.deserialize_data(),
+            Ok(UpgradeableLoaderState::ProgramData { slot: 0, .. })
+        ));
+        assert!(genesis.deactivate_feature_set.contains(&inactive_feature));
+        assert!(!genesis.deactivate_feature_set.contains(&active_feature));
+        assert!(genesis
+            .clone_accounts([missing_address], &snapshot_accounts, false)
+            .is_err());
+    }
 }
@@ -3,7 +3,8 @@
 //! Tpu-client-next supports three features:
 //!
 //! - **`metrics`**: Enables implementation of the method `report_to_influxdb` for
-//!   [`SendTransactionStats`] structure.
+//!   [`SendTransactionStats`] and
+//!   [`TransactionTrackerStats`](transaction_tracker::TransactionTrackerStats) structures.
 //! - **`log`**: Enables logging using `log` crate. It is enabled by default.
 //! - **`tracing`**: Enables logging using `tracing` crate instead of `log`. This feature is
 //!   mutually exclusive with `log`.
@@ -15,11 +16,13 @@ pub mod workers_cache;
 pub use crate::{
     connection_workers_scheduler::{ConnectionWorkersScheduler, ConnectionWorkersSchedulerError},
     send_transaction_stats::SendTransactionStats,
+    transaction_tracker::TransactionTracker,
 };
 pub(crate) mod quic_networking;
 pub(crate) use crate::quic_networking::QuicError;
 pub mod leader_updater;
 pub mod transaction_batch;
+pub mod transaction_tracker;
 
 #[cfg(feature = "metrics")]
 pub mod metrics;
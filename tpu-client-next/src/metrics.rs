@@ -1,8 +1,9 @@
 //! If `metrics` feature is activated, this module provides `report_to_influxdb`
-//! method for [`SendTransactionStats`] which periodically reports transaction
-//! sending statistics to InfluxDB.
+//! method for [`SendTransactionStats`] and [`TransactionTrackerStats`] which
+//! periodically reports transaction sending and tracking statistics to
+//! InfluxDB.
 use {
-    crate::SendTransactionStats,
+    crate::{transaction_tracker::TransactionTrackerStats, SendTransactionStats},
     solana_metrics::datapoint_info,
     std::{sync::Arc, time::Duration},
     tokio::{select, time::interval},
@@ -52,3 +53,34 @@ impl SendTransactionStats {
         }
     }
 }
+
+impl TransactionTrackerStats {
+    /// Report the statistics to influxdb.
+    pub async fn report_to_influxdb(
+        self: Arc<Self>,
+        name: &'static str,
+        reporting_interval: Duration,
+        cancel: CancellationToken,
+    ) {
+        let mut interval = interval(reporting_interval);
+        loop {
+            select! {
+                _ = interval.tick() => {
+                    let view = self.read_and_reset();
+                    datapoint_info!(
+                        name,
+                        ("received", view.received, i64),
+                        ("untracked", view.untracked, i64),
+                        ("landed", view.landed, i64),
+                        ("landed_with_error", view.landed_with_error, i64),
+                        ("expired", view.expired, i64),
+                        ("rebroadcasts_exhausted", view.rebroadcasts_exhausted, i64),
+                        ("rebroadcast", view.rebroadcast, i64),
+                        ("status_source_error", view.status_source_error, i64),
+                    );
+                }
+                _ = cancel.cancelled() => break,
+            }
+        }
+    }
+}
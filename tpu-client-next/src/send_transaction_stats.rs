@@ -191,6 +191,7 @@ macro_rules! define_non_atomic_struct_for {
         }
     };
 }
+pub(crate) use define_non_atomic_struct_for;
 
 // Define the non-atomic struct and the `to_non_atomic` conversion method
 define_non_atomic_struct_for!(
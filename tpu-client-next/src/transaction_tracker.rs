@@ -0,0 +1,388 @@
+//! This module defines [`TransactionTracker`] which tracks the transactions
+//! sent to the upcoming leaders until they land or their blockhash expires,
+//! and rebroadcasts them in the meantime.
+//!
+//! The tracker is placed in front of the
+//! [`ConnectionWorkersScheduler`](crate::ConnectionWorkersScheduler): it
+//! forwards the transactions it receives to the scheduler in batches and
+//! periodically checks their statuses with a [`TransactionStatusSource`],
+//! sending the ones which did not land again according to the
+//! [`RebroadcastPolicy`].
+
+pub mod status_source;
+
+pub use status_source::{
+    LandedStatus, NotifiedStatusSource, StatusNotifier, StatusSourceError, TransactionStatusSource,
+    MAX_NOTIFIED_STATUS_AGE,
+};
+use {
+    crate::{
+        logging::{debug, warn},
+        send_transaction_stats::define_non_atomic_struct_for,
+        transaction_batch::TransactionBatch,
+    },
+    solana_signature::Signature,
+    std::{
+        collections::HashMap,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc,
+        },
+        time::Duration,
+    },
+    thiserror::Error,
+    tokio::{
+        sync::mpsc,
+        time::{interval, Instant, MissedTickBehavior},
+    },
+    tokio_util::{bytes::Bytes, sync::CancellationToken},
+};
+
+/// Default interval between the status checks, the same as the default retry
+/// rate of the send transaction service.
+pub const DEFAULT_REBROADCAST_INTERVAL: Duration = Duration::from_secs(2);
+
+pub type TrackedTransactionReceiver = mpsc::Receiver<TrackedTransaction>;
+
+/// Channel the outcomes of the tracked transactions are reported to.
+pub type TransactionOutcomeSender = mpsc::UnboundedSender<(Signature, TransactionOutcome)>;
+
+/// Transaction to send to the leaders and track until it lands.
+#[derive(Clone, Debug)]
+pub struct TrackedTransaction {
+    pub signature: Signature,
+    pub wire_transaction: Bytes,
+    /// The last block height at which the blockhash of the transaction is
+    /// valid. Durable nonce transactions use `u64::MAX`, they are only bounded
+    /// by [`RebroadcastPolicy::max_rebroadcasts`].
+    pub last_valid_block_height: u64,
+}
+
+impl TrackedTransaction {
+    pub fn new<T>(signature: Signature, wire_transaction: T, last_valid_block_height: u64) -> Self
+    where
+        T: AsRef<[u8]> + Send + 'static,
+    {
+        Self {
+            signature,
+            wire_transaction: Bytes::from_owner(wire_transaction),
+            last_valid_block_height,
+        }
+    }
+}
+
+/// Final status of a tracked transaction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TransactionOutcome {
+    /// The transaction landed, its execution may have failed.
+    Landed(LandedStatus),
+    /// The blockhash of the transaction expired before it landed.
+    Expired,
+    /// The transaction did not land after the maximum number of rebroadcasts.
+    RebroadcastsExhausted,
+}
+
+/// [`RebroadcastPolicy`] specifies when the transactions which did not land
+/// are sent again.
+#[derive(Debug, Clone)]
+pub struct RebroadcastPolicy {
+    /// The interval between the status checks of the tracked transactions.
+    /// The transactions sent at least this long ago which did not land are
+    /// rebroadcast after every check.
+    pub rebroadcast_interval: Duration,
+
+    /// The maximum number of rebroadcasts of a transaction, or `None` to
+    /// rebroadcast it until its blockhash expires.
+    pub max_rebroadcasts: Option<usize>,
+}
+
+impl Default for RebroadcastPolicy {
+    fn default() -> Self {
+        Self {
+            rebroadcast_interval: DEFAULT_REBROADCAST_INTERVAL,
+            max_rebroadcasts: None,
+        }
+    }
+}
+
+/// Configuration for the [`TransactionTracker`].
+pub struct TransactionTrackerConfig {
+    /// Configures when the transactions are rebroadcast.
+    pub rebroadcast_policy: RebroadcastPolicy,
+
+    /// The maximum number of transactions tracked at once. The transactions
+    /// received while this number is reached are sent once without being
+    /// tracked.
+    pub max_tracked_transactions: usize,
+
+    /// The maximum number of transactions per batch sent to the scheduler.
+    pub batch_size: usize,
+
+    /// Optional channel the outcomes of the tracked transactions are reported
+    /// to.
+    pub outcome_sender: Option<TransactionOutcomeSender>,
+}
+
+/// Errors that arise from running [`TransactionTracker`].
+#[derive(Debug, Error, PartialEq)]
+pub enum TransactionTrackerError {
+    #[error("Scheduler transaction receiver unexpectedly dropped.")]
+    SchedulerReceiverDropped,
+}
+
+/// [`TransactionTrackerStats`] aggregates counters related to tracking
+/// transactions.
+#[derive(Debug, Default)]
+pub struct TransactionTrackerStats {
+    pub received: AtomicU64,
+    pub untracked: AtomicU64,
+    pub landed: AtomicU64,
+    pub landed_with_error: AtomicU64,
+    pub expired: AtomicU64,
+    pub rebroadcasts_exhausted: AtomicU64,
+    pub rebroadcast: AtomicU64,
+    pub status_source_error: AtomicU64,
+}
+
+define_non_atomic_struct_for!(
+    TransactionTrackerStatsNonAtomic,
+    TransactionTrackerStats,
+    {
+        received,
+        untracked,
+        landed,
+        landed_with_error,
+        expired,
+        rebroadcasts_exhausted,
+        rebroadcast,
+        status_source_error
+    }
+);
+
+struct PendingTransaction {
+    wire_transaction: Bytes,
+    last_valid_block_height: u64,
+    last_sent: Instant,
+    rebroadcasts: usize,
+}
+
+/// The [`TransactionTracker`] sends the transactions from the provided
+/// receiver channel to the [`ConnectionWorkersScheduler`](crate::ConnectionWorkersScheduler)
+/// and keeps sending them until they land or their blockhash expires. It
+/// obtains the statuses of the transactions from the implementation of the
+/// [`TransactionStatusSource`] trait.
+pub struct TransactionTracker {
+    status_source: Box<dyn TransactionStatusSource>,
+    transaction_receiver: TrackedTransactionReceiver,
+    scheduler_sender: mpsc::Sender<TransactionBatch>,
+    cancel: CancellationToken,
+    stats: Arc<TransactionTrackerStats>,
+}
+
+impl TransactionTracker {
+    /// Creates the tracker, `scheduler_sender` is the sender of the channel
+    /// received by the scheduler.
+    pub fn new(
+        status_source: Box<dyn TransactionStatusSource>,
+        transaction_receiver: TrackedTransactionReceiver,
+        scheduler_sender: mpsc::Sender<TransactionBatch>,
+        cancel: CancellationToken,
+    ) -> Self {
+        let stats = Arc::new(TransactionTrackerStats::default());
+        Self {
+            status_source,
+            transaction_receiver,
+            scheduler_sender,
+            cancel,
+            stats,
+        }
+    }
+
+    /// Retrieves a reference to the statistics of the tracker
+    pub fn get_stats(&self) -> Arc<TransactionTrackerStats> {
+        self.stats.clone()
+    }
+
+    /// Starts the tracker.
+    ///
+    /// Once `transaction_receiver` is closed, the tracker keeps tracking the
+    /// pending transactions and stops when all of them are resolved. The
+    /// scheduler channel is closed when the tracker stops. Returns
+    /// [`TransactionTrackerStats`] or an error.
+    pub async fn run(
+        self,
+        TransactionTrackerConfig {
+            rebroadcast_policy,
+            max_tracked_transactions,
+            batch_size,
+            outcome_sender,
+        }: TransactionTrackerConfig,
+    ) -> Result<Arc<TransactionTrackerStats>, TransactionTrackerError> {
+        let TransactionTracker {
+            status_source,
+            mut transaction_receiver,
+            scheduler_sender,
+            cancel,
+            stats,
+        } = self;
+        let mut pending = HashMap::<Signature, PendingTransaction>::new();
+        let mut check_interval = interval(rebroadcast_policy.rebroadcast_interval);
+        check_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut receiver_is_active = true;
+        let batch_size = batch_size.max(1);
+
+        loop {
+            tokio::select! {
+                recv_res = transaction_receiver.recv(), if receiver_is_active => {
+                    let Some(transaction) = recv_res else {
+                        debug!("End of `transaction_receiver`: tracking the pending transactions.");
+                        receiver_is_active = false;
+                        if pending.is_empty() {
+                            break;
+                        }
+                        continue;
+                    };
+                    let mut transactions = vec![transaction];
+                    while transactions.len() < batch_size {
+                        let Ok(transaction) = transaction_receiver.try_recv() else {
+                            break;
+                        };
+                        transactions.push(transaction);
+                    }
+                    stats
+                        .received
+                        .fetch_add(transactions.len() as u64, Ordering::Relaxed);
+
+                    let now = Instant::now();
+                    let mut wire_transactions = Vec::with_capacity(transactions.len());
+                    for transaction in transactions {
+                        if pending.len() < max_tracked_transactions {
+                            pending.insert(
+                                transaction.signature,
+                                PendingTransaction {
+                                    wire_transaction: transaction.wire_transaction.clone(),
+                                    last_valid_block_height: transaction.last_valid_block_height,
+                                    last_sent: now,
+                                    rebroadcasts: 0,
+                                },
+                            );
+                        } else {
+                            stats.untracked.fetch_add(1, Ordering::Relaxed);
+                        }
+                        wire_transactions.push(transaction.wire_transaction);
+                    }
+                    send_to_scheduler(&scheduler_sender, wire_transactions).await?;
+                },
+                _ = check_interval.tick() => {
+                    let rebroadcast = check_pending(
+                        status_source.as_ref(),
+                        &mut pending,
+                        &rebroadcast_policy,
+                        outcome_sender.as_ref(),
+                        &stats,
+                    )
+                    .await;
+                    for batch in rebroadcast.chunks(batch_size) {
+                        send_to_scheduler(&scheduler_sender, batch.to_vec()).await?;
+                    }
+                    if !receiver_is_active && pending.is_empty() {
+                        debug!("All the pending transactions are resolved: shutting down.");
+                        break;
+                    }
+                },
+                () = cancel.cancelled() => {
+                    debug!("Cancelled: Shutting down");
+                    break;
+                }
+            }
+        }
+        Ok(stats)
+    }
+}
+
+async fn send_to_scheduler(
+    scheduler_sender: &mpsc::Sender<TransactionBatch>,
+    wire_transactions: Vec<Bytes>,
+) -> Result<(), TransactionTrackerError> {
+    if wire_transactions.is_empty() {
+        return Ok(());
+    }
+    scheduler_sender
+        .send(TransactionBatch::new(wire_transactions))
+        .await
+        .map_err(|_| TransactionTrackerError::SchedulerReceiverDropped)
+}
+
+/// Resolves the pending transactions which landed or expired, and returns the
+/// ones to rebroadcast.
+async fn check_pending(
+    status_source: &dyn TransactionStatusSource,
+    pending: &mut HashMap<Signature, PendingTransaction>,
+    rebroadcast_policy: &RebroadcastPolicy,
+    outcome_sender: Option<&TransactionOutcomeSender>,
+    stats: &TransactionTrackerStats,
+) -> Vec<Bytes> {
+    if pending.is_empty() {
+        return vec![];
+    }
+    // Fetch the block height first, so that a transaction without a status
+    // did not land up to that block height. Otherwise, a transaction landing
+    // between the two requests could be reported as expired.
+    let block_height = match status_source.get_block_height().await {
+        Ok(block_height) => block_height,
+        Err(err) => {
+            warn!("Failed to get the block height: {err}");
+            stats.status_source_error.fetch_add(1, Ordering::Relaxed);
+            return vec![];
+        }
+    };
+    let signatures: Vec<Signature> = pending.keys().copied().collect();
+    let statuses = match status_source.get_signature_statuses(&signatures).await {
+        Ok(statuses) => statuses,
+        Err(err) => {
+            warn!("Failed to get the statuses of the tracked transactions: {err}");
+            stats.status_source_error.fetch_add(1, Ordering::Relaxed);
+            return vec![];
+        }
+    };
+
+    let now = Instant::now();
+    let mut rebroadcast = vec![];
+    for (signature, status) in signatures.into_iter().zip(statuses) {
+        let Some(transaction) = pending.get_mut(&signature) else {
+            continue;
+        };
+        let outcome = if let Some(status) = status {
+            stats.landed.fetch_add(1, Ordering::Relaxed);
+            if status.err.is_some() {
+                stats.landed_with_error.fetch_add(1, Ordering::Relaxed);
+            }
+            TransactionOutcome::Landed(status)
+        } else if block_height > transaction.last_valid_block_height {
+            stats.expired.fetch_add(1, Ordering::Relaxed);
+            TransactionOutcome::Expired
+        } else if now.duration_since(transaction.last_sent)
+            < rebroadcast_policy.rebroadcast_interval
+        {
+            continue;
+        } else if rebroadcast_policy
+            .max_rebroadcasts
+            .is_some_and(|max_rebroadcasts| transaction.rebroadcasts >= max_rebroadcasts)
+        {
+            stats.rebroadcasts_exhausted.fetch_add(1, Ordering::Relaxed);
+            TransactionOutcome::RebroadcastsExhausted
+        } else {
+            transaction.rebroadcasts = transaction.rebroadcasts.saturating_add(1);
+            transaction.last_sent = now;
+            stats.rebroadcast.fetch_add(1, Ordering::Relaxed);
+            rebroadcast.push(transaction.wire_transaction.clone());
+            continue;
+        };
+        pending.remove(&signature);
+        if let Some(outcome_sender) = outcome_sender {
+            // The receiver may not be interested in the outcomes anymore
+            let _ = outcome_sender.send((signature, outcome));
+        }
+    }
+    rebroadcast
+}
@@ -0,0 +1,210 @@
+//! This module provides [`TransactionStatusSource`] trait, which is used by
+//! [`TransactionTracker`](super::TransactionTracker) to find out whether the
+//! transactions it tracks landed, along with its implementations for
+//! [`RpcClient`] and for notifications ([`NotifiedStatusSource`]).
+
+use {
+    async_trait::async_trait,
+    solana_clock::{Slot, MAX_PROCESSING_AGE},
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
+    solana_signature::Signature,
+    solana_transaction_error::TransactionError,
+    std::{
+        collections::HashMap,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc, Mutex,
+        },
+    },
+    thiserror::Error,
+};
+
+/// Maximum number of signatures per `getSignatureStatuses` request.
+const MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS: usize = 256;
+
+/// Number of block heights a notified status is kept for when it is not
+/// queried. A tracked transaction is checked at every rebroadcast interval,
+/// far more often than that.
+pub const MAX_NOTIFIED_STATUS_AGE: u64 = MAX_PROCESSING_AGE as u64;
+
+/// Status of a transaction which landed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LandedStatus {
+    /// The slot the transaction was processed in.
+    pub slot: Slot,
+    /// The error of the transaction, if its execution failed.
+    pub err: Option<TransactionError>,
+}
+
+/// Error type for [`TransactionStatusSource`].
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("Transaction status source error: {0}")]
+pub struct StatusSourceError(pub String);
+
+/// [`TransactionStatusSource`] trait abstracts out how the
+/// [`TransactionTracker`](super::TransactionTracker) learns about the
+/// transactions which landed and about the current block height.
+#[async_trait]
+pub trait TransactionStatusSource: Send + Sync {
+    /// Returns the statuses of the transactions with the given `signatures`,
+    /// in the same order, or `None` for the transactions which did not land.
+    async fn get_signature_statuses(
+        &self,
+        signatures: &[Signature],
+    ) -> Result<Vec<Option<LandedStatus>>, StatusSourceError>;
+
+    /// Returns the current block height, which is compared with the last
+    /// valid block height of the transactions to detect expired blockhashes.
+    async fn get_block_height(&self) -> Result<u64, StatusSourceError>;
+}
+
+#[async_trait]
+impl<T: TransactionStatusSource + ?Sized> TransactionStatusSource for Arc<T> {
+    async fn get_signature_statuses(
+        &self,
+        signatures: &[Signature],
+    ) -> Result<Vec<Option<LandedStatus>>, StatusSourceError> {
+        self.as_ref().get_signature_statuses(signatures).await
+    }
+
+    async fn get_block_height(&self) -> Result<u64, StatusSourceError> {
+        self.as_ref().get_block_height().await
+    }
+}
+
+/// Queries the statuses with `getSignatureStatuses` and the block height with
+/// `getBlockHeight`. A transaction is considered landed once it reaches the
+/// commitment of the client.
+#[async_trait]
+impl TransactionStatusSource for RpcClient {
+    async fn get_signature_statuses(
+        &self,
+        signatures: &[Signature],
+    ) -> Result<Vec<Option<LandedStatus>>, StatusSourceError> {
+        let commitment = self.commitment();
+        let mut landed_statuses = Vec::with_capacity(signatures.len());
+        for chunk in signatures.chunks(MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS) {
+            let statuses = RpcClient::get_signature_statuses(self, chunk)
+                .await
+                .map_err(|err| StatusSourceError(err.to_string()))?
+                .value;
+            landed_statuses.extend(statuses.into_iter().map(|status| {
+                status
+                    .filter(|status| status.satisfies_commitment(commitment))
+                    .map(|status| LandedStatus {
+                        slot: status.slot,
+                        err: status.err,
+                    })
+            }));
+        }
+        Ok(landed_statuses)
+    }
+
+    async fn get_block_height(&self) -> Result<u64, StatusSourceError> {
+        RpcClient::get_block_height(self)
+            .await
+            .map_err(|err| StatusSourceError(err.to_string()))
+    }
+}
+
+/// [`NotifiedStatusSource`] is a [`TransactionStatusSource`] which is fed with
+/// notifications through its [`StatusNotifier`], e.g. from `signatureSubscribe`
+/// and `blockSubscribe` subscriptions or from a geyser plugin, instead of
+/// polling.
+///
+/// The status of a transaction is kept until it is queried, or until the
+/// block height is more than [`MAX_NOTIFIED_STATUS_AGE`] past the one it was
+/// notified at, so that the statuses of the transactions which are not
+/// tracked, or no longer are, do not accumulate.
+#[derive(Default)]
+pub struct NotifiedStatusSource {
+    state: Arc<NotifiedState>,
+}
+
+#[derive(Default)]
+struct NotifiedState {
+    statuses: Mutex<HashMap<Signature, NotifiedStatus>>,
+    block_height: AtomicU64,
+}
+
+struct NotifiedStatus {
+    status: LandedStatus,
+    /// The block height when the status was notified.
+    block_height: u64,
+}
+
+impl NotifiedStatusSource {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a [`StatusNotifier`] which feeds this source.
+    pub fn notifier(&self) -> StatusNotifier {
+        StatusNotifier {
+            state: self.state.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl TransactionStatusSource for NotifiedStatusSource {
+    async fn get_signature_statuses(
+        &self,
+        signatures: &[Signature],
+    ) -> Result<Vec<Option<LandedStatus>>, StatusSourceError> {
+        let mut statuses = self.state.statuses.lock().unwrap();
+        Ok(signatures
+            .iter()
+            .map(|signature| {
+                statuses
+                    .remove(signature)
+                    .map(|notified_status| notified_status.status)
+            })
+            .collect())
+    }
+
+    async fn get_block_height(&self) -> Result<u64, StatusSourceError> {
+        Ok(self.state.block_height.load(Ordering::Relaxed))
+    }
+}
+
+/// Handle to notify a [`NotifiedStatusSource`].
+#[derive(Clone)]
+pub struct StatusNotifier {
+    state: Arc<NotifiedState>,
+}
+
+impl StatusNotifier {
+    /// Notifies that the transaction with `signature` landed.
+    pub fn notify_landed(&self, signature: Signature, status: LandedStatus) {
+        let block_height = self.state.block_height.load(Ordering::Relaxed);
+        self.state.statuses.lock().unwrap().insert(
+            signature,
+            NotifiedStatus {
+                status,
+                block_height,
+            },
+        );
+    }
+
+    /// Notifies the current block height, the notifications of lower block
+    /// heights than the highest notified one are ignored. The statuses which
+    /// were not queried within [`MAX_NOTIFIED_STATUS_AGE`] block heights are
+    /// dropped.
+    pub fn notify_block_height(&self, block_height: u64) {
+        let previous_block_height = self
+            .state
+            .block_height
+            .fetch_max(block_height, Ordering::Relaxed);
+        if block_height <= previous_block_height {
+            return;
+        }
+        self.state
+            .statuses
+            .lock()
+            .unwrap()
+            .retain(|_, notified_status| {
+                block_height.saturating_sub(notified_status.block_height) <= MAX_NOTIFIED_STATUS_AGE
+            });
+    }
+}
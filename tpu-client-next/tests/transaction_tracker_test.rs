@@ -0,0 +1,269 @@
+use {
+    solana_signature::Signature,
+    solana_tpu_client_next::{
+        transaction_batch::TransactionBatch,
+        transaction_tracker::{
+            LandedStatus, NotifiedStatusSource, RebroadcastPolicy, StatusNotifier,
+            TrackedTransaction, TransactionOutcome, TransactionStatusSource,
+            TransactionTrackerConfig, TransactionTrackerError, TransactionTrackerStats,
+            TransactionTrackerStatsNonAtomic, MAX_NOTIFIED_STATUS_AGE,
+        },
+        TransactionTracker,
+    },
+    std::{sync::Arc, time::Duration},
+    tokio::{
+        sync::mpsc::{channel, unbounded_channel, Receiver, Sender, UnboundedReceiver},
+        task::JoinHandle,
+        time::timeout,
+    },
+    tokio_util::sync::CancellationToken,
+};
+
+const REBROADCAST_INTERVAL: Duration = Duration::from_millis(50);
+
+// Specify the pessimistic time to receive a batch or an outcome.
+const TEST_MAX_TIME: Duration = Duration::from_millis(2500);
+
+struct TrackerSetup {
+    tracker: JoinHandle<Result<Arc<TransactionTrackerStats>, TransactionTrackerError>>,
+    transaction_sender: Sender<TrackedTransaction>,
+    scheduler_receiver: Receiver<TransactionBatch>,
+    outcome_receiver: UnboundedReceiver<(Signature, TransactionOutcome)>,
+    notifier: StatusNotifier,
+    cancel: CancellationToken,
+}
+
+fn setup_tracker(max_rebroadcasts: Option<usize>, max_tracked_transactions: usize) -> TrackerSetup {
+    let status_source = NotifiedStatusSource::new();
+    let notifier = status_source.notifier();
+    let (transaction_sender, transaction_receiver) = channel(16);
+    let (scheduler_sender, scheduler_receiver) = channel(16);
+    let (outcome_sender, outcome_receiver) = unbounded_channel();
+    let cancel = CancellationToken::new();
+
+    let tracker = TransactionTracker::new(
+        Box::new(status_source),
+        transaction_receiver,
+        scheduler_sender,
+        cancel.clone(),
+    );
+    let config = TransactionTrackerConfig {
+        rebroadcast_policy: RebroadcastPolicy {
+            rebroadcast_interval: REBROADCAST_INTERVAL,
+            max_rebroadcasts,
+        },
+        max_tracked_transactions,
+        batch_size: 8,
+        outcome_sender: Some(outcome_sender),
+    };
+    let tracker = tokio::spawn(tracker.run(config));
+
+    TrackerSetup {
+        tracker,
+        transaction_sender,
+        scheduler_receiver,
+        outcome_receiver,
+        notifier,
+        cancel,
+    }
+}
+
+fn tracked_transaction(seed: u8, last_valid_block_height: u64) -> TrackedTransaction {
+    TrackedTransaction::new(
+        Signature::from([seed; 64]),
+        vec![seed; 8],
+        last_valid_block_height,
+    )
+}
+
+async fn recv_batch(scheduler_receiver: &mut Receiver<TransactionBatch>) -> Vec<Vec<u8>> {
+    timeout(TEST_MAX_TIME, scheduler_receiver.recv())
+        .await
+        .expect("Batch should be sent in time")
+        .expect("Scheduler channel should be open")
+        .into_iter()
+        .map(|wire_transaction| wire_transaction.to_vec())
+        .collect()
+}
+
+async fn recv_outcome(
+    outcome_receiver: &mut UnboundedReceiver<(Signature, TransactionOutcome)>,
+) -> (Signature, TransactionOutcome) {
+    timeout(TEST_MAX_TIME, outcome_receiver.recv())
+        .await
+        .expect("Outcome should be reported in time")
+        .expect("Outcome channel should be open")
+}
+
+async fn join_tracker(
+    tracker: JoinHandle<Result<Arc<TransactionTrackerStats>, TransactionTrackerError>>,
+) -> TransactionTrackerStatsNonAtomic {
+    timeout(TEST_MAX_TIME, tracker)
+        .await
+        .expect("Tracker should stop in time")
+        .unwrap()
+        .expect("Tracker should stop successfully.")
+        .read_and_reset()
+}
+
+#[tokio::test]
+async fn test_rebroadcast_until_landed() {
+    let TrackerSetup {
+        tracker,
+        transaction_sender,
+        mut scheduler_receiver,
+        mut outcome_receiver,
+        notifier,
+        cancel: _cancel,
+    } = setup_tracker(None, 16);
+
+    let transaction = tracked_transaction(1, 100);
+    let signature = transaction.signature;
+    transaction_sender.send(transaction).await.unwrap();
+    // Sent once, then rebroadcast while it did not land
+    for _ in 0..3 {
+        assert_eq!(recv_batch(&mut scheduler_receiver).await, vec![vec![1; 8]]);
+    }
+
+    let landed_status = LandedStatus {
+        slot: 42,
+        err: None,
+    };
+    notifier.notify_landed(signature, landed_status.clone());
+    assert_eq!(
+        recv_outcome(&mut outcome_receiver).await,
+        (signature, TransactionOutcome::Landed(landed_status))
+    );
+
+    // Nothing is pending once the transaction landed, so the tracker stops
+    // when its transaction channel is closed.
+    drop(transaction_sender);
+    let stats = join_tracker(tracker).await;
+    assert_eq!(stats.received, 1);
+    assert_eq!(stats.landed, 1);
+    assert!(stats.rebroadcast >= 2);
+    assert_eq!(stats.expired, 0);
+}
+
+#[tokio::test]
+async fn test_blockhash_expiry() {
+    let TrackerSetup {
+        tracker,
+        transaction_sender,
+        mut scheduler_receiver,
+        mut outcome_receiver,
+        notifier,
+        cancel: _cancel,
+    } = setup_tracker(None, 16);
+
+    let transaction = tracked_transaction(2, 100);
+    let signature = transaction.signature;
+    transaction_sender.send(transaction).await.unwrap();
+    assert_eq!(recv_batch(&mut scheduler_receiver).await, vec![vec![2; 8]]);
+    // The tracker keeps tracking the pending transaction after the transaction
+    // channel is closed.
+    drop(transaction_sender);
+
+    notifier.notify_block_height(100);
+    assert_eq!(recv_batch(&mut scheduler_receiver).await, vec![vec![2; 8]]);
+    notifier.notify_block_height(101);
+    assert_eq!(
+        recv_outcome(&mut outcome_receiver).await,
+        (signature, TransactionOutcome::Expired)
+    );
+
+    let stats = join_tracker(tracker).await;
+    assert_eq!(stats.expired, 1);
+    assert_eq!(stats.landed, 0);
+}
+
+#[tokio::test]
+async fn test_rebroadcasts_exhausted() {
+    let TrackerSetup {
+        tracker,
+        transaction_sender,
+        mut scheduler_receiver,
+        mut outcome_receiver,
+        notifier: _notifier,
+        cancel: _cancel,
+    } = setup_tracker(Some(2), 16);
+
+    let transaction = tracked_transaction(3, u64::MAX);
+    let signature = transaction.signature;
+    transaction_sender.send(transaction).await.unwrap();
+    drop(transaction_sender);
+    for _ in 0..3 {
+        assert_eq!(recv_batch(&mut scheduler_receiver).await, vec![vec![3; 8]]);
+    }
+    assert_eq!(
+        recv_outcome(&mut outcome_receiver).await,
+        (signature, TransactionOutcome::RebroadcastsExhausted)
+    );
+
+    let stats = join_tracker(tracker).await;
+    assert_eq!(stats.rebroadcast, 2);
+    assert_eq!(stats.rebroadcasts_exhausted, 1);
+    // The scheduler channel is closed once the tracker stops
+    assert!(scheduler_receiver.recv().await.is_none());
+}
+
+#[tokio::test]
+async fn test_max_tracked_transactions() {
+    let TrackerSetup {
+        tracker,
+        transaction_sender,
+        mut scheduler_receiver,
+        outcome_receiver: _outcome_receiver,
+        notifier: _notifier,
+        cancel,
+    } = setup_tracker(None, 1);
+
+    transaction_sender
+        .send(tracked_transaction(4, u64::MAX))
+        .await
+        .unwrap();
+    assert_eq!(recv_batch(&mut scheduler_receiver).await, vec![vec![4; 8]]);
+    transaction_sender
+        .send(tracked_transaction(5, u64::MAX))
+        .await
+        .unwrap();
+    assert_eq!(recv_batch(&mut scheduler_receiver).await, vec![vec![5; 8]]);
+    // Only the first transaction is rebroadcast
+    for _ in 0..2 {
+        assert_eq!(recv_batch(&mut scheduler_receiver).await, vec![vec![4; 8]]);
+    }
+
+    cancel.cancel();
+    let stats = join_tracker(tracker).await;
+    assert_eq!(stats.received, 2);
+    assert_eq!(stats.untracked, 1);
+}
+
+#[tokio::test]
+async fn test_notified_statuses_expire() {
+    let status_source = NotifiedStatusSource::new();
+    let notifier = status_source.notifier();
+    let landed_status = LandedStatus { slot: 7, err: None };
+    let kept = Signature::from([6; 64]);
+    let expired = Signature::from([7; 64]);
+
+    notifier.notify_block_height(10);
+    notifier.notify_landed(expired, landed_status.clone());
+    notifier.notify_block_height(11);
+    notifier.notify_landed(kept, landed_status.clone());
+    // The status notified at block height 10 is too old, not the one notified
+    // at 11
+    notifier.notify_block_height(11 + MAX_NOTIFIED_STATUS_AGE);
+    assert_eq!(
+        status_source
+            .get_signature_statuses(&[kept, expired])
+            .await
+            .unwrap(),
+        vec![Some(landed_status), None]
+    );
+    // The statuses are removed once queried
+    assert_eq!(
+        status_source.get_signature_statuses(&[kept]).await.unwrap(),
+        vec![None]
+    );
+}
@@ -0,0 +1 @@
+pub mod tpu_ingress_filter_interface;
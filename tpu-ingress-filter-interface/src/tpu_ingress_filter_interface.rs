@@ -0,0 +1,111 @@
+//! The interface for TPU ingress filters. A filter must implement
+//! the TpuIngressFilter trait to work with the validator.
+//! In addition, the dynamic library must export a "C" function _create_tpu_ingress_filter which
+//! creates the implementation of the filter.
+use {
+    std::{any::Any, error, io, net::IpAddr},
+    thiserror::Error,
+};
+
+/// Information about a packet received by the TPU
+///
+/// Filters are Rust trait objects, so they must be built with the same compiler as the validator,
+/// and this structure keeps the Rust layout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IngressPacketInfo<'a> {
+    /// The payload of the packet, a serialized transaction unless the
+    /// sender is misbehaving
+    pub data: &'a [u8],
+
+    /// The IP address of the sender
+    pub ip: IpAddr,
+
+    /// The port of the sender
+    pub port: u16,
+
+    /// Whether the packet was received from a staked node over QUIC
+    pub from_staked_node: bool,
+
+    /// Whether the packet was forwarded by another node
+    pub forwarded: bool,
+}
+
+/// A wrapper to future-proof IngressPacketInfo handling. To make a change to the structure
+/// of IngressPacketInfo, add an new enum variant wrapping a newer version, which will force
+/// filter implementations to handle the change.
+#[repr(u32)]
+pub enum IngressPacketInfoVersions<'a> {
+    V0_0_1(&'a IngressPacketInfo<'a>),
+}
+
+/// What the TPU does with a packet after filtering it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum IngressFilterAction {
+    /// The packet goes on to signature verification
+    Accept,
+
+    /// The packet goes on to signature verification, unless signature
+    /// verification is falling behind, in which case it is dropped
+    Deprioritize,
+
+    /// The packet is dropped
+    Drop,
+}
+
+/// Errors returned by filter calls
+#[derive(Error, Debug)]
+#[repr(u32)]
+pub enum TpuIngressFilterError {
+    /// Error opening the configuration file; for example, when the file
+    /// is not found or when the validator process has no permission to read it.
+    #[error("Error opening config file. Error detail: ({0}).")]
+    ConfigFileOpenError(#[from] io::Error),
+
+    /// Error in reading the content of the config file or the content
+    /// is not in the expected format.
+    #[error("Error reading config file. Error message: ({msg})")]
+    ConfigFileReadError { msg: String },
+
+    /// Any custom error defined by the filter.
+    #[error("Filter-defined custom error. Error message: ({0})")]
+    Custom(Box<dyn error::Error + Send + Sync>),
+}
+
+pub type Result<T> = std::result::Result<T, TpuIngressFilterError>;
+
+/// Defines a TPU ingress filter, to drop or deprioritize the packets received
+/// by the TPU before their signatures are verified.
+///
+/// When several filters are loaded, a packet is dropped if any of them drops
+/// it, and deprioritized if any of them deprioritizes it.
+pub trait TpuIngressFilter: Any + Send + Sync + std::fmt::Debug {
+    /// The callback to allow the filter to setup the logging configuration using the logger
+    /// and log level specified by the validator. Will be called first on load/reload, before any other
+    /// callback, and only called once.
+    #[allow(unused_variables)]
+    fn setup_logger(&self, logger: &'static dyn log::Log, level: log::LevelFilter) -> Result<()> {
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str;
+
+    /// The callback called when a filter is loaded by the system,
+    /// used for doing whatever initialization is required by the filter.
+    /// The _config_file contains the name of the
+    /// of the config file. The config must be in JSON format and
+    /// include a field "libpath" indicating the full path
+    /// name of the shared library implementing this interface.
+    fn on_load(&mut self, _config_file: &str, _is_reload: bool) -> Result<()> {
+        Ok(())
+    }
+
+    /// The callback called right before a filter is unloaded by the system
+    /// Used for doing cleanup before unload.
+    fn on_unload(&mut self) {}
+
+    /// Called for every packet received by the TPU, except the packets already
+    /// discarded. It is called from the thread of the filter stage, so it must
+    /// return quickly not to delay the packets.
+    fn filter_packet(&self, packet: IngressPacketInfoVersions) -> IngressFilterAction;
+}
@@ -18,6 +18,7 @@ use {
         v0::{LoadedAddresses, MessageAddressTableLookup},
         MessageHeader,
     },
+    solana_pubkey::Pubkey,
     solana_reward_info::RewardType,
     solana_signature::Signature,
     solana_transaction::versioned::{TransactionVersion, VersionedTransaction},
@@ -368,6 +369,16 @@ pub struct UiTransactionStatusMeta {
         skip_serializing_if = "OptionSerializer::should_skip"
     )]
     pub cost_units: OptionSerializer<u64>,
+    #[serde(
+        default = "OptionSerializer::skip",
+        skip_serializing_if = "OptionSerializer::should_skip"
+    )]
+    pub log_events: OptionSerializer<Vec<UiLogEvent>>,
+    #[serde(
+        default = "OptionSerializer::skip",
+        skip_serializing_if = "OptionSerializer::should_skip"
+    )]
+    pub syscall_compute_units: OptionSerializer<UiSyscallComputeUnits>,
 }
 
 impl From<TransactionStatusMeta> for UiTransactionStatusMeta {
@@ -398,6 +409,13 @@ impl From<TransactionStatusMeta> for UiTransactionStatusMeta {
             ),
             compute_units_consumed: OptionSerializer::or_skip(meta.compute_units_consumed),
             cost_units: OptionSerializer::or_skip(meta.cost_units),
+            log_events: OptionSerializer::or_skip(
+                meta.log_events
+                    .map(|events| events.into_iter().map(Into::into).collect()),
+            ),
+            syscall_compute_units: OptionSerializer::or_skip(
+                meta.syscall_compute_units.map(Into::into),
+            ),
         }
     }
 }
@@ -436,6 +454,150 @@ pub enum UiReturnDataEncoding {
     Base64,
 }
 
+/// Typed program log event, recorded alongside the log messages of a transaction
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TransactionLogEvent {
+    /// A program was invoked, `stack_height` is its height in the invocation stack
+    Invoke {
+        program_id: Pubkey,
+        stack_height: usize,
+    },
+    /// Message logged by the program
+    Log { message: String },
+    /// Data emitted by the program with `sol_log_data`
+    Data { data: Vec<Vec<u8>> },
+    /// Return data set by the program
+    Return { program_id: Pubkey, data: Vec<u8> },
+    /// Compute units consumed by the program, out of the ones available when it was invoked
+    ComputeUnits {
+        program_id: Pubkey,
+        consumed: u64,
+        available: u64,
+    },
+    /// The program completed successfully
+    Success { program_id: Pubkey },
+    /// The program failed, `error` describes why
+    Failure { program_id: Pubkey, error: String },
+    /// The payload events past this one were dropped because of the bytes limit
+    Truncated,
+}
+
+/// Typed program log event, the binary payloads are base64 encoded
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum UiLogEvent {
+    #[serde(rename_all = "camelCase")]
+    Invoke {
+        program_id: String,
+        stack_height: usize,
+    },
+    Log {
+        message: String,
+    },
+    Data {
+        data: Vec<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Return {
+        program_id: String,
+        data: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    ComputeUnits {
+        program_id: String,
+        consumed: u64,
+        available: u64,
+    },
+    #[serde(rename_all = "camelCase")]
+    Success {
+        program_id: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    Failure {
+        program_id: String,
+        error: String,
+    },
+    Truncated,
+}
+
+impl From<TransactionLogEvent> for UiLogEvent {
+    fn from(event: TransactionLogEvent) -> Self {
+        match event {
+            TransactionLogEvent::Invoke {
+                program_id,
+                stack_height,
+            } => Self::Invoke {
+                program_id: program_id.to_string(),
+                stack_height,
+            },
+            TransactionLogEvent::Log { message } => Self::Log { message },
+            TransactionLogEvent::Data { data } => Self::Data {
+                data: data.iter().map(|v| BASE64_STANDARD.encode(v)).collect(),
+            },
+            TransactionLogEvent::Return { program_id, data } => Self::Return {
+                program_id: program_id.to_string(),
+                data: BASE64_STANDARD.encode(data),
+            },
+            TransactionLogEvent::ComputeUnits {
+                program_id,
+                consumed,
+                available,
+            } => Self::ComputeUnits {
+                program_id: program_id.to_string(),
+                consumed,
+                available,
+            },
+            TransactionLogEvent::Success { program_id } => Self::Success {
+                program_id: program_id.to_string(),
+            },
+            TransactionLogEvent::Failure { program_id, error } => Self::Failure {
+                program_id: program_id.to_string(),
+                error,
+            },
+            TransactionLogEvent::Truncated => Self::Truncated,
+        }
+    }
+}
+
+/// Compute units charged by the syscalls of a transaction, per category
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TransactionSyscallComputeUnits {
+    pub hashing: u64,
+    pub curve_ops: u64,
+    /// Not including the compute units consumed by the invoked programs
+    pub cpi: u64,
+    pub logging: u64,
+    pub memory_ops: u64,
+    pub sysvars: u64,
+    pub other: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UiSyscallComputeUnits {
+    pub hashing: u64,
+    pub curve_ops: u64,
+    pub cpi: u64,
+    pub logging: u64,
+    pub memory_ops: u64,
+    pub sysvars: u64,
+    pub other: u64,
+}
+
+impl From<TransactionSyscallComputeUnits> for UiSyscallComputeUnits {
+    fn from(units: TransactionSyscallComputeUnits) -> Self {
+        Self {
+            hashing: units.hashing,
+            curve_ops: units.curve_ops,
+            cpi: units.cpi,
+            logging: units.logging,
+            memory_ops: units.memory_ops,
+            sysvars: units.sysvars,
+            other: units.other,
+        }
+    }
+}
+
 /// A duplicate representation of LoadedAddresses
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -642,6 +804,10 @@ pub struct TransactionStatusMeta {
     pub return_data: Option<TransactionReturnData>,
     pub compute_units_consumed: Option<u64>,
     pub cost_units: Option<u64>,
+    /// Typed counterpart of `log_messages`
+    pub log_events: Option<Vec<TransactionLogEvent>>,
+    /// Compute units charged by the syscalls per category
+    pub syscall_compute_units: Option<TransactionSyscallComputeUnits>,
 }
 
 impl Default for TransactionStatusMeta {
@@ -660,6 +826,8 @@ impl Default for TransactionStatusMeta {
             return_data: None,
             compute_units_consumed: None,
             cost_units: None,
+            log_events: None,
+            syscall_compute_units: None,
         }
     }
 }
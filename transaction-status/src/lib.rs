@@ -8,12 +8,13 @@ pub use {
         EncodedConfirmedBlock, EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction,
         EncodedTransactionWithStatusMeta, InnerInstruction, InnerInstructions, Reward, Rewards,
         TransactionBinaryEncoding, TransactionConfirmationStatus, TransactionDetails,
-        TransactionStatus, TransactionStatusMeta, TransactionTokenBalance, UiAccountsList,
+        TransactionLogEvent, TransactionStatus, TransactionStatusMeta,
+        TransactionSyscallComputeUnits, TransactionTokenBalance, UiAccountsList,
         UiAddressTableLookup, UiCompiledInstruction, UiConfirmedBlock, UiInnerInstructions,
-        UiInstruction, UiLoadedAddresses, UiMessage, UiParsedInstruction, UiParsedMessage,
-        UiPartiallyDecodedInstruction, UiRawMessage, UiReturnDataEncoding, UiTransaction,
-        UiTransactionEncoding, UiTransactionReturnData, UiTransactionStatusMeta,
-        UiTransactionTokenBalance,
+        UiInstruction, UiLoadedAddresses, UiLogEvent, UiMessage, UiParsedInstruction,
+        UiParsedMessage, UiPartiallyDecodedInstruction, UiRawMessage, UiReturnDataEncoding,
+        UiSyscallComputeUnits, UiTransaction, UiTransactionEncoding, UiTransactionReturnData,
+        UiTransactionStatusMeta, UiTransactionTokenBalance,
     },
 };
 use {
@@ -34,6 +35,8 @@ use {
     },
     solana_pubkey::Pubkey,
     solana_signature::Signature,
+    solana_svm_log_collector::LogEvent,
+    solana_svm_timings::SyscallComputeUnits,
     solana_transaction::{
         versioned::{TransactionVersion, VersionedTransaction},
         Transaction,
@@ -139,6 +142,56 @@ pub fn map_inner_instructions(
         .filter(|i| !i.instructions.is_empty())
 }
 
+pub fn map_log_events(log_events: Vec<LogEvent>) -> impl Iterator<Item = TransactionLogEvent> {
+    log_events.into_iter().map(|event| match event {
+        LogEvent::Invoke {
+            program_id,
+            stack_height,
+        } => TransactionLogEvent::Invoke {
+            program_id,
+            stack_height,
+        },
+        LogEvent::Log { message } => TransactionLogEvent::Log { message },
+        LogEvent::Data { data } => TransactionLogEvent::Data { data },
+        LogEvent::Return { program_id, data } => TransactionLogEvent::Return { program_id, data },
+        LogEvent::ComputeUnits {
+            program_id,
+            consumed,
+            available,
+        } => TransactionLogEvent::ComputeUnits {
+            program_id,
+            consumed,
+            available,
+        },
+        LogEvent::Success { program_id } => TransactionLogEvent::Success { program_id },
+        LogEvent::Failure { program_id, error } => {
+            TransactionLogEvent::Failure { program_id, error }
+        }
+        LogEvent::Truncated => TransactionLogEvent::Truncated,
+    })
+}
+
+pub fn map_syscall_compute_units(units: SyscallComputeUnits) -> TransactionSyscallComputeUnits {
+    let SyscallComputeUnits {
+        hashing,
+        curve_ops,
+        cpi,
+        logging,
+        memory_ops,
+        sysvars,
+        other,
+    } = units;
+    TransactionSyscallComputeUnits {
+        hashing,
+        curve_ops,
+        cpi,
+        logging,
+        memory_ops,
+        sysvars,
+        other,
+    }
+}
+
 pub fn parse_ui_inner_instructions(
     inner_instructions: InnerInstructions,
     account_keys: &AccountKeys,
@@ -187,6 +240,8 @@ fn build_simple_ui_transaction_status_meta(
         return_data: OptionSerializer::Skip,
         compute_units_consumed: OptionSerializer::Skip,
         cost_units: OptionSerializer::Skip,
+        log_events: OptionSerializer::Skip,
+        syscall_compute_units: OptionSerializer::Skip,
     }
 }
 
@@ -226,6 +281,13 @@ fn parse_ui_transaction_status_meta(
         ),
         compute_units_consumed: OptionSerializer::or_skip(meta.compute_units_consumed),
         cost_units: OptionSerializer::or_skip(meta.cost_units),
+        log_events: OptionSerializer::or_skip(
+            meta.log_events
+                .map(|events| events.into_iter().map(Into::into).collect()),
+        ),
+        syscall_compute_units: OptionSerializer::or_skip(
+            meta.syscall_compute_units.map(Into::into),
+        ),
     }
 }
 
@@ -902,6 +964,8 @@ mod test {
             return_data: None,
             compute_units_consumed: None,
             cost_units: None,
+            log_events: None,
+            syscall_compute_units: None,
         };
         #[rustfmt::skip]
         let expected_json_output_value: serde_json::Value = serde_json::from_str(
@@ -15,6 +15,9 @@ pub mod quic_endpoint;
 #[cfg(feature = "agave-unstable-api")]
 pub mod retransmit_stage;
 
+#[cfg(feature = "agave-unstable-api")]
+pub mod shred_tap;
+
 #[cfg(feature = "agave-unstable-api")]
 pub mod sigverify_shreds;
 
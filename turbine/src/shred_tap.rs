@@ -0,0 +1,397 @@
+//! The `shred_tap` mirrors the shreds which passed signature verification to
+//! a configured list of local subscribers, e.g. indexers which want the
+//! entries of a slot before replay completes.
+//!
+//! Over UDP the shreds are sent with sendmmsg from a dedicated socket. Over
+//! QUIC they are sent as datagrams from a dedicated QUIC endpoint, whose
+//! certificate is signed by the node identity, so that subscribers can
+//! authenticate the validator. The turbine QUIC endpoint is not shared, so
+//! that a slow subscriber never takes buffer space away from retransmit.
+
+use {
+    crate::quic_endpoint::{self, AsyncTryJoinHandle},
+    bytes::Bytes,
+    crossbeam_channel::{Receiver, RecvTimeoutError},
+    quinn::Endpoint,
+    solana_gossip::contact_info::Protocol,
+    solana_keypair::Keypair,
+    solana_ledger::shred::{self, ShredType},
+    solana_net_utils::sockets::bind_to,
+    solana_pubkey::Pubkey,
+    solana_runtime::bank_forks::BankForks,
+    solana_streamer::sendmmsg::{batch_send, SendPktsError},
+    std::{
+        io,
+        net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket},
+        sync::{Arc, RwLock},
+        thread::{self, Builder, JoinHandle},
+        time::{Duration, Instant},
+    },
+    tokio::{runtime::Runtime as TokioRuntime, sync::mpsc::Sender as AsyncSender},
+};
+
+/// Number of batches of shreds buffered for the tap. The oldest batches are
+/// evicted when the tap falls behind, so it never delays the turbine.
+pub const SHRED_TAP_CHANNEL_SIZE: usize = 1024;
+
+const RECV_TIMEOUT: Duration = Duration::from_secs(1);
+const STATS_REPORT_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShredTapConfig {
+    /// Addresses of the subscribers the shreds are mirrored to.
+    pub addrs: Vec<SocketAddr>,
+    /// Over UDP the addresses must be loopback addresses, since the
+    /// subscribers cannot authenticate the node.
+    pub protocol: Protocol,
+    /// Only mirror the data shreds, which are enough to recover the entries
+    /// unless some of them were lost.
+    pub data_shreds_only: bool,
+}
+
+#[derive(Default)]
+struct ShredTapStats {
+    num_shreds: usize,
+    num_shreds_skipped: usize,
+    num_packets_sent: usize,
+    num_packets_failed: usize,
+}
+
+impl ShredTapStats {
+    fn report(&self) {
+        datapoint_info!(
+            "shred-tap",
+            ("num_shreds", self.num_shreds, i64),
+            ("num_shreds_skipped", self.num_shreds_skipped, i64),
+            ("num_packets_sent", self.num_packets_sent, i64),
+            ("num_packets_failed", self.num_packets_failed, i64),
+        );
+    }
+}
+
+enum ShredTapSender {
+    Udp(UdpSocket),
+    Quic(AsyncSender<(SocketAddr, Bytes)>),
+}
+
+impl ShredTapSender {
+    // Returns the number of packets which failed to be sent.
+    fn send(&self, shreds: &[Bytes], addrs: &[SocketAddr]) -> usize {
+        match self {
+            Self::Udp(socket) => {
+                let packets: Vec<_> = shreds
+                    .iter()
+                    .flat_map(|shred| addrs.iter().map(move |addr| (shred, addr)))
+                    .collect();
+                match batch_send(socket, packets) {
+                    Ok(()) => 0,
+                    Err(SendPktsError::IoError(err, num_failed)) => {
+                        debug!("shred tap batch_send error: {err:?}");
+                        num_failed
+                    }
+                }
+            }
+            Self::Quic(quic_endpoint_sender) => shreds
+                .iter()
+                .flat_map(|shred| addrs.iter().map(move |&addr| (addr, shred.clone())))
+                .map(|packet| quic_endpoint_sender.try_send(packet))
+                .filter(Result::is_err)
+                .count(),
+        }
+    }
+}
+
+// The QUIC endpoint the shreds are sent from, with the runtime driving it.
+struct ShredTapQuicEndpoint {
+    runtime: TokioRuntime,
+    endpoint: Endpoint,
+    join_handle: AsyncTryJoinHandle,
+}
+
+pub struct ShredTap {
+    thread_hdl: JoinHandle<()>,
+    quic_endpoint: Option<ShredTapQuicEndpoint>,
+}
+
+impl ShredTap {
+    pub fn new(
+        config: ShredTapConfig,
+        shred_receiver: Receiver<Vec<shred::Payload>>,
+        keypair: &Keypair,
+        bank_forks: Arc<RwLock<BankForks>>,
+    ) -> io::Result<Self> {
+        // UDP is unauthenticated, so the shreds may only be sent over it to
+        // subscribers on the same host.
+        if config.protocol == Protocol::UDP {
+            if let Some(addr) = config.addrs.iter().find(|addr| !addr.ip().is_loopback()) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("UDP shred tap address is not a loopback address: {addr}"),
+                ));
+            }
+        }
+        let socket = bind_to(IpAddr::V4(Ipv4Addr::UNSPECIFIED), /*port:*/ 0)?;
+        // Datagrams sent by the subscribers are received here and discarded.
+        let (quic_incoming_sender, quic_incoming_receiver) =
+            crossbeam_channel::unbounded::<(Pubkey, SocketAddr, Bytes)>();
+        let (sender, quic_endpoint) = match config.protocol {
+            Protocol::UDP => (ShredTapSender::Udp(socket), None),
+            Protocol::QUIC => {
+                let runtime = tokio::runtime::Builder::new_multi_thread()
+                    .worker_threads(2)
+                    .enable_all()
+                    .thread_name("solShredTapQuic")
+                    .build()?;
+                let (endpoint, quic_endpoint_sender, join_handle) =
+                    quic_endpoint::new_quic_endpoint(
+                        runtime.handle(),
+                        keypair,
+                        socket,
+                        quic_incoming_sender,
+                        bank_forks,
+                    )
+                    .map_err(io::Error::other)?;
+                let quic_endpoint = ShredTapQuicEndpoint {
+                    runtime,
+                    endpoint,
+                    join_handle,
+                };
+                (
+                    ShredTapSender::Quic(quic_endpoint_sender),
+                    Some(quic_endpoint),
+                )
+            }
+        };
+        let ShredTapConfig {
+            addrs,
+            protocol: _,
+            data_shreds_only,
+        } = config;
+        let thread_hdl = Builder::new()
+            .name("solShredTap".to_string())
+            .spawn(move || {
+                let mut stats = ShredTapStats::default();
+                let mut last_report = Instant::now();
+                loop {
+                    quic_incoming_receiver.try_iter().for_each(drop);
+                    match shred_receiver.recv_timeout(RECV_TIMEOUT) {
+                        Ok(shreds) => {
+                            let shreds: Vec<Bytes> = std::iter::once(shreds)
+                                .chain(shred_receiver.try_iter())
+                                .flatten()
+                                .filter_map(|shred| {
+                                    stats.num_shreds += 1;
+                                    if data_shreds_only && !is_data_shred(&shred) {
+                                        stats.num_shreds_skipped += 1;
+                                        return None;
+                                    }
+                                    Some(shred.bytes)
+                                })
+                                .collect();
+                            let num_failed = sender.send(&shreds, &addrs);
+                            stats.num_packets_sent += shreds.len() * addrs.len() - num_failed;
+                            stats.num_packets_failed += num_failed;
+                        }
+                        Err(RecvTimeoutError::Timeout) => (),
+                        Err(RecvTimeoutError::Disconnected) => break,
+                    }
+                    if last_report.elapsed() > STATS_REPORT_INTERVAL {
+                        if stats.num_shreds > 0 {
+                            stats.report();
+                        }
+                        stats = ShredTapStats::default();
+                        last_report = Instant::now();
+                    }
+                }
+            })?;
+        Ok(Self {
+            thread_hdl,
+            quic_endpoint,
+        })
+    }
+
+    pub fn join(self) -> thread::Result<()> {
+        self.thread_hdl.join()?;
+        if let Some(ShredTapQuicEndpoint {
+            runtime,
+            endpoint,
+            join_handle,
+        }) = self.quic_endpoint
+        {
+            quic_endpoint::close_quic_endpoint(&endpoint);
+            if let Err(err) = runtime.block_on(join_handle) {
+                error!("shred tap quic endpoint: {err:?}");
+            }
+        }
+        Ok(())
+    }
+}
+
+fn is_data_shred(shred: &[u8]) -> bool {
+    shred::layout::get_shred_id(shred)
+        .map(|shred_id| shred_id.shred_type() == ShredType::Data)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crossbeam_channel::unbounded,
+        solana_entry::entry::create_ticks,
+        solana_hash::Hash,
+        solana_ledger::{
+            genesis_utils::{create_genesis_config, GenesisConfigInfo},
+            shred::{ProcessShredsStats, ReedSolomonCache, Shredder},
+        },
+        solana_net_utils::sockets::{bind_to_localhost_unique, localhost_port_range_for_tests},
+        solana_perf::packet::Packet,
+        solana_runtime::bank::Bank,
+        solana_signer::Signer,
+        solana_streamer::recvmmsg::recv_mmsg,
+        std::collections::HashSet,
+    };
+
+    fn new_bank_forks() -> Arc<RwLock<BankForks>> {
+        let GenesisConfigInfo { genesis_config, .. } =
+            create_genesis_config(/*mint_lamports:*/ 100_000);
+        BankForks::new_rw_arc(Bank::new_for_tests(&genesis_config))
+    }
+
+    fn make_shreds() -> (Vec<shred::Payload>, Vec<shred::Payload>) {
+        let keypair = Keypair::new();
+        let entries = create_ticks(10, 1, Hash::new_unique());
+        let shredder = Shredder::new(1, 0, 0, 0).unwrap();
+        let (data_shreds, coding_shreds) = shredder.entries_to_merkle_shreds_for_tests(
+            &keypair,
+            &entries,
+            true,
+            Hash::new_unique(),
+            0,
+            0,
+            &ReedSolomonCache::default(),
+            &mut ProcessShredsStats::default(),
+        );
+        (
+            data_shreds
+                .into_iter()
+                .map(shred::Shred::into_payload)
+                .collect(),
+            coding_shreds
+                .into_iter()
+                .map(shred::Shred::into_payload)
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_shred_tap_udp_data_shreds_only() {
+        let subscriber = bind_to_localhost_unique().unwrap();
+        subscriber
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        let (shred_sender, shred_receiver) = unbounded();
+        let shred_tap = ShredTap::new(
+            ShredTapConfig {
+                addrs: vec![subscriber.local_addr().unwrap()],
+                protocol: Protocol::UDP,
+                data_shreds_only: true,
+            },
+            shred_receiver,
+            &Keypair::new(),
+            new_bank_forks(),
+        )
+        .unwrap();
+
+        let (data_shreds, coding_shreds) = make_shreds();
+        assert!(!coding_shreds.is_empty());
+        let num_data_shreds = data_shreds.len();
+        shred_sender
+            .send(
+                coding_shreds
+                    .into_iter()
+                    .chain(data_shreds.clone())
+                    .collect(),
+            )
+            .unwrap();
+
+        let mut packets = vec![Packet::default(); num_data_shreds];
+        let mut num_received = 0;
+        while num_received < num_data_shreds {
+            num_received += recv_mmsg(&subscriber, &mut packets[num_received..]).unwrap();
+        }
+        for (packet, shred) in packets.iter().zip(&data_shreds) {
+            assert_eq!(packet.data(..).unwrap(), shred.as_ref());
+        }
+
+        drop(shred_sender);
+        shred_tap.join().unwrap();
+    }
+
+    #[test]
+    fn test_shred_tap_quic() {
+        const RECV_TIMEOUT: Duration = Duration::from_secs(60);
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(2)
+            .enable_all()
+            .build()
+            .unwrap();
+        let bank_forks = new_bank_forks();
+        // The subscriber runs its own QUIC endpoint and authenticates the
+        // validator by the pubkey of its certificate.
+        let subscriber_socket = bind_to(
+            IpAddr::V4(Ipv4Addr::LOCALHOST),
+            localhost_port_range_for_tests().0,
+        )
+        .unwrap();
+        let subscriber = subscriber_socket.local_addr().unwrap();
+        let (subscriber_sender, subscriber_receiver) = unbounded();
+        let (subscriber_endpoint, subscriber_endpoint_sender, subscriber_task) =
+            quic_endpoint::new_quic_endpoint(
+                runtime.handle(),
+                &Keypair::new(),
+                subscriber_socket,
+                subscriber_sender,
+                bank_forks.clone(),
+            )
+            .unwrap();
+
+        let keypair = Keypair::new();
+        let (shred_sender, shred_receiver) = unbounded();
+        let shred_tap = ShredTap::new(
+            ShredTapConfig {
+                addrs: vec![subscriber],
+                protocol: Protocol::QUIC,
+                data_shreds_only: false,
+            },
+            shred_receiver,
+            &keypair,
+            bank_forks,
+        )
+        .unwrap();
+
+        let (data_shreds, coding_shreds) = make_shreds();
+        let shreds: HashSet<Bytes> = data_shreds
+            .into_iter()
+            .chain(coding_shreds)
+            .map(|shred| shred.bytes)
+            .collect();
+        shred_sender
+            .send(shreds.iter().cloned().map(shred::Payload::from).collect())
+            .unwrap();
+
+        let mut received = HashSet::new();
+        while received.len() < shreds.len() {
+            let (pubkey, _, bytes) = subscriber_receiver.recv_timeout(RECV_TIMEOUT).unwrap();
+            assert_eq!(pubkey, keypair.pubkey());
+            received.insert(bytes);
+        }
+        assert_eq!(received, shreds);
+
+        drop(shred_sender);
+        shred_tap.join().unwrap();
+        quic_endpoint::close_quic_endpoint(&subscriber_endpoint);
+        drop(subscriber_endpoint_sender);
+        runtime.block_on(subscriber_task).unwrap();
+    }
+}
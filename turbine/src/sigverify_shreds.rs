@@ -4,7 +4,7 @@ use {
         retransmit_stage::RetransmitStage,
     },
     agave_feature_set as feature_set,
-    crossbeam_channel::{Receiver, RecvTimeoutError, SendError, Sender},
+    crossbeam_channel::{Receiver, RecvTimeoutError, SendError, Sender, TrySendError},
     itertools::{Either, Itertools},
     rayon::{prelude::*, ThreadPool, ThreadPoolBuilder},
     solana_clock::Slot,
@@ -81,6 +81,7 @@ pub fn spawn_shred_sigverify(
     shred_fetch_receiver: Receiver<PacketBatch>,
     retransmit_sender: EvictingSender<Vec<shred::Payload>>,
     verified_sender: Sender<Vec<(shred::Payload, /*is_repaired:*/ bool)>>,
+    shred_tap_sender: Option<EvictingSender<Vec<shred::Payload>>>,
     num_sigverify_threads: NonZeroUsize,
 ) -> JoinHandle<()> {
     let recycler_cache = RecyclerCache::warmed();
@@ -117,6 +118,7 @@ pub fn spawn_shred_sigverify(
                 &shred_fetch_receiver,
                 &retransmit_sender,
                 &verified_sender,
+                shred_tap_sender.as_ref(),
                 &cluster_nodes_cache,
                 &cache,
                 &mut stats,
@@ -148,6 +150,7 @@ fn run_shred_sigverify<const K: usize>(
     shred_fetch_receiver: &Receiver<PacketBatch>,
     retransmit_sender: &EvictingSender<Vec<shred::Payload>>,
     verified_sender: &Sender<Vec<(shred::Payload, /*is_repaired:*/ bool)>>,
+    shred_tap_sender: Option<&EvictingSender<Vec<shred::Payload>>>,
     cluster_nodes_cache: &ClusterNodesCache<RetransmitStage>,
     cache: &RwLock<LruCache>,
     stats: &mut ShredSigVerifyStats,
@@ -266,6 +269,13 @@ fn run_shred_sigverify<const K: usize>(
             _ => unreachable!("EvictingSender holds on to both ends of the channel"),
         }
     }
+    // Mirror all verified shreds to the local subscribers of the shred tap.
+    if let Some(shred_tap_sender) = shred_tap_sender {
+        let tap_shreds = shreds.iter().chain(&repairs).cloned().collect();
+        if let Err(TrySendError::Full(v)) = shred_tap_sender.try_send(tap_shreds) {
+            stats.num_shred_tap_overflow_shreds += v.len();
+        }
+    }
     // Send all shreds to window service to be inserted into blockstore.
     let shreds = shreds
         .into_iter()
@@ -473,6 +483,7 @@ struct ShredSigVerifyStats {
     num_retranmitter_signature_verified: AtomicUsize,
     num_retransmit_stage_overflow_shreds: usize,
     num_retransmit_shreds: usize,
+    num_shred_tap_overflow_shreds: usize,
     num_unknown_slot_leader: AtomicUsize,
     num_unknown_turbine_parent: AtomicUsize,
     elapsed_micros: u64,
@@ -497,6 +508,7 @@ impl ShredSigVerifyStats {
             num_retranmitter_signature_verified: AtomicUsize::default(),
             num_retransmit_stage_overflow_shreds: 0usize,
             num_retransmit_shreds: 0usize,
+            num_shred_tap_overflow_shreds: 0usize,
             num_unknown_slot_leader: AtomicUsize::default(),
             num_unknown_turbine_parent: AtomicUsize::default(),
             elapsed_micros: 0u64,
@@ -540,6 +552,11 @@ impl ShredSigVerifyStats {
                 i64
             ),
             ("num_retransmit_shreds", self.num_retransmit_shreds, i64),
+            (
+                "num_shred_tap_overflow_shreds",
+                self.num_shred_tap_overflow_shreds,
+                i64
+            ),
             (
                 "num_unknown_slot_leader",
                 self.num_unknown_slot_leader.load(Ordering::Relaxed),
@@ -568,7 +585,7 @@ mod tests {
         solana_keypair::Keypair,
         solana_ledger::{
             genesis_utils::create_genesis_config_with_leader,
-            shred::{Nonce, ProcessShredsStats, ReedSolomonCache, Shredder},
+            shred::{Nonce, ProcessShredsStats, ReedSolomonCache, Shred, Shredder},
         },
         solana_perf::packet::{Packet, PacketFlags, PinnedPacketBatch},
         solana_runtime::bank::Bank,
@@ -779,4 +796,120 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_run_shred_sigverify_shred_tap() {
+        let mut rng = rand::thread_rng();
+        let leader_keypair = Arc::new(Keypair::new());
+        let bank = Bank::new_for_tests(
+            &create_genesis_config_with_leader(100, &leader_keypair.pubkey(), 10).genesis_config,
+        );
+        let leader_schedule_cache = LeaderScheduleCache::new_from_bank(&bank);
+        let bank_forks = BankForks::new_rw_arc(bank);
+        let keypair = Arc::new(Keypair::new());
+        let cluster_info = ClusterInfo::new(
+            ContactInfo::new_localhost(&keypair.pubkey(), timestamp()),
+            keypair.clone(),
+            SocketAddrSpace::Unspecified,
+        );
+        let shredder = Shredder::new(1, 0, 0, 0).unwrap();
+        let make_shreds = |keypair: &Keypair| {
+            let (data_shreds, _) = shredder.entries_to_merkle_shreds_for_tests(
+                keypair,
+                &create_ticks(10, 1, Hash::new_unique()),
+                false,
+                Hash::new_unique(),
+                0,
+                0,
+                &ReedSolomonCache::default(),
+                &mut ProcessShredsStats::default(),
+            );
+            data_shreds
+        };
+        let shreds = make_shreds(&leader_keypair);
+        let wrong_shreds = make_shreds(&Keypair::new());
+        let mut to_packet_batch = |shreds: &[Shred], repaired: bool| {
+            let packets = shreds
+                .iter()
+                .map(|shred| {
+                    let mut packet = shred.payload().to_packet(repaired.then(|| rng.gen()));
+                    if repaired {
+                        packet.meta_mut().flags |= PacketFlags::REPAIR;
+                    }
+                    packet
+                })
+                .collect();
+            PacketBatch::from(PinnedPacketBatch::new(packets))
+        };
+
+        let thread_pool = ThreadPoolBuilder::new().num_threads(2).build().unwrap();
+        let recycler_cache = RecyclerCache::warmed();
+        let deduper =
+            Deduper::<2, [u8]>::new(&mut rand::thread_rng(), /*num_bits:*/ 63_999_979);
+        let (fetch_sender, fetch_receiver) = crossbeam_channel::unbounded();
+        let (retransmit_sender, _retransmit_receiver) = EvictingSender::new_bounded(16);
+        let (verified_sender, verified_receiver) = crossbeam_channel::unbounded();
+        let (shred_tap_sender, shred_tap_receiver) = EvictingSender::new_bounded(1);
+        let cluster_nodes_cache = ClusterNodesCache::<RetransmitStage>::new(
+            CLUSTER_NODES_CACHE_NUM_EPOCH_CAP,
+            CLUSTER_NODES_CACHE_TTL,
+        );
+        let cache = RwLock::new(LruCache::new(/*capacity:*/ 128));
+        let mut stats = ShredSigVerifyStats::new(Instant::now());
+        let mut shred_buffer = Vec::new();
+        let mut run = |packets: Vec<PacketBatch>, stats: &mut ShredSigVerifyStats| {
+            for packets in packets {
+                fetch_sender.send(packets).unwrap();
+            }
+            assert!(run_shred_sigverify(
+                &thread_pool,
+                &keypair,
+                &cluster_info,
+                &bank_forks,
+                &leader_schedule_cache,
+                &recycler_cache,
+                &deduper,
+                &fetch_receiver,
+                &retransmit_sender,
+                &verified_sender,
+                Some(&shred_tap_sender),
+                &cluster_nodes_cache,
+                &cache,
+                stats,
+                &mut shred_buffer,
+            )
+            .is_ok());
+        };
+
+        // Shreds which fail signature verification are not mirrored, while
+        // the repaired ones are.
+        run(
+            vec![
+                to_packet_batch(&shreds[..4], false),
+                to_packet_batch(&wrong_shreds[4..6], false),
+                to_packet_batch(&shreds[4..6], true),
+            ],
+            &mut stats,
+        );
+        let verified: Vec<_> = verified_receiver
+            .try_recv()
+            .unwrap()
+            .into_iter()
+            .map(|(shred, _)| shred)
+            .collect();
+        assert_eq!(verified.len(), 6);
+        assert_eq!(shred_tap_receiver.try_recv().unwrap(), verified);
+        assert_eq!(stats.num_shred_tap_overflow_shreds, 0);
+
+        // When the subscribers fall behind, the oldest shreds are evicted.
+        run(vec![to_packet_batch(&shreds[6..9], false)], &mut stats);
+        run(vec![to_packet_batch(&shreds[9..11], false)], &mut stats);
+        assert_eq!(stats.num_shred_tap_overflow_shreds, 3);
+        let expected: Vec<_> = shreds[9..11]
+            .iter()
+            .map(|shred| shred.payload().clone())
+            .collect();
+        assert_eq!(shred_tap_receiver.try_recv().unwrap(), expected);
+        assert!(shred_tap_receiver.is_empty());
+    }
 }
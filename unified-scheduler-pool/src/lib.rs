@@ -4582,6 +4582,7 @@ mod tests {
             transaction_status_sender: Some(TransactionStatusSender {
                 sender,
                 dependency_tracker: None,
+                enable_log_event_recording: false,
             }),
             replay_vote_sender: None,
             prioritization_fee_cache,
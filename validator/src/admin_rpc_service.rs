@@ -16,6 +16,7 @@ use {
         },
         consensus::{tower_storage::TowerStorage, Tower},
         repair::repair_service,
+        tpu_ingress_filter_manager::TpuIngressFilterManagerError,
         validator::{
             BlockProductionMethod, SchedulerPacing, TransactionStructure, ValidatorStartProgress,
         },
@@ -27,6 +28,7 @@ use {
     solana_rpc::rpc::verify_pubkey,
     solana_rpc_client_api::{config::RpcAccountIndex, custom_error::RpcCustomError},
     solana_signer::Signer,
+    solana_streamer::nonblocking::connection_qos::ConnectionQosSnapshot,
     solana_validator_exit::Exit,
     std::{
         collections::{HashMap, HashSet},
@@ -98,6 +100,49 @@ pub struct AdminRpcRepairWhitelist {
     pub whitelist: Vec<Pubkey>,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcConnectionQos {
+    pub server: String,
+    pub remote_address: SocketAddr,
+    pub remote_pubkey: Option<String>,
+    pub stake: u64,
+    pub connected_secs: u64,
+    pub bytes: u64,
+    pub streams: u64,
+    pub timed_out_streams: u64,
+    pub chunks: u64,
+    pub mean_chunk_latency_us: u64,
+    pub max_chunk_latency_us: u64,
+    pub malformed_packets_dropped: u64,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcTpuConnectionQos {
+    pub connections: Vec<AdminRpcConnectionQos>,
+    pub evicted_connections: Vec<AdminRpcConnectionQos>,
+}
+
+impl From<ConnectionQosSnapshot> for AdminRpcConnectionQos {
+    fn from(snapshot: ConnectionQosSnapshot) -> Self {
+        Self {
+            server: snapshot.server.to_string(),
+            remote_address: snapshot.remote_address,
+            remote_pubkey: snapshot.remote_pubkey.map(|pubkey| pubkey.to_string()),
+            stake: snapshot.stake,
+            connected_secs: snapshot.connected_for.as_secs(),
+            bytes: snapshot.bytes,
+            streams: snapshot.streams,
+            timed_out_streams: snapshot.timed_out_streams,
+            chunks: snapshot.chunks,
+            mean_chunk_latency_us: snapshot.mean_chunk_latency.as_micros() as u64,
+            max_chunk_latency_us: snapshot.max_chunk_latency.as_micros() as u64,
+            malformed_packets_dropped: snapshot.malformed_packets_dropped,
+        }
+    }
+}
+
 impl From<ContactInfo> for AdminRpcContactInfo {
     fn from(node: ContactInfo) -> Self {
         macro_rules! unwrap_socket {
@@ -153,6 +198,61 @@ impl Display for AdminRpcRepairWhitelist {
 impl solana_cli_output::VerboseDisplay for AdminRpcRepairWhitelist {}
 impl solana_cli_output::QuietDisplay for AdminRpcRepairWhitelist {}
 
+impl Display for AdminRpcTpuConnectionQos {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fn write_connections(
+            f: &mut fmt::Formatter,
+            connections: &[AdminRpcConnectionQos],
+        ) -> fmt::Result {
+            writeln!(
+                f,
+                "{:<28} {:<22} {:<45} {:>20} {:>8} {:>12} {:>8} {:>8} {:>12} {:>12} {:>9}",
+                "Server",
+                "Remote Address",
+                "Remote Pubkey",
+                "Stake",
+                "Age (s)",
+                "Bytes",
+                "Streams",
+                "Timeouts",
+                "Mean Lat (us)",
+                "Max Lat (us)",
+                "Malformed",
+            )?;
+            for connection in connections {
+                writeln!(
+                    f,
+                    "{:<28} {:<22} {:<45} {:>20} {:>8} {:>12} {:>8} {:>8} {:>12} {:>12} {:>9}",
+                    connection.server,
+                    connection.remote_address,
+                    connection.remote_pubkey.as_deref().unwrap_or("-"),
+                    connection.stake,
+                    connection.connected_secs,
+                    connection.bytes,
+                    connection.streams,
+                    connection.timed_out_streams,
+                    connection.mean_chunk_latency_us,
+                    connection.max_chunk_latency_us,
+                    connection.malformed_packets_dropped,
+                )?;
+            }
+            Ok(())
+        }
+
+        writeln!(f, "Open connections: {}", self.connections.len())?;
+        write_connections(f, &self.connections)?;
+        writeln!(f)?;
+        writeln!(
+            f,
+            "Recently evicted slow peers: {}",
+            self.evicted_connections.len()
+        )?;
+        write_connections(f, &self.evicted_connections)
+    }
+}
+impl solana_cli_output::VerboseDisplay for AdminRpcTpuConnectionQos {}
+impl solana_cli_output::QuietDisplay for AdminRpcTpuConnectionQos {}
+
 #[rpc]
 pub trait AdminRpc {
     type Metadata;
@@ -227,6 +327,26 @@ pub trait AdminRpc {
     #[rpc(meta, name = "contactInfo")]
     fn contact_info(&self, meta: Self::Metadata) -> Result<AdminRpcContactInfo>;
 
+    #[rpc(meta, name = "tpuConnectionQos")]
+    fn tpu_connection_qos(&self, meta: Self::Metadata) -> Result<AdminRpcTpuConnectionQos>;
+
+    #[rpc(meta, name = "listTpuIngressFilters")]
+    fn list_tpu_ingress_filters(&self, meta: Self::Metadata) -> Result<Vec<String>>;
+
+    #[rpc(meta, name = "loadTpuIngressFilter")]
+    fn load_tpu_ingress_filter(&self, meta: Self::Metadata, config_file: String) -> Result<String>;
+
+    #[rpc(meta, name = "unloadTpuIngressFilter")]
+    fn unload_tpu_ingress_filter(&self, meta: Self::Metadata, name: String) -> Result<()>;
+
+    #[rpc(meta, name = "reloadTpuIngressFilter")]
+    fn reload_tpu_ingress_filter(
+        &self,
+        meta: Self::Metadata,
+        name: String,
+        config_file: String,
+    ) -> Result<()>;
+
     #[rpc(meta, name = "selectActiveInterface")]
     fn select_active_interface(&self, meta: Self::Metadata, interface: IpAddr) -> Result<()>;
 
@@ -563,6 +683,79 @@ impl AdminRpc for AdminRpcImpl {
         meta.with_post_init(|post_init| Ok(post_init.cluster_info.my_contact_info().into()))
     }
 
+    fn tpu_connection_qos(&self, meta: Self::Metadata) -> Result<AdminRpcTpuConnectionQos> {
+        debug!("tpu_connection_qos request received");
+
+        meta.with_post_init(|post_init| {
+            let mut connection_qos = AdminRpcTpuConnectionQos::default();
+            for registry in &post_init.tpu_connection_qos {
+                connection_qos
+                    .connections
+                    .extend(registry.connections().into_iter().map(Into::into));
+                connection_qos
+                    .evicted_connections
+                    .extend(registry.evicted_connections().into_iter().map(Into::into));
+            }
+            Ok(connection_qos)
+        })
+    }
+
+    fn list_tpu_ingress_filters(&self, meta: Self::Metadata) -> Result<Vec<String>> {
+        debug!("list_tpu_ingress_filters request received");
+
+        meta.with_post_init(|post_init| {
+            Ok(post_init
+                .tpu_ingress_filter_manager
+                .read()
+                .unwrap()
+                .list_filters())
+        })
+    }
+
+    fn load_tpu_ingress_filter(&self, meta: Self::Metadata, config_file: String) -> Result<String> {
+        debug!("load_tpu_ingress_filter request received: {config_file}");
+
+        meta.with_post_init(|post_init| {
+            post_init
+                .tpu_ingress_filter_manager
+                .write()
+                .unwrap()
+                .load_filter(&config_file)
+                .map_err(tpu_ingress_filter_error)
+        })
+    }
+
+    fn unload_tpu_ingress_filter(&self, meta: Self::Metadata, name: String) -> Result<()> {
+        debug!("unload_tpu_ingress_filter request received: {name}");
+
+        meta.with_post_init(|post_init| {
+            post_init
+                .tpu_ingress_filter_manager
+                .write()
+                .unwrap()
+                .unload_filter(&name)
+                .map_err(tpu_ingress_filter_error)
+        })
+    }
+
+    fn reload_tpu_ingress_filter(
+        &self,
+        meta: Self::Metadata,
+        name: String,
+        config_file: String,
+    ) -> Result<()> {
+        debug!("reload_tpu_ingress_filter request received: {name} {config_file}");
+
+        meta.with_post_init(|post_init| {
+            post_init
+                .tpu_ingress_filter_manager
+                .write()
+                .unwrap()
+                .reload_filter(&name, &config_file)
+                .map_err(tpu_ingress_filter_error)
+        })
+    }
+
     fn select_active_interface(&self, meta: Self::Metadata, interface: IpAddr) -> Result<()> {
         debug!("select_active_interface received: {interface}");
         meta.with_post_init(|post_init| {
@@ -668,9 +861,10 @@ impl AdminRpc for AdminRpcImpl {
                 .indexes
                 .iter()
                 .filter_map(|index| {
+                    let rpc_account_index = rpc_account_index_from_account_index(index)?;
                     accounts_index
                         .get_index_key_size(index, &index_key)
-                        .map(|size| (rpc_account_index_from_account_index(index), size))
+                        .map(|size| (rpc_account_index, size))
                 })
                 .collect::<HashMap<_, _>>();
 
@@ -851,11 +1045,21 @@ impl AdminRpcImpl {
     }
 }
 
-fn rpc_account_index_from_account_index(account_index: &AccountIndex) -> RpcAccountIndex {
+fn rpc_account_index_from_account_index(account_index: &AccountIndex) -> Option<RpcAccountIndex> {
     match account_index {
-        AccountIndex::ProgramId => RpcAccountIndex::ProgramId,
-        AccountIndex::SplTokenOwner => RpcAccountIndex::SplTokenOwner,
-        AccountIndex::SplTokenMint => RpcAccountIndex::SplTokenMint,
+        AccountIndex::ProgramId => Some(RpcAccountIndex::ProgramId),
+        AccountIndex::SplTokenOwner => Some(RpcAccountIndex::SplTokenOwner),
+        AccountIndex::SplTokenMint => Some(RpcAccountIndex::SplTokenMint),
+        // data slice indexes are not keyed by pubkey, so their sizes are not reported
+        AccountIndex::DataSlice(_) => None,
+    }
+}
+
+fn tpu_ingress_filter_error(err: TpuIngressFilterManagerError) -> jsonrpc_core::Error {
+    jsonrpc_core::Error {
+        code: ErrorCode::InvalidRequest,
+        message: err.to_string(),
+        data: None,
     }
 }
 
@@ -994,6 +1198,7 @@ mod tests {
         solana_core::{
             admin_rpc_post_init::{KeyUpdaterType, KeyUpdaters},
             consensus::tower_storage::NullTowerStorage,
+            tpu_ingress_filter_manager::TpuIngressFilterManager,
             validator::{Validator, ValidatorConfig, ValidatorTpuConfig},
         },
         solana_gossip::{cluster_info::ClusterInfo, node::Node},
@@ -1083,6 +1288,10 @@ mod tests {
                     ),
                     node: None,
                     banking_stage: Arc::new(RwLock::new(None)),
+                    tpu_connection_qos: vec![],
+                    tpu_ingress_filter_manager: Arc::new(RwLock::new(
+                        TpuIngressFilterManager::default(),
+                    )),
                 }))),
                 staked_nodes_overrides: Arc::new(RwLock::new(HashMap::new())),
                 rpc_to_plugin_manager_sender: None,
@@ -3,7 +3,7 @@ use {
         admin_rpc_service, cli, dashboard::Dashboard, ledger_lockfile, lock_ledger,
         println_name_value,
     },
-    clap::{crate_name, value_t, value_t_or_exit, values_t_or_exit},
+    clap::{crate_name, value_t, value_t_or_exit, values_t_or_exit, ArgMatches},
     crossbeam_channel::unbounded,
     itertools::Itertools,
     log::*,
@@ -56,6 +56,11 @@ fn main() {
     let version = solana_version::version!();
     let matches = cli::test_app(version, &default_args).get_matches();
 
+    // Exporting only reads from the cluster, handle it before the ledger is locked or reset
+    if let Some(bundle_path) = matches.value_of("export_clone_bundle") {
+        export_clone_bundle(&matches, Path::new(bundle_path));
+    }
+
     let output = if matches.is_present("quiet") {
         Output::None
     } else if matches.is_present("log") {
@@ -284,24 +289,25 @@ fn main() {
         .unwrap_or_default()
         .collect();
 
-    let accounts_to_clone: HashSet<_> = pubkeys_of(&matches, "clone_account")
-        .map(|v| v.into_iter().collect())
-        .unwrap_or_default();
-
-    let accounts_to_maybe_clone: HashSet<_> = pubkeys_of(&matches, "maybe_clone_account")
-        .map(|v| v.into_iter().collect())
-        .unwrap_or_default();
-
-    let upgradeable_programs_to_clone: HashSet<_> =
-        pubkeys_of(&matches, "clone_upgradeable_program")
-            .map(|v| v.into_iter().collect())
-            .unwrap_or_default();
-
-    let alt_accounts_to_clone: HashSet<_> = pubkeys_of(&matches, "deep_clone_address_lookup_table")
-        .map(|v| v.into_iter().collect())
-        .unwrap_or_default();
-
-    let clone_feature_set = matches.is_present("clone_feature_set");
+    let clone_args = CloneArgs::new(&matches);
+    let offline_clone_source: Option<Box<dyn AccountsSource>> =
+        if let Some(bundle_path) = matches.value_of("clone_from_bundle") {
+            Some(Box::new(
+                AccountsBundle::load(Path::new(bundle_path)).unwrap_or_else(|err| {
+                    println!("Error: {err}");
+                    exit(1);
+                }),
+            ))
+        } else if let Some(snapshot_dir) = matches.value_of("clone_from_snapshot") {
+            Some(Box::new(
+                SnapshotAccounts::from_ledger(Path::new(snapshot_dir)).unwrap_or_else(|err| {
+                    println!("Error: {err}");
+                    exit(1);
+                }),
+            ))
+        } else {
+            None
+        };
 
     let warp_slot = if matches.is_present("warp_slot") {
         Some(match matches.value_of("warp_slot") {
@@ -502,66 +508,18 @@ fn main() {
         ..JsonRpcConfig::default_for_test()
     });
 
-    if !accounts_to_clone.is_empty() {
-        if let Err(e) = genesis.clone_accounts(
-            accounts_to_clone,
-            cluster_rpc_client
-                .as_ref()
-                .expect("--clone-account requires --json-rpc-url argument"),
-            false,
-        ) {
-            println!("Error: clone_accounts failed: {e}");
-            exit(1);
-        }
-    }
-
-    if !alt_accounts_to_clone.is_empty() {
-        if let Err(e) = genesis.deep_clone_address_lookup_table_accounts(
-            alt_accounts_to_clone,
-            cluster_rpc_client
-                .as_ref()
-                .expect("--deep-clone-address-lookup-table requires --json-rpc-url argument"),
-        ) {
-            println!("Error: alt_accounts_to_clone failed: {e}");
-            exit(1);
-        }
-    }
-
-    if !accounts_to_maybe_clone.is_empty() {
-        if let Err(e) = genesis.clone_accounts(
-            accounts_to_maybe_clone,
-            cluster_rpc_client
-                .as_ref()
-                .expect("--maybe-clone requires --json-rpc-url argument"),
-            true,
-        ) {
-            println!("Error: clone_accounts failed: {e}");
-            exit(1);
-        }
-    }
-
-    if !upgradeable_programs_to_clone.is_empty() {
-        if let Err(e) = genesis.clone_upgradeable_programs(
-            upgradeable_programs_to_clone,
-            cluster_rpc_client
-                .as_ref()
-                .expect("--clone-upgradeable-program requires --json-rpc-url argument"),
-        ) {
-            println!("Error: clone_upgradeable_programs failed: {e}");
-            exit(1);
-        }
-    }
-
-    if clone_feature_set {
-        if let Err(e) = genesis.clone_feature_set(
-            cluster_rpc_client
-                .as_ref()
-                .expect("--clone-feature-set requires --json-rpc-url argument"),
-        ) {
-            println!("Error: clone_feature_set failed: {e}");
-            exit(1);
-        }
-    }
+    // Never fetched from, the clone arguments require a clone source
+    let no_clone_source = AccountsBundle::default();
+    let clone_source: &dyn AccountsSource = if let Some(clone_source) = &offline_clone_source {
+        clone_source.as_ref()
+    } else if let Ok(cluster_rpc_client) = &cluster_rpc_client {
+        cluster_rpc_client
+    } else {
+        &no_clone_source
+    };
+    clone_args.clone_into(&mut genesis, clone_source);
+    // Release the snapshot accounts before starting the validator
+    drop(offline_clone_source);
 
     if let Some(warp_slot) = warp_slot {
         genesis.warp_slot(warp_slot);
@@ -629,6 +587,97 @@ fn main() {
     }
 }
 
+/// The accounts, upgradeable programs and feature set requested by the clone arguments
+struct CloneArgs {
+    accounts: HashSet<Pubkey>,
+    maybe_accounts: HashSet<Pubkey>,
+    upgradeable_programs: HashSet<Pubkey>,
+    alt_accounts: HashSet<Pubkey>,
+    feature_set: bool,
+}
+
+impl CloneArgs {
+    fn new(matches: &ArgMatches) -> Self {
+        let pubkey_set = |name| {
+            pubkeys_of(matches, name)
+                .map(|v| v.into_iter().collect())
+                .unwrap_or_default()
+        };
+        Self {
+            accounts: pubkey_set("clone_account"),
+            maybe_accounts: pubkey_set("maybe_clone_account"),
+            upgradeable_programs: pubkey_set("clone_upgradeable_program"),
+            alt_accounts: pubkey_set("deep_clone_address_lookup_table"),
+            feature_set: matches.is_present("clone_feature_set"),
+        }
+    }
+
+    fn clone_into(self, genesis: &mut TestValidatorGenesis, clone_source: &dyn AccountsSource) {
+        if !self.accounts.is_empty() {
+            if let Err(e) = genesis.clone_accounts(self.accounts, clone_source, false) {
+                println!("Error: clone_accounts failed: {e}");
+                exit(1);
+            }
+        }
+
+        if !self.alt_accounts.is_empty() {
+            if let Err(e) =
+                genesis.deep_clone_address_lookup_table_accounts(self.alt_accounts, clone_source)
+            {
+                println!("Error: alt_accounts_to_clone failed: {e}");
+                exit(1);
+            }
+        }
+
+        if !self.maybe_accounts.is_empty() {
+            if let Err(e) = genesis.clone_accounts(self.maybe_accounts, clone_source, true) {
+                println!("Error: clone_accounts failed: {e}");
+                exit(1);
+            }
+        }
+
+        if !self.upgradeable_programs.is_empty() {
+            if let Err(e) =
+                genesis.clone_upgradeable_programs(self.upgradeable_programs, clone_source)
+            {
+                println!("Error: clone_upgradeable_programs failed: {e}");
+                exit(1);
+            }
+        }
+
+        if self.feature_set {
+            if let Err(e) = genesis.clone_feature_set(clone_source) {
+                println!("Error: clone_feature_set failed: {e}");
+                exit(1);
+            }
+        }
+    }
+}
+
+/// Write the accounts and feature set requested by the clone arguments from the cluster referenced
+/// by the --url argument to `bundle_path`, and exit without starting the validator
+fn export_clone_bundle(matches: &ArgMatches, bundle_path: &Path) -> ! {
+    let cluster_rpc_client = RpcClient::new(normalize_to_url_if_moniker(value_t_or_exit!(
+        matches,
+        "json_rpc_url",
+        String
+    )));
+    let clone_recorder = AccountsBundle::record(&cluster_rpc_client);
+    CloneArgs::new(matches).clone_into(&mut TestValidatorGenesis::default(), &clone_recorder);
+
+    let bundle = clone_recorder.into_bundle();
+    if let Err(err) = bundle.save(bundle_path) {
+        println!("Error: {err}");
+        exit(1);
+    }
+    println!(
+        "Wrote {} accounts to {}",
+        bundle.len(),
+        bundle_path.display()
+    );
+    exit(0);
+}
+
 fn remove_directory_contents(ledger_path: &Path) -> Result<(), io::Error> {
     for entry in fs::read_dir(ledger_path)? {
         let entry = entry?;
@@ -1,6 +1,8 @@
 use {
     crate::commands,
-    clap::{crate_description, crate_name, App, AppSettings, Arg, ArgMatches, SubCommand},
+    clap::{
+        crate_description, crate_name, App, AppSettings, Arg, ArgGroup, ArgMatches, SubCommand,
+    },
     solana_accounts_db::{
         accounts_db::{
             DEFAULT_ACCOUNTS_SHRINK_OPTIMIZE_TOTAL_SPACE, DEFAULT_ACCOUNTS_SHRINK_RATIO,
@@ -74,6 +76,8 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
         .subcommand(commands::set_identity::command())
         .subcommand(commands::set_log_filter::command())
         .subcommand(commands::staked_nodes_overrides::command())
+        .subcommand(commands::tpu_connection_qos::command())
+        .subcommand(commands::tpu_ingress_filter::command())
         .subcommand(commands::wait_for_restart_window::command())
         .subcommand(commands::set_public_address::command())
         .subcommand(commands::manage_block_production::command(default_args));
@@ -771,7 +775,7 @@ pub fn test_app<'a>(version: &'a str, default_args: &'a DefaultTestArgs) -> App<
                 .takes_value(true)
                 .validator(is_pubkey_or_keypair)
                 .multiple(true)
-                .requires("json_rpc_url")
+                .requires("clone_source")
                 .help(
                     "Copy an account from the cluster referenced by the --url argument the \
                      genesis configuration. If the ledger already exists then this parameter is \
@@ -784,7 +788,7 @@ pub fn test_app<'a>(version: &'a str, default_args: &'a DefaultTestArgs) -> App<
                 .takes_value(true)
                 .validator(is_pubkey_or_keypair)
                 .multiple(true)
-                .requires("json_rpc_url")
+                .requires("clone_source")
                 .help(
                     "Copy an address lookup table and all accounts it references from the cluster \
                      referenced by the --url argument in the genesis configuration. If the ledger \
@@ -798,7 +802,7 @@ pub fn test_app<'a>(version: &'a str, default_args: &'a DefaultTestArgs) -> App<
                 .takes_value(true)
                 .validator(is_pubkey_or_keypair)
                 .multiple(true)
-                .requires("json_rpc_url")
+                .requires("clone_source")
                 .help(
                     "Copy an account from the cluster referenced by the --url argument, skipping \
                      it if it doesn't exist. If the ledger already exists then this parameter is \
@@ -812,7 +816,7 @@ pub fn test_app<'a>(version: &'a str, default_args: &'a DefaultTestArgs) -> App<
                 .takes_value(true)
                 .validator(is_pubkey_or_keypair)
                 .multiple(true)
-                .requires("json_rpc_url")
+                .requires("clone_source")
                 .help(
                     "Copy an upgradeable program and its executable data from the cluster \
                      referenced by the --url argument the genesis configuration. If the ledger \
@@ -927,13 +931,54 @@ pub fn test_app<'a>(version: &'a str, default_args: &'a DefaultTestArgs) -> App<
             Arg::with_name("clone_feature_set")
                 .long("clone-feature-set")
                 .takes_value(false)
-                .requires("json_rpc_url")
+                .requires("clone_source")
                 .help(
                     "Copy a feature set from the cluster referenced by the --url argument in the \
                      genesis configuration. If the ledger already exists then this parameter is \
                      silently ignored",
                 ),
         )
+        .arg(
+            Arg::with_name("clone_from_bundle")
+                .long("clone-from-bundle")
+                .value_name("FILE")
+                .takes_value(true)
+                .conflicts_with("clone_from_snapshot")
+                .help(
+                    "Copy the accounts and feature set requested by the clone arguments from a \
+                     bundle written by --export-clone-bundle instead of the cluster referenced by \
+                     the --url argument",
+                ),
+        )
+        .arg(
+            Arg::with_name("clone_from_snapshot")
+                .long("clone-from-snapshot")
+                .value_name("DIR")
+                .takes_value(true)
+                .help(
+                    "Copy the accounts and feature set requested by the clone arguments from the \
+                     highest snapshot archives in DIR instead of the cluster referenced by the \
+                     --url argument. DIR must also contain the genesis of the cluster",
+                ),
+        )
+        .group(
+            ArgGroup::with_name("clone_source")
+                .args(&["json_rpc_url", "clone_from_bundle", "clone_from_snapshot"])
+                .multiple(true),
+        )
+        .arg(
+            Arg::with_name("export_clone_bundle")
+                .long("export-clone-bundle")
+                .value_name("FILE")
+                .takes_value(true)
+                .requires("json_rpc_url")
+                .conflicts_with_all(&["clone_from_bundle", "clone_from_snapshot"])
+                .help(
+                    "Write the accounts and feature set requested by the clone arguments from the \
+                     cluster referenced by the --url argument to a bundle file, and exit. The \
+                     bundle can be cloned from later with --clone-from-bundle",
+                ),
+        )
 }
 
 pub struct DefaultTestArgs {
@@ -11,6 +11,8 @@ pub mod set_identity;
 pub mod set_log_filter;
 pub mod set_public_address;
 pub mod staked_nodes_overrides;
+pub mod tpu_connection_qos;
+pub mod tpu_ingress_filter;
 pub mod wait_for_restart_window;
 
 use thiserror::Error;
@@ -5,7 +5,9 @@ use {
         commands::{FromClapArgMatches, Result},
     },
     clap::{values_t, App, Arg, ArgMatches},
-    solana_accounts_db::utils::create_and_canonicalize_directory,
+    solana_accounts_db::{
+        accounts_file::AccountsFileProvider, utils::create_and_canonicalize_directory,
+    },
     solana_clap_utils::{
         hidden_unless_forced,
         input_parsers::keypair_of,
@@ -21,6 +23,7 @@ use {
         banking_trace::DirByteLimit,
         validator::{BlockProductionMethod, BlockVerificationMethod, TransactionStructure},
     },
+    solana_gossip::contact_info::Protocol,
     solana_keypair::Keypair,
     solana_ledger::{blockstore_options::BlockstoreOptions, use_snapshot_archives_at_startup},
     solana_pubkey::Pubkey,
@@ -31,6 +34,7 @@ use {
     },
     solana_signer::Signer,
     solana_streamer::socket::SocketAddrSpace,
+    solana_turbine::shred_tap::ShredTapConfig,
     solana_unified_scheduler_pool::DefaultSchedulerPool,
     std::{collections::HashSet, net::SocketAddr, path::PathBuf, str::FromStr},
 };
@@ -78,6 +82,7 @@ pub struct RunArgs {
     pub json_rpc_config: JsonRpcConfig,
     pub pub_sub_config: PubSubConfig,
     pub send_transaction_service_config: SendTransactionServiceConfig,
+    pub shred_tap_config: Option<ShredTapConfig>,
 }
 
 impl FromClapArgMatches for RunArgs {
@@ -132,6 +137,8 @@ impl FromClapArgMatches for RunArgs {
 
         let socket_addr_space = SocketAddrSpace::new(matches.is_present("allow_private_addr"));
 
+        let shred_tap_config = shred_tap_config(matches)?;
+
         Ok(RunArgs {
             identity_keypair,
             ledger_path,
@@ -146,6 +153,7 @@ impl FromClapArgMatches for RunArgs {
             send_transaction_service_config: SendTransactionServiceConfig::from_clap_arg_match(
                 matches,
             )?,
+            shred_tap_config,
         })
     }
 }
@@ -325,6 +333,18 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
             .takes_value(false)
             .help("Upload new confirmed blocks into a BigTable instance"),
     )
+    .arg(
+        Arg::with_name("rpc_local_ledger_storage_path")
+            .long("rpc-local-ledger-storage-path")
+            .value_name("DIR")
+            .requires("enable_rpc_transaction_history")
+            .takes_value(true)
+            .help(
+                "Keep the historical ledger data in this local directory instead of a BigTable \
+                 instance. Implies --enable-rpc-bigtable-ledger-storage, and combined with \
+                 --enable-bigtable-ledger-upload, new confirmed blocks are uploaded into it",
+            ),
+    )
     .arg(
         Arg::with_name("enable_extended_tx_metadata_storage")
             .long("enable-extended-tx-metadata-storage")
@@ -335,6 +355,15 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
                  transaction info stored",
             ),
     )
+    .arg(
+        Arg::with_name("enable_log_event_recording")
+            .long("enable-log-event-recording")
+            .takes_value(false)
+            .help(
+                "Record typed program log events alongside the logs in the transaction info \
+                 stored and sent to Geyser plugins",
+            ),
+    )
     .arg(
         Arg::with_name("rpc_max_multiple_accounts")
             .long("rpc-max-multiple-accounts")
@@ -896,6 +925,15 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
             .hidden(hidden_unless_forced())
             .help("Controls the rate of the clients connections per IpAddr per minute."),
     )
+    .arg(
+        Arg::with_name("tpu_evict_slow_peers")
+            .long("tpu-evict-slow-peers")
+            .takes_value(false)
+            .help(
+                "Evict the unstaked connections of the TPU and TPU-forward QUIC servers whose \
+                 streams time out or wait too long for their chunks.",
+            ),
+    )
     .arg(
         Arg::with_name("vote_use_quic")
             .long("vote-use-quic")
@@ -1210,6 +1248,19 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
             .takes_value(false)
             .help("Еnable Geyser interface even if no Geyser configs are specified."),
     )
+    .arg(
+        Arg::with_name("tpu_ingress_filter_config")
+            .long("tpu-ingress-filter-config")
+            .value_name("FILE")
+            .takes_value(true)
+            .multiple(true)
+            .help(
+                "Specify the configuration file for a TPU ingress filter, which may drop or \
+                 deprioritize the packets received by the TPU before their signatures are \
+                 verified. May be specified multiple times. Filters can only be loaded at runtime \
+                 if at least one is specified.",
+            ),
+    )
     .arg(
         Arg::with_name("snapshot_archive_format")
             .long("snapshot-archive-format")
@@ -1295,6 +1346,21 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
             .value_name("INDEX")
             .help("Enable an accounts index, indexed by the selected account field"),
     )
+    .arg(
+        Arg::with_name("account_index_data_slice")
+            .long("account-index-data-slice")
+            .takes_value(true)
+            .multiple(true)
+            .validator(|value| {
+                account_secondary_indexes::parse_data_slice_index(&value).map(|_| ())
+            })
+            .value_name("PROGRAM_ID:OFFSET:LENGTH")
+            .help(
+                "Enable an accounts index over LENGTH bytes of account data at OFFSET, for \
+                 accounts owned by PROGRAM_ID. getProgramAccounts uses this index for memcmp \
+                 filters within the indexed bytes.",
+            ),
+    )
     .arg(
         Arg::with_name("account_index_exclude_key")
             .long(EXCLUDE_KEY)
@@ -1387,6 +1453,15 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
             .help("The number of ancient storages the ancient slot combining should converge to.")
             .hidden(hidden_unless_forced()),
     )
+    .arg(
+        Arg::with_name("accounts_db_ancient_storage_format")
+            .long("accounts-db-ancient-storage-format")
+            .value_name("FORMAT")
+            .takes_value(true)
+            .possible_values(AccountsFileProvider::POSSIBLE_VALUES)
+            .help("The storage format used when combining ancient slots.")
+            .hidden(hidden_unless_forced()),
+    )
     .arg(
         Arg::with_name("accounts_db_cache_limit_mb")
             .long("accounts-db-cache-limit-mb")
@@ -1646,6 +1721,40 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
             .requires("retransmit_xdp_cpu_cores")
             .help("EXPERIMENTAL: Enable XDP zero copy. Requires hardware support"),
     )
+    .arg(
+        Arg::with_name("shred_tap_address")
+            .long("shred-tap-address")
+            .takes_value(true)
+            .number_of_values(1)
+            .multiple(true)
+            .value_name("HOST:PORT")
+            .validator(solana_net_utils::is_host_port)
+            .help(
+                "Mirror the shreds received by the node, once their signatures are verified, to \
+                 this address. May be specified multiple times.",
+            ),
+    )
+    .arg(
+        Arg::with_name("shred_tap_protocol")
+            .long("shred-tap-protocol")
+            .takes_value(true)
+            .value_name("PROTOCOL")
+            .possible_values(&["udp", "quic"])
+            .requires("shred_tap_address")
+            .help(
+                "Protocol used to mirror the shreds to the --shred-tap-address addresses \
+                 [default: udp]. Over QUIC, the subscribers can authenticate the node by its \
+                 identity certificate. UDP is unauthenticated and only allowed for loopback \
+                 addresses.",
+            ),
+    )
+    .arg(
+        Arg::with_name("shred_tap_data_shreds_only")
+            .long("shred-tap-data-shreds-only")
+            .takes_value(false)
+            .requires("shred_tap_address")
+            .help("Only mirror the data shreds to the --shred-tap-address addresses"),
+    )
     .arg(
         Arg::with_name("use_connection_cache")
             .long("use-connection-cache")
@@ -1683,6 +1792,39 @@ fn validators_set(
     }
 }
 
+fn shred_tap_config(matches: &ArgMatches<'_>) -> Result<Option<ShredTapConfig>> {
+    let Some(addrs) = matches.values_of("shred_tap_address") else {
+        return Ok(None);
+    };
+    let addrs = addrs
+        .map(solana_net_utils::parse_host_port)
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|err| {
+            crate::commands::Error::Dynamic(Box::<dyn std::error::Error>::from(format!(
+                "failed to parse shred tap address: {err}"
+            )))
+        })?;
+    let protocol = match matches.value_of("shred_tap_protocol") {
+        Some("quic") => Protocol::QUIC,
+        _ => Protocol::UDP,
+    };
+    if protocol == Protocol::UDP {
+        if let Some(addr) = addrs.iter().find(|addr| !addr.ip().is_loopback()) {
+            return Err(crate::commands::Error::Dynamic(
+                Box::<dyn std::error::Error>::from(format!(
+                    "shred tap address {addr} is not a loopback address, use --shred-tap-protocol \
+                     quic to mirror the shreds to remote subscribers"
+                )),
+            ));
+        }
+    }
+    Ok(Some(ShredTapConfig {
+        addrs,
+        protocol,
+        data_shreds_only: matches.is_present("shred_tap_data_shreds_only"),
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use {
@@ -1730,6 +1872,7 @@ mod tests {
                     ..PubSubConfig::default_for_tests()
                 },
                 send_transaction_service_config: SendTransactionServiceConfig::default(),
+                shred_tap_config: None,
             }
         }
     }
@@ -1748,6 +1891,7 @@ mod tests {
                 json_rpc_config: self.json_rpc_config.clone(),
                 pub_sub_config: self.pub_sub_config.clone(),
                 send_transaction_service_config: self.send_transaction_service_config.clone(),
+                shred_tap_config: self.shred_tap_config.clone(),
             }
         }
     }
@@ -2223,4 +2367,80 @@ mod tests {
             expected_args,
         );
     }
+
+    #[test]
+    fn verify_args_struct_by_command_run_with_shred_tap() {
+        // udp by default
+        {
+            let default_run_args = RunArgs::default();
+            let expected_args = RunArgs {
+                shred_tap_config: Some(ShredTapConfig {
+                    addrs: vec![SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 9000)],
+                    protocol: Protocol::UDP,
+                    data_shreds_only: false,
+                }),
+                ..default_run_args.clone()
+            };
+            verify_args_struct_by_command_run_with_identity_setup(
+                default_run_args,
+                vec!["--shred-tap-address", "127.0.0.1:9000"],
+                expected_args,
+            );
+        }
+
+        // quic + multiple addresses + data shreds only
+        {
+            let default_run_args = RunArgs::default();
+            let expected_args = RunArgs {
+                shred_tap_config: Some(ShredTapConfig {
+                    addrs: vec![
+                        SocketAddr::new(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)), 9000),
+                        SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 9001),
+                    ],
+                    protocol: Protocol::QUIC,
+                    data_shreds_only: true,
+                }),
+                ..default_run_args.clone()
+            };
+            verify_args_struct_by_command_run_with_identity_setup(
+                default_run_args,
+                vec![
+                    "--shred-tap-address",
+                    "10.0.0.1:9000",
+                    "--shred-tap-address",
+                    "127.0.0.1:9001",
+                    "--shred-tap-protocol",
+                    "quic",
+                    "--shred-tap-data-shreds-only",
+                ],
+                expected_args,
+            );
+        }
+
+        // protocol without address
+        {
+            verify_args_struct_by_command_run_is_error_with_identity_setup(
+                RunArgs::default(),
+                vec!["--shred-tap-protocol", "quic"],
+            );
+        }
+
+        // udp to a non-loopback address
+        {
+            let default_args = DefaultArgs::default();
+            let tmp_dir = tempfile::tempdir().unwrap();
+            let file = tmp_dir.path().join("id.json");
+            solana_keypair::write_keypair_file(&Keypair::new(), &file).unwrap();
+            let app = add_args(App::new("run_command"), &default_args)
+                .args(&thread_args(&default_args.thread_args));
+            let matches = app.get_matches_from(vec![
+                "run_command",
+                "--identity",
+                file.to_str().unwrap(),
+                "--shred-tap-address",
+                "10.0.0.1:9000",
+            ]);
+            assert!(RunArgs::from_clap_arg_match(&matches).is_err());
+        }
+    }
 }
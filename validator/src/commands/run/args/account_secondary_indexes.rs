@@ -3,11 +3,42 @@ use {
     clap::{values_t, ArgMatches},
     solana_accounts_db::accounts_index::{
         AccountIndex, AccountSecondaryIndexes, AccountSecondaryIndexesIncludeExclude,
+        DataSliceIndex,
     },
     solana_pubkey::Pubkey,
-    std::collections::HashSet,
+    std::{collections::HashSet, str::FromStr},
 };
 
+/// The largest number of account data bytes a data slice index may cover
+const MAX_DATA_SLICE_INDEX_LENGTH: usize = 128;
+
+/// Parses a data slice index given as `PROGRAM_ID:OFFSET:LENGTH`
+pub(crate) fn parse_data_slice_index(value: &str) -> std::result::Result<DataSliceIndex, String> {
+    let [program_id, offset, length] = value.split(':').collect::<Vec<_>>()[..] else {
+        return Err(format!(
+            "invalid data slice index {value}, expected PROGRAM_ID:OFFSET:LENGTH"
+        ));
+    };
+    let program_id = Pubkey::from_str(program_id)
+        .map_err(|err| format!("invalid program id {program_id}: {err}"))?;
+    let offset = offset
+        .parse::<usize>()
+        .map_err(|err| format!("invalid offset {offset}: {err}"))?;
+    let length = length
+        .parse::<usize>()
+        .map_err(|err| format!("invalid length {length}: {err}"))?;
+    if length == 0 || length > MAX_DATA_SLICE_INDEX_LENGTH {
+        return Err(format!(
+            "invalid length {length}, must be between 1 and {MAX_DATA_SLICE_INDEX_LENGTH}"
+        ));
+    }
+    Ok(DataSliceIndex {
+        program_id,
+        offset,
+        length,
+    })
+}
+
 impl FromClapArgMatches for AccountSecondaryIndexes {
     fn from_clap_arg_match(matches: &ArgMatches) -> Result<Self> {
         let account_indexes: HashSet<AccountIndex> = matches
@@ -19,6 +50,15 @@ impl FromClapArgMatches for AccountSecondaryIndexes {
                 "spl-token-owner" => AccountIndex::SplTokenOwner,
                 _ => unreachable!(),
             })
+            .chain(
+                matches
+                    .values_of("account_index_data_slice")
+                    .unwrap_or_default()
+                    .map(|value| {
+                        // clap validates the value with `parse_data_slice_index()`
+                        AccountIndex::DataSlice(parse_data_slice_index(value).unwrap())
+                    }),
+            )
             .collect();
 
         let account_indexes_include_keys: HashSet<Pubkey> =
@@ -126,6 +166,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn verify_args_struct_by_command_run_with_account_index_data_slice() {
+        let default_run_args = crate::commands::run::args::RunArgs::default();
+        let program_id = Pubkey::new_unique();
+        let expected_args = RunArgs {
+            json_rpc_config: JsonRpcConfig {
+                account_indexes: AccountSecondaryIndexes {
+                    keys: None,
+                    indexes: HashSet::from([
+                        AccountIndex::ProgramId,
+                        AccountIndex::DataSlice(DataSliceIndex {
+                            program_id,
+                            offset: 8,
+                            length: 32,
+                        }),
+                    ]),
+                },
+                ..default_run_args.json_rpc_config.clone()
+            },
+            ..default_run_args.clone()
+        };
+        verify_args_struct_by_command_run_with_identity_setup(
+            default_run_args,
+            vec![
+                "--account-index",
+                "program-id",
+                "--account-index-data-slice",
+                format!("{program_id}:8:32").as_str(),
+            ],
+            expected_args,
+        );
+    }
+
+    #[test]
+    fn test_parse_data_slice_index() {
+        let program_id = Pubkey::new_unique();
+        assert_eq!(
+            parse_data_slice_index(&format!("{program_id}:0:32")),
+            Ok(DataSliceIndex {
+                program_id,
+                offset: 0,
+                length: 32,
+            })
+        );
+        assert!(parse_data_slice_index(&format!("{program_id}:0")).is_err());
+        assert!(parse_data_slice_index(&format!("{program_id}:0:0")).is_err());
+        assert!(parse_data_slice_index(&format!("{program_id}:0:129")).is_err());
+        assert!(parse_data_slice_index(&format!("{program_id}:-1:32")).is_err());
+        assert!(parse_data_slice_index("not-a-pubkey:0:32").is_err());
+    }
+
     #[test]
     fn verify_args_struct_by_command_run_with_account_index_include_key() {
         // single key
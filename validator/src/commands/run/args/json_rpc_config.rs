@@ -9,6 +9,7 @@ impl FromClapArgMatches for JsonRpcConfig {
     fn from_clap_arg_match(matches: &ArgMatches) -> Result<Self> {
         let rpc_bigtable_config = if matches.is_present("enable_rpc_bigtable_ledger_storage")
             || matches.is_present("enable_bigtable_ledger_upload")
+            || matches.is_present("rpc_local_ledger_storage_path")
         {
             Some(RpcBigtableConfig::from_clap_arg_match(matches)?)
         } else {
@@ -19,6 +20,7 @@ impl FromClapArgMatches for JsonRpcConfig {
             enable_rpc_transaction_history: matches.is_present("enable_rpc_transaction_history"),
             enable_extended_tx_metadata_storage: matches
                 .is_present("enable_extended_tx_metadata_storage"),
+            enable_log_event_recording: matches.is_present("enable_log_event_recording"),
             faucet_addr: matches
                 .value_of("rpc_faucet_addr")
                 .map(|address| {
@@ -104,6 +106,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn verify_args_struct_by_command_run_with_enable_log_event_recording() {
+        {
+            let default_run_args = crate::commands::run::args::RunArgs::default();
+            let expected_args = RunArgs {
+                json_rpc_config: JsonRpcConfig {
+                    enable_log_event_recording: true,
+                    ..default_run_args.json_rpc_config.clone()
+                },
+                ..default_run_args.clone()
+            };
+            verify_args_struct_by_command_run_with_identity_setup(
+                default_run_args,
+                vec!["--enable-log-event-recording"],
+                expected_args,
+            );
+        }
+    }
+
     #[test]
     fn verify_args_struct_by_command_run_with_rpc_faucet_addr() {
         {
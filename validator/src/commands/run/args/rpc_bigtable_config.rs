@@ -2,7 +2,7 @@ use {
     crate::commands::{FromClapArgMatches, Result},
     clap::{value_t, ArgMatches},
     solana_rpc::rpc::RpcBigtableConfig,
-    std::time::Duration,
+    std::{path::PathBuf, time::Duration},
 };
 
 impl FromClapArgMatches for RpcBigtableConfig {
@@ -15,6 +15,9 @@ impl FromClapArgMatches for RpcBigtableConfig {
                 .ok()
                 .map(Duration::from_secs),
             max_message_size: value_t!(matches, "rpc_bigtable_max_message_size", usize)?,
+            local_ledger_storage_path: matches
+                .value_of("rpc_local_ledger_storage_path")
+                .map(PathBuf::from),
         })
     }
 }
@@ -190,4 +193,31 @@ mod tests {
             expected_args,
         );
     }
+
+    #[test]
+    fn verify_args_struct_by_command_run_with_rpc_local_ledger_storage_path() {
+        let default_run_args = crate::commands::run::args::RunArgs::default();
+        let expected_args = RunArgs {
+            json_rpc_config: JsonRpcConfig {
+                enable_rpc_transaction_history: true,
+                rpc_bigtable_config: Some(RpcBigtableConfig {
+                    enable_bigtable_ledger_upload: true,
+                    local_ledger_storage_path: Some(PathBuf::from("/tmp/long-term-ledger")),
+                    ..default_rpc_bigtable_config()
+                }),
+                ..default_run_args.json_rpc_config.clone()
+            },
+            ..default_run_args.clone()
+        };
+        verify_args_struct_by_command_run_with_identity_setup(
+            default_run_args,
+            vec![
+                "--enable-rpc-transaction-history", // required by rpc-local-ledger-storage-path
+                "--enable-bigtable-ledger-upload",
+                "--rpc-local-ledger-storage-path",
+                "/tmp/long-term-ledger",
+            ],
+            expected_args,
+        );
+    }
 }
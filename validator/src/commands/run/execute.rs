@@ -12,7 +12,7 @@ use {
     rand::{seq::SliceRandom, thread_rng},
     solana_accounts_db::{
         accounts_db::{AccountShrinkThreshold, AccountsDbConfig, MarkObsoleteAccounts},
-        accounts_file::StorageAccess,
+        accounts_file::{AccountsFileProvider, StorageAccess},
         accounts_index::{AccountSecondaryIndexes, AccountsIndexConfig, IndexLimitMb, ScanFilter},
         hardened_unpack::MAX_GENESIS_ARCHIVE_UNPACKED_SIZE,
         utils::{
@@ -61,7 +61,10 @@ use {
         },
     },
     solana_signer::Signer,
-    solana_streamer::quic::{QuicServerParams, DEFAULT_TPU_COALESCE},
+    solana_streamer::{
+        nonblocking::connection_qos::SlowPeerEvictionConfig,
+        quic::{QuicServerParams, DEFAULT_TPU_COALESCE},
+    },
     solana_tpu_client::tpu_client::DEFAULT_TPU_ENABLE_UDP,
     solana_turbine::{
         broadcast_stage::BroadcastStageType,
@@ -391,6 +394,13 @@ pub fn execute(
         })
         .unwrap_or_default();
 
+    let ancient_accounts_file_provider = value_t!(
+        matches,
+        "accounts_db_ancient_storage_format",
+        AccountsFileProvider
+    )
+    .ok();
+
     let scan_filter_for_shrinking = matches
         .value_of("accounts_db_scan_filter_for_shrinking")
         .map(|filter| match filter {
@@ -428,6 +438,7 @@ pub fn execute(
         )
         .ok(),
         max_ancient_storages: value_t!(matches, "accounts_db_max_ancient_storages", usize).ok(),
+        ancient_accounts_file_provider,
         exhaustively_verify_refcounts: matches.is_present("accounts_db_verify_refcounts"),
         storage_access,
         scan_filter_for_shrinking,
@@ -531,6 +542,12 @@ pub fn execute(
         rpc_config: run_args.json_rpc_config,
         on_start_geyser_plugin_config_files,
         geyser_plugin_always_enabled: matches.is_present("geyser_plugin_always_enabled"),
+        on_start_tpu_ingress_filter_config_files: values_t!(
+            matches,
+            "tpu_ingress_filter_config",
+            PathBuf
+        )
+        .ok(),
         rpc_addrs: value_t!(matches, "rpc_port", u16).ok().map(|rpc_port| {
             (
                 SocketAddr::new(rpc_bind_address, rpc_port),
@@ -593,6 +610,7 @@ pub fn execute(
         wen_restart_coordinator: value_t!(matches, "wen_restart_coordinator", Pubkey).ok(),
         turbine_disabled: Arc::<AtomicBool>::default(),
         retransmit_xdp,
+        shred_tap: run_args.shred_tap_config,
         broadcast_stage_type: BroadcastStageType::Standard,
         use_tpu_client_next: !matches.is_present("use_connection_cache"),
         block_verification_method: value_t_or_exit!(
@@ -816,6 +834,9 @@ pub fn execute(
 
     let tpu_max_connections_per_ipaddr_per_minute: u64 =
         value_t_or_exit!(matches, "tpu_max_connections_per_ipaddr_per_minute", u64);
+    let tpu_slow_peer_eviction = matches
+        .is_present("tpu_evict_slow_peers")
+        .then(SlowPeerEvictionConfig::default);
     let max_streams_per_ms = value_t_or_exit!(matches, "tpu_max_streams_per_ms", u64);
 
     let node_config = NodeConfig {
@@ -933,6 +954,7 @@ pub fn execute(
         max_connections_per_ipaddr_per_min: tpu_max_connections_per_ipaddr_per_minute,
         coalesce: tpu_coalesce,
         num_threads: tpu_transaction_receive_threads,
+        slow_peer_eviction: tpu_slow_peer_eviction.clone(),
         ..Default::default()
     };
 
@@ -944,6 +966,7 @@ pub fn execute(
         max_connections_per_ipaddr_per_min: tpu_max_connections_per_ipaddr_per_minute,
         coalesce: tpu_coalesce,
         num_threads: tpu_transaction_forward_receive_threads,
+        slow_peer_eviction: tpu_slow_peer_eviction,
         ..Default::default()
     };
 
@@ -953,6 +976,7 @@ pub fn execute(
     vote_quic_server_config.max_connections_per_peer = 1;
     vote_quic_server_config.max_unstaked_connections = 0;
     vote_quic_server_config.num_threads = tpu_vote_transaction_receive_threads;
+    vote_quic_server_config.slow_peer_eviction = None;
 
     let validator = match Validator::new(
         node,
@@ -1191,7 +1215,9 @@ fn new_snapshot_config(
         let archive_format_str = value_t_or_exit!(matches, "snapshot_archive_format", String);
         let mut archive_format = ArchiveFormat::from_cli_arg(&archive_format_str)
             .unwrap_or_else(|| panic!("Archive format not recognized: {archive_format_str}"));
-        if let ArchiveFormat::TarZstd { config } = &mut archive_format {
+        if let ArchiveFormat::TarZstd { config } | ArchiveFormat::TarZstdSeekable { config } =
+            &mut archive_format
+        {
             config.compression_level =
                 value_t_or_exit!(matches, "snapshot_zstd_compression_level", i32);
         }
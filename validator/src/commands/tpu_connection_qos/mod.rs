@@ -0,0 +1,94 @@
+use {
+    crate::{
+        admin_rpc_service,
+        commands::{FromClapArgMatches, Result},
+    },
+    clap::{App, Arg, ArgMatches, SubCommand},
+    solana_cli_output::OutputFormat,
+    std::path::Path,
+};
+
+const COMMAND: &str = "tpu-connection-qos";
+
+#[derive(Debug, PartialEq)]
+pub struct TpuConnectionQosArgs {
+    pub output: OutputFormat,
+}
+
+impl FromClapArgMatches for TpuConnectionQosArgs {
+    fn from_clap_arg_match(matches: &ArgMatches) -> Result<Self> {
+        Ok(TpuConnectionQosArgs {
+            output: OutputFormat::from_matches(matches, "output", false),
+        })
+    }
+}
+
+pub fn command<'a>() -> App<'a, 'a> {
+    SubCommand::with_name(COMMAND)
+        .about("Display the per-connection QoS accounting of the TPU QUIC servers")
+        .arg(
+            Arg::with_name("output")
+                .long("output")
+                .takes_value(true)
+                .value_name("MODE")
+                .possible_values(&["json", "json-compact"])
+                .help("Output display mode"),
+        )
+}
+
+pub fn execute(matches: &ArgMatches, ledger_path: &Path) -> Result<()> {
+    let tpu_connection_qos_args = TpuConnectionQosArgs::from_clap_arg_match(matches)?;
+
+    let admin_client = admin_rpc_service::connect(ledger_path);
+    let connection_qos = admin_rpc_service::runtime()
+        .block_on(async move { admin_client.await?.tpu_connection_qos().await })?;
+
+    println!(
+        "{}",
+        tpu_connection_qos_args
+            .output
+            .formatted_string(&connection_qos)
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::commands::tests::{
+            verify_args_struct_by_command, verify_args_struct_by_command_is_error,
+        },
+    };
+
+    #[test]
+    fn verify_args_struct_by_command_tpu_connection_qos_output_json() {
+        verify_args_struct_by_command(
+            command(),
+            vec![COMMAND, "--output", "json"],
+            TpuConnectionQosArgs {
+                output: OutputFormat::Json,
+            },
+        );
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_tpu_connection_qos_output_default() {
+        verify_args_struct_by_command(
+            command(),
+            vec![COMMAND],
+            TpuConnectionQosArgs {
+                output: OutputFormat::Display,
+            },
+        );
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_tpu_connection_qos_output_invalid() {
+        verify_args_struct_by_command_is_error::<TpuConnectionQosArgs>(
+            command(),
+            vec![COMMAND, "--output", "invalid_output_type"],
+        );
+    }
+}
@@ -0,0 +1,194 @@
+use {
+    crate::{
+        admin_rpc_service,
+        commands::{FromClapArgMatches, Result},
+    },
+    clap::{value_t, App, AppSettings, Arg, ArgMatches, SubCommand},
+    std::path::Path,
+};
+
+const COMMAND: &str = "tpu-ingress-filter";
+
+#[derive(Debug, PartialEq)]
+pub struct TpuIngressFilterUnloadArgs {
+    pub name: String,
+}
+
+impl FromClapArgMatches for TpuIngressFilterUnloadArgs {
+    fn from_clap_arg_match(matches: &ArgMatches) -> Result<Self> {
+        Ok(TpuIngressFilterUnloadArgs {
+            name: value_t!(matches, "name", String)?,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct TpuIngressFilterLoadArgs {
+    pub config: String,
+}
+
+impl FromClapArgMatches for TpuIngressFilterLoadArgs {
+    fn from_clap_arg_match(matches: &ArgMatches) -> Result<Self> {
+        Ok(TpuIngressFilterLoadArgs {
+            config: value_t!(matches, "config", String)?,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct TpuIngressFilterReloadArgs {
+    pub name: String,
+    pub config: String,
+}
+
+impl FromClapArgMatches for TpuIngressFilterReloadArgs {
+    fn from_clap_arg_match(matches: &ArgMatches) -> Result<Self> {
+        Ok(TpuIngressFilterReloadArgs {
+            name: value_t!(matches, "name", String)?,
+            config: value_t!(matches, "config", String)?,
+        })
+    }
+}
+
+pub fn command<'a>() -> App<'a, 'a> {
+    let name_arg = Arg::with_name("name").required(true).takes_value(true);
+    let config_arg = Arg::with_name("config").required(true).takes_value(true);
+
+    SubCommand::with_name(COMMAND)
+        .about("Manage and view TPU ingress filters")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .setting(AppSettings::InferSubcommands)
+        .subcommand(SubCommand::with_name("list").about("List all loaded TPU ingress filters"))
+        .subcommand(
+            SubCommand::with_name("unload")
+                .about("Unload a particular TPU ingress filter. You must specify the filter name")
+                .arg(&name_arg),
+        )
+        .subcommand(
+            SubCommand::with_name("reload")
+                .about(
+                    "Reload a particular TPU ingress filter. You must specify the filter name and \
+                     the new config path",
+                )
+                .arg(&name_arg)
+                .arg(&config_arg),
+        )
+        .subcommand(
+            SubCommand::with_name("load")
+                .about(
+                    "Load a new TPU ingress filter. You must specify the config path. Fails if \
+                     overwriting (use reload)",
+                )
+                .arg(&config_arg),
+        )
+}
+
+pub fn execute(matches: &ArgMatches, ledger_path: &Path) -> Result<()> {
+    match matches.subcommand() {
+        ("list", _) => {
+            let admin_client = admin_rpc_service::connect(ledger_path);
+            let filters = admin_rpc_service::runtime()
+                .block_on(async move { admin_client.await?.list_tpu_ingress_filters().await })?;
+            if !filters.is_empty() {
+                println!("Currently the following TPU ingress filters are loaded:");
+                for (filter, i) in filters.into_iter().zip(1..) {
+                    println!("  {i}) {filter}");
+                }
+            } else {
+                println!("There are currently no TPU ingress filters loaded");
+            }
+        }
+        ("unload", Some(subcommand_matches)) => {
+            let TpuIngressFilterUnloadArgs { name } =
+                TpuIngressFilterUnloadArgs::from_clap_arg_match(subcommand_matches)?;
+
+            let admin_client = admin_rpc_service::connect(ledger_path);
+            admin_rpc_service::runtime().block_on(async {
+                admin_client
+                    .await?
+                    .unload_tpu_ingress_filter(name.clone())
+                    .await
+            })?;
+            println!("Successfully unloaded TPU ingress filter: {name}");
+        }
+        ("load", Some(subcommand_matches)) => {
+            let TpuIngressFilterLoadArgs { config } =
+                TpuIngressFilterLoadArgs::from_clap_arg_match(subcommand_matches)?;
+
+            let admin_client = admin_rpc_service::connect(ledger_path);
+            let name = admin_rpc_service::runtime().block_on(async {
+                admin_client
+                    .await?
+                    .load_tpu_ingress_filter(config.clone())
+                    .await
+            })?;
+            println!("Successfully loaded TPU ingress filter: {name}");
+        }
+        ("reload", Some(subcommand_matches)) => {
+            let TpuIngressFilterReloadArgs { name, config } =
+                TpuIngressFilterReloadArgs::from_clap_arg_match(subcommand_matches)?;
+
+            let admin_client = admin_rpc_service::connect(ledger_path);
+            admin_rpc_service::runtime().block_on(async {
+                admin_client
+                    .await?
+                    .reload_tpu_ingress_filter(name.clone(), config.clone())
+                    .await
+            })?;
+            println!("Successfully reloaded TPU ingress filter: {name}");
+        }
+        _ => unreachable!(),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::commands::tests::verify_args_struct_by_command_is_error};
+
+    #[test]
+    fn verify_args_struct_by_command_tpu_ingress_filter_unload_default() {
+        verify_args_struct_by_command_is_error::<TpuIngressFilterUnloadArgs>(
+            command(),
+            vec![COMMAND, "unload"],
+        );
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_tpu_ingress_filter_load_with_config() {
+        let app = command();
+        let matches = app.get_matches_from(vec![COMMAND, "load", "testconfig"]);
+        let subcommand_matches = matches.subcommand_matches("load").unwrap();
+        let args = TpuIngressFilterLoadArgs::from_clap_arg_match(subcommand_matches).unwrap();
+        assert_eq!(
+            args,
+            TpuIngressFilterLoadArgs {
+                config: "testconfig".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_tpu_ingress_filter_reload_with_name() {
+        verify_args_struct_by_command_is_error::<TpuIngressFilterReloadArgs>(
+            command(),
+            vec![COMMAND, "reload", "testname"],
+        );
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_tpu_ingress_filter_reload_with_name_and_config() {
+        let app = command();
+        let matches = app.get_matches_from(vec![COMMAND, "reload", "testname", "testconfig"]);
+        let subcommand_matches = matches.subcommand_matches("reload").unwrap();
+        let args = TpuIngressFilterReloadArgs::from_clap_arg_match(subcommand_matches).unwrap();
+        assert_eq!(
+            args,
+            TpuIngressFilterReloadArgs {
+                name: "testname".to_string(),
+                config: "testconfig".to_string(),
+            }
+        );
+    }
+}
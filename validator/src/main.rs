@@ -47,6 +47,12 @@ pub fn main() {
         ("contact-info", Some(subcommand_matches)) => {
             commands::contact_info::execute(subcommand_matches, &ledger_path)
         }
+        ("tpu-connection-qos", Some(subcommand_matches)) => {
+            commands::tpu_connection_qos::execute(subcommand_matches, &ledger_path)
+        }
+        ("tpu-ingress-filter", Some(subcommand_matches)) => {
+            commands::tpu_ingress_filter::execute(subcommand_matches, &ledger_path)
+        }
         ("exit", Some(subcommand_matches)) => {
             commands::exit::execute(subcommand_matches, &ledger_path)
         }